@@ -0,0 +1,244 @@
+//! Binary parsing code, dense in indexing, shifts, arithmetic and panics.
+
+use std::{collections::HashMap, fmt};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Kind {
+    Handshake,
+    Data,
+    Ack,
+    Close,
+}
+
+#[derive(Debug)]
+pub enum Error {
+    Truncated(usize),
+    UnknownKind(u8),
+    Checksum { expected: u16, found: u16 },
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::Truncated(length) => write!(formatter, "truncated at {length}"),
+            Error::UnknownKind(kind) => write!(formatter, "unknown kind {kind}"),
+            Error::Checksum { expected, found } => {
+                write!(formatter, "checksum {found:#x} instead of {expected:#x}")
+            },
+        }
+    }
+}
+
+pub struct Header {
+    pub kind: Kind,
+    pub length: u16,
+    pub sequence: u32,
+    pub flags: u8,
+}
+
+pub struct Frame<'a> {
+    pub header: Header,
+    pub payload: &'a [u8],
+}
+
+pub fn read_u16(bytes: &[u8], offset: usize) -> u16 {
+    (bytes[offset] as u16) << 8 | bytes[offset + 1] as u16
+}
+
+pub fn read_u32(bytes: &[u8], offset: usize) -> u32 {
+    (bytes[offset] as u32) << 24
+        | (bytes[offset + 1] as u32) << 16
+        | (bytes[offset + 2] as u32) << 8
+        | bytes[offset + 3] as u32
+}
+
+pub fn write_u32(bytes: &mut [u8], offset: usize, value: u32) {
+    bytes[offset] = (value >> 24) as u8;
+    bytes[offset + 1] = (value >> 16) as u8;
+    bytes[offset + 2] = (value >> 8) as u8;
+    bytes[offset + 3] = value as u8;
+}
+
+pub fn kind(byte: u8) -> Result<Kind, Error> {
+    match byte {
+        0 => Ok(Kind::Handshake),
+        1 => Ok(Kind::Data),
+        2 => Ok(Kind::Ack),
+        3 => Ok(Kind::Close),
+        other => Err(Error::UnknownKind(other)),
+    }
+}
+
+pub fn checksum(bytes: &[u8]) -> u16 {
+    let mut sum = 0u32;
+    let mut index = 0;
+    while index + 1 < bytes.len() {
+        sum += read_u16(bytes, index) as u32;
+        index += 2;
+    }
+    if index < bytes.len() {
+        sum += (bytes[index] as u32) << 8;
+    }
+    while sum >> 16 != 0 {
+        sum = (sum & 0xffff) + (sum >> 16);
+    }
+    !(sum as u16)
+}
+
+pub fn parse_header(bytes: &[u8]) -> Result<Header, Error> {
+    if bytes.len() < 8 {
+        return Err(Error::Truncated(bytes.len()));
+    }
+    Ok(Header {
+        kind: kind(bytes[0])?,
+        flags: bytes[1],
+        length: read_u16(bytes, 2),
+        sequence: read_u32(bytes, 4),
+    })
+}
+
+pub fn parse_frame(bytes: &[u8]) -> Result<Frame<'_>, Error> {
+    let header = parse_header(bytes)?;
+    let end = 8 + header.length as usize;
+    if bytes.len() < end + 2 {
+        return Err(Error::Truncated(bytes.len()));
+    }
+    let payload = &bytes[8..end];
+    let expected = read_u16(bytes, end);
+    let found = checksum(payload);
+    if expected != found {
+        return Err(Error::Checksum { expected, found });
+    }
+    Ok(Frame { header, payload })
+}
+
+pub fn parse_frames(mut bytes: &[u8]) -> Vec<Frame<'_>> {
+    let mut frames = Vec::new();
+    while !bytes.is_empty() {
+        let frame = parse_frame(bytes).unwrap();
+        let consumed = 10 + frame.header.length as usize;
+        frames.push(frame);
+        bytes = &bytes[consumed..];
+    }
+    frames
+}
+
+pub fn encode(kind: Kind, sequence: u32, payload: &[u8]) -> Vec<u8> {
+    let mut bytes = vec![0u8; 10 + payload.len()];
+    bytes[0] = kind as u8;
+    bytes[2] = (payload.len() >> 8) as u8;
+    bytes[3] = payload.len() as u8;
+    write_u32(&mut bytes, 4, sequence);
+    bytes[8..8 + payload.len()].copy_from_slice(payload);
+    let sum = checksum(payload);
+    let end = 8 + payload.len();
+    bytes[end] = (sum >> 8) as u8;
+    bytes[end + 1] = sum as u8;
+    bytes
+}
+
+pub fn varint(bytes: &[u8]) -> (u64, usize) {
+    let mut value = 0u64;
+    let mut shift = 0;
+    let mut index = 0;
+    loop {
+        let byte = bytes[index];
+        value |= ((byte & 0x7f) as u64) << shift;
+        index += 1;
+        if byte & 0x80 == 0 {
+            return (value, index);
+        }
+        shift += 7;
+    }
+}
+
+pub fn encode_varint(mut value: u64, out: &mut Vec<u8>) {
+    while value >= 0x80 {
+        out.push((value as u8) | 0x80);
+        value >>= 7;
+    }
+    out.push(value as u8);
+}
+
+pub fn hex(bytes: &[u8]) -> String {
+    const DIGITS: &[u8; 16] = b"0123456789abcdef";
+    let mut out = String::with_capacity(bytes.len() * 2);
+    for byte in bytes {
+        out.push(DIGITS[(byte >> 4) as usize] as char);
+        out.push(DIGITS[(byte & 0xf) as usize] as char);
+    }
+    out
+}
+
+pub fn unhex(text: &str) -> Vec<u8> {
+    let digits = text.as_bytes();
+    let mut out = Vec::with_capacity(digits.len() / 2);
+    for pair in digits.chunks(2) {
+        let high = (pair[0] as char).to_digit(16).expect("hex digit");
+        let low = (pair[1] as char).to_digit(16).expect("hex digit");
+        out.push((high << 4 | low) as u8);
+    }
+    out
+}
+
+pub struct Reassembler {
+    pending: HashMap<u32, Vec<u8>>,
+    next: u32,
+}
+
+impl Reassembler {
+    pub fn new() -> Self {
+        Self { pending: HashMap::new(), next: 0 }
+    }
+
+    pub fn push(&mut self, frame: &Frame<'_>) -> Vec<u8> {
+        self.pending.insert(frame.header.sequence, frame.payload.to_vec());
+        let mut out = Vec::new();
+        while let Some(payload) = self.pending.remove(&self.next) {
+            out.extend_from_slice(&payload);
+            self.next += 1;
+        }
+        out
+    }
+
+    pub fn window(&self) -> u32 {
+        let highest = self.pending.keys().max().copied().unwrap_or(self.next);
+        highest - self.next
+    }
+}
+
+impl Default for Reassembler {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+pub fn histogram(bytes: &[u8]) -> [usize; 256] {
+    let mut counts = [0usize; 256];
+    for byte in bytes {
+        counts[*byte as usize] += 1;
+    }
+    counts
+}
+
+pub fn entropy(bytes: &[u8]) -> f64 {
+    let counts = histogram(bytes);
+    let total = bytes.len() as f64;
+    counts
+        .iter()
+        .filter(|count| **count > 0)
+        .map(|count| {
+            let probability = *count as f64 / total;
+            -probability * probability.log2()
+        })
+        .sum()
+}
+
+pub fn rotate(block: &mut [u32; 16], rounds: usize) {
+    for round in 0..rounds {
+        let index = round % 16;
+        block[index] = block[index].rotate_left(7) ^ block[(index + 1) % 16];
+        block[(index + 5) % 16] =
+            block[(index + 5) % 16].wrapping_add(block[index] >> 3);
+    }
+}
@@ -0,0 +1,227 @@
+//! Application code, dense in traits, generics, closures, iterators and
+//! process, time and network calls.
+
+use std::{
+    collections::{BTreeMap, HashMap},
+    fmt,
+    io::{self, Read, Write},
+    net::TcpStream,
+    process::Command,
+    sync::{Arc, Mutex},
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Account {
+    pub id: u64,
+    pub owner: String,
+    pub balance: i64,
+    pub created_at: u64,
+}
+
+#[derive(Debug)]
+pub enum ServiceError {
+    NotFound(u64),
+    Insufficient { id: u64, missing: i64 },
+    Io(io::Error),
+}
+
+impl fmt::Display for ServiceError {
+    fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ServiceError::NotFound(id) => write!(formatter, "account {id} not found"),
+            ServiceError::Insufficient { id, missing } => {
+                write!(formatter, "account {id} misses {missing}")
+            },
+            ServiceError::Io(error) => write!(formatter, "{error}"),
+        }
+    }
+}
+
+impl From<io::Error> for ServiceError {
+    fn from(error: io::Error) -> Self {
+        ServiceError::Io(error)
+    }
+}
+
+pub trait Store {
+    fn get(&self, id: u64) -> Option<Account>;
+    fn put(&mut self, account: Account);
+    fn all(&self) -> Vec<Account>;
+}
+
+#[derive(Default)]
+pub struct MemoryStore {
+    accounts: HashMap<u64, Account>,
+}
+
+impl Store for MemoryStore {
+    fn get(&self, id: u64) -> Option<Account> {
+        self.accounts.get(&id).cloned()
+    }
+
+    fn put(&mut self, account: Account) {
+        self.accounts.insert(account.id, account);
+    }
+
+    fn all(&self) -> Vec<Account> {
+        let mut accounts: Vec<Account> = self.accounts.values().cloned().collect();
+        accounts.sort_by_key(|account| account.id);
+        accounts
+    }
+}
+
+pub struct Service<S: Store> {
+    store: Arc<Mutex<S>>,
+    next_id: u64,
+}
+
+pub fn now() -> u64 {
+    SystemTime::now().duration_since(UNIX_EPOCH).unwrap().as_secs()
+}
+
+impl<S: Store> Service<S> {
+    pub fn new(store: S) -> Self {
+        Self { store: Arc::new(Mutex::new(store)), next_id: 1 }
+    }
+
+    pub fn open(&mut self, owner: &str) -> u64 {
+        let id = self.next_id;
+        self.next_id += 1;
+        let account =
+            Account { id, owner: owner.to_owned(), balance: 0, created_at: now() };
+        self.store.lock().unwrap().put(account);
+        id
+    }
+
+    pub fn deposit(&self, id: u64, amount: i64) -> Result<i64, ServiceError> {
+        let mut store = self.store.lock().unwrap();
+        let mut account = store.get(id).ok_or(ServiceError::NotFound(id))?;
+        account.balance += amount;
+        let balance = account.balance;
+        store.put(account);
+        Ok(balance)
+    }
+
+    pub fn withdraw(&self, id: u64, amount: i64) -> Result<i64, ServiceError> {
+        let mut store = self.store.lock().unwrap();
+        let mut account = store.get(id).ok_or(ServiceError::NotFound(id))?;
+        if account.balance < amount {
+            return Err(ServiceError::Insufficient {
+                id,
+                missing: amount - account.balance,
+            });
+        }
+        account.balance -= amount;
+        let balance = account.balance;
+        store.put(account);
+        Ok(balance)
+    }
+
+    pub fn transfer(&self, from: u64, to: u64, amount: i64) -> Result<(), ServiceError> {
+        self.withdraw(from, amount)?;
+        self.deposit(to, amount).map(|_| ())
+    }
+
+    pub fn report(&self) -> BTreeMap<String, i64> {
+        self.store
+            .lock()
+            .unwrap()
+            .all()
+            .into_iter()
+            .fold(BTreeMap::new(), |mut totals, account| {
+                *totals.entry(account.owner).or_insert(0) += account.balance;
+                totals
+            })
+    }
+
+    pub fn richest(&self, count: usize) -> Vec<(String, i64)> {
+        let mut totals: Vec<(String, i64)> = self.report().into_iter().collect();
+        totals.sort_by(|left, right| right.1.cmp(&left.1));
+        totals.into_iter().take(count).collect()
+    }
+
+    pub fn older_than(&self, age: Duration) -> Vec<u64> {
+        let limit = now() - age.as_secs();
+        self.store
+            .lock()
+            .unwrap()
+            .all()
+            .iter()
+            .filter(|account| account.created_at < limit)
+            .map(|account| account.id)
+            .collect()
+    }
+}
+
+pub fn export(accounts: &[Account], out: &mut impl Write) -> io::Result<()> {
+    for account in accounts {
+        writeln!(
+            out,
+            "{},{},{},{}",
+            account.id, account.owner, account.balance, account.created_at
+        )?;
+    }
+    Ok(())
+}
+
+pub fn import(text: &str) -> Vec<Account> {
+    text.lines()
+        .filter(|line| !line.is_empty())
+        .map(|line| {
+            let fields: Vec<&str> = line.split(',').collect();
+            Account {
+                id: fields[0].parse().unwrap(),
+                owner: fields[1].to_owned(),
+                balance: fields[2].parse().unwrap(),
+                created_at: fields[3].parse().unwrap(),
+            }
+        })
+        .collect()
+}
+
+pub fn fetch(address: &str, path: &str) -> io::Result<String> {
+    let mut stream = TcpStream::connect(address)?;
+    write!(stream, "GET {path} HTTP/1.0\r\n\r\n")?;
+    let mut response = String::new();
+    stream.read_to_string(&mut response)?;
+    Ok(response)
+}
+
+pub fn backup(directory: &str) -> io::Result<bool> {
+    let status = Command::new("tar").arg("czf").arg("backup.tgz").arg(directory).status()?;
+    Ok(status.success())
+}
+
+pub fn retry<T, E>(attempts: usize, mut operation: impl FnMut() -> Result<T, E>) -> Result<T, E> {
+    let mut last = operation();
+    for _ in 1..attempts {
+        if last.is_ok() {
+            break;
+        }
+        std::thread::sleep(Duration::from_millis(100));
+        last = operation();
+    }
+    last
+}
+
+pub fn interest(balance: i64, rate: f64, days: u32) -> i64 {
+    let daily = rate / 365.0;
+    (balance as f64 * (1.0 + daily).powi(days as i32)) as i64
+}
+
+pub fn group_by<T, K: Ord, F: Fn(&T) -> K>(items: Vec<T>, key: F) -> BTreeMap<K, Vec<T>> {
+    let mut groups = BTreeMap::new();
+    for item in items {
+        groups.entry(key(&item)).or_insert_with(Vec::new).push(item);
+    }
+    groups
+}
+
+pub fn percentiles(mut values: Vec<i64>, points: &[usize]) -> Vec<i64> {
+    values.sort_unstable();
+    points
+        .iter()
+        .map(|point| values[(values.len() - 1) * point / 100])
+        .collect()
+}
@@ -0,0 +1,414 @@
+//! Measures the wall-clock overhead of each lint library.
+//!
+//! Every fixture crate is checked with plain `cargo check` (the baseline) and
+//! with `cargo dylint --path <library>` for each library of `rules/`, and the
+//! median of the runs is compared against the baseline. The fixture is
+//! touched before each run so that cargo checks it again while its
+//! dependencies stay cached. The overhead therefore includes the start-up of
+//! the Dylint driver, which is the same for every library: compare the
+//! libraries between themselves, or a library between two revisions, rather
+//! than reading the overhead as the cost of the passes alone.
+//!
+//! ```bash
+//! cargo bench -p cargo-security-lints --bench lint_overhead -- \
+//!     [--runs 5] [--lib panic_usage]... [--max-overhead 50] [FIXTURE]...
+//! ```
+
+use std::{
+    env,
+    ffi::{OsStr, OsString},
+    fs::{self, File},
+    path::{Path, PathBuf},
+    process::{Command, ExitCode, ExitStatus, Stdio},
+    time::{Duration, Instant, SystemTime},
+};
+
+use anyhow::{Context, Result, bail};
+
+/// Usage printed by `--help` and on invalid arguments.
+const USAGE: &str = "\
+Measures the overhead of each lint library on fixture crates.
+
+Usage: cargo bench -p cargo-security-lints --bench lint_overhead -- [OPTIONS] [FIXTURE]...
+
+Options:
+  --runs <N>       Number of timed runs per library and fixture [default: 5]
+  --lib <NAME>     Only measure this library of `rules/` (repeatable)
+                   [default: every library]
+  --max-overhead <PERCENT>
+                   Fail when the median overhead of a library on a fixture
+                   exceeds this percentage of the baseline
+  -h, --help       Print this help
+
+Fixtures are paths of crates to check [default: cli/benches/fixtures/*].";
+
+/// Command-line options of the benchmark.
+#[derive(Debug)]
+struct Options {
+    runs: usize,
+    libraries: Vec<String>,
+    max_overhead: Option<f64>,
+    fixtures: Vec<PathBuf>,
+    help: bool,
+}
+
+/// The medians measured for one fixture.
+struct Measurement {
+    fixture: String,
+    baseline: Duration,
+    /// The library names and their median duration.
+    libraries: Vec<(String, Duration)>,
+}
+
+fn main() -> ExitCode {
+    match run() {
+        Ok(code) => code,
+        Err(error) => {
+            eprintln!("error: {error:#}");
+            ExitCode::from(2)
+        },
+    }
+}
+
+/// Measures every selected library on every fixture, prints the report and
+/// returns whether the overheads are within `--max-overhead`.
+fn run() -> Result<ExitCode> {
+    let options: Options = parse(env::args().skip(1).collect())?;
+    if options.help {
+        println!("{USAGE}");
+        return Ok(ExitCode::SUCCESS);
+    }
+
+    let root: PathBuf = workspace_root();
+    let libraries: Vec<String> = if options.libraries.is_empty() {
+        libraries(&root)?
+    } else {
+        options.libraries.clone()
+    };
+    let fixtures: Vec<PathBuf> = if options.fixtures.is_empty() {
+        fixtures(&root)?
+    } else {
+        options.fixtures.clone()
+    };
+
+    let mut measurements: Vec<Measurement> = Vec::new();
+    for fixture in &fixtures {
+        let name: String =
+            fixture.file_name().map_or_else(String::new, |name: &OsStr| {
+                name.to_string_lossy().into_owned()
+            });
+        eprintln!("Measuring `{name}`...");
+
+        let baseline: Duration = measure(&root, fixture, None, options.runs)?;
+        let mut medians: Vec<(String, Duration)> = Vec::new();
+        for library in &libraries {
+            let path: PathBuf = root.join("rules").join(library);
+            let median: Duration =
+                measure(&root, fixture, Some(&path), options.runs)?;
+            medians.push((library.clone(), median));
+        }
+
+        measurements.push(Measurement {
+            fixture: name,
+            baseline,
+            libraries: medians,
+        });
+    }
+
+    print!("{}", report(&measurements));
+
+    let exceeded: Vec<String> = options
+        .max_overhead
+        .map(|limit: f64| exceeded(&measurements, limit))
+        .unwrap_or_default();
+    if exceeded.is_empty() {
+        return Ok(ExitCode::SUCCESS);
+    }
+    for entry in &exceeded {
+        eprintln!("error: overhead of {entry} exceeds the limit");
+    }
+
+    Ok(ExitCode::FAILURE)
+}
+
+/// Parses the command-line arguments, without the program name. The
+/// `--bench` argument added by `cargo bench` is skipped.
+///
+/// # Arguments
+/// * `args` (`Vec<String>`) - The arguments to parse.
+fn parse(args: Vec<String>) -> Result<Options> {
+    let mut options: Options = Options {
+        runs: 5,
+        libraries: Vec::new(),
+        max_overhead: None,
+        fixtures: Vec::new(),
+        help: false,
+    };
+    let mut args: std::vec::IntoIter<String> = args.into_iter();
+
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--bench" => {},
+            "--runs" => {
+                options.runs = value(&mut args, "--runs")?
+                    .parse()
+                    .context("`--runs` expects a number")?;
+                if options.runs == 0 {
+                    bail!("`--runs` must be at least 1");
+                }
+            },
+            "--lib" => options.libraries.push(value(&mut args, "--lib")?),
+            "--max-overhead" => {
+                options.max_overhead = Some(
+                    value(&mut args, "--max-overhead")?
+                        .parse()
+                        .context("`--max-overhead` expects a percentage")?,
+                );
+            },
+            "-h" | "--help" => options.help = true,
+            flag if flag.starts_with('-') => {
+                bail!("unknown argument `{flag}`\n\n{USAGE}")
+            },
+            _ => options.fixtures.push(PathBuf::from(arg)),
+        }
+    }
+
+    Ok(options)
+}
+
+/// Returns the value following an option.
+///
+/// # Arguments
+/// * `args` (`&mut impl Iterator<Item = String>`) - The remaining arguments.
+/// * `option` (`&str`) - The option expecting a value, for the error message.
+fn value(
+    args: &mut impl Iterator<Item = String>,
+    option: &str,
+) -> Result<String> {
+    args.next().with_context(|| format!("`{option}` expects a value"))
+}
+
+/// Returns the root of the lint workspace, the parent of the `cli` package.
+fn workspace_root() -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR"))
+        .parent()
+        .map_or_else(|| PathBuf::from("."), Path::to_path_buf)
+}
+
+/// Returns the names of the lint libraries, the directories of `rules/`.
+///
+/// # Arguments
+/// * `root` (`&Path`) - The root of the lint workspace.
+fn libraries(root: &Path) -> Result<Vec<String>> {
+    let mut libraries: Vec<String> = directories(&root.join("rules"))?
+        .iter()
+        .filter_map(|path: &PathBuf| path.file_name())
+        .map(|name: &OsStr| name.to_string_lossy().into_owned())
+        .collect();
+    libraries.sort();
+
+    Ok(libraries)
+}
+
+/// Returns the bundled fixture crates.
+///
+/// # Arguments
+/// * `root` (`&Path`) - The root of the lint workspace.
+fn fixtures(root: &Path) -> Result<Vec<PathBuf>> {
+    let mut fixtures: Vec<PathBuf> =
+        directories(&root.join("cli").join("benches").join("fixtures"))?;
+    fixtures.sort();
+
+    Ok(fixtures)
+}
+
+/// Returns the subdirectories of `path` containing a `Cargo.toml`.
+///
+/// # Arguments
+/// * `path` (`&Path`) - The directory to list.
+fn directories(path: &Path) -> Result<Vec<PathBuf>> {
+    let mut directories: Vec<PathBuf> = Vec::new();
+    for entry in fs::read_dir(path)
+        .with_context(|| format!("could not read `{}`", path.display()))?
+    {
+        let entry_path: PathBuf = entry?.path();
+        if entry_path.join("Cargo.toml").is_file() {
+            directories.push(entry_path);
+        }
+    }
+
+    Ok(directories)
+}
+
+/// Checks `fixture` once to warm the caches (and build the library), then
+/// `runs` times, and returns the median duration.
+///
+/// # Arguments
+/// * `root` (`&Path`) - The root of the lint workspace, from which cargo is
+///   run so that the fixture is checked with the toolchain of the libraries.
+/// * `fixture` (`&Path`) - The crate to check.
+/// * `library` (`Option<&Path>`) - The library package to load, or `None` for
+///   the baseline.
+/// * `runs` (`usize`) - The number of timed runs.
+fn measure(
+    root: &Path,
+    fixture: &Path,
+    library: Option<&Path>,
+    runs: usize,
+) -> Result<Duration> {
+    check(root, fixture, library)?;
+
+    let mut durations: Vec<Duration> = Vec::with_capacity(runs);
+    for _ in 0..runs {
+        touch(fixture)?;
+        let start: Instant = Instant::now();
+        check(root, fixture, library)?;
+        durations.push(start.elapsed());
+    }
+
+    Ok(median(&mut durations))
+}
+
+/// Checks `fixture` once, with `library` loaded if given. Lint levels are
+/// capped to warnings, so that deny-level findings do not fail the run.
+///
+/// # Arguments
+/// * `root` (`&Path`) - The directory cargo is run from.
+/// * `fixture` (`&Path`) - The crate to check.
+/// * `library` (`Option<&Path>`) - The library package to load.
+fn check(root: &Path, fixture: &Path, library: Option<&Path>) -> Result<()> {
+    let cargo: OsString =
+        env::var_os("CARGO").unwrap_or_else(|| OsString::from("cargo"));
+    let mut command: Command = Command::new(cargo);
+    match library {
+        Some(library) => {
+            command.args(["dylint", "--quiet", "--path"]).arg(library);
+        },
+        None => {
+            command.args(["check", "--quiet"]);
+        },
+    }
+
+    let status: ExitStatus = command
+        .arg("--manifest-path")
+        .arg(fixture.join("Cargo.toml"))
+        .current_dir(root)
+        .env("RUSTFLAGS", "--cap-lints=warn")
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .status()
+        .context("could not run cargo")?;
+    if !status.success() {
+        bail!(
+            "checking `{}` with {} failed with {status}",
+            fixture.display(),
+            library.map_or_else(
+                || String::from("`cargo check`"),
+                |library: &Path| format!("`{}`", library.display()),
+            ),
+        );
+    }
+
+    Ok(())
+}
+
+/// Updates the modification time of the crate root of `fixture`, so that
+/// cargo checks it again.
+///
+/// # Arguments
+/// * `fixture` (`&Path`) - The crate to touch.
+fn touch(fixture: &Path) -> Result<()> {
+    let Some(path) = ["src/lib.rs", "src/main.rs"]
+        .iter()
+        .map(|root: &&str| fixture.join(root))
+        .find(|path: &PathBuf| path.is_file())
+    else {
+        bail!("`{}` has no `src/lib.rs` or `src/main.rs`", fixture.display());
+    };
+
+    File::options()
+        .write(true)
+        .open(&path)
+        .and_then(|file: File| file.set_modified(SystemTime::now()))
+        .with_context(|| format!("could not touch `{}`", path.display()))
+}
+
+/// Returns the median of `durations`, which must not be empty.
+///
+/// # Arguments
+/// * `durations` (`&mut [Duration]`) - The durations, sorted in place.
+fn median(durations: &mut [Duration]) -> Duration {
+    durations.sort_unstable();
+    let middle: usize = durations.len() / 2;
+    if durations.len().is_multiple_of(2) {
+        (durations[middle - 1] + durations[middle]) / 2
+    } else {
+        durations[middle]
+    }
+}
+
+/// Returns the overhead of `duration` over `baseline`, as a percentage of
+/// the baseline.
+///
+/// # Arguments
+/// * `duration` (`Duration`) - The duration with the library loaded.
+/// * `baseline` (`Duration`) - The duration without any library.
+fn overhead(duration: Duration, baseline: Duration) -> f64 {
+    (duration.as_secs_f64() - baseline.as_secs_f64()) / baseline.as_secs_f64()
+        * 100.0
+}
+
+/// Formats the measurements as a table with one row per fixture and
+/// library.
+///
+/// # Arguments
+/// * `measurements` (`&[Measurement]`) - The measurements to format.
+fn report(measurements: &[Measurement]) -> String {
+    let mut out: String = format!(
+        "{:<16} {:<22} {:>9} {:>10} {:>9}\n",
+        "fixture", "library", "median", "overhead", "relative"
+    );
+    for measurement in measurements {
+        out.push_str(&format!(
+            "{:<16} {:<22} {:>8.3}s\n",
+            measurement.fixture,
+            "(baseline)",
+            measurement.baseline.as_secs_f64(),
+        ));
+        for (library, duration) in &measurement.libraries {
+            out.push_str(&format!(
+                "{:<16} {:<22} {:>8.3}s {:>+9.3}s {:>+8.1}%\n",
+                measurement.fixture,
+                library,
+                duration.as_secs_f64(),
+                duration.as_secs_f64() - measurement.baseline.as_secs_f64(),
+                overhead(*duration, measurement.baseline),
+            ));
+        }
+    }
+
+    out
+}
+
+/// Returns the `library` on `fixture` entries whose overhead exceeds
+/// `limit` percent.
+///
+/// # Arguments
+/// * `measurements` (`&[Measurement]`) - The measurements to compare.
+/// * `limit` (`f64`) - The maximum overhead, as a percentage.
+fn exceeded(measurements: &[Measurement], limit: f64) -> Vec<String> {
+    measurements
+        .iter()
+        .flat_map(|measurement: &Measurement| {
+            measurement
+                .libraries
+                .iter()
+                .filter(|(_, duration): &&(String, Duration)| {
+                    overhead(*duration, measurement.baseline) > limit
+                })
+                .map(|(library, _): &(String, Duration)| {
+                    format!("`{library}` on `{}`", measurement.fixture)
+                })
+        })
+        .collect()
+}
@@ -0,0 +1,385 @@
+use std::{collections::BTreeMap, fs, io::ErrorKind, path::Path};
+
+use anyhow::{Context, Result, bail};
+use serde::{Deserialize, Deserializer, de::Error};
+
+use crate::{finding, profile::Profile};
+
+/// The libraries reading a table of `dylint.toml` named after them.
+const CONFIGURABLE_LIBRARIES: [&str; 22] = [
+    "alloc_usage",
+    "api_surface",
+    "async_usage",
+    "cfg_usage",
+    "error_masking",
+    "http_client",
+    "indexing_usage",
+    "interrupt_handler",
+    "missing_type",
+    "monetary_float",
+    "network_address",
+    "network_timeout",
+    "no_std_usage",
+    "panic_usage",
+    "process_usage",
+    "random_usage",
+    "secret_handling",
+    "timestamp_identifier",
+    "tls_config",
+    "unsafe_usage",
+    "wasm_usage",
+    "xml_config",
+];
+
+/// The libraries without configuration.
+const UNCONFIGURABLE_LIBRARIES: [&str; 5] = [
+    "fs_usage",
+    "intrinsics_usage",
+    "iterator_usage",
+    "lint_expectations",
+    "shift_usage",
+];
+
+/// The tables of `dylint.toml` shared by the libraries and the subcommand.
+const SHARED_TABLES: [&str; 2] = ["allow", "policy"];
+
+/// Severity of a finding, used by the exit-code policy. Ordered from the
+/// least to the most severe.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Severity {
+    Low,
+    Medium,
+    High,
+}
+
+impl Severity {
+    /// Returns the name of the severity as written in `dylint.toml`.
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::Low => "low",
+            Self::Medium => "medium",
+            Self::High => "high",
+        }
+    }
+
+    /// Parses a severity gate: `low`, `medium`, `high`, or `never` for a gate
+    /// that no finding reaches.
+    ///
+    /// # Arguments
+    /// * `value` (`&str`) - The value to parse.
+    pub fn parse_gate(value: &str) -> Option<Option<Self>> {
+        match value {
+            "low" => Some(Some(Self::Low)),
+            "medium" => Some(Some(Self::Medium)),
+            "high" => Some(Some(Self::High)),
+            "never" => Some(None),
+            _ => None,
+        }
+    }
+}
+
+/// Deserializes a severity gate with [`Severity::parse_gate`].
+fn deserialize_gate<'de, D: Deserializer<'de>>(
+    deserializer: D,
+) -> Result<Option<Severity>, D::Error> {
+    let value: String = String::deserialize(deserializer)?;
+    Severity::parse_gate(&value).ok_or_else(|| {
+        D::Error::custom(format!(
+            "invalid severity `{value}`, expected `low`, `medium`, `high` or \
+             `never`"
+        ))
+    })
+}
+
+/// The `[policy]` table of the workspace's `dylint.toml`, applied by
+/// `cargo security-lints` to the findings of a run. The lint libraries
+/// ignore this table.
+#[derive(Debug, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Policy {
+    /// Maximum number of findings allowed per lint (e.g.
+    /// `security_unsafe_usage = 3`). Findings of a lint within its budget are
+    /// reported as warnings; exceeding the budget fails the run.
+    pub thresholds: BTreeMap<String, usize>,
+    /// Severity of each lint. Lints not listed here are `high` when
+    /// deny-level and `medium` otherwise.
+    pub severity: BTreeMap<String, Severity>,
+    /// Findings at or above this severity fail the run, unless their lint
+    /// has a threshold. `None` (`"never"`) only reports.
+    #[serde(deserialize_with = "deserialize_gate")]
+    pub fail_on: Option<Severity>,
+    /// Findings at or above this severity that do not fail the run are
+    /// reported as warnings; the others are reported as notes.
+    pub warn_on: Severity,
+    /// The profile setting the rustc levels of the lints, if any.
+    pub profile: Option<Profile>,
+}
+
+impl Default for Policy {
+    fn default() -> Self {
+        Self {
+            thresholds: BTreeMap::new(),
+            severity: BTreeMap::new(),
+            fail_on: Some(Severity::High),
+            warn_on: Severity::Low,
+            profile: None,
+        }
+    }
+}
+
+/// Reads the `[policy]` table of the `dylint.toml` file at `path`. A missing
+/// file or table yields the default policy.
+///
+/// # Arguments
+/// * `path` (`&Path`) - The path of the `dylint.toml` file.
+pub fn load(path: &Path) -> Result<Policy> {
+    let contents: String = match fs::read_to_string(path) {
+        Ok(contents) => contents,
+        Err(error) if error.kind() == ErrorKind::NotFound => {
+            return Ok(Policy::default());
+        },
+        Err(error) => {
+            return Err(error).with_context(|| {
+                format!("could not read `{}`", path.display())
+            });
+        },
+    };
+
+    parse(&contents).with_context(|| format!("invalid `{}`", path.display()))
+}
+
+/// Parses the `[policy]` table out of the contents of a `dylint.toml` file,
+/// after checking the names of its tables and of the lints it configures.
+///
+/// # Arguments
+/// * `contents` (`&str`) - The contents of the file.
+pub fn parse(contents: &str) -> Result<Policy> {
+    let mut table: toml::Table = toml::from_str(contents)?;
+    validate(&table)?;
+
+    match table.remove("policy") {
+        Some(policy) => Ok(policy.try_into()?),
+        None => Ok(Policy::default()),
+    }
+}
+
+/// Checks the tables of `dylint.toml` and the lint names of its `[allow]`
+/// table and `[policy]` lint tables. A table that no library of this
+/// repository reads is assumed to belong to a third-party library, unless
+/// its name is close to a known one.
+///
+/// # Arguments
+/// * `table` (`&toml::Table`) - The contents of `dylint.toml`.
+fn validate(table: &toml::Table) -> Result<()> {
+    let known: Vec<&str> = SHARED_TABLES
+        .into_iter()
+        .chain(CONFIGURABLE_LIBRARIES)
+        .chain(UNCONFIGURABLE_LIBRARIES)
+        .collect();
+
+    for name in table.keys() {
+        if UNCONFIGURABLE_LIBRARIES.contains(&name.as_str()) {
+            bail!(
+                "unexpected `[{name}]` table, the `{name}` library takes no \
+                 configuration"
+            );
+        }
+        if !known.contains(&name.as_str())
+            && let Some(suggestion) = closest(name, &known)
+        {
+            bail!("unknown table `[{name}]`, did you mean `[{suggestion}]`?");
+        }
+    }
+
+    if let Some(allow) = table.get("allow") {
+        let allow: BTreeMap<String, Vec<String>> =
+            allow.clone().try_into().context("invalid `[allow]` table")?;
+        for lint in allow.keys() {
+            if !lint.starts_with("security::") {
+                check_lint(lint, "[allow]")?;
+            }
+        }
+    }
+
+    if let Some(policy) = table.get("policy").and_then(toml::Value::as_table) {
+        for name in ["thresholds", "severity"] {
+            for lint in policy
+                .get(name)
+                .and_then(toml::Value::as_table)
+                .into_iter()
+                .flat_map(toml::Table::keys)
+            {
+                check_lint(lint, &format!("[policy.{name}]"))?;
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// Fails if `lint`, a key of the `table` table, is not the flat name of a
+/// security lint, suggesting the `security_` prefix when it is missing.
+///
+/// # Arguments
+/// * `lint` (`&str`) - The name of the lint.
+/// * `table` (`&str`) - The table the name is a key of, e.g. `[allow]`.
+fn check_lint(lint: &str, table: &str) -> Result<()> {
+    if finding::is_security_lint(lint) {
+        return Ok(());
+    }
+    if let Some(name) = lint.strip_prefix("security::") {
+        bail!(
+            "unknown lint `{lint}` in the `{table}` table, did you mean \
+             `security_{name}`?"
+        );
+    }
+
+    bail!(
+        "unknown lint `{lint}` in the `{table}` table, did you mean \
+         `security_{lint}`?"
+    )
+}
+
+/// Returns the candidate closest to `name`, if its edit distance is at most
+/// a third of the length of `name`.
+///
+/// # Arguments
+/// * `name` (`&str`) - The misspelled name.
+/// * `candidates` (`&[&'a str]`) - The known names.
+fn closest<'a>(name: &str, candidates: &[&'a str]) -> Option<&'a str> {
+    let limit: usize = name.chars().count().max(3) / 3;
+
+    candidates
+        .iter()
+        .map(|candidate: &&str| (edit_distance(name, candidate), *candidate))
+        .filter(|(distance, _): &(usize, &str)| *distance <= limit)
+        .min_by_key(|(distance, _): &(usize, &str)| *distance)
+        .map(|(_, candidate): (usize, &str)| candidate)
+}
+
+/// Returns the Levenshtein distance between `left` and `right`.
+///
+/// # Arguments
+/// * `left` (`&str`) - The first string.
+/// * `right` (`&str`) - The second string.
+fn edit_distance(left: &str, right: &str) -> usize {
+    let right: Vec<char> = right.chars().collect();
+    let mut previous: Vec<usize> = (0..=right.len()).collect();
+
+    for (row, left_char) in left.chars().enumerate() {
+        let mut current: Vec<usize> = Vec::with_capacity(previous.len());
+        current.push(row + 1);
+        // `previous` holds one more distance than `right` has characters:
+        // the cells above-left and above of `right_char` are at `column`
+        // and `column + 1`.
+        for (column, right_char) in right.iter().enumerate() {
+            let (Some(&diagonal), Some(&above)): (
+                Option<&usize>,
+                Option<&usize>,
+            ) = (previous.get(column), previous.get(column + 1)) else {
+                continue;
+            };
+            let before: usize = current.last().copied().unwrap_or_default();
+            current.push(
+                (diagonal + usize::from(left_char != *right_char))
+                    .min(above + 1)
+                    .min(before + 1),
+            );
+        }
+        previous = current;
+    }
+
+    previous.last().copied().unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Policy, Severity, edit_distance, parse};
+    use crate::profile::Profile;
+
+    #[test]
+    fn thresholds() {
+        let policy: Policy = parse(
+            r#"
+            [monetary_float]
+            patterns = ["price"]
+
+            [policy.thresholds]
+            security_unsafe_usage = 3
+            "#,
+        )
+        .unwrap();
+
+        assert_eq!(policy.thresholds.get("security_unsafe_usage"), Some(&3));
+        assert!(parse("[policy]\nunknown = 1").is_err());
+        assert!(parse("").unwrap().thresholds.is_empty());
+    }
+
+    #[test]
+    fn gates() {
+        let policy: Policy = parse(
+            r#"
+            [policy]
+            fail_on = "never"
+            warn_on = "medium"
+            profile = "embedded"
+
+            [policy.severity]
+            security_shift_usage = "low"
+            "#,
+        )
+        .unwrap();
+
+        assert_eq!(policy.fail_on, None);
+        assert_eq!(policy.warn_on, Severity::Medium);
+        assert_eq!(policy.profile, Some(Profile::Embedded));
+        assert_eq!(
+            policy.severity.get("security_shift_usage"),
+            Some(&Severity::Low)
+        );
+        assert_eq!(Policy::default().fail_on, Some(Severity::High));
+        assert!(parse("[policy]\nfail_on = \"critical\"").is_err());
+    }
+
+    #[test]
+    fn validation() {
+        assert_eq!(edit_distance("panci_usage", "panic_usage"), 2);
+        assert_eq!(edit_distance("", "tls"), 3);
+
+        let error: String = format!(
+            "{:#}",
+            parse("[monetry_float]\npatterns = []").unwrap_err()
+        );
+        assert_eq!(
+            error,
+            "unknown table `[monetry_float]`, did you mean `[monetary_float]`?"
+        );
+        let error: String =
+            format!("{:#}", parse("[shift_usage]\nlevel = 1").unwrap_err());
+        assert!(error.contains("takes no configuration"), "{error}");
+        let error: String = format!(
+            "{:#}",
+            parse("[allow]\npanic_usage = [\"crate::tests\"]").unwrap_err()
+        );
+        assert_eq!(
+            error,
+            "unknown lint `panic_usage` in the `[allow]` table, did you mean \
+             `security_panic_usage`?"
+        );
+        assert!(parse("[policy.thresholds]\nunsafe_usage = 1").is_err());
+        assert!(parse("[allow]\nsecurity_panic_usage = \"crate\"").is_err());
+
+        assert!(
+            parse(
+                "[acme_lints]\nlevel = 1\n[allow]\n\"security::panic_usage\" \
+                 = [\"crate::tests\"]\nmissing_let_type = [\"crate\"]\n\
+                 missing_let_else_type = [\"crate\"]\nmissing_if_let_type = \
+                 [\"crate\"]\nmissing_while_let_type = [\"crate\"]\n\
+                 missing_for_loop_type = [\"crate\"]\nmissing_target_type = \
+                 [\"crate\"]"
+            )
+            .is_ok()
+        );
+    }
+}
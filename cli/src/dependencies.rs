@@ -0,0 +1,351 @@
+use std::{
+    collections::{BTreeSet, HashMap, HashSet},
+    env,
+    fs,
+    io,
+    path::{Path, PathBuf},
+    process,
+};
+
+use anyhow::{Context, Result};
+use serde::Deserialize;
+
+use crate::{finding, runner};
+
+/// Directories of a dependency that are not copied into the scratch
+/// workspace.
+const SKIPPED_DIRECTORIES: [&str; 2] = ["target", ".git"];
+
+/// Dependencies scanned by `--dependencies`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Scope {
+    /// The dependencies declared by the workspace members.
+    Direct,
+    /// Every dependency of the workspace members, direct or not.
+    All,
+}
+
+impl Scope {
+    /// Parses the value of the `--dependencies` option.
+    ///
+    /// # Arguments
+    /// * `value` (`&str`) - The value to parse.
+    pub fn parse(value: &str) -> Option<Self> {
+        match value {
+            "direct" => Some(Self::Direct),
+            "all" => Some(Self::All),
+            _ => None,
+        }
+    }
+}
+
+/// The subset of the output of `cargo metadata` used to select the
+/// dependencies.
+#[derive(Debug, Deserialize)]
+struct Metadata {
+    packages: Vec<Package>,
+    workspace_members: Vec<String>,
+    resolve: Option<Resolve>,
+    workspace_root: PathBuf,
+    /// The `[workspace.metadata]` table of the workspace.
+    metadata: Option<toml::Table>,
+}
+
+#[derive(Debug, Deserialize)]
+struct Package {
+    id: String,
+    manifest_path: PathBuf,
+}
+
+#[derive(Debug, Deserialize)]
+struct Resolve {
+    nodes: Vec<Node>,
+}
+
+#[derive(Debug, Deserialize)]
+struct Node {
+    id: String,
+    deps: Vec<NodeDep>,
+}
+
+#[derive(Debug, Deserialize)]
+struct NodeDep {
+    pkg: String,
+    dep_kinds: Vec<DepKind>,
+}
+
+#[derive(Debug, Deserialize)]
+struct DepKind {
+    /// `dev`, `build`, or `None` for normal dependencies.
+    kind: Option<String>,
+}
+
+/// A scratch directory removed when dropped.
+struct ScratchDirectory(PathBuf);
+
+impl Drop for ScratchDirectory {
+    fn drop(&mut self) {
+        let _: io::Result<()> = fs::remove_dir_all(&self.0);
+    }
+}
+
+/// Returns the IDs of the dependencies of the workspace members within
+/// `scope`, sorted. Development dependencies are not shipped with the
+/// workspace and are skipped.
+///
+/// # Arguments
+/// * `metadata` (`&Metadata`) - The output of `cargo metadata`.
+/// * `scope` (`Scope`) - The dependencies to select.
+fn select(metadata: &Metadata, scope: Scope) -> BTreeSet<String> {
+    let members: HashSet<&str> =
+        metadata.workspace_members.iter().map(String::as_str).collect();
+    let edges: HashMap<&str, Vec<&str>> = metadata
+        .resolve
+        .iter()
+        .flat_map(|resolve: &Resolve| &resolve.nodes)
+        .map(|node: &Node| {
+            let deps: Vec<&str> = node
+                .deps
+                .iter()
+                .filter(|dep: &&NodeDep| {
+                    dep.dep_kinds.iter().any(|kind: &DepKind| {
+                        kind.kind.as_deref() != Some("dev")
+                    })
+                })
+                .map(|dep: &NodeDep| dep.pkg.as_str())
+                .collect();
+            (node.id.as_str(), deps)
+        })
+        .collect();
+
+    let mut selected: BTreeSet<String> = BTreeSet::new();
+    let mut pending: Vec<&str> = members.iter().copied().collect();
+    while let Some(id) = pending.pop() {
+        for dep in edges.get(id).into_iter().flatten() {
+            if members.contains(dep) || !selected.insert((*dep).to_owned()) {
+                continue;
+            }
+            if scope == Scope::All {
+                pending.push(dep);
+            }
+        }
+    }
+
+    selected
+}
+
+/// Returns the `[workspace.metadata.dylint]` table of the workspace, with
+/// relative library paths made absolute so that it can be used from the
+/// scratch workspace of a dependency.
+///
+/// # Arguments
+/// * `metadata` (`&Metadata`) - The output of `cargo metadata`.
+fn dylint_metadata(metadata: &Metadata) -> Option<toml::Table> {
+    let mut dylint: toml::Table =
+        metadata.metadata.as_ref()?.get("dylint")?.as_table()?.clone();
+
+    if let Some(libraries) =
+        dylint.get_mut("libraries").and_then(toml::Value::as_array_mut)
+    {
+        for library in
+            libraries.iter_mut().filter_map(toml::Value::as_table_mut)
+        {
+            if let Some(toml::Value::String(path)) = library.get_mut("path") {
+                *path =
+                    metadata.workspace_root.join(&*path).display().to_string();
+            }
+        }
+    }
+
+    Some(dylint)
+}
+
+/// Copies the directory `from` to `to`, except for build artifacts and
+/// version control data.
+///
+/// # Arguments
+/// * `from` (`&Path`) - The directory to copy.
+/// * `to` (`&Path`) - The destination, created if needed.
+fn copy_directory(from: &Path, to: &Path) -> io::Result<()> {
+    fs::create_dir_all(to)?;
+
+    for entry in fs::read_dir(from)? {
+        let entry: fs::DirEntry = entry?;
+        let destination: PathBuf = to.join(entry.file_name());
+
+        if entry.file_type()?.is_dir() {
+            if SKIPPED_DIRECTORIES
+                .iter()
+                .any(|skipped: &&str| entry.file_name() == *skipped)
+            {
+                continue;
+            }
+            copy_directory(&entry.path(), &destination)?;
+        } else {
+            fs::copy(entry.path(), destination)?;
+        }
+    }
+
+    Ok(())
+}
+
+/// Copies the sources of `package` into `directory` and turns them into a
+/// standalone workspace running the same Dylint libraries. The lock file of
+/// the workspace is reused when the package has none, so that the
+/// dependency is checked against the versions already resolved.
+///
+/// # Arguments
+/// * `package` (`&Package`) - The dependency to copy.
+/// * `metadata` (`&Metadata`) - The output of `cargo metadata`.
+/// * `directory` (`&Path`) - The destination of the sources.
+fn prepare(
+    package: &Package,
+    metadata: &Metadata,
+    directory: &Path,
+) -> Result<PathBuf> {
+    let source: &Path = package
+        .manifest_path
+        .parent()
+        .context("manifest path without a parent directory")?;
+    copy_directory(source, directory)
+        .with_context(|| format!("could not copy `{}`", source.display()))?;
+
+    let manifest_path: PathBuf = directory.join("Cargo.toml");
+    let mut manifest: toml::Table =
+        toml::from_str(&fs::read_to_string(&manifest_path)?)?;
+    let mut workspace: toml::Table = toml::Table::new();
+    if let Some(dylint) = dylint_metadata(metadata) {
+        let mut workspace_metadata: toml::Table = toml::Table::new();
+        workspace_metadata.insert(String::from("dylint"), dylint.into());
+        workspace.insert(String::from("metadata"), workspace_metadata.into());
+    }
+    manifest.insert(String::from("workspace"), workspace.into());
+    fs::write(&manifest_path, toml::to_string(&manifest)?)?;
+
+    let lock_file: PathBuf = directory.join("Cargo.lock");
+    let workspace_lock_file: PathBuf =
+        metadata.workspace_root.join("Cargo.lock");
+    if !lock_file.exists() && workspace_lock_file.exists() {
+        fs::copy(workspace_lock_file, lock_file)?;
+    }
+
+    Ok(manifest_path)
+}
+
+/// Runs the lints over the sources of the dependencies of the workspace
+/// within `scope` and returns the concatenated compiler output. Each
+/// dependency is copied into a scratch workspace of its own; its findings
+/// are attributed to it through the `package_id` of the compiler messages.
+/// Dependencies that cannot be checked are reported and skipped.
+///
+/// # Arguments
+/// * `scope` (`Scope`) - The dependencies to scan.
+/// * `registry` (`&Path`) - The directory the lint libraries register
+///   themselves in.
+/// * `cargo_args` (`&[String]`) - Extra arguments forwarded to `cargo check`.
+/// * `rustc_flags` (`&[String]`) - Extra flags passed to rustc.
+pub fn scan(
+    scope: Scope,
+    registry: &Path,
+    cargo_args: &[String],
+    rustc_flags: &[String],
+) -> Result<String> {
+    let metadata: Metadata =
+        serde_json::from_str(&runner::cargo_metadata(None)?)
+            .context("invalid `cargo metadata` output")?;
+
+    let scratch: ScratchDirectory = ScratchDirectory(
+        env::temp_dir().join(format!("security-lints-{}", process::id())),
+    );
+    // A shared target directory avoids rebuilding common dependencies.
+    let mut cargo_args: Vec<String> = cargo_args.to_vec();
+    cargo_args.push(String::from("--target-dir"));
+    cargo_args.push(scratch.0.join("target").display().to_string());
+
+    let mut output: String = String::new();
+    for id in select(&metadata, scope) {
+        let Some(package): Option<&Package> = metadata
+            .packages
+            .iter()
+            .find(|package: &&Package| package.id == id)
+        else {
+            continue;
+        };
+        let name: String = finding::package_name(&id);
+        eprintln!("security-lints: scanning {name}");
+
+        let result: Result<String> = prepare(
+            package,
+            &metadata,
+            &scratch.0.join("sources").join(&name),
+        )
+        .and_then(|manifest_path: PathBuf| {
+            runner::run_dylint(
+                Some(&manifest_path),
+                registry,
+                &cargo_args,
+                rustc_flags,
+            )
+        });
+        match result {
+            Ok(stdout) => output.push_str(&stdout),
+            Err(error) => {
+                eprintln!("warning: could not scan {name}: {error:#}");
+            },
+        }
+    }
+
+    Ok(output)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeSet;
+
+    use super::{Metadata, Scope, dylint_metadata, select};
+
+    const METADATA: &str = r#"{
+        "packages": [],
+        "workspace_members": ["app"],
+        "workspace_root": "/src/app",
+        "metadata": {"dylint": {"libraries": [{"path": "lints/*"}, {"git": "https://example.com/lints"}]}},
+        "resolve": {"nodes": [
+            {"id": "app", "deps": [
+                {"pkg": "serde", "dep_kinds": [{"kind": null}]},
+                {"pkg": "cc", "dep_kinds": [{"kind": "build"}]},
+                {"pkg": "mockall", "dep_kinds": [{"kind": "dev"}]}
+            ]},
+            {"id": "serde", "deps": [{"pkg": "serde_derive", "dep_kinds": [{"kind": null}]}]},
+            {"id": "mockall", "deps": [{"pkg": "predicates", "dep_kinds": [{"kind": null}]}]}
+        ]}
+    }"#;
+
+    #[test]
+    fn selection() {
+        let metadata: Metadata = serde_json::from_str(METADATA).unwrap();
+
+        assert_eq!(
+            select(&metadata, Scope::Direct),
+            BTreeSet::from([String::from("cc"), String::from("serde")])
+        );
+        assert_eq!(
+            select(&metadata, Scope::All),
+            BTreeSet::from([
+                String::from("cc"),
+                String::from("serde"),
+                String::from("serde_derive")
+            ])
+        );
+    }
+
+    #[test]
+    fn library_paths() {
+        let metadata: Metadata = serde_json::from_str(METADATA).unwrap();
+
+        assert_eq!(
+            toml::to_string(&dylint_metadata(&metadata).unwrap()).unwrap(),
+            "[[libraries]]\npath = \"/src/app/lints/*\"\n\n[[libraries]]\n\
+             git = \"https://example.com/lints\"\n"
+        );
+    }
+}
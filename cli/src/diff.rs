@@ -0,0 +1,226 @@
+use std::{
+    collections::HashMap,
+    env,
+    ffi::OsString,
+    process::{Command, Output, Stdio},
+};
+
+use anyhow::{Context, Result, bail};
+
+use crate::finding::Finding;
+
+/// The lines added or modified in the working tree since a base commit, per
+/// file relative to the current directory. Files absent from the base (new
+/// or untracked files) are changed as a whole.
+#[derive(Debug, Default, PartialEq, Eq)]
+pub struct ChangedLines {
+    /// The changed line ranges (first and last line, inclusive) of each
+    /// modified file.
+    ranges: HashMap<String, Vec<(usize, usize)>>,
+    /// Files whose every line is changed.
+    whole_files: Vec<String>,
+}
+
+impl ChangedLines {
+    /// Returns `true` if `line` of `file` was changed.
+    ///
+    /// # Arguments
+    /// * `file` (`&str`) - The path of the file, relative to the current
+    ///   directory.
+    /// * `line` (`usize`) - The line number, starting at 1.
+    pub fn contains(&self, file: &str, line: usize) -> bool {
+        self.whole_files.iter().any(|whole: &String| whole == file)
+            || self.ranges.get(file).is_some_and(
+                |ranges: &Vec<(usize, usize)>| {
+                    ranges.iter().any(|&(first, last): &(usize, usize)| {
+                        (first..=last).contains(&line)
+                    })
+                },
+            )
+    }
+}
+
+/// Returns the lines changed in the working tree since the merge base of
+/// `reference` and `HEAD`, uncommitted and untracked files included.
+///
+/// # Arguments
+/// * `reference` (`&str`) - The git reference the changes are compared
+///   against, e.g. `origin/main`.
+pub fn changed_lines(reference: &str) -> Result<ChangedLines> {
+    let base: String =
+        git(&["merge-base", reference, "HEAD"]).with_context(|| {
+            format!("could not find the merge base of `{reference}`")
+        })?;
+
+    let mut changed: ChangedLines = parse(&git(&[
+        "diff",
+        "--unified=0",
+        "--relative",
+        "--no-color",
+        "--no-ext-diff",
+        "--no-renames",
+        "--src-prefix=a/",
+        "--dst-prefix=b/",
+        base.trim(),
+    ])?);
+    changed.whole_files.extend(
+        git(&["ls-files", "--others", "--exclude-standard"])?
+            .lines()
+            .map(str::to_owned),
+    );
+
+    Ok(changed)
+}
+
+/// Parses the output of `git diff --unified=0` into the changed lines of
+/// each file. Deleted files and hunks removing lines only have no changed
+/// lines.
+///
+/// # Arguments
+/// * `diff` (`&str`) - The output of `git diff`.
+pub fn parse(diff: &str) -> ChangedLines {
+    let mut changed: ChangedLines = ChangedLines::default();
+    let mut file: Option<String> = None;
+    let mut added: bool = false;
+    // Whether the lines are the header of a file, before its first hunk;
+    // `---` and `+++` lines of the hunks are removed and added lines.
+    let mut in_header: bool = false;
+
+    for line in diff.lines() {
+        if line.starts_with("diff --git ") {
+            file = None;
+            added = false;
+            in_header = true;
+        } else if in_header && line == "--- /dev/null" {
+            added = true;
+        } else if in_header && let Some(path) = line.strip_prefix("+++ ") {
+            file = path.strip_prefix("b/").map(str::to_owned);
+            if added && let Some(path) = &file {
+                changed.whole_files.push(path.clone());
+            }
+        } else if let Some(header) = line.strip_prefix("@@ ") {
+            in_header = false;
+            if let Some(path) = &file
+                && let Some(range) = hunk_range(header)
+            {
+                changed.ranges.entry(path.clone()).or_default().push(range);
+            }
+        }
+    }
+
+    changed
+}
+
+/// Returns the first and last line of the new side of a hunk, from its
+/// header without the leading `@@ ` (e.g. `-10,2 +12,3 @@ fn main()`), or
+/// `None` if the hunk only removes lines.
+///
+/// # Arguments
+/// * `header` (`&str`) - The hunk header.
+fn hunk_range(header: &str) -> Option<(usize, usize)> {
+    let new: &str = header
+        .split_whitespace()
+        .find_map(|part: &str| part.strip_prefix('+'))?;
+    let (start, count): (&str, &str) =
+        new.split_once(',').unwrap_or((new, "1"));
+    let start: usize = start.parse().ok()?;
+    let count: usize = count.parse().ok()?;
+
+    (count > 0).then(|| (start, start + count - 1))
+}
+
+/// Keeps the findings whose primary span is on a changed line, and returns
+/// the number of findings dropped.
+///
+/// # Arguments
+/// * `findings` (`&mut Vec<Finding>`) - The findings of the run.
+/// * `changed` (`&ChangedLines`) - The lines changed by the diff.
+pub fn retain_changed(
+    findings: &mut Vec<Finding>,
+    changed: &ChangedLines,
+) -> usize {
+    let before: usize = findings.len();
+    findings.retain(|finding: &Finding| {
+        changed.contains(&finding.file, finding.line)
+    });
+
+    before - findings.len()
+}
+
+/// Runs `git` with `args` and returns its standard output.
+///
+/// # Arguments
+/// * `args` (`&[&str]`) - The arguments of the git command.
+#[cfg_attr(
+    dylint_lib = "process_usage",
+    expect(
+        security_command_inherited_env,
+        reason = "git needs the `GIT_*` variables, `HOME` and `PATH` of the \
+                  caller to find the repository and its configuration"
+    )
+)]
+fn git(args: &[&str]) -> Result<String> {
+    let output: Output = Command::new(git_program())
+        .args(args)
+        .stderr(Stdio::inherit())
+        .output()
+        .context("could not run `git`")?;
+    if !output.status.success() {
+        bail!("`git {}` failed with {}", args.join(" "), output.status);
+    }
+
+    String::from_utf8(output.stdout).context("`git` printed invalid UTF-8")
+}
+
+/// Returns the git executable to run, `GIT` when set (e.g. to pin an
+/// absolute path in CI).
+fn git_program() -> OsString {
+    env::var_os("GIT").unwrap_or_else(|| OsString::from("git"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{ChangedLines, parse};
+
+    #[test]
+    fn changed() {
+        let changed: ChangedLines = parse(
+            "\
+diff --git a/src/main.rs b/src/main.rs
+index 1111111..2222222 100644
+--- a/src/main.rs
++++ b/src/main.rs
+@@ -3,0 +4,2 @@ fn main() {
++    let value: u8 = values[4];
++    let other: u8 = values[5];
+@@ -10 +12 @@ fn helper() {
+-    old();
++++ counter;
+@@ -20,3 +21,0 @@ fn removed() {
+-    gone();
+diff --git a/src/lib.rs b/src/lib.rs
+deleted file mode 100644
+--- a/src/lib.rs
++++ /dev/null
+@@ -1,2 +0,0 @@
+-pub fn gone() {}
+diff --git a/src/new.rs b/src/new.rs
+new file mode 100644
+--- /dev/null
++++ b/src/new.rs
+@@ -0,0 +1 @@
++pub fn added() {}
+",
+        );
+
+        assert!(!changed.contains("src/main.rs", 3));
+        assert!(changed.contains("src/main.rs", 4));
+        assert!(changed.contains("src/main.rs", 5));
+        assert!(!changed.contains("src/main.rs", 6));
+        assert!(changed.contains("src/main.rs", 12));
+        assert!(!changed.contains("src/main.rs", 21));
+        assert!(!changed.contains("src/lib.rs", 1));
+        assert!(changed.contains("src/new.rs", 40));
+        assert!(!changed.contains("src/other.rs", 1));
+    }
+}
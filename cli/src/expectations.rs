@@ -0,0 +1,170 @@
+use std::io::{self, Write};
+
+use serde::Serialize;
+
+use crate::finding::{Finding, Messages, is_security_lint};
+
+/// Name of the lint of the `lint_expectations` library reporting every
+/// `#[expect(...)]` attribute, which the expectations mode enables.
+pub const EXPECTATION_LINT: &str = "security_lint_expectation";
+
+/// An `#[expect(...)]` attribute waiving a security lint.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct Expectation {
+    /// The expected lint, e.g. `security_panic_usage`.
+    pub lint: String,
+    /// The package the attribute belongs to, as `name@version`.
+    pub package: String,
+    pub file: String,
+    pub line: usize,
+    pub column: usize,
+    /// The `reason = "..."` of the attribute.
+    pub reason: Option<String>,
+    /// Whether the expected lint was emitted in the scope of the attribute.
+    /// Unfulfilled expectations are reported by rustc.
+    pub fulfilled: bool,
+}
+
+/// The expectations of a run, sorted by package and location, and the
+/// number of unfulfilled ones.
+#[derive(Debug, Default, Clone, PartialEq, Eq, Serialize)]
+pub struct Report {
+    pub expectations: Vec<Expectation>,
+    pub unfulfilled: usize,
+}
+
+/// Returns the expectation reported by `finding`, if it is a finding of
+/// [`EXPECTATION_LINT`] for a security lint. The expected lint is read from
+/// the message (``Expectation of `security_panic_usage` detected.``) and the
+/// reason from the `reason: ...` note.
+///
+/// # Arguments
+/// * `finding` (`&Finding`) - The finding to convert.
+/// * `messages` (`&Messages`) - The messages of the run, listing the
+///   unfulfilled expectations.
+fn expectation(finding: &Finding, messages: &Messages) -> Option<Expectation> {
+    if finding.lint != EXPECTATION_LINT {
+        return None;
+    }
+
+    let (lint, _): (&str, &str) =
+        finding.message.strip_prefix("Expectation of `")?.split_once('`')?;
+    if !is_security_lint(lint) || lint == EXPECTATION_LINT {
+        return None;
+    }
+
+    let location: (String, usize, usize) =
+        (finding.file.clone(), finding.line, finding.column);
+
+    Some(Expectation {
+        lint: lint.to_owned(),
+        package: finding.package.clone(),
+        file: finding.file.clone(),
+        line: finding.line,
+        column: finding.column,
+        reason: finding.notes.iter().find_map(|note: &String| {
+            note.strip_prefix("reason: ").map(str::to_owned)
+        }),
+        fulfilled: !messages.unfulfilled_expectations.contains(&location),
+    })
+}
+
+/// Collects the expectations of security lints reported in `messages`.
+///
+/// # Arguments
+/// * `messages` (`&Messages`) - The messages of the run.
+pub fn collect(messages: &Messages) -> Report {
+    let mut expectations: Vec<Expectation> = messages
+        .findings
+        .iter()
+        .filter_map(|finding: &Finding| expectation(finding, messages))
+        .collect();
+    expectations.sort_by(|first: &Expectation, second: &Expectation| {
+        (&first.package, &first.file, first.line, first.column).cmp(&(
+            &second.package,
+            &second.file,
+            second.line,
+            second.column,
+        ))
+    });
+
+    Report {
+        unfulfilled: expectations
+            .iter()
+            .filter(|expectation: &&Expectation| !expectation.fulfilled)
+            .count(),
+        expectations,
+    }
+}
+
+/// Writes `report` as pretty-printed JSON.
+///
+/// # Arguments
+/// * `out` (`&mut dyn Write`) - The destination of the report.
+/// * `report` (`&Report`) - The report to write.
+pub fn write(out: &mut dyn Write, report: &Report) -> io::Result<()> {
+    serde_json::to_writer_pretty(&mut *out, report)?;
+    writeln!(out)
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::Value;
+
+    use super::{Report, collect};
+    use crate::finding::{Finding, Level, Messages};
+
+    fn finding(message: &str, line: usize, notes: &[&str]) -> Finding {
+        Finding {
+            lint: String::from("security_lint_expectation"),
+            package: String::from("demo@0.1.0"),
+            level: Level::Warning,
+            message: message.to_owned(),
+            file: String::from("src/lib.rs"),
+            line,
+            column: 10,
+            rendered: String::new(),
+            notes: notes.iter().map(|note: &&str| note.to_string()).collect(),
+            suggestions: Vec::new(),
+            diagnostic: Value::Null,
+        }
+    }
+
+    #[test]
+    fn report() {
+        let messages: Messages = Messages {
+            findings: vec![
+                finding(
+                    "Expectation of `security_panic_usage` detected.",
+                    8,
+                    &[],
+                ),
+                finding(
+                    "Expectation of `security_indexing_usage` detected.",
+                    3,
+                    &["reason: bounds checked by the caller"],
+                ),
+                finding("Expectation of `dead_code` detected.", 5, &[]),
+            ],
+            errors: Vec::new(),
+            unfulfilled_expectations: vec![(
+                String::from("src/lib.rs"),
+                8,
+                10,
+            )],
+            diagnostics: Vec::new(),
+        };
+
+        let report: Report = collect(&messages);
+
+        assert_eq!(report.expectations.len(), 2);
+        assert_eq!(report.expectations[0].lint, "security_indexing_usage");
+        assert_eq!(
+            report.expectations[0].reason.as_deref(),
+            Some("bounds checked by the caller")
+        );
+        assert!(report.expectations[0].fulfilled);
+        assert!(!report.expectations[1].fulfilled);
+        assert_eq!(report.unfulfilled, 1);
+    }
+}
@@ -0,0 +1,544 @@
+use std::collections::HashMap;
+
+use serde::Deserialize;
+use serde_json::Value;
+
+/// Names of the lints provided by this repository that do not share the
+/// `security_` prefix, all declared by the `missing_type` library.
+const UNPREFIXED_LINTS: [&str; 7] = [
+    "missing_let_type",
+    "missing_closure_param_type",
+    "missing_target_type",
+    "missing_let_else_type",
+    "missing_if_let_type",
+    "missing_while_let_type",
+    "missing_for_loop_type",
+];
+
+/// FNV-1a offset basis and prime, used for stable fingerprints.
+const FNV_OFFSET_BASIS: u64 = 0xcbf2_9ce4_8422_2325;
+const FNV_PRIME: u64 = 0x0000_0100_0000_01b3;
+
+/// Diagnostic level of a finding, as reported by the compiler or as
+/// relabelled by the policy.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Level {
+    Note,
+    Warning,
+    Error,
+}
+
+impl Level {
+    /// Returns the label used by rustc for this level.
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::Note => "note",
+            Self::Warning => "warning",
+            Self::Error => "error",
+        }
+    }
+}
+
+/// A single lint finding, extracted from a `compiler-message` emitted by
+/// `cargo --message-format=json`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Finding {
+    /// The lint name, e.g. `security_panic_usage`.
+    pub lint: String,
+    /// The package the finding belongs to, as `name@version`.
+    pub package: String,
+    pub level: Level,
+    pub message: String,
+    /// The path of the file containing the primary span, relative to the
+    /// workspace root.
+    pub file: String,
+    pub line: usize,
+    pub column: usize,
+    /// The diagnostic as rendered by the compiler, including notes and
+    /// help.
+    pub rendered: String,
+    /// The messages of the notes attached to the diagnostic.
+    pub notes: Vec<String>,
+    /// The machine-applicable suggestions of the diagnostic.
+    pub suggestions: Vec<Suggestion>,
+    /// The diagnostic as emitted by rustc, in its JSON format.
+    pub diagnostic: Value,
+}
+
+/// A replacement suggested by a lint that rustc marks as machine-applicable,
+/// i.e. that can be applied without review.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+pub struct Suggestion {
+    /// The path of the file to edit, relative to the workspace root.
+    pub file: String,
+    /// The byte offset of the start of the replaced text.
+    pub byte_start: usize,
+    /// The byte offset of the end of the replaced text.
+    pub byte_end: usize,
+    /// The replaced text when the lints ran, or `None` if the compiler did
+    /// not report it.
+    pub original: Option<String>,
+    pub replacement: String,
+}
+
+/// Outcome of parsing the compiler output of a run.
+#[derive(Debug, Default)]
+pub struct Messages {
+    /// Findings of the security lints, deduplicated.
+    pub findings: Vec<Finding>,
+    /// Rendered compiler errors unrelated to the security lints (e.g. type
+    /// errors), which prevent the lints from running.
+    pub errors: Vec<String>,
+    /// The locations (file, line and column) of the lint names of the
+    /// `#[expect(...)]` attributes reported as unfulfilled by rustc.
+    pub unfulfilled_expectations: Vec<(String, usize, usize)>,
+    /// The other compiler diagnostics with a location (errors, rustc and
+    /// Clippy warnings), in the JSON format of rustc, deduplicated.
+    pub diagnostics: Vec<Value>,
+}
+
+#[derive(Deserialize)]
+struct CargoMessage {
+    reason: String,
+    package_id: Option<String>,
+    message: Option<Diagnostic>,
+}
+
+#[derive(Deserialize)]
+struct Diagnostic {
+    message: String,
+    code: Option<DiagnosticCode>,
+    level: String,
+    spans: Vec<DiagnosticSpan>,
+    #[serde(default)]
+    children: Vec<Diagnostic>,
+    rendered: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct DiagnosticCode {
+    code: String,
+}
+
+#[derive(Deserialize)]
+struct DiagnosticSpan {
+    file_name: String,
+    #[serde(default)]
+    byte_start: usize,
+    #[serde(default)]
+    byte_end: usize,
+    line_start: usize,
+    column_start: usize,
+    is_primary: bool,
+    suggested_replacement: Option<String>,
+    suggestion_applicability: Option<String>,
+    #[serde(default)]
+    text: Vec<DiagnosticSpanLine>,
+}
+
+/// A line of source touched by a span, with the 1-based character columns
+/// of the span on it.
+#[derive(Deserialize)]
+struct DiagnosticSpanLine {
+    text: String,
+    highlight_start: usize,
+    highlight_end: usize,
+}
+
+/// Returns the text covered by `span`, rebuilt from the source lines the
+/// compiler reports with it, or `None` if it reported none.
+///
+/// # Arguments
+/// * `span` (`&DiagnosticSpan`) - The span of a suggestion.
+fn span_text(span: &DiagnosticSpan) -> Option<String> {
+    if span.text.is_empty() {
+        return None;
+    }
+
+    Some(
+        span.text
+            .iter()
+            .map(|line: &DiagnosticSpanLine| {
+                line.text
+                    .chars()
+                    .skip(line.highlight_start.saturating_sub(1))
+                    .take(
+                        line.highlight_end
+                            .saturating_sub(line.highlight_start),
+                    )
+                    .collect::<String>()
+            })
+            .collect::<Vec<String>>()
+            .join("\n"),
+    )
+}
+
+/// Returns the machine-applicable suggestions attached to the children of
+/// `diagnostic`.
+///
+/// # Arguments
+/// * `diagnostic` (`&Diagnostic`) - The diagnostic of a finding.
+fn suggestions(diagnostic: &Diagnostic) -> Vec<Suggestion> {
+    diagnostic
+        .children
+        .iter()
+        .flat_map(|child: &Diagnostic| &child.spans)
+        .filter(|span: &&DiagnosticSpan| {
+            span.suggestion_applicability.as_deref()
+                == Some("MachineApplicable")
+        })
+        .filter_map(|span: &DiagnosticSpan| {
+            Some(Suggestion {
+                file: span.file_name.clone(),
+                byte_start: span.byte_start,
+                byte_end: span.byte_end,
+                original: span_text(span),
+                replacement: span.suggested_replacement.clone()?,
+            })
+        })
+        .collect()
+}
+
+/// Returns each finding paired with its fingerprint, which identifies it
+/// across runs and commits.
+///
+/// The fingerprint is derived from the lint, the file and the message rather
+/// than from the line, which changes whenever code is added above the
+/// finding; identical findings in the same file are told apart by their
+/// order of occurrence.
+///
+/// # Arguments
+/// * `findings` (`&[Finding]`) - The findings of the run.
+pub fn fingerprinted(findings: &[Finding]) -> Vec<(&Finding, String)> {
+    let mut occurrences: HashMap<(&str, &str, &str), usize> = HashMap::new();
+
+    findings
+        .iter()
+        .map(|finding: &Finding| {
+            let occurrence: &mut usize = occurrences
+                .entry((&finding.lint, &finding.file, &finding.message))
+                .or_default();
+            *occurrence += 1;
+
+            (
+                finding,
+                fingerprint(&[
+                    &finding.lint,
+                    &finding.file,
+                    &finding.message,
+                    &occurrence.to_string(),
+                ]),
+            )
+        })
+        .collect()
+}
+
+/// Returns the FNV-1a hash of `parts` as a hexadecimal string. The hash is
+/// stable across runs and toolchains, unlike the standard library hasher.
+///
+/// # Arguments
+/// * `parts` (`&[&str]`) - The values identifying a finding.
+fn fingerprint(parts: &[&str]) -> String {
+    let hash: u64 = parts
+        .iter()
+        .flat_map(|part: &&str| part.bytes().chain(std::iter::once(0)))
+        .fold(FNV_OFFSET_BASIS, |hash: u64, byte: u8| {
+            (hash ^ u64::from(byte)).wrapping_mul(FNV_PRIME)
+        });
+
+    format!("{hash:016x}")
+}
+
+/// Returns `true` if `name` is one of the lints provided by this repository.
+///
+/// # Arguments
+/// * `name` (`&str`) - The lint name reported by the compiler.
+pub fn is_security_lint(name: &str) -> bool {
+    name.starts_with("security_") || UNPREFIXED_LINTS.contains(&name)
+}
+
+/// Returns the `name@version` of the package identified by `package_id`, in
+/// either the package ID specification format of recent cargo versions
+/// (`path+file:///src/demo#demo@0.1.0`, or `...#0.1.0` when the name is the
+/// last path segment) or the legacy `demo 0.1.0 (path+file:///src/demo)`
+/// format.
+///
+/// # Arguments
+/// * `package_id` (`&str`) - The package ID reported by cargo.
+pub fn package_name(package_id: &str) -> String {
+    let Some((url, fragment)): Option<(&str, &str)> =
+        package_id.split_once('#')
+    else {
+        let mut parts: std::str::SplitWhitespace<'_> =
+            package_id.split_whitespace();
+        return match (parts.next(), parts.next()) {
+            (Some(name), Some(version)) => format!("{name}@{version}"),
+            _ => package_id.to_owned(),
+        };
+    };
+
+    if fragment.contains('@') {
+        return fragment.to_owned();
+    }
+
+    let name: &str = url.rsplit('/').next().unwrap_or(url);
+    format!("{name}@{fragment}")
+}
+
+/// Parses the JSON messages printed by `cargo --message-format=json`, one
+/// per line. Lines that are not JSON compiler messages are ignored, and
+/// findings reported twice (e.g. for the library and test targets of a
+/// package) are kept once.
+///
+/// # Arguments
+/// * `output` (`&str`) - The standard output of the cargo invocation.
+pub fn parse_messages(output: &str) -> Messages {
+    let mut messages: Messages = Messages::default();
+
+    for line in output.lines() {
+        let Ok(mut raw): serde_json::Result<Value> =
+            serde_json::from_str(line)
+        else {
+            continue;
+        };
+        let Ok(cargo_message): serde_json::Result<CargoMessage> =
+            CargoMessage::deserialize(&raw)
+        else {
+            continue;
+        };
+        if cargo_message.reason != "compiler-message" {
+            continue;
+        }
+        let Some(diagnostic): Option<Diagnostic> = cargo_message.message
+        else {
+            continue;
+        };
+
+        let lint: Option<String> = diagnostic
+            .code
+            .as_ref()
+            .map(|code: &DiagnosticCode| code.code.clone())
+            .filter(|code: &String| is_security_lint(code));
+        let raw: Value =
+            raw.get_mut("message").map_or(Value::Null, Value::take);
+        if lint.is_none()
+            && !diagnostic.spans.is_empty()
+            && !messages.diagnostics.contains(&raw)
+        {
+            messages.diagnostics.push(raw.clone());
+        }
+
+        if diagnostic.code.as_ref().is_some_and(|code: &DiagnosticCode| {
+            code.code == "unfulfilled_lint_expectations"
+        }) {
+            messages.unfulfilled_expectations.extend(
+                diagnostic
+                    .spans
+                    .iter()
+                    .filter(|span: &&DiagnosticSpan| span.is_primary)
+                    .map(|span: &DiagnosticSpan| {
+                        (
+                            span.file_name.clone(),
+                            span.line_start,
+                            span.column_start,
+                        )
+                    }),
+            );
+            continue;
+        }
+
+        let Some(lint): Option<String> = lint else {
+            // Errors without a lint code (or with a rustc error code) are
+            // compilation failures, except for the final "aborting" summary
+            // printed when deny-level lints fire.
+            let is_compile_error: bool = diagnostic.level == "error"
+                && !diagnostic.message.starts_with("aborting due to")
+                && diagnostic.code.as_ref().is_none_or(
+                    |code: &DiagnosticCode| code.code.starts_with('E'),
+                );
+            if is_compile_error {
+                messages
+                    .errors
+                    .push(diagnostic.rendered.unwrap_or(diagnostic.message));
+            }
+            continue;
+        };
+
+        let Some(span): Option<&DiagnosticSpan> = diagnostic
+            .spans
+            .iter()
+            .find(|span: &&DiagnosticSpan| span.is_primary)
+        else {
+            continue;
+        };
+
+        let suggestions: Vec<Suggestion> = suggestions(&diagnostic);
+        let notes: Vec<String> = diagnostic
+            .children
+            .iter()
+            .filter(|child: &&Diagnostic| child.level == "note")
+            .map(|child: &Diagnostic| child.message.clone())
+            .collect();
+        let finding: Finding = Finding {
+            lint,
+            package: cargo_message
+                .package_id
+                .as_deref()
+                .map(package_name)
+                .unwrap_or_default(),
+            level: match diagnostic.level.as_str() {
+                "error" => Level::Error,
+                _ => Level::Warning,
+            },
+            message: diagnostic.message,
+            file: span.file_name.clone(),
+            line: span.line_start,
+            column: span.column_start,
+            rendered: diagnostic.rendered.unwrap_or_default(),
+            notes,
+            suggestions,
+            diagnostic: raw,
+        };
+
+        if !messages.findings.contains(&finding) {
+            messages.findings.push(finding);
+        }
+    }
+
+    messages
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        DiagnosticSpan,
+        Level,
+        Messages,
+        Suggestion,
+        UNPREFIXED_LINTS,
+        package_name,
+        parse_messages,
+        span_text,
+    };
+
+    const OUTPUT: &str = r#"{"reason":"compiler-artifact","package_id":"demo"}
+{"reason":"compiler-message","package_id":"path+file:///src/demo#0.1.0","message":{"message":"Usage of unsafe block detected.","code":{"code":"security_unsafe_usage","explanation":null},"level":"error","spans":[{"file_name":"src/main.rs","line_start":3,"column_start":5,"is_primary":true}],"rendered":"error: Usage of unsafe block detected."}}
+{"reason":"compiler-message","package_id":"path+file:///src/demo#0.1.0","message":{"message":"Usage of unsafe block detected.","code":{"code":"security_unsafe_usage","explanation":null},"level":"error","spans":[{"file_name":"src/main.rs","line_start":3,"column_start":5,"is_primary":true}],"rendered":"error: Usage of unsafe block detected."}}
+{"reason":"compiler-message","message":{"message":"unused variable: `x`","code":{"code":"unused_variables","explanation":null},"level":"warning","spans":[{"file_name":"src/main.rs","line_start":2,"column_start":9,"is_primary":true}],"rendered":"warning: unused variable"}}
+{"reason":"compiler-message","message":{"message":"mismatched types","code":{"code":"E0308","explanation":null},"level":"error","spans":[],"rendered":"error[E0308]: mismatched types"}}
+Building library
+{"reason":"compiler-message","message":{"message":"aborting due to 1 previous error","code":null,"level":"error","spans":[],"rendered":"error: aborting due to 1 previous error"}}
+{"reason":"build-finished","success":false}"#;
+
+    #[test]
+    fn parse() {
+        let messages: Messages = parse_messages(OUTPUT);
+
+        assert_eq!(messages.findings.len(), 1);
+        assert_eq!(messages.findings[0].lint, "security_unsafe_usage");
+        assert_eq!(messages.findings[0].package, "demo@0.1.0");
+        assert_eq!(messages.findings[0].level, Level::Error);
+        assert_eq!(messages.findings[0].file, "src/main.rs");
+        assert_eq!(messages.findings[0].line, 3);
+        assert_eq!(messages.errors, ["error[E0308]: mismatched types"]);
+        assert_eq!(messages.diagnostics.len(), 1);
+        assert_eq!(messages.diagnostics[0]["message"], "unused variable: `x`");
+    }
+
+    /// Returns a `compiler-message` line reporting a finding of `lint`.
+    ///
+    /// # Arguments
+    /// * `lint` (`&str`) - The lint name.
+    fn message(lint: &str) -> String {
+        format!(
+            r#"{{"reason":"compiler-message","package_id":"path+file:///src/demo#0.1.0","message":{{"message":"Missing explicit type annotation.","code":{{"code":"{lint}","explanation":null}},"level":"warning","spans":[{{"file_name":"src/main.rs","line_start":2,"column_start":9,"is_primary":true}}],"rendered":"warning: Missing explicit type annotation."}}}}"#
+        )
+    }
+
+    #[test]
+    fn unprefixed() {
+        for lint in [
+            "missing_target_type",
+            "missing_let_else_type",
+            "missing_if_let_type",
+            "missing_while_let_type",
+            "missing_for_loop_type",
+        ] {
+            let messages: Messages = parse_messages(&message(lint));
+
+            assert_eq!(messages.findings.len(), 1, "{lint}");
+            assert_eq!(messages.findings[0].lint, lint);
+        }
+    }
+
+    #[test]
+    fn declared_unprefixed() {
+        let source: &str = include_str!("../../rules/missing_type/src/lib.rs");
+        let declared: Vec<String> = source
+            .split("declare_lint! {")
+            .skip(1)
+            .filter_map(|declaration: &str| {
+                declaration
+                    .trim_start()
+                    .strip_prefix("pub ")?
+                    .split(',')
+                    .next()
+            })
+            .map(str::to_lowercase)
+            .collect();
+
+        assert_eq!(declared.len(), UNPREFIXED_LINTS.len());
+        for lint in &declared {
+            assert!(UNPREFIXED_LINTS.contains(&lint.as_str()), "{lint}");
+        }
+    }
+
+    #[test]
+    fn suggestions() {
+        let messages: Messages = parse_messages(
+            r#"{"reason":"compiler-message","package_id":"demo 0.1.0 (path+file:///src/demo)","message":{"message":"Missing explicit type annotation on let binding.","code":{"code":"missing_let_type","explanation":null},"level":"warning","spans":[{"file_name":"src/main.rs","byte_start":20,"byte_end":21,"line_start":2,"column_start":9,"is_primary":true,"suggested_replacement":null,"suggestion_applicability":null}],"children":[{"message":"add the inferred type","code":null,"level":"help","spans":[{"file_name":"src/main.rs","byte_start":21,"byte_end":21,"line_start":2,"column_start":10,"is_primary":true,"suggested_replacement":": i32","suggestion_applicability":"MachineApplicable","text":[{"text":"    let x = 5;","highlight_start":10,"highlight_end":10}]},{"file_name":"src/main.rs","byte_start":30,"byte_end":31,"line_start":3,"column_start":9,"is_primary":true,"suggested_replacement":"_","suggestion_applicability":"MaybeIncorrect"}],"children":[],"rendered":null}],"rendered":"warning: Missing explicit type annotation on let binding."}}"#,
+        );
+
+        assert_eq!(
+            messages.findings[0].suggestions,
+            [Suggestion {
+                file: String::from("src/main.rs"),
+                byte_start: 21,
+                byte_end: 21,
+                original: Some(String::new()),
+                replacement: String::from(": i32"),
+            }]
+        );
+    }
+
+    #[test]
+    fn span_texts() {
+        let span: DiagnosticSpan = serde_json::from_str(
+            r#"{"file_name":"src/main.rs","line_start":2,"column_start":13,"is_primary":true,"suggested_replacement":"5","suggestion_applicability":"MachineApplicable","text":[{"text":"    let é = (","highlight_start":13,"highlight_end":14},{"text":"        4 + 1","highlight_start":1,"highlight_end":14},{"text":"    );","highlight_start":1,"highlight_end":6}]}"#,
+        )
+        .unwrap();
+        assert_eq!(span_text(&span).unwrap(), "(\n        4 + 1\n    )");
+
+        let span: DiagnosticSpan = serde_json::from_str(
+            r#"{"file_name":"src/main.rs","line_start":2,"column_start":9,"is_primary":true,"suggested_replacement":null,"suggestion_applicability":null}"#,
+        )
+        .unwrap();
+        assert!(span_text(&span).is_none());
+    }
+
+    #[test]
+    fn package_names() {
+        assert_eq!(
+            package_name(
+                "registry+https://github.com/rust-lang/crates.io-index#\
+                 serde@1.0.219"
+            ),
+            "serde@1.0.219"
+        );
+        assert_eq!(package_name("path+file:///src/demo#0.1.0"), "demo@0.1.0");
+        assert_eq!(
+            package_name("demo 0.1.0 (path+file:///src/demo)"),
+            "demo@0.1.0"
+        );
+    }
+}
@@ -0,0 +1,423 @@
+use std::{
+    collections::BTreeMap,
+    fs,
+    io::Write,
+    path::{Path, PathBuf},
+};
+
+use anyhow::{Context, Result};
+
+use crate::finding::{Finding, Suggestion};
+
+/// The lines of a file touched by one or more suggestions.
+#[derive(Debug, PartialEq, Eq)]
+struct Hunk {
+    /// The index of the first line, starting at 0.
+    line: usize,
+    /// The byte offset of the start of the first line.
+    start: usize,
+    /// The byte offset of the end of the last line, after its newline.
+    end: usize,
+    /// The lines with the suggestions applied.
+    replacement: String,
+}
+
+/// The suggestions applied to the content of a file.
+#[derive(Debug)]
+pub struct Patch<'a> {
+    file: &'a str,
+    original: String,
+    hunks: Vec<Hunk>,
+}
+
+/// Groups the machine-applicable suggestions of `findings` by file, sorted by
+/// offset. Suggestions reported by several findings are kept once,
+/// suggestions overlapping an earlier one are dropped, and files outside the
+/// workspace (whose paths are absolute, e.g. code expanded from a
+/// dependency) are skipped.
+///
+/// # Arguments
+/// * `findings` (`&[Finding]`) - The findings of the run.
+pub fn group(findings: &[Finding]) -> BTreeMap<&str, Vec<&Suggestion>> {
+    let mut files: BTreeMap<&str, Vec<&Suggestion>> = BTreeMap::new();
+
+    for suggestion in findings
+        .iter()
+        .flat_map(|finding: &Finding| &finding.suggestions)
+        .filter(|suggestion: &&Suggestion| {
+            Path::new(&suggestion.file).is_relative()
+        })
+    {
+        files.entry(&suggestion.file).or_default().push(suggestion);
+    }
+
+    for suggestions in files.values_mut() {
+        suggestions.sort();
+        suggestions.dedup();
+
+        let mut end: Option<usize> = None;
+        suggestions.retain(|suggestion: &&Suggestion| {
+            let disjoint: bool =
+                end.is_none_or(|end: usize| suggestion.byte_start >= end);
+            if disjoint {
+                end = Some(suggestion.byte_end);
+            }
+            disjoint
+        });
+    }
+
+    files
+}
+
+/// Returns the byte offsets of the start of each line of `source`.
+///
+/// # Arguments
+/// * `source` (`&str`) - The content of a file.
+fn line_starts(source: &str) -> Vec<usize> {
+    std::iter::once(0)
+        .chain(
+            source
+                .match_indices('\n')
+                .map(|(offset, _): (usize, &str)| offset.saturating_add(1))
+                .filter(|offset: &usize| *offset < source.len()),
+        )
+        .collect()
+}
+
+/// Returns the index of the line containing the byte at `offset`.
+///
+/// # Arguments
+/// * `starts` (`&[usize]`) - The offsets of the start of each line.
+/// * `offset` (`usize`) - The byte offset.
+fn line_index(starts: &[usize], offset: usize) -> usize {
+    starts.partition_point(|start: &usize| *start <= offset).saturating_sub(1)
+}
+
+/// Applies `suggestions`, sorted and disjoint, to the text of `source`
+/// between `start` and `end`. Returns `None` if a suggestion does not fall
+/// on character boundaries.
+///
+/// # Arguments
+/// * `source` (`&str`) - The content of the file.
+/// * `start` (`usize`) - The byte offset of the start of the text.
+/// * `end` (`usize`) - The byte offset of the end of the text.
+/// * `suggestions` (`&[&Suggestion]`) - The suggestions to apply.
+fn replace(
+    source: &str,
+    start: usize,
+    end: usize,
+    suggestions: &[&Suggestion],
+) -> Option<String> {
+    let mut replaced: String = String::new();
+    let mut cursor: usize = start;
+
+    for suggestion in suggestions {
+        replaced.push_str(source.get(cursor..suggestion.byte_start)?);
+        replaced.push_str(&suggestion.replacement);
+        cursor = suggestion.byte_end;
+    }
+    replaced.push_str(source.get(cursor..end)?);
+
+    Some(replaced)
+}
+
+/// Formats the range of a unified diff hunk of `count` lines starting at
+/// the line index `line`. Empty ranges refer to the preceding line.
+///
+/// # Arguments
+/// * `line` (`usize`) - The index of the first line.
+/// * `count` (`usize`) - The number of lines.
+fn hunk_range(line: usize, count: usize) -> String {
+    match count {
+        0 => format!("{line},0"),
+        _ => format!("{},{count}", line.saturating_add(1)),
+    }
+}
+
+impl<'a> Patch<'a> {
+    /// Builds the patch applying `suggestions`, sorted and disjoint, to
+    /// `original`. Suggestions on the same or adjacent lines share a hunk.
+    /// Returns `None` if the file changed since the lints ran, i.e. if the
+    /// text a suggestion replaces is not the text reported by the compiler
+    /// or does not fall on character boundaries of the file.
+    ///
+    /// # Arguments
+    /// * `file` (`&'a str`) - The path of the file.
+    /// * `original` (`String`) - The content of the file.
+    /// * `suggestions` (`&[&Suggestion]`) - The suggestions to apply.
+    pub fn new(
+        file: &'a str,
+        original: String,
+        suggestions: &[&Suggestion],
+    ) -> Option<Self> {
+        for suggestion in suggestions {
+            let current: &str =
+                original.get(suggestion.byte_start..suggestion.byte_end)?;
+            if suggestion
+                .original
+                .as_deref()
+                .is_some_and(|expected: &str| expected != current)
+            {
+                return None;
+            }
+        }
+
+        let starts: Vec<usize> = line_starts(&original);
+        // The lines touched by each group of suggestions, and the position
+        // of the first suggestion of the group.
+        let mut groups: Vec<(usize, usize, usize)> = Vec::new();
+
+        for (position, suggestion) in suggestions.iter().enumerate() {
+            let first: usize = line_index(&starts, suggestion.byte_start);
+            let last: usize =
+                line_index(&starts, suggestion.byte_end.saturating_sub(1))
+                    .max(first);
+
+            match groups.last_mut() {
+                Some((_, end, _)) if first <= end.saturating_add(1) => {
+                    *end = last.max(*end);
+                },
+                _ => groups.push((first, last, position)),
+            }
+        }
+
+        let mut hunks: Vec<Hunk> = Vec::new();
+        for (index, (first, last, position)) in groups.iter().enumerate() {
+            let next: usize = groups
+                .get(index.saturating_add(1))
+                .map_or(suggestions.len(), |group: &(usize, usize, usize)| {
+                    group.2
+                });
+            let start: usize = starts.get(*first).copied()?;
+            let end: usize = starts
+                .get(last.saturating_add(1))
+                .copied()
+                .unwrap_or(original.len());
+
+            hunks.push(Hunk {
+                line: *first,
+                start,
+                end,
+                replacement: replace(
+                    &original,
+                    start,
+                    end,
+                    suggestions.get(*position..next)?,
+                )?,
+            });
+        }
+
+        Some(Self { file, original, hunks })
+    }
+
+    /// Returns the content of the file with the suggestions applied.
+    pub fn fixed(&self) -> String {
+        let mut fixed: String = String::new();
+        let mut cursor: usize = 0;
+
+        for hunk in &self.hunks {
+            fixed
+                .push_str(self.original.get(cursor..hunk.start).unwrap_or(""));
+            fixed.push_str(&hunk.replacement);
+            cursor = hunk.end;
+        }
+        fixed.push_str(self.original.get(cursor..).unwrap_or(""));
+
+        fixed
+    }
+
+    /// Writes the patch as a unified diff without context lines.
+    ///
+    /// # Arguments
+    /// * `out` (`&mut dyn Write`) - The destination of the diff.
+    pub fn write_diff(&self, out: &mut dyn Write) -> std::io::Result<()> {
+        writeln!(out, "--- a/{}", self.file)?;
+        writeln!(out, "+++ b/{}", self.file)?;
+
+        let mut shift: isize = 0;
+        for hunk in &self.hunks {
+            let removed: Vec<&str> = self
+                .original
+                .get(hunk.start..hunk.end)
+                .unwrap_or("")
+                .lines()
+                .collect();
+            let added: Vec<&str> = hunk.replacement.lines().collect();
+            let line: usize = hunk.line.saturating_add_signed(shift);
+
+            writeln!(
+                out,
+                "@@ -{} +{} @@",
+                hunk_range(hunk.line, removed.len()),
+                hunk_range(line, added.len()),
+            )?;
+            for removed_line in &removed {
+                writeln!(out, "-{removed_line}")?;
+            }
+            for added_line in &added {
+                writeln!(out, "+{added_line}")?;
+            }
+
+            shift = shift
+                .saturating_add_unsigned(added.len())
+                .saturating_sub_unsigned(removed.len());
+        }
+
+        Ok(())
+    }
+}
+
+/// Applies the machine-applicable suggestions of `findings` to the files of
+/// the workspace, or writes them to `out` as a unified diff if `dry_run` is
+/// set, and returns the number of suggestions applied. The paths of the
+/// suggestions, relative to the workspace root as reported by rustc, are
+/// resolved against `workspace_root`, whatever the current directory. Files
+/// whose content no longer matches the suggestions are reported and left
+/// untouched.
+///
+/// # Arguments
+/// * `findings` (`&[Finding]`) - The findings of the run.
+/// * `workspace_root` (`&Path`) - The root of the linted workspace.
+/// * `dry_run` (`bool`) - Whether to print the diff instead of editing the
+///   files.
+/// * `out` (`&mut dyn Write`) - The destination of the diff.
+pub fn apply(
+    findings: &[Finding],
+    workspace_root: &Path,
+    dry_run: bool,
+    out: &mut dyn Write,
+) -> Result<usize> {
+    let mut applied: usize = 0;
+
+    for (file, suggestions) in group(findings) {
+        let path: PathBuf = workspace_root.join(file);
+        let original: String = fs::read_to_string(&path)
+            .with_context(|| format!("could not read `{}`", path.display()))?;
+        let Some(patch): Option<Patch<'_>> =
+            Patch::new(file, original, &suggestions)
+        else {
+            eprintln!(
+                "warning: skipped `{file}`, which changed since the lints ran"
+            );
+            continue;
+        };
+
+        if dry_run {
+            patch.write_diff(out)?;
+        } else {
+            fs::write(&path, patch.fixed()).with_context(|| {
+                format!("could not write `{}`", path.display())
+            })?;
+        }
+        applied = applied.saturating_add(suggestions.len());
+    }
+
+    Ok(applied)
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::Value;
+
+    use super::{Patch, group};
+    use crate::finding::{Finding, Level, Suggestion};
+
+    const SOURCE: &str =
+        "fn main() {\n    let x = 5;\n    let y = x;\n\n    let z = 1;\n}\n";
+
+    fn suggestion(file: &str, offset: usize, replacement: &str) -> Suggestion {
+        Suggestion {
+            file: file.to_owned(),
+            byte_start: offset,
+            byte_end: offset,
+            original: None,
+            replacement: replacement.to_owned(),
+        }
+    }
+
+    fn finding(suggestions: Vec<Suggestion>) -> Finding {
+        Finding {
+            lint: String::from("missing_let_type"),
+            package: String::from("demo@0.1.0"),
+            level: Level::Warning,
+            message: String::from("Missing explicit type annotation."),
+            file: String::from("src/main.rs"),
+            line: 2,
+            column: 9,
+            rendered: String::new(),
+            notes: Vec::new(),
+            suggestions,
+            diagnostic: Value::Null,
+        }
+    }
+
+    #[test]
+    fn groups() {
+        let findings: Vec<Finding> = vec![
+            finding(vec![suggestion("src/main.rs", 21, ": i32")]),
+            finding(vec![
+                suggestion("src/main.rs", 21, ": i32"),
+                suggestion("/registry/dep/src/lib.rs", 3, ": u8"),
+            ]),
+            finding(vec![Suggestion {
+                byte_end: 23,
+                ..suggestion("src/main.rs", 20, "_")
+            }]),
+        ];
+
+        let files = group(&findings);
+        assert_eq!(files.len(), 1);
+        assert_eq!(files["src/main.rs"].len(), 1);
+        assert_eq!(files["src/main.rs"][0].byte_start, 20);
+    }
+
+    #[test]
+    fn patch() {
+        let first: Suggestion = suggestion("src/main.rs", 21, ": i32");
+        let second: Suggestion = suggestion("src/main.rs", 36, ": i32");
+        let third: Suggestion = suggestion("src/main.rs", 52, ": i32");
+        let patch: Patch<'_> = Patch::new(
+            "src/main.rs",
+            SOURCE.to_owned(),
+            &[&first, &second, &third],
+        )
+        .unwrap();
+
+        assert_eq!(
+            patch.fixed(),
+            "fn main() {\n    let x: i32 = 5;\n    let y: i32 = x;\n\n    let z: i32 = 1;\n}\n"
+        );
+
+        let mut diff: Vec<u8> = Vec::new();
+        patch.write_diff(&mut diff).unwrap();
+        assert_eq!(
+            String::from_utf8(diff).unwrap(),
+            "--- a/src/main.rs\n+++ b/src/main.rs\n\
+             @@ -2,2 +2,2 @@\n-    let x = 5;\n-    let y = x;\n\
+             +    let x: i32 = 5;\n+    let y: i32 = x;\n\
+             @@ -5,1 +5,1 @@\n-    let z = 1;\n+    let z: i32 = 1;\n"
+        );
+
+        let outside: Suggestion = suggestion("src/main.rs", 100, "_");
+        assert!(
+            Patch::new("src/main.rs", SOURCE.to_owned(), &[&outside])
+                .is_none()
+        );
+
+        let expected: Suggestion = Suggestion {
+            byte_end: 21,
+            original: Some(String::from("x")),
+            ..suggestion("src/main.rs", 20, "_x")
+        };
+        assert!(
+            Patch::new("src/main.rs", SOURCE.to_owned(), &[&expected])
+                .is_some()
+        );
+        let changed: Suggestion =
+            Suggestion { original: Some(String::from("y")), ..expected };
+        assert!(
+            Patch::new("src/main.rs", SOURCE.to_owned(), &[&changed])
+                .is_none()
+        );
+    }
+}
@@ -0,0 +1,302 @@
+//! `cargo security-lints` runs every lint library of this repository over a
+//! workspace and applies the `[policy]` table of its `dylint.toml` to the
+//! findings. The exit code follows the severity gates and thresholds of the
+//! policy, independently of the deny/warn levels used by rustc. With `--fix`,
+//! the machine-applicable suggestions of the lints are applied instead.
+
+mod config;
+mod dependencies;
+mod diff;
+mod expectations;
+mod finding;
+mod fix;
+mod metrics;
+mod options;
+mod output;
+mod policy;
+mod profile;
+mod registry;
+mod runner;
+mod suppressions;
+mod trends;
+
+use std::{
+    env,
+    fs,
+    io::{self, Read},
+    path::{Path, PathBuf},
+    process::ExitCode,
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+use anyhow::{Context, Result};
+
+use crate::{
+    config::Policy,
+    finding::Messages,
+    options::{Options, USAGE},
+    policy::Evaluation,
+    profile::Profile,
+    registry::Library,
+    runner::Workspace,
+};
+
+/// Exit code of a run whose findings fail the policy.
+const EXIT_POLICY_FAILURE: u8 = 1;
+/// Exit code of a run that could not complete (invalid arguments or
+/// configuration, compilation errors).
+const EXIT_ERROR: u8 = 2;
+
+fn main() -> ExitCode {
+    match run() {
+        Ok(code) => code,
+        Err(error) => {
+            eprintln!("error: {error:#}");
+            ExitCode::from(EXIT_ERROR)
+        },
+    }
+}
+
+/// Runs the lints (or reads their captured output), writes the report and
+/// returns the exit code mandated by the policy.
+fn run() -> Result<ExitCode> {
+    let options: Options = options::parse(env::args().skip(1))?;
+    if options.help {
+        println!("{USAGE}");
+        return Ok(ExitCode::SUCCESS);
+    }
+
+    if let Some(path) = &options.trends {
+        let runs: Vec<trends::Run> = trends::load(&trends::open(path)?)?;
+        trends::write(&mut io::stdout().lock(), &trends::trends(&runs))?;
+
+        return Ok(ExitCode::SUCCESS);
+    }
+
+    let mut policy: Policy = config::load(&options.config)?;
+    if let Some(fail_on) = options.fail_on {
+        policy.fail_on = fail_on;
+    }
+
+    let profile: Option<Profile> = options.profile.or(policy.profile);
+    let mut workspace: Option<Workspace> = None;
+    let mut rustc_flags: Vec<String> = run_flags(&options, profile, &[]);
+
+    let output: String = match &options.input {
+        Some(path) if path.as_os_str() == "-" => {
+            let mut output: String = String::new();
+            io::stdin().read_to_string(&mut output)?;
+            output
+        },
+        Some(path) => fs::read_to_string(path)
+            .with_context(|| format!("could not read `{}`", path.display()))?,
+        None => {
+            let registry: PathBuf = registry::directory(
+                &workspace
+                    .insert(runner::workspace(runner::manifest_path(
+                        &options.cargo_args,
+                    ))?)
+                    .target_directory,
+            );
+            rustc_flags =
+                run_flags(&options, profile, &registry::load(&registry)?);
+            let output: String = lint(&options, &registry, &rustc_flags)?;
+
+            // A library registering itself for the first time, e.g. in an
+            // empty target directory, ran without the levels it sets in the
+            // profile.
+            let registered: Vec<String> =
+                run_flags(&options, profile, &registry::load(&registry)?);
+            if registered == rustc_flags {
+                output
+            } else {
+                eprintln!(
+                    "security-lints: running the lints again with the profile \
+                     levels of the newly registered libraries"
+                );
+                rustc_flags = registered;
+                lint(&options, &registry, &rustc_flags)?
+            }
+        },
+    };
+
+    let mut messages: Messages = finding::parse_messages(&output);
+    if let Some(reference) = &options.diff {
+        let ignored: usize = diff::retain_changed(
+            &mut messages.findings,
+            &diff::changed_lines(reference)?,
+        );
+        eprintln!(
+            "security-lints: ignored {ignored} finding(s) outside the lines \
+             changed since `{reference}`"
+        );
+    }
+
+    if options.fix {
+        for error in &messages.errors {
+            eprintln!("{}", error.trim_end());
+        }
+        let workspace: Workspace = match workspace {
+            Some(workspace) => workspace,
+            None => {
+                runner::workspace(runner::manifest_path(&options.cargo_args))?
+            },
+        };
+        let applied: usize = fix::apply(
+            &messages.findings,
+            &workspace.workspace_root,
+            options.dry_run,
+            &mut io::stdout().lock(),
+        )?;
+        if !options.dry_run {
+            eprintln!("security-lints: applied {applied} suggestion(s)");
+        }
+
+        return Ok(if messages.errors.is_empty() {
+            ExitCode::SUCCESS
+        } else {
+            ExitCode::from(EXIT_ERROR)
+        });
+    }
+
+    if options.metrics {
+        for error in &messages.errors {
+            eprintln!("{}", error.trim_end());
+        }
+        metrics::write(
+            &mut io::stdout().lock(),
+            &metrics::collect(&messages.findings),
+        )?;
+
+        return Ok(if messages.errors.is_empty() {
+            ExitCode::SUCCESS
+        } else {
+            ExitCode::from(EXIT_ERROR)
+        });
+    }
+
+    if options.expectations {
+        for error in &messages.errors {
+            eprintln!("{}", error.trim_end());
+        }
+        let report: expectations::Report = expectations::collect(&messages);
+        expectations::write(&mut io::stdout().lock(), &report)?;
+
+        return Ok(if !messages.errors.is_empty() {
+            ExitCode::from(EXIT_ERROR)
+        } else if report.unfulfilled > 0 {
+            ExitCode::from(EXIT_POLICY_FAILURE)
+        } else {
+            ExitCode::SUCCESS
+        });
+    }
+
+    if options.suppressions {
+        for error in &messages.errors {
+            eprintln!("{}", error.trim_end());
+        }
+        suppressions::write(
+            &mut io::stdout().lock(),
+            &suppressions::collect(&messages.findings, &rustc_flags),
+        )?;
+
+        return Ok(if messages.errors.is_empty() {
+            ExitCode::SUCCESS
+        } else {
+            ExitCode::from(EXIT_ERROR)
+        });
+    }
+
+    let evaluation: Evaluation = policy::evaluate(&messages.findings, &policy);
+    // A run that did not compile misses findings, which would count as fixed.
+    if let Some(path) = &options.record
+        && messages.errors.is_empty()
+    {
+        let timestamp: u64 = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .context("the system clock is before the Unix epoch")?
+            .as_secs();
+        trends::record(
+            &mut trends::open(path)?,
+            timestamp,
+            &messages.findings,
+        )?;
+    }
+
+    output::write_report(
+        &mut io::stdout().lock(),
+        options.format,
+        &messages,
+        &evaluation,
+    )?;
+
+    if !messages.errors.is_empty() {
+        return Ok(ExitCode::from(EXIT_ERROR));
+    }
+    if evaluation.failed() {
+        return Ok(ExitCode::from(EXIT_POLICY_FAILURE));
+    }
+
+    Ok(ExitCode::SUCCESS)
+}
+
+/// Returns the rustc flags of the run: the levels set by `profile`,
+/// including those of the registered `libraries`, and the informational
+/// lints enabled by `options`.
+///
+/// # Arguments
+/// * `options` (`&Options`) - The options of the run.
+/// * `profile` (`Option<Profile>`) - The profile of the run, if any.
+/// * `libraries` (`&[Library]`) - The registered lint libraries.
+fn run_flags(
+    options: &Options,
+    profile: Option<Profile>,
+    libraries: &[Library],
+) -> Vec<String> {
+    let mut rustc_flags: Vec<String> = profile
+        .map(|profile: Profile| profile.rustc_flags(libraries))
+        .unwrap_or_default();
+    if options.expectations {
+        rustc_flags.extend([
+            String::from("-W"),
+            String::from(expectations::EXPECTATION_LINT),
+        ]);
+    }
+    if options.suppressions {
+        rustc_flags.extend([
+            String::from("-W"),
+            String::from(suppressions::SUPPRESSION_LINT),
+        ]);
+    }
+
+    rustc_flags
+}
+
+/// Runs the lints over the workspace, or over its dependencies with
+/// `--dependencies`, and returns the compiler output.
+///
+/// # Arguments
+/// * `options` (`&Options`) - The options of the run.
+/// * `registry` (`&Path`) - The directory the lint libraries register
+///   themselves in.
+/// * `rustc_flags` (`&[String]`) - Extra flags passed to rustc.
+fn lint(
+    options: &Options,
+    registry: &Path,
+    rustc_flags: &[String],
+) -> Result<String> {
+    match options.dependencies {
+        Some(scope) => dependencies::scan(
+            scope,
+            registry,
+            &options.cargo_args,
+            rustc_flags,
+        ),
+        None => runner::run_dylint(
+            None,
+            registry,
+            &options.cargo_args,
+            rustc_flags,
+        ),
+    }
+}
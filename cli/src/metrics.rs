@@ -0,0 +1,205 @@
+use std::{
+    collections::BTreeMap,
+    io::{self, Write},
+};
+
+use serde::Serialize;
+
+use crate::finding::Finding;
+
+/// Kind of site counted by the metrics mode.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Site {
+    UnsafeBlock,
+    UnsafeFunction,
+    UnsafeTrait,
+    UnsafeImpl,
+    Panic,
+    Indexing,
+}
+
+/// Number of sites of each kind, for a package or for the whole run.
+#[derive(Debug, Default, Clone, PartialEq, Eq, Serialize)]
+pub struct Counts {
+    pub unsafe_blocks: usize,
+    pub unsafe_functions: usize,
+    pub unsafe_traits: usize,
+    pub unsafe_impls: usize,
+    pub panic_sites: usize,
+    pub indexing_sites: usize,
+}
+
+impl Counts {
+    /// Increments the counter of `site`.
+    ///
+    /// # Arguments
+    /// * `site` (`Site`) - The kind of site found.
+    fn add(&mut self, site: Site) {
+        let counter: &mut usize = match site {
+            Site::UnsafeBlock => &mut self.unsafe_blocks,
+            Site::UnsafeFunction => &mut self.unsafe_functions,
+            Site::UnsafeTrait => &mut self.unsafe_traits,
+            Site::UnsafeImpl => &mut self.unsafe_impls,
+            Site::Panic => &mut self.panic_sites,
+            Site::Indexing => &mut self.indexing_sites,
+        };
+        *counter += 1;
+    }
+}
+
+/// The metrics of a single package.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct PackageMetrics {
+    /// The package, as `name@version`.
+    pub package: String,
+    #[serde(flatten)]
+    pub counts: Counts,
+}
+
+/// The metrics of a run: one row per package with at least one site, sorted
+/// by package, and their sum.
+#[derive(Debug, Default, Clone, PartialEq, Eq, Serialize)]
+pub struct Metrics {
+    pub packages: Vec<PackageMetrics>,
+    pub total: Counts,
+}
+
+/// Returns the kind of site reported by `finding`, if it is counted by the
+/// metrics. Sites are told apart by the lint and its message, as emitted by
+/// the `unsafe_usage`, `panic_usage` and `indexing_usage` libraries.
+///
+/// # Arguments
+/// * `finding` (`&Finding`) - The finding to classify.
+fn site(finding: &Finding) -> Option<Site> {
+    match finding.lint.as_str() {
+        "security_unsafe_usage" => match finding.message.as_str() {
+            "Usage of unsafe block detected." => Some(Site::UnsafeBlock),
+            "Unsafe function detected." => Some(Site::UnsafeFunction),
+            "Unsafe trait detected." => Some(Site::UnsafeTrait),
+            "Unsafe impl detected." => Some(Site::UnsafeImpl),
+            _ => None,
+        },
+        "security_panic_usage" => Some(Site::Panic),
+        "security_literal_indexing" | "security_map_indexing" => {
+            Some(Site::Indexing)
+        },
+        "security_indexing_usage"
+            if finding.message.ends_with("operation detected.") =>
+        {
+            Some(Site::Indexing)
+        },
+        _ => None,
+    }
+}
+
+/// Counts the unsafe, panic and indexing sites of `findings` per package.
+///
+/// # Arguments
+/// * `findings` (`&[Finding]`) - The findings of the run.
+pub fn collect(findings: &[Finding]) -> Metrics {
+    let mut by_package: BTreeMap<&str, Counts> = BTreeMap::new();
+    let mut total: Counts = Counts::default();
+
+    for finding in findings {
+        let Some(site): Option<Site> = site(finding) else {
+            continue;
+        };
+        by_package.entry(&finding.package).or_default().add(site);
+        total.add(site);
+    }
+
+    Metrics {
+        packages: by_package
+            .into_iter()
+            .map(|(package, counts): (&str, Counts)| PackageMetrics {
+                package: package.to_owned(),
+                counts,
+            })
+            .collect(),
+        total,
+    }
+}
+
+/// Writes `metrics` as pretty-printed JSON.
+///
+/// # Arguments
+/// * `out` (`&mut dyn Write`) - The destination of the metrics.
+/// * `metrics` (`&Metrics`) - The metrics to write.
+pub fn write(out: &mut dyn Write, metrics: &Metrics) -> io::Result<()> {
+    serde_json::to_writer_pretty(&mut *out, metrics)?;
+    writeln!(out)
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::Value;
+
+    use super::{Counts, Metrics, collect};
+    use crate::finding::{Finding, Level};
+
+    fn finding(package: &str, lint: &str, message: &str) -> Finding {
+        Finding {
+            lint: lint.to_owned(),
+            package: package.to_owned(),
+            level: Level::Error,
+            message: message.to_owned(),
+            file: String::from("src/lib.rs"),
+            line: 1,
+            column: 1,
+            rendered: String::new(),
+            notes: Vec::new(),
+            suggestions: Vec::new(),
+            diagnostic: Value::Null,
+        }
+    }
+
+    #[test]
+    fn counts() {
+        let findings: Vec<Finding> = vec![
+            finding(
+                "demo@0.1.0",
+                "security_unsafe_usage",
+                "Usage of unsafe block detected.",
+            ),
+            finding(
+                "demo@0.1.0",
+                "security_indexing_usage",
+                "Usage of slicing operation detected.",
+            ),
+            finding(
+                "demo@0.1.0",
+                "security_indexing_usage",
+                "Implementation of Index/IndexMut trait detected.",
+            ),
+            finding(
+                "demo@0.1.0",
+                "security_map_indexing",
+                "Usage of map indexing detected.",
+            ),
+            finding(
+                "core@0.2.0",
+                "security_panic_usage",
+                "Call to panic backend `unwrap/expect` detected.",
+            ),
+            finding("core@0.2.0", "security_shift_usage", "Shift detected."),
+        ];
+
+        let metrics: Metrics = collect(&findings);
+
+        assert_eq!(metrics.packages.len(), 2);
+        assert_eq!(metrics.packages[0].package, "core@0.2.0");
+        assert_eq!(
+            metrics.packages[1].counts,
+            Counts {
+                unsafe_blocks: 1,
+                indexing_sites: 2,
+                ..Counts::default()
+            }
+        );
+        assert_eq!(metrics.total.panic_sites, 1);
+        assert_eq!(
+            serde_json::to_value(&metrics.packages[0]).unwrap()["package"],
+            "core@0.2.0"
+        );
+    }
+}
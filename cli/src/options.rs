@@ -0,0 +1,331 @@
+use std::path::PathBuf;
+
+use anyhow::{Context, Result, bail};
+
+use crate::{
+    config::Severity,
+    dependencies::Scope,
+    output::Format,
+    profile::Profile,
+};
+
+/// Usage printed by `--help` and on invalid arguments.
+pub const USAGE: &str = "\
+Runs the security lints and applies the `[policy]` table of `dylint.toml`.
+
+Usage: cargo security-lints [OPTIONS] [-- <CARGO_ARGS>...]
+
+Options:
+  --config <PATH>  Path of the `dylint.toml` file [default: dylint.toml]
+  --input <PATH>   Read `cargo --message-format=json` output from a file
+                   (`-` for standard input) instead of running the lints
+  --fail-on <SEVERITY>
+                   Override the `fail_on` gate of the policy (`low`,
+                   `medium`, `high` or `never`)
+  --format <FORMAT>
+                   Format of the report: `text`, `github` (workflow
+                   commands annotating pull requests), `gitlab` (Code
+                   Quality report), `junit` (JUnit XML) or `json` (rustc
+                   diagnostics, for editors) [default: text]
+  --profile <PROFILE>
+                   Set the lint levels for a class of applications:
+                   `web-service`, `embedded`, `crypto` or `cli`; overrides
+                   the `profile` of the policy
+  --metrics        Print the number of unsafe, panic and indexing sites per
+                   package as JSON instead of applying the policy
+  --expectations   Print the `#[expect(...)]` attributes waiving security
+                   lints, with their reason and whether they are fulfilled,
+                   as JSON instead of applying the policy
+  --suppressions   Print the number of findings reported and suppressed
+                   (by `#[allow]`/`#[expect]` attributes, the `[allow]`
+                   table or the profile) per lint as JSON instead of
+                   applying the policy
+  --fix            Apply the machine-applicable suggestions of the lints
+                   (e.g. inferred type annotations) instead of applying
+                   the policy
+  --dry-run        With `--fix`, print the changes as a unified diff
+                   instead of editing the files
+  --dependencies <SCOPE>
+                   Lint the sources of the `direct` (or `all`) dependencies
+                   of the workspace instead of the workspace itself
+  --diff <REF>     Only keep the findings on lines changed since the merge
+                   base of the git reference and `HEAD` (e.g. `origin/main`)
+  --record <DB>    Record the findings of the run in the SQLite trend
+                   database (created if missing)
+  --trends <DB>    Print the new, fixed, regressed and open findings per
+                   lint and week recorded in the trend database as JSON,
+                   without running the lints
+  -h, --help       Print this help
+
+Arguments after `--` are forwarded to `cargo check`.";
+
+/// Command-line options of `cargo security-lints`.
+#[derive(Debug, PartialEq, Eq)]
+pub struct Options {
+    pub config: PathBuf,
+    /// Previously captured compiler output to read instead of running the
+    /// lints.
+    pub input: Option<PathBuf>,
+    /// Overrides the `fail_on` gate of the policy; `Some(None)` never fails.
+    pub fail_on: Option<Option<Severity>>,
+    pub format: Format,
+    /// Overrides the `profile` of the policy.
+    pub profile: Option<Profile>,
+    /// Lints the dependencies of the workspace instead of the workspace.
+    pub dependencies: Option<Scope>,
+    /// Keeps the findings on the lines changed since the merge base of this
+    /// git reference.
+    pub diff: Option<String>,
+    /// Records the findings of the run in this trend database.
+    pub record: Option<PathBuf>,
+    /// Prints the trends recorded in this database instead of running the
+    /// lints.
+    pub trends: Option<PathBuf>,
+    /// Prints the metrics of the run instead of the report.
+    pub metrics: bool,
+    /// Prints the expectations of the security lints instead of the report.
+    pub expectations: bool,
+    /// Prints the suppressed findings of each lint instead of the report.
+    pub suppressions: bool,
+    /// Applies the suggestions of the lints instead of the policy.
+    pub fix: bool,
+    /// Prints the changes of `fix` instead of applying them.
+    pub dry_run: bool,
+    /// Arguments forwarded to `cargo check`.
+    pub cargo_args: Vec<String>,
+    pub help: bool,
+}
+
+impl Default for Options {
+    fn default() -> Self {
+        Self {
+            config: PathBuf::from("dylint.toml"),
+            input: None,
+            fail_on: None,
+            format: Format::Text,
+            profile: None,
+            dependencies: None,
+            diff: None,
+            record: None,
+            trends: None,
+            metrics: false,
+            expectations: false,
+            suppressions: false,
+            fix: false,
+            dry_run: false,
+            cargo_args: Vec::new(),
+            help: false,
+        }
+    }
+}
+
+/// Parses the command-line arguments, without the program name. The
+/// `security-lints` argument added by cargo when running the binary as a
+/// subcommand is skipped.
+///
+/// # Arguments
+/// * `args` (`impl IntoIterator<Item = String>`) - The arguments to parse.
+pub fn parse(args: impl IntoIterator<Item = String>) -> Result<Options> {
+    let mut options: Options = Options::default();
+    let mut args: std::iter::Peekable<_> = args.into_iter().peekable();
+
+    if args.peek().is_some_and(|arg: &String| arg == "security-lints") {
+        args.next();
+    }
+
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--config" => {
+                options.config =
+                    PathBuf::from(args.next().context("missing config path")?);
+            },
+            "--input" => {
+                options.input = Some(PathBuf::from(
+                    args.next().context("missing input path")?,
+                ));
+            },
+            "--fail-on" => {
+                let value: String = args.next().context("missing severity")?;
+                options.fail_on =
+                    Some(Severity::parse_gate(&value).with_context(|| {
+                        format!("invalid severity `{value}`")
+                    })?);
+            },
+            "--format" => {
+                let value: String = args.next().context("missing format")?;
+                options.format = Format::parse(&value)
+                    .with_context(|| format!("invalid format `{value}`"))?;
+            },
+            "--profile" => {
+                let value: String = args.next().context("missing profile")?;
+                options.profile =
+                    Some(Profile::parse(&value).with_context(|| {
+                        format!("invalid profile `{value}`")
+                    })?);
+            },
+            "--dependencies" => {
+                let value: String = args.next().context("missing scope")?;
+                options.dependencies =
+                    Some(Scope::parse(&value).with_context(|| {
+                        format!("invalid scope `{value}`")
+                    })?);
+            },
+            "--diff" => {
+                options.diff =
+                    Some(args.next().context("missing git reference")?);
+            },
+            "--record" => {
+                options.record = Some(PathBuf::from(
+                    args.next().context("missing database path")?,
+                ));
+            },
+            "--trends" => {
+                options.trends = Some(PathBuf::from(
+                    args.next().context("missing database path")?,
+                ));
+            },
+            "--metrics" => options.metrics = true,
+            "--expectations" => options.expectations = true,
+            "--suppressions" => options.suppressions = true,
+            "--fix" => options.fix = true,
+            "--dry-run" => options.dry_run = true,
+            "-h" | "--help" => options.help = true,
+            "--" => {
+                options.cargo_args.extend(args.by_ref());
+            },
+            _ => bail!("unexpected argument `{arg}`"),
+        }
+    }
+
+    if options.dry_run && !options.fix {
+        bail!("`--dry-run` requires `--fix`");
+    }
+    if [options.metrics, options.expectations, options.suppressions]
+        .into_iter()
+        .filter(|mode: &bool| *mode)
+        .count()
+        > 1
+    {
+        bail!(
+            "`--metrics`, `--expectations` and `--suppressions` cannot be \
+             combined"
+        );
+    }
+    if options.fix
+        && (options.metrics
+            || options.expectations
+            || options.suppressions
+            || options.dependencies.is_some())
+    {
+        bail!(
+            "`--fix` cannot be combined with `--metrics`, `--expectations`, \
+             `--suppressions` or `--dependencies`"
+        );
+    }
+
+    if options.diff.is_some() && options.dependencies.is_some() {
+        bail!("`--diff` cannot be combined with `--dependencies`");
+    }
+    if options.record.is_some()
+        && (options.fix
+            || options.metrics
+            || options.expectations
+            || options.suppressions
+            || options.diff.is_some())
+    {
+        bail!(
+            "`--record` cannot be combined with `--fix`, `--metrics`, \
+             `--expectations`, `--suppressions` or `--diff`"
+        );
+    }
+    if options.trends.is_some()
+        && (options.input.is_some()
+            || options.fix
+            || options.metrics
+            || options.expectations
+            || options.suppressions
+            || options.record.is_some()
+            || options.diff.is_some()
+            || options.dependencies.is_some())
+    {
+        bail!("`--trends` does not run the lints and cannot be combined");
+    }
+
+    Ok(options)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::PathBuf;
+
+    use super::{Options, parse};
+    use crate::{dependencies::Scope, output::Format, profile::Profile};
+
+    fn arguments(args: &[&str]) -> Vec<String> {
+        args.iter().map(|arg: &&str| arg.to_string()).collect()
+    }
+
+    #[test]
+    fn subcommand() {
+        let options: Options = parse(arguments(&[
+            "security-lints",
+            "--config",
+            "ci/dylint.toml",
+            "--fail-on",
+            "never",
+            "--format",
+            "github",
+            "--profile",
+            "crypto",
+            "--metrics",
+            "--dependencies",
+            "all",
+            "--",
+            "--all-targets",
+        ]))
+        .unwrap();
+
+        assert_eq!(options.config, PathBuf::from("ci/dylint.toml"));
+        assert_eq!(options.fail_on, Some(None));
+        assert_eq!(options.format, Format::Github);
+        assert_eq!(options.profile, Some(Profile::Crypto));
+        assert!(options.metrics);
+        assert_eq!(options.dependencies, Some(Scope::All));
+        assert_eq!(options.cargo_args, ["--all-targets"]);
+        assert!(parse(arguments(&["--unknown"])).is_err());
+
+        let options: Options =
+            parse(arguments(&["--fix", "--dry-run"])).unwrap();
+        assert!(options.fix && options.dry_run);
+        assert!(parse(arguments(&["--dry-run"])).is_err());
+        assert!(parse(arguments(&["--fix", "--metrics"])).is_err());
+        assert!(parse(arguments(&["--expectations"])).unwrap().expectations);
+        assert!(parse(arguments(&["--expectations", "--metrics"])).is_err());
+        assert!(parse(arguments(&["--suppressions"])).unwrap().suppressions);
+        assert!(
+            parse(arguments(&["--suppressions", "--expectations"])).is_err()
+        );
+        assert!(parse(arguments(&["--fix", "--suppressions"])).is_err());
+        assert_eq!(
+            parse(arguments(&["--diff", "origin/main"])).unwrap().diff,
+            Some(String::from("origin/main"))
+        );
+        assert!(
+            parse(arguments(&["--diff", "main", "--dependencies", "all"]))
+                .is_err()
+        );
+        assert_eq!(
+            parse(arguments(&["--record", "trends.db"])).unwrap().record,
+            Some(PathBuf::from("trends.db"))
+        );
+        assert!(parse(arguments(&["--record", "a.db", "--metrics"])).is_err());
+        assert_eq!(
+            parse(arguments(&["--trends", "trends.db"])).unwrap().trends,
+            Some(PathBuf::from("trends.db"))
+        );
+        assert!(
+            parse(arguments(&["--trends", "a.db", "--record", "a.db"]))
+                .is_err()
+        );
+    }
+}
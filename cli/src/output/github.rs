@@ -0,0 +1,104 @@
+use std::io::{self, Write};
+
+use crate::{
+    finding::{Finding, Level, Messages},
+    output::text,
+    policy::Evaluation,
+};
+
+/// Writes the findings as GitHub Actions workflow commands, so that they are
+/// shown as annotations on the lines of the pull request, followed by the
+/// per-lint summary table of the text report.
+///
+/// # Arguments
+/// * `out` (`&mut dyn Write`) - The destination of the report.
+/// * `messages` (`&Messages`) - The findings and compiler errors of the run.
+/// * `evaluation` (`&Evaluation`) - The outcome of the policy.
+pub fn write(
+    out: &mut dyn Write,
+    messages: &Messages,
+    evaluation: &Evaluation,
+) -> io::Result<()> {
+    for error in &messages.errors {
+        writeln!(out, "::error::{}", escape_data(error.trim_end()))?;
+    }
+
+    for (index, finding) in messages.findings.iter().enumerate() {
+        let level: Level = evaluation.level(index, finding);
+        writeln!(out, "{}", command(finding, level))?;
+    }
+
+    text::write_summary(out, messages, evaluation)
+}
+
+/// Formats the workflow command annotating `finding`, e.g.
+/// `::error file=src/lib.rs,line=3,col=5,title=security_panic_usage::...`.
+///
+/// # Arguments
+/// * `finding` (`&Finding`) - The finding to annotate.
+/// * `level` (`Level`) - The level chosen by the policy.
+fn command(finding: &Finding, level: Level) -> String {
+    let name: &str = match level {
+        Level::Note => "notice",
+        Level::Warning => "warning",
+        Level::Error => "error",
+    };
+
+    format!(
+        "::{name} file={},line={},col={},title={}::{}",
+        escape_property(&finding.file),
+        finding.line,
+        finding.column,
+        escape_property(&finding.lint),
+        escape_data(&finding.message)
+    )
+}
+
+/// Escapes the message of a workflow command.
+///
+/// # Arguments
+/// * `value` (`&str`) - The message to escape.
+fn escape_data(value: &str) -> String {
+    value.replace('%', "%25").replace('\r', "%0D").replace('\n', "%0A")
+}
+
+/// Escapes a property value of a workflow command, which additionally must
+/// not contain the `:` and `,` separators.
+///
+/// # Arguments
+/// * `value` (`&str`) - The property value to escape.
+fn escape_property(value: &str) -> String {
+    escape_data(value).replace(':', "%3A").replace(',', "%2C")
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::Value;
+
+    use super::command;
+    use crate::finding::{Finding, Level};
+
+    #[test]
+    fn workflow_command() {
+        let finding: Finding = Finding {
+            lint: String::from("security_panic_usage"),
+            package: String::from("demo@0.1.0"),
+            level: Level::Error,
+            message: String::from("Usage of `unwrap` detected.\n100% sure"),
+            file: String::from("src/a,b.rs"),
+            line: 3,
+            column: 5,
+            rendered: String::new(),
+            notes: Vec::new(),
+            suggestions: Vec::new(),
+            diagnostic: Value::Null,
+        };
+
+        assert_eq!(
+            command(&finding, Level::Warning),
+            "::warning file=src/a%2Cb.rs,line=3,col=5,\
+             title=security_panic_usage::Usage of `unwrap` detected.%0A100%25 \
+             sure"
+        );
+    }
+}
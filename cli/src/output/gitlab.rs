@@ -0,0 +1,145 @@
+use std::io::{self, Write};
+
+use serde::Serialize;
+
+use crate::{
+    config::Severity,
+    finding::{self, Finding, Messages},
+    policy::Evaluation,
+};
+
+/// An issue of a GitLab Code Quality report.
+#[derive(Serialize)]
+struct Issue<'a> {
+    description: &'a str,
+    check_name: &'a str,
+    fingerprint: String,
+    severity: &'static str,
+    location: Location<'a>,
+}
+
+#[derive(Serialize)]
+struct Location<'a> {
+    path: &'a str,
+    lines: Lines,
+}
+
+#[derive(Serialize)]
+struct Lines {
+    begin: usize,
+}
+
+/// Writes the findings as a GitLab Code Quality report, to be uploaded as
+/// the `codequality` artifact of a job. Compiler errors have no place in the
+/// report and are written to the standard error instead.
+///
+/// # Arguments
+/// * `out` (`&mut dyn Write`) - The destination of the report.
+/// * `messages` (`&Messages`) - The findings and compiler errors of the run.
+/// * `evaluation` (`&Evaluation`) - The outcome of the policy.
+pub fn write(
+    out: &mut dyn Write,
+    messages: &Messages,
+    evaluation: &Evaluation,
+) -> io::Result<()> {
+    for error in &messages.errors {
+        eprintln!("{}", error.trim_end());
+    }
+
+    serde_json::to_writer_pretty(
+        &mut *out,
+        &issues(&messages.findings, evaluation),
+    )?;
+    writeln!(out)
+}
+
+/// Converts the findings into Code Quality issues. The fingerprint of an
+/// issue identifies it across commits (see [`finding::fingerprinted`]), so
+/// that GitLab can tell new findings from fixed ones.
+///
+/// # Arguments
+/// * `findings` (`&'a [Finding]`) - The findings of the run.
+/// * `evaluation` (`&Evaluation`) - The outcome of the policy, holding the
+///   severity of each finding.
+fn issues<'a>(
+    findings: &'a [Finding],
+    evaluation: &Evaluation,
+) -> Vec<Issue<'a>> {
+    finding::fingerprinted(findings)
+        .into_iter()
+        .enumerate()
+        .map(
+            |(index, (finding, fingerprint)): (
+                usize,
+                (&'a Finding, String),
+            )| {
+                Issue {
+                    description: &finding.message,
+                    check_name: &finding.lint,
+                    fingerprint,
+                    severity: match evaluation.severity(index, finding) {
+                        Severity::Low => "minor",
+                        Severity::Medium => "major",
+                        Severity::High => "critical",
+                    },
+                    location: Location {
+                        path: &finding.file,
+                        lines: Lines { begin: finding.line },
+                    },
+                }
+            },
+        )
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::Value;
+
+    use super::issues;
+    use crate::{
+        config::Severity,
+        finding::{Finding, Level},
+        policy::Evaluation,
+    };
+
+    fn finding(line: usize) -> Finding {
+        Finding {
+            lint: String::from("security_indexing_usage"),
+            package: String::from("demo@0.1.0"),
+            level: Level::Error,
+            message: String::from("Usage of indexing operation detected."),
+            file: String::from("src/lib.rs"),
+            line,
+            column: 1,
+            rendered: String::new(),
+            notes: Vec::new(),
+            suggestions: Vec::new(),
+            diagnostic: Value::Null,
+        }
+    }
+
+    #[test]
+    fn fingerprints() {
+        let findings: Vec<Finding> = vec![finding(3), finding(7)];
+        let moved: Vec<Finding> = vec![finding(4)];
+        let evaluation: Evaluation = Evaluation {
+            severities: vec![Severity::High, Severity::Low],
+            ..Evaluation::default()
+        };
+
+        let report: String =
+            serde_json::to_string(&issues(&findings, &evaluation)).unwrap();
+        assert!(report.contains(r#""severity":"critical""#));
+        assert!(report.contains(
+            r#""location":{"path":"src/lib.rs","lines":{"begin":7}}"#
+        ));
+
+        let first: String =
+            issues(&findings, &evaluation)[0].fingerprint.clone();
+        let second: String =
+            issues(&findings, &evaluation)[1].fingerprint.clone();
+        assert_ne!(first, second);
+        assert_eq!(issues(&moved, &evaluation)[0].fingerprint, first);
+    }
+}
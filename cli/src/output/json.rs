@@ -0,0 +1,99 @@
+use std::io::{self, Write};
+
+use serde_json::Value;
+
+use crate::{
+    finding::{Finding, Level, Messages},
+    output::text,
+    policy::Evaluation,
+};
+
+/// Writes the diagnostics of the run in the JSON format of rustc, one per
+/// line, so that editors show the findings inline: rust-analyzer reads this
+/// output when the command is set as its `check.overrideCommand`. The other
+/// compiler diagnostics are written as emitted, as the command then replaces
+/// `cargo check`, and the findings are relabelled with the level chosen by
+/// the policy. Each diagnostic carries its file, line and column range,
+/// level, lint code and suggested replacements.
+///
+/// # Arguments
+/// * `out` (`&mut dyn Write`) - The destination of the report.
+/// * `messages` (`&Messages`) - The findings and compiler diagnostics of the
+///   run.
+/// * `evaluation` (`&Evaluation`) - The outcome of the policy.
+pub fn write(
+    out: &mut dyn Write,
+    messages: &Messages,
+    evaluation: &Evaluation,
+) -> io::Result<()> {
+    for diagnostic in &messages.diagnostics {
+        serde_json::to_writer(&mut *out, diagnostic)?;
+        writeln!(out)?;
+    }
+
+    for (index, finding) in messages.findings.iter().enumerate() {
+        let level: Level = evaluation.level(index, finding);
+        serde_json::to_writer(&mut *out, &relabelled(finding, level))?;
+        writeln!(out)?;
+    }
+
+    Ok(())
+}
+
+/// Returns the diagnostic of `finding` with its level, and the level of its
+/// rendered text, replaced by `level`.
+///
+/// # Arguments
+/// * `finding` (`&Finding`) - The finding to write.
+/// * `level` (`Level`) - The level chosen by the policy.
+fn relabelled(finding: &Finding, level: Level) -> Value {
+    let mut diagnostic: Value = finding.diagnostic.clone();
+    if let Some(fields) = diagnostic.as_object_mut() {
+        fields.insert(String::from("level"), Value::from(level.label()));
+        fields.insert(
+            String::from("rendered"),
+            Value::from(text::rendered(finding, level)),
+        );
+    }
+
+    diagnostic
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::Value;
+
+    use super::write;
+    use crate::{
+        finding::{self, Level, Messages},
+        policy::Evaluation,
+    };
+
+    #[test]
+    fn relabelled() {
+        let messages: Messages = finding::parse_messages(
+            r#"{"reason":"compiler-message","message":{"message":"unused variable: `x`","code":{"code":"unused_variables","explanation":null},"level":"warning","spans":[{"file_name":"src/main.rs","line_start":2,"line_end":2,"column_start":9,"column_end":10,"is_primary":true}],"rendered":"warning: unused variable"}}
+{"reason":"compiler-message","package_id":"path+file:///src/demo#0.1.0","message":{"message":"Usage of unsafe block detected.","code":{"code":"security_unsafe_usage","explanation":null},"level":"error","spans":[{"file_name":"src/main.rs","line_start":3,"line_end":3,"column_start":5,"column_end":14,"is_primary":true}],"children":[],"rendered":"error: Usage of unsafe block detected."}}"#,
+        );
+        let evaluation: Evaluation =
+            Evaluation { levels: vec![Level::Note], ..Evaluation::default() };
+
+        let mut out: Vec<u8> = Vec::new();
+        write(&mut out, &messages, &evaluation).unwrap();
+        let lines: Vec<Value> = String::from_utf8(out)
+            .unwrap()
+            .lines()
+            .map(|line: &str| serde_json::from_str(line).unwrap())
+            .collect();
+
+        assert_eq!(lines.len(), 2);
+        assert_eq!(lines[0]["level"], "warning");
+        assert_eq!(lines[1]["level"], "note");
+        assert_eq!(
+            lines[1]["rendered"],
+            "note: Usage of unsafe block detected."
+        );
+        assert_eq!(lines[1]["code"]["code"], "security_unsafe_usage");
+        assert_eq!(lines[1]["spans"][0]["column_end"], 14);
+    }
+}
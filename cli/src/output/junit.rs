@@ -0,0 +1,171 @@
+use std::{
+    collections::BTreeMap,
+    io::{self, Write},
+};
+
+use crate::{
+    finding::{Finding, Level, Messages},
+    policy::Evaluation,
+};
+
+/// Writes the findings as a JUnit XML report: one test suite per lint and one
+/// failed test case per finding. Compiler errors are reported as errored
+/// test cases of a `compilation` suite.
+///
+/// # Arguments
+/// * `out` (`&mut dyn Write`) - The destination of the report.
+/// * `messages` (`&Messages`) - The findings and compiler errors of the run.
+/// * `evaluation` (`&Evaluation`) - The outcome of the policy.
+pub fn write(
+    out: &mut dyn Write,
+    messages: &Messages,
+    evaluation: &Evaluation,
+) -> io::Result<()> {
+    let mut by_lint: BTreeMap<&str, Vec<(&Finding, Level)>> = BTreeMap::new();
+    for (index, finding) in messages.findings.iter().enumerate() {
+        let level: Level = evaluation.level(index, finding);
+        by_lint.entry(&finding.lint).or_default().push((finding, level));
+    }
+
+    let total: usize = messages.findings.len() + messages.errors.len();
+    writeln!(out, r#"<?xml version="1.0" encoding="UTF-8"?>"#)?;
+    writeln!(
+        out,
+        r#"<testsuites name="security-lints" tests="{total}" failures="{}" errors="{}">"#,
+        messages.findings.len(),
+        messages.errors.len()
+    )?;
+
+    if !messages.errors.is_empty() {
+        let count: usize = messages.errors.len();
+        writeln!(
+            out,
+            r#"  <testsuite name="compilation" tests="{count}" failures="0" errors="{count}">"#
+        )?;
+        for (index, error) in messages.errors.iter().enumerate() {
+            let message: &str = error.lines().next().unwrap_or_default();
+            writeln!(
+                out,
+                r#"    <testcase name="error {}" classname="compilation">"#,
+                index + 1
+            )?;
+            writeln!(
+                out,
+                r#"      <error message="{}">{}</error>"#,
+                escape(message),
+                escape(error.trim_end())
+            )?;
+            writeln!(out, "    </testcase>")?;
+        }
+        writeln!(out, "  </testsuite>")?;
+    }
+
+    for (lint, findings) in by_lint {
+        writeln!(
+            out,
+            r#"  <testsuite name="{lint}" tests="{count}" failures="{count}" errors="0">"#,
+            lint = escape(lint),
+            count = findings.len()
+        )?;
+        for (finding, level) in findings {
+            write_test_case(out, finding, level)?;
+        }
+        writeln!(out, "  </testsuite>")?;
+    }
+
+    writeln!(out, "</testsuites>")
+}
+
+/// Writes the failed test case of `finding`, named after its location.
+///
+/// # Arguments
+/// * `out` (`&mut dyn Write`) - The destination of the report.
+/// * `finding` (`&Finding`) - The finding to report.
+/// * `level` (`Level`) - The level chosen by the policy, used as the failure
+///   type.
+fn write_test_case(
+    out: &mut dyn Write,
+    finding: &Finding,
+    level: Level,
+) -> io::Result<()> {
+    writeln!(
+        out,
+        r#"    <testcase name="{}:{}:{}" classname="{}" file="{}" line="{}">"#,
+        escape(&finding.file),
+        finding.line,
+        finding.column,
+        escape(&finding.lint),
+        escape(&finding.file),
+        finding.line
+    )?;
+    writeln!(
+        out,
+        r#"      <failure message="{}" type="{}">{}</failure>"#,
+        escape(&finding.message),
+        level.label(),
+        escape(finding.rendered.trim_end())
+    )?;
+    writeln!(out, "    </testcase>")
+}
+
+/// Escapes `value` for use in XML text and attribute values.
+///
+/// # Arguments
+/// * `value` (`&str`) - The value to escape.
+fn escape(value: &str) -> String {
+    value
+        .replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::Value;
+
+    use super::write;
+    use crate::{
+        finding::{Finding, Level, Messages},
+        policy::Evaluation,
+    };
+
+    #[test]
+    fn report() {
+        let messages: Messages = Messages {
+            findings: vec![Finding {
+                lint: String::from("security_unsafe_usage"),
+                package: String::from("demo@0.1.0"),
+                level: Level::Error,
+                message: String::from("Usage of `unsafe` block detected."),
+                file: String::from("src/main.rs"),
+                line: 4,
+                column: 5,
+                rendered: String::from("error: Vec<u8> & co"),
+                notes: Vec::new(),
+                suggestions: Vec::new(),
+                diagnostic: Value::Null,
+            }],
+            errors: Vec::new(),
+            unfulfilled_expectations: Vec::new(),
+            diagnostics: Vec::new(),
+        };
+        let evaluation: Evaluation = Evaluation {
+            levels: vec![Level::Warning],
+            ..Evaluation::default()
+        };
+
+        let mut out: Vec<u8> = Vec::new();
+        write(&mut out, &messages, &evaluation).unwrap();
+        let report: String = String::from_utf8(out).unwrap();
+
+        assert!(report.contains(
+            r#"<testsuite name="security_unsafe_usage" tests="1" failures="1" errors="0">"#
+        ));
+        assert!(report.contains(r#"<testcase name="src/main.rs:4:5""#));
+        assert!(report.contains(
+            r#"type="warning">error: Vec&lt;u8&gt; &amp; co</failure>"#
+        ));
+        assert!(report.ends_with("</testsuites>\n"));
+    }
+}
@@ -0,0 +1,66 @@
+mod github;
+mod gitlab;
+mod json;
+mod junit;
+mod text;
+
+use std::io::{self, Write};
+
+use crate::{finding::Messages, policy::Evaluation};
+
+/// Format of the report written by `cargo security-lints`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Format {
+    /// Rendered diagnostics followed by a per-lint summary table.
+    Text,
+    /// GitHub Actions workflow commands, shown as annotations on pull
+    /// requests.
+    Github,
+    /// A GitLab Code Quality report, shown on merge requests.
+    Gitlab,
+    /// A JUnit XML report with one test suite per lint and one failed test
+    /// case per finding.
+    Junit,
+    /// The diagnostics in the JSON format of rustc, one per line, as read by
+    /// editors (e.g. the `check.overrideCommand` of rust-analyzer).
+    Json,
+}
+
+impl Format {
+    /// Parses the value of the `--format` option.
+    ///
+    /// # Arguments
+    /// * `value` (`&str`) - The value to parse.
+    pub fn parse(value: &str) -> Option<Self> {
+        match value {
+            "text" => Some(Self::Text),
+            "github" => Some(Self::Github),
+            "gitlab" => Some(Self::Gitlab),
+            "junit" => Some(Self::Junit),
+            "json" => Some(Self::Json),
+            _ => None,
+        }
+    }
+}
+
+/// Writes the findings of a run and the outcome of the policy to `out`.
+///
+/// # Arguments
+/// * `out` (`&mut dyn Write`) - The destination of the report.
+/// * `format` (`Format`) - The format of the report.
+/// * `messages` (`&Messages`) - The findings and compiler errors of the run.
+/// * `evaluation` (`&Evaluation`) - The outcome of the policy.
+pub fn write_report(
+    out: &mut dyn Write,
+    format: Format,
+    messages: &Messages,
+    evaluation: &Evaluation,
+) -> io::Result<()> {
+    match format {
+        Format::Text => text::write(out, messages, evaluation),
+        Format::Github => github::write(out, messages, evaluation),
+        Format::Gitlab => gitlab::write(out, messages, evaluation),
+        Format::Junit => junit::write(out, messages, evaluation),
+        Format::Json => json::write(out, messages, evaluation),
+    }
+}
@@ -0,0 +1,86 @@
+use std::io::{self, Write};
+
+use crate::{
+    finding::{Finding, Level, Messages},
+    policy::{Evaluation, LintSummary, Status},
+};
+
+/// Writes the rendered diagnostics followed by a per-lint summary table.
+/// Findings are labelled with the level chosen by the policy rather than the
+/// one chosen by rustc.
+///
+/// # Arguments
+/// * `out` (`&mut dyn Write`) - The destination of the report.
+/// * `messages` (`&Messages`) - The findings and compiler errors of the run.
+/// * `evaluation` (`&Evaluation`) - The outcome of the policy.
+pub fn write(
+    out: &mut dyn Write,
+    messages: &Messages,
+    evaluation: &Evaluation,
+) -> io::Result<()> {
+    for error in &messages.errors {
+        writeln!(out, "{}", error.trim_end())?;
+    }
+
+    for (index, finding) in messages.findings.iter().enumerate() {
+        let level: Level = evaluation.level(index, finding);
+        writeln!(out, "{}\n", rendered(finding, level).trim_end())?;
+    }
+
+    write_summary(out, messages, evaluation)
+}
+
+/// Writes the number of findings followed by a per-lint summary table.
+///
+/// # Arguments
+/// * `out` (`&mut dyn Write`) - The destination of the report.
+/// * `messages` (`&Messages`) - The findings and compiler errors of the run.
+/// * `evaluation` (`&Evaluation`) - The outcome of the policy.
+pub fn write_summary(
+    out: &mut dyn Write,
+    messages: &Messages,
+    evaluation: &Evaluation,
+) -> io::Result<()> {
+    writeln!(out, "security-lints: {} finding(s)", messages.findings.len())?;
+    for summary in &evaluation.summaries {
+        writeln!(out, "{}", summary_line(summary))?;
+    }
+
+    Ok(())
+}
+
+/// Returns the rendered diagnostic of `finding`, relabelled with `level`.
+///
+/// # Arguments
+/// * `finding` (`&Finding`) - The finding to render.
+/// * `level` (`Level`) - The level chosen by the policy.
+pub fn rendered(finding: &Finding, level: Level) -> String {
+    match finding.rendered.strip_prefix(finding.level.label()) {
+        Some(rest) => format!("{}{rest}", level.label()),
+        None => finding.rendered.clone(),
+    }
+}
+
+/// Formats one row of the summary table, e.g.
+/// `  security_unsafe_usage  high  2 / 3  ok`.
+///
+/// # Arguments
+/// * `summary` (`&LintSummary`) - The summary of a lint.
+fn summary_line(summary: &LintSummary) -> String {
+    let budget: String = summary
+        .budget
+        .map_or_else(|| String::from("-"), |budget: usize| budget.to_string());
+    let status: &str = match (summary.status, summary.budget) {
+        (Status::Pass, _) => "ok",
+        (Status::Fail, Some(_)) => "budget exceeded",
+        (Status::Fail, None) => "failed",
+    };
+
+    format!(
+        "  {:<40} {:<6} {:>5} / {:<5} {status}",
+        summary.lint,
+        summary.severity.name(),
+        summary.count,
+        budget
+    )
+}
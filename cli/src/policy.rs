@@ -0,0 +1,278 @@
+use std::collections::BTreeMap;
+
+use crate::{
+    config::{Policy, Severity},
+    finding::{Finding, Level},
+};
+
+/// Outcome of the policy for a single lint.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Status {
+    /// The lint has no findings that fail the run.
+    Pass,
+    /// The lint exceeded its budget, or reported findings at or above the
+    /// `fail_on` severity without a budget.
+    Fail,
+}
+
+/// Findings count and policy outcome of a single lint.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LintSummary {
+    pub lint: String,
+    pub count: usize,
+    /// The highest severity among the findings of the lint.
+    pub severity: Severity,
+    /// The maximum number of findings allowed by `[policy.thresholds]`, if
+    /// any.
+    pub budget: Option<usize>,
+    pub status: Status,
+}
+
+/// Outcome of the policy for a whole run.
+#[derive(Debug, Default)]
+pub struct Evaluation {
+    /// One summary per lint with findings, sorted by lint name.
+    pub summaries: Vec<LintSummary>,
+    /// The level each finding is reported with, in the order of the
+    /// findings: `error` if it fails the run, `warning` if it is at or above
+    /// the `warn_on` severity, `note` otherwise.
+    pub levels: Vec<Level>,
+    /// The severity of each finding, in the order of the findings.
+    pub severities: Vec<Severity>,
+}
+
+impl Evaluation {
+    /// Returns `true` if at least one lint fails the run.
+    pub fn failed(&self) -> bool {
+        self.summaries
+            .iter()
+            .any(|summary: &LintSummary| summary.status == Status::Fail)
+    }
+
+    /// Returns the level the finding at `index` is reported with, or the
+    /// level chosen by rustc if the policy did not evaluate it, so that no
+    /// finding is dropped from the reports.
+    ///
+    /// # Arguments
+    /// * `index` (`usize`) - The position of the finding in the findings.
+    /// * `finding` (`&Finding`) - The finding.
+    pub fn level(&self, index: usize, finding: &Finding) -> Level {
+        self.levels.get(index).copied().unwrap_or(finding.level)
+    }
+
+    /// Returns the severity of the finding at `index`, or its default
+    /// severity if the policy did not evaluate it.
+    ///
+    /// # Arguments
+    /// * `index` (`usize`) - The position of the finding in the findings.
+    /// * `finding` (`&Finding`) - The finding.
+    pub fn severity(&self, index: usize, finding: &Finding) -> Severity {
+        self.severities
+            .get(index)
+            .copied()
+            .unwrap_or_else(|| severity(finding, &Policy::default()))
+    }
+}
+
+/// Returns the severity of `finding`: the one configured for its lint, or
+/// `high` for deny-level and `medium` for other findings.
+///
+/// # Arguments
+/// * `finding` (`&Finding`) - The finding to classify.
+/// * `policy` (`&Policy`) - The policy loaded from `dylint.toml`.
+pub fn severity(finding: &Finding, policy: &Policy) -> Severity {
+    match policy.severity.get(&finding.lint) {
+        Some(severity) => *severity,
+        None if finding.level == Level::Error => Severity::High,
+        None => Severity::Medium,
+    }
+}
+
+/// Applies `policy` to `findings`. A lint with a budget fails when its count
+/// exceeds the budget, whatever the severity of its findings; a lint without
+/// a budget fails when one of its findings reaches the `fail_on` severity.
+/// The deny/warn level chosen by rustc only matters through the default
+/// severity.
+///
+/// # Arguments
+/// * `findings` (`&[Finding]`) - The findings of the run.
+/// * `policy` (`&Policy`) - The policy loaded from `dylint.toml`.
+pub fn evaluate(findings: &[Finding], policy: &Policy) -> Evaluation {
+    let fails: &dyn Fn(Severity) -> bool = &|severity: Severity| {
+        policy.fail_on.is_some_and(|gate: Severity| severity >= gate)
+    };
+
+    let severities: Vec<Severity> = findings
+        .iter()
+        .map(|finding: &Finding| severity(finding, policy))
+        .collect();
+
+    let mut by_lint: BTreeMap<&str, Vec<Severity>> = BTreeMap::new();
+    for (finding, severity) in findings.iter().zip(&severities) {
+        by_lint.entry(finding.lint.as_str()).or_default().push(*severity);
+    }
+
+    let summaries: Vec<LintSummary> = by_lint
+        .into_iter()
+        .map(|(lint, severities): (&str, Vec<Severity>)| {
+            let budget: Option<usize> = policy.thresholds.get(lint).copied();
+            let failed: bool = match budget {
+                Some(budget) => severities.len() > budget,
+                None => severities.iter().copied().any(fails),
+            };
+
+            LintSummary {
+                lint: lint.to_owned(),
+                count: severities.len(),
+                severity: severities
+                    .iter()
+                    .copied()
+                    .max()
+                    .unwrap_or(Severity::Low),
+                budget,
+                status: if failed { Status::Fail } else { Status::Pass },
+            }
+        })
+        .collect();
+
+    let levels: Vec<Level> = findings
+        .iter()
+        .zip(&severities)
+        .map(|(finding, severity): (&Finding, &Severity)| {
+            let severity: Severity = *severity;
+            let fails_run: bool = match policy.thresholds.get(&finding.lint) {
+                Some(_) => summaries.iter().any(|summary: &LintSummary| {
+                    summary.lint == finding.lint
+                        && summary.status == Status::Fail
+                }),
+                None => fails(severity),
+            };
+
+            if fails_run {
+                Level::Error
+            } else if severity >= policy.warn_on {
+                Level::Warning
+            } else {
+                Level::Note
+            }
+        })
+        .collect();
+
+    Evaluation { summaries, levels, severities }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeMap;
+
+    use serde_json::Value;
+
+    use super::{Evaluation, LintSummary, Status, evaluate};
+    use crate::{
+        config::{Policy, Severity},
+        finding::{Finding, Level},
+    };
+
+    fn finding(lint: &str, level: Level, line: usize) -> Finding {
+        Finding {
+            lint: lint.to_owned(),
+            package: String::from("demo@0.1.0"),
+            level,
+            message: String::new(),
+            file: String::from("src/lib.rs"),
+            line,
+            column: 1,
+            rendered: String::new(),
+            notes: Vec::new(),
+            suggestions: Vec::new(),
+            diagnostic: Value::Null,
+        }
+    }
+
+    #[test]
+    fn thresholds() {
+        let mut policy: Policy = Policy::default();
+        policy.thresholds.insert(String::from("security_unsafe_usage"), 2);
+        policy.thresholds.insert(String::from("security_panic_usage"), 0);
+
+        let findings: Vec<Finding> = vec![
+            finding("security_unsafe_usage", Level::Error, 1),
+            finding("security_unsafe_usage", Level::Error, 2),
+            finding("security_panic_usage", Level::Warning, 3),
+            finding("security_shift_usage", Level::Warning, 4),
+        ];
+        let evaluation: Evaluation = evaluate(&findings, &policy);
+
+        let statuses: Vec<(&str, Status)> = evaluation
+            .summaries
+            .iter()
+            .map(|summary: &LintSummary| {
+                (summary.lint.as_str(), summary.status)
+            })
+            .collect();
+        assert_eq!(
+            statuses,
+            [
+                ("security_panic_usage", Status::Fail),
+                ("security_shift_usage", Status::Pass),
+                ("security_unsafe_usage", Status::Pass),
+            ]
+        );
+        assert!(evaluation.failed());
+        assert_eq!(
+            evaluation.levels,
+            [Level::Warning, Level::Warning, Level::Error, Level::Warning]
+        );
+    }
+
+    #[test]
+    fn deny_without_budget() {
+        let findings: Vec<Finding> =
+            vec![finding("security_unsafe_usage", Level::Error, 1)];
+        let evaluation: Evaluation = evaluate(&findings, &Policy::default());
+
+        assert!(evaluation.failed());
+        assert_eq!(evaluation.levels, [Level::Error]);
+    }
+
+    #[test]
+    fn severity_gates() {
+        let mut policy: Policy = Policy {
+            severity: BTreeMap::from([(
+                String::from("security_shift_usage"),
+                Severity::Low,
+            )]),
+            fail_on: Some(Severity::Medium),
+            warn_on: Severity::Medium,
+            ..Policy::default()
+        };
+
+        let findings: Vec<Finding> = vec![
+            finding("security_monetary_float", Level::Warning, 1),
+            finding("security_shift_usage", Level::Error, 2),
+        ];
+        let evaluation: Evaluation = evaluate(&findings, &policy);
+        assert!(evaluation.failed());
+        assert_eq!(evaluation.levels, [Level::Error, Level::Note]);
+
+        policy.fail_on = None;
+        let evaluation: Evaluation = evaluate(&findings, &policy);
+        assert!(!evaluation.failed());
+        assert_eq!(evaluation.levels, [Level::Warning, Level::Note]);
+    }
+
+    #[test]
+    fn unevaluated() {
+        let evaluated: Finding =
+            finding("security_unsafe_usage", Level::Error, 1);
+        let extra: Finding =
+            finding("security_shift_usage", Level::Warning, 2);
+        let evaluation: Evaluation =
+            evaluate(std::slice::from_ref(&evaluated), &Policy::default());
+
+        assert_eq!(evaluation.level(0, &evaluated), Level::Error);
+        assert_eq!(evaluation.severity(0, &evaluated), Severity::High);
+        assert_eq!(evaluation.level(1, &extra), Level::Warning);
+        assert_eq!(evaluation.severity(1, &extra), Severity::Medium);
+    }
+}
@@ -0,0 +1,286 @@
+use std::collections::BTreeMap;
+
+use serde::Deserialize;
+
+use crate::registry::Library;
+
+/// A rustc lint level set by a profile.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum LintLevel {
+    Allow,
+    Warn,
+    Deny,
+}
+
+impl LintLevel {
+    /// Returns the rustc flag setting a lint to this level.
+    fn flag(self) -> &'static str {
+        match self {
+            Self::Allow => "-A",
+            Self::Warn => "-W",
+            Self::Deny => "-D",
+        }
+    }
+}
+
+/// A named set of lint levels suited to a class of applications, selected
+/// with `profile` in the `[policy]` table or with `--profile`. Lints a
+/// profile does not mention keep their default level.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum Profile {
+    /// Network services: async and timeout lints are denied, firmware lints
+    /// are disabled and indexing is only a warning.
+    WebService,
+    /// Firmware and bare-metal code: panic, interrupt and `no_std` lints are
+    /// denied, async and network lints are disabled.
+    Embedded,
+    /// Cryptographic code: memory, arithmetic and truncation lints are
+    /// denied, lints about application concerns are disabled.
+    Crypto,
+    /// Command-line tools: panics and indexing are only warnings, firmware
+    /// lints are disabled.
+    Cli,
+}
+
+/// Levels of the `web-service` profile.
+const WEB_SERVICE: [(&str, LintLevel); 28] = [
+    ("security_await_without_timeout", LintLevel::Deny),
+    ("security_client_without_timeout", LintLevel::Deny),
+    ("security_server_without_timeout", LintLevel::Deny),
+    ("security_dropped_join_handle", LintLevel::Deny),
+    ("security_refcell_await", LintLevel::Deny),
+    ("security_sleep_synchronization", LintLevel::Deny),
+    ("security_monetary_float", LintLevel::Deny),
+    ("security_timestamp_identifier", LintLevel::Warn),
+    ("security_timestamp_randomness", LintLevel::Warn),
+    ("security_indexing_usage", LintLevel::Warn),
+    ("security_literal_indexing", LintLevel::Warn),
+    ("security_map_indexing", LintLevel::Warn),
+    ("security_unrestricted_redirect", LintLevel::Warn),
+    ("security_lossy_utf8_decoding", LintLevel::Warn),
+    ("security_hardcoded_address", LintLevel::Warn),
+    ("security_default_on_parse_failure", LintLevel::Deny),
+    ("security_url_credentials", LintLevel::Deny),
+    ("security_interrupt_panic", LintLevel::Allow),
+    ("security_interrupt_allocation", LintLevel::Allow),
+    ("security_no_std_panic_formatting", LintLevel::Allow),
+    ("security_silent_panic_handler", LintLevel::Allow),
+    ("security_intrinsics_usage", LintLevel::Deny),
+    ("security_weak_tls_version", LintLevel::Deny),
+    ("security_weak_tls_cipher", LintLevel::Deny),
+    ("security_xml_external_entity", LintLevel::Deny),
+    ("security_debug_only_check", LintLevel::Deny),
+    ("security_feature_gated_check", LintLevel::Deny),
+    ("security_wasm_export_panic", LintLevel::Deny),
+];
+
+/// Levels of the `embedded` profile.
+const EMBEDDED: [(&str, LintLevel); 25] = [
+    ("security_panic_usage", LintLevel::Deny),
+    ("security_missing_track_caller", LintLevel::Allow),
+    ("security_indexing_usage", LintLevel::Deny),
+    ("security_literal_indexing", LintLevel::Deny),
+    ("security_map_indexing", LintLevel::Deny),
+    ("security_shift_usage", LintLevel::Deny),
+    ("security_allocation_size_overflow", LintLevel::Deny),
+    ("security_manual_allocation", LintLevel::Deny),
+    ("security_custom_global_allocator", LintLevel::Deny),
+    ("security_dangling_pointer", LintLevel::Deny),
+    ("security_pointer_aliasing", LintLevel::Deny),
+    ("security_interrupt_panic", LintLevel::Deny),
+    ("security_interrupt_allocation", LintLevel::Deny),
+    ("security_no_std_panic_formatting", LintLevel::Deny),
+    ("security_silent_panic_handler", LintLevel::Deny),
+    ("security_await_without_timeout", LintLevel::Allow),
+    ("security_client_without_timeout", LintLevel::Allow),
+    ("security_server_without_timeout", LintLevel::Allow),
+    ("security_unrestricted_redirect", LintLevel::Allow),
+    ("security_dropped_join_handle", LintLevel::Allow),
+    ("security_refcell_await", LintLevel::Allow),
+    ("security_monetary_float", LintLevel::Allow),
+    ("security_timestamp_identifier", LintLevel::Allow),
+    ("security_timestamp_randomness", LintLevel::Allow),
+    ("security_wasm_export_panic", LintLevel::Allow),
+];
+
+/// Levels of the `crypto` profile.
+const CRYPTO: [(&str, LintLevel); 38] = [
+    ("security_unsafe_usage", LintLevel::Deny),
+    ("security_static_unsafe_cell", LintLevel::Deny),
+    ("security_const_fn_unsafe", LintLevel::Deny),
+    ("security_dangling_pointer", LintLevel::Deny),
+    ("security_pointer_aliasing", LintLevel::Deny),
+    ("security_allocation_size_overflow", LintLevel::Deny),
+    ("security_manual_allocation", LintLevel::Deny),
+    ("security_custom_global_allocator", LintLevel::Deny),
+    ("security_intrinsics_usage", LintLevel::Deny),
+    ("security_shift_usage", LintLevel::Deny),
+    ("security_indexing_usage", LintLevel::Deny),
+    ("security_literal_indexing", LintLevel::Deny),
+    ("security_map_indexing", LintLevel::Deny),
+    ("security_panic_usage", LintLevel::Deny),
+    ("security_partial_cmp_unwrap", LintLevel::Deny),
+    ("security_zip_truncation", LintLevel::Deny),
+    ("security_weak_tls_version", LintLevel::Deny),
+    ("security_weak_tls_cipher", LintLevel::Deny),
+    ("security_encoding_as_encryption", LintLevel::Deny),
+    ("security_copyable_secret", LintLevel::Deny),
+    ("security_url_credentials", LintLevel::Deny),
+    ("security_persisted_secret", LintLevel::Deny),
+    ("security_short_key", LintLevel::Deny),
+    ("security_late_permissions", LintLevel::Deny),
+    ("security_missing_must_use", LintLevel::Deny),
+    ("security_public_raw_pointer", LintLevel::Deny),
+    ("security_default_on_parse_failure", LintLevel::Deny),
+    ("security_debug_only_check", LintLevel::Deny),
+    ("security_feature_gated_check", LintLevel::Deny),
+    ("security_await_without_timeout", LintLevel::Allow),
+    ("security_client_without_timeout", LintLevel::Allow),
+    ("security_server_without_timeout", LintLevel::Allow),
+    ("security_monetary_float", LintLevel::Allow),
+    ("security_timestamp_identifier", LintLevel::Allow),
+    ("security_timestamp_randomness", LintLevel::Deny),
+    ("security_modulo_bias", LintLevel::Deny),
+    ("security_interrupt_panic", LintLevel::Allow),
+    ("security_interrupt_allocation", LintLevel::Allow),
+];
+
+/// Levels of the `cli` profile.
+const CLI: [(&str, LintLevel); 18] = [
+    ("security_panic_usage", LintLevel::Warn),
+    ("security_partial_cmp_unwrap", LintLevel::Warn),
+    ("security_indexing_usage", LintLevel::Warn),
+    ("security_literal_indexing", LintLevel::Warn),
+    ("security_map_indexing", LintLevel::Warn),
+    ("security_await_without_timeout", LintLevel::Warn),
+    ("security_client_without_timeout", LintLevel::Warn),
+    ("security_server_without_timeout", LintLevel::Warn),
+    ("security_sleep_synchronization", LintLevel::Warn),
+    ("security_unsafe_usage", LintLevel::Deny),
+    ("security_static_unsafe_cell", LintLevel::Deny),
+    ("security_secret_argument", LintLevel::Deny),
+    ("security_interrupt_panic", LintLevel::Allow),
+    ("security_interrupt_allocation", LintLevel::Allow),
+    ("security_no_std_panic_formatting", LintLevel::Allow),
+    ("security_silent_panic_handler", LintLevel::Allow),
+    ("security_command_inherited_env", LintLevel::Allow),
+    ("security_command_relative_program", LintLevel::Allow),
+];
+
+impl Profile {
+    /// Parses the name of a profile, as written in `dylint.toml`.
+    ///
+    /// # Arguments
+    /// * `value` (`&str`) - The value to parse.
+    pub fn parse(value: &str) -> Option<Self> {
+        match value {
+            "web-service" => Some(Self::WebService),
+            "embedded" => Some(Self::Embedded),
+            "crypto" => Some(Self::Crypto),
+            "cli" => Some(Self::Cli),
+            _ => None,
+        }
+    }
+
+    /// Returns the level of each lint the profile sets.
+    pub fn levels(self) -> &'static [(&'static str, LintLevel)] {
+        match self {
+            Self::WebService => &WEB_SERVICE,
+            Self::Embedded => &EMBEDDED,
+            Self::Crypto => &CRYPTO,
+            Self::Cli => &CLI,
+        }
+    }
+
+    /// Returns the rustc flags setting the levels of the profile, e.g.
+    /// `["-D", "security_panic_usage", ...]`, followed by those of the
+    /// registered `libraries` for the profile. As with any command-line lint
+    /// level, `#[allow(...)]` and `#[deny(...)]` attributes in the sources
+    /// still take precedence.
+    ///
+    /// # Arguments
+    /// * `libraries` (`&[Library]`) - The registered lint libraries.
+    pub fn rustc_flags(self, libraries: &[Library]) -> Vec<String> {
+        let registered: Vec<(&str, LintLevel)> = libraries
+            .iter()
+            .filter_map(|library: &Library| {
+                library.profiles.iter().find_map(
+                    |(profile, levels): (
+                        &String,
+                        &BTreeMap<String, LintLevel>,
+                    )| {
+                        (Self::parse(profile) == Some(self)).then_some(levels)
+                    },
+                )
+            })
+            .flatten()
+            .map(|(lint, level): (&String, &LintLevel)| {
+                (lint.as_str(), *level)
+            })
+            .collect();
+
+        self.levels()
+            .iter()
+            .map(|(lint, level): &(&str, LintLevel)| (*lint, *level))
+            .chain(registered)
+            .flat_map(|(lint, level): (&str, LintLevel)| {
+                [level.flag().to_owned(), lint.to_owned()]
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeMap;
+
+    use super::{LintLevel, Profile};
+    use crate::registry::Library;
+
+    #[test]
+    fn levels() {
+        assert_eq!(Profile::parse("web-service"), Some(Profile::WebService));
+        assert_eq!(Profile::parse("firmware"), None);
+        assert!(
+            Profile::Embedded
+                .levels()
+                .contains(&("security_interrupt_panic", LintLevel::Deny))
+        );
+        assert_eq!(
+            Profile::Cli.rustc_flags(&[])[..4],
+            [
+                "-W",
+                "security_panic_usage",
+                "-W",
+                "security_partial_cmp_unwrap"
+            ]
+        );
+    }
+
+    #[test]
+    fn registered_levels() {
+        let libraries: [Library; 1] = [Library {
+            name: String::from("acme_lints"),
+            lints: vec![String::from("security_acme_unaudited_ffi")],
+            profiles: BTreeMap::from([(
+                String::from("crypto"),
+                BTreeMap::from([(
+                    String::from("security_acme_unaudited_ffi"),
+                    LintLevel::Deny,
+                )]),
+            )]),
+        }];
+
+        let flags: Vec<String> = Profile::Crypto.rustc_flags(&libraries);
+        assert_eq!(
+            flags[flags.len() - 2..],
+            ["-D", "security_acme_unaudited_ffi"]
+        );
+        assert_eq!(
+            Profile::Cli.rustc_flags(&libraries),
+            Profile::Cli.rustc_flags(&[])
+        );
+    }
+}
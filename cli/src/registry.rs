@@ -0,0 +1,120 @@
+use std::{
+    collections::BTreeMap,
+    ffi::OsStr,
+    fs,
+    io::ErrorKind,
+    path::{Path, PathBuf},
+};
+
+use anyhow::{Context, Result};
+use serde::Deserialize;
+
+use crate::profile::LintLevel;
+
+/// The environment variable naming the directory the lint libraries register
+/// themselves in, as read by `security_utils::registry::register`.
+pub const REGISTRY_VARIABLE: &str = "SECURITY_LINTS_REGISTRY";
+
+/// A lint library registered with `security_utils::registry::register`,
+/// typically a third-party library whose lints the subcommand does not
+/// know.
+#[derive(Debug, Default, PartialEq, Eq, Deserialize)]
+#[serde(default)]
+pub struct Library {
+    /// The name of the library, from the name of its file.
+    #[serde(skip)]
+    pub name: String,
+    /// The lints of the library.
+    pub lints: Vec<String>,
+    /// The levels of the lints of the library, by profile name (e.g.
+    /// `crypto`).
+    pub profiles: BTreeMap<String, BTreeMap<String, LintLevel>>,
+}
+
+/// Returns the directory the libraries register themselves in, within the
+/// target directory of the workspace so that the registrations survive
+/// between runs.
+///
+/// # Arguments
+/// * `target_directory` (`&Path`) - The target directory of the workspace.
+pub fn directory(target_directory: &Path) -> PathBuf {
+    target_directory.join("security-lints").join("libraries")
+}
+
+/// Reads the libraries registered in `directory`, sorted by name. A missing
+/// directory registers no library.
+///
+/// # Arguments
+/// * `directory` (`&Path`) - The directory the libraries are registered in.
+pub fn load(directory: &Path) -> Result<Vec<Library>> {
+    let entries: fs::ReadDir = match fs::read_dir(directory) {
+        Ok(entries) => entries,
+        Err(error) if error.kind() == ErrorKind::NotFound => {
+            return Ok(Vec::new());
+        },
+        Err(error) => {
+            return Err(error).with_context(|| {
+                format!("could not read `{}`", directory.display())
+            });
+        },
+    };
+
+    let mut libraries: Vec<Library> = Vec::new();
+    for entry in entries {
+        let path: PathBuf = entry?.path();
+        if path.extension().is_none_or(|extension: &OsStr| extension != "toml")
+        {
+            continue;
+        }
+        let Some(name): Option<&str> =
+            path.file_stem().and_then(|stem: &OsStr| stem.to_str())
+        else {
+            continue;
+        };
+
+        libraries.push(Library {
+            name: name.to_owned(),
+            ..parse(&fs::read_to_string(&path)?).with_context(|| {
+                format!("invalid registration `{}`", path.display())
+            })?
+        });
+    }
+    libraries
+        .sort_by(|left: &Library, right: &Library| left.name.cmp(&right.name));
+
+    Ok(libraries)
+}
+
+/// Parses the registration of a library.
+///
+/// # Arguments
+/// * `contents` (`&str`) - The contents of the file registering the library.
+fn parse(contents: &str) -> Result<Library> {
+    Ok(toml::from_str(contents)?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Library, parse};
+    use crate::profile::LintLevel;
+
+    #[test]
+    fn registrations() {
+        let library: Library = parse(
+            "lints = [\"security_acme_unaudited_ffi\"]\n\n\
+             [profiles.crypto]\nsecurity_acme_unaudited_ffi = \"deny\"\n",
+        )
+        .unwrap();
+
+        assert_eq!(library.lints, ["security_acme_unaudited_ffi"]);
+        assert_eq!(
+            library.profiles["crypto"]["security_acme_unaudited_ffi"],
+            LintLevel::Deny
+        );
+        assert!(parse("lints = [1]").is_err());
+        assert!(
+            parse("[profiles.cli]\nsecurity_acme_unaudited_ffi = \"forbid\"")
+                .is_err()
+        );
+    }
+}
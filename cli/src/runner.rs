@@ -0,0 +1,207 @@
+use std::{
+    env,
+    ffi::OsString,
+    path::{Path, PathBuf},
+    process::{Command, Output, Stdio},
+};
+
+use anyhow::{Context, Result, bail};
+use serde::Deserialize;
+
+use crate::registry::REGISTRY_VARIABLE;
+
+/// The subset of the output of `cargo metadata` locating the workspace.
+#[derive(Debug, Deserialize)]
+pub struct Workspace {
+    /// The directory the paths reported by rustc are relative to.
+    pub workspace_root: PathBuf,
+    pub target_directory: PathBuf,
+}
+
+/// Runs every Dylint library of the workspace with
+/// `cargo dylint --all --workspace -- --message-format=json` and returns its
+/// standard output. The exit status of cargo is not checked, as deny-level
+/// findings make it fail; the caller applies its own policy instead.
+///
+/// # Arguments
+/// * `manifest_path` (`Option<&Path>`) - The manifest of the workspace to
+///   lint, or `None` for the workspace of the current directory.
+/// * `registry` (`&Path`) - The directory the lint libraries register
+///   themselves in.
+/// * `cargo_args` (`&[String]`) - Extra arguments forwarded to `cargo check`
+///   (e.g. `--all-targets`).
+/// * `rustc_flags` (`&[String]`) - Extra flags passed to rustc, appended to
+///   those of the environment (e.g. the lint levels of a profile).
+#[cfg_attr(
+    dylint_lib = "process_usage",
+    expect(
+        security_command_inherited_env,
+        reason = "cargo and the Dylint drivers need the toolchain, `PATH` and \
+                  `CARGO_*` variables of the caller"
+    )
+)]
+pub fn run_dylint(
+    manifest_path: Option<&Path>,
+    registry: &Path,
+    cargo_args: &[String],
+    rustc_flags: &[String],
+) -> Result<String> {
+    let mut command: Command = Command::new(cargo());
+    command.args(["dylint", "--all", "--workspace"]);
+    if let Some(manifest_path) = manifest_path {
+        command.arg("--manifest-path").arg(manifest_path);
+    }
+    command.env(REGISTRY_VARIABLE, registry);
+    if !rustc_flags.is_empty() {
+        let (variable, flags): (&str, String) = rustflags(rustc_flags);
+        command.env(variable, flags);
+    }
+
+    let output: Output = command
+        .arg("--")
+        .arg("--message-format=json")
+        .args(cargo_args)
+        .stdout(Stdio::piped())
+        .stderr(Stdio::inherit())
+        .output()
+        .context("could not run `cargo dylint`")?;
+
+    let stdout: String = String::from_utf8(output.stdout)
+        .context("`cargo dylint` printed invalid UTF-8")?;
+    if stdout.trim().is_empty() && !output.status.success() {
+        bail!("`cargo dylint` failed with {}", output.status);
+    }
+
+    Ok(stdout)
+}
+
+/// Returns the output of `cargo metadata --format-version 1` for the
+/// workspace of `manifest_path`.
+///
+/// # Arguments
+/// * `manifest_path` (`Option<&Path>`) - The manifest of the workspace, or
+///   `None` for the workspace of the current directory.
+#[cfg_attr(
+    dylint_lib = "process_usage",
+    expect(
+        security_command_inherited_env,
+        reason = "cargo needs the toolchain, `PATH` and `CARGO_*` variables \
+                  of the caller"
+    )
+)]
+pub fn cargo_metadata(manifest_path: Option<&Path>) -> Result<String> {
+    let mut command: Command = Command::new(cargo());
+    command.args(["metadata", "--format-version", "1"]);
+    if let Some(manifest_path) = manifest_path {
+        command.arg("--manifest-path").arg(manifest_path);
+    }
+
+    let output: Output = command
+        .stderr(Stdio::inherit())
+        .output()
+        .context("could not run `cargo metadata`")?;
+    if !output.status.success() {
+        bail!("`cargo metadata` failed with {}", output.status);
+    }
+
+    String::from_utf8(output.stdout)
+        .context("`cargo metadata` printed invalid UTF-8")
+}
+
+/// Returns the root and target directories of the workspace of
+/// `manifest_path`.
+///
+/// # Arguments
+/// * `manifest_path` (`Option<&Path>`) - The manifest of the workspace, or
+///   `None` for the workspace of the current directory.
+pub fn workspace(manifest_path: Option<&Path>) -> Result<Workspace> {
+    serde_json::from_str(&cargo_metadata(manifest_path)?)
+        .context("invalid `cargo metadata` output")
+}
+
+/// Returns the manifest passed to `cargo check` in `cargo_args`, as
+/// `--manifest-path <path>` or `--manifest-path=<path>`, if any.
+///
+/// # Arguments
+/// * `cargo_args` (`&[String]`) - The arguments forwarded to `cargo check`.
+pub fn manifest_path(cargo_args: &[String]) -> Option<&Path> {
+    let mut arguments: std::slice::Iter<'_, String> = cargo_args.iter();
+    while let Some(argument) = arguments.next() {
+        if argument == "--manifest-path" {
+            return arguments.next().map(Path::new);
+        }
+        if let Some(path) = argument.strip_prefix("--manifest-path=") {
+            return Some(Path::new(path));
+        }
+    }
+
+    None
+}
+
+/// Returns the environment variable cargo reads rustc flags from and its
+/// value with `extra` appended. `CARGO_ENCODED_RUSTFLAGS`, which takes
+/// precedence over `RUSTFLAGS`, is extended when set.
+///
+/// # Arguments
+/// * `extra` (`&[String]`) - The flags to append.
+fn rustflags(extra: &[String]) -> (&'static str, String) {
+    if let Some(encoded) = env::var("CARGO_ENCODED_RUSTFLAGS")
+        .ok()
+        .filter(|encoded: &String| !encoded.is_empty())
+    {
+        return (
+            "CARGO_ENCODED_RUSTFLAGS",
+            [encoded]
+                .into_iter()
+                .chain(extra.iter().cloned())
+                .collect::<Vec<String>>()
+                .join("\x1f"),
+        );
+    }
+
+    let mut flags: Vec<String> = env::var("RUSTFLAGS")
+        .map(|flags: String| {
+            flags.split_whitespace().map(str::to_owned).collect()
+        })
+        .unwrap_or_default();
+    flags.extend(extra.iter().cloned());
+
+    ("RUSTFLAGS", flags.join(" "))
+}
+
+/// Returns the cargo executable to run.
+fn cargo() -> OsString {
+    // When invoked as `cargo security-lints`, cargo exposes its own path.
+    env::var_os("CARGO").unwrap_or_else(|| OsString::from("cargo"))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::Path;
+
+    use super::manifest_path;
+
+    #[test]
+    fn manifest_paths() {
+        let arguments = |arguments: &[&str]| -> Vec<String> {
+            arguments
+                .iter()
+                .map(|argument: &&str| (*argument).to_owned())
+                .collect()
+        };
+
+        assert_eq!(
+            manifest_path(&arguments(&[
+                "--all-targets",
+                "--manifest-path",
+                "app/Cargo.toml"
+            ])),
+            Some(Path::new("app/Cargo.toml"))
+        );
+        assert_eq!(
+            manifest_path(&arguments(&["--manifest-path=app/Cargo.toml"])),
+            Some(Path::new("app/Cargo.toml"))
+        );
+        assert_eq!(manifest_path(&arguments(&["--all-targets"])), None);
+    }
+}
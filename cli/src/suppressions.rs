@@ -0,0 +1,264 @@
+use std::{
+    collections::BTreeMap,
+    io::{self, Write},
+};
+
+use serde::Serialize;
+
+use crate::{
+    expectations::EXPECTATION_LINT,
+    finding::{Finding, is_security_lint},
+};
+
+/// Name of the lint reporting each suppressed finding of a security lint,
+/// declared by the `lint_expectations` library and emitted by every library
+/// through `security_utils`, which the suppressions mode enables.
+pub const SUPPRESSION_LINT: &str = "security_suppressed_finding";
+
+/// Mechanism that silenced a finding, as named in the `suppressed by: ...`
+/// note of a [`SUPPRESSION_LINT`] finding.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Mechanism {
+    AllowAttribute,
+    ExpectAttribute,
+    AllowTable,
+    CommandLine,
+}
+
+impl Mechanism {
+    /// Parses the name of a mechanism.
+    ///
+    /// # Arguments
+    /// * `name` (`&str`) - The name to parse, e.g. `allow-attribute`.
+    fn parse(name: &str) -> Option<Self> {
+        match name {
+            "allow-attribute" => Some(Self::AllowAttribute),
+            "expect-attribute" => Some(Self::ExpectAttribute),
+            "allow-table" => Some(Self::AllowTable),
+            "command-line" => Some(Self::CommandLine),
+            _ => None,
+        }
+    }
+}
+
+/// Number of findings reported and suppressed by each mechanism, for a lint
+/// or for the whole run.
+#[derive(Debug, Default, Clone, PartialEq, Eq, Serialize)]
+pub struct Counts {
+    /// Findings reported, i.e. not suppressed.
+    pub reported: usize,
+    /// Findings silenced by an `#[allow(...)]` attribute.
+    pub allow_attribute: usize,
+    /// Findings silenced by an `#[expect(...)]` attribute.
+    pub expect_attribute: usize,
+    /// Findings silenced by the `[allow]` table of `dylint.toml`.
+    pub allow_table: usize,
+    /// Findings silenced by a `-A` flag, e.g. of a profile.
+    pub command_line: usize,
+}
+
+impl Counts {
+    /// Increments the counter of `mechanism`, or of the reported findings.
+    ///
+    /// # Arguments
+    /// * `mechanism` (`Option<Mechanism>`) - The mechanism that silenced the
+    ///   finding, if any.
+    fn add(&mut self, mechanism: Option<Mechanism>) {
+        let counter: &mut usize = match mechanism {
+            None => &mut self.reported,
+            Some(Mechanism::AllowAttribute) => &mut self.allow_attribute,
+            Some(Mechanism::ExpectAttribute) => &mut self.expect_attribute,
+            Some(Mechanism::AllowTable) => &mut self.allow_table,
+            Some(Mechanism::CommandLine) => &mut self.command_line,
+        };
+        *counter += 1;
+    }
+}
+
+/// The counts of a single lint.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct LintCounts {
+    pub lint: String,
+    #[serde(flatten)]
+    pub counts: Counts,
+}
+
+/// The suppression summary of a run: one row per lint with at least one
+/// finding, reported or suppressed, sorted by lint, their sum and the lints
+/// disabled for the whole run.
+#[derive(Debug, Default, Clone, PartialEq, Eq, Serialize)]
+pub struct Summary {
+    pub lints: Vec<LintCounts>,
+    pub total: Counts,
+    /// The security lints allowed by the `-A` flags of the run (e.g. of a
+    /// profile). rustc does not run a lint allowed for a whole crate, so
+    /// their findings are not counted.
+    pub disabled: Vec<String>,
+}
+
+/// Returns the lint of `finding` and the mechanism that silenced it, if it
+/// is a [`SUPPRESSION_LINT`] finding, or `None` as mechanism for a reported
+/// finding. The suppressed lint is read from the message (``Suppressed
+/// finding of `security_panic_usage` detected.``) and the mechanism from the
+/// `suppressed by: ...` note. Findings of the inventory lints are ignored.
+///
+/// # Arguments
+/// * `finding` (`&Finding`) - The finding to classify.
+fn classify(finding: &Finding) -> Option<(&str, Option<Mechanism>)> {
+    if finding.lint == EXPECTATION_LINT {
+        return None;
+    }
+    if finding.lint != SUPPRESSION_LINT {
+        return Some((&finding.lint, None));
+    }
+
+    let (lint, _): (&str, &str) = finding
+        .message
+        .strip_prefix("Suppressed finding of `")?
+        .split_once('`')?;
+    let mechanism: Mechanism =
+        finding.notes.iter().find_map(|note: &String| {
+            note.strip_prefix("suppressed by: ").and_then(Mechanism::parse)
+        })?;
+
+    is_security_lint(lint).then_some((lint, Some(mechanism)))
+}
+
+/// Counts the reported and suppressed findings of `findings` per lint.
+///
+/// # Arguments
+/// * `findings` (`&[Finding]`) - The findings of the run.
+/// * `rustc_flags` (`&[String]`) - The extra rustc flags of the run, e.g.
+///   `["-A", "security_shift_usage"]`.
+pub fn collect(findings: &[Finding], rustc_flags: &[String]) -> Summary {
+    let mut by_lint: BTreeMap<&str, Counts> = BTreeMap::new();
+    let mut total: Counts = Counts::default();
+
+    for finding in findings {
+        let Some((lint, mechanism)): Option<(&str, Option<Mechanism>)> =
+            classify(finding)
+        else {
+            continue;
+        };
+        by_lint.entry(lint).or_default().add(mechanism);
+        total.add(mechanism);
+    }
+
+    Summary {
+        lints: by_lint
+            .into_iter()
+            .map(|(lint, counts): (&str, Counts)| LintCounts {
+                lint: lint.to_owned(),
+                counts,
+            })
+            .collect(),
+        total,
+        disabled: rustc_flags
+            .windows(2)
+            .filter_map(|pair: &[String]| match pair {
+                [flag, lint] if flag == "-A" && is_security_lint(lint) => {
+                    Some(lint.clone())
+                },
+                _ => None,
+            })
+            .collect(),
+    }
+}
+
+/// Writes `summary` as pretty-printed JSON.
+///
+/// # Arguments
+/// * `out` (`&mut dyn Write`) - The destination of the summary.
+/// * `summary` (`&Summary`) - The summary to write.
+pub fn write(out: &mut dyn Write, summary: &Summary) -> io::Result<()> {
+    serde_json::to_writer_pretty(&mut *out, summary)?;
+    writeln!(out)
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::Value;
+
+    use super::{Counts, Summary, collect};
+    use crate::finding::{Finding, Level};
+
+    fn finding(lint: &str, message: &str, notes: &[&str]) -> Finding {
+        Finding {
+            lint: lint.to_owned(),
+            package: String::from("demo@0.1.0"),
+            level: Level::Warning,
+            message: message.to_owned(),
+            file: String::from("src/lib.rs"),
+            line: 1,
+            column: 1,
+            rendered: String::new(),
+            notes: notes.iter().map(|note: &&str| note.to_string()).collect(),
+            suggestions: Vec::new(),
+            diagnostic: Value::Null,
+        }
+    }
+
+    #[test]
+    fn summary() {
+        let suppressed: &str =
+            "Suppressed finding of `security_panic_usage` detected.";
+        let findings: Vec<Finding> = vec![
+            finding(
+                "security_panic_usage",
+                "Call to panic backend `unwrap/expect` detected.",
+                &[],
+            ),
+            finding(
+                "security_suppressed_finding",
+                suppressed,
+                &["suppressed by: allow-attribute"],
+            ),
+            finding(
+                "security_suppressed_finding",
+                suppressed,
+                &["suppressed by: allow-table"],
+            ),
+            finding(
+                "security_suppressed_finding",
+                "Suppressed finding of `security_shift_usage` detected.",
+                &["suppressed by: command-line"],
+            ),
+            finding(
+                "security_suppressed_finding",
+                suppressed,
+                &["suppressed by: baseline"],
+            ),
+            finding(
+                "security_lint_expectation",
+                "Expectation of `security_panic_usage` detected.",
+                &[],
+            ),
+        ];
+
+        let flags: Vec<String> =
+            ["-D", "security_panic_usage", "-A", "security_zip_truncation"]
+                .map(str::to_owned)
+                .to_vec();
+        let summary: Summary = collect(&findings, &flags);
+
+        assert_eq!(summary.lints.len(), 2);
+        assert_eq!(summary.lints[0].lint, "security_panic_usage");
+        assert_eq!(
+            summary.lints[0].counts,
+            Counts {
+                reported: 1,
+                allow_attribute: 1,
+                allow_table: 1,
+                ..Counts::default()
+            }
+        );
+        assert_eq!(summary.lints[1].counts.command_line, 1);
+        assert_eq!(summary.total.reported, 1);
+        assert_eq!(summary.total.command_line, 1);
+        assert_eq!(
+            serde_json::to_value(&summary.lints[1]).unwrap()["lint"],
+            "security_shift_usage"
+        );
+        assert_eq!(summary.disabled, ["security_zip_truncation"]);
+    }
+}
@@ -0,0 +1,318 @@
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    io::{self, Write},
+    path::Path,
+};
+
+use anyhow::{Context, Result};
+use rusqlite::{Connection, Transaction, params};
+use serde::Serialize;
+
+use crate::finding::{self, Finding};
+
+/// Tables of the trend database: one row per run, and one row per finding of
+/// each run.
+const SCHEMA: &str = "
+CREATE TABLE IF NOT EXISTS runs (
+    id INTEGER PRIMARY KEY,
+    timestamp INTEGER NOT NULL
+);
+CREATE TABLE IF NOT EXISTS findings (
+    run INTEGER NOT NULL REFERENCES runs (id),
+    fingerprint TEXT NOT NULL,
+    lint TEXT NOT NULL,
+    package TEXT NOT NULL,
+    file TEXT NOT NULL,
+    line INTEGER NOT NULL,
+    message TEXT NOT NULL
+);
+CREATE INDEX IF NOT EXISTS findings_run ON findings (run);
+";
+
+/// Number of seconds in a day.
+const SECONDS_PER_DAY: u64 = 86_400;
+
+/// The findings of a recorded run, as their fingerprints and lints.
+#[derive(Debug, Default)]
+pub struct Run {
+    /// The time of the run, in seconds since the Unix epoch.
+    pub timestamp: u64,
+    /// The lint of each finding, by fingerprint.
+    pub findings: BTreeMap<String, String>,
+}
+
+/// The evolution of the findings of a lint over a week.
+#[derive(Debug, Default, Clone, PartialEq, Eq, Serialize)]
+pub struct Trend {
+    /// The Monday starting the week, as `YYYY-MM-DD` (UTC).
+    pub week: String,
+    pub lint: String,
+    /// Findings reported for the first time.
+    pub new: usize,
+    /// Findings of the previous run no longer reported.
+    pub fixed: usize,
+    /// Findings reported again after having been fixed.
+    pub regressed: usize,
+    /// Findings reported by the last run of the week.
+    pub open: usize,
+}
+
+/// The trends of the recorded runs, sorted by week and lint.
+#[derive(Debug, Default, Serialize)]
+pub struct Report {
+    pub trends: Vec<Trend>,
+}
+
+/// Opens the trend database at `path`, creating it and its tables when
+/// missing.
+///
+/// # Arguments
+/// * `path` (`&Path`) - The path of the SQLite database.
+pub fn open(path: &Path) -> Result<Connection> {
+    let connection: Connection =
+        Connection::open(path).with_context(|| {
+            format!("could not open the trend database `{}`", path.display())
+        })?;
+    connection
+        .execute_batch(SCHEMA)
+        .context("could not create the trend database tables")?;
+
+    Ok(connection)
+}
+
+/// Records the findings of a run made at `timestamp`.
+///
+/// # Arguments
+/// * `connection` (`&mut Connection`) - The trend database.
+/// * `timestamp` (`u64`) - The time of the run, in seconds since the Unix
+///   epoch.
+/// * `findings` (`&[Finding]`) - The findings of the run.
+pub fn record(
+    connection: &mut Connection,
+    timestamp: u64,
+    findings: &[Finding],
+) -> Result<()> {
+    let transaction: Transaction<'_> = connection.transaction()?;
+    transaction.execute(
+        "INSERT INTO runs (timestamp) VALUES (?1)",
+        params![timestamp],
+    )?;
+    let run: i64 = transaction.last_insert_rowid();
+
+    for (finding, fingerprint) in finding::fingerprinted(findings) {
+        transaction.execute(
+            "INSERT INTO findings (run, fingerprint, lint, package, file, \
+             line, message) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)",
+            params![
+                run,
+                fingerprint,
+                finding.lint,
+                finding.package,
+                finding.file,
+                finding.line,
+                finding.message,
+            ],
+        )?;
+    }
+
+    transaction.commit().context("could not record the run")
+}
+
+/// Returns the recorded runs, from the oldest to the most recent.
+///
+/// # Arguments
+/// * `connection` (`&Connection`) - The trend database.
+pub fn load(connection: &Connection) -> Result<Vec<Run>> {
+    let mut runs: BTreeMap<(u64, i64), Run> = BTreeMap::new();
+    let mut statement: rusqlite::Statement<'_> = connection.prepare(
+        "SELECT runs.id, runs.timestamp, findings.fingerprint, findings.lint \
+         FROM runs LEFT JOIN findings ON findings.run = runs.id",
+    )?;
+    let mut rows: rusqlite::Rows<'_> = statement.query([])?;
+
+    while let Some(row) = rows.next()? {
+        let id: i64 = row.get(0)?;
+        let timestamp: u64 = row.get(1)?;
+        let run: &mut Run = runs
+            .entry((timestamp, id))
+            .or_insert_with(|| Run { timestamp, findings: BTreeMap::new() });
+        if let (Some(fingerprint), Some(lint)) = (
+            row.get::<_, Option<String>>(2)?,
+            row.get::<_, Option<String>>(3)?,
+        ) {
+            run.findings.insert(fingerprint, lint);
+        }
+    }
+
+    Ok(runs.into_values().collect())
+}
+
+/// Computes the weekly trends of each lint from `runs`, sorted from the
+/// oldest. Each run is compared with the previous one: a finding is new the
+/// first time it is reported, fixed when the previous run reported it and
+/// this one does not, and regressed when it is reported again after having
+/// been fixed.
+///
+/// # Arguments
+/// * `runs` (`&[Run]`) - The recorded runs, from the oldest.
+pub fn trends(runs: &[Run]) -> Report {
+    let mut weeks: BTreeMap<(String, String), Trend> = BTreeMap::new();
+    let mut seen: BTreeSet<&str> = BTreeSet::new();
+    let empty: BTreeMap<String, String> = BTreeMap::new();
+    let mut previous: &BTreeMap<String, String> = &empty;
+
+    for (index, run) in runs.iter().enumerate() {
+        let week: String = week_start(run.timestamp);
+
+        for (fingerprint, lint) in &run.findings {
+            if !seen.contains(fingerprint.as_str()) {
+                trend(&mut weeks, &week, lint).new += 1;
+            } else if !previous.contains_key(fingerprint) {
+                trend(&mut weeks, &week, lint).regressed += 1;
+            }
+        }
+        for (fingerprint, lint) in previous {
+            if !run.findings.contains_key(fingerprint) {
+                trend(&mut weeks, &week, lint).fixed += 1;
+            }
+        }
+
+        // The open findings of a week are those of its last run.
+        let is_last_of_week: bool = runs
+            .get(index + 1)
+            .is_none_or(|next: &Run| week_start(next.timestamp) != week);
+        if is_last_of_week {
+            for lint in run.findings.values() {
+                trend(&mut weeks, &week, lint).open += 1;
+            }
+        }
+
+        seen.extend(run.findings.keys().map(String::as_str));
+        previous = &run.findings;
+    }
+
+    Report { trends: weeks.into_values().collect() }
+}
+
+/// Returns the trend of `lint` over `week`, inserting an empty one if
+/// missing.
+///
+/// # Arguments
+/// * `weeks` (`&mut BTreeMap<(String, String), Trend>`) - The trends, by week
+///   and lint.
+/// * `week` (`&str`) - The Monday starting the week.
+/// * `lint` (`&str`) - The name of the lint.
+fn trend<'a>(
+    weeks: &'a mut BTreeMap<(String, String), Trend>,
+    week: &str,
+    lint: &str,
+) -> &'a mut Trend {
+    weeks.entry((week.to_owned(), lint.to_owned())).or_insert_with(|| Trend {
+        week: week.to_owned(),
+        lint: lint.to_owned(),
+        ..Trend::default()
+    })
+}
+
+/// Returns the Monday starting the week of `timestamp`, as `YYYY-MM-DD`
+/// (UTC).
+///
+/// # Arguments
+/// * `timestamp` (`u64`) - The time, in seconds since the Unix epoch.
+fn week_start(timestamp: u64) -> String {
+    let days: u64 = timestamp / SECONDS_PER_DAY;
+    // Converts the Monday to a civil date, after H. Hinnant's
+    // `civil_from_days`, counting days from 0000-03-01. The Unix epoch,
+    // 1970-01-01, is a Thursday.
+    let shifted: u64 = days + 719_468 - (days + 3) % 7;
+    let era: u64 = shifted / 146_097;
+    let day_of_era: u64 = shifted - era * 146_097;
+    let year_of_era: u64 = (day_of_era - day_of_era / 1460
+        + day_of_era / 36_524
+        - day_of_era / 146_096)
+        / 365;
+    let day_of_year: u64 =
+        day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let month_index: u64 = (5 * day_of_year + 2) / 153;
+    let day: u64 = day_of_year - (153 * month_index + 2) / 5 + 1;
+    let month: u64 =
+        if month_index < 10 { month_index + 3 } else { month_index - 9 };
+    let year: u64 = year_of_era + era * 400 + u64::from(month <= 2);
+
+    format!("{year:04}-{month:02}-{day:02}")
+}
+
+/// Writes `report` as pretty-printed JSON.
+///
+/// # Arguments
+/// * `out` (`&mut dyn Write`) - The destination of the report.
+/// * `report` (`&Report`) - The report to write.
+pub fn write(out: &mut dyn Write, report: &Report) -> io::Result<()> {
+    serde_json::to_writer_pretty(&mut *out, report)?;
+    writeln!(out)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeMap;
+
+    use super::{Report, Run, Trend, trends, week_start};
+
+    /// A Monday, 2026-10-12 00:00 UTC.
+    const MONDAY: u64 = 1_791_763_200;
+    const WEEK: u64 = 7 * 86_400;
+
+    fn run(timestamp: u64, findings: &[(&str, &str)]) -> Run {
+        Run {
+            timestamp,
+            findings: findings
+                .iter()
+                .map(|(fingerprint, lint): &(&str, &str)| {
+                    (fingerprint.to_string(), lint.to_string())
+                })
+                .collect::<BTreeMap<String, String>>(),
+        }
+    }
+
+    fn row(week: &str, counts: [usize; 4]) -> Trend {
+        let [new, fixed, regressed, open]: [usize; 4] = counts;
+        Trend {
+            week: week.to_owned(),
+            lint: "security_panic_usage".to_owned(),
+            new,
+            fixed,
+            regressed,
+            open,
+        }
+    }
+
+    #[test]
+    fn weeks() {
+        assert_eq!(week_start(0), "1969-12-29");
+        assert_eq!(week_start(MONDAY), "2026-10-12");
+        assert_eq!(week_start(MONDAY + WEEK - 1), "2026-10-12");
+        assert_eq!(week_start(MONDAY + WEEK), "2026-10-19");
+        assert_eq!(week_start(951_782_400), "2000-02-28");
+    }
+
+    #[test]
+    fn trend() {
+        let panic: &str = "security_panic_usage";
+        let runs: Vec<Run> = vec![
+            run(MONDAY, &[("a", panic), ("b", panic)]),
+            run(MONDAY + 3600, &[("a", panic)]),
+            run(MONDAY + WEEK, &[("a", panic), ("b", panic), ("c", panic)]),
+            run(MONDAY + 2 * WEEK, &[]),
+        ];
+
+        let report: Report = trends(&runs);
+        assert_eq!(
+            report.trends,
+            [
+                row("2026-10-12", [2, 1, 0, 1]),
+                row("2026-10-19", [1, 0, 1, 3]),
+                row("2026-10-26", [0, 3, 0, 0]),
+            ]
+        );
+    }
+}
@@ -0,0 +1,665 @@
+#![feature(rustc_private)]
+
+extern crate rustc_data_structures;
+extern crate rustc_errors;
+extern crate rustc_hir;
+extern crate rustc_lint;
+extern crate rustc_middle;
+extern crate rustc_session;
+extern crate rustc_span;
+
+use rustc_data_structures::fx::FxHashSet;
+use rustc_errors::Diag;
+use rustc_hir::{
+    BinOpKind,
+    Expr,
+    ExprKind,
+    Item,
+    ItemKind,
+    Node,
+    QPath,
+    def::{DefKind, Res},
+    def_id::{DefId, LocalDefId},
+    intravisit::{Visitor, walk_expr},
+};
+use rustc_lint::{LateContext, LateLintPass, LintStore};
+use rustc_middle::ty::{Ty, TyCtxt, TyKind};
+use rustc_session::{Session, declare_lint, impl_lint_pass};
+use rustc_span::{ExpnData, ExpnKind, MacroKind, Span, Symbol, sym};
+use security_utils::{
+    calls::{Call, call},
+    known::DefIdCache,
+};
+use serde::Deserialize;
+
+declare_lint! {
+    pub SECURITY_ALLOCATION_SIZE_OVERFLOW,
+    Warn,
+    "Detects unchecked multiplications computing the size of an allocation."
+}
+
+declare_lint! {
+    pub SECURITY_MANUAL_ALLOCATION,
+    Warn,
+    "Detects direct calls to the allocator API and layouts unwrapped from
+    `Layout::from_size_align`."
+}
+
+declare_lint! {
+    pub SECURITY_CUSTOM_GLOBAL_ALLOCATOR,
+    Warn,
+    "Detects implementations of `GlobalAlloc` and `#[global_allocator]`
+    statics whose allocator is not trusted."
+}
+
+/// Configuration of the `alloc_usage` library, read from the
+/// `[alloc_usage]` table of the workspace's `dylint.toml`.
+#[derive(Deserialize)]
+#[serde(default)]
+struct Config {
+    /// Paths of the functions taking the size or length of an allocation
+    /// (e.g. `std::vec::Vec::with_capacity`). An argument computed with an
+    /// unchecked multiplication is reported.
+    size_functions: Vec<String>,
+    /// Paths of the functions allocating or freeing memory directly
+    /// (e.g. `std::alloc::alloc`). A call to one of them is reported.
+    allocator_functions: Vec<String>,
+    /// Paths of the fallible layout constructors (e.g.
+    /// `std::alloc::Layout::from_size_align`). Unwrapping their result with
+    /// `unwrap` or `expect` is reported.
+    layout_functions: Vec<String>,
+    /// Paths of the allocator types trusted as `#[global_allocator]` (e.g.
+    /// `std::alloc::System`). A global allocator of another type is
+    /// reported.
+    trusted_allocators: Vec<String>,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Self {
+            size_functions: [
+                "std::vec::Vec::with_capacity",
+                "std::vec::Vec::reserve",
+                "std::vec::Vec::reserve_exact",
+                "std::vec::Vec::from_raw_parts",
+                "std::string::String::with_capacity",
+                "std::collections::VecDeque::with_capacity",
+                "std::alloc::alloc",
+                "std::alloc::alloc_zeroed",
+                "std::alloc::realloc",
+                "std::alloc::Layout::from_size_align",
+                "std::alloc::Layout::from_size_align_unchecked",
+                "std::slice::from_raw_parts",
+                "std::slice::from_raw_parts_mut",
+            ]
+            .into_iter()
+            .map(String::from)
+            .collect(),
+            allocator_functions: [
+                "std::alloc::alloc",
+                "std::alloc::alloc_zeroed",
+                "std::alloc::dealloc",
+                "std::alloc::realloc",
+            ]
+            .into_iter()
+            .map(String::from)
+            .collect(),
+            layout_functions: ["std::alloc::Layout::from_size_align"]
+                .into_iter()
+                .map(String::from)
+                .collect(),
+            trusted_allocators: ["std::alloc::System"]
+                .into_iter()
+                .map(String::from)
+                .collect(),
+        }
+    }
+}
+
+/// Lint pass holding the size, allocator and layout functions and the
+/// trusted allocators loaded from the configuration, whether each called
+/// function is an allocator and a size function, and the global allocators
+/// already reported.
+struct SecurityAllocUsage {
+    size_functions: Vec<String>,
+    allocator_functions: Vec<String>,
+    layout_functions: Vec<String>,
+    trusted_allocators: Vec<String>,
+    functions: DefIdCache<(bool, bool)>,
+    global_allocators: FxHashSet<LocalDefId>,
+}
+
+impl_lint_pass!(SecurityAllocUsage => [
+    SECURITY_ALLOCATION_SIZE_OVERFLOW,
+    SECURITY_MANUAL_ALLOCATION,
+    SECURITY_CUSTOM_GLOBAL_ALLOCATOR,
+]);
+
+/// Returns `true` if `expression` is a literal or a constant, whose products
+/// are evaluated, and checked for overflow, at compile time.
+///
+/// # Arguments
+/// * `context` (`&LateContext<'_>`) - The lint context, used to resolve paths.
+/// * `expression` (`&Expr<'_>`) - The expression to inspect.
+fn is_constant(context: &LateContext<'_>, expression: &Expr<'_>) -> bool {
+    match &expression.kind {
+        ExprKind::Lit(_) => true,
+        ExprKind::Path(path) => matches!(
+            context.qpath_res(path, expression.hir_id),
+            Res::Def(DefKind::Const | DefKind::AssocConst, _)
+        ),
+        ExprKind::Cast(inner, _) => is_constant(context, inner),
+        ExprKind::Binary(_, left, right) => {
+            is_constant(context, left) && is_constant(context, right)
+        },
+        _ => false,
+    }
+}
+
+/// Returns the unchecked multiplication computing `expression`, if any:
+/// `expression` itself, behind casts, or the initializer of the local it
+/// reads, e.g. `size` in `let size = count * size_of::<T>();`. Products of
+/// constants are not reported.
+///
+/// # Arguments
+/// * `context` (`&LateContext<'tcx>`) - The lint context, used to resolve
+///   locals.
+/// * `expression` (`&'tcx Expr<'tcx>`) - The size argument.
+fn unchecked_product<'tcx>(
+    context: &LateContext<'tcx>,
+    expression: &'tcx Expr<'tcx>,
+) -> Option<&'tcx Expr<'tcx>> {
+    match &expression.kind {
+        ExprKind::Binary(operator, left, right)
+            if operator.node == BinOpKind::Mul =>
+        {
+            (!is_constant(context, left) || !is_constant(context, right))
+                .then_some(expression)
+        },
+        ExprKind::Cast(inner, _) => unchecked_product(context, inner),
+        ExprKind::Path(path) => {
+            let Res::Local(id): Res =
+                context.qpath_res(path, expression.hir_id)
+            else {
+                return None;
+            };
+            let Node::LetStmt(local): Node<'tcx> =
+                context.tcx.parent_hir_node(id)
+            else {
+                return None;
+            };
+            unchecked_product(context, local.init?)
+        },
+        _ => None,
+    }
+}
+
+/// Returns the path of the ADT `ty`, behind any references, if it is one.
+///
+/// # Arguments
+/// * `context` (`&LateContext<'_>`) - The lint context, used to name the ADT.
+/// * `ty` (`Ty<'_>`) - The type to inspect.
+fn adt_path(context: &LateContext<'_>, ty: Ty<'_>) -> Option<String> {
+    match ty.peel_refs().kind() {
+        TyKind::Adt(adt, _) => Some(context.tcx.def_path_str(adt.did())),
+        _ => None,
+    }
+}
+
+/// Returns `true` if `def_id` is the `GlobalAlloc` trait of `core`/`std`.
+///
+/// # Arguments
+/// * `tcx` (`TyCtxt<'_>`) - The type context used to name the trait.
+/// * `def_id` (`DefId`) - The implemented trait.
+fn is_global_alloc(tcx: TyCtxt<'_>, def_id: DefId) -> bool {
+    let krate: Symbol = tcx.crate_name(def_id.krate);
+    (krate == sym::core || krate == sym::std)
+        && tcx.item_name(def_id) == sym::GlobalAlloc
+}
+
+/// Returns `true` if `span` was produced by the `#[global_allocator]`
+/// attribute, which generates the allocator shims of the crate.
+///
+/// # Arguments
+/// * `span` (`Span`) - The span whose expansion is inspected.
+fn expanded_from_global_allocator(span: Span) -> bool {
+    let data: ExpnData = span.ctxt().outer_expn_data();
+    matches!(
+        data.kind,
+        ExpnKind::Macro(MacroKind::Attr, name) if name == sym::global_allocator
+    )
+}
+
+/// Visitor collecting the local statics read by the body of an allocator
+/// shim, i.e. the `#[global_allocator]` static it forwards to.
+struct StaticCollector<'a> {
+    statics: &'a mut Vec<LocalDefId>,
+}
+
+impl<'tcx> Visitor<'tcx> for StaticCollector<'_> {
+    fn visit_expr(&mut self, expression: &'tcx Expr<'tcx>) {
+        if let ExprKind::Path(QPath::Resolved(_, path)) = &expression.kind
+            && let Res::Def(DefKind::Static { .. }, id) = path.res
+            && let Some(local) = id.as_local()
+        {
+            self.statics.push(local);
+        }
+
+        walk_expr(self, expression);
+    }
+}
+
+impl SecurityAllocUsage {
+    fn new(tcx: TyCtxt<'_>) -> Self {
+        let config: Config =
+            security_utils::config::load(tcx, env!("CARGO_PKG_NAME"));
+
+        Self {
+            size_functions: config.size_functions,
+            allocator_functions: config.allocator_functions,
+            layout_functions: config.layout_functions,
+            trusted_allocators: config.trusted_allocators,
+            functions: DefIdCache::default(),
+            global_allocators: FxHashSet::default(),
+        }
+    }
+
+    /// Returns the path of the layout function whose result `expression`
+    /// unwraps with `unwrap` or `expect`, if it is one of the
+    /// `layout_functions`.
+    ///
+    /// # Arguments
+    /// * `context` (`&LateContext<'tcx>`) - The lint context, used to resolve
+    ///   the call.
+    /// * `expression` (`&'tcx Expr<'tcx>`) - The expression to inspect.
+    fn unwrapped_layout<'tcx>(
+        &self,
+        context: &LateContext<'tcx>,
+        expression: &'tcx Expr<'tcx>,
+    ) -> Option<String> {
+        let ExprKind::MethodCall(segment, receiver, _, _): &ExprKind<'_> =
+            &expression.kind
+        else {
+            return None;
+        };
+        if !matches!(segment.ident.as_str(), "unwrap" | "expect") {
+            return None;
+        }
+
+        let Call { callee: id, .. }: Call<'_> = call(context, receiver)?;
+        let path: String =
+            security_utils::paths::function_path(context.tcx, id);
+        self.layout_functions.contains(&path).then_some(path)
+    }
+}
+
+/// Emits a `SECURITY_ALLOCATION_SIZE_OVERFLOW` finding at `span`, the size
+/// argument of `name` computed by the multiplication `product`.
+///
+/// # Arguments
+/// * `context` (`&LateContext<'_>`) - The lint context used to emit the
+///   diagnostic.
+/// * `span` (`Span`) - The span of the size argument.
+/// * `name` (`Symbol`) - The name of the allocating function.
+/// * `product` (`Span`) - The span of the multiplication.
+fn report_size(
+    context: &LateContext<'_>,
+    span: Span,
+    name: Symbol,
+    product: Span,
+) {
+    security_utils::span_lint(
+        context,
+        SECURITY_ALLOCATION_SIZE_OVERFLOW,
+        span,
+        |diagnostic: &mut Diag<'_, ()>| {
+            diagnostic.primary_message(format!(
+                "Unchecked size multiplication passed to `{name}` detected."
+            ));
+            if product != span {
+                diagnostic.span_note(product, "the size is computed here");
+            }
+            diagnostic.note(
+                "with overflow checks disabled, as in the release profile, \
+                 the product wraps around and the allocation is smaller than \
+                 the elements later written to it (heap overflow)",
+            );
+            diagnostic.help(
+                "compute the size with `checked_mul` and handle the overflow, \
+                 or use `Layout::array::<T>(count)`",
+            );
+        },
+    );
+}
+
+/// Emits a `SECURITY_MANUAL_ALLOCATION` finding at `span`, a call to the
+/// allocator function `name`, with the pitfalls of that function.
+///
+/// # Arguments
+/// * `context` (`&LateContext<'_>`) - The lint context used to emit the
+///   diagnostic.
+/// * `span` (`Span`) - The span of the call.
+/// * `name` (`Symbol`) - The name of the allocator function.
+fn report_allocator(context: &LateContext<'_>, span: Span, name: Symbol) {
+    security_utils::span_lint(
+        context,
+        SECURITY_MANUAL_ALLOCATION,
+        span,
+        |diagnostic: &mut Diag<'_, ()>| {
+            diagnostic.primary_message(format!(
+                "Manual memory management through `{name}` detected."
+            ));
+            match name.as_str() {
+                "alloc" | "alloc_zeroed" => {
+                    diagnostic.note(
+                        "allocating a zero-sized layout is undefined \
+                         behavior, and the returned pointer is null when the \
+                         allocation fails, which must be checked (e.g. with \
+                         `handle_alloc_error`)",
+                    );
+                },
+                "dealloc" => {
+                    diagnostic.note(
+                        "the pointer must come from the same allocator, with \
+                         the same layout, and must not be used or freed again \
+                         afterwards",
+                    );
+                },
+                "realloc" => {
+                    diagnostic.note(
+                        "the new size must not be zero, the old pointer is \
+                         invalid once the call succeeds, and the old block \
+                         stays allocated when it returns null",
+                    );
+                },
+                _ => {
+                    diagnostic.note(
+                        "manual memory management bypasses the ownership \
+                         guarantees of the standard collections",
+                    );
+                },
+            }
+            diagnostic.help(
+                "prefer `Box`, `Vec` or another owning abstraction, or review \
+                 the size, alignment and lifetime of every block",
+            );
+        },
+    );
+}
+
+/// Emits a `SECURITY_MANUAL_ALLOCATION` finding at `span`, a layout built by
+/// `function` and unwrapped.
+///
+/// # Arguments
+/// * `context` (`&LateContext<'_>`) - The lint context used to emit the
+///   diagnostic.
+/// * `span` (`Span`) - The span of the unwrapped call.
+/// * `function` (`&str`) - The path of the layout function.
+fn report_layout(context: &LateContext<'_>, span: Span, function: &str) {
+    security_utils::span_lint(
+        context,
+        SECURITY_MANUAL_ALLOCATION,
+        span,
+        |diagnostic: &mut Diag<'_, ()>| {
+            diagnostic.primary_message(format!(
+                "Unwrapped layout from `{function}` detected."
+            ));
+            diagnostic.note(
+                "the layout is rejected when the alignment is not a power of \
+                 two or the size rounded up to it overflows `isize`, and a \
+                 zero size is accepted although allocating it is undefined \
+                 behavior",
+            );
+            diagnostic.help(
+                "use `Layout::new::<T>()` or `Layout::array::<T>(count)`, and \
+                 propagate the `LayoutError`",
+            );
+        },
+    );
+}
+
+/// Emits a `SECURITY_CUSTOM_GLOBAL_ALLOCATOR` finding at `span`, an
+/// implementation of `GlobalAlloc` for `ty`, with the invariants it must
+/// uphold.
+///
+/// # Arguments
+/// * `context` (`&LateContext<'_>`) - The lint context used to emit the
+///   diagnostic.
+/// * `span` (`Span`) - The span of the implementation.
+/// * `ty` (`Ty<'_>`) - The implementing type.
+fn report_global_alloc(context: &LateContext<'_>, span: Span, ty: Ty<'_>) {
+    security_utils::span_lint(
+        context,
+        SECURITY_CUSTOM_GLOBAL_ALLOCATOR,
+        span,
+        |diagnostic: &mut Diag<'_, ()>| {
+            diagnostic.primary_message(format!(
+                "Implementation of `GlobalAlloc` for `{ty}` detected."
+            ));
+            diagnostic.note(
+                "a bug in an allocator undermines the memory safety of every \
+                 allocation of the program",
+            );
+            diagnostic.note(
+                "the returned blocks must be aligned to `layout.align()` and \
+                 span `layout.size()` bytes, `alloc_zeroed` must zero them, \
+                 and `realloc` must preserve their contents",
+            );
+            diagnostic.note(
+                "the allocator is shared by every thread: it must be \
+                 thread-safe, must not allocate through itself and must not \
+                 unwind",
+            );
+            diagnostic.help(
+                "prefer `std::alloc::System` or a reviewed allocator crate, \
+                 and fuzz the implementation with every layout",
+            );
+        },
+    );
+}
+
+/// Emits a `SECURITY_CUSTOM_GLOBAL_ALLOCATOR` finding at `span`, the
+/// `#[global_allocator]` static `name` of the untrusted type `ty`.
+///
+/// # Arguments
+/// * `context` (`&LateContext<'_>`) - The lint context used to emit the
+///   diagnostic.
+/// * `span` (`Span`) - The span of the static.
+/// * `name` (`Symbol`) - The name of the static.
+/// * `ty` (`Ty<'_>`) - The type of the static.
+fn report_global_allocator(
+    context: &LateContext<'_>,
+    span: Span,
+    name: Symbol,
+    ty: Ty<'_>,
+) {
+    security_utils::span_lint(
+        context,
+        SECURITY_CUSTOM_GLOBAL_ALLOCATOR,
+        span,
+        |diagnostic: &mut Diag<'_, ()>| {
+            diagnostic.primary_message(format!(
+                "Global allocator `{name}` of type `{ty}` detected."
+            ));
+            diagnostic.note(
+                "every allocation of the program, including those of the \
+                 standard library, goes through this allocator",
+            );
+            diagnostic.help(
+                "review its alignment, zeroing and thread safety, or add its \
+                 type to `trusted_allocators` once reviewed",
+            );
+        },
+    );
+}
+
+impl<'tcx> LateLintPass<'tcx> for SecurityAllocUsage {
+    /// Detect implementations of `GlobalAlloc`, and the `#[global_allocator]`
+    /// statics forwarded to by the allocator shims the attribute generates,
+    /// unless their type is one of the `trusted_allocators`. Each static is
+    /// reported once, although every shim reads it.
+    ///
+    /// # Arguments
+    /// * `context` (`&LateContext<'tcx>`) - The lint context providing access
+    ///   to the compiler's internal state.
+    /// * `item` (`&'tcx Item<'tcx>`) - The item being checked for custom
+    ///   allocators.
+    fn check_item(
+        &mut self,
+        context: &LateContext<'tcx>,
+        item: &'tcx Item<'tcx>,
+    ) {
+        match &item.kind {
+            ItemKind::Impl(implementation) => {
+                if let Some(trait_impl) = implementation.of_trait
+                    && let Some(id) =
+                        trait_impl.trait_ref.path.res.opt_def_id()
+                    && is_global_alloc(context.tcx, id)
+                {
+                    report_global_alloc(
+                        context,
+                        item.span,
+                        context
+                            .tcx
+                            .type_of(item.owner_id)
+                            .instantiate_identity(),
+                    );
+                }
+            },
+            ItemKind::Fn { body, .. } => {
+                if !expanded_from_global_allocator(item.span) {
+                    return;
+                }
+
+                let mut statics: Vec<LocalDefId> = Vec::new();
+                StaticCollector { statics: &mut statics }
+                    .visit_expr(context.tcx.hir_body(*body).value);
+
+                for id in statics {
+                    if !self.global_allocators.insert(id) {
+                        continue;
+                    }
+                    let ty: Ty<'_> =
+                        context.tcx.type_of(id).instantiate_identity();
+                    if adt_path(context, ty).is_some_and(|path: String| {
+                        self.trusted_allocators.contains(&path)
+                    }) {
+                        continue;
+                    }
+                    report_global_allocator(
+                        context,
+                        context.tcx.def_span(id),
+                        context.tcx.item_name(id.to_def_id()),
+                        ty,
+                    );
+                }
+            },
+            _ => {},
+        }
+    }
+
+    /// Detect calls to the configured size functions, e.g.
+    /// `Vec::with_capacity`, `alloc::alloc` or `slice::from_raw_parts`,
+    /// whose size argument is computed with an unchecked multiplication such
+    /// as `count * size_of::<T>()`, directly or through a local. Calls to
+    /// the configured allocator functions, e.g. `alloc::dealloc`, and
+    /// layouts from `Layout::from_size_align(..).unwrap()` are reported as
+    /// manual memory management.
+    ///
+    /// # Arguments
+    /// * `context` (`&LateContext<'tcx>`) - The lint context providing access
+    ///   to the compiler's internal state.
+    /// * `expression` (`&'tcx Expr<'tcx>`) - The expression being checked for
+    ///   allocation sizes.
+    fn check_expr(
+        &mut self,
+        context: &LateContext<'tcx>,
+        expression: &'tcx Expr<'tcx>,
+    ) {
+        if expression.span.from_expansion() {
+            return;
+        }
+        if let Some(function) = self.unwrapped_layout(context, expression) {
+            report_layout(context, expression.span, &function);
+        }
+        let Some(Call { callee: id, arguments, .. }): Option<Call<'_>> =
+            call(context, expression)
+        else {
+            return;
+        };
+        let (allocator, size): (bool, bool) =
+            self.functions.get_or_insert_with(id, || {
+                let path: String =
+                    security_utils::paths::function_path(context.tcx, id);
+                (
+                    self.allocator_functions.contains(&path),
+                    self.size_functions.contains(&path),
+                )
+            });
+        if allocator {
+            report_allocator(
+                context,
+                expression.span,
+                context.tcx.item_name(id),
+            );
+        }
+        if !size {
+            return;
+        }
+
+        for argument in arguments {
+            if let Some(product) = unchecked_product(context, argument) {
+                report_size(
+                    context,
+                    argument.span,
+                    context.tcx.item_name(id),
+                    product.span,
+                );
+            }
+        }
+    }
+}
+
+/// Registers the `SECURITY_ALLOCATION_SIZE_OVERFLOW`,
+/// `SECURITY_MANUAL_ALLOCATION` and `SECURITY_CUSTOM_GLOBAL_ALLOCATOR` lints
+/// and their shared lint pass with the Rust compiler. The size, allocator and
+/// layout functions and the trusted allocators are read from the
+/// `[alloc_usage]` table of `dylint.toml` when the pass is created.
+///
+/// # Arguments
+/// * `session` (`&Session`) - The compiler session providing access to the
+///   compiler's internal state.
+/// * `lint_store` (`&mut LintStore`) - The lint store where the lints and
+///   their lint pass will be registered.
+#[unsafe(no_mangle)]
+pub fn register_lints(session: &Session, lint_store: &mut LintStore) {
+    dylint_linting::init_config(session);
+
+    security_utils::tool::register_lints(
+        lint_store,
+        &[
+            SECURITY_ALLOCATION_SIZE_OVERFLOW,
+            SECURITY_MANUAL_ALLOCATION,
+            SECURITY_CUSTOM_GLOBAL_ALLOCATOR,
+        ],
+    );
+    lint_store.register_late_pass(|tcx: TyCtxt<'_>| {
+        Box::new(SecurityAllocUsage::new(tcx))
+    });
+}
+
+dylint_linting::dylint_library!();
+
+/// UI tests for the `SECURITY_ALLOCATION_SIZE_OVERFLOW`,
+/// `SECURITY_MANUAL_ALLOCATION` and `SECURITY_CUSTOM_GLOBAL_ALLOCATOR` lints.
+#[cfg(test)]
+mod tests {
+    use dylint_testing::ui::Test;
+
+    #[test]
+    fn ui() {
+        Test::src_base(env!("CARGO_PKG_NAME"), "ui")
+            .rustc_flags(["--edition=2024", "-Z", "ui-testing"])
+            .run();
+    }
+}
@@ -0,0 +1,45 @@
+#![allow(security_allocation_size_overflow)]
+
+use std::alloc::{Layout, alloc, alloc_zeroed, dealloc, realloc};
+
+struct Block {
+    pointer: *mut u8,
+    layout: Layout,
+}
+
+unsafe fn allocate(size: usize) -> Option<Block> {
+    let layout: Layout = Layout::from_size_align(size, 8).unwrap(); // Should trigger.
+    let pointer: *mut u8 = unsafe { alloc(layout) }; // Should trigger.
+    (!pointer.is_null()).then_some(Block { pointer, layout })
+}
+
+unsafe fn zeroed(size: usize) -> *mut u8 {
+    let layout: Layout = Layout::from_size_align(size, 8).expect("valid layout"); // Should trigger.
+    unsafe { alloc_zeroed(layout) } // Should trigger.
+}
+
+unsafe fn grow(block: &mut Block, size: usize) {
+    block.pointer = unsafe { realloc(block.pointer, block.layout, size) }; // Should trigger.
+}
+
+unsafe fn free(block: Block) {
+    unsafe { dealloc(block.pointer, block.layout) }; // Should trigger.
+}
+
+fn layouts(count: usize) -> Result<Layout, std::alloc::LayoutError> {
+    let _: Layout = Layout::new::<u64>(); // Should not trigger.
+    let _: Layout = Layout::from_size_align(count, 8)?; // Should not trigger (propagated).
+    Layout::array::<u64>(count) // Should not trigger.
+}
+
+fn main() {
+    let _: Box<[u8]> = vec![0; 16].into_boxed_slice(); // Should not trigger.
+    let _ = layouts(4);
+    unsafe {
+        if let Some(mut block) = allocate(16) {
+            grow(&mut block, 32);
+            free(block);
+        }
+        let _ = zeroed(8);
+    }
+}
@@ -0,0 +1,49 @@
+#![allow(security_manual_allocation)]
+
+use std::alloc::{Layout, alloc, dealloc};
+use std::mem::size_of;
+
+const HEADER: usize = 16;
+const ENTRIES: usize = 64;
+
+fn buffer(count: usize, width: usize) -> Vec<u8> {
+    Vec::with_capacity(count * width) // Should trigger.
+}
+
+fn records(count: usize) -> Vec<u64> {
+    let bytes: usize = count * size_of::<u64>();
+    let mut records: Vec<u64> = Vec::new();
+    records.reserve(bytes); // Should trigger (through a local).
+    records
+}
+
+fn text(lines: u32, columns: u32) -> String {
+    String::with_capacity((lines * columns) as usize) // Should trigger (behind a cast).
+}
+
+unsafe fn raw(count: usize) {
+    let layout: Layout = Layout::from_size_align(count * size_of::<u32>(), 4).unwrap(); // Should trigger.
+    unsafe {
+        let pointer: *mut u8 = alloc(layout);
+        let _: &[u32] = std::slice::from_raw_parts(pointer.cast::<u32>(), count * 2); // Should trigger.
+        dealloc(pointer, layout);
+    }
+}
+
+fn checked(count: usize, width: usize) -> Option<Vec<u8>> {
+    let size: usize = count.checked_mul(width)?;
+    Some(Vec::with_capacity(size)) // Should not trigger (checked).
+}
+
+fn table() -> Vec<u8> {
+    Vec::with_capacity(HEADER * ENTRIES) // Should not trigger (constant).
+}
+
+fn array(count: usize) -> Option<Layout> {
+    Layout::array::<u32>(count).ok() // Should not trigger (checked by `array`).
+}
+
+fn main() {
+    let _ = (buffer(1, 2), records(1), text(1, 2), checked(1, 2), table(), array(1));
+    unsafe { raw(1) };
+}
@@ -0,0 +1,6 @@
+use std::alloc::System;
+
+#[global_allocator]
+static ALLOCATOR: System = System;
+
+fn main() {}
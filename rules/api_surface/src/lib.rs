@@ -0,0 +1,612 @@
+#![feature(rustc_private)]
+
+extern crate rustc_abi;
+extern crate rustc_errors;
+extern crate rustc_hir;
+extern crate rustc_lint;
+extern crate rustc_middle;
+extern crate rustc_session;
+extern crate rustc_span;
+
+use rustc_abi::ExternAbi;
+use rustc_errors::{Applicability, Diag};
+use rustc_hir::{
+    FieldDef,
+    FnDecl,
+    ImplItem,
+    ImplItemKind,
+    Item,
+    ItemKind,
+    TraitItem,
+    TraitItemKind,
+    VariantData,
+    attrs::AttributeKind,
+    def::DefKind,
+    def_id::{DefId, LocalDefId},
+    find_attr,
+};
+use rustc_lint::{LateContext, LateLintPass, LintStore};
+use rustc_middle::ty::{self, GenericArg, GenericArgsRef, Ty, TyCtxt};
+use rustc_session::{
+    Session,
+    config::CrateType,
+    declare_lint,
+    impl_lint_pass,
+};
+use rustc_span::{Ident, Span, sym};
+use security_utils::allow::matches_pattern;
+use serde::Deserialize;
+
+declare_lint! {
+    pub SECURITY_MISSING_MUST_USE,
+    Warn,
+    "Detects functions returning guards or verification verdicts without `#[must_use]`, whose result can be dropped unexamined."
+}
+
+declare_lint! {
+    pub SECURITY_PUBLIC_RAW_POINTER,
+    Warn,
+    "Detects raw pointers in the exported functions and fields of library crates, which force `unsafe` onto downstream users."
+}
+
+declare_lint! {
+    pub SECURITY_EXHAUSTIVE_ERROR_ENUM,
+    Warn,
+    "Detects public error enums without `#[non_exhaustive]`, whose new variants break the exhaustive matches of downstream crates."
+}
+
+/// Configuration of the `api_surface` library, read from the
+/// `[api_surface]` table of the workspace's `dylint.toml`.
+#[derive(Deserialize)]
+#[serde(default)]
+struct Config {
+    /// Patterns of the names of the guard-like and verdict-like types (e.g.
+    /// `*Guard`), where `*` matches any sequence of characters. A function
+    /// returning one of them without `#[must_use]` is reported, unless the
+    /// type itself is `#[must_use]`.
+    must_use_types: Vec<String>,
+    /// Patterns of the names of the error types whose `Result<(), E>` is a
+    /// verification verdict (e.g. `AuthError`). A function returning such a
+    /// result without `#[must_use]` is reported.
+    must_use_errors: Vec<String>,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Self {
+            must_use_types: ["*Guard", "*Permit", "Verified*"]
+                .into_iter()
+                .map(String::from)
+                .collect(),
+            must_use_errors: ["AuthError", "*VerificationError"]
+                .into_iter()
+                .map(String::from)
+                .collect(),
+        }
+    }
+}
+
+/// Lint pass holding the type patterns loaded from the configuration, and
+/// whether the crate is a Rust library.
+struct SecurityApiSurface {
+    must_use_types: Vec<String>,
+    must_use_errors: Vec<String>,
+    library: bool,
+}
+
+impl_lint_pass!(SecurityApiSurface => [
+    SECURITY_MISSING_MUST_USE,
+    SECURITY_PUBLIC_RAW_POINTER,
+    SECURITY_EXHAUSTIVE_ERROR_ENUM,
+]);
+
+impl SecurityApiSurface {
+    fn new(tcx: TyCtxt<'_>) -> Self {
+        let config: Config =
+            security_utils::config::load(tcx, env!("CARGO_PKG_NAME"));
+
+        Self {
+            must_use_types: config.must_use_types,
+            must_use_errors: config.must_use_errors,
+            library: false,
+        }
+    }
+
+    /// Returns `true` if `ty` is a guard-like or verdict-like type whose
+    /// value must be examined: an ADT named after one of the
+    /// `must_use_types` and not itself `#[must_use]`, or a `Result<(), E>`
+    /// whose error is named after one of the `must_use_errors`.
+    ///
+    /// # Arguments
+    /// * `tcx` (`TyCtxt<'tcx>`) - The type context used to inspect the type.
+    /// * `ty` (`Ty<'tcx>`) - The return type of a function.
+    fn is_must_use_type<'tcx>(&self, tcx: TyCtxt<'tcx>, ty: Ty<'tcx>) -> bool {
+        let ty::Adt(adt, args): &ty::TyKind<'tcx> = ty.kind() else {
+            return false;
+        };
+
+        if tcx.is_diagnostic_item(sym::Result, adt.did()) {
+            return is_unit_result_of(tcx, args, &self.must_use_errors);
+        }
+
+        matches_name(tcx, adt.did(), &self.must_use_types)
+            && !find_attr!(
+                tcx.get_all_attrs(adt.did()),
+                AttributeKind::MustUse { .. }
+            )
+    }
+
+    /// Reports the function `def_id` if it returns a guard-like or
+    /// verdict-like type without being `#[must_use]`.
+    ///
+    /// # Arguments
+    /// * `context` (`&LateContext<'_>`) - The lint context used to emit the
+    ///   diagnostic.
+    /// * `def_id` (`LocalDefId`) - The function or method.
+    /// * `ident` (`Ident`) - The name of the function.
+    /// * `span` (`Span`) - The span of the function.
+    fn check_must_use(
+        &self,
+        context: &LateContext<'_>,
+        def_id: LocalDefId,
+        ident: Ident,
+        span: Span,
+    ) {
+        let tcx: TyCtxt<'_> = context.tcx;
+        if span.from_expansion()
+            || tcx.asyncness(def_id).is_async()
+            || find_attr!(
+                tcx.get_all_attrs(def_id),
+                AttributeKind::MustUse { .. }
+            )
+        {
+            return;
+        }
+
+        let output: Ty<'_> =
+            tcx.fn_sig(def_id).instantiate_identity().skip_binder().output();
+        if !self.is_must_use_type(tcx, output) {
+            return;
+        }
+
+        security_utils::span_lint(
+            context,
+            SECURITY_MISSING_MUST_USE,
+            ident.span,
+            |diagnostic: &mut Diag<'_, ()>| {
+                diagnostic.primary_message(format!(
+                    "Function `{ident}` returning `{output}` without \
+                     `#[must_use]` detected."
+                ));
+                diagnostic.note(
+                    "a caller dropping the value unexamined, e.g. with `let \
+                     _ = ...`, skips the check or releases the guard at once \
+                     without any warning",
+                );
+                diagnostic.span_suggestion(
+                    span.shrink_to_lo(),
+                    "add `#[must_use]` to the function",
+                    "#[must_use] ",
+                    Applicability::MaybeIncorrect,
+                );
+            },
+        );
+    }
+
+    /// Reports the raw pointers in the signature of the function `def_id`,
+    /// if it is exported by a library crate with the Rust ABI.
+    ///
+    /// # Arguments
+    /// * `context` (`&LateContext<'_>`) - The lint context used to emit the
+    ///   diagnostics.
+    /// * `def_id` (`LocalDefId`) - The function or method.
+    /// * `ident` (`Ident`) - The name of the function.
+    /// * `decl` (`&FnDecl<'_>`) - The declaration of the function, giving the
+    ///   spans of its parameter and return types.
+    fn check_raw_pointers(
+        &self,
+        context: &LateContext<'_>,
+        def_id: LocalDefId,
+        ident: Ident,
+        decl: &FnDecl<'_>,
+    ) {
+        let tcx: TyCtxt<'_> = context.tcx;
+        if !self.library
+            || ident.span.from_expansion()
+            || !tcx.effective_visibilities(()).is_exported(def_id)
+        {
+            return;
+        }
+
+        let signature: ty::FnSig<'_> =
+            tcx.fn_sig(def_id).instantiate_identity().skip_binder();
+        // Functions with a foreign ABI are FFI entry points, where raw
+        // pointers are expected.
+        if signature.abi != ExternAbi::Rust {
+            return;
+        }
+
+        for (index, input) in signature.inputs().iter().enumerate() {
+            if let Some(pointer) = raw_pointer(tcx, *input) {
+                let span: Span = decl
+                    .inputs
+                    .get(index)
+                    .map_or(ident.span, |input: &rustc_hir::Ty<'_>| {
+                        input.span
+                    });
+                report_raw_pointer(
+                    context,
+                    span,
+                    pointer,
+                    &format!("the signature of the public function `{ident}`"),
+                );
+            }
+        }
+        if let Some(pointer) = raw_pointer(tcx, signature.output()) {
+            report_raw_pointer(
+                context,
+                decl.output.span(),
+                pointer,
+                &format!("the signature of the public function `{ident}`"),
+            );
+        }
+    }
+
+    /// Reports the raw pointers in the exported fields of the struct `def_id`,
+    /// unless it is `#[repr(C)]`, i.e. shared with foreign code.
+    ///
+    /// # Arguments
+    /// * `context` (`&LateContext<'_>`) - The lint context used to emit the
+    ///   diagnostics.
+    /// * `def_id` (`LocalDefId`) - The struct.
+    /// * `fields` (`&[FieldDef<'_>]`) - The fields of the struct.
+    fn check_fields(
+        &self,
+        context: &LateContext<'_>,
+        def_id: LocalDefId,
+        fields: &[FieldDef<'_>],
+    ) {
+        let tcx: TyCtxt<'_> = context.tcx;
+        if !self.library || tcx.adt_def(def_id).repr().c() {
+            return;
+        }
+
+        for field in fields {
+            if field.span.from_expansion()
+                || !tcx.effective_visibilities(()).is_exported(field.def_id)
+            {
+                continue;
+            }
+            if let Some(pointer) = raw_pointer(
+                tcx,
+                tcx.type_of(field.def_id).instantiate_identity(),
+            ) {
+                report_raw_pointer(
+                    context,
+                    field.ty.span,
+                    pointer,
+                    &format!("the public field `{}`", field.ident),
+                );
+            }
+        }
+    }
+
+    /// Reports the enum `def_id` if it is an error type exported by a
+    /// library crate without `#[non_exhaustive]`. An enum is an error type
+    /// if it implements `std::error::Error` or if its name ends with
+    /// `Error`.
+    ///
+    /// # Arguments
+    /// * `context` (`&LateContext<'_>`) - The lint context used to emit the
+    ///   diagnostic.
+    /// * `def_id` (`LocalDefId`) - The enum.
+    /// * `ident` (`Ident`) - The name of the enum.
+    /// * `span` (`Span`) - The span of the enum.
+    fn check_error_enum(
+        &self,
+        context: &LateContext<'_>,
+        def_id: LocalDefId,
+        ident: Ident,
+        span: Span,
+    ) {
+        let tcx: TyCtxt<'_> = context.tcx;
+        if !self.library
+            || span.from_expansion()
+            || !tcx.effective_visibilities(()).is_exported(def_id)
+            || tcx.adt_def(def_id).is_variant_list_non_exhaustive()
+        {
+            return;
+        }
+
+        let implements_error: bool =
+            tcx.get_diagnostic_item(sym::Error).is_some_and(|error: DefId| {
+                tcx.non_blanket_impls_for_ty(
+                    error,
+                    tcx.type_of(def_id).instantiate_identity(),
+                )
+                .next()
+                .is_some()
+            });
+        if !implements_error && !ident.as_str().ends_with("Error") {
+            return;
+        }
+
+        security_utils::span_lint(
+            context,
+            SECURITY_EXHAUSTIVE_ERROR_ENUM,
+            ident.span,
+            |diagnostic: &mut Diag<'_, ()>| {
+                diagnostic.primary_message(format!(
+                    "Public error enum `{ident}` without `#[non_exhaustive]` \
+                     detected."
+                ));
+                diagnostic.note(
+                    "downstream crates can match every variant, so adding a \
+                     failure mode later is a breaking change, or gets folded \
+                     into an existing variant and handled as the wrong error",
+                );
+                diagnostic.span_suggestion(
+                    span.shrink_to_lo(),
+                    "add `#[non_exhaustive]` to the enum",
+                    "#[non_exhaustive]\n",
+                    Applicability::MaybeIncorrect,
+                );
+            },
+        );
+    }
+}
+
+/// Returns the first raw pointer or `NonNull` found in `ty`, including
+/// nested in its generic arguments (e.g. `Option<*const u8>`).
+///
+/// # Arguments
+/// * `tcx` (`TyCtxt<'tcx>`) - The type context used to recognize `NonNull`.
+/// * `ty` (`Ty<'tcx>`) - The type to inspect.
+fn raw_pointer<'tcx>(tcx: TyCtxt<'tcx>, ty: Ty<'tcx>) -> Option<Ty<'tcx>> {
+    ty.walk().filter_map(|argument: GenericArg<'tcx>| argument.as_type()).find(
+        |ty: &Ty<'tcx>| {
+            ty.is_raw_ptr()
+                || ty.ty_adt_def().is_some_and(|adt: ty::AdtDef<'tcx>| {
+                    tcx.is_diagnostic_item(sym::NonNull, adt.did())
+                })
+        },
+    )
+}
+
+/// Emits a `SECURITY_PUBLIC_RAW_POINTER` finding for `pointer` at `span`.
+///
+/// # Arguments
+/// * `context` (`&LateContext<'_>`) - The lint context used to emit the
+///   diagnostic.
+/// * `span` (`Span`) - The span of the type containing the pointer.
+/// * `pointer` (`Ty<'_>`) - The raw pointer or `NonNull` type.
+/// * `location` (`&str`) - A description of where the pointer appears.
+fn report_raw_pointer(
+    context: &LateContext<'_>,
+    span: Span,
+    pointer: Ty<'_>,
+    location: &str,
+) {
+    security_utils::span_lint(
+        context,
+        SECURITY_PUBLIC_RAW_POINTER,
+        span,
+        |diagnostic: &mut Diag<'_, ()>| {
+            diagnostic.primary_message(format!(
+                "Raw pointer `{pointer}` in {location} detected."
+            ));
+            diagnostic.note(
+                "every user of this API has to uphold the validity and \
+                 aliasing contract of the pointer in `unsafe` code of their \
+                 own",
+            );
+            diagnostic.help(
+                "take or return a reference, a slice or a safe wrapper \
+                 owning the pointer instead",
+            );
+        },
+    );
+}
+
+/// Returns `true` if the name of the item `def_id` matches one of `patterns`.
+///
+/// # Arguments
+/// * `tcx` (`TyCtxt<'_>`) - The type context used to name the item.
+/// * `def_id` (`DefId`) - The item, e.g. a struct.
+/// * `patterns` (`&[String]`) - The patterns of the names.
+fn matches_name(tcx: TyCtxt<'_>, def_id: DefId, patterns: &[String]) -> bool {
+    let name: String = tcx.item_name(def_id).to_string();
+
+    patterns.iter().any(|pattern: &String| matches_pattern(pattern, &name))
+}
+
+/// Returns `true` if `args`, the generic arguments of a `Result`, are `()`
+/// and an error type named after one of `patterns`.
+///
+/// # Arguments
+/// * `tcx` (`TyCtxt<'tcx>`) - The type context used to name the error type.
+/// * `args` (`GenericArgsRef<'tcx>`) - The generic arguments of the result.
+/// * `patterns` (`&[String]`) - The patterns of the error type names.
+fn is_unit_result_of<'tcx>(
+    tcx: TyCtxt<'tcx>,
+    args: GenericArgsRef<'tcx>,
+    patterns: &[String],
+) -> bool {
+    let (Some(ok), Some(error)): (Option<Ty<'tcx>>, Option<Ty<'tcx>>) =
+        (args.types().next(), args.types().nth(1))
+    else {
+        return false;
+    };
+
+    ok.is_unit()
+        && error.ty_adt_def().is_some_and(|adt: ty::AdtDef<'tcx>| {
+            matches_name(tcx, adt.did(), patterns)
+        })
+}
+
+impl<'tcx> LateLintPass<'tcx> for SecurityApiSurface {
+    /// Records whether the crate is a Rust library, whose exported items
+    /// are used by other crates.
+    ///
+    /// # Arguments
+    /// * `context` (`&LateContext<'tcx>`) - The lint context.
+    fn check_crate(&mut self, context: &LateContext<'tcx>) {
+        self.library =
+            context.tcx.crate_types().iter().any(|crate_type: &CrateType| {
+                matches!(crate_type, CrateType::Rlib | CrateType::Dylib)
+            });
+    }
+
+    /// Detect free functions returning a guard-like or verdict-like type
+    /// without `#[must_use]`, raw pointers in the exported functions and
+    /// struct fields of a library, and its exhaustive error enums.
+    ///
+    /// # Arguments
+    /// * `context` (`&LateContext<'tcx>`) - The lint context providing access
+    ///   to the compiler's internal state.
+    /// * `item` (`&'tcx Item<'tcx>`) - The item being checked.
+    fn check_item(
+        &mut self,
+        context: &LateContext<'tcx>,
+        item: &'tcx Item<'tcx>,
+    ) {
+        match item.kind {
+            ItemKind::Fn { ident, sig, .. } => {
+                self.check_must_use(
+                    context,
+                    item.owner_id.def_id,
+                    ident,
+                    item.span,
+                );
+                self.check_raw_pointers(
+                    context,
+                    item.owner_id.def_id,
+                    ident,
+                    sig.decl,
+                );
+            },
+            ItemKind::Struct(
+                _,
+                _,
+                VariantData::Struct { fields, .. }
+                | VariantData::Tuple(fields, ..),
+            ) => {
+                self.check_fields(context, item.owner_id.def_id, fields);
+            },
+            ItemKind::Enum(ident, ..) => {
+                self.check_error_enum(
+                    context,
+                    item.owner_id.def_id,
+                    ident,
+                    item.span,
+                );
+            },
+            _ => {},
+        }
+    }
+
+    /// Detect inherent methods returning a guard-like or verdict-like type
+    /// without `#[must_use]`, or exposing raw pointers. Methods of trait
+    /// implementations are checked through the declaration of the trait,
+    /// which carries the attribute and dictates the signature.
+    ///
+    /// # Arguments
+    /// * `context` (`&LateContext<'tcx>`) - The lint context providing access
+    ///   to the compiler's internal state.
+    /// * `item` (`&'tcx ImplItem<'tcx>`) - The impl item being checked.
+    fn check_impl_item(
+        &mut self,
+        context: &LateContext<'tcx>,
+        item: &'tcx ImplItem<'tcx>,
+    ) {
+        let parent: DefId = context.tcx.parent(item.owner_id.to_def_id());
+        if let ImplItemKind::Fn(sig, _) = item.kind
+            && matches!(
+                context.tcx.def_kind(parent),
+                DefKind::Impl { of_trait: false }
+            )
+        {
+            self.check_must_use(
+                context,
+                item.owner_id.def_id,
+                item.ident,
+                item.span,
+            );
+            self.check_raw_pointers(
+                context,
+                item.owner_id.def_id,
+                item.ident,
+                sig.decl,
+            );
+        }
+    }
+
+    /// Detect trait methods returning a guard-like or verdict-like type
+    /// without `#[must_use]`, or exposing raw pointers.
+    ///
+    /// # Arguments
+    /// * `context` (`&LateContext<'tcx>`) - The lint context providing access
+    ///   to the compiler's internal state.
+    /// * `item` (`&'tcx TraitItem<'tcx>`) - The trait item being checked.
+    fn check_trait_item(
+        &mut self,
+        context: &LateContext<'tcx>,
+        item: &'tcx TraitItem<'tcx>,
+    ) {
+        if let TraitItemKind::Fn(sig, _) = item.kind {
+            self.check_must_use(
+                context,
+                item.owner_id.def_id,
+                item.ident,
+                item.span,
+            );
+            self.check_raw_pointers(
+                context,
+                item.owner_id.def_id,
+                item.ident,
+                sig.decl,
+            );
+        }
+    }
+}
+
+/// Registers the `api_surface` lints and their lint pass with the Rust
+/// compiler. The type patterns are read from the `[api_surface]` table of
+/// `dylint.toml` when the pass is created.
+///
+/// # Arguments
+/// * `session` (`&Session`) - The compiler session providing access to the
+///   compiler's internal state.
+/// * `lint_store` (`&mut LintStore`) - The lint store where the lints and
+///   their lint pass will be registered.
+#[unsafe(no_mangle)]
+pub fn register_lints(session: &Session, lint_store: &mut LintStore) {
+    dylint_linting::init_config(session);
+
+    security_utils::tool::register_lints(
+        lint_store,
+        &[
+            SECURITY_MISSING_MUST_USE,
+            SECURITY_PUBLIC_RAW_POINTER,
+            SECURITY_EXHAUSTIVE_ERROR_ENUM,
+        ],
+    );
+    lint_store.register_late_pass(|tcx: TyCtxt<'_>| {
+        Box::new(SecurityApiSurface::new(tcx))
+    });
+}
+
+dylint_linting::dylint_library!();
+
+/// UI tests for the `api_surface` lints.
+#[cfg(test)]
+mod tests {
+    use dylint_testing::ui::Test;
+
+    #[test]
+    fn ui() {
+        Test::src_base(env!("CARGO_PKG_NAME"), "ui")
+            .rustc_flags(["--edition=2024", "-Z", "ui-testing"])
+            .run();
+    }
+}
@@ -0,0 +1,47 @@
+#![crate_type = "lib"]
+#![allow(dead_code)]
+
+use std::fmt;
+
+pub enum AuthError {
+    // Should trigger.
+    InvalidPassword,
+    Expired,
+}
+
+#[derive(Debug)]
+pub enum Failure {
+    // Should trigger (implements `Error`).
+    Denied,
+}
+
+impl fmt::Display for Failure {
+    fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        formatter.write_str("denied")
+    }
+}
+
+impl std::error::Error for Failure {}
+
+#[non_exhaustive]
+pub enum TokenError {
+    // Should not trigger.
+    Malformed,
+    Revoked,
+}
+
+pub enum Mode {
+    // Should not trigger (not an error type).
+    Strict,
+    Lenient,
+}
+
+enum ParseError {
+    // Should not trigger (not exported).
+    Truncated,
+}
+
+#[expect(security_exhaustive_error_enum, reason = "frozen by the wire protocol")]
+pub enum WireError {
+    Closed,
+}
@@ -0,0 +1,85 @@
+#![allow(dead_code)]
+
+pub struct SessionGuard;
+pub struct RatePermit;
+pub struct VerifiedToken(String);
+pub struct Token(String);
+pub struct AuthError;
+pub struct IoError;
+
+#[must_use]
+pub struct ScopedGuard;
+
+pub fn lock_session() -> SessionGuard {
+    // Should trigger.
+    SessionGuard
+}
+
+#[must_use]
+pub fn lock_session_checked() -> SessionGuard {
+    // Should not trigger.
+    SessionGuard
+}
+
+pub fn enter_scope() -> ScopedGuard {
+    // Should not trigger (the type is `#[must_use]`).
+    ScopedGuard
+}
+
+pub fn authorize(_user: &str) -> Result<(), AuthError> {
+    // Should trigger.
+    Ok(())
+}
+
+pub fn flush() -> Result<(), IoError> {
+    // Should not trigger (the error is not a verdict).
+    Ok(())
+}
+
+pub fn parse(raw: &str) -> Token {
+    // Should not trigger.
+    Token(raw.to_owned())
+}
+
+pub struct Limiter;
+
+impl Limiter {
+    pub fn reserve(&self) -> RatePermit {
+        // Should trigger.
+        RatePermit
+    }
+
+    pub fn verify(&self, token: Token) -> VerifiedToken {
+        // Should trigger.
+        VerifiedToken(token.0)
+    }
+}
+
+pub trait Verifier {
+    fn check(&self, token: Token) -> VerifiedToken; // Should trigger.
+
+    #[must_use]
+    fn check_all(&self, tokens: Vec<Token>) -> Vec<VerifiedToken>; // Should not trigger.
+}
+
+impl Verifier for Limiter {
+    fn check(&self, token: Token) -> VerifiedToken {
+        // Should not trigger (checked on the trait).
+        VerifiedToken(token.0)
+    }
+
+    fn check_all(&self, tokens: Vec<Token>) -> Vec<VerifiedToken> {
+        tokens.into_iter().map(|token: Token| VerifiedToken(token.0)).collect()
+    }
+}
+
+#[expect(security_missing_must_use, reason = "the guard only logs on drop")]
+pub fn trace() -> SessionGuard {
+    SessionGuard
+}
+
+/// The `main` function checks that dropping the guards compiles.
+fn main() {
+    let _ = lock_session();
+    let _ = authorize("root");
+}
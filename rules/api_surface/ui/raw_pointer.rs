@@ -0,0 +1,71 @@
+#![crate_type = "lib"]
+#![allow(dead_code)]
+
+use std::ptr::NonNull;
+
+pub fn buffer_start(data: *const u8) -> usize {
+    // Should trigger.
+    data as usize
+}
+
+pub fn allocate(len: usize) -> Option<NonNull<u8>> {
+    // Should trigger.
+    let _ = len;
+    None
+}
+
+pub fn checksum(data: &[u8]) -> u8 {
+    // Should not trigger.
+    data.iter().fold(0, |sum: u8, byte: &u8| sum.wrapping_add(*byte))
+}
+
+fn internal(data: *mut u8) -> *mut u8 {
+    // Should not trigger (not exported).
+    data
+}
+
+#[unsafe(no_mangle)]
+pub extern "C" fn ffi_len(data: *const u8) -> usize {
+    // Should not trigger (FFI entry point).
+    data as usize
+}
+
+pub struct Buffer {
+    pub start: *mut u8, // Should trigger.
+    len: usize,
+    cursor: *mut u8,
+}
+
+#[repr(C)]
+pub struct RawBuffer {
+    pub start: *mut u8, // Should not trigger (shared with foreign code).
+    pub len: usize,
+}
+
+impl Buffer {
+    pub fn as_ptr(&self) -> *const u8 {
+        // Should trigger.
+        self.start
+    }
+
+    pub fn as_slice(&self) -> &[u8] {
+        // Should not trigger.
+        &[]
+    }
+}
+
+pub trait Sink {
+    fn write_raw(&mut self, data: *const u8, len: usize); // Should trigger.
+}
+
+impl Sink for Buffer {
+    fn write_raw(&mut self, data: *const u8, len: usize) {
+        // Should not trigger (checked on the trait).
+        let _ = (data, len);
+    }
+}
+
+#[expect(security_public_raw_pointer, reason = "the handle is opaque to callers")]
+pub fn handle() -> *const () {
+    std::ptr::null()
+}
@@ -0,0 +1,606 @@
+#![feature(rustc_private)]
+
+extern crate rustc_errors;
+extern crate rustc_hir;
+extern crate rustc_lint;
+extern crate rustc_middle;
+extern crate rustc_session;
+extern crate rustc_span;
+
+use rustc_errors::Diag;
+use rustc_hir::{
+    Block,
+    Closure,
+    ClosureKind,
+    CoroutineDesugaring,
+    CoroutineKind,
+    Expr,
+    ExprKind,
+    LetStmt,
+    PatKind,
+    Stmt,
+    StmtKind,
+    def_id::DefId,
+    intravisit::{Visitor, walk_expr},
+};
+use rustc_lint::{LateContext, LateLintPass, LintStore};
+use rustc_middle::{
+    mir::{
+        CoroutineLayout,
+        CoroutineSavedLocal,
+        CoroutineSavedTy,
+        SourceInfo,
+    },
+    ty::{self, Ty, TyCtxt},
+};
+use rustc_session::{Session, declare_lint, impl_lint_pass};
+use rustc_span::{Span, sym};
+use serde::Deserialize;
+
+declare_lint! {
+    pub SECURITY_DROPPED_JOIN_HANDLE,
+    Warn,
+    "Detects `tokio` tasks spawned without keeping their `JoinHandle`."
+}
+
+declare_lint! {
+    pub SECURITY_REFCELL_AWAIT,
+    Deny,
+    "Detects `RefCell` borrows held across an `.await` point."
+}
+
+declare_lint! {
+    pub SECURITY_SLEEP_SYNCHRONIZATION,
+    Warn,
+    "Detects loops polling shared state and sleeping between iterations to
+    wait for another thread or task."
+}
+
+/// Configuration of the `async_usage` library, read from the
+/// `[async_usage]` table of the workspace's `dylint.toml`.
+#[derive(Deserialize)]
+#[serde(default)]
+struct Config {
+    /// Paths of the functions suspending the current thread or task for a
+    /// duration.
+    sleep_functions: Vec<String>,
+    /// Paths of the types, or of the modules defining them, whose methods
+    /// read state shared with other threads or tasks.
+    polled_types: Vec<String>,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Self {
+            sleep_functions: [
+                "std::thread::sleep",
+                "tokio::time::sleep",
+                "async_std::task::sleep",
+            ]
+            .into_iter()
+            .map(String::from)
+            .collect(),
+            polled_types: [
+                "core::sync::atomic",
+                "std::sync::atomic",
+                "std::sync::Mutex",
+                "std::sync::RwLock",
+                "std::sync::mpsc::Receiver",
+                "parking_lot::Mutex",
+                "parking_lot::RwLock",
+                "crossbeam_channel::Receiver",
+                "tokio::sync::Mutex",
+                "tokio::sync::RwLock",
+            ]
+            .into_iter()
+            .map(String::from)
+            .collect(),
+        }
+    }
+}
+
+/// Lint pass for the `async_usage` lints, holding the sleeping functions and
+/// the polled types of `SECURITY_SLEEP_SYNCHRONIZATION`.
+struct SecurityAsyncUsage {
+    sleep_functions: Vec<String>,
+    polled_types: Vec<String>,
+}
+
+impl_lint_pass!(SecurityAsyncUsage => [
+    SECURITY_DROPPED_JOIN_HANDLE,
+    SECURITY_REFCELL_AWAIT,
+    SECURITY_SLEEP_SYNCHRONIZATION,
+]);
+
+/// Returns `true` if `path` is one of `paths` or is defined in one of them.
+///
+/// # Arguments
+/// * `paths` (`&[String]`) - The configured paths.
+/// * `path` (`&str`) - The path to match, e.g. `std::sync::Mutex`.
+fn matches_path(paths: &[String], path: &str) -> bool {
+    paths.iter().any(|prefix: &String| {
+        path.strip_prefix(prefix.as_str()).is_some_and(|rest: &str| {
+            rest.is_empty() || rest.starts_with("::")
+        })
+    })
+}
+
+/// Visitor finding the `break` and `return` expressions of a branch, outside
+/// of nested loops and closures.
+struct ExitFinder {
+    found: bool,
+}
+
+impl<'tcx> Visitor<'tcx> for ExitFinder {
+    fn visit_expr(&mut self, expression: &'tcx Expr<'tcx>) {
+        match expression.kind {
+            ExprKind::Break(..) | ExprKind::Ret(_) => self.found = true,
+            ExprKind::Loop(..) | ExprKind::Closure(_) => {},
+            _ => walk_expr(self, expression),
+        }
+    }
+}
+
+/// Returns `true` if `branch` leaves the enclosing loop or function.
+///
+/// # Arguments
+/// * `branch` (`&Expr<'_>`) - The branch of a condition.
+fn exits(branch: &Expr<'_>) -> bool {
+    let mut finder: ExitFinder = ExitFinder { found: false };
+    finder.visit_expr(branch);
+    finder.found
+}
+
+/// Visitor walking the body of a loop, outside of nested loops and
+/// closures, and recording its calls to sleeping functions and the reads of
+/// shared state in the conditions leaving it.
+struct PollingFinder<'a, 'tcx> {
+    context: &'a LateContext<'tcx>,
+    pass: &'a SecurityAsyncUsage,
+    /// Whether the visited expression is a condition leaving the loop.
+    in_condition: bool,
+    sleeps: Vec<(Span, String)>,
+    reads: Vec<Span>,
+}
+
+impl<'tcx> PollingFinder<'_, 'tcx> {
+    /// Returns the path of the function `callee` refers to, if it is one of
+    /// the sleeping functions.
+    ///
+    /// # Arguments
+    /// * `callee` (`&'tcx Expr<'tcx>`) - The called expression.
+    fn sleep_function(&self, callee: &'tcx Expr<'tcx>) -> Option<String> {
+        let ExprKind::Path(path): &ExprKind<'_> = &callee.kind else {
+            return None;
+        };
+        let id: DefId =
+            self.context.qpath_res(path, callee.hir_id).opt_def_id()?;
+        let function: String = self.context.tcx.def_path_str(id);
+
+        matches_path(&self.pass.sleep_functions, &function).then_some(function)
+    }
+
+    /// Returns `true` if `receiver` is of one of the polled types, through
+    /// references and smart pointers.
+    ///
+    /// # Arguments
+    /// * `receiver` (`&Expr<'_>`) - The receiver of a method call.
+    fn is_polled(&self, receiver: &Expr<'_>) -> bool {
+        let ty: Ty<'_> = self
+            .context
+            .typeck_results()
+            .expr_ty_adjusted(receiver)
+            .peel_refs();
+        let ty::Adt(adt, _): &ty::TyKind<'_> = ty.kind() else {
+            return false;
+        };
+
+        matches_path(
+            &self.pass.polled_types,
+            &self.context.tcx.def_path_str(adt.did()),
+        )
+    }
+}
+
+impl<'tcx> Visitor<'tcx> for PollingFinder<'_, 'tcx> {
+    fn visit_expr(&mut self, expression: &'tcx Expr<'tcx>) {
+        match &expression.kind {
+            ExprKind::Loop(..) | ExprKind::Closure(_) => return,
+            ExprKind::If(condition, then, otherwise)
+                if exits(then) || otherwise.is_some_and(exits) =>
+            {
+                let in_condition: bool = self.in_condition;
+                self.in_condition = true;
+                self.visit_expr(condition);
+                self.in_condition = in_condition;
+                self.visit_expr(then);
+                if let Some(otherwise) = otherwise {
+                    self.visit_expr(otherwise);
+                }
+                return;
+            },
+            ExprKind::Call(callee, _) => {
+                if let Some(function) = self.sleep_function(callee) {
+                    self.sleeps.push((expression.span, function));
+                }
+            },
+            ExprKind::MethodCall(_, receiver, _, _)
+                if self.in_condition && self.is_polled(receiver) =>
+            {
+                self.reads.push(expression.span);
+            },
+            _ => {},
+        }
+
+        walk_expr(self, expression);
+    }
+}
+
+impl SecurityAsyncUsage {
+    fn new(tcx: TyCtxt<'_>) -> Self {
+        let config: Config =
+            security_utils::config::load(tcx, env!("CARGO_PKG_NAME"));
+
+        Self {
+            sleep_functions: config.sleep_functions,
+            polled_types: config.polled_types,
+        }
+    }
+
+    /// Reports the loop `body` if it sleeps between iterations and leaves on
+    /// a condition reading shared state.
+    ///
+    /// # Arguments
+    /// * `context` (`&LateContext<'tcx>`) - The lint context.
+    /// * `body` (`&'tcx Block<'tcx>`) - The body of the loop.
+    fn check_loop<'tcx>(
+        &self,
+        context: &LateContext<'tcx>,
+        body: &'tcx Block<'tcx>,
+    ) {
+        let mut finder: PollingFinder<'_, 'tcx> = PollingFinder {
+            context,
+            pass: self,
+            in_condition: false,
+            sleeps: Vec::new(),
+            reads: Vec::new(),
+        };
+        for statement in body.stmts {
+            finder.visit_stmt(statement);
+        }
+        if let Some(tail) = body.expr {
+            finder.visit_expr(tail);
+        }
+
+        let PollingFinder { sleeps, reads, .. }: PollingFinder<'_, 'tcx> =
+            finder;
+        if reads.is_empty() {
+            return;
+        }
+        if let Some((span, function)) = sleeps.into_iter().next() {
+            report_sleep_synchronization(context, span, &function, reads);
+        }
+    }
+}
+
+/// Returns the path of the spawning function called by `expression`, if it
+/// is one of `tokio::spawn`, `tokio::task::spawn_blocking`,
+/// `tokio::task::spawn_local` or the `spawn` methods of the runtime and its
+/// handle.
+///
+/// # Arguments
+/// * `context` (`&LateContext<'tcx>`) - The lint context, used to resolve the
+///   called function.
+/// * `expression` (`&'tcx Expr<'tcx>`) - The expression to inspect.
+fn spawned_task<'tcx>(
+    context: &LateContext<'tcx>,
+    expression: &'tcx Expr<'tcx>,
+) -> Option<String> {
+    let def_id: DefId = match &expression.kind {
+        ExprKind::Call(callee, _) => match &callee.kind {
+            ExprKind::Path(path) => {
+                context.qpath_res(path, callee.hir_id).opt_def_id()?
+            },
+            _ => return None,
+        },
+        ExprKind::MethodCall(..) => context
+            .typeck_results()
+            .type_dependent_def_id(expression.hir_id)?,
+        _ => return None,
+    };
+
+    let tcx: TyCtxt<'tcx> = context.tcx;
+    let is_spawn: bool = tcx.crate_name(def_id.krate).as_str() == "tokio"
+        && matches!(
+            tcx.item_name(def_id).as_str(),
+            "spawn" | "spawn_blocking" | "spawn_local"
+        );
+
+    is_spawn.then(|| tcx.def_path_str(def_id))
+}
+
+/// Emits a `SECURITY_DROPPED_JOIN_HANDLE` finding at `span`.
+///
+/// # Arguments
+/// * `context` (`&LateContext<'_>`) - The lint context used to emit the
+///   diagnostic.
+/// * `span` (`Span`) - The span of the statement dropping the handle.
+/// * `function` (`&str`) - The path of the spawning function.
+fn report_dropped_handle(
+    context: &LateContext<'_>,
+    span: Span,
+    function: &str,
+) {
+    security_utils::span_lint(
+        context,
+        SECURITY_DROPPED_JOIN_HANDLE,
+        span,
+        |diagnostic: &mut Diag<'_, ()>| {
+            diagnostic.primary_message(format!(
+                "Dropped `JoinHandle` of a task spawned with `{function}` \
+                 detected."
+            ));
+            diagnostic.note(
+                "panics and errors of the spawned task are silently lost",
+            );
+            diagnostic.help(
+                "store the `JoinHandle` and await it, or spawn the task on a \
+                 `JoinSet` and handle the results of `join_next`",
+            );
+        },
+    );
+}
+
+/// Emits a `SECURITY_SLEEP_SYNCHRONIZATION` finding at `span`.
+///
+/// # Arguments
+/// * `context` (`&LateContext<'_>`) - The lint context used to emit the
+///   diagnostic.
+/// * `span` (`Span`) - The call to the sleeping function.
+/// * `function` (`&str`) - The path of the sleeping function.
+/// * `reads` (`Vec<Span>`) - The reads of shared state the loop polls.
+fn report_sleep_synchronization(
+    context: &LateContext<'_>,
+    span: Span,
+    function: &str,
+    reads: Vec<Span>,
+) {
+    security_utils::span_lint(
+        context,
+        SECURITY_SLEEP_SYNCHRONIZATION,
+        span,
+        |diagnostic: &mut Diag<'_, ()>| {
+            diagnostic.primary_message(format!(
+                "Polling loop synchronized with `{function}` detected."
+            ));
+            diagnostic.span_note(
+                reads,
+                "the loop waits for this shared state to be changed by \
+                 another thread or task",
+            );
+            diagnostic.note(
+                "no sleep duration guarantees that the change has happened: \
+                 the loop either wastes time or races with the other side",
+            );
+            diagnostic.help(
+                "block on a `Condvar`, a channel or an async notification \
+                 such as `tokio::sync::Notify` or `watch` instead",
+            );
+        },
+    );
+}
+
+/// Reports every `Ref` and `RefMut` guard stored in the state of an async
+/// block or function, i.e. every borrow that is alive across at least one
+/// `.await` point.
+///
+/// # Arguments
+/// * `context` (`&LateContext<'_>`) - The lint context used to emit the
+///   diagnostics.
+/// * `layout` (`&CoroutineLayout<'_>`) - The layout of the coroutine state,
+///   listing the locals saved across suspension points.
+fn check_refcell_guards(
+    context: &LateContext<'_>,
+    layout: &CoroutineLayout<'_>,
+) {
+    for (local, saved) in layout.field_tys.iter_enumerated() {
+        let ty::Adt(adt, _): &ty::TyKind<'_> = saved.ty.kind() else {
+            continue;
+        };
+
+        let guard: &str = if context
+            .tcx
+            .is_diagnostic_item(sym::RefCellRef, adt.did())
+        {
+            "Ref"
+        } else if context.tcx.is_diagnostic_item(sym::RefCellRefMut, adt.did())
+        {
+            "RefMut"
+        } else {
+            continue;
+        };
+
+        // The suspension points whose saved state contains this guard.
+        let mut await_points: Vec<Span> = Vec::new();
+        for (variant, fields) in layout.variant_fields.iter_enumerated() {
+            let Some(source_info): Option<&SourceInfo> =
+                layout.variant_source_info.get(variant)
+            else {
+                continue;
+            };
+            if fields.iter().any(|field: &CoroutineSavedLocal| *field == local)
+            {
+                await_points.push(source_info.span);
+            }
+        }
+
+        report_refcell_guard(context, saved, guard, await_points);
+    }
+}
+
+/// Emits a `SECURITY_REFCELL_AWAIT` finding for the guard `saved`.
+///
+/// # Arguments
+/// * `context` (`&LateContext<'_>`) - The lint context used to emit the
+///   diagnostic.
+/// * `saved` (`&CoroutineSavedTy<'_>`) - The guard saved across suspension
+///   points.
+/// * `guard` (`&str`) - The name of the guard type (`Ref` or `RefMut`).
+/// * `await_points` (`Vec<Span>`) - The `.await` points the guard is held
+///   across.
+fn report_refcell_guard(
+    context: &LateContext<'_>,
+    saved: &CoroutineSavedTy<'_>,
+    guard: &str,
+    await_points: Vec<Span>,
+) {
+    security_utils::span_lint(
+        context,
+        SECURITY_REFCELL_AWAIT,
+        saved.source_info.span,
+        |diagnostic: &mut Diag<'_, ()>| {
+            diagnostic.primary_message(format!(
+                "`RefCell` borrow (`{guard}`) held across an `.await` point \
+                 detected."
+            ));
+            diagnostic.span_note(
+                await_points,
+                "the borrow is still alive at these `.await` points",
+            );
+            diagnostic.note(
+                "any other borrow of the same `RefCell` while this future is \
+                 suspended panics at runtime",
+            );
+            diagnostic.help(
+                "drop the borrow before awaiting, or copy the needed data out \
+                 of the `RefCell` first",
+            );
+        },
+    );
+}
+
+impl<'tcx> LateLintPass<'tcx> for SecurityAsyncUsage {
+    /// Detect `RefCell` borrows kept alive across an `.await` point of an
+    /// async block or function, and loops polling shared state with a sleep
+    /// between iterations.
+    ///
+    /// # Arguments
+    /// * `context` (`&LateContext<'tcx>`) - The lint context providing access
+    ///   to the compiler's internal state.
+    /// * `expression` (`&'tcx Expr<'tcx>`) - The expression being checked for
+    ///   async blocks.
+    fn check_expr(
+        &mut self,
+        context: &LateContext<'tcx>,
+        expression: &'tcx Expr<'tcx>,
+    ) {
+        // Async functions are lowered to a function returning an async block,
+        // so both are handled here.
+        if let ExprKind::Closure(&Closure {
+            kind:
+                ClosureKind::Coroutine(CoroutineKind::Desugared(
+                    CoroutineDesugaring::Async,
+                    _,
+                )),
+            def_id,
+            ..
+        }) = expression.kind
+            && let Some(layout) =
+                context.tcx.mir_coroutine_witnesses(def_id.to_def_id())
+        {
+            check_refcell_guards(context, layout);
+        }
+
+        if let ExprKind::Loop(body, _, _, _) = expression.kind
+            && !expression.span.from_expansion()
+        {
+            self.check_loop(context, body);
+        }
+    }
+
+    /// Detect spawning calls used as expression statements, such as
+    /// `tokio::spawn(task);`.
+    ///
+    /// # Arguments
+    /// * `context` (`&LateContext<'tcx>`) - The lint context providing access
+    ///   to the compiler's internal state.
+    /// * `statement` (`&'tcx Stmt<'tcx>`) - The statement being checked.
+    fn check_stmt(
+        &mut self,
+        context: &LateContext<'tcx>,
+        statement: &'tcx Stmt<'tcx>,
+    ) {
+        if let StmtKind::Semi(expression) = statement.kind
+            && !statement.span.from_expansion()
+            && let Some(function) = spawned_task(context, expression)
+        {
+            report_dropped_handle(context, statement.span, &function);
+        }
+    }
+
+    /// Detect spawning calls bound to the `_` pattern, such as
+    /// `let _ = tokio::spawn(task);`.
+    ///
+    /// # Arguments
+    /// * `context` (`&LateContext<'tcx>`) - The lint context providing access
+    ///   to the compiler's internal state.
+    /// * `local` (`&'tcx LetStmt<'tcx>`) - The let statement being checked.
+    fn check_local(
+        &mut self,
+        context: &LateContext<'tcx>,
+        local: &'tcx LetStmt<'tcx>,
+    ) {
+        if let PatKind::Wild = local.pat.kind
+            && let Some(init) = local.init
+            && !local.span.from_expansion()
+            && let Some(function) = spawned_task(context, init)
+        {
+            report_dropped_handle(context, local.span, &function);
+        }
+    }
+}
+
+/// Registers the `SECURITY_DROPPED_JOIN_HANDLE`, `SECURITY_REFCELL_AWAIT` and
+/// `SECURITY_SLEEP_SYNCHRONIZATION` lints and their shared lint pass with the
+/// Rust compiler.
+///
+/// # Arguments
+/// * `session` (`&Session`) - The compiler session providing access to the
+///   compiler's internal state.
+/// * `lint_store` (`&mut LintStore`) - The lint store where the lints and
+///   their lint pass will be registered.
+#[unsafe(no_mangle)]
+pub fn register_lints(session: &Session, lint_store: &mut LintStore) {
+    dylint_linting::init_config(session);
+
+    security_utils::tool::register_lints(
+        lint_store,
+        &[
+            SECURITY_DROPPED_JOIN_HANDLE,
+            SECURITY_REFCELL_AWAIT,
+            SECURITY_SLEEP_SYNCHRONIZATION,
+        ],
+    );
+    lint_store.register_late_pass(|tcx: TyCtxt<'_>| {
+        Box::new(SecurityAsyncUsage::new(tcx))
+    });
+}
+
+dylint_linting::dylint_library!();
+
+/// UI tests for the `async_usage` lints. The tests use a stand-in for the
+/// `tokio` task API and check that join handles discarded by a statement or a
+/// `_` binding are reported, while stored, awaited or named handles are not,
+/// that `RefCell` borrows are reported only when they outlive an `.await`,
+/// and that loops sleeping between polls of shared state are reported.
+#[cfg(test)]
+mod tests {
+    use dylint_testing::ui::Test;
+
+    #[test]
+    fn ui() {
+        Test::src_base(env!("CARGO_PKG_NAME"), "ui")
+            .rustc_flags(["--edition=2024", "-Z", "ui-testing"])
+            .run();
+    }
+}
@@ -0,0 +1,30 @@
+// aux-build:tokio.rs
+#![warn(security_dropped_join_handle)]
+
+extern crate tokio;
+
+use tokio::{runtime::Handle, task::JoinHandle};
+
+async fn process() -> Result<(), ()> {
+    Ok(())
+}
+
+async fn dropped() {
+    tokio::spawn(process()); // Should trigger.
+    let _ = tokio::spawn(process()); // Should trigger.
+    tokio::task::spawn_blocking(|| 1); // Should trigger.
+    Handle::current().spawn(process()); // Should trigger.
+}
+
+async fn kept() -> Result<(), ()> {
+    let handle: JoinHandle<Result<(), ()>> = tokio::spawn(process()); // Should not trigger.
+    handle.await??;
+
+    let _guard: JoinHandle<u8> = tokio::task::spawn_blocking(|| 1); // Should not trigger.
+    tokio::spawn(process()).await? // Should not trigger.
+}
+
+fn main() {
+    let _ = dropped();
+    let _ = kept();
+}
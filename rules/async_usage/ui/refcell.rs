@@ -0,0 +1,35 @@
+#![warn(security_refcell_await)]
+
+use std::cell::{Ref, RefCell, RefMut};
+
+async fn tick() {}
+
+async fn held(counter: &RefCell<u32>, names: &RefCell<Vec<String>>) {
+    let mut count: RefMut<'_, u32> = counter.borrow_mut(); // Should trigger.
+    tick().await;
+    *count += 1;
+
+    let first: Ref<'_, Vec<String>> = names.borrow(); // Should trigger.
+    tick().await;
+    tick().await;
+    let _: usize = first.len();
+}
+
+async fn released(counter: &RefCell<u32>) {
+    {
+        let mut count: RefMut<'_, u32> = counter.borrow_mut(); // Should not trigger.
+        *count += 1;
+    }
+    tick().await;
+
+    let value: u32 = *counter.borrow(); // Should not trigger.
+    tick().await;
+    let _: u32 = value;
+}
+
+fn main() {
+    let counter: RefCell<u32> = RefCell::new(0);
+    let names: RefCell<Vec<String>> = RefCell::new(Vec::new());
+    let _ = held(&counter, &names);
+    let _ = released(&counter);
+}
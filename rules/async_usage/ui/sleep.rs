@@ -0,0 +1,86 @@
+// aux-build:tokio.rs
+#![warn(security_sleep_synchronization)]
+
+extern crate tokio;
+
+use std::{
+    sync::{
+        Arc,
+        Mutex,
+        atomic::{AtomicBool, Ordering},
+        mpsc::Receiver,
+    },
+    thread,
+    time::Duration,
+};
+
+// Should trigger (`while` polling an atomic flag).
+fn wait_ready(ready: &AtomicBool) {
+    while !ready.load(Ordering::Acquire) {
+        thread::sleep(Duration::from_millis(10));
+    }
+}
+
+// Should trigger (`loop` polling a mutex through an `Arc`).
+fn wait_queue(queue: Arc<Mutex<Vec<u32>>>) -> u32 {
+    loop {
+        if let Some(job) = queue.lock().unwrap().pop() {
+            return job;
+        }
+        thread::sleep(Duration::from_millis(10));
+    }
+}
+
+// Should trigger (`while` polling a channel).
+fn wait_message(receiver: &Receiver<u32>) {
+    while receiver.try_recv().is_err() {
+        thread::sleep(Duration::from_millis(10));
+    }
+}
+
+// Should trigger (async task polling an atomic flag).
+async fn wait_shutdown(stopped: Arc<AtomicBool>) {
+    loop {
+        if stopped.load(Ordering::Relaxed) {
+            break;
+        }
+        tokio::time::sleep(Duration::from_millis(10)).await;
+    }
+}
+
+// Should not trigger (fixed-rate loop without shared state in its exit).
+fn tick(count: &mut u32) {
+    while *count < 10 {
+        *count += 1;
+        thread::sleep(Duration::from_millis(10));
+    }
+}
+
+// Should not trigger (shared state read without sleeping).
+fn spin(ready: &AtomicBool) {
+    while !ready.load(Ordering::Acquire) {
+        std::hint::spin_loop();
+    }
+}
+
+// Should not trigger (shared state read outside of the exit condition).
+fn report(hits: &AtomicBool) {
+    for _ in 0..3 {
+        println!("{}", hits.load(Ordering::Relaxed));
+        thread::sleep(Duration::from_secs(1));
+    }
+}
+
+fn main() {
+    let ready: AtomicBool = AtomicBool::new(true);
+    let mut count: u32 = 0;
+    wait_ready(&ready);
+    spin(&ready);
+    report(&ready);
+    tick(&mut count);
+    let _ = wait_queue(Arc::new(Mutex::new(vec![1])));
+    let (sender, receiver) = std::sync::mpsc::channel::<u32>();
+    sender.send(1).unwrap();
+    wait_message(&receiver);
+    let _ = wait_shutdown(Arc::new(AtomicBool::new(true)));
+}
@@ -0,0 +1,511 @@
+#![feature(rustc_private)]
+
+extern crate rustc_ast;
+extern crate rustc_errors;
+extern crate rustc_hir;
+extern crate rustc_lint;
+extern crate rustc_middle;
+extern crate rustc_session;
+extern crate rustc_span;
+extern crate thin_vec;
+
+use rustc_ast::{LitKind, MetaItemInner};
+use rustc_errors::Diag;
+use rustc_hir::{
+    Attribute,
+    Expr,
+    ExprKind,
+    HirId,
+    ImplItem,
+    Item,
+    LetStmt,
+    PathSegment,
+    QPath,
+    def_id::DefId,
+    intravisit::{Visitor, walk_expr},
+};
+use rustc_lint::{LateContext, LateLintPass, LintContext, LintStore};
+use rustc_middle::ty::TyCtxt;
+use rustc_session::{Session, declare_lint, impl_lint_pass};
+use rustc_span::{ExpnData, Ident, Span, Symbol, sym};
+use serde::Deserialize;
+use thin_vec::ThinVec;
+
+declare_lint! {
+    pub SECURITY_DEBUG_ONLY_CHECK,
+    Warn,
+    "Detects security checks only compiled with `debug_assertions`, which vanish from release builds."
+}
+
+declare_lint! {
+    pub SECURITY_FEATURE_GATED_CHECK,
+    Warn,
+    "Detects security checks compiled in or out by cargo features."
+}
+
+/// Configuration of the `cfg_usage` library, read from the `[cfg_usage]`
+/// table of the workspace's `dylint.toml`.
+#[derive(Deserialize)]
+#[serde(default)]
+struct Config {
+    /// Prefixes of the words (split on `_` and lower-to-upper case
+    /// boundaries) naming security checks, matched case-insensitively, e.g.
+    /// `verif` for `verify_signature`.
+    patterns: Vec<String>,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Self {
+            patterns: [
+                "auth",
+                "verif",
+                "valid",
+                "permission",
+                "acl",
+                "csrf",
+                "signature",
+                "login",
+            ]
+            .into_iter()
+            .map(String::from)
+            .collect(),
+        }
+    }
+}
+
+/// Lint pass holding the patterns loaded from the configuration.
+struct SecurityCfgUsage {
+    patterns: Vec<String>,
+}
+
+impl_lint_pass!(
+    SecurityCfgUsage => [SECURITY_DEBUG_ONLY_CHECK, SECURITY_FEATURE_GATED_CHECK]
+);
+
+/// Predicate a security check is compiled under.
+enum Gate {
+    /// A `#[cfg(...)]` attribute requiring `debug_assertions`.
+    DebugAttribute,
+    /// An `if cfg!(debug_assertions)` condition.
+    DebugCondition,
+    /// A `debug_assert!`, `debug_assert_eq!` or `debug_assert_ne!`.
+    DebugAssert,
+    /// A `#[cfg(...)]` attribute depending on the cargo feature.
+    Feature(Symbol),
+}
+
+/// Visitor finding the first function, method or path whose name matches the
+/// configured patterns in a gated expression.
+struct CheckFinder<'a> {
+    pass: &'a SecurityCfgUsage,
+    found: Option<Symbol>,
+}
+
+impl<'tcx> Visitor<'tcx> for CheckFinder<'_> {
+    fn visit_expr(&mut self, expression: &'tcx Expr<'tcx>) {
+        if self.found.is_some() {
+            return;
+        }
+
+        let name: Option<Ident> = match &expression.kind {
+            ExprKind::MethodCall(segment, ..) => Some(segment.ident),
+            ExprKind::Path(QPath::Resolved(_, path)) => path
+                .segments
+                .last()
+                .map(|segment: &PathSegment<'_>| segment.ident),
+            ExprKind::Path(QPath::TypeRelative(_, segment)) => {
+                Some(segment.ident)
+            },
+            _ => None,
+        };
+        if let Some(name) = name
+            && self.pass.is_check_name(name.as_str())
+        {
+            self.found = Some(name.name);
+            return;
+        }
+        walk_expr(self, expression);
+    }
+}
+
+/// Returns `true` if the cfg `predicate` only holds when `debug_assertions`
+/// is enabled: `debug_assertions` itself, an `all(...)` requiring it, or an
+/// `any(...)` whose every branch requires it. Negations are never
+/// considered to require it.
+///
+/// # Arguments
+/// * `predicate` (`&MetaItemInner`) - The predicate to inspect.
+fn requires_debug_assertions(predicate: &MetaItemInner) -> bool {
+    if predicate.is_word() {
+        return predicate.has_name(sym::debug_assertions);
+    }
+    let Some(list): Option<&[MetaItemInner]> = predicate.meta_item_list()
+    else {
+        return false;
+    };
+
+    if predicate.has_name(sym::all) {
+        list.iter().any(requires_debug_assertions)
+    } else if predicate.has_name(sym::any) {
+        !list.is_empty() && list.iter().all(requires_debug_assertions)
+    } else {
+        false
+    }
+}
+
+/// Returns the first cargo feature the cfg `predicate` depends on, either
+/// way: the check is compiled out by enabling the feature of
+/// `not(feature = "...")` as much as by disabling that of
+/// `feature = "..."`.
+///
+/// # Arguments
+/// * `predicate` (`&MetaItemInner`) - The predicate to inspect.
+fn feature(predicate: &MetaItemInner) -> Option<Symbol> {
+    if predicate.has_name(sym::feature) {
+        return predicate.value_str();
+    }
+    predicate.meta_item_list()?.iter().find_map(feature)
+}
+
+/// Returns the gate of the node `hir_id`, if it carries a `#[cfg(...)]`
+/// attribute requiring `debug_assertions` or depending on a cargo feature.
+/// The attributes of enabled `#[cfg(...)]` predicates are kept in the HIR as
+/// `cfg_trace` attributes.
+///
+/// # Arguments
+/// * `context` (`&LateContext<'_>`) - The lint context, used to read the
+///   attributes.
+/// * `hir_id` (`HirId`) - The node to inspect.
+fn attribute_gate(context: &LateContext<'_>, hir_id: HirId) -> Option<Gate> {
+    context.tcx.hir_attrs(hir_id).iter().find_map(|attribute: &Attribute| {
+        if !attribute.has_name(sym::cfg_trace) {
+            return None;
+        }
+        let list: ThinVec<MetaItemInner> = attribute.meta_item_list()?;
+        if list.iter().any(requires_debug_assertions) {
+            return Some(Gate::DebugAttribute);
+        }
+        list.iter().find_map(feature).map(Gate::Feature)
+    })
+}
+
+/// Returns the gate of `if` expression `expression` whose condition is
+/// `cfg!(debug_assertions)` and the span to report, if it has one: the
+/// `debug_assert!` family, which expands to such an expression, or a
+/// `cfg!(...)` written in the sources. The predicate of `cfg!(...)` is read
+/// from its sources, as the macro expands to a boolean literal.
+///
+/// # Arguments
+/// * `context` (`&LateContext<'_>`) - The lint context, used to identify the
+///   macros and read their sources.
+/// * `expression` (`&Expr<'_>`) - The `if` expression.
+/// * `condition` (`&Expr<'_>`) - The condition of the `if` expression.
+fn debug_condition(
+    context: &LateContext<'_>,
+    expression: &Expr<'_>,
+    condition: &Expr<'_>,
+) -> Option<(Span, Gate)> {
+    let condition: &Expr<'_> = match &condition.kind {
+        ExprKind::DropTemps(inner) => inner,
+        _ => condition,
+    };
+    let ExprKind::Lit(literal): &ExprKind<'_> = &condition.kind else {
+        return None;
+    };
+    let cfg: ExpnData = condition.span.ctxt().outer_expn_data();
+    if !matches!(literal.node, LitKind::Bool(_))
+        || !cfg.macro_def_id.is_some_and(|id: DefId| {
+            context.tcx.crate_name(id.krate) == sym::core
+                && context.tcx.item_name(id) == sym::cfg
+        })
+    {
+        return None;
+    }
+
+    if expression.span.from_expansion() {
+        let outer: ExpnData = expression.span.ctxt().outer_expn_data();
+        let id: DefId = outer.macro_def_id?;
+        return matches!(
+            context.tcx.get_diagnostic_name(id),
+            Some(
+                sym::debug_assert_macro
+                    | sym::debug_assert_eq_macro
+                    | sym::debug_assert_ne_macro
+            )
+        )
+        .then_some((outer.call_site, Gate::DebugAssert));
+    }
+
+    let source: String =
+        context.sess().source_map().span_to_snippet(cfg.call_site).ok()?;
+    let predicate: String = source
+        .chars()
+        .filter(|character: &char| !character.is_whitespace())
+        .collect();
+    let predicate: &str = predicate
+        .split_once('(')
+        .and_then(|(_, rest): (&str, &str)| rest.strip_suffix(')'))?;
+    (!predicate.contains("not(") && predicate.contains("debug_assertions"))
+        .then_some((expression.span, Gate::DebugCondition))
+}
+
+impl SecurityCfgUsage {
+    fn new(tcx: TyCtxt<'_>) -> Self {
+        let config: Config =
+            security_utils::config::load(tcx, env!("CARGO_PKG_NAME"));
+
+        Self { patterns: config.patterns }
+    }
+
+    /// Returns `true` if one of the words of `name` (see
+    /// `security_utils::words::split`) starts with a configured pattern.
+    ///
+    /// # Arguments
+    /// * `name` (`&str`) - The identifier to check.
+    fn is_check_name(&self, name: &str) -> bool {
+        security_utils::words::split(name).iter().any(|word: &String| {
+            self.patterns
+                .iter()
+                .any(|pattern: &String| word.starts_with(pattern.as_str()))
+        })
+    }
+
+    /// Returns the name of the first security check performed by
+    /// `expression`, if any.
+    ///
+    /// # Arguments
+    /// * `expression` (`&Expr<'_>`) - The expression to inspect.
+    fn check(&self, expression: &Expr<'_>) -> Option<Symbol> {
+        let mut finder: CheckFinder<'_> =
+            CheckFinder { pass: self, found: None };
+        finder.visit_expr(expression);
+        finder.found
+    }
+
+    /// Reports the item or impl item `ident` if it is named after a security
+    /// check and gated by a `#[cfg(...)]` attribute.
+    ///
+    /// # Arguments
+    /// * `context` (`&LateContext<'_>`) - The lint context used to emit the
+    ///   diagnostic.
+    /// * `hir_id` (`HirId`) - The item.
+    /// * `ident` (`Ident`) - The name of the item.
+    /// * `span` (`Span`) - The span of the item.
+    fn check_definition(
+        &self,
+        context: &LateContext<'_>,
+        hir_id: HirId,
+        ident: Ident,
+        span: Span,
+    ) {
+        if !span.from_expansion()
+            && self.is_check_name(ident.as_str())
+            && let Some(gate) = attribute_gate(context, hir_id)
+        {
+            report(context, span, ident.name, gate);
+        }
+    }
+}
+
+/// Emits a `SECURITY_DEBUG_ONLY_CHECK` or `SECURITY_FEATURE_GATED_CHECK`
+/// finding at `span`, where the check `name` is compiled under `gate`.
+///
+/// # Arguments
+/// * `context` (`&LateContext<'_>`) - The lint context used to emit the
+///   diagnostic.
+/// * `span` (`Span`) - The span of the gated code.
+/// * `name` (`Symbol`) - The name of the check.
+/// * `gate` (`Gate`) - The predicate gating the check.
+fn report(context: &LateContext<'_>, span: Span, name: Symbol, gate: Gate) {
+    let debug: &str = match gate {
+        Gate::DebugAttribute => "`#[cfg(debug_assertions)]`",
+        Gate::DebugCondition => "`cfg!(debug_assertions)`",
+        Gate::DebugAssert => "`debug_assert!`",
+        Gate::Feature(feature) => {
+            security_utils::span_lint(
+                context,
+                SECURITY_FEATURE_GATED_CHECK,
+                span,
+                |diagnostic: &mut Diag<'_, ()>| {
+                    diagnostic.primary_message(format!(
+                        "Security check `{name}` gated on feature \
+                         `{feature}` detected."
+                    ));
+                    diagnostic.note(format!(
+                        "the `{feature}` cargo feature compiles the check in \
+                         or out, so a build with the check disabled is one \
+                         flag away, and features enabled by any crate of the \
+                         dependency graph are unified"
+                    ));
+                    diagnostic.help(
+                        "enforce the check in every build, and let features \
+                         select between implementations of it",
+                    );
+                },
+            );
+            return;
+        },
+    };
+
+    security_utils::span_lint(
+        context,
+        SECURITY_DEBUG_ONLY_CHECK,
+        span,
+        |diagnostic: &mut Diag<'_, ()>| {
+            diagnostic.primary_message(format!(
+                "Security check `{name}` only compiled with \
+                 `debug_assertions` detected."
+            ));
+            diagnostic.note(format!(
+                "{debug} compiles the check out of the release profile, so \
+                 the protection is only exercised in development and absent \
+                 in production"
+            ));
+            diagnostic.help(
+                "perform the check unconditionally, or gate only its \
+                 diagnostics",
+            );
+        },
+    );
+}
+
+impl<'tcx> LateLintPass<'tcx> for SecurityCfgUsage {
+    /// Detect items named after a security check, e.g. `fn verify_token`,
+    /// only compiled with `#[cfg(debug_assertions)]` or gated on a cargo
+    /// feature.
+    ///
+    /// # Arguments
+    /// * `context` (`&LateContext<'tcx>`) - The lint context providing access
+    ///   to the compiler's internal state.
+    /// * `item` (`&'tcx Item<'tcx>`) - The item being checked for its
+    ///   predicate.
+    fn check_item(
+        &mut self,
+        context: &LateContext<'tcx>,
+        item: &'tcx Item<'tcx>,
+    ) {
+        if let Some(ident) = item.kind.ident() {
+            self.check_definition(context, item.hir_id(), ident, item.span);
+        }
+    }
+
+    /// Detect methods and associated items named after a security check only
+    /// compiled with `#[cfg(debug_assertions)]` or gated on a cargo feature.
+    ///
+    /// # Arguments
+    /// * `context` (`&LateContext<'tcx>`) - The lint context providing access
+    ///   to the compiler's internal state.
+    /// * `item` (`&'tcx ImplItem<'tcx>`) - The impl item being checked for its
+    ///   predicate.
+    fn check_impl_item(
+        &mut self,
+        context: &LateContext<'tcx>,
+        item: &'tcx ImplItem<'tcx>,
+    ) {
+        self.check_definition(context, item.hir_id(), item.ident, item.span);
+    }
+
+    /// Detect `let` statements performing a security check only compiled
+    /// with `#[cfg(debug_assertions)]` or gated on a cargo feature.
+    ///
+    /// # Arguments
+    /// * `context` (`&LateContext<'tcx>`) - The lint context providing access
+    ///   to the compiler's internal state.
+    /// * `local` (`&'tcx LetStmt<'tcx>`) - The `let` statement being checked
+    ///   for its predicate.
+    fn check_local(
+        &mut self,
+        context: &LateContext<'tcx>,
+        local: &'tcx LetStmt<'tcx>,
+    ) {
+        if local.span.from_expansion() {
+            return;
+        }
+
+        if let Some(init) = local.init
+            && let Some(name) = self.check(init)
+            && let Some(gate) = attribute_gate(context, local.hir_id)
+        {
+            report(context, local.span, name, gate);
+        }
+    }
+
+    /// Detect statements and expressions performing a security check only
+    /// compiled with `debug_assertions`: gated by `#[cfg(debug_assertions)]`,
+    /// in the body of `if cfg!(debug_assertions)`, or asserted by
+    /// `debug_assert!`. Statements and blocks gated on a cargo feature by
+    /// `#[cfg(feature = "...")]` are detected as well.
+    ///
+    /// # Arguments
+    /// * `context` (`&LateContext<'tcx>`) - The lint context providing access
+    ///   to the compiler's internal state.
+    /// * `expression` (`&'tcx Expr<'tcx>`) - The expression being checked for
+    ///   its predicate.
+    fn check_expr(
+        &mut self,
+        context: &LateContext<'tcx>,
+        expression: &'tcx Expr<'tcx>,
+    ) {
+        if let ExprKind::If(condition, then, _) = &expression.kind
+            && let Some((span, gate)) =
+                debug_condition(context, expression, condition)
+            && let Some(name) = self.check(then)
+        {
+            report(context, span, name, gate);
+        }
+
+        if !expression.span.from_expansion()
+            && let Some(name) = self.check(expression)
+            && let Some(gate) = attribute_gate(context, expression.hir_id)
+        {
+            report(context, expression.span, name, gate);
+        }
+    }
+}
+
+/// Registers the `SECURITY_DEBUG_ONLY_CHECK` and
+/// `SECURITY_FEATURE_GATED_CHECK` lints and their shared lint pass with the
+/// Rust compiler. The patterns are read from the `[cfg_usage]` table of
+/// `dylint.toml` when the pass is created.
+///
+/// # Arguments
+/// * `session` (`&Session`) - The compiler session providing access to the
+///   compiler's internal state.
+/// * `lint_store` (`&mut LintStore`) - The lint store where the lint and its
+///   lint pass will be registered.
+#[unsafe(no_mangle)]
+pub fn register_lints(session: &Session, lint_store: &mut LintStore) {
+    dylint_linting::init_config(session);
+
+    security_utils::tool::register_lints(
+        lint_store,
+        &[SECURITY_DEBUG_ONLY_CHECK, SECURITY_FEATURE_GATED_CHECK],
+    );
+    lint_store.register_late_pass(|tcx: TyCtxt<'_>| {
+        Box::new(SecurityCfgUsage::new(tcx))
+    });
+}
+
+dylint_linting::dylint_library!();
+
+/// UI tests for the `SECURITY_DEBUG_ONLY_CHECK` and
+/// `SECURITY_FEATURE_GATED_CHECK` lints. The `sso` feature is enabled, so
+/// that the code gated on it is compiled.
+#[cfg(test)]
+mod tests {
+    use dylint_testing::ui::Test;
+
+    #[test]
+    fn ui() {
+        Test::src_base(env!("CARGO_PKG_NAME"), "ui")
+            .rustc_flags([
+                "--edition=2024",
+                "-Z",
+                "ui-testing",
+                "--cfg",
+                "feature=\"sso\"",
+            ])
+            .run();
+    }
+}
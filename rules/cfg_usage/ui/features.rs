@@ -0,0 +1,56 @@
+struct Session {
+    roles: Vec<String>,
+}
+
+fn has_permission(session: &Session, role: &str) -> bool {
+    session.roles.iter().any(|granted: &String| granted == role)
+}
+
+// Should trigger.
+#[cfg(feature = "sso")]
+fn authenticate(session: &Session) -> bool {
+    !session.roles.is_empty()
+}
+
+// Should trigger.
+#[cfg(not(feature = "insecure"))]
+fn enforce_acl(session: &Session) -> bool {
+    has_permission(session, "admin")
+}
+
+// Should not trigger.
+#[cfg(feature = "sso")]
+fn display_name(session: &Session) -> String {
+    session.roles.join(", ")
+}
+
+fn delete(session: &Session) -> Result<(), &'static str> {
+    // Should trigger.
+    #[cfg(feature = "sso")]
+    {
+        if !has_permission(session, "admin") {
+            return Err("forbidden");
+        }
+    }
+
+    // Should trigger.
+    #[cfg(all(unix, not(feature = "insecure")))]
+    let allowed: bool = has_permission(session, "delete");
+
+    // Should not trigger.
+    #[cfg(feature = "sso")]
+    let name: String = display_name(session);
+
+    // Should not trigger.
+    if !has_permission(session, "delete") {
+        return Err("forbidden");
+    }
+
+    let _ = (allowed, name, authenticate(session), enforce_acl(session));
+    Ok(())
+}
+
+fn main() {
+    let session: Session = Session { roles: vec![String::from("admin")] };
+    let _ = delete(&session);
+}
@@ -0,0 +1,96 @@
+struct Request {
+    token: String,
+}
+
+impl Request {
+    fn is_authorized(&self) -> bool {
+        !self.token.is_empty()
+    }
+
+    // Should trigger.
+    #[cfg(debug_assertions)]
+    fn validate_origin(&self) -> bool {
+        true
+    }
+
+    // Should not trigger.
+    #[cfg(debug_assertions)]
+    fn dump(&self) -> String {
+        self.token.clone()
+    }
+}
+
+fn verify_signature(payload: &[u8]) -> bool {
+    !payload.is_empty()
+}
+
+// Should trigger.
+#[cfg(debug_assertions)]
+fn check_permissions(request: &Request) -> bool {
+    request.is_authorized()
+}
+
+// Should not trigger.
+#[cfg(not(debug_assertions))]
+fn check_permissions(request: &Request) -> bool {
+    request.is_authorized()
+}
+
+// Should not trigger.
+#[cfg(debug_assertions)]
+fn log_request(request: &Request) {
+    let _ = request.token.len();
+}
+
+fn handle(request: &Request, payload: &[u8]) -> Option<usize> {
+    // Should trigger.
+    if cfg!(debug_assertions) {
+        if !request.is_authorized() {
+            return None;
+        }
+    }
+
+    // Should trigger.
+    debug_assert!(verify_signature(payload));
+
+    // Should trigger.
+    #[cfg(debug_assertions)]
+    let valid: bool = verify_signature(payload);
+
+    // Should trigger.
+    #[cfg(all(debug_assertions, unix))]
+    {
+        if !request.is_authorized() {
+            return None;
+        }
+    }
+
+    // Should not trigger.
+    if cfg!(not(debug_assertions)) {
+        if !request.is_authorized() {
+            return None;
+        }
+    }
+
+    // Should not trigger.
+    if cfg!(debug_assertions) {
+        log_request(request);
+    }
+
+    // Should not trigger.
+    debug_assert!(!payload.is_empty());
+
+    // Should not trigger.
+    if !request.is_authorized() || !verify_signature(payload) {
+        return None;
+    }
+
+    let _ = (valid, check_permissions(request), request.validate_origin());
+    Some(payload.len())
+}
+
+fn main() {
+    let request: Request = Request { token: String::from("token") };
+    let _ = handle(&request, b"payload");
+    let _ = request.dump();
+}
@@ -0,0 +1,575 @@
+#![feature(rustc_private)]
+
+extern crate rustc_errors;
+extern crate rustc_hir;
+extern crate rustc_lint;
+extern crate rustc_middle;
+extern crate rustc_session;
+extern crate rustc_span;
+
+use rustc_errors::Diag;
+use rustc_hir::{
+    BinOpKind,
+    Expr,
+    ExprKind,
+    HirId,
+    Node,
+    PatKind,
+    QPath,
+    UnOp,
+    def::DefKind,
+    def_id::DefId,
+};
+use rustc_lint::{LateContext, LateLintPass, LintStore};
+use rustc_middle::ty::{AdtDef, TyCtxt};
+use rustc_session::{Session, declare_lint, impl_lint_pass};
+use rustc_span::{Symbol, sym};
+use serde::Deserialize;
+
+declare_lint! {
+    pub SECURITY_DEFAULT_ON_PARSE_FAILURE,
+    Warn,
+    "Detects `unwrap_or_default`, `unwrap_or` and `unwrap_or_else` on the results of parsing configuration, keys or policies, which fall back to a default on malformed input."
+}
+
+declare_lint! {
+    pub SECURITY_LOSSY_UTF8_DECODING,
+    Warn,
+    "Detects `String::from_utf8_lossy` on data used for verification, paths or identifier comparisons, where the replaced bytes slip past validation."
+}
+
+/// Methods returning a view or a copy of their receiver, through which the
+/// data keeps flowing, e.g. `String::from_utf8_lossy(&raw).into_owned()`.
+const CONVERSIONS: [&str; 12] = [
+    "as_bytes",
+    "as_ref",
+    "as_slice",
+    "as_str",
+    "borrow",
+    "clone",
+    "into_owned",
+    "to_lowercase",
+    "to_owned",
+    "to_string",
+    "to_vec",
+    "trim",
+];
+
+/// Methods replacing the error of a `Result` with a fallback value.
+const FALLBACKS: [&str; 3] =
+    ["unwrap_or", "unwrap_or_default", "unwrap_or_else"];
+
+/// Configuration of the `error_masking` library, read from the
+/// `[error_masking]` table of the workspace's `dylint.toml`.
+#[derive(Deserialize)]
+#[serde(default)]
+struct Config {
+    /// Identifier words that mark decoded data as security-relevant (e.g.
+    /// `path` matches `file_path`, `PathBuf::from` and `paths`), matched
+    /// against the decoded buffer, the binding or field receiving the
+    /// string, the function it is passed to and the operand it is compared
+    /// with.
+    lossy_patterns: Vec<String>,
+    /// Identifier words that mark parsed input as security-relevant (e.g.
+    /// `config` matches `load_config`, `Config::from_str` and `configs`),
+    /// matched against the parsing function, its input and the binding or
+    /// field receiving the fallback.
+    fallback_patterns: Vec<String>,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Self {
+            lossy_patterns: [
+                "signature",
+                "verify",
+                "path",
+                "file",
+                "dir",
+                "fs",
+                "user",
+                "username",
+                "login",
+                "email",
+                "host",
+                "domain",
+                "id",
+            ]
+            .into_iter()
+            .map(String::from)
+            .collect(),
+            fallback_patterns: [
+                "config",
+                "configuration",
+                "settings",
+                "policy",
+                "key",
+                "cert",
+                "certificate",
+                "permission",
+                "role",
+                "acl",
+                "cipher",
+                "tls",
+            ]
+            .into_iter()
+            .map(String::from)
+            .collect(),
+        }
+    }
+}
+
+/// Lint pass holding the identifier patterns loaded from the configuration.
+struct SecurityErrorMasking {
+    lossy_patterns: Vec<String>,
+    fallback_patterns: Vec<String>,
+}
+
+impl_lint_pass!(SecurityErrorMasking => [
+    SECURITY_LOSSY_UTF8_DECODING,
+    SECURITY_DEFAULT_ON_PARSE_FAILURE,
+]);
+
+/// Returns `true` if `def_id` is `String::from_utf8_lossy`.
+///
+/// # Arguments
+/// * `tcx` (`TyCtxt<'_>`) - The type context used to resolve the function.
+/// * `def_id` (`DefId`) - The called function.
+fn is_lossy_decoding(tcx: TyCtxt<'_>, def_id: DefId) -> bool {
+    if tcx.item_name(def_id).as_str() != "from_utf8_lossy" {
+        return false;
+    }
+
+    let parent: DefId = tcx.parent(def_id);
+    matches!(tcx.def_kind(parent), DefKind::Impl { .. })
+        && tcx.type_of(parent).instantiate_identity().ty_adt_def().is_some_and(
+            |adt: AdtDef<'_>| tcx.lang_items().string() == Some(adt.did()),
+        )
+}
+
+/// Returns the name of the buffer read by `expression`: a local variable, a
+/// path, a field or a method call, looking through borrows, slicing and
+/// conversions (e.g. `&raw.signature[..len]`).
+///
+/// # Arguments
+/// * `expression` (`&Expr<'_>`) - The decoded expression.
+fn buffer_name(expression: &Expr<'_>) -> Option<Symbol> {
+    match &expression.kind {
+        ExprKind::AddrOf(_, _, inner)
+        | ExprKind::Unary(UnOp::Deref, inner)
+        | ExprKind::Index(inner, _, _) => buffer_name(inner),
+        ExprKind::MethodCall(segment, receiver, _, _) => {
+            if CONVERSIONS.contains(&segment.ident.name.as_str()) {
+                buffer_name(receiver)
+            } else {
+                Some(segment.ident.name)
+            }
+        },
+        ExprKind::Path(QPath::Resolved(None, path)) => {
+            Some(path.segments.last()?.ident.name)
+        },
+        ExprKind::Field(_, ident) => Some(ident.name),
+        _ => None,
+    }
+}
+
+/// Returns the function or method called by `expression`, if it is a call.
+///
+/// # Arguments
+/// * `context` (`&LateContext<'_>`) - The lint context, used to resolve the
+///   callee.
+/// * `expression` (`&Expr<'_>`) - The expression to inspect.
+fn callee(context: &LateContext<'_>, expression: &Expr<'_>) -> Option<DefId> {
+    match &expression.kind {
+        ExprKind::MethodCall(..) => {
+            context.typeck_results().type_dependent_def_id(expression.hir_id)
+        },
+        ExprKind::Call(callee, _) => {
+            let ExprKind::Path(path): &ExprKind<'_> = &callee.kind else {
+                return None;
+            };
+            context.qpath_res(path, callee.hir_id).opt_def_id()
+        },
+        _ => None,
+    }
+}
+
+/// Returns `true` if the name of `def_id` or of its parent (the type or
+/// module defining it, e.g. `Path` for `Path::new`) matches `patterns`.
+///
+/// # Arguments
+/// * `patterns` (`&[String]`) - The lowercase identifier words.
+/// * `tcx` (`TyCtxt<'_>`) - The type context used to name the function.
+/// * `def_id` (`DefId`) - The called function.
+fn matches_function(
+    patterns: &[String],
+    tcx: TyCtxt<'_>,
+    def_id: DefId,
+) -> bool {
+    tcx.def_path_str(def_id)
+        .rsplit("::")
+        .take(2)
+        .any(|segment: &str| security_utils::words::matches(patterns, segment))
+}
+
+/// Describes the `let` binding, struct field or assigned place receiving a
+/// value, if its name matches `patterns`.
+///
+/// # Arguments
+/// * `patterns` (`&[String]`) - The lowercase identifier words.
+/// * `node` (`Node<'_>`) - The parent of the value.
+fn sensitive_destination(
+    patterns: &[String],
+    node: Node<'_>,
+) -> Option<String> {
+    let (name, destination): (Symbol, &str) = match node {
+        Node::LetStmt(local) => {
+            let PatKind::Binding(_, _, ident, _): PatKind<'_> = local.pat.kind
+            else {
+                return None;
+            };
+            (ident.name, "is bound to")
+        },
+        Node::ExprField(field) => (field.ident.name, "is stored in the field"),
+        Node::Expr(Expr { kind: ExprKind::Assign(place, _, _), .. }) => {
+            (buffer_name(place)?, "is assigned to")
+        },
+        _ => return None,
+    };
+
+    security_utils::words::matches(patterns, name.as_str())
+        .then(|| format!("{destination} `{name}`"))
+}
+
+impl SecurityErrorMasking {
+    fn new(tcx: TyCtxt<'_>) -> Self {
+        let config: Config =
+            security_utils::config::load(tcx, env!("CARGO_PKG_NAME"));
+
+        Self {
+            lossy_patterns: config
+                .lossy_patterns
+                .into_iter()
+                .map(|pattern: String| pattern.to_lowercase())
+                .collect(),
+            fallback_patterns: config
+                .fallback_patterns
+                .into_iter()
+                .map(|pattern: String| pattern.to_lowercase())
+                .collect(),
+        }
+    }
+
+    /// Describes how the string decoded by `expression` is used, if it is
+    /// bound to or stored in a security-relevant name, passed to a
+    /// security-relevant function, or compared with a security-relevant
+    /// operand. Borrows, dereferences and conversions of the string are
+    /// followed up to its use.
+    ///
+    /// # Arguments
+    /// * `context` (`&LateContext<'tcx>`) - The lint context, used to walk the
+    ///   parents of the expression and resolve the callees.
+    /// * `expression` (`&'tcx Expr<'tcx>`) - The decoding call.
+    fn sensitive_use<'tcx>(
+        &self,
+        context: &LateContext<'tcx>,
+        expression: &'tcx Expr<'tcx>,
+    ) -> Option<String> {
+        let tcx: TyCtxt<'tcx> = context.tcx;
+        let mut current: HirId = expression.hir_id;
+
+        loop {
+            let node: Node<'tcx> = tcx.parent_hir_node(current);
+            let Node::Expr(parent): Node<'tcx> = node else {
+                return sensitive_destination(&self.lossy_patterns, node);
+            };
+
+            match &parent.kind {
+                ExprKind::AddrOf(..) | ExprKind::Unary(UnOp::Deref, _) => {},
+                ExprKind::MethodCall(segment, receiver, _, _)
+                    if receiver.hir_id == current
+                        && CONVERSIONS
+                            .contains(&segment.ident.name.as_str()) => {},
+                ExprKind::Call(..) | ExprKind::MethodCall(..) => {
+                    let def_id: DefId = callee(context, parent)?;
+                    return matches_function(
+                        &self.lossy_patterns,
+                        tcx,
+                        def_id,
+                    )
+                    .then(|| {
+                        format!("is passed to `{}`", tcx.def_path_str(def_id))
+                    });
+                },
+                ExprKind::Binary(operator, left, right)
+                    if matches!(
+                        operator.node,
+                        BinOpKind::Eq | BinOpKind::Ne
+                    ) =>
+                {
+                    let other: &Expr<'_> =
+                        if left.hir_id == current { right } else { left };
+                    let name: Symbol = buffer_name(other)?;
+                    return security_utils::words::matches(
+                        &self.lossy_patterns,
+                        name.as_str(),
+                    )
+                    .then(|| format!("is compared with `{name}`"));
+                },
+                ExprKind::Assign(..) => {
+                    return sensitive_destination(&self.lossy_patterns, node);
+                },
+                _ => return None,
+            }
+
+            current = parent.hir_id;
+        }
+    }
+
+    /// Describes the security-relevant input parsed by `expression`, the
+    /// receiver of a fallback: the parsing function or method, or the input
+    /// passed to it, looking through `.ok()`.
+    ///
+    /// # Arguments
+    /// * `context` (`&LateContext<'_>`) - The lint context, used to resolve
+    ///   the parsing function.
+    /// * `expression` (`&Expr<'_>`) - The receiver of the fallback.
+    fn sensitive_input(
+        &self,
+        context: &LateContext<'_>,
+        expression: &Expr<'_>,
+    ) -> Option<String> {
+        let (inputs, receiver): (&[Expr<'_>], Option<&Expr<'_>>) =
+            match &expression.kind {
+                ExprKind::MethodCall(segment, receiver, arguments, _) => {
+                    if segment.ident.name.as_str() == "ok" {
+                        return self.sensitive_input(context, receiver);
+                    }
+                    (arguments, Some(receiver))
+                },
+                ExprKind::Call(_, arguments) => (arguments, None),
+                _ => return None,
+            };
+
+        let def_id: DefId = callee(context, expression)?;
+        if matches_function(&self.fallback_patterns, context.tcx, def_id) {
+            return Some(format!(
+                "the fallback replaces the result of `{}`",
+                context.tcx.def_path_str(def_id)
+            ));
+        }
+
+        receiver
+            .into_iter()
+            .chain(inputs)
+            .filter_map(buffer_name)
+            .find(|name: &Symbol| {
+                security_utils::words::matches(
+                    &self.fallback_patterns,
+                    name.as_str(),
+                )
+            })
+            .map(|name: Symbol| {
+                format!("the fallback replaces the value parsed from `{name}`")
+            })
+    }
+
+    /// Detect `String::from_utf8_lossy` on a buffer with a security-relevant
+    /// name, or whose result flows into a security-relevant binding,
+    /// function or comparison.
+    ///
+    /// # Arguments
+    /// * `context` (`&LateContext<'tcx>`) - The lint context, used to resolve
+    ///   the callee and emit the diagnostic.
+    /// * `expression` (`&'tcx Expr<'tcx>`) - The call being checked.
+    fn check_lossy_decoding<'tcx>(
+        &self,
+        context: &LateContext<'tcx>,
+        expression: &'tcx Expr<'tcx>,
+    ) {
+        let ExprKind::Call(_, [buffer]): &ExprKind<'_> = &expression.kind
+        else {
+            return;
+        };
+        let Some(def_id): Option<DefId> = callee(context, expression) else {
+            return;
+        };
+        if !is_lossy_decoding(context.tcx, def_id) {
+            return;
+        }
+
+        let Some(reason): Option<String> = buffer_name(buffer)
+            .filter(|name: &Symbol| {
+                security_utils::words::matches(
+                    &self.lossy_patterns,
+                    name.as_str(),
+                )
+            })
+            .map(|name: Symbol| {
+                format!("the decoded bytes come from `{name}`")
+            })
+            .or_else(|| {
+                self.sensitive_use(context, expression)
+                    .map(|usage: String| format!("the decoded string {usage}"))
+            })
+        else {
+            return;
+        };
+
+        security_utils::span_lint(
+            context,
+            SECURITY_LOSSY_UTF8_DECODING,
+            expression.span,
+            |diagnostic: &mut Diag<'_, ()>| {
+                diagnostic.primary_message(
+                    "Lossy UTF-8 decoding of security-relevant data detected.",
+                );
+                diagnostic.note(reason);
+                diagnostic.note(
+                    "invalid bytes are replaced with U+FFFD, so distinct \
+                     inputs decode to the same string and malformed data \
+                     passes validation unnoticed",
+                );
+                diagnostic.help(
+                    "decode with `String::from_utf8` or `std::str::from_utf8` \
+                     and reject invalid input",
+                );
+            },
+        );
+    }
+
+    /// Detect `unwrap_or_default`, `unwrap_or` and `unwrap_or_else` on a
+    /// `Result` parsing security-relevant input, or whose fallback is bound
+    /// to a security-relevant name.
+    ///
+    /// # Arguments
+    /// * `context` (`&LateContext<'tcx>`) - The lint context, used to type the
+    ///   receiver and emit the diagnostic.
+    /// * `expression` (`&'tcx Expr<'tcx>`) - The method call being checked.
+    fn check_fallback<'tcx>(
+        &self,
+        context: &LateContext<'tcx>,
+        expression: &'tcx Expr<'tcx>,
+    ) {
+        let ExprKind::MethodCall(segment, receiver, _, _): &ExprKind<'_> =
+            &expression.kind
+        else {
+            return;
+        };
+        let method: Symbol = segment.ident.name;
+        if !FALLBACKS.contains(&method.as_str()) {
+            return;
+        }
+
+        // `result.ok().unwrap_or(...)` discards the error just the same.
+        let result: &Expr<'_> = match &receiver.kind {
+            ExprKind::MethodCall(segment, inner, _, _)
+                if segment.ident.name.as_str() == "ok" =>
+            {
+                inner
+            },
+            _ => receiver,
+        };
+        let is_result: bool =
+            context.typeck_results().expr_ty(result).ty_adt_def().is_some_and(
+                |adt: AdtDef<'_>| {
+                    context.tcx.is_diagnostic_item(sym::Result, adt.did())
+                },
+            );
+        if !is_result {
+            return;
+        }
+
+        let Some(reason): Option<String> =
+            self.sensitive_input(context, receiver).or_else(|| {
+                sensitive_destination(
+                    &self.fallback_patterns,
+                    context.tcx.parent_hir_node(expression.hir_id),
+                )
+                .map(|usage: String| format!("the fallback {usage}"))
+            })
+        else {
+            return;
+        };
+
+        security_utils::span_lint(
+            context,
+            SECURITY_DEFAULT_ON_PARSE_FAILURE,
+            expression.span,
+            |diagnostic: &mut Diag<'_, ()>| {
+                diagnostic.primary_message(format!(
+                    "Parse failure of security-relevant input masked by \
+                     `{method}` detected."
+                ));
+                diagnostic.note(reason);
+                diagnostic.note(
+                    "a malformed or tampered input silently yields the \
+                     default, which may be weaker than the intended setting",
+                );
+                diagnostic.help(
+                    "propagate the error, e.g. with `?`, and refuse to start \
+                     or to proceed on invalid input",
+                );
+            },
+        );
+    }
+}
+
+impl<'tcx> LateLintPass<'tcx> for SecurityErrorMasking {
+    /// Detect lossy UTF-8 decoding and fallbacks on parse failures of
+    /// security-relevant data.
+    ///
+    /// # Arguments
+    /// * `context` (`&LateContext<'tcx>`) - The lint context providing access
+    ///   to the compiler's internal state.
+    /// * `expression` (`&'tcx Expr<'tcx>`) - The expression being checked.
+    fn check_expr(
+        &mut self,
+        context: &LateContext<'tcx>,
+        expression: &'tcx Expr<'tcx>,
+    ) {
+        if expression.span.from_expansion() {
+            return;
+        }
+
+        self.check_lossy_decoding(context, expression);
+        self.check_fallback(context, expression);
+    }
+}
+
+/// Registers the `error_masking` lints and their lint pass with the Rust
+/// compiler. The identifier patterns are read from the `[error_masking]`
+/// table of `dylint.toml` when the pass is created.
+///
+/// # Arguments
+/// * `session` (`&Session`) - The compiler session providing access to the
+///   compiler's internal state.
+/// * `lint_store` (`&mut LintStore`) - The lint store where the lints and
+///   their lint pass will be registered.
+#[unsafe(no_mangle)]
+pub fn register_lints(session: &Session, lint_store: &mut LintStore) {
+    dylint_linting::init_config(session);
+
+    security_utils::tool::register_lints(
+        lint_store,
+        &[SECURITY_LOSSY_UTF8_DECODING, SECURITY_DEFAULT_ON_PARSE_FAILURE],
+    );
+    lint_store.register_late_pass(|tcx: TyCtxt<'_>| {
+        Box::new(SecurityErrorMasking::new(tcx))
+    });
+}
+
+dylint_linting::dylint_library!();
+
+/// UI tests for the `error_masking` lints.
+#[cfg(test)]
+mod tests {
+    use dylint_testing::ui::Test;
+
+    #[test]
+    fn ui() {
+        Test::src_base(env!("CARGO_PKG_NAME"), "ui")
+            .rustc_flags(["--edition=2024", "-Z", "ui-testing"])
+            .run();
+    }
+}
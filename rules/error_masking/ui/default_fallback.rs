@@ -0,0 +1,61 @@
+#![allow(dead_code)]
+
+use std::str::FromStr;
+
+#[derive(Default)]
+struct Policy {
+    max_attempts: u32,
+}
+
+impl FromStr for Policy {
+    type Err = ();
+
+    fn from_str(raw: &str) -> Result<Self, ()> {
+        raw.parse().map(|max_attempts: u32| Policy { max_attempts }).map_err(drop)
+    }
+}
+
+struct Settings {
+    key_bits: u32,
+}
+
+fn load_config(_path: &str) -> Result<String, std::io::Error> {
+    Ok(String::new())
+}
+
+fn read_input() -> Result<String, std::io::Error> {
+    Ok(String::new())
+}
+
+fn startup(raw_policy: &str, raw_timeout: &str, raw_bits: &str, key_size: &str) {
+    // Should trigger (the parser names a configuration).
+    let _ = load_config("app.toml").unwrap_or_default();
+
+    // Should trigger (the parsed input is a policy).
+    let _ = Policy::from_str(raw_policy).unwrap_or_default();
+
+    // Should trigger (the parsed input is a key size, through `.ok()`).
+    let _ = key_size.parse::<usize>().ok().unwrap_or(2048);
+
+    // Should trigger (the fallback is bound to a policy).
+    let policy: Policy = read_input()
+        .map_err(drop)
+        .and_then(|raw: String| raw.parse())
+        .unwrap_or_else(|()| Policy::default());
+
+    // Should trigger (the fallback is stored in a key field).
+    let _ = Settings { key_bits: raw_bits.parse().unwrap_or(1024) };
+
+    // Should not trigger (the input is not security-relevant).
+    let retries: u32 = raw_timeout.parse().unwrap_or(3);
+
+    // Should not trigger (the error is propagated).
+    let _ = Policy::from_str(raw_policy).map(|policy: Policy| policy.max_attempts);
+
+    // Should not trigger (`Option` lookups have no error to mask).
+    let _ = [1, 2].first().copied().unwrap_or_default();
+
+    let _ = (policy, retries);
+}
+
+fn main() {}
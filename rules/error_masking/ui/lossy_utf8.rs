@@ -0,0 +1,54 @@
+#![allow(dead_code)]
+
+use std::path::{Path, PathBuf};
+
+struct Request {
+    signature: Vec<u8>,
+    body: Vec<u8>,
+}
+
+struct Account {
+    username: String,
+}
+
+fn verify_signature(_signature: &str) -> bool {
+    true
+}
+
+fn log(_message: &str) {}
+
+fn handle(request: &Request, raw_name: &[u8], expected_user: &str) {
+    // Should trigger (the buffer is a signature).
+    let _ = String::from_utf8_lossy(&request.signature);
+
+    // Should trigger (the string is decoded into a path).
+    let _ = Path::new(&*String::from_utf8_lossy(raw_name));
+
+    // Should trigger (the string is passed to a verification).
+    verify_signature(&String::from_utf8_lossy(&request.body));
+
+    // Should trigger (the string is bound to an identifier).
+    let username = String::from_utf8_lossy(raw_name).into_owned();
+
+    // Should trigger (the string is compared with an identifier).
+    if String::from_utf8_lossy(raw_name) == expected_user {}
+
+    // Should trigger (the string is stored in an identifier field).
+    let _ = Account { username: String::from_utf8_lossy(raw_name).to_string() };
+
+    // Should trigger (the string is joined to a path).
+    let _ = PathBuf::from("/srv").join(String::from_utf8_lossy(raw_name).as_ref());
+
+    // Should not trigger (the string is only logged).
+    log(&String::from_utf8_lossy(&request.body));
+
+    // Should not trigger.
+    let message = String::from_utf8_lossy(&request.body);
+
+    // Should not trigger (invalid input is rejected).
+    let _ = String::from_utf8(request.signature.clone());
+
+    let _ = (username, message);
+}
+
+fn main() {}
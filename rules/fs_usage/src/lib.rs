@@ -0,0 +1,429 @@
+#![feature(rustc_private)]
+
+extern crate rustc_ast;
+extern crate rustc_errors;
+extern crate rustc_hir;
+extern crate rustc_lint;
+extern crate rustc_middle;
+extern crate rustc_session;
+extern crate rustc_span;
+
+use rustc_ast::LitKind;
+use rustc_errors::Diag;
+use rustc_hir::{
+    Block,
+    Expr,
+    ExprKind,
+    HirId,
+    Node,
+    PatKind,
+    QPath,
+    Stmt,
+    StmtKind,
+    def::Res,
+    def_id::DefId,
+    intravisit::{Visitor, walk_expr},
+};
+use rustc_lint::{LateContext, LateLintPass, LintStore};
+use rustc_middle::ty::TyCtxt;
+use rustc_session::{Session, declare_lint, declare_lint_pass};
+use rustc_span::Span;
+use security_utils::calls::call;
+
+declare_lint! {
+    pub SECURITY_LATE_PERMISSIONS,
+    Warn,
+    "Detects files created with the default permissions and restricted afterwards."
+}
+
+declare_lint_pass!(SecurityFsUsage => [SECURITY_LATE_PERMISSIONS]);
+
+/// Functions creating a file with the default permissions, with the index of
+/// the path among their inputs, the receiver of a method included.
+const CREATING_FUNCTIONS: [(&str, usize); 4] = [
+    ("std::fs::File::create", 0),
+    ("std::fs::File::create_new", 0),
+    ("std::fs::write", 0),
+    ("std::fs::OpenOptions::open", 1),
+];
+
+/// Function setting the permissions of the file at a path.
+const PATH_PERMISSIONS_SETTER: &str = "std::fs::set_permissions";
+
+/// Method setting the permissions of an open file.
+const FILE_PERMISSIONS_SETTER: &str = "std::fs::File::set_permissions";
+
+/// Function building permissions from a Unix mode.
+const MODE_CONSTRUCTOR: &str = "std::os::unix::fs::PermissionsExt::from_mode";
+
+/// Method setting the Unix mode of permissions.
+const MODE_SETTER: &str = "std::os::unix::fs::PermissionsExt::set_mode";
+
+/// Method setting the mode of the files created by `OpenOptions`.
+const OPEN_MODE_SETTER: &str = "std::os::unix::fs::OpenOptionsExt::mode";
+
+/// A file created by a statement of a block.
+struct Creation {
+    /// The source of the path of the file, e.g. `&path`.
+    path: Option<String>,
+    /// The local the statement binds the created file to, if any.
+    file: Option<HirId>,
+    /// The span of the creating call.
+    span: Span,
+}
+
+/// A call found in a statement: the path of the called function, its inputs,
+/// the receiver of a method included, and its span.
+type Call<'tcx> = (String, Vec<&'tcx Expr<'tcx>>, Span);
+
+/// Visitor collecting the calls of a statement, without entering the nested
+/// blocks and closures, which are checked as blocks of their own.
+struct CallCollector<'a, 'tcx> {
+    context: &'a LateContext<'tcx>,
+    calls: Vec<Call<'tcx>>,
+}
+
+impl<'tcx> Visitor<'tcx> for CallCollector<'_, 'tcx> {
+    fn visit_expr(&mut self, expression: &'tcx Expr<'tcx>) {
+        if matches!(
+            expression.kind,
+            ExprKind::Block(..) | ExprKind::Closure(_)
+        ) {
+            return;
+        }
+
+        if let Some(call) = call(self.context, expression) {
+            self.calls.push((
+                security_utils::paths::function_path(
+                    self.context.tcx,
+                    call.callee,
+                ),
+                call.inputs(),
+                expression.span,
+            ));
+        }
+        walk_expr(self, expression);
+    }
+}
+
+/// Returns `expression` without its leading borrows, e.g. `path` for
+/// `&path`.
+///
+/// # Arguments
+/// * `expression` (`&'tcx Expr<'tcx>`) - The expression to peel.
+fn peel_borrows<'tcx>(expression: &'tcx Expr<'tcx>) -> &'tcx Expr<'tcx> {
+    match &expression.kind {
+        ExprKind::AddrOf(_, _, inner) => peel_borrows(inner),
+        _ => expression,
+    }
+}
+
+/// Returns the local read by `expression`, behind any borrows.
+///
+/// # Arguments
+/// * `expression` (`&Expr<'_>`) - The expression to inspect.
+fn local(expression: &Expr<'_>) -> Option<HirId> {
+    match &peel_borrows(expression).kind {
+        ExprKind::Path(QPath::Resolved(None, path)) => match path.res {
+            Res::Local(id) => Some(id),
+            _ => None,
+        },
+        _ => None,
+    }
+}
+
+/// Returns the source of the path `expression`, behind any borrows, used to
+/// match the path of a creation with the one of `set_permissions`.
+///
+/// # Arguments
+/// * `context` (`&LateContext<'_>`) - The lint context, used to read the
+///   source.
+/// * `expression` (`&Expr<'_>`) - The path argument.
+fn path_source(
+    context: &LateContext<'_>,
+    expression: &Expr<'_>,
+) -> Option<String> {
+    context
+        .tcx
+        .sess
+        .source_map()
+        .span_to_snippet(peel_borrows(expression).span)
+        .ok()
+}
+
+/// Returns the integer value of `expression`, if it is an integer literal.
+///
+/// # Arguments
+/// * `expression` (`&Expr<'_>`) - The expression to inspect.
+fn integer(expression: &Expr<'_>) -> Option<u128> {
+    match &expression.kind {
+        ExprKind::Lit(literal) => match literal.node {
+            LitKind::Int(value, _) => Some(value.get()),
+            _ => None,
+        },
+        _ => None,
+    }
+}
+
+/// Returns `true` if the `OpenOptions` receiver of `open` is a chain
+/// creating the file without setting its mode, e.g.
+/// `OpenOptions::new().write(true).create(true)`. A receiver bound to a
+/// local may have its mode set elsewhere, and is not considered.
+///
+/// # Arguments
+/// * `context` (`&LateContext<'_>`) - The lint context, used to resolve the
+///   methods.
+/// * `options` (`&Expr<'_>`) - The receiver of `open`.
+fn creates_with_default_mode(
+    context: &LateContext<'_>,
+    options: &Expr<'_>,
+) -> bool {
+    let mut creates: bool = false;
+    let mut current: &Expr<'_> = options;
+    loop {
+        match &current.kind {
+            ExprKind::MethodCall(segment, receiver, _, _) => {
+                if context
+                    .typeck_results()
+                    .type_dependent_def_id(current.hir_id)
+                    .is_some_and(|id: DefId| {
+                        security_utils::paths::function_path(context.tcx, id)
+                            == OPEN_MODE_SETTER
+                    })
+                {
+                    return false;
+                }
+                creates |=
+                    matches!(segment.ident.as_str(), "create" | "create_new");
+                current = receiver;
+            },
+            ExprKind::Call(..) => return creates,
+            _ => return false,
+        }
+    }
+}
+
+/// Returns the Unix mode of the permissions `expression`, built with
+/// `Permissions::from_mode` or set with `set_mode` on a local by an earlier
+/// statement of the block, as recorded in `modes`.
+///
+/// # Arguments
+/// * `context` (`&LateContext<'tcx>`) - The lint context, used to resolve the
+///   calls and locals.
+/// * `expression` (`&'tcx Expr<'tcx>`) - The permissions argument.
+/// * `modes` (`&[(HirId, u128)]`) - The modes set on locals so far.
+fn permissions_mode<'tcx>(
+    context: &LateContext<'tcx>,
+    expression: &'tcx Expr<'tcx>,
+    modes: &[(HirId, u128)],
+) -> Option<u128> {
+    if let Some(id) = local(expression) {
+        if let Some((_, mode)) =
+            modes.iter().rev().find(|(local, _): &&(HirId, u128)| *local == id)
+        {
+            return Some(*mode);
+        }
+        let Node::LetStmt(binding): Node<'tcx> =
+            context.tcx.parent_hir_node(id)
+        else {
+            return None;
+        };
+        return permissions_mode(context, binding.init?, modes);
+    }
+
+    let call: security_utils::calls::Call<'_> =
+        call(context, peel_borrows(expression))?;
+    (security_utils::paths::function_path(context.tcx, call.callee)
+        == MODE_CONSTRUCTOR)
+        .then(|| integer(call.inputs().first()?))
+        .flatten()
+}
+
+/// Emits a `SECURITY_LATE_PERMISSIONS` finding at `span`, restricting the
+/// permissions of the file created at `creation`.
+///
+/// # Arguments
+/// * `context` (`&LateContext<'_>`) - The lint context used to emit the
+///   diagnostic.
+/// * `span` (`Span`) - The span of the `set_permissions` call.
+/// * `creation` (`&Creation`) - The creation of the file.
+fn report(context: &LateContext<'_>, span: Span, creation: &Creation) {
+    security_utils::span_lint(
+        context,
+        SECURITY_LATE_PERMISSIONS,
+        span,
+        |diagnostic: &mut Diag<'_, ()>| {
+            diagnostic.primary_message(match &creation.path {
+                Some(path) => format!(
+                    "Permissions of `{path}` restricted after its creation \
+                     detected."
+                ),
+                None => String::from(
+                    "Permissions of a file restricted after its creation \
+                     detected.",
+                ),
+            });
+            diagnostic.span_note(
+                creation.span,
+                "the file is created here with the default permissions",
+            );
+            diagnostic.note(
+                "until `set_permissions` runs, the file exists with the \
+                 default permissions (`0o666` without the umask, usually \
+                 world-readable), and another user may open it in this \
+                 window and keep reading it afterwards",
+            );
+            diagnostic.help(
+                "create the file with its final permissions, e.g. with \
+                 `OpenOptions::new().write(true).create_new(true).mode(0o600)\
+                 .open(path)`",
+            );
+        },
+    );
+}
+
+impl<'tcx> LateLintPass<'tcx> for SecurityFsUsage {
+    /// Detect a file created by a statement of `block` with the default
+    /// permissions, whose permissions a later statement restricts to a mode
+    /// not readable by others, with `fs::set_permissions` on the same path
+    /// or `File::set_permissions` on the created file.
+    ///
+    /// # Arguments
+    /// * `context` (`&LateContext<'tcx>`) - The lint context providing access
+    ///   to the compiler's internal state.
+    /// * `block` (`&'tcx Block<'tcx>`) - The block being checked.
+    fn check_block(
+        &mut self,
+        context: &LateContext<'tcx>,
+        block: &'tcx Block<'tcx>,
+    ) {
+        if block.span.from_expansion() {
+            return;
+        }
+
+        let mut creations: Vec<Creation> = Vec::new();
+        let mut modes: Vec<(HirId, u128)> = Vec::new();
+
+        let statements: Vec<(&Expr<'_>, Option<HirId>)> = block
+            .stmts
+            .iter()
+            .filter_map(|statement: &Stmt<'_>| match statement.kind {
+                StmtKind::Let(binding) => Some((
+                    binding.init?,
+                    match binding.pat.kind {
+                        PatKind::Binding(_, id, _, _) => Some(id),
+                        _ => None,
+                    },
+                )),
+                StmtKind::Expr(expression) | StmtKind::Semi(expression) => {
+                    Some((expression, None))
+                },
+                StmtKind::Item(_) => None,
+            })
+            .chain(block.expr.map(|expression: &Expr<'_>| (expression, None)))
+            .collect();
+
+        for (expression, binding) in statements {
+            let mut collector: CallCollector<'_, 'tcx> =
+                CallCollector { context, calls: Vec::new() };
+            collector.visit_expr(expression);
+
+            for (path, inputs, span) in collector.calls {
+                if let Some((function, index)) = CREATING_FUNCTIONS
+                    .iter()
+                    .find(|(function, _): &&(&str, usize)| *function == path)
+                {
+                    let Some(file): Option<&&Expr<'_>> = inputs.get(*index)
+                    else {
+                        continue;
+                    };
+                    if *function == "std::fs::OpenOptions::open"
+                        && !inputs.first().is_some_and(
+                            |options: &&Expr<'_>| {
+                                creates_with_default_mode(context, options)
+                            },
+                        )
+                    {
+                        continue;
+                    }
+                    creations.push(Creation {
+                        path: path_source(context, file),
+                        file: binding,
+                        span,
+                    });
+                    continue;
+                }
+
+                let (Some(target), Some(permissions)): (
+                    Option<&&Expr<'_>>,
+                    Option<&&Expr<'_>>,
+                ) = (inputs.first(), inputs.get(1)) else {
+                    continue;
+                };
+
+                let creation: Option<&Creation> = if path == MODE_SETTER {
+                    if let Some(id) = local(target)
+                        && let Some(mode) = integer(permissions)
+                    {
+                        modes.push((id, mode));
+                    }
+                    continue;
+                } else if path == PATH_PERMISSIONS_SETTER {
+                    let source: Option<String> = path_source(context, target);
+                    creations.iter().find(|creation: &&Creation| {
+                        source.is_some() && creation.path == source
+                    })
+                } else if path == FILE_PERMISSIONS_SETTER {
+                    let file: Option<HirId> = local(target);
+                    creations.iter().find(|creation: &&Creation| {
+                        file.is_some() && creation.file == file
+                    })
+                } else {
+                    continue;
+                };
+
+                if let Some(creation) = creation
+                    && permissions_mode(context, permissions, &modes)
+                        .is_some_and(|mode: u128| mode & 0o004 == 0)
+                {
+                    report(context, span, creation);
+                }
+            }
+        }
+    }
+}
+
+/// Registers the `SECURITY_LATE_PERMISSIONS` lint and its corresponding lint
+/// pass with the Rust compiler.
+///
+/// # Arguments
+/// * `session` (`&Session`) - The compiler session providing access to the
+///   compiler's internal state.
+/// * `lint_store` (`&mut LintStore`) - The lint store where the
+///   `SECURITY_LATE_PERMISSIONS` lint and its corresponding lint pass will be
+///   registered.
+#[unsafe(no_mangle)]
+pub fn register_lints(session: &Session, lint_store: &mut LintStore) {
+    dylint_linting::init_config(session);
+
+    security_utils::tool::register_lints(
+        lint_store,
+        &[SECURITY_LATE_PERMISSIONS],
+    );
+    lint_store.register_late_pass(|_: TyCtxt<'_>| Box::new(SecurityFsUsage));
+}
+
+dylint_linting::dylint_library!();
+
+/// UI tests for the `SECURITY_LATE_PERMISSIONS` lint.
+#[cfg(test)]
+mod tests {
+    use dylint_testing::ui::Test;
+
+    #[test]
+    fn ui() {
+        Test::src_base(env!("CARGO_PKG_NAME"), "ui")
+            .rustc_flags(["--edition=2024", "-Z", "ui-testing"])
+            .run();
+    }
+}
@@ -0,0 +1,55 @@
+use std::fs::{self, File, OpenOptions, Permissions};
+use std::io::{self, Write};
+use std::os::unix::fs::{OpenOptionsExt, PermissionsExt};
+
+fn key_file(path: &str, key: &[u8]) -> io::Result<()> {
+    fs::write(path, key)?;
+    fs::set_permissions(path, Permissions::from_mode(0o600)) // Should trigger.
+}
+
+fn created(path: &str, key: &[u8]) -> io::Result<()> {
+    let mut file: File = File::create(path)?;
+    file.write_all(key)?;
+    file.set_permissions(Permissions::from_mode(0o400))?; // Should trigger.
+
+    let mut permissions: Permissions = fs::metadata(path)?.permissions();
+    permissions.set_mode(0o640);
+    fs::set_permissions(&path, permissions) // Should trigger.
+}
+
+fn opened(key: &[u8]) -> io::Result<()> {
+    let mut file: File = OpenOptions::new().write(true).create(true).open("key.pem")?;
+    file.write_all(key)?;
+    fs::set_permissions("key.pem", Permissions::from_mode(0o600)) // Should trigger.
+}
+
+fn created_with_mode(key: &[u8]) -> io::Result<()> {
+    let mut file: File = OpenOptions::new().write(true).create(true).mode(0o600).open("key.pem")?;
+    file.write_all(key)?;
+    fs::set_permissions("key.pem", Permissions::from_mode(0o600)) // Should not trigger.
+}
+
+fn other_path(path: &str, other: &str, data: &[u8]) -> io::Result<()> {
+    fs::write(path, data)?;
+    fs::set_permissions(other, Permissions::from_mode(0o600)) // Should not trigger.
+}
+
+fn widened(path: &str, data: &[u8]) -> io::Result<()> {
+    fs::write(path, data)?;
+    fs::set_permissions(path, Permissions::from_mode(0o755)) // Should not trigger.
+}
+
+fn existing(path: &str) -> io::Result<()> {
+    let _file: File = OpenOptions::new().write(true).open(path)?;
+    fs::set_permissions(path, Permissions::from_mode(0o600)) // Should not trigger.
+}
+
+fn main() {
+    let _ = key_file("key", b"");
+    let _ = created("key", b"");
+    let _ = opened(b"");
+    let _ = created_with_mode(b"");
+    let _ = other_path("key", "other", b"");
+    let _ = widened("key", b"");
+    let _ = existing("key");
+}
@@ -0,0 +1,459 @@
+#![feature(rustc_private)]
+
+extern crate rustc_ast;
+extern crate rustc_errors;
+extern crate rustc_hir;
+extern crate rustc_lint;
+extern crate rustc_middle;
+extern crate rustc_session;
+extern crate rustc_span;
+
+use rustc_ast::LitKind;
+use rustc_errors::Diag;
+use rustc_hir::{
+    Expr,
+    ExprKind,
+    MatchSource,
+    Node,
+    def::Res,
+    def_id::DefId,
+    intravisit::{Visitor, walk_expr},
+};
+use rustc_lint::{LateContext, LateLintPass, LintStore};
+use rustc_middle::ty::TyCtxt;
+use rustc_session::{Session, declare_lint, impl_lint_pass};
+use rustc_span::{Span, Symbol};
+use serde::Deserialize;
+
+declare_lint! {
+    pub SECURITY_UNRESTRICTED_REDIRECT,
+    Warn,
+    "Detects requests to caller-supplied URLs sent by clients following redirects."
+}
+
+/// Number of redirects followed by the default policy of reqwest.
+const DEFAULT_REDIRECTS: u128 = 10;
+
+/// Configuration of the `http_client` library, read from the
+/// `[http_client]` table of the workspace's `dylint.toml`.
+#[derive(Deserialize)]
+#[serde(default)]
+struct Config {
+    /// Paths of the functions creating an HTTP client with the default
+    /// redirect policy (e.g. `reqwest::Client::new`).
+    clients: Vec<String>,
+    /// Paths of the `build` methods of HTTP client builders (e.g.
+    /// `reqwest::ClientBuilder::build`), whose chain may set a redirect
+    /// policy with `redirect`.
+    builders: Vec<String>,
+    /// Paths of the functions sending a request through a default client
+    /// (e.g. `reqwest::get`).
+    request_functions: Vec<String>,
+    /// Names of the client methods creating a request from a URL (e.g.
+    /// `Client::get`).
+    request_methods: Vec<String>,
+    /// Largest number of redirects a client may follow. `Policy::limited(n)`
+    /// with `n` above it is reported.
+    max_redirects: u128,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Self {
+            clients: [
+                "reqwest::Client::new",
+                "reqwest::blocking::Client::new",
+            ]
+            .into_iter()
+            .map(String::from)
+            .collect(),
+            builders: [
+                "reqwest::ClientBuilder::build",
+                "reqwest::blocking::ClientBuilder::build",
+            ]
+            .into_iter()
+            .map(String::from)
+            .collect(),
+            request_functions: ["reqwest::get", "reqwest::blocking::get"]
+                .into_iter()
+                .map(String::from)
+                .collect(),
+            request_methods: [
+                "get", "post", "put", "patch", "delete", "head", "request",
+            ]
+            .into_iter()
+            .map(String::from)
+            .collect(),
+            max_redirects: 0,
+        }
+    }
+}
+
+/// Lint pass holding the clients, request functions and redirect limit
+/// loaded from the configuration.
+struct SecurityHttpClient {
+    clients: Vec<String>,
+    builders: Vec<String>,
+    request_functions: Vec<String>,
+    request_methods: Vec<String>,
+    max_redirects: u128,
+}
+
+impl_lint_pass!(SecurityHttpClient => [SECURITY_UNRESTRICTED_REDIRECT]);
+
+/// Request sent through a client following redirects.
+struct RedirectedRequest<'tcx> {
+    /// The name of the function or method creating the request.
+    name: Symbol,
+    /// The URL of the request.
+    url: &'tcx Expr<'tcx>,
+    /// The span creating the client, or `None` for a default client.
+    client: Option<Span>,
+    /// The number of redirects the client follows.
+    redirects: u128,
+}
+
+/// Visitor searching an expression, and the initializers of the local
+/// variables it reads, for a parameter of the enclosing function or
+/// closure, i.e. a value supplied by the caller.
+struct ParameterFinder<'a, 'tcx> {
+    context: &'a LateContext<'tcx>,
+    found: bool,
+}
+
+impl<'tcx> Visitor<'tcx> for ParameterFinder<'_, 'tcx> {
+    fn visit_expr(&mut self, expression: &'tcx Expr<'tcx>) {
+        if self.found {
+            return;
+        }
+
+        if let ExprKind::Path(path) = &expression.kind
+            && let Res::Local(id) =
+                self.context.qpath_res(path, expression.hir_id)
+        {
+            match self.context.tcx.parent_hir_node(id) {
+                Node::Param(_) => self.found = true,
+                Node::LetStmt(local) => {
+                    if let Some(init) = local.init {
+                        self.visit_expr(init);
+                    }
+                },
+                _ => {},
+            }
+            return;
+        }
+
+        walk_expr(self, expression);
+    }
+}
+
+/// Returns the function or method called by `expression`, if it is a call.
+///
+/// # Arguments
+/// * `context` (`&LateContext<'_>`) - The lint context, used to resolve the
+///   callee.
+/// * `expression` (`&Expr<'_>`) - The expression to inspect.
+fn callee(context: &LateContext<'_>, expression: &Expr<'_>) -> Option<DefId> {
+    match &expression.kind {
+        ExprKind::MethodCall(..) => {
+            context.typeck_results().type_dependent_def_id(expression.hir_id)
+        },
+        ExprKind::Call(callee, _) => {
+            let ExprKind::Path(path): &ExprKind<'_> = &callee.kind else {
+                return None;
+            };
+            context.qpath_res(path, callee.hir_id).opt_def_id()
+        },
+        _ => None,
+    }
+}
+
+/// Returns the expression a client is read from, through borrows, local
+/// variables and the handling of the `Result` of `build` (`?`, `unwrap` or
+/// `expect`).
+///
+/// # Arguments
+/// * `context` (`&LateContext<'tcx>`) - The lint context, used to resolve
+///   local variables.
+/// * `expression` (`&'tcx Expr<'tcx>`) - The expression to peel.
+fn origin<'tcx>(
+    context: &LateContext<'tcx>,
+    expression: &'tcx Expr<'tcx>,
+) -> &'tcx Expr<'tcx> {
+    match &expression.kind {
+        ExprKind::AddrOf(_, _, inner) => origin(context, inner),
+        ExprKind::MethodCall(segment, receiver, _, _)
+            if matches!(segment.ident.as_str(), "unwrap" | "expect") =>
+        {
+            origin(context, receiver)
+        },
+        // `result?` is lowered to `match Try::branch(result) { ... }`.
+        ExprKind::Match(scrutinee, _, MatchSource::TryDesugar(_)) => {
+            match &scrutinee.kind {
+                ExprKind::Call(_, [result]) => origin(context, result),
+                _ => expression,
+            }
+        },
+        ExprKind::Path(path) => {
+            if let Res::Local(id) = context.qpath_res(path, expression.hir_id)
+                && let Node::LetStmt(local) = context.tcx.parent_hir_node(id)
+                && let Some(init) = local.init
+            {
+                origin(context, init)
+            } else {
+                expression
+            }
+        },
+        _ => expression,
+    }
+}
+
+/// Returns the number of redirects followed with the policy `policy` passed
+/// to `redirect`: `0` for `Policy::none()`, `n` for `Policy::limited(n)`,
+/// and `None` for custom policies, which may validate the destinations.
+///
+/// # Arguments
+/// * `context` (`&LateContext<'_>`) - The lint context, used to resolve the
+///   policy constructor.
+/// * `policy` (`&Expr<'_>`) - The argument of `redirect`.
+fn policy_redirects(
+    context: &LateContext<'_>,
+    policy: &Expr<'_>,
+) -> Option<u128> {
+    let ExprKind::Call(_, arguments): &ExprKind<'_> = &policy.kind else {
+        return None;
+    };
+    match (context.tcx.item_name(callee(context, policy)?).as_str(), arguments)
+    {
+        ("none", []) => Some(0),
+        ("limited", [limit]) => match &limit.kind {
+            ExprKind::Lit(literal) => match literal.node {
+                LitKind::Int(value, _) => Some(value.get()),
+                _ => None,
+            },
+            _ => Some(u128::MAX),
+        },
+        _ => None,
+    }
+}
+
+impl SecurityHttpClient {
+    fn new(tcx: TyCtxt<'_>) -> Self {
+        let config: Config =
+            security_utils::config::load(tcx, env!("CARGO_PKG_NAME"));
+
+        Self {
+            clients: config.clients,
+            builders: config.builders,
+            request_functions: config.request_functions,
+            request_methods: config.request_methods,
+            max_redirects: config.max_redirects,
+        }
+    }
+
+    /// Returns the span creating the client `client` and the number of
+    /// redirects it follows, if it is created in the function by one of the
+    /// `clients` or `builders` with the default policy or a
+    /// `Policy::limited` limit above `max_redirects`. A limit that is not a
+    /// literal is assumed to be unbounded.
+    ///
+    /// # Arguments
+    /// * `context` (`&LateContext<'tcx>`) - The lint context, used to resolve
+    ///   the client.
+    /// * `client` (`&'tcx Expr<'tcx>`) - The receiver of the request method.
+    fn following_client<'tcx>(
+        &self,
+        context: &LateContext<'tcx>,
+        client: &'tcx Expr<'tcx>,
+    ) -> Option<(Span, u128)> {
+        let creation: &Expr<'_> = origin(context, client);
+        let path: String = security_utils::paths::function_path(
+            context.tcx,
+            callee(context, creation)?,
+        );
+        if self.clients.contains(&path) {
+            return (DEFAULT_REDIRECTS > self.max_redirects)
+                .then_some((creation.span, DEFAULT_REDIRECTS));
+        }
+        if !self.builders.contains(&path) {
+            return None;
+        }
+
+        let mut current: &Expr<'_> = creation;
+        while let ExprKind::MethodCall(segment, receiver, arguments, _) =
+            &current.kind
+        {
+            if segment.ident.as_str() == "redirect"
+                && let [policy] = arguments
+            {
+                let redirects: u128 = policy_redirects(context, policy)?;
+                return (redirects > self.max_redirects)
+                    .then_some((creation.span, redirects));
+            }
+            current = receiver;
+        }
+        (DEFAULT_REDIRECTS > self.max_redirects)
+            .then_some((creation.span, DEFAULT_REDIRECTS))
+    }
+
+    /// Returns the request sent by `expression`, if it is sent through a
+    /// client following redirects.
+    ///
+    /// # Arguments
+    /// * `context` (`&LateContext<'tcx>`) - The lint context, used to resolve
+    ///   the call.
+    /// * `expression` (`&'tcx Expr<'tcx>`) - The expression to inspect.
+    fn redirected_request<'tcx>(
+        &self,
+        context: &LateContext<'tcx>,
+        expression: &'tcx Expr<'tcx>,
+    ) -> Option<RedirectedRequest<'tcx>> {
+        let id: DefId = callee(context, expression)?;
+        let name: Symbol = context.tcx.item_name(id);
+        match &expression.kind {
+            ExprKind::Call(_, [url, ..]) => (self.request_functions.contains(
+                &security_utils::paths::function_path(context.tcx, id),
+            ) && DEFAULT_REDIRECTS
+                > self.max_redirects)
+                .then_some(RedirectedRequest {
+                    name,
+                    url,
+                    client: None,
+                    redirects: DEFAULT_REDIRECTS,
+                }),
+            ExprKind::MethodCall(segment, receiver, arguments, _) => {
+                if !self
+                    .request_methods
+                    .iter()
+                    .any(|method: &String| method == segment.ident.as_str())
+                {
+                    return None;
+                }
+                let url: &Expr<'_> = arguments.last()?;
+                let (span, redirects): (Span, u128) =
+                    self.following_client(context, receiver)?;
+                Some(RedirectedRequest {
+                    name,
+                    url,
+                    client: Some(span),
+                    redirects,
+                })
+            },
+            _ => None,
+        }
+    }
+}
+
+impl<'tcx> LateLintPass<'tcx> for SecurityHttpClient {
+    /// Detect requests whose URL derives from a parameter of the enclosing
+    /// function, sent through a client following redirects: a client created
+    /// in the function with the default redirect policy or a large
+    /// `Policy::limited`, or a top-level function such as `reqwest::get`.
+    ///
+    /// # Arguments
+    /// * `context` (`&LateContext<'tcx>`) - The lint context providing access
+    ///   to the compiler's internal state.
+    /// * `expression` (`&'tcx Expr<'tcx>`) - The expression being checked for
+    ///   requests.
+    fn check_expr(
+        &mut self,
+        context: &LateContext<'tcx>,
+        expression: &'tcx Expr<'tcx>,
+    ) {
+        if expression.span.from_expansion() {
+            return;
+        }
+
+        let Some(request): Option<RedirectedRequest<'tcx>> =
+            self.redirected_request(context, expression)
+        else {
+            return;
+        };
+
+        let mut finder: ParameterFinder<'_, 'tcx> =
+            ParameterFinder { context, found: false };
+        finder.visit_expr(request.url);
+        if !finder.found {
+            return;
+        }
+
+        security_utils::span_lint(
+            context,
+            SECURITY_UNRESTRICTED_REDIRECT,
+            expression.span,
+            |diagnostic: &mut Diag<'_, ()>| {
+                diagnostic.primary_message(format!(
+                    "Request to a caller-supplied URL by `{}` following \
+                     redirects detected.",
+                    request.name
+                ));
+                diagnostic.span_note(
+                    request.url.span,
+                    "the URL is supplied by the caller",
+                );
+                let limit: String = if request.redirects == u128::MAX {
+                    String::from("an unbounded number of redirects")
+                } else {
+                    format!("up to {} redirects", request.redirects)
+                };
+                match request.client {
+                    Some(span) => diagnostic.span_note(
+                        span,
+                        format!("the client follows {limit}"),
+                    ),
+                    None => diagnostic
+                        .note(format!("the default client follows {limit}")),
+                };
+                diagnostic.note(
+                    "a validated URL can redirect to an internal host, \
+                     bypassing the validation (SSRF)",
+                );
+                diagnostic.help(
+                    "build the client with `redirect(Policy::none())` and \
+                     validate the `Location` of each redirect before \
+                     following it, or check the destinations in a \
+                     `Policy::custom`",
+                );
+            },
+        );
+    }
+}
+
+/// Registers the `SECURITY_UNRESTRICTED_REDIRECT` lint and its lint pass with
+/// the Rust compiler. The clients, request functions and redirect limit are
+/// read from the `[http_client]` table of `dylint.toml` when the pass is
+/// created.
+///
+/// # Arguments
+/// * `session` (`&Session`) - The compiler session providing access to the
+///   compiler's internal state.
+/// * `lint_store` (`&mut LintStore`) - The lint store where the lint and its
+///   lint pass will be registered.
+#[unsafe(no_mangle)]
+pub fn register_lints(session: &Session, lint_store: &mut LintStore) {
+    dylint_linting::init_config(session);
+
+    security_utils::tool::register_lints(
+        lint_store,
+        &[SECURITY_UNRESTRICTED_REDIRECT],
+    );
+    lint_store.register_late_pass(|tcx: TyCtxt<'_>| {
+        Box::new(SecurityHttpClient::new(tcx))
+    });
+}
+
+dylint_linting::dylint_library!();
+
+/// UI tests for the `SECURITY_UNRESTRICTED_REDIRECT` lint. The `ui` directory
+/// uses a stand-in for `reqwest`.
+#[cfg(test)]
+mod tests {
+    use dylint_testing::ui::Test;
+
+    #[test]
+    fn ui() {
+        Test::src_base(env!("CARGO_PKG_NAME"), "ui")
+            .rustc_flags(["--edition=2024", "-Z", "ui-testing"])
+            .run();
+    }
+}
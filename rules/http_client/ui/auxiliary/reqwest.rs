@@ -0,0 +1,63 @@
+//! Minimal stand-in for the `reqwest` HTTP client.
+
+pub struct Client;
+
+pub struct ClientBuilder;
+
+pub struct RequestBuilder;
+
+impl Client {
+    pub fn new() -> Self {
+        Self
+    }
+
+    pub fn builder() -> ClientBuilder {
+        ClientBuilder
+    }
+
+    pub fn get(&self, _url: &str) -> RequestBuilder {
+        RequestBuilder
+    }
+
+    pub fn post(&self, _url: &str) -> RequestBuilder {
+        RequestBuilder
+    }
+}
+
+impl ClientBuilder {
+    pub fn user_agent(self, _value: &str) -> Self {
+        self
+    }
+
+    pub fn redirect(self, _policy: redirect::Policy) -> Self {
+        self
+    }
+
+    pub fn build(self) -> Result<Client, ()> {
+        Ok(Client)
+    }
+}
+
+pub mod redirect {
+    pub struct Policy;
+
+    pub struct Attempt;
+
+    impl Policy {
+        pub fn none() -> Self {
+            Policy
+        }
+
+        pub fn limited(_max: usize) -> Self {
+            Policy
+        }
+
+        pub fn custom<F: Fn(Attempt) -> bool>(_policy: F) -> Self {
+            Policy
+        }
+    }
+}
+
+pub fn get(_url: &str) -> RequestBuilder {
+    RequestBuilder
+}
@@ -0,0 +1,48 @@
+// aux-build:reqwest.rs
+
+extern crate reqwest;
+
+use reqwest::redirect::{Attempt, Policy};
+use reqwest::{Client, RequestBuilder};
+
+const HEALTH: &str = "https://internal.example/health";
+
+fn following(url: &str, path: &str, limit: usize) -> Result<(), ()> {
+    let client: Client = Client::new();
+    let _: RequestBuilder = client.get(url); // Should trigger.
+
+    let target: String = format!("https://api.example/{path}");
+    let _: RequestBuilder = reqwest::get(&target); // Should trigger.
+
+    let agent: Client = Client::builder().user_agent("demo").build()?;
+    let _: RequestBuilder = agent.post(url); // Should trigger.
+
+    let limited: Client =
+        Client::builder().redirect(Policy::limited(5)).build().unwrap();
+    let _: RequestBuilder = limited.get(url); // Should trigger.
+
+    let dynamic: Client =
+        Client::builder().redirect(Policy::limited(limit)).build()?;
+    let _: RequestBuilder = dynamic.get(url); // Should trigger.
+    Ok(())
+}
+
+fn restricted(url: &str, shared: &Client) -> Result<(), ()> {
+    let client: Client = Client::builder().redirect(Policy::none()).build()?;
+    let _: RequestBuilder = client.get(url); // Should not trigger.
+
+    let custom: Client = Client::builder()
+        .redirect(Policy::custom(|_: Attempt| true))
+        .build()?;
+    let _: RequestBuilder = custom.get(url); // Should not trigger.
+
+    let _: RequestBuilder = shared.get(url); // Should not trigger.
+    let _: RequestBuilder = Client::new().get(HEALTH); // Should not trigger.
+    let _: RequestBuilder = reqwest::get("https://example.com"); // Should not trigger.
+    Ok(())
+}
+
+fn main() {
+    let _: Result<(), ()> = following("https://example.com", "users", 3);
+    let _: Result<(), ()> = restricted("https://example.com", &Client::new());
+}
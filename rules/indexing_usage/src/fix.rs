@@ -0,0 +1,182 @@
+//! Suggestions rewriting indexing to the checked accessors.
+//!
+//! `values[index]` panics when the index is out of bounds, the key is missing
+//! from a map or a bound of a string slice is not on a character boundary,
+//! while `values.get(index)` returns `None`.
+//! [`suggest_get`] suggests the accessor matching the use of the indexed
+//! place: `get` for reads and shared borrows, `get_mut` for writes and mutable
+//! borrows, replacing the borrow too (`&mut values[index]` becomes
+//! `values.get_mut(index)`).
+
+use rustc_errors::{Applicability, Diag};
+use rustc_hir::{BorrowKind, Expr, ExprKind, LangItem, Mutability, Node};
+use rustc_lint::LateContext;
+use rustc_middle::ty::{
+    self,
+    Ty,
+    adjustment::{Adjust, Adjustment, AutoBorrow, AutoBorrowMutability},
+};
+use rustc_span::{Span, Symbol, source_map::SourceMap, sym};
+
+/// Collections whose `Index` implementation has a `get` and a `get_mut`
+/// counterpart taking the same index.
+const COLLECTIONS: [Symbol; 2] = [sym::Vec, sym::VecDeque];
+
+/// Maps whose `Index` implementation panics on a missing key.
+const MAPS: [Symbol; 2] = [sym::HashMap, sym::BTreeMap];
+
+/// Returns `true` if `ty`, through references, is one of the standard maps.
+///
+/// # Arguments
+/// * `context` (`&LateContext<'_>`) - The lint context.
+/// * `ty` (`Ty<'_>`) - The type of the indexed expression.
+pub fn is_map(context: &LateContext<'_>, ty: Ty<'_>) -> bool {
+    match ty.peel_refs().kind() {
+        ty::Adt(adt, _) => MAPS.iter().any(|map: &Symbol| {
+            context.tcx.is_diagnostic_item(*map, adt.did())
+        }),
+        _ => false,
+    }
+}
+
+/// Returns `true` if `ty`, through references, is `str` or `String`, whose
+/// slicing panics when a bound is not on a character boundary.
+///
+/// # Arguments
+/// * `context` (`&LateContext<'_>`) - The lint context.
+/// * `ty` (`Ty<'_>`) - The type of the indexed expression.
+pub fn is_string(context: &LateContext<'_>, ty: Ty<'_>) -> bool {
+    match ty.peel_refs().kind() {
+        ty::Str => true,
+        ty::Adt(adt, _) => {
+            context.tcx.is_lang_item(adt.did(), LangItem::String)
+        },
+        _ => false,
+    }
+}
+
+/// Returns `true` if values of type `ty`, through references, have `get`
+/// and `get_mut` methods taking the index of their `Index` implementation:
+/// arrays, slices, strings and the standard collections.
+///
+/// # Arguments
+/// * `context` (`&LateContext<'_>`) - The lint context.
+/// * `ty` (`Ty<'_>`) - The type of the indexed expression.
+fn has_accessors(context: &LateContext<'_>, ty: Ty<'_>) -> bool {
+    match ty.peel_refs().kind() {
+        ty::Array(..) | ty::Slice(_) | ty::Str => true,
+        ty::Adt(adt, _) => {
+            COLLECTIONS.iter().any(|collection: &Symbol| {
+                context.tcx.is_diagnostic_item(*collection, adt.did())
+            }) || is_string(context, ty)
+                || is_map(context, ty)
+        },
+        _ => false,
+    }
+}
+
+/// Returns `true` if the place `expression` is used mutably: borrowed
+/// mutably, assigned to, or the base of a place used mutably.
+///
+/// # Arguments
+/// * `context` (`&LateContext<'_>`) - The lint context.
+/// * `expression` (`&Expr<'_>`) - The indexing expression.
+fn is_mutable_use(context: &LateContext<'_>, expression: &Expr<'_>) -> bool {
+    let borrowed: bool =
+        context.typeck_results().expr_adjustments(expression).iter().any(
+            |adjustment: &Adjustment<'_>| {
+                matches!(
+                    adjustment.kind,
+                    Adjust::Borrow(AutoBorrow::Ref(
+                        AutoBorrowMutability::Mut { .. }
+                    ))
+                )
+            },
+        );
+    if borrowed {
+        return true;
+    }
+
+    match context.tcx.parent_hir_node(expression.hir_id) {
+        Node::Expr(parent) => match parent.kind {
+            ExprKind::AddrOf(_, Mutability::Mut, _) => true,
+            ExprKind::Assign(target, _, _)
+            | ExprKind::AssignOp(_, target, _) => {
+                target.hir_id == expression.hir_id
+            },
+            ExprKind::Index(base, _, _) | ExprKind::Field(base, _) => {
+                base.hir_id == expression.hir_id
+                    && is_mutable_use(context, parent)
+            },
+            _ => false,
+        },
+        _ => false,
+    }
+}
+
+/// Suggests replacing the indexing `expression` of `base` by `index` with
+/// the checked accessor, when the indexed type has one and the code is
+/// written in the source rather than expanded from a macro.
+///
+/// # Arguments
+/// * `context` (`&LateContext<'_>`) - The lint context.
+/// * `diagnostic` (`&mut Diag<'_, ()>`) - The diagnostic to extend.
+/// * `expression` (`&Expr<'_>`) - The indexing expression.
+/// * `base` (`&Expr<'_>`) - The indexed expression.
+/// * `index` (`&Expr<'_>`) - The index or range.
+pub fn suggest_get(
+    context: &LateContext<'_>,
+    diagnostic: &mut Diag<'_, ()>,
+    expression: &Expr<'_>,
+    base: &Expr<'_>,
+    index: &Expr<'_>,
+) {
+    let ty: Ty<'_> = context.typeck_results().expr_ty(base);
+    if expression.span.from_expansion() || !has_accessors(context, ty) {
+        return;
+    }
+    let source_map: &SourceMap = context.tcx.sess.source_map();
+    let Some((base_snippet, index_snippet)): Option<(String, String)> =
+        source_map
+            .span_to_snippet(base.span)
+            .ok()
+            .zip(source_map.span_to_snippet(index.span).ok())
+    else {
+        return;
+    };
+
+    // A borrow of the indexed place is replaced together with it, as the
+    // accessors already return a reference.
+    let (span, mutable): (Span, bool) =
+        match context.tcx.parent_hir_node(expression.hir_id) {
+            Node::Expr(Expr {
+                kind: ExprKind::AddrOf(BorrowKind::Ref, mutability, _),
+                span,
+                ..
+            }) if !span.from_expansion() => (*span, mutability.is_mut()),
+            _ => (expression.span, is_mutable_use(context, expression)),
+        };
+    let method: &str = if mutable { "get_mut" } else { "get" };
+    let (case, failure): (&str, &str) = if is_map(context, ty) {
+        ("a missing key", "the key is missing")
+    } else if is_string(context, ty) {
+        (
+            "an invalid range",
+            "a bound is out of bounds or not on a character boundary",
+        )
+    } else {
+        ("an out-of-bounds index", "the index is out of bounds")
+    };
+
+    diagnostic.span_suggestion(
+        span,
+        format!("use `{method}` to handle {case}"),
+        format!("{base_snippet}.{method}({index_snippet})"),
+        Applicability::MaybeIncorrect,
+    );
+    diagnostic.note(format!(
+        "`{method}` returns an `Option` holding a reference, `None` when \
+         {failure}: propagate it with `?` or `.ok_or(..)?`, or match it with \
+         `if let Some(..)` or `let Some(..) = .. else`"
+    ));
+}
@@ -1,5 +1,6 @@
 #![feature(rustc_private)]
 
+extern crate rustc_ast;
 extern crate rustc_errors;
 extern crate rustc_hir;
 extern crate rustc_lint;
@@ -7,21 +8,391 @@ extern crate rustc_middle;
 extern crate rustc_session;
 extern crate rustc_span;
 
-use rustc_errors::Diag;
-use rustc_hir::{Expr, ExprKind, Item, ItemKind};
-use rustc_lint::{LateContext, LateLintPass, LintContext, LintStore};
+mod fix;
+mod methods;
+mod propagation;
+mod ranges;
+
+use rustc_errors::{Applicability, Diag};
+use rustc_hir::{
+    BodyId,
+    ConstContext,
+    Expr,
+    ExprField,
+    ExprKind,
+    HirId,
+    Item,
+    ItemKind,
+    LangItem,
+    Path,
+    QPath,
+    def::{DefKind, Res},
+    def_id::{DefId, LocalDefId},
+};
+use rustc_lint::{LateContext, LateLintPass, LintStore};
 use rustc_middle::ty::TyCtxt;
-use rustc_session::{Session, declare_lint, declare_lint_pass};
+use rustc_session::{Session, declare_lint, impl_lint_pass};
+use rustc_span::{Symbol, sym};
+use security_utils::{dedup::Reported, known::KnownItems};
+use serde::Deserialize;
+
+use crate::{
+    methods::Method,
+    propagation::{Facts, Propagation},
+    ranges::Upper,
+};
 
 declare_lint! {
     pub SECURITY_INDEXING_USAGE,
     Deny,
     "Detects usage of indexing and slicing operations."
 }
-declare_lint_pass!(SecurityIndexingUsage => [SECURITY_INDEXING_USAGE]);
+
+declare_lint! {
+    pub SECURITY_LITERAL_INDEXING,
+    Deny,
+    "Detects indexing with integer literals not known to be within bounds."
+}
+
+declare_lint! {
+    pub SECURITY_MAP_INDEXING,
+    Deny,
+    "Detects indexing of maps, which panics on missing keys."
+}
+
+/// Configuration of the `indexing_usage` library, read from the
+/// `[indexing_usage]` table of the workspace's `dylint.toml`.
+#[derive(Deserialize)]
+#[serde(default)]
+struct Config {
+    /// Whether the indexes guarded by a preceding bounds check, such as
+    /// `if i < v.len()` or `if v.get(i).is_some()`, are not reported.
+    skip_guarded_indexes: bool,
+    /// Whether the indexing in the bodies of `Index` and `IndexMut`
+    /// implementations, which usually index the wrapped container, is not
+    /// reported. The implementations themselves still are.
+    skip_index_impls: bool,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Self { skip_guarded_indexes: true, skip_index_impls: false }
+    }
+}
+
+/// Lint pass for `SECURITY_INDEXING_USAGE`, `SECURITY_LITERAL_INDEXING` and
+/// `SECURITY_MAP_INDEXING`, holding the options loaded from the configuration,
+/// the facts propagated across the private functions of the crate, the `Index`
+/// traits resolved for the crate and the findings already reported for each
+/// macro call site.
+struct SecurityIndexingUsage {
+    skip_guarded_indexes: bool,
+    skip_index_impls: bool,
+    propagation: Propagation,
+    known: KnownItems,
+    reported: Reported,
+}
+
+impl_lint_pass!(
+    SecurityIndexingUsage => [
+        SECURITY_INDEXING_USAGE,
+        SECURITY_LITERAL_INDEXING,
+        SECURITY_MAP_INDEXING,
+    ]
+);
+
+impl SecurityIndexingUsage {
+    fn new(tcx: TyCtxt<'_>) -> Self {
+        let config: Config =
+            security_utils::config::load(tcx, env!("CARGO_PKG_NAME"));
+
+        Self {
+            skip_guarded_indexes: config.skip_guarded_indexes,
+            skip_index_impls: config.skip_index_impls,
+            propagation: Propagation::default(),
+            known: KnownItems::default(),
+            reported: Reported::default(),
+        }
+    }
+
+    /// Returns `true` if `hir_id` is in the body of a method of an `Index` or
+    /// `IndexMut` implementation.
+    ///
+    /// # Arguments
+    /// * `context` (`&LateContext<'_>`) - The lint context.
+    /// * `hir_id` (`HirId`) - The expression to check.
+    fn in_index_impl(&self, context: &LateContext<'_>, hir_id: HirId) -> bool {
+        let owner: LocalDefId = context.tcx.hir_get_parent_item(hir_id).def_id;
+        context
+            .tcx
+            .opt_local_parent(owner)
+            .filter(|parent: &LocalDefId| {
+                matches!(
+                    context.tcx.def_kind(*parent),
+                    DefKind::Impl { of_trait: true }
+                )
+            })
+            .and_then(|parent: LocalDefId| {
+                context.tcx.trait_id_of_impl(parent.to_def_id())
+            })
+            .is_some_and(|trait_id: DefId| self.known.is_index_trait(trait_id))
+    }
+
+    /// Returns what is known about `expression`: the number of elements of
+    /// the array it evaluates to and its value if it is a constant, either
+    /// from the expression itself or, for a parameter of a private function,
+    /// from the arguments of its call sites.
+    ///
+    /// # Arguments
+    /// * `context` (`&LateContext<'_>`) - The lint context.
+    /// * `expression` (`&Expr<'_>`) - The expression to evaluate.
+    fn facts(
+        &mut self,
+        context: &LateContext<'_>,
+        expression: &Expr<'_>,
+    ) -> Facts {
+        let mut current: &Expr<'_> = expression;
+        while let ExprKind::AddrOf(_, _, inner) = current.kind {
+            current = inner;
+        }
+
+        let parameter: Facts = match (current.kind, context.enclosing_body) {
+            (
+                ExprKind::Path(QPath::Resolved(
+                    None,
+                    Path { res: Res::Local(binding), .. },
+                )),
+                Some(body_id),
+            ) => {
+                // Parameters used inside closures belong to the enclosing
+                // function.
+                let owner: LocalDefId =
+                    context.tcx.hir_body_owner_def_id(body_id);
+                context
+                    .tcx
+                    .typeck_root_def_id(owner.to_def_id())
+                    .as_local()
+                    .map(|function: LocalDefId| {
+                        self.propagation.parameter_facts(
+                            context.tcx,
+                            function,
+                            *binding,
+                        )
+                    })
+                    .unwrap_or_default()
+            },
+            _ => Facts::default(),
+        };
+
+        Facts {
+            length: propagation::array_length(
+                context.tcx,
+                context.typeck_results().expr_ty(expression),
+            )
+            .or(parameter.length),
+            value: propagation::constant_value(
+                context.tcx,
+                context.typeck_results(),
+                expression,
+            )
+            .or(parameter.value),
+        }
+    }
+
+    /// Returns the value of the field `name` of a range expression, or
+    /// `default` if the range has no such bound.
+    ///
+    /// # Arguments
+    /// * `context` (`&LateContext<'_>`) - The lint context.
+    /// * `fields` (`&[ExprField<'_>]`) - The fields of the range expression.
+    /// * `name` (`Symbol`) - The bound, `start` or `end`.
+    /// * `default` (`u128`) - The value of a missing bound.
+    fn range_bound(
+        &mut self,
+        context: &LateContext<'_>,
+        fields: &[ExprField<'_>],
+        name: Symbol,
+        default: u128,
+    ) -> Option<u128> {
+        match fields
+            .iter()
+            .find(|field: &&ExprField<'_>| field.ident.name == name)
+        {
+            Some(field) => self.facts(context, field.expr).value,
+            None => Some(default),
+        }
+    }
+
+    /// Returns `true` if the call to the panicking slice method `method` on
+    /// `receiver` is known not to panic: a split index bounded by the length
+    /// of an array or of the receiver, a copy between arrays of the same
+    /// length, or a non-zero constant size.
+    ///
+    /// # Arguments
+    /// * `context` (`&LateContext<'tcx>`) - The lint context.
+    /// * `method` (`Method`) - How the method panics.
+    /// * `receiver` (`&'tcx Expr<'tcx>`) - The receiver of the call.
+    /// * `arguments` (`&'tcx [Expr<'tcx>]`) - The other arguments of the call.
+    fn safe_call<'tcx>(
+        &mut self,
+        context: &LateContext<'tcx>,
+        method: Method,
+        receiver: &'tcx Expr<'tcx>,
+        arguments: &'tcx [Expr<'tcx>],
+    ) -> bool {
+        let Some(argument): Option<&'tcx Expr<'tcx>> = arguments.first()
+        else {
+            return false;
+        };
+
+        match method {
+            Method::Split => {
+                if ranges::length_of(argument).is_some_and(
+                    |place: &Expr<'_>| ranges::same_place(place, receiver),
+                ) {
+                    return true;
+                }
+
+                let length: Option<u128> =
+                    self.facts(context, receiver).length;
+                let value: Option<u128> = self.facts(context, argument).value;
+                value.zip(length).is_some_and(
+                    |(value, length): (u128, u128)| value <= length,
+                ) || ranges::upper_bounds(
+                    context,
+                    argument,
+                    self.skip_guarded_indexes,
+                )
+                .into_iter()
+                .any(|upper: Upper<'tcx>| match upper {
+                    Upper::Length(place) => {
+                        ranges::same_place(place, receiver)
+                    },
+                    Upper::Constant(bound) => {
+                        length.is_some_and(|length: u128| bound <= length)
+                    },
+                })
+            },
+            Method::SplitStr => self.facts(context, argument).value == Some(0),
+            Method::Copy => {
+                let target: Option<u128> =
+                    self.facts(context, receiver).length;
+                target.is_some()
+                    && target == self.facts(context, argument).length
+            },
+            Method::Size => self
+                .facts(context, argument)
+                .value
+                .is_some_and(|size: u128| size > 0),
+        }
+    }
+
+    /// Returns `true` if indexing `base` with `index` is known to stay within
+    /// bounds: an index whose upper bound is the length of `base` or a
+    /// constant within it, a constant index smaller than the length of an
+    /// array, or a range of constants within it.
+    ///
+    /// # Arguments
+    /// * `context` (`&LateContext<'tcx>`) - The lint context.
+    /// * `base` (`&'tcx Expr<'tcx>`) - The indexed expression.
+    /// * `index` (`&'tcx Expr<'tcx>`) - The index or range.
+    fn in_bounds<'tcx>(
+        &mut self,
+        context: &LateContext<'tcx>,
+        base: &'tcx Expr<'tcx>,
+        index: &'tcx Expr<'tcx>,
+    ) -> bool {
+        let length: Option<u128> = self.facts(context, base).length;
+        let bounded: bool =
+            ranges::upper_bounds(context, index, self.skip_guarded_indexes)
+                .into_iter()
+                .any(|upper: Upper<'tcx>| match upper {
+                    Upper::Length(place) => ranges::same_place(place, base),
+                    Upper::Constant(bound) => {
+                        length.is_some_and(|length: u128| bound <= length)
+                    },
+                });
+        if bounded {
+            return true;
+        }
+
+        let Some(length): Option<u128> = length else {
+            return false;
+        };
+
+        let ExprKind::Struct(QPath::LangItem(item, _), fields, _): ExprKind<
+            '_,
+        > = index.kind
+        else {
+            return self
+                .facts(context, index)
+                .value
+                .is_some_and(|value: u128| value < length);
+        };
+
+        let (Some(start), Some(end)): (Option<u128>, Option<u128>) = (
+            self.range_bound(context, fields, sym::start, 0),
+            self.range_bound(context, fields, sym::end, length),
+        ) else {
+            return false;
+        };
+
+        matches!(
+            item,
+            LangItem::Range
+                | LangItem::RangeFrom
+                | LangItem::RangeTo
+                | LangItem::RangeFull
+        ) && start <= end
+            && end <= length
+    }
+}
+
+/// Returns `true` if the body being checked is evaluated at compile time:
+/// the initializer of a `const` or a `static`, an inline `const` block or
+/// an array length, where an out-of-bounds index is a compilation error
+/// rather than a panic. The bodies of `const fn`s can also run at runtime,
+/// so they are still checked.
+///
+/// # Arguments
+/// * `context` (`&LateContext<'_>`) - The lint context.
+fn in_const_initializer(context: &LateContext<'_>) -> bool {
+    context.enclosing_body.is_some_and(|body_id: BodyId| {
+        matches!(
+            context.tcx.hir_body_const_context(
+                context.tcx.hir_body_owner_def_id(body_id)
+            ),
+            Some(ConstContext::Const { .. } | ConstContext::Static(_))
+        )
+    })
+}
 
 impl<'tcx> LateLintPass<'tcx> for SecurityIndexingUsage {
-    /// Detect indexing and slicing operations.
+    /// Collects the call sites of the local functions of the crate and
+    /// resolves the `Index` traits.
+    ///
+    /// # Arguments
+    /// * `context` (`&LateContext<'tcx>`) - The lint context.
+    fn check_crate(&mut self, context: &LateContext<'tcx>) {
+        self.propagation = Propagation::collect(context.tcx);
+        self.known = KnownItems::new(context.tcx);
+    }
+
+    /// Detect indexing and slicing operations, suggesting the checked
+    /// accessors. Constant indexes and ranges known to stay within the bounds
+    /// of an array are not reported, including through the parameters of
+    /// private functions. Indexing of the standard maps, which panics on a
+    /// missing key rather than an out-of-bounds index, is reported by
+    /// `SECURITY_MAP_INDEXING`, indexing with a literal by
+    /// `SECURITY_LITERAL_INDEXING`, both by `SECURITY_INDEXING_USAGE` where
+    /// only the level of the latter is set, and slicing of strings, which
+    /// also panics off character boundaries, with a dedicated message. Calls
+    /// to the slice methods panicking as indexing does, such as
+    /// `split_at`, `copy_from_slice` and `chunks`, and to the unchecked
+    /// accessors `get_unchecked` and `get_unchecked_mut` are reported too,
+    /// except in the initializers evaluated at compile time. With
+    /// `skip_index_impls`, nothing is reported in the bodies of the `Index`
+    /// and `IndexMut` implementations.
     ///
     /// # Arguments
     /// * `context` (`&LateContext<'tcx>`) - The lint context, providing access
@@ -33,16 +404,153 @@ impl<'tcx> LateLintPass<'tcx> for SecurityIndexingUsage {
         context: &LateContext<'tcx>,
         expression: &'tcx Expr<'tcx>,
     ) {
-        if let ExprKind::Index(_, index_expr, _) = &expression.kind {
+        if in_const_initializer(context)
+            || (self.skip_index_impls
+                && self.in_index_impl(context, expression.hir_id))
+        {
+            return;
+        }
+
+        if let ExprKind::MethodCall(_, receiver, arguments, _) =
+            expression.kind
+            && let Some((method, name)) =
+                methods::slice_method(context, expression)
+            && !self.safe_call(context, method, receiver, arguments)
+        {
+            let (note, help): (String, String) = method.explanation(&name);
+            self.reported.span_lint(
+                context,
+                SECURITY_INDEXING_USAGE,
+                expression.span,
+                format!("Usage of panicking slice method `{name}` detected."),
+                |diagnostic: &mut Diag<'_, ()>| {
+                    diagnostic.note(note);
+                    diagnostic.help(help);
+                },
+            );
+        }
+
+        if let ExprKind::MethodCall(segment, receiver, [index], _) =
+            expression.kind
+            && let Some(checked) =
+                methods::unchecked_accessor(context, expression)
+            && (fix::is_string(
+                context,
+                context.typeck_results().expr_ty(receiver),
+            ) || !self.in_bounds(context, receiver, index))
+        {
+            let name: Symbol = segment.ident.name;
+            self.reported.span_lint(
+                context,
+                SECURITY_INDEXING_USAGE,
+                expression.span,
+                format!("Usage of unchecked indexing `{name}` detected."),
+                |diagnostic: &mut Diag<'_, ()>| {
+                    diagnostic.note(
+                        "an out-of-bounds index, or a string range off a \
+                         character boundary, is undefined behavior rather \
+                         than a panic: the access is not checked at all",
+                    );
+                    diagnostic.span_suggestion(
+                        segment.ident.span,
+                        format!(
+                            "use `{checked}` and handle the `None` returned \
+                             for an out-of-bounds index"
+                        ),
+                        checked,
+                        Applicability::MaybeIncorrect,
+                    );
+                },
+            );
+        }
+
+        if let ExprKind::Index(base, index_expr, _) = &expression.kind {
+            if fix::is_map(context, context.typeck_results().expr_ty(base)) {
+                self.reported.span_lint(
+                    context,
+                    security_utils::tool::split_lint(
+                        context,
+                        context.last_node_with_lint_attrs,
+                        SECURITY_MAP_INDEXING,
+                        SECURITY_INDEXING_USAGE,
+                    ),
+                    expression.span,
+                    String::from("Usage of map indexing detected."),
+                    |diagnostic: &mut Diag<'_, ()>| {
+                        fix::suggest_get(
+                            context, diagnostic, expression, base, index_expr,
+                        );
+                        diagnostic.help(
+                            "use the entry API, e.g. \
+                             `entry(key).or_insert(value)`, to insert a \
+                             missing key instead",
+                        );
+                    },
+                );
+                return;
+            }
+
+            // A range within the length of a string can still split one of
+            // its characters, so only `&s[..]` is known not to panic.
+            if fix::is_string(context, context.typeck_results().expr_ty(base))
+            {
+                if matches!(
+                    index_expr.kind,
+                    ExprKind::Struct(
+                        QPath::LangItem(LangItem::RangeFull, _),
+                        ..
+                    )
+                ) {
+                    return;
+                }
+
+                self.reported.span_lint(
+                    context,
+                    SECURITY_INDEXING_USAGE,
+                    expression.span,
+                    String::from(
+                        "Usage of string slicing operation detected.",
+                    ),
+                    |diagnostic: &mut Diag<'_, ()>| {
+                        diagnostic.note(
+                            "slicing a string panics when a bound is not on a \
+                             UTF-8 character boundary, even within its length",
+                        );
+                        fix::suggest_get(
+                            context, diagnostic, expression, base, index_expr,
+                        );
+                        diagnostic.help(
+                            "use `char_indices` to find the byte offsets at \
+                             which the characters start",
+                        );
+                    },
+                );
+                return;
+            }
+
+            if self.in_bounds(context, base, index_expr) {
+                return;
+            }
+
             match &index_expr.kind {
-                // Literal indexing: array[0].
+                // Literal indexing: vector[0], reported separately so that
+                // its level can differ from dynamic indexing, and under the
+                // former lint while only its level is set.
                 ExprKind::Lit(_) => {
-                    context.span_lint(
-                        SECURITY_INDEXING_USAGE,
+                    self.reported.span_lint(
+                        context,
+                        security_utils::tool::split_lint(
+                            context,
+                            context.last_node_with_lint_attrs,
+                            SECURITY_LITERAL_INDEXING,
+                            SECURITY_INDEXING_USAGE,
+                        ),
                         expression.span,
+                        String::from("Usage of indexing operation detected."),
                         |diagnostic: &mut Diag<'_, ()>| {
-                            diagnostic.primary_message(
-                                "Usage of indexing operation detected.",
+                            fix::suggest_get(
+                                context, diagnostic, expression, base,
+                                index_expr,
                             );
                         },
                     );
@@ -50,12 +558,15 @@ impl<'tcx> LateLintPass<'tcx> for SecurityIndexingUsage {
 
                 // Range slicing: array[1..], array[..], array[a..b].
                 ExprKind::Struct(_, _, _) => {
-                    context.span_lint(
+                    self.reported.span_lint(
+                        context,
                         SECURITY_INDEXING_USAGE,
                         expression.span,
+                        String::from("Usage of slicing operation detected."),
                         |diagnostic: &mut Diag<'_, ()>| {
-                            diagnostic.primary_message(
-                                "Usage of slicing operation detected.",
+                            fix::suggest_get(
+                                context, diagnostic, expression, base,
+                                index_expr,
                             );
                         },
                     );
@@ -63,12 +574,15 @@ impl<'tcx> LateLintPass<'tcx> for SecurityIndexingUsage {
 
                 // Any other dynamic indexing: array[i].
                 _ => {
-                    context.span_lint(
+                    self.reported.span_lint(
+                        context,
                         SECURITY_INDEXING_USAGE,
                         expression.span,
+                        String::from("Usage of indexing operation detected."),
                         |diagnostic: &mut Diag<'_, ()>| {
-                            diagnostic.primary_message(
-                                "Usage of indexing operation detected.",
+                            fix::suggest_get(
+                                context, diagnostic, expression, base,
+                                index_expr,
                             );
                         },
                     );
@@ -93,10 +607,10 @@ impl<'tcx> LateLintPass<'tcx> for SecurityIndexingUsage {
         if let ItemKind::Impl(implementation) = &item.kind
             && let Some(trait_ref) = implementation.of_trait
             && let Some(def_id) = trait_ref.trait_ref.path.res.opt_def_id()
-            && (context.tcx.lang_items().index_trait() == Some(def_id)
-                || context.tcx.lang_items().index_mut_trait() == Some(def_id))
+            && self.known.is_index_trait(def_id)
         {
-            context.span_lint(
+            security_utils::span_lint(
+                context,
                 SECURITY_INDEXING_USAGE,
                 item.span,
                 |diagnostic: &mut Diag<'_, ()>| {
@@ -120,9 +634,17 @@ impl<'tcx> LateLintPass<'tcx> for SecurityIndexingUsage {
 pub fn register_lints(session: &Session, lint_store: &mut LintStore) {
     dylint_linting::init_config(session);
 
-    lint_store.register_lints(&[SECURITY_INDEXING_USAGE]);
-    lint_store
-        .register_late_pass(|_: TyCtxt<'_>| Box::new(SecurityIndexingUsage));
+    security_utils::tool::register_lints(
+        lint_store,
+        &[
+            SECURITY_INDEXING_USAGE,
+            SECURITY_LITERAL_INDEXING,
+            SECURITY_MAP_INDEXING,
+        ],
+    );
+    lint_store.register_late_pass(|tcx: TyCtxt<'_>| {
+        Box::new(SecurityIndexingUsage::new(tcx))
+    });
 }
 
 dylint_linting::dylint_library!();
@@ -146,4 +668,20 @@ mod tests {
             .rustc_flags(["--edition=2024", "-Z", "ui-testing"])
             .run();
     }
+
+    #[test]
+    fn ui_unguarded() {
+        Test::src_base(env!("CARGO_PKG_NAME"), "ui_unguarded")
+            .rustc_flags(["--edition=2024", "-Z", "ui-testing"])
+            .dylint_toml("indexing_usage.skip_guarded_indexes = false")
+            .run();
+    }
+
+    #[test]
+    fn ui_index_impls() {
+        Test::src_base(env!("CARGO_PKG_NAME"), "ui_index_impls")
+            .rustc_flags(["--edition=2024", "-Z", "ui-testing"])
+            .dylint_toml("indexing_usage.skip_index_impls = true")
+            .run();
+    }
 }
@@ -0,0 +1,166 @@
+//! Slice methods panicking on their arguments as indexing does.
+//!
+//! `bytes.split_at(mid)` panics when `mid` is past the end of `bytes`,
+//! `target.copy_from_slice(source)` when the two slices differ in length,
+//! and `bytes.chunks(size)` when `size` is zero. [`slice_method`] resolves a
+//! method call to one of these methods of `[T]`, or of `str` for the
+//! splitting methods, which also panic off character boundaries.
+//!
+//! `bytes.get_unchecked(index)` does not panic at all: an out-of-bounds
+//! index is undefined behavior. [`unchecked_accessor`] resolves a method call
+//! to these accessors of `[T]` and `str`, often used to silence the indexing
+//! lint.
+
+use rustc_hir::{Expr, def_id::DefId};
+use rustc_lint::LateContext;
+use rustc_middle::ty::{self, Ty};
+
+/// Methods splitting their receiver at an index.
+const SPLITTING: [&str; 2] = ["split_at", "split_at_mut"];
+
+/// Methods copying a slice of the same length into their receiver.
+const COPYING: [&str; 2] = ["copy_from_slice", "clone_from_slice"];
+
+/// Methods iterating over subslices of a size.
+const SIZED: [&str; 9] = [
+    "chunks",
+    "chunks_mut",
+    "chunks_exact",
+    "chunks_exact_mut",
+    "rchunks",
+    "rchunks_mut",
+    "rchunks_exact",
+    "rchunks_exact_mut",
+    "windows",
+];
+
+/// Accessors skipping the bounds check, with their checked counterpart.
+const UNCHECKED: [(&str, &str); 2] =
+    [("get_unchecked", "get"), ("get_unchecked_mut", "get_mut")];
+
+/// How a slice method panics.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Method {
+    /// The index argument is greater than the length of the receiver.
+    Split,
+    /// The index argument is greater than the length of the receiver, or
+    /// not on one of its character boundaries.
+    SplitStr,
+    /// The argument and the receiver have different lengths.
+    Copy,
+    /// The size argument is zero.
+    Size,
+}
+
+impl Method {
+    /// Returns the explanation of the panic of the method `name`, and how
+    /// to avoid it.
+    ///
+    /// # Arguments
+    /// * `name` (`&str`) - The name of the method.
+    pub fn explanation(self, name: &str) -> (String, String) {
+        match self {
+            Self::Split => (
+                format!(
+                    "`{name}` panics when the index is greater than the \
+                     length of the slice"
+                ),
+                format!("use `{name}_checked`, which returns `None` instead"),
+            ),
+            Self::SplitStr => (
+                format!(
+                    "`{name}` panics when the index is greater than the \
+                     length of the string or not on a UTF-8 character \
+                     boundary"
+                ),
+                format!("use `{name}_checked`, which returns `None` instead"),
+            ),
+            Self::Copy => (
+                format!(
+                    "`{name}` panics when the two slices have different \
+                     lengths"
+                ),
+                String::from(
+                    "check the lengths beforehand, or slice both sides to \
+                     the same length",
+                ),
+            ),
+            Self::Size => (
+                format!("`{name}` panics when the size is zero"),
+                String::from(
+                    "use a non-zero constant size, or check the size \
+                     beforehand",
+                ),
+            ),
+        }
+    }
+}
+
+/// Returns the inherent method of `[T]` or `str` called by `expression`,
+/// with the type of its receiver and its name.
+///
+/// # Arguments
+/// * `context` (`&LateContext<'tcx>`) - The lint context.
+/// * `expression` (`&Expr<'_>`) - The method call.
+fn inherent_method<'tcx>(
+    context: &LateContext<'tcx>,
+    expression: &Expr<'_>,
+) -> Option<(Ty<'tcx>, String)> {
+    let method: DefId =
+        context.typeck_results().type_dependent_def_id(expression.hir_id)?;
+    let implementation: DefId = context.tcx.impl_of_assoc(method)?;
+    if context.tcx.trait_id_of_impl(implementation).is_some() {
+        return None;
+    }
+
+    Some((
+        context.tcx.type_of(implementation).instantiate_identity(),
+        context.tcx.item_name(method).to_string(),
+    ))
+}
+
+/// Returns the name of the checked counterpart of the unchecked accessor of
+/// `[T]` or `str` called by `expression`, if it is a call to one.
+///
+/// # Arguments
+/// * `context` (`&LateContext<'_>`) - The lint context.
+/// * `expression` (`&Expr<'_>`) - The method call.
+pub fn unchecked_accessor(
+    context: &LateContext<'_>,
+    expression: &Expr<'_>,
+) -> Option<&'static str> {
+    let (receiver, name): (Ty<'_>, String) =
+        inherent_method(context, expression)?;
+    if !matches!(receiver.kind(), ty::Slice(_) | ty::Str) {
+        return None;
+    }
+
+    UNCHECKED
+        .iter()
+        .find(|(unchecked, _): &&(&str, &str)| *unchecked == name)
+        .map(|(_, checked): &(&str, &str)| *checked)
+}
+
+/// Returns the panicking method of slices called by `expression`, with its
+/// name, if `expression` is a call to one. The splitting methods of `str`
+/// are included.
+///
+/// # Arguments
+/// * `context` (`&LateContext<'_>`) - The lint context.
+/// * `expression` (`&Expr<'_>`) - The method call.
+pub fn slice_method(
+    context: &LateContext<'_>,
+    expression: &Expr<'_>,
+) -> Option<(Method, String)> {
+    let (receiver, name): (Ty<'_>, String) =
+        inherent_method(context, expression)?;
+    let kind: Method = match receiver.kind() {
+        ty::Slice(_) if SPLITTING.contains(&name.as_str()) => Method::Split,
+        ty::Str if SPLITTING.contains(&name.as_str()) => Method::SplitStr,
+        ty::Slice(_) if COPYING.contains(&name.as_str()) => Method::Copy,
+        ty::Slice(_) if SIZED.contains(&name.as_str()) => Method::Size,
+        _ => return None,
+    };
+
+    Some((kind, name))
+}
@@ -0,0 +1,448 @@
+//! Propagation of constant array lengths and index values across the private
+//! functions of a crate.
+//!
+//! A parameter of a function that is not exported and whose address is never
+//! taken only receives the arguments of the calls written in the crate. For
+//! each such parameter, [`Propagation`] joins the arguments of every call
+//! site: the smallest length of the arrays passed to it, and the largest
+//! constant passed to it. Arguments that are themselves parameters of a
+//! private function are followed to the call sites of that function.
+//!
+//! ```rust
+//! fn first(bytes: &[u8]) -> u8 {
+//!     bytes[0] // `bytes` always has at least 4 elements.
+//! }
+//!
+//! fn checksum(header: &[u8; 4]) -> u8 {
+//!     first(header)
+//! }
+//! ```
+
+use std::collections::{HashMap, HashSet};
+
+use rustc_ast::LitKind;
+use rustc_hir::{
+    BinOpKind,
+    BindingMode,
+    Body,
+    Expr,
+    ExprKind,
+    HirId,
+    Param,
+    PatKind,
+    QPath,
+    def::{DefKind, Res},
+    def_id::{DefId, LocalDefId},
+    intravisit::{Visitor, walk_expr},
+};
+use rustc_middle::ty::{self, Ty, TyCtxt, TypeckResults};
+
+/// What is known about the values received by a parameter.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct Facts {
+    /// The smallest number of elements of the arrays passed to the
+    /// parameter.
+    pub length: Option<u128>,
+    /// The largest constant passed to the parameter.
+    pub value: Option<u128>,
+}
+
+impl Facts {
+    /// Returns the facts holding for both `self` and `other`.
+    ///
+    /// # Arguments
+    /// * `other` (`Facts`) - The facts of another call site.
+    fn join(self, other: Facts) -> Facts {
+        Facts {
+            length: self
+                .length
+                .zip(other.length)
+                .map(|(first, second): (u128, u128)| first.min(second)),
+            value: self
+                .value
+                .zip(other.value)
+                .map(|(first, second): (u128, u128)| first.max(second)),
+        }
+    }
+}
+
+/// A call to a local function.
+#[derive(Debug)]
+struct CallSite {
+    /// The function containing the call.
+    caller: LocalDefId,
+    /// The arguments of the call, the receiver of a method call first.
+    arguments: Vec<HirId>,
+}
+
+/// Visitor collecting the calls to local functions of a body, and the local
+/// functions used as values.
+struct CallCollector<'a, 'tcx> {
+    tcx: TyCtxt<'tcx>,
+    typeck: &'tcx TypeckResults<'tcx>,
+    caller: LocalDefId,
+    sites: &'a mut HashMap<LocalDefId, Vec<CallSite>>,
+    escaped: &'a mut HashSet<LocalDefId>,
+}
+
+impl CallCollector<'_, '_> {
+    /// Returns `def_id` if it is a local function or associated function.
+    ///
+    /// # Arguments
+    /// * `def_id` (`DefId`) - The resolved item.
+    fn local_function(&self, def_id: DefId) -> Option<LocalDefId> {
+        matches!(self.tcx.def_kind(def_id), DefKind::Fn | DefKind::AssocFn)
+            .then(|| def_id.as_local())
+            .flatten()
+    }
+
+    /// Records a call to `def_id` with `arguments`.
+    ///
+    /// # Arguments
+    /// * `def_id` (`DefId`) - The called function.
+    /// * `arguments` (`Vec<HirId>`) - The arguments of the call.
+    fn record(&mut self, def_id: DefId, arguments: Vec<HirId>) {
+        if let Some(callee) = self.local_function(def_id) {
+            self.sites
+                .entry(callee)
+                .or_default()
+                .push(CallSite { caller: self.caller, arguments });
+        }
+    }
+}
+
+impl<'tcx> Visitor<'tcx> for CallCollector<'_, 'tcx> {
+    fn visit_expr(&mut self, expression: &'tcx Expr<'tcx>) {
+        match expression.kind {
+            ExprKind::Call(
+                callee @ Expr { kind: ExprKind::Path(qpath), .. },
+                arguments,
+            ) => {
+                if let Some(def_id) =
+                    self.typeck.qpath_res(qpath, callee.hir_id).opt_def_id()
+                {
+                    self.record(
+                        def_id,
+                        arguments
+                            .iter()
+                            .map(|argument: &Expr<'_>| argument.hir_id)
+                            .collect(),
+                    );
+                }
+                for argument in arguments {
+                    self.visit_expr(argument);
+                }
+                return;
+            },
+            ExprKind::MethodCall(_, receiver, arguments, _) => {
+                if let Some(def_id) =
+                    self.typeck.type_dependent_def_id(expression.hir_id)
+                {
+                    self.record(
+                        def_id,
+                        [receiver]
+                            .into_iter()
+                            .chain(arguments)
+                            .map(|argument: &Expr<'_>| argument.hir_id)
+                            .collect(),
+                    );
+                }
+            },
+            ExprKind::Path(ref qpath) => {
+                if let Some(def_id) = self
+                    .typeck
+                    .qpath_res(qpath, expression.hir_id)
+                    .opt_def_id()
+                    .and_then(|def_id: DefId| self.local_function(def_id))
+                {
+                    self.escaped.insert(def_id);
+                }
+            },
+            _ => {},
+        }
+
+        walk_expr(self, expression);
+    }
+}
+
+/// Returns the position of the parameter of `body` bound to `binding`, if
+/// it is an immutable binding.
+///
+/// # Arguments
+/// * `body` (`&Body<'_>`) - The body of the function.
+/// * `binding` (`HirId`) - The binding of the local.
+fn parameter_position(body: &Body<'_>, binding: HirId) -> Option<usize> {
+    body.params.iter().position(|param: &Param<'_>| {
+        matches!(
+            param.pat.kind,
+            PatKind::Binding(BindingMode::NONE, id, _, None) if id == binding
+        )
+    })
+}
+
+/// Returns the number of elements of `ty` if it is an array, or a reference
+/// to one, with a known length.
+///
+/// # Arguments
+/// * `tcx` (`TyCtxt<'tcx>`) - The type context used to evaluate the length.
+/// * `ty` (`Ty<'tcx>`) - The type to check.
+pub fn array_length<'tcx>(tcx: TyCtxt<'tcx>, ty: Ty<'tcx>) -> Option<u128> {
+    match ty.peel_refs().kind() {
+        ty::Array(_, length) => {
+            length.try_to_target_usize(tcx).map(u128::from)
+        },
+        _ => None,
+    }
+}
+
+/// Returns `value` if it is representable by the integer type `ty`.
+///
+/// # Arguments
+/// * `tcx` (`TyCtxt<'tcx>`) - The type context used to compute the size of
+///   `ty`.
+/// * `ty` (`Ty<'tcx>`) - The type of the value.
+/// * `value` (`u128`) - The value, as a non-negative number.
+fn representable<'tcx>(
+    tcx: TyCtxt<'tcx>,
+    ty: Ty<'tcx>,
+    value: u128,
+) -> Option<u128> {
+    let bits: u64 = match ty.kind() {
+        ty::Uint(_) => ty.primitive_size(tcx).bits(),
+        // Only the non-negative half of a signed type.
+        ty::Int(_) => ty.primitive_size(tcx).bits().checked_sub(1)?,
+        _ => return None,
+    };
+
+    value
+        .checked_shr(u32::try_from(bits).ok()?)
+        .is_none_or(|high: u128| high == 0)
+        .then_some(value)
+}
+
+/// Returns the value of `expression` if it can be evaluated at compile time:
+/// an integer literal, a path to an integer constant or associated constant,
+/// an arithmetic operation on unsigned values, or a cast of a non-negative
+/// value to an integer type wide enough to hold it.
+///
+/// # Arguments
+/// * `tcx` (`TyCtxt<'tcx>`) - The type context used to evaluate constants.
+/// * `typeck` (`&TypeckResults<'tcx>`) - The type-checking results of the body
+///   containing `expression`.
+/// * `expression` (`&Expr<'_>`) - The expression to evaluate.
+pub fn constant_value<'tcx>(
+    tcx: TyCtxt<'tcx>,
+    typeck: &TypeckResults<'tcx>,
+    expression: &Expr<'_>,
+) -> Option<u128> {
+    let ty: Ty<'tcx> = typeck.expr_ty(expression);
+    match &expression.kind {
+        ExprKind::Lit(literal) => match literal.node {
+            LitKind::Int(value, _) => Some(value.get()),
+            _ => None,
+        },
+        ExprKind::Path(qpath) => {
+            let Res::Def(DefKind::Const | DefKind::AssocConst, def_id): Res =
+                typeck.qpath_res(qpath, expression.hir_id)
+            else {
+                return None;
+            };
+            // The value of a trait constant depends on the implementation.
+            if tcx.trait_of_assoc(def_id).is_some() || !ty.is_integral() {
+                return None;
+            }
+
+            let value: u128 = tcx
+                .const_eval_poly(def_id)
+                .ok()?
+                .try_to_scalar_int()?
+                .try_to_bits(ty.primitive_size(tcx))
+                .ok()?;
+            representable(tcx, ty, value)
+        },
+        ExprKind::Binary(operator, left, right)
+            if matches!(ty.kind(), ty::Uint(_)) =>
+        {
+            let left: u128 = constant_value(tcx, typeck, left)?;
+            let right: u128 = constant_value(tcx, typeck, right)?;
+            let value: u128 = match operator.node {
+                BinOpKind::Add => left.checked_add(right)?,
+                BinOpKind::Sub => left.checked_sub(right)?,
+                BinOpKind::Mul => left.checked_mul(right)?,
+                BinOpKind::Div => left.checked_div(right)?,
+                BinOpKind::Rem => left.checked_rem(right)?,
+                BinOpKind::Shl => {
+                    left.checked_shl(u32::try_from(right).ok()?)?
+                },
+                BinOpKind::Shr => {
+                    left.checked_shr(u32::try_from(right).ok()?)?
+                },
+                BinOpKind::BitAnd => left & right,
+                BinOpKind::BitOr => left | right,
+                BinOpKind::BitXor => left ^ right,
+                _ => return None,
+            };
+            representable(tcx, ty, value)
+        },
+        ExprKind::Cast(operand, _) => {
+            let value: u128 = constant_value(tcx, typeck, operand)?;
+            representable(tcx, typeck.expr_ty(operand), value)?;
+            representable(tcx, ty, value)
+        },
+        _ => None,
+    }
+}
+
+/// The call sites of the local functions of a crate, and the facts derived
+/// from them for the parameters of its private functions.
+#[derive(Debug, Default)]
+pub struct Propagation {
+    sites: HashMap<LocalDefId, Vec<CallSite>>,
+    escaped: HashSet<LocalDefId>,
+    facts: HashMap<(LocalDefId, usize), Facts>,
+}
+
+impl Propagation {
+    /// Collects the call sites of the local functions of the crate.
+    ///
+    /// # Arguments
+    /// * `tcx` (`TyCtxt<'_>`) - The type context of the crate.
+    pub fn collect(tcx: TyCtxt<'_>) -> Self {
+        let mut propagation: Propagation = Propagation::default();
+
+        for caller in tcx.hir_body_owners() {
+            let body: &Body<'_> = tcx.hir_body_owned_by(caller);
+            let mut collector: CallCollector<'_, '_> = CallCollector {
+                tcx,
+                typeck: tcx.typeck(caller),
+                caller,
+                sites: &mut propagation.sites,
+                escaped: &mut propagation.escaped,
+            };
+            collector.visit_body(body);
+        }
+
+        propagation
+    }
+
+    /// Returns what is known about the values received by the local
+    /// `binding`, if it is an immutable parameter of the private function
+    /// `function`.
+    ///
+    /// # Arguments
+    /// * `tcx` (`TyCtxt<'_>`) - The type context of the crate.
+    /// * `function` (`LocalDefId`) - The function owning the local.
+    /// * `binding` (`HirId`) - The binding of the local.
+    pub fn parameter_facts(
+        &mut self,
+        tcx: TyCtxt<'_>,
+        function: LocalDefId,
+        binding: HirId,
+    ) -> Facts {
+        if !matches!(tcx.def_kind(function), DefKind::Fn | DefKind::AssocFn) {
+            return Facts::default();
+        }
+
+        tcx.hir_maybe_body_owned_by(function)
+            .and_then(|body: &Body<'_>| parameter_position(body, binding))
+            .map(|position: usize| self.facts(tcx, function, position))
+            .unwrap_or_default()
+    }
+
+    /// Returns what is known about the `position`-th parameter of
+    /// `function`, joined over its call sites. A function that is part of
+    /// the public interface of the crate, used as a value or never called
+    /// has no known facts.
+    ///
+    /// # Arguments
+    /// * `tcx` (`TyCtxt<'_>`) - The type context of the crate.
+    /// * `function` (`LocalDefId`) - The function.
+    /// * `position` (`usize`) - The position of the parameter.
+    fn facts(
+        &mut self,
+        tcx: TyCtxt<'_>,
+        function: LocalDefId,
+        position: usize,
+    ) -> Facts {
+        if let Some(facts) = self.facts.get(&(function, position)) {
+            return *facts;
+        }
+        // Recursive calls see no facts until the result is known.
+        self.facts.insert((function, position), Facts::default());
+
+        if tcx.effective_visibilities(()).is_exported(function)
+            || self.escaped.contains(&function)
+        {
+            return Facts::default();
+        }
+
+        let arguments: Vec<(LocalDefId, HirId)> = self
+            .sites
+            .get(&function)
+            .into_iter()
+            .flatten()
+            .filter_map(|site: &CallSite| {
+                Some((site.caller, *site.arguments.get(position)?))
+            })
+            .collect();
+        let complete: bool =
+            self.sites.get(&function).is_some_and(|sites: &Vec<CallSite>| {
+                sites.len() == arguments.len()
+            });
+        if arguments.is_empty() || !complete {
+            return Facts::default();
+        }
+
+        let facts: Facts = arguments
+            .into_iter()
+            .map(|(caller, argument): (LocalDefId, HirId)| {
+                self.argument_facts(tcx, caller, argument)
+            })
+            .reduce(Facts::join)
+            .unwrap_or_default();
+        self.facts.insert((function, position), facts);
+
+        facts
+    }
+
+    /// Returns what is known about the argument `argument` of a call inside
+    /// `caller`.
+    ///
+    /// # Arguments
+    /// * `tcx` (`TyCtxt<'_>`) - The type context of the crate.
+    /// * `caller` (`LocalDefId`) - The function containing the call.
+    /// * `argument` (`HirId`) - The argument.
+    fn argument_facts(
+        &mut self,
+        tcx: TyCtxt<'_>,
+        caller: LocalDefId,
+        argument: HirId,
+    ) -> Facts {
+        let rustc_hir::Node::Expr(expression): rustc_hir::Node<'_> =
+            tcx.hir_node(argument)
+        else {
+            return Facts::default();
+        };
+        let typeck: &TypeckResults<'_> = tcx.typeck(caller);
+
+        let mut current: &Expr<'_> = expression;
+        while let ExprKind::AddrOf(_, _, inner) = current.kind {
+            current = inner;
+        }
+        let forwarded: Facts = match current.kind {
+            ExprKind::Path(QPath::Resolved(None, path)) => match path.res {
+                Res::Local(binding) => {
+                    self.parameter_facts(tcx, caller, binding)
+                },
+                _ => Facts::default(),
+            },
+            _ => Facts::default(),
+        };
+
+        Facts {
+            length: array_length(tcx, typeck.expr_ty(expression))
+                .or(forwarded.length),
+            value: constant_value(tcx, typeck, expression).or(forwarded.value),
+        }
+    }
+}
@@ -0,0 +1,556 @@
+//! A lightweight range analysis of index expressions: upper bounds of an
+//! index established by its shape (`i % v.len()`, `i.min(v.len() - 1)`), by
+//! the `for` loop binding it (`for i in 0..v.len()`,
+//! `for (i, _) in v.iter().enumerate()`), or by the checks guarding it
+//! (`if i < v.len() { .. }`, `if i >= v.len() { return; }`, `assert!`,
+//! `if v.get(i).is_some() { .. }`).
+//!
+//! Only immutable bindings are tracked, so the bounds hold wherever the
+//! binding is in scope.
+
+use rustc_ast::LitKind;
+use rustc_hir::{
+    BinOpKind,
+    BindingMode,
+    Block,
+    Expr,
+    ExprField,
+    ExprKind,
+    HirId,
+    LangItem,
+    MatchSource,
+    Node,
+    Pat,
+    PatKind,
+    QPath,
+    Stmt,
+    StmtKind,
+    UnOp,
+    def::{DefKind, Res},
+};
+use rustc_lint::LateContext;
+use rustc_span::sym;
+
+/// Adapters whose items are a subset of the items of their receiver, so
+/// that `enumerate` after them counts less items than the receiver has.
+const NON_INCREASING: [&str; 11] = [
+    "iter",
+    "iter_mut",
+    "into_iter",
+    "skip",
+    "take",
+    "filter",
+    "rev",
+    "step_by",
+    "copied",
+    "cloned",
+    "map",
+];
+
+/// An exclusive upper bound of an index.
+#[derive(Debug, Clone, Copy)]
+pub enum Upper<'tcx> {
+    /// The index is smaller than the length of the expression, i.e. the
+    /// receiver of a `len()` call.
+    Length(&'tcx Expr<'tcx>),
+    /// The index is smaller than the constant.
+    Constant(u128),
+}
+
+/// Returns `expression` without references, dereferences and the
+/// temporaries introduced by desugaring.
+///
+/// # Arguments
+/// * `expression` (`&'tcx Expr<'tcx>`) - The expression to peel.
+fn peel<'tcx>(expression: &'tcx Expr<'tcx>) -> &'tcx Expr<'tcx> {
+    let mut current: &Expr<'_> = expression;
+    loop {
+        match current.kind {
+            ExprKind::AddrOf(_, _, inner)
+            | ExprKind::Unary(UnOp::Deref, inner)
+            | ExprKind::DropTemps(inner) => current = inner,
+            _ => return current,
+        }
+    }
+}
+
+/// Returns `true` if `first` and `second` denote the same place: the same
+/// local or item, followed by the same fields.
+///
+/// # Arguments
+/// * `first` (`&'tcx Expr<'tcx>`) - The first expression.
+/// * `second` (`&'tcx Expr<'tcx>`) - The second expression.
+pub fn same_place<'tcx>(
+    first: &'tcx Expr<'tcx>,
+    second: &'tcx Expr<'tcx>,
+) -> bool {
+    let mut first: &Expr<'_> = first;
+    let mut second: &Expr<'_> = second;
+    loop {
+        first = peel(first);
+        second = peel(second);
+        match (first.kind, second.kind) {
+            (
+                ExprKind::Field(first_base, first_field),
+                ExprKind::Field(second_base, second_field),
+            ) if first_field.name == second_field.name => {
+                first = first_base;
+                second = second_base;
+            },
+            (
+                ExprKind::Path(QPath::Resolved(None, first_path)),
+                ExprKind::Path(QPath::Resolved(None, second_path)),
+            ) => return first_path.res == second_path.res,
+            _ => return false,
+        }
+    }
+}
+
+/// Returns the value of `expression` if it is an integer literal.
+///
+/// # Arguments
+/// * `expression` (`&Expr<'_>`) - The expression to evaluate.
+fn literal(expression: &Expr<'_>) -> Option<u128> {
+    match &peel(expression).kind {
+        ExprKind::Lit(literal) => match literal.node {
+            LitKind::Int(value, _) => Some(value.get()),
+            _ => None,
+        },
+        _ => None,
+    }
+}
+
+/// Returns the receiver of `expression` if it is a `len()` call.
+///
+/// # Arguments
+/// * `expression` (`&'tcx Expr<'tcx>`) - The expression to check.
+pub fn length_of<'tcx>(
+    expression: &'tcx Expr<'tcx>,
+) -> Option<&'tcx Expr<'tcx>> {
+    match peel(expression).kind {
+        ExprKind::MethodCall(segment, receiver, [], _)
+            if segment.ident.name == sym::len =>
+        {
+            Some(receiver)
+        },
+        _ => None,
+    }
+}
+
+/// Returns the bound `expression` is an exclusive upper bound of, e.g.
+/// `Length(v)` for `v.len()`.
+///
+/// # Arguments
+/// * `expression` (`&'tcx Expr<'tcx>`) - The bound.
+fn exclusive<'tcx>(expression: &'tcx Expr<'tcx>) -> Option<Upper<'tcx>> {
+    length_of(expression)
+        .map(Upper::Length)
+        .or_else(|| literal(expression).map(Upper::Constant))
+}
+
+/// Returns the bound `expression` is an inclusive upper bound of, e.g.
+/// `Length(v)` for `v.len() - 1`.
+///
+/// # Arguments
+/// * `expression` (`&'tcx Expr<'tcx>`) - The bound.
+fn inclusive<'tcx>(expression: &'tcx Expr<'tcx>) -> Option<Upper<'tcx>> {
+    match peel(expression).kind {
+        ExprKind::Binary(operator, length, one)
+            if operator.node == BinOpKind::Sub && literal(one) == Some(1) =>
+        {
+            length_of(length).map(Upper::Length)
+        },
+        _ => literal(expression)
+            .and_then(|value: u128| value.checked_add(1))
+            .map(Upper::Constant),
+    }
+}
+
+/// Returns `true` if `callee` is a path to `core::cmp::min`.
+///
+/// # Arguments
+/// * `context` (`&LateContext<'_>`) - The lint context.
+/// * `callee` (`&Expr<'_>`) - The called expression.
+fn is_cmp_min(context: &LateContext<'_>, callee: &Expr<'_>) -> bool {
+    let ExprKind::Path(qpath): &ExprKind<'_> = &callee.kind else {
+        return false;
+    };
+
+    matches!(
+        context.qpath_res(qpath, callee.hir_id),
+        Res::Def(DefKind::Fn, def_id)
+            if context.tcx.is_diagnostic_item(sym::cmp_min, def_id)
+    )
+}
+
+/// Returns `true` if `expression` reads the local `binding`.
+///
+/// # Arguments
+/// * `binding` (`HirId`) - The binding of the local.
+/// * `expression` (`&Expr<'_>`) - The expression to check.
+fn is_binding(binding: HirId, expression: &Expr<'_>) -> bool {
+    matches!(
+        peel(expression).kind,
+        ExprKind::Path(QPath::Resolved(None, path))
+            if path.res == Res::Local(binding)
+    )
+}
+
+/// Returns the bounds established by the shape of `index`: the divisor of a
+/// remainder, or an argument of `min`.
+///
+/// # Arguments
+/// * `context` (`&LateContext<'tcx>`) - The lint context.
+/// * `index` (`&'tcx Expr<'tcx>`) - The index.
+fn shape_bounds<'tcx>(
+    context: &LateContext<'tcx>,
+    index: &'tcx Expr<'tcx>,
+) -> Vec<Upper<'tcx>> {
+    match peel(index).kind {
+        ExprKind::Binary(operator, _, divisor)
+            if operator.node == BinOpKind::Rem =>
+        {
+            exclusive(divisor).into_iter().collect()
+        },
+        ExprKind::MethodCall(segment, receiver, [argument], _)
+            if segment.ident.name.as_str() == "min" =>
+        {
+            [receiver, argument].into_iter().filter_map(inclusive).collect()
+        },
+        ExprKind::Call(callee, [first, second])
+            if is_cmp_min(context, callee) =>
+        {
+            [first, second].into_iter().filter_map(inclusive).collect()
+        },
+        _ => Vec::new(),
+    }
+}
+
+/// Returns the receiver of `expression` if it is a `get` call with the
+/// local `binding` as argument, e.g. `v` for `v.get(i)`.
+///
+/// # Arguments
+/// * `binding` (`HirId`) - The binding of the index.
+/// * `expression` (`&'tcx Expr<'tcx>`) - The expression to check.
+fn checked_receiver<'tcx>(
+    binding: HirId,
+    expression: &'tcx Expr<'tcx>,
+) -> Option<&'tcx Expr<'tcx>> {
+    match peel(expression).kind {
+        ExprKind::MethodCall(segment, receiver, [argument], _)
+            if segment.ident.name.as_str() == "get"
+                && is_binding(binding, argument) =>
+        {
+            Some(receiver)
+        },
+        _ => None,
+    }
+}
+
+/// Returns the bounds established by the comparison or the `get` check
+/// `condition` on the local `binding` when the condition evaluates to
+/// `holds`.
+///
+/// # Arguments
+/// * `binding` (`HirId`) - The binding of the index.
+/// * `condition` (`&'tcx Expr<'tcx>`) - The condition.
+/// * `holds` (`bool`) - The value of the condition.
+fn condition_bounds<'tcx>(
+    binding: HirId,
+    condition: &'tcx Expr<'tcx>,
+    holds: bool,
+) -> Vec<Upper<'tcx>> {
+    match peel(condition).kind {
+        ExprKind::Unary(UnOp::Not, inner) => {
+            condition_bounds(binding, inner, !holds)
+        },
+        // Both sides of `&&` hold when it is true.
+        ExprKind::Binary(operator, first, second)
+            if operator.node == BinOpKind::And && holds =>
+        {
+            let mut bounds: Vec<Upper<'tcx>> =
+                condition_bounds(binding, first, holds);
+            bounds.extend(condition_bounds(binding, second, holds));
+            bounds
+        },
+        // Neither side of `||` holds when it is false.
+        ExprKind::Binary(operator, first, second)
+            if operator.node == BinOpKind::Or && !holds =>
+        {
+            let mut bounds: Vec<Upper<'tcx>> =
+                condition_bounds(binding, first, holds);
+            bounds.extend(condition_bounds(binding, second, holds));
+            bounds
+        },
+        // `v.get(i).is_some()` holds, or `v.get(i).is_none()` does not, when
+        // `i` is within the bounds of `v`.
+        ExprKind::MethodCall(segment, receiver, [], _)
+            if matches!(
+                segment.ident.name.as_str(),
+                "is_some" | "is_none"
+            ) =>
+        {
+            if holds != (segment.ident.name.as_str() == "is_some") {
+                return Vec::new();
+            }
+            checked_receiver(binding, receiver)
+                .map(Upper::Length)
+                .into_iter()
+                .collect()
+        },
+        ExprKind::Binary(operator, first, second) => {
+            // Normalized to `binding < bound` or `binding <= bound`.
+            let (operator, bound): (BinOpKind, &Expr<'_>) = match (
+                is_binding(binding, first),
+                is_binding(binding, second),
+            ) {
+                (true, false) => (operator.node, second),
+                (false, true) => match operator.node {
+                    BinOpKind::Gt => (BinOpKind::Lt, first),
+                    BinOpKind::Ge => (BinOpKind::Le, first),
+                    BinOpKind::Lt => (BinOpKind::Gt, first),
+                    BinOpKind::Le => (BinOpKind::Ge, first),
+                    _ => return Vec::new(),
+                },
+                _ => return Vec::new(),
+            };
+
+            let bound: Option<Upper<'tcx>> = match (operator, holds) {
+                (BinOpKind::Lt, true) | (BinOpKind::Ge, false) => {
+                    exclusive(bound)
+                },
+                (BinOpKind::Le, true) | (BinOpKind::Gt, false) => {
+                    inclusive(bound)
+                },
+                _ => None,
+            };
+            bound.into_iter().collect()
+        },
+        _ => Vec::new(),
+    }
+}
+
+/// Returns the bound established by the iterable of a `for` loop on its
+/// binding or, with `enumerated`, on the counter of `enumerate`.
+///
+/// # Arguments
+/// * `iterable` (`&'tcx Expr<'tcx>`) - The iterable of the loop.
+/// * `enumerated` (`bool`) - Whether the binding is the counter of
+///   `enumerate`.
+fn iterable_bound<'tcx>(
+    iterable: &'tcx Expr<'tcx>,
+    enumerated: bool,
+) -> Option<Upper<'tcx>> {
+    let mut current: &Expr<'_> = peel(iterable);
+
+    if enumerated {
+        let ExprKind::MethodCall(segment, receiver, [], _): ExprKind<'_> =
+            current.kind
+        else {
+            return None;
+        };
+        if segment.ident.name.as_str() != "enumerate" {
+            return None;
+        }
+        current = receiver;
+        while let ExprKind::MethodCall(segment, receiver, _, _) = current.kind
+        {
+            if !NON_INCREASING.contains(&segment.ident.name.as_str()) {
+                return None;
+            }
+            current = receiver;
+        }
+        return Some(Upper::Length(current));
+    }
+
+    // `(0..n).rev()`, `(0..n).step_by(2)`, ...
+    while let ExprKind::MethodCall(segment, receiver, _, _) = current.kind {
+        if !NON_INCREASING.contains(&segment.ident.name.as_str()) {
+            return None;
+        }
+        current = peel(receiver);
+    }
+
+    match current.kind {
+        ExprKind::Struct(QPath::LangItem(LangItem::Range, _), fields, _) => {
+            fields
+                .iter()
+                .find(|field: &&ExprField<'_>| field.ident.name == sym::end)
+                .and_then(|field: &ExprField<'_>| exclusive(field.expr))
+        },
+        ExprKind::Call(callee, [_, end])
+            if matches!(
+                callee.kind,
+                ExprKind::Path(QPath::LangItem(
+                    LangItem::RangeInclusiveNew,
+                    _
+                ))
+            ) =>
+        {
+            inclusive(end)
+        },
+        _ => None,
+    }
+}
+
+/// Returns the bound established on `binding` by the `for` loop binding
+/// it, if any.
+///
+/// # Arguments
+/// * `context` (`&LateContext<'tcx>`) - The lint context.
+/// * `binding` (`HirId`) - The binding of the index.
+fn loop_bound<'tcx>(
+    context: &LateContext<'tcx>,
+    binding: HirId,
+) -> Option<Upper<'tcx>> {
+    // `for pattern in iterable { .. }` is desugared to
+    // `match into_iter(iterable) { mut iter => loop {
+    //     match next(&mut iter) { None => break, Some(pattern) => .. } } }`.
+    let mut enumerated: bool = false;
+    let mut matches: usize = 0;
+    for (_, node) in context.tcx.hir_parent_iter(binding) {
+        match node {
+            Node::Pat(Pat { kind: PatKind::Tuple([first, _], _), .. })
+                if first.hir_id == binding =>
+            {
+                enumerated = true;
+            },
+            Node::Pat(_)
+            | Node::PatField(_)
+            | Node::Arm(_)
+            | Node::Block(_)
+            | Node::Stmt(_) => {},
+            Node::Expr(Expr {
+                kind:
+                    ExprKind::Match(scrutinee, _, MatchSource::ForLoopDesugar),
+                ..
+            }) => {
+                matches += 1;
+                if matches == 2 {
+                    let ExprKind::Call(_, [iterable]): ExprKind<'_> =
+                        scrutinee.kind
+                    else {
+                        return None;
+                    };
+                    return iterable_bound(iterable, enumerated);
+                }
+            },
+            Node::Expr(Expr { kind: ExprKind::Loop(..), .. }) => {},
+            _ => return None,
+        }
+    }
+
+    None
+}
+
+/// Returns `true` if evaluating `expression` never completes, e.g. a block
+/// ending with `return`, `continue` or a panic.
+///
+/// # Arguments
+/// * `context` (`&LateContext<'_>`) - The lint context.
+/// * `expression` (`&Expr<'_>`) - The expression to check.
+fn diverges(context: &LateContext<'_>, expression: &Expr<'_>) -> bool {
+    if context.typeck_results().expr_ty(expression).is_never() {
+        return true;
+    }
+
+    // Blocks ending with a diverging statement, such as the panic of
+    // `assert!`, have the unit type.
+    let ExprKind::Block(block, _): ExprKind<'_> = expression.kind else {
+        return false;
+    };
+    block.stmts.iter().any(|statement: &Stmt<'_>| {
+        matches!(
+            statement.kind,
+            StmtKind::Expr(inner) | StmtKind::Semi(inner)
+                if diverges(context, inner)
+        )
+    }) || block.expr.is_some_and(|tail: &Expr<'_>| diverges(context, tail))
+}
+
+/// Returns the bounds established on `binding` by the comparisons guarding
+/// `index`: the conditions of the `if` expressions containing it, and the
+/// diverging `if` statements preceding it in the enclosing blocks.
+///
+/// # Arguments
+/// * `context` (`&LateContext<'tcx>`) - The lint context.
+/// * `index` (`&'tcx Expr<'tcx>`) - The index.
+/// * `binding` (`HirId`) - The binding of the index.
+fn guard_bounds<'tcx>(
+    context: &LateContext<'tcx>,
+    index: &'tcx Expr<'tcx>,
+    binding: HirId,
+) -> Vec<Upper<'tcx>> {
+    let mut bounds: Vec<Upper<'tcx>> = Vec::new();
+    let mut child: HirId = index.hir_id;
+
+    for (parent, node) in context.tcx.hir_parent_iter(index.hir_id) {
+        match node {
+            Node::Expr(Expr {
+                kind: ExprKind::If(condition, then, otherwise),
+                ..
+            }) => {
+                if then.hir_id == child {
+                    bounds.extend(condition_bounds(binding, condition, true));
+                } else if otherwise.is_some_and(|otherwise: &Expr<'_>| {
+                    otherwise.hir_id == child
+                }) {
+                    bounds.extend(condition_bounds(binding, condition, false));
+                }
+            },
+            Node::Block(Block { stmts, .. }) => {
+                for statement in
+                    stmts.iter().take_while(|statement: &&Stmt<'_>| {
+                        statement.hir_id != child
+                    })
+                {
+                    if let StmtKind::Expr(expression)
+                    | StmtKind::Semi(expression) = statement.kind
+                        && let ExprKind::If(condition, then, None) =
+                            peel(expression).kind
+                        && diverges(context, then)
+                    {
+                        bounds.extend(condition_bounds(
+                            binding, condition, false,
+                        ));
+                    }
+                }
+            },
+            Node::Item(_) | Node::ImplItem(_) | Node::TraitItem(_) => break,
+            _ => {},
+        }
+        child = parent;
+    }
+
+    bounds
+}
+
+/// Returns the known exclusive upper bounds of `index`, including those
+/// established by the checks guarding it when `guards` is set.
+///
+/// # Arguments
+/// * `context` (`&LateContext<'tcx>`) - The lint context.
+/// * `index` (`&'tcx Expr<'tcx>`) - The index.
+/// * `guards` (`bool`) - Whether the checks guarding the index are used.
+pub fn upper_bounds<'tcx>(
+    context: &LateContext<'tcx>,
+    index: &'tcx Expr<'tcx>,
+    guards: bool,
+) -> Vec<Upper<'tcx>> {
+    let mut bounds: Vec<Upper<'tcx>> = shape_bounds(context, index);
+
+    if let ExprKind::Path(QPath::Resolved(None, path)) = peel(index).kind
+        && let Res::Local(binding) = path.res
+        && let Node::Pat(Pat {
+            kind: PatKind::Binding(BindingMode::NONE, _, _, None),
+            ..
+        }) = context.tcx.hir_node(binding)
+    {
+        bounds.extend(loop_bound(context, binding));
+        if guards {
+            bounds.extend(guard_bounds(context, index, binding));
+        }
+    }
+
+    bounds
+}
@@ -0,0 +1,41 @@
+#![warn(security_indexing_usage, security_map_indexing)]
+
+use std::collections::HashMap;
+
+pub fn update(values: &mut Vec<u32>, grid: &mut [[u8; 4]], index: usize) {
+    // Read (should suggest `get`).
+    let value: u32 = values[index];
+
+    // Shared borrow (should suggest `get`, replacing the borrow).
+    let borrowed: u32 = *&values[index];
+
+    // Mutable borrow (should suggest `get_mut`, replacing the borrow).
+    let slot: &mut u32 = &mut values[index];
+    *slot += value + borrowed;
+
+    // Assignment (should suggest `get_mut`).
+    values[index] = 0;
+
+    // Compound assignment through nested indexing (should suggest `get_mut`
+    // for both).
+    grid[index][index] += 1;
+
+    // Method call taking `&mut self` (should suggest `get_mut`).
+    values[index..].sort();
+}
+
+pub fn lookup(names: &HashMap<String, u32>, text: &str, start: usize) -> u32 {
+    // Map lookup (should suggest `get`).
+    let id: u32 = names["root"];
+
+    // String slicing (should suggest `get`).
+    let tail: &str = &text[start..];
+    id + tail.len() as u32
+}
+
+fn main() {
+    let mut values: Vec<u32> = vec![1, 2, 3];
+    let mut grid: [[u8; 4]; 4] = [[0; 4]; 4];
+    update(&mut values, &mut grid, 1);
+    let _: u32 = lookup(&HashMap::new(), "abc", 1);
+}
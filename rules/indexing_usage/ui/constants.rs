@@ -0,0 +1,58 @@
+#![warn(security_indexing_usage, security_literal_indexing)]
+
+const SIZE: usize = 8;
+const LAST: usize = SIZE - 1;
+const OFFSET: i32 = -1;
+
+struct Buffer {
+    bytes: [u8; Buffer::LEN],
+}
+
+impl Buffer {
+    const LEN: usize = 16;
+    const HEADER: usize = 4;
+
+    /// Associated constants of the implementation are evaluated.
+    fn header(&self) -> &[u8] {
+        &self.bytes[..Self::HEADER] // Should not trigger.
+    }
+
+    fn footer(&self) -> u8 {
+        self.bytes[Self::LEN - 1] // Should not trigger.
+    }
+
+    fn overflow(&self) -> u8 {
+        self.bytes[Self::LEN] // Should trigger.
+    }
+}
+
+trait Table {
+    const INDEX: usize;
+
+    /// The value of a trait constant depends on the implementation.
+    fn entry(&self, table: [u8; 4]) -> u8 {
+        table[Self::INDEX] // Should trigger.
+    }
+}
+
+fn main() {
+    let table: [u8; SIZE] = [0; SIZE];
+
+    let first: u8 = [1, 2, 3][0]; // Should not trigger: array literal.
+    let last: u8 = table[LAST]; // Should not trigger: constant item.
+    let middle: u8 = table[SIZE / 2]; // Should not trigger.
+    let end: u8 = table[SIZE - 1]; // Should not trigger.
+    let masked: u8 = table[7 & 3]; // Should not trigger.
+    let shifted: u8 = table[1 << 2]; // Should not trigger.
+    let cast: u8 = table[3u8 as usize]; // Should not trigger.
+    let upper: &[u8] = &table[SIZE / 2..SIZE]; // Should not trigger.
+
+    let past: u8 = table[SIZE]; // Should trigger: out of bounds.
+    let doubled: u8 = table[SIZE * 2 - 1]; // Should trigger: out of bounds.
+    let negative: u8 = table[(OFFSET + 2) as usize]; // Should trigger.
+    let truncated: u8 = table[(SIZE * 64) as u8 as usize]; // Should trigger.
+
+    let matrix: [[u8; 3]; 2] = [[0; 3]; 2];
+    let corner: u8 = matrix[1][2]; // Should not trigger: nested arrays.
+    let outside: u8 = matrix[2][0]; // Should trigger once.
+}
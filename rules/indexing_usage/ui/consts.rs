@@ -0,0 +1,28 @@
+#![warn(security_indexing_usage)]
+
+const TABLE: [u8; 4] = [1, 2, 4, 8];
+const SHIFT: usize = 2;
+
+// Evaluated at compile time, where an out-of-bounds index does not compile.
+const MASK: u8 = TABLE[SHIFT] | TABLE[SHIFT + 1]; // Should not trigger.
+static HEAD: &[u8] = TABLE.split_at(SHIFT).0; // Should not trigger.
+
+pub struct Lookup;
+
+impl Lookup {
+    const LAST: u8 = TABLE[TABLE.len() - 1]; // Should not trigger.
+}
+
+pub const fn entry(index: usize) -> u8 {
+    TABLE[index] // Should trigger: also callable at runtime.
+}
+
+pub fn runtime(index: usize) -> u8 {
+    let inline: u8 = const { TABLE[SHIFT] }; // Should not trigger.
+    let lengths: [u8; TABLE[1] as usize] = [0; 2]; // Should not trigger.
+    inline + lengths[index] // Should trigger.
+}
+
+fn main() {
+    let _: u8 = MASK + HEAD.len() as u8 + Lookup::LAST + entry(1) + runtime(1);
+}
@@ -0,0 +1,19 @@
+#![warn(security_indexing_usage)]
+#![allow(security_literal_indexing)]
+
+pub fn levels(values: &[u8], index: usize) -> u8 {
+    let first: u8 = values[0]; // Should not trigger: literal indexing allowed.
+    let other: u8 = values[index]; // Should trigger: dynamic indexing.
+    let window: &[u8] = &values[1..]; // Should trigger: slicing.
+
+    first + other + window.len() as u8
+}
+
+#[warn(security_literal_indexing)]
+pub fn literal(values: &[u8]) -> u8 {
+    values[1] // Should trigger: warned for this function.
+}
+
+fn main() {
+    let _: u8 = levels(&[1, 2, 3], 1) + literal(&[1, 2]);
+}
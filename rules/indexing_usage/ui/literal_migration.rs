@@ -0,0 +1,42 @@
+#[allow(security_indexing_usage)]
+pub fn allowed(values: &[u16]) -> u16 {
+    // OK: the former lint still applies to literal indexing.
+    values[0]
+}
+
+#[expect(security_indexing_usage)]
+pub fn expected(values: &Vec<u16>) -> u16 {
+    // OK: the literal indexing fulfills the expectation.
+    values[1]
+}
+
+#[warn(security_indexing_usage)]
+pub fn former(values: &[u16]) -> u16 {
+    values[0] // Should trigger the indexing lint, as a warning.
+}
+
+#[allow(security_indexing_usage)]
+#[warn(security_literal_indexing)]
+pub fn migrated(values: &[u16]) -> u16 {
+    values[0] // Should trigger the literal lint.
+}
+
+#[allow(security_literal_indexing)]
+pub fn split(values: &[u16], index: usize) -> u16 {
+    // The literal lint is allowed on its own, the indexing lint still applies.
+    values[0] + values[index]
+}
+
+pub fn default(values: &[u16]) -> u16 {
+    values[0] // Should trigger the literal lint, denied by default.
+}
+
+fn main() {
+    let values: Vec<u16> = vec![1, 2];
+    let _: u16 = allowed(&values);
+    let _: u16 = expected(&values);
+    let _: u16 = former(&values);
+    let _: u16 = migrated(&values);
+    let _: u16 = split(&values, 0);
+    let _: u16 = default(&values);
+}
@@ -1,4 +1,4 @@
-#![warn(security_indexing_usage)]
+#![warn(security_indexing_usage, security_literal_indexing)]
 
 /// This module defines the `SECURITY_INDEXING_USAGE` lint, which detects the
 /// use of indexing and slicing operations in Rust code. The lint is designed
@@ -11,9 +11,15 @@
 /// potential runtime panics and security vulnerabilities.
 fn main() {
     let array: [i32; 3] = [1, 2, 3];
-    let x: i32 = array[0]; // Should trigger.
+    let x: i32 = array[3]; // Should trigger.
 
-    let slice: &[i32] = &array[1..]; // Should trigger.
+    let slice: &[i32] = &array[1..4]; // Should trigger.
+
+    let vector: Vec<i32> = vec![1, 2, 3];
+    let y: i32 = vector[0]; // Should trigger.
+
+    let z: i32 = array[0]; // Should not trigger: within the array.
+    let tail: &[i32] = &array[1..]; // Should not trigger: within the array.
 
     use std::ops::Index;
 
@@ -26,4 +32,16 @@ fn main() {
             &self.0[index] // Should trigger.
         }
     }
+
+    let values: Vec<i32> = vec![1, 2, 3];
+    let _: (i32, i32) = pair!(values, 1); // Should trigger once per lint.
+}
+
+/// Each use of the macro indexes twice, at two spans of the macro definition
+/// sharing the call site.
+#[macro_export]
+macro_rules! pair {
+    ($values:expr, $index:expr) => {
+        ($values[$index - 1], $values[$index])
+    };
 }
@@ -0,0 +1,27 @@
+#![warn(security_indexing_usage, security_map_indexing)]
+
+use std::collections::{BTreeMap, HashMap};
+
+pub fn lookup(
+    ports: &HashMap<String, u16>,
+    limits: &BTreeMap<u32, u64>,
+    name: &str,
+    user: u32,
+) -> u64 {
+    let port: u16 = ports[name]; // Should trigger the map lint.
+    let limit: &u64 = &limits[&user]; // Should trigger the map lint.
+
+    u64::from(port) + limit
+}
+
+pub fn nested(tables: &Vec<HashMap<u8, u8>>, index: usize, key: u8) -> u8 {
+    // The map lint for the outer indexing, the indexing lint for the inner.
+    tables[index][&key]
+}
+
+fn main() {
+    let ports: HashMap<String, u16> = HashMap::new();
+    let limits: BTreeMap<u32, u64> = BTreeMap::new();
+    let _: u64 = lookup(&ports, &limits, "http", 0);
+    let _: u8 = nested(&Vec::new(), 0, 1);
+}
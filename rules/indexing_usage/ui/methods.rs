@@ -0,0 +1,38 @@
+#![warn(security_indexing_usage)]
+
+pub fn split(bytes: &[u8], header: &[u8; 8], text: &str, mid: usize) -> usize {
+    let (head, _) = bytes.split_at(mid); // Should trigger.
+    let (_, tail) = header.split_at(4); // Should not trigger: within the array.
+    let (_, past) = header.split_at(9); // Should trigger: past the end.
+    let (all, _) = bytes.split_at(bytes.len()); // Should not trigger.
+    let (word, _) = text.split_at(mid); // Should trigger: character boundary.
+
+    if mid < bytes.len() {
+        let (_, rest) = bytes.split_at(mid); // Should not trigger: checked.
+        return rest.len();
+    }
+
+    head.len() + tail.len() + past.len() + all.len() + word.len()
+}
+
+pub fn copy(target: &mut [u8], source: &[u8], key: &mut [u8; 4], id: [u8; 4]) {
+    target.copy_from_slice(source); // Should trigger.
+    key.copy_from_slice(&id); // Should not trigger: same array length.
+    target.clone_from_slice(&id); // Should trigger.
+}
+
+pub fn chunks(bytes: &[u8], size: usize) -> usize {
+    const BLOCK: usize = 16;
+
+    bytes.chunks(size).count() // Should trigger: the size may be zero.
+        + bytes.windows(size).count() // Should trigger.
+        + bytes.chunks_exact(BLOCK).count() // Should not trigger: constant.
+        + bytes.chunks(0).count() // Should trigger: zero.
+}
+
+fn main() {
+    let mut key: [u8; 4] = [0; 4];
+    let _: usize = split(&[1, 2, 3], &[0; 8], "abc", 1);
+    copy(&mut [0; 4], &[1; 4], &mut key, [2; 4]);
+    let _: usize = chunks(&[1, 2, 3], 2);
+}
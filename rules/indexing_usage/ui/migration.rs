@@ -0,0 +1,38 @@
+use std::collections::HashMap;
+
+#[allow(security_indexing_usage)]
+pub fn allowed(ports: &HashMap<u8, u16>, values: &[u16], key: u8) -> u16 {
+    // OK: the former lint still applies to map indexing.
+    ports[&key] + values[usize::from(key)]
+}
+
+#[expect(security_indexing_usage)]
+pub fn expected(ports: &HashMap<u8, u16>, key: u8) -> u16 {
+    // OK: the map indexing fulfills the expectation.
+    ports[&key]
+}
+
+#[allow(security_indexing_usage)]
+#[warn(security_map_indexing)]
+pub fn migrated(ports: &HashMap<u8, u16>, key: u8) -> u16 {
+    ports[&key] // Should trigger the map lint.
+}
+
+#[allow(security_map_indexing)]
+pub fn split(ports: &HashMap<u8, u16>, values: &[u16], key: u8) -> u16 {
+    // The map lint is allowed on its own, the indexing lint still applies.
+    ports[&key] + values[usize::from(key)]
+}
+
+pub fn default(ports: &HashMap<u8, u16>, key: u8) -> u16 {
+    ports[&key] // Should trigger the map lint.
+}
+
+fn main() {
+    let ports: HashMap<u8, u16> = HashMap::new();
+    let _: u16 = allowed(&ports, &[], 0);
+    let _: u16 = expected(&ports, 0);
+    let _: u16 = migrated(&ports, 0);
+    let _: u16 = split(&ports, &[], 0);
+    let _: u16 = default(&ports, 0);
+}
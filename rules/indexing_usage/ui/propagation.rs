@@ -0,0 +1,51 @@
+#![warn(security_indexing_usage, security_literal_indexing)]
+
+const OFFSET: usize = 2;
+
+fn first(bytes: &[u8]) -> u8 {
+    bytes[0] // Should not trigger: always called with 4 elements.
+}
+
+fn at(bytes: &[u8], index: usize) -> u8 {
+    bytes[index] // Should not trigger: always called with indexes below 4.
+}
+
+fn checksum(header: &[u8; 4]) -> u8 {
+    first(header) ^ at(header, 3)
+}
+
+fn header(bytes: &[u8]) -> &[u8] {
+    &bytes[..OFFSET] // Should not trigger: `OFFSET` is within 4 elements.
+}
+
+fn unknown(bytes: &[u8]) -> u8 {
+    bytes[1] // Should trigger: called with a slice of unknown length.
+}
+
+fn too_far(bytes: &[u8]) -> u8 {
+    bytes[4] // Should trigger: out of the arrays passed to it.
+}
+
+pub fn exported(bytes: &[u8]) -> u8 {
+    bytes[0] // Should trigger: callable from other crates.
+}
+
+fn escaped(bytes: &[u8]) -> u8 {
+    bytes[0] // Should trigger: used as a function pointer.
+}
+
+fn main() {
+    let bytes: [u8; 4] = [1, 2, 3, 4];
+    let vector: Vec<u8> = vec![1, 2];
+
+    let _: u8 = checksum(&bytes);
+    let _: u8 = at(&bytes, OFFSET);
+    let _: &[u8] = header(&bytes);
+    let _: u8 = unknown(&vector);
+    let _: u8 = unknown(&bytes);
+    let _: u8 = too_far(&bytes);
+    let _: u8 = exported(&bytes);
+    let _: u8 = escaped(&bytes);
+    let pointer: fn(&[u8]) -> u8 = escaped;
+    let _: u8 = pointer(&bytes);
+}
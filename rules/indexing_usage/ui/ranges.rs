@@ -0,0 +1,97 @@
+#![warn(security_indexing_usage)]
+
+struct Ring {
+    slots: Vec<u32>,
+}
+
+impl Ring {
+    fn slot(&self, position: usize) -> u32 {
+        self.slots[position % self.slots.len()] // Should not trigger: remainder.
+    }
+}
+
+fn clamped(values: &[u32], index: usize) -> u32 {
+    values[index.min(values.len() - 1)] // Should not trigger: clamped.
+}
+
+fn compared(values: &[u32], index: usize) -> u32 {
+    if index < values.len() && index > 0 {
+        values[index] // Should not trigger: compared beforehand.
+    } else {
+        values[index] // Should trigger: out of the comparison.
+    }
+}
+
+fn early_return(values: &[u32], index: usize) -> u32 {
+    if index >= values.len() {
+        return 0;
+    }
+    values[index] // Should not trigger: returned before.
+}
+
+fn asserted(values: &[u32], index: usize) -> u32 {
+    assert!(index < values.len());
+    values[index] // Should not trigger: asserted before.
+}
+
+fn checked(values: &[u32], others: &[u32], index: usize) -> u32 {
+    if values.get(index).is_some() {
+        return values[index]; // Should not trigger: checked with `get`.
+    }
+    if others.get(index).is_none() {
+        return 0;
+    }
+    others[index] // Should not trigger: checked with `get` before.
+}
+
+fn other_length(values: &[u32], others: &[u32], index: usize) -> u32 {
+    if index < others.len() {
+        values[index] // Should trigger: compared with another length.
+    } else {
+        0
+    }
+}
+
+fn looped(values: &[u32]) -> u32 {
+    let mut sum: u32 = 0;
+    for index in 0..values.len() {
+        sum += values[index]; // Should not trigger: loop bound.
+    }
+    for index in (0..values.len()).rev() {
+        sum += values[index]; // Should not trigger: loop bound.
+    }
+    for (index, _) in values.iter().enumerate() {
+        sum += values[index]; // Should not trigger: enumerated.
+    }
+    for index in 0..=values.len() {
+        sum += values[index]; // Should trigger: inclusive loop bound.
+    }
+    sum
+}
+
+fn constants(values: [u32; 8], index: usize) -> u32 {
+    let mut sum: u32 = values[index % 8]; // Should not trigger: remainder.
+    for position in 0..8 {
+        sum += values[position]; // Should not trigger: loop bound.
+    }
+    let mut cursor: usize = index;
+    if cursor < 8 {
+        cursor += 1;
+        sum += values[cursor]; // Should trigger: mutable binding.
+    }
+    sum
+}
+
+fn main() {
+    let ring: Ring = Ring { slots: vec![1, 2, 3] };
+    let values: Vec<u32> = vec![1, 2, 3];
+    let _: u32 = ring.slot(4);
+    let _: u32 = clamped(&values, 4);
+    let _: u32 = compared(&values, 4);
+    let _: u32 = early_return(&values, 4);
+    let _: u32 = asserted(&values, 1);
+    let _: u32 = checked(&values, &values, 1);
+    let _: u32 = other_length(&values, &values, 1);
+    let _: u32 = looped(&values);
+    let _: u32 = constants([0; 8], 3);
+}
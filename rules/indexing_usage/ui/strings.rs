@@ -0,0 +1,22 @@
+#![warn(security_indexing_usage)]
+
+pub fn prefix(name: &str, owned: &String, end: usize) -> usize {
+    let head: &str = &name[..end]; // Should trigger: character boundary.
+    let bounded: &str = &name[..end.min(name.len())]; // Should trigger.
+    let literal: &str = &"héllo"[0..2]; // Should trigger: splits `é`.
+    let tail: &str = &owned[1..]; // Should trigger on `String` too.
+    let whole: &str = &owned[..]; // Should not trigger: never panics.
+
+    head.len() + bounded.len() + literal.len() + tail.len() + whole.len()
+}
+
+pub fn bytes(name: &str, end: usize) -> u8 {
+    // Should trigger the indexing message: bytes have no boundaries.
+    name.as_bytes()[..end.min(name.len())].iter().sum::<u8>()
+        + name.as_bytes()[end]
+}
+
+fn main() {
+    let _: usize = prefix("abc", &String::from("abc"), 1);
+    let _: u8 = bytes("abc", 1);
+}
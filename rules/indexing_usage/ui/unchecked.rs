@@ -0,0 +1,46 @@
+#![warn(security_indexing_usage)]
+
+pub fn read(values: &[u32], index: usize) -> u32 {
+    // SAFETY: not checked.
+    unsafe { *values.get_unchecked(index) } // Should trigger.
+}
+
+pub fn write(values: &mut Vec<u32>, index: usize) {
+    // SAFETY: not checked.
+    unsafe { *values.get_unchecked_mut(index) = 0 } // Should trigger.
+}
+
+pub fn tail(text: &str, start: usize) -> &str {
+    // SAFETY: not checked.
+    unsafe { text.get_unchecked(start..) } // Should trigger.
+}
+
+pub fn known(table: &[u8; 4]) -> u8 {
+    // SAFETY: the index is within the array.
+    unsafe { *table.get_unchecked(3) } // Should not trigger.
+}
+
+pub fn checked(values: &[u32], index: usize) -> u32 {
+    if index < values.len() {
+        // SAFETY: the index was checked.
+        return unsafe { *values.get_unchecked(index) }; // Should not trigger.
+    }
+    0
+}
+
+pub fn prefix(text: &str, end: usize) -> &str {
+    if end < text.len() {
+        // SAFETY: the end may still split a character.
+        return unsafe { text.get_unchecked(..end) }; // Should trigger.
+    }
+    text
+}
+
+fn main() {
+    let mut values: Vec<u32> = vec![1, 2, 3];
+    write(&mut values, 1);
+    let _: u32 = read(&values, 1) + checked(&values, 1);
+    let _: &str = tail("abc", 1);
+    let _: &str = prefix("abc", 1);
+    let _: u8 = known(&[0; 4]);
+}
@@ -0,0 +1,33 @@
+#![warn(security_indexing_usage, security_literal_indexing)]
+
+use std::ops::{Index, IndexMut};
+
+pub struct Registers(Vec<u32>);
+
+// The implementations are still reported, not the indexing in their bodies.
+impl Index<usize> for Registers {
+    type Output = u32;
+
+    fn index(&self, index: usize) -> &Self::Output {
+        &self.0[index] // Should not trigger.
+    }
+}
+
+impl IndexMut<usize> for Registers {
+    fn index_mut(&mut self, index: usize) -> &mut Self::Output {
+        let offset: usize = [0, 1][index & 1]; // Should not trigger.
+        &mut self.0[index - offset] // Should not trigger.
+    }
+}
+
+impl Registers {
+    pub fn read(&self, index: usize) -> u32 {
+        self.0[index] // Should trigger: not an `Index` implementation.
+    }
+}
+
+fn main() {
+    let mut registers: Registers = Registers(vec![0; 4]);
+    registers[1] = 2;
+    let _: u32 = registers.read(1);
+}
@@ -0,0 +1,31 @@
+#![warn(security_indexing_usage)]
+
+fn compared(values: &[u32], index: usize) -> u32 {
+    if index < values.len() {
+        values[index] // Should trigger: guards are not used.
+    } else {
+        0
+    }
+}
+
+fn checked(values: &[u32], index: usize) -> u32 {
+    if values.get(index).is_none() {
+        return 0;
+    }
+    values[index] // Should trigger: guards are not used.
+}
+
+fn looped(values: &[u32]) -> u32 {
+    let mut sum: u32 = 0;
+    for index in 0..values.len() {
+        sum += values[index]; // Should not trigger: loop bound.
+    }
+    sum
+}
+
+fn main() {
+    let values: Vec<u32> = vec![1, 2, 3];
+    let _: u32 = compared(&values, 4);
+    let _: u32 = checked(&values, 4);
+    let _: u32 = looped(&values);
+}
@@ -0,0 +1,460 @@
+#![feature(rustc_private)]
+
+extern crate rustc_errors;
+extern crate rustc_hir;
+extern crate rustc_lint;
+extern crate rustc_middle;
+extern crate rustc_session;
+extern crate rustc_span;
+
+use rustc_errors::Diag;
+use rustc_hir::{
+    Attribute,
+    Expr,
+    ExprKind,
+    HirId,
+    Item,
+    ItemKind,
+    def::DefKind,
+    def_id::DefId,
+    intravisit::{Visitor, walk_expr},
+};
+use rustc_lint::{LateContext, LateLintPass, LintStore};
+use rustc_middle::{
+    hir::nested_filter,
+    ty::{Ty, TyCtxt, TypeckResults},
+};
+use rustc_session::{Session, declare_lint, impl_lint_pass};
+use rustc_span::{ExpnData, ExpnKind, MacroKind, Span, Symbol, sym};
+use security_utils::{dedup::Reported, known::is_panic_function};
+use serde::Deserialize;
+
+declare_lint! {
+    pub SECURITY_INTERRUPT_PANIC,
+    Deny,
+    "Detects panic-capable code inside interrupt and exception handlers."
+}
+
+declare_lint! {
+    pub SECURITY_INTERRUPT_ALLOCATION,
+    Deny,
+    "Detects heap allocations inside interrupt and exception handlers."
+}
+
+/// Configuration of the `interrupt_handler` library, read from the
+/// `[interrupt_handler]` table of the workspace's `dylint.toml`.
+#[derive(Deserialize)]
+#[serde(default)]
+struct Config {
+    /// Attributes (or attribute macros) marking a function as an interrupt
+    /// or exception handler.
+    attributes: Vec<String>,
+    /// Names of functions registered as interrupt entry points by other means
+    /// (e.g. a hand-written vector table).
+    entry_points: Vec<String>,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Self {
+            attributes: vec!["interrupt".to_string(), "exception".to_string()],
+            entry_points: Vec::new(),
+        }
+    }
+}
+
+/// Lint pass holding the handler attributes and entry points loaded from the
+/// configuration.
+struct SecurityInterruptHandler {
+    attributes: Vec<Symbol>,
+    entry_points: Vec<Symbol>,
+}
+
+impl_lint_pass!(SecurityInterruptHandler => [
+    SECURITY_INTERRUPT_PANIC,
+    SECURITY_INTERRUPT_ALLOCATION
+]);
+
+impl SecurityInterruptHandler {
+    fn new(tcx: TyCtxt<'_>) -> Self {
+        let config: Config =
+            security_utils::config::load(tcx, env!("CARGO_PKG_NAME"));
+
+        Self {
+            attributes: config
+                .attributes
+                .iter()
+                .map(|attribute: &String| Symbol::intern(attribute))
+                .collect(),
+            entry_points: config
+                .entry_points
+                .iter()
+                .map(|entry_point: &String| Symbol::intern(entry_point))
+                .collect(),
+        }
+    }
+
+    /// Returns `true` if `span` was produced by one of the configured
+    /// attribute macros. Handler macros such as `cortex-m-rt`'s rename the
+    /// handler with a call-site span, so the expansion is still visible on
+    /// the function's identifier after the attribute itself is gone.
+    ///
+    /// # Arguments
+    /// * `span` (`Span`) - The span whose expansion chain is inspected.
+    fn expanded_from_handler_attribute(&self, mut span: Span) -> bool {
+        while span.from_expansion() {
+            let data: ExpnData = span.ctxt().outer_expn_data();
+            if let ExpnKind::Macro(MacroKind::Attr, name) = data.kind
+                && self.attributes.iter().any(|attribute: &Symbol| {
+                    name.as_str().rsplit("::").next()
+                        == Some(attribute.as_str())
+                })
+            {
+                return true;
+            }
+            span = data.call_site;
+        }
+
+        false
+    }
+
+    /// Returns `true` if `item` is an interrupt or exception handler, either
+    /// because it carries (or was expanded from) a configured attribute or
+    /// because its name is a configured entry point.
+    ///
+    /// # Arguments
+    /// * `context` (`&LateContext<'_>`) - The lint context used to read the
+    ///   item's attributes.
+    /// * `item` (`&Item<'_>`) - The function item to check.
+    /// * `name` (`Symbol`) - The function's name.
+    /// * `name_span` (`Span`) - The span of the function's name.
+    fn is_handler(
+        &self,
+        context: &LateContext<'_>,
+        item: &Item<'_>,
+        name: Symbol,
+        name_span: Span,
+    ) -> bool {
+        self.entry_points.contains(&name)
+            || self.expanded_from_handler_attribute(name_span)
+            || self.expanded_from_handler_attribute(item.span)
+            || context.tcx.hir_attrs(item.hir_id()).iter().any(
+                |attribute: &Attribute| {
+                    attribute.path().last().is_some_and(|segment: &Symbol| {
+                        self.attributes.contains(segment)
+                    })
+                },
+            )
+    }
+}
+
+/// Returns a display name such as `Box::new` or `ToString::to_string` if
+/// `def_id` is a function of the `alloc` crate that allocates on the heap.
+///
+/// # Arguments
+/// * `tcx` (`TyCtxt<'_>`) - The type context used to inspect the function.
+/// * `def_id` (`DefId`) - The called function.
+fn allocating_function(tcx: TyCtxt<'_>, def_id: DefId) -> Option<String> {
+    if tcx.crate_name(def_id.krate) != sym::alloc {
+        return None;
+    }
+
+    let parent: DefId = tcx.parent(def_id);
+    let owner: Symbol = match tcx.def_kind(parent) {
+        DefKind::Impl { .. } => {
+            let self_ty: Ty<'_> = tcx.type_of(parent).instantiate_identity();
+            tcx.item_name(self_ty.ty_adt_def()?.did())
+        },
+        _ => tcx.opt_item_name(parent)?,
+    };
+    let name: Symbol = tcx.item_name(def_id);
+
+    let allocates: bool = matches!(
+        (owner.as_str(), name.as_str()),
+        ("Box" | "Rc" | "Arc", "new" | "pin" | "from")
+            | ("String" | "Vec", "from")
+            | (
+                _,
+                "push"
+                    | "push_back"
+                    | "push_front"
+                    | "push_str"
+                    | "insert"
+                    | "extend"
+                    | "extend_from_slice"
+                    | "append"
+                    | "resize"
+                    | "reserve"
+                    | "reserve_exact"
+                    | "with_capacity"
+                    | "to_vec"
+                    | "into_vec"
+                    | "box_new"
+                    | "from_elem"
+                    | "format"
+                    | "to_string"
+                    | "to_owned"
+                    | "repeat"
+                    | "concat"
+                    | "join"
+            )
+    );
+
+    allocates.then(|| format!("{owner}::{name}"))
+}
+
+/// Returns the name of the function-like macro `span` was expanded from, if
+/// any (e.g. `format` for a call generated by `format!`).
+///
+/// # Arguments
+/// * `span` (`Span`) - The span of the expanded expression.
+fn bang_macro_name(span: Span) -> Option<Symbol> {
+    if !span.from_expansion() {
+        return None;
+    }
+
+    match span.source_callee()?.kind {
+        ExpnKind::Macro(MacroKind::Bang, name) => Some(name),
+        _ => None,
+    }
+}
+
+/// Visitor walking the body of an interrupt handler, including nested
+/// closures, and reporting every panic-capable construct and heap
+/// allocation. Findings are reported once per macro call site.
+struct HandlerVisitor<'a, 'tcx> {
+    context: &'a LateContext<'tcx>,
+    typeck_results: &'tcx TypeckResults<'tcx>,
+    handler_span: Span,
+    reported: Reported,
+}
+
+impl HandlerVisitor<'_, '_> {
+    /// Emits a `SECURITY_INTERRUPT_PANIC` finding at `span`, with the lint
+    /// level of `node`.
+    ///
+    /// # Arguments
+    /// * `node` (`HirId`) - The expression of the construct.
+    /// * `span` (`Span`) - The span of the panic-capable construct.
+    /// * `what` (`&str`) - A description of the construct.
+    fn report_panic(&mut self, node: HirId, span: Span, what: &str) {
+        let message: String =
+            format!("{what} inside an interrupt handler detected.");
+        if !self.reported.insert(SECURITY_INTERRUPT_PANIC, span, &message) {
+            return;
+        }
+
+        security_utils::span_lint_hir(
+            self.context,
+            SECURITY_INTERRUPT_PANIC,
+            node,
+            span,
+            |diagnostic: &mut Diag<'_, ()>| {
+                diagnostic.primary_message(message);
+                diagnostic.span_note(
+                    self.handler_span,
+                    "a panic in this handler halts the device",
+                );
+            },
+        );
+    }
+
+    /// Emits a `SECURITY_INTERRUPT_ALLOCATION` finding at `span`, with the
+    /// lint level of `node`.
+    ///
+    /// # Arguments
+    /// * `node` (`HirId`) - The allocating expression.
+    /// * `span` (`Span`) - The span of the allocating call.
+    /// * `what` (`&str`) - The name of the allocating function or macro.
+    fn report_allocation(&mut self, node: HirId, span: Span, what: &str) {
+        let message: String = format!(
+            "Heap allocation through `{what}` inside an interrupt handler \
+             detected."
+        );
+        if !self.reported.insert(SECURITY_INTERRUPT_ALLOCATION, span, &message)
+        {
+            return;
+        }
+
+        security_utils::span_lint_hir(
+            self.context,
+            SECURITY_INTERRUPT_ALLOCATION,
+            node,
+            span,
+            |diagnostic: &mut Diag<'_, ()>| {
+                diagnostic.primary_message(message);
+                diagnostic.span_note(
+                    self.handler_span,
+                    "the allocator is typically not reentrant and may be in \
+                     use by the interrupted code",
+                );
+                diagnostic.help(
+                    "use statically allocated buffers or a lock-free \
+                     fixed-capacity queue instead",
+                );
+            },
+        );
+    }
+}
+
+impl<'tcx> Visitor<'tcx> for HandlerVisitor<'_, 'tcx> {
+    type MaybeTyCtxt = TyCtxt<'tcx>;
+    type NestedFilter = nested_filter::OnlyBodies;
+
+    fn maybe_tcx(&mut self) -> Self::MaybeTyCtxt {
+        self.context.tcx
+    }
+
+    fn visit_expr(&mut self, expression: &'tcx Expr<'tcx>) {
+        let tcx: TyCtxt<'tcx> = self.context.tcx;
+
+        let callee: Option<DefId> = match &expression.kind {
+            ExprKind::MethodCall(..) => {
+                self.typeck_results.type_dependent_def_id(expression.hir_id)
+            },
+            ExprKind::Call(callee, _) => match &callee.kind {
+                ExprKind::Path(path) => self
+                    .typeck_results
+                    .qpath_res(path, callee.hir_id)
+                    .opt_def_id(),
+                _ => None,
+            },
+            _ => None,
+        };
+
+        // Indexing and slicing: `array[i]`, `array[a..b]`.
+        if let ExprKind::Index(..) = expression.kind
+            && !expression.span.from_expansion()
+        {
+            self.report_panic(
+                expression.hir_id,
+                expression.span,
+                "Indexing operation",
+            );
+        }
+
+        if let Some(def_id) = callee {
+            // `unwrap` and `expect` on `Option` and `Result`.
+            if !expression.span.from_expansion()
+                && (tcx.is_diagnostic_item(sym::unwrap, def_id)
+                    || tcx.is_diagnostic_item(sym::option_unwrap, def_id)
+                    || tcx.is_diagnostic_item(sym::except, def_id)
+                    || tcx.is_diagnostic_item(sym::option_expect, def_id))
+            {
+                self.report_panic(
+                    expression.hir_id,
+                    expression.span,
+                    "Call to `unwrap/expect`",
+                );
+            }
+
+            // Explicit panics: `panic!`, `assert!`, `todo!`, ...
+            if is_panic_function(tcx, def_id) {
+                self.report_panic(
+                    expression.hir_id,
+                    expression.span.source_callsite(),
+                    "Explicit panic",
+                );
+            }
+
+            // Heap allocations: `Box::new`, `Vec::push`, `format!`, ...
+            if let Some(function) = allocating_function(tcx, def_id) {
+                match bang_macro_name(expression.span) {
+                    Some(name) => self.report_allocation(
+                        expression.hir_id,
+                        expression.span.source_callsite(),
+                        &format!("{name}!"),
+                    ),
+                    None => self.report_allocation(
+                        expression.hir_id,
+                        expression.span,
+                        &function,
+                    ),
+                }
+            }
+        }
+
+        walk_expr(self, expression);
+    }
+}
+
+impl<'tcx> LateLintPass<'tcx> for SecurityInterruptHandler {
+    /// Detect interrupt and exception handlers and walk their bodies for
+    /// panic-capable code and heap allocations.
+    ///
+    /// # Arguments
+    /// * `context` (`&LateContext<'tcx>`) - The lint context providing access
+    ///   to the compiler's internal state.
+    /// * `item` (`&'tcx Item<'tcx>`) - The item being checked for interrupt
+    ///   handlers.
+    fn check_item(
+        &mut self,
+        context: &LateContext<'tcx>,
+        item: &'tcx Item<'tcx>,
+    ) {
+        let ItemKind::Fn { ident, body, .. }: ItemKind<'_> = item.kind else {
+            return;
+        };
+
+        if !self.is_handler(context, item, ident.name, ident.span) {
+            return;
+        }
+
+        let mut visitor: HandlerVisitor<'_, 'tcx> = HandlerVisitor {
+            context,
+            typeck_results: context.tcx.typeck_body(body),
+            handler_span: context.tcx.def_span(item.owner_id),
+            reported: Reported::default(),
+        };
+        visitor.visit_expr(context.tcx.hir_body(body).value);
+    }
+}
+
+/// Registers the `SECURITY_INTERRUPT_PANIC` and
+/// `SECURITY_INTERRUPT_ALLOCATION` lints and their shared lint pass with the
+/// Rust compiler. The handler attributes and entry points are
+/// read from the `[interrupt_handler]` table of `dylint.toml` when the pass is
+/// created.
+///
+/// # Arguments
+/// * `session` (`&Session`) - The compiler session providing access to the
+///   compiler's internal state.
+/// * `lint_store` (`&mut LintStore`) - The lint store where the lints and
+///   their lint pass will be registered.
+#[unsafe(no_mangle)]
+pub fn register_lints(session: &Session, lint_store: &mut LintStore) {
+    dylint_linting::init_config(session);
+
+    security_utils::tool::register_lints(
+        lint_store,
+        &[SECURITY_INTERRUPT_PANIC, SECURITY_INTERRUPT_ALLOCATION],
+    );
+    lint_store.register_late_pass(|tcx: TyCtxt<'_>| {
+        Box::new(SecurityInterruptHandler::new(tcx))
+    });
+}
+
+dylint_linting::dylint_library!();
+
+/// UI tests for the `interrupt_handler` lints. The `ui` directory uses a
+/// stand-in for the `cortex-m-rt` attribute macros, while `ui_config` checks
+/// handlers registered through the `entry_points` configuration key.
+#[cfg(test)]
+mod tests {
+    use dylint_testing::ui::Test;
+
+    #[test]
+    fn ui() {
+        Test::src_base(env!("CARGO_PKG_NAME"), "ui")
+            .rustc_flags(["--edition=2024", "-Z", "ui-testing"])
+            .run();
+    }
+
+    #[test]
+    fn ui_config() {
+        Test::src_base(env!("CARGO_PKG_NAME"), "ui_config")
+            .rustc_flags(["--edition=2024", "-Z", "ui-testing"])
+            .dylint_toml(r#"interrupt_handler.entry_points = ["on_timer"]"#)
+            .run();
+    }
+}
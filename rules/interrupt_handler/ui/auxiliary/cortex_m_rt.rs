@@ -0,0 +1,45 @@
+// force-host
+// no-prefer-dynamic
+
+//! Minimal stand-in for the `cortex-m-rt` attribute macros. Like the real
+//! macros, `#[interrupt]` and `#[exception]` rename the handler with a
+//! call-site span, which is what the lint uses to recognize handlers after
+//! expansion.
+
+#![crate_type = "proc-macro"]
+
+extern crate proc_macro;
+
+use proc_macro::{Ident, Span, TokenStream, TokenTree};
+
+fn rename_handler(item: TokenStream) -> TokenStream {
+    let mut renamed: Vec<TokenTree> = Vec::new();
+    let mut after_fn: bool = false;
+
+    for token in item {
+        match token {
+            TokenTree::Ident(ident) if after_fn => {
+                let name: String = format!("__cortex_m_rt_{ident}");
+                renamed.push(TokenTree::Ident(Ident::new(&name, Span::call_site())));
+                after_fn = false;
+            },
+            TokenTree::Ident(ident) => {
+                after_fn = ident.to_string() == "fn";
+                renamed.push(TokenTree::Ident(ident));
+            },
+            other => renamed.push(other),
+        }
+    }
+
+    renamed.into_iter().collect()
+}
+
+#[proc_macro_attribute]
+pub fn interrupt(_: TokenStream, item: TokenStream) -> TokenStream {
+    rename_handler(item)
+}
+
+#[proc_macro_attribute]
+pub fn exception(_: TokenStream, item: TokenStream) -> TokenStream {
+    rename_handler(item)
+}
@@ -0,0 +1,56 @@
+// aux-build:cortex_m_rt.rs
+#![warn(security_interrupt_panic, security_interrupt_allocation)]
+
+extern crate cortex_m_rt;
+
+use cortex_m_rt::{exception, interrupt};
+
+static BUFFER: [u8; 4] = [0; 4];
+
+#[interrupt]
+fn USART1() {
+    let byte: Option<u8> = BUFFER.first().copied();
+    let _: u8 = byte.unwrap(); // Should trigger.
+
+    let index: usize = 2;
+    let _: u8 = BUFFER[index]; // Should trigger.
+
+    if index > 3 {
+        panic!("unexpected index"); // Should trigger.
+    }
+
+    let check = || assert!(index < 4); // Should trigger (closure in handler).
+    check();
+}
+
+#[interrupt]
+fn TIMER0() {
+    let _: Box<u32> = Box::new(1); // Should trigger.
+
+    let mut samples: Vec<u8> = Vec::new(); // Should not trigger.
+    samples.push(1); // Should trigger.
+
+    let _: String = format!("sample {}", samples.len()); // Should trigger.
+    let _: Vec<u8> = vec![0; 8]; // Should trigger.
+}
+
+#[exception]
+fn SysTick() {
+    let _: u8 = BUFFER.get(1).copied().unwrap_or_default(); // Should not trigger.
+    let _: bool = std::thread::panicking(); // Should not trigger: a query.
+
+    #[expect(security_interrupt_panic, reason = "`BUFFER` has 4 elements")]
+    let _: u8 = BUFFER[3]; // Should not trigger (fulfilled expectation).
+
+    #[expect(security_interrupt_allocation)]
+    let _: usize = BUFFER.len(); // Should trigger (unfulfilled expectation).
+}
+
+/// Regular functions are not interrupt handlers, so panic-capable code here
+/// should not trigger the `SECURITY_INTERRUPT_PANIC` and
+/// `SECURITY_INTERRUPT_ALLOCATION` lints.
+fn main() {
+    let _: u8 = BUFFER[0]; // Should not trigger.
+    let _: u8 = BUFFER.first().copied().unwrap(); // Should not trigger.
+    let _: Box<u32> = Box::new(1); // Should not trigger.
+}
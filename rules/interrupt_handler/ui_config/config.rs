@@ -0,0 +1,14 @@
+#![warn(security_interrupt_panic)]
+
+static COUNTERS: [u32; 2] = [0; 2];
+
+#[unsafe(no_mangle)]
+extern "C" fn on_timer() {
+    let _: u32 = COUNTERS[1]; // Should trigger (configured entry point).
+}
+
+/// The `main` function is not a configured entry point, so indexing here
+/// should not trigger the `SECURITY_INTERRUPT_PANIC` lint.
+fn main() {
+    let _: u32 = COUNTERS[0]; // Should not trigger.
+}
@@ -0,0 +1,174 @@
+#![feature(rustc_private)]
+
+extern crate rustc_errors;
+extern crate rustc_hir;
+extern crate rustc_lint;
+extern crate rustc_middle;
+extern crate rustc_session;
+extern crate rustc_span;
+
+use rustc_errors::Diag;
+use rustc_hir::{
+    HirId,
+    Path,
+    PathSegment,
+    def::{DefKind, Res},
+    def_id::DefId,
+};
+use rustc_lint::{LateContext, LateLintPass, LintStore};
+use rustc_middle::ty::TyCtxt;
+use rustc_session::{Session, declare_lint, declare_lint_pass};
+use rustc_span::{Ident, Symbol, sym};
+
+declare_lint! {
+    pub SECURITY_INTRINSICS_USAGE,
+    Deny,
+    "Detects direct usage of the `core::intrinsics` and `std::intrinsics` modules."
+}
+
+declare_lint_pass!(SecurityIntrinsicsUsage => [SECURITY_INTRINSICS_USAGE]);
+
+/// Returns `true` if a path segment named `ident` and resolved to `res`
+/// names the `intrinsics` module of `core` or `std`.
+///
+/// # Arguments
+/// * `tcx` (`TyCtxt<'_>`) - The type context used to resolve the module.
+/// * `ident` (`Ident`) - The identifier of the segment.
+/// * `res` (`Res`) - The resolution of the segment.
+fn is_intrinsics_module(tcx: TyCtxt<'_>, ident: Ident, res: Res) -> bool {
+    if ident.name.as_str() != "intrinsics" {
+        return false;
+    }
+
+    let Some(def_id): Option<DefId> = res.opt_def_id() else {
+        return false;
+    };
+    let krate: Symbol = tcx.crate_name(def_id.krate);
+
+    tcx.def_kind(def_id) == DefKind::Mod
+        && (krate == sym::core || krate == sym::std)
+}
+
+/// Returns the stable counterpart of the intrinsic `name`, if any.
+///
+/// # Arguments
+/// * `name` (`&str`) - The name of the intrinsic.
+fn stable_alternative(name: &str) -> Option<&'static str> {
+    match name {
+        "transmute" => Some("core::mem::transmute"),
+        "copy" => Some("core::ptr::copy"),
+        "copy_nonoverlapping" => Some("core::ptr::copy_nonoverlapping"),
+        "write_bytes" => Some("core::ptr::write_bytes"),
+        "assume" => Some("core::hint::assert_unchecked"),
+        "unreachable" => Some("core::hint::unreachable_unchecked"),
+        "abort" => Some("std::process::abort"),
+        "size_of" => Some("core::mem::size_of"),
+        "min_align_of" => Some("core::mem::align_of"),
+        "needs_drop" => Some("core::mem::needs_drop"),
+        _ => None,
+    }
+}
+
+impl<'tcx> LateLintPass<'tcx> for SecurityIntrinsicsUsage {
+    /// Checks every path, including the paths of `use` items, and reports
+    /// those going through the `intrinsics` module of `core` or `std`.
+    /// Stable re-exports such as `core::mem::transmute` resolve to the same
+    /// intrinsics but are not reported, as only the written path is
+    /// inspected.
+    ///
+    /// # Arguments
+    /// * `context` (`&LateContext<'tcx>`) - The lint context.
+    /// * `path` (`&Path<'tcx>`) - The path to check.
+    /// * `_` (`HirId`) - The HIR identifier of the path's owner node.
+    fn check_path(
+        &mut self,
+        context: &LateContext<'tcx>,
+        path: &Path<'tcx>,
+        _: HirId,
+    ) {
+        if path.span.from_expansion() {
+            return;
+        }
+
+        // The last segment of a `use` path is resolved through the path
+        // itself, e.g. for `use core::intrinsics;`.
+        let through_intrinsics: bool =
+            path.segments.iter().any(|segment: &PathSegment<'_>| {
+                is_intrinsics_module(context.tcx, segment.ident, segment.res)
+            }) || path.segments.last().is_some_and(
+                |segment: &PathSegment<'_>| {
+                    is_intrinsics_module(context.tcx, segment.ident, path.res)
+                },
+            );
+        if !through_intrinsics {
+            return;
+        }
+
+        let written: String = path
+            .segments
+            .iter()
+            .map(|segment: &PathSegment<'_>| segment.ident.to_string())
+            .collect::<Vec<String>>()
+            .join("::");
+        let alternative: Option<&str> =
+            path.segments.last().and_then(|segment: &PathSegment<'_>| {
+                stable_alternative(segment.ident.name.as_str())
+            });
+
+        security_utils::span_lint(
+            context,
+            SECURITY_INTRINSICS_USAGE,
+            path.span,
+            |diagnostic: &mut Diag<'_, ()>| {
+                diagnostic.primary_message(format!(
+                    "Usage of compiler intrinsics through `{written}` detected."
+                ));
+                diagnostic.note(
+                    "intrinsics bypass the stable unsafe API and its \
+                     documented safety contracts",
+                );
+                match alternative {
+                    Some(alternative) => {
+                        diagnostic
+                            .help(format!("use `{alternative}` instead"));
+                    },
+                    None => {
+                        diagnostic.help(
+                            "use the stable wrappers of `core::mem`, \
+                             `core::ptr` or `core::hint` instead",
+                        );
+                    },
+                }
+            },
+        );
+    }
+}
+
+#[unsafe(no_mangle)]
+pub fn register_lints(session: &Session, lint_store: &mut LintStore) {
+    dylint_linting::init_config(session);
+
+    security_utils::tool::register_lints(
+        lint_store,
+        &[SECURITY_INTRINSICS_USAGE],
+    );
+    lint_store
+        .register_late_pass(|_: TyCtxt<'_>| Box::new(SecurityIntrinsicsUsage));
+}
+
+dylint_linting::dylint_library!();
+
+/// UI tests for the `SECURITY_INTRINSICS_USAGE` lint. The tests check that
+/// paths and imports going through `core::intrinsics` or `std::intrinsics`
+/// are reported, while the stable wrappers are not.
+#[cfg(test)]
+mod tests {
+    use dylint_testing::ui::Test;
+
+    #[test]
+    fn ui() {
+        Test::src_base(env!("CARGO_PKG_NAME"), "ui")
+            .rustc_flags(["--edition=2024", "-Z", "ui-testing"])
+            .run();
+    }
+}
@@ -0,0 +1,28 @@
+#![feature(core_intrinsics)]
+#![allow(internal_features)]
+
+use core::intrinsics;
+use std::intrinsics::copy_nonoverlapping;
+
+#[repr(transparent)]
+struct Wrapper(u32);
+
+fn main() {
+    let value: u32 = 1;
+    let source: [u8; 4] = [1, 2, 3, 4];
+    let mut bytes: [u8; 4] = [0; 4];
+
+    // Should trigger the lint: intrinsics paths.
+    let _: Wrapper = unsafe { std::intrinsics::transmute::<u32, Wrapper>(value) };
+    unsafe { intrinsics::assume(value == 1) };
+    unsafe { core::intrinsics::copy(source.as_ptr(), bytes.as_mut_ptr(), 4) };
+    let _: bool = core::intrinsics::likely(value == 1);
+
+    // Should not trigger the lint: the import is reported instead.
+    unsafe { copy_nonoverlapping(source.as_ptr(), bytes.as_mut_ptr(), 4) };
+
+    // Should not trigger the lint: stable wrappers.
+    let _: Wrapper = unsafe { std::mem::transmute::<u32, Wrapper>(value) };
+    unsafe { core::ptr::copy(source.as_ptr(), bytes.as_mut_ptr(), 4) };
+    unsafe { core::hint::assert_unchecked(value == 1) };
+}
@@ -0,0 +1,432 @@
+#![feature(rustc_private)]
+
+extern crate rustc_errors;
+extern crate rustc_hir;
+extern crate rustc_lint;
+extern crate rustc_middle;
+extern crate rustc_session;
+extern crate rustc_span;
+
+use rustc_errors::Diag;
+use rustc_hir::{
+    BinOpKind,
+    Body,
+    Expr,
+    ExprKind,
+    HirId,
+    Node,
+    QPath,
+    UnOp,
+    def::{DefKind, Res},
+    def_id::DefId,
+    intravisit::{Visitor, walk_expr},
+};
+use rustc_lint::{LateContext, LateLintPass, LintStore};
+use rustc_middle::ty::{self, Ty, TyCtxt};
+use rustc_session::{Session, declare_lint, declare_lint_pass};
+use rustc_span::{Span, Symbol, sym};
+
+declare_lint! {
+    pub SECURITY_ZIP_TRUNCATION,
+    Warn,
+    "Detects `zip` over inputs whose lengths are not known to be equal."
+}
+
+declare_lint_pass!(SecurityIteratorUsage => [SECURITY_ZIP_TRUNCATION]);
+
+/// Iterator types that never run out of elements, whose truncation by `zip`
+/// is the point of zipping them.
+const INFINITE_ITERATORS: [&str; 4] =
+    ["RangeFrom", "Repeat", "RepeatWith", "Cycle"];
+
+/// Methods whose result has as many elements as their receiver, through
+/// which an input of `zip` or a local initialized from another place keeps
+/// the length of that place, e.g. `users.iter().map(..)` or
+/// `let names: Vec<String> = users.iter().map(..).collect()`.
+const LENGTH_PRESERVING: [&str; 11] = [
+    "iter",
+    "iter_mut",
+    "into_iter",
+    "map",
+    "cloned",
+    "copied",
+    "enumerate",
+    "rev",
+    "collect",
+    "to_vec",
+    "clone",
+];
+
+/// The place an iterator is built from: a local or a path, followed by the
+/// fields accessed on it, e.g. `self.keys` in `self.keys.iter()`.
+#[derive(Debug, PartialEq)]
+struct Source {
+    res: Res,
+    fields: Vec<Symbol>,
+}
+
+/// Returns the place `expression` iterates over, peeling length-preserving
+/// method calls (`a.iter().copied()`), references and dereferences. A local
+/// initialized from another place through length-preserving methods only is
+/// replaced by that place. Returns `None` when the input is built some other
+/// way, e.g. by a function call, an index or a method changing the length
+/// such as `chunks(2)`, `step_by(2)` or `skip(1)`.
+///
+/// # Arguments
+/// * `context` (`&LateContext<'_>`) - The lint context used to find the
+///   initializers of locals.
+/// * `expression` (`&Expr<'_>`) - The receiver or argument of `zip`.
+fn source(context: &LateContext<'_>, expression: &Expr<'_>) -> Option<Source> {
+    let mut fields: Vec<Symbol> = Vec::new();
+    let mut current: &Expr<'_> = expression;
+
+    loop {
+        match current.kind {
+            ExprKind::MethodCall(segment, receiver, _, _)
+                if LENGTH_PRESERVING
+                    .contains(&segment.ident.name.as_str()) =>
+            {
+                current = receiver;
+            },
+            ExprKind::AddrOf(_, _, inner)
+            | ExprKind::Unary(UnOp::Deref, inner) => {
+                current = inner;
+            },
+            ExprKind::Field(base, field) => {
+                fields.push(field.name);
+                current = base;
+            },
+            ExprKind::Path(QPath::Resolved(None, path)) => {
+                if fields.is_empty()
+                    && let Res::Local(binding) = path.res
+                    && let Some(origin) = derived_from(context, binding)
+                {
+                    return Some(origin);
+                }
+
+                fields.reverse();
+                return Some(Source { res: path.res, fields });
+            },
+            _ => return None,
+        }
+    }
+}
+
+/// Returns the place the local `binding` is derived from, if it is
+/// initialized by a chain of length-preserving methods on that place.
+///
+/// # Arguments
+/// * `context` (`&LateContext<'_>`) - The lint context used to find the
+///   initializer.
+/// * `binding` (`HirId`) - The binding of the local.
+fn derived_from(context: &LateContext<'_>, binding: HirId) -> Option<Source> {
+    let Node::LetStmt(local): Node<'_> = context.tcx.parent_hir_node(binding)
+    else {
+        return None;
+    };
+    let init: &Expr<'_> = local.init?;
+
+    let mut current: &Expr<'_> = init;
+    while let ExprKind::MethodCall(segment, receiver, _, _) = current.kind {
+        if !LENGTH_PRESERVING.contains(&segment.ident.name.as_str()) {
+            return None;
+        }
+        current = receiver;
+    }
+
+    (!core::ptr::eq(current, init)).then(|| source(context, current)).flatten()
+}
+
+/// Returns the place whose length `expression` reads, if it is a call to a
+/// method named `len`.
+///
+/// # Arguments
+/// * `context` (`&LateContext<'_>`) - The lint context.
+/// * `expression` (`&Expr<'_>`) - The expression to check.
+fn length_of(
+    context: &LateContext<'_>,
+    expression: &Expr<'_>,
+) -> Option<Source> {
+    let mut current: &Expr<'_> = expression;
+    while let ExprKind::AddrOf(_, _, inner) = current.kind {
+        current = inner;
+    }
+
+    match current.kind {
+        ExprKind::MethodCall(segment, receiver, [], _)
+            if segment.ident.name.as_str() == "len" =>
+        {
+            source(context, receiver)
+        },
+        _ => None,
+    }
+}
+
+/// Visitor looking for a comparison between the lengths of two sources
+/// before a given position, such as `assert_eq!(a.len(), b.len())` or
+/// `if a.len() != b.len() { return ... }`.
+struct LengthCheckFinder<'a, 'tcx> {
+    context: &'a LateContext<'tcx>,
+    left: &'a Source,
+    right: &'a Source,
+    before: Span,
+    found: bool,
+}
+
+impl LengthCheckFinder<'_, '_> {
+    /// Returns `true` if `first` and `second` read the lengths of the two
+    /// sources, in either order.
+    ///
+    /// # Arguments
+    /// * `first` (`&Expr<'_>`) - The first operand of the comparison.
+    /// * `second` (`&Expr<'_>`) - The second operand of the comparison.
+    fn compares_lengths(&self, first: &Expr<'_>, second: &Expr<'_>) -> bool {
+        let (Some(first), Some(second)): (Option<Source>, Option<Source>) =
+            (length_of(self.context, first), length_of(self.context, second))
+        else {
+            return false;
+        };
+
+        (&first == self.left && &second == self.right)
+            || (&first == self.right && &second == self.left)
+    }
+}
+
+impl<'tcx> Visitor<'tcx> for LengthCheckFinder<'_, 'tcx> {
+    fn visit_expr(&mut self, expression: &'tcx Expr<'tcx>) {
+        // Spans of macro expansions, e.g. of `assert_eq!`, point into the
+        // macro definition.
+        if self.found
+            || expression.span.source_callsite().lo() >= self.before.lo()
+        {
+            return;
+        }
+
+        let compares: bool = match expression.kind {
+            ExprKind::Binary(operator, first, second) => {
+                matches!(
+                    operator.node,
+                    BinOpKind::Eq
+                        | BinOpKind::Ne
+                        | BinOpKind::Lt
+                        | BinOpKind::Le
+                        | BinOpKind::Gt
+                        | BinOpKind::Ge
+                ) && self.compares_lengths(first, second)
+            },
+            // `assert_eq!(a, b)` expands to `match (&a, &b) { ... }`.
+            ExprKind::Tup([first, second]) => {
+                self.compares_lengths(first, second)
+            },
+            _ => false,
+        };
+        if compares {
+            self.found = true;
+            return;
+        }
+
+        walk_expr(self, expression);
+    }
+}
+
+/// Returns the length of `ty` if it is an array (or a reference to one)
+/// with a known length.
+///
+/// # Arguments
+/// * `tcx` (`TyCtxt<'tcx>`) - The type context used to evaluate the length.
+/// * `ty` (`Ty<'tcx>`) - The type to check.
+fn array_length<'tcx>(tcx: TyCtxt<'tcx>, ty: Ty<'tcx>) -> Option<u64> {
+    match ty.peel_refs().kind() {
+        ty::Array(_, length) => length.try_to_target_usize(tcx),
+        _ => None,
+    }
+}
+
+/// Returns `true` if `ty` is an iterator that never ends, e.g. `0..` or
+/// `iter::repeat(x)`.
+///
+/// # Arguments
+/// * `tcx` (`TyCtxt<'_>`) - The type context used to name the type.
+/// * `ty` (`Ty<'_>`) - The type of the receiver or argument of `zip`.
+fn is_infinite(tcx: TyCtxt<'_>, ty: Ty<'_>) -> bool {
+    ty.peel_refs().ty_adt_def().is_some_and(|adt: ty::AdtDef<'_>| {
+        tcx.crate_name(adt.did().krate) == sym::core
+            && INFINITE_ITERATORS.contains(&tcx.item_name(adt.did()).as_str())
+    })
+}
+
+/// Returns the place at the root of the method calls of `expression`, e.g.
+/// `a` in `a.iter().skip(1)`, to read its type.
+///
+/// # Arguments
+/// * `expression` (`&'tcx Expr<'tcx>`) - The receiver or argument of `zip`.
+fn peel_to_base<'tcx>(expression: &'tcx Expr<'tcx>) -> &'tcx Expr<'tcx> {
+    let mut current: &Expr<'_> = expression;
+    while let ExprKind::MethodCall(_, receiver, _, _) = current.kind {
+        current = receiver;
+    }
+
+    current
+}
+
+/// Returns the two inputs of `expression` if it is `a.zip(b)` with
+/// `Iterator::zip` or `iter::zip(a, b)`.
+///
+/// # Arguments
+/// * `context` (`&LateContext<'tcx>`) - The lint context used to resolve the
+///   called function.
+/// * `expression` (`&'tcx Expr<'tcx>`) - The expression to check.
+fn zip_inputs<'tcx>(
+    context: &LateContext<'tcx>,
+    expression: &'tcx Expr<'tcx>,
+) -> Option<(&'tcx Expr<'tcx>, &'tcx Expr<'tcx>)> {
+    match expression.kind {
+        ExprKind::MethodCall(segment, receiver, [argument], _)
+            if segment.ident.name.as_str() == "zip" =>
+        {
+            let def_id: DefId = context
+                .typeck_results()
+                .type_dependent_def_id(expression.hir_id)?;
+            let iterator: DefId = context.tcx.trait_of_assoc(def_id)?;
+
+            context
+                .tcx
+                .is_diagnostic_item(sym::Iterator, iterator)
+                .then_some((receiver, argument))
+        },
+        ExprKind::Call(callee, [first, second]) => {
+            let ExprKind::Path(qpath): &ExprKind<'_> = &callee.kind else {
+                return None;
+            };
+            let def_id: DefId =
+                context.qpath_res(qpath, callee.hir_id).opt_def_id()?;
+
+            (context.tcx.def_kind(def_id) == DefKind::Fn
+                && context.tcx.crate_name(def_id.krate) == sym::core
+                && context.tcx.item_name(def_id).as_str() == "zip")
+                .then_some((first, second))
+        },
+        _ => None,
+    }
+}
+
+impl<'tcx> LateLintPass<'tcx> for SecurityIteratorUsage {
+    /// Reports `zip` calls whose inputs may have different lengths. Inputs
+    /// are considered equally long when they iterate over the same place,
+    /// are arrays of the same length, or have their lengths compared
+    /// earlier in the enclosing body; zipping with an infinite iterator is
+    /// never reported.
+    ///
+    /// # Arguments
+    /// * `context` (`&LateContext<'tcx>`) - The lint context.
+    /// * `expression` (`&'tcx Expr<'tcx>`) - The expression to check.
+    fn check_expr(
+        &mut self,
+        context: &LateContext<'tcx>,
+        expression: &'tcx Expr<'tcx>,
+    ) {
+        if expression.span.from_expansion() {
+            return;
+        }
+        let Some((left, right)): Option<(&Expr<'_>, &Expr<'_>)> =
+            zip_inputs(context, expression)
+        else {
+            return;
+        };
+
+        let left_ty: Ty<'tcx> = context.typeck_results().expr_ty(left);
+        let right_ty: Ty<'tcx> = context.typeck_results().expr_ty(right);
+        if is_infinite(context.tcx, left_ty)
+            || is_infinite(context.tcx, right_ty)
+        {
+            return;
+        }
+
+        let left_source: Option<Source> = source(context, left);
+        let right_source: Option<Source> = source(context, right);
+        if let (Some(left_source), Some(right_source)) =
+            (&left_source, &right_source)
+        {
+            if left_source == right_source {
+                return;
+            }
+
+            let typeck: &ty::TypeckResults<'tcx> = context.typeck_results();
+            let lengths: (Option<u64>, Option<u64>) = (
+                array_length(context.tcx, typeck.expr_ty(peel_to_base(left))),
+                array_length(context.tcx, typeck.expr_ty(peel_to_base(right))),
+            );
+            if let (Some(left_length), Some(right_length)) = lengths
+                && left_length == right_length
+            {
+                return;
+            }
+
+            if let Some(body_id) = context.enclosing_body {
+                let body: &Body<'_> = context.tcx.hir_body(body_id);
+                let mut finder: LengthCheckFinder<'_, 'tcx> =
+                    LengthCheckFinder {
+                        context,
+                        left: left_source,
+                        right: right_source,
+                        before: expression.span,
+                        found: false,
+                    };
+                finder.visit_expr(body.value);
+                if finder.found {
+                    return;
+                }
+            }
+        }
+
+        security_utils::span_lint(
+            context,
+            SECURITY_ZIP_TRUNCATION,
+            expression.span,
+            |diagnostic: &mut Diag<'_, ()>| {
+                diagnostic.primary_message(
+                    "`zip` over inputs of possibly different lengths detected.",
+                );
+                diagnostic.note(
+                    "`zip` stops at the end of the shorter input and silently \
+                     drops the remaining elements of the other one",
+                );
+                diagnostic.help(
+                    "check that the lengths are equal before zipping (e.g. \
+                     `assert_eq!(a.len(), b.len())`), or use \
+                     `itertools::zip_eq`",
+                );
+            },
+        );
+    }
+}
+
+#[unsafe(no_mangle)]
+pub fn register_lints(session: &Session, lint_store: &mut LintStore) {
+    dylint_linting::init_config(session);
+
+    security_utils::tool::register_lints(
+        lint_store,
+        &[SECURITY_ZIP_TRUNCATION],
+    );
+    lint_store
+        .register_late_pass(|_: TyCtxt<'_>| Box::new(SecurityIteratorUsage));
+}
+
+dylint_linting::dylint_library!();
+
+/// UI tests for the `SECURITY_ZIP_TRUNCATION` lint. The tests check that
+/// `zip` over unrelated inputs is reported, while inputs sharing a source,
+/// arrays of the same length, inputs whose lengths are compared beforehand
+/// and infinite iterators are not.
+#[cfg(test)]
+mod tests {
+    use dylint_testing::ui::Test;
+
+    #[test]
+    fn ui() {
+        Test::src_base(env!("CARGO_PKG_NAME"), "ui")
+            .rustc_flags(["--edition=2024", "-Z", "ui-testing"])
+            .run();
+    }
+}
@@ -0,0 +1,53 @@
+use std::iter;
+
+struct Table {
+    keys: Vec<u32>,
+    values: Vec<u32>,
+}
+
+fn checked(keys: &[u32], values: &[u32]) -> u32 {
+    assert_eq!(keys.len(), values.len());
+    keys.iter().zip(values.iter()).map(|(key, value): (&u32, &u32)| key + value).sum()
+}
+
+fn early_return(keys: &[u32], values: &[u32]) -> u32 {
+    if keys.len() != values.len() {
+        return 0;
+    }
+    keys.iter().zip(values).map(|(key, value): (&u32, &u32)| key * value).sum()
+}
+
+fn main() {
+    let keys: Vec<u32> = vec![1, 2, 3];
+    let values: Vec<u32> = vec![4, 5];
+    let table: Table = Table { keys: keys.clone(), values: values.clone() };
+
+    // Should trigger the lint: unrelated inputs.
+    let _: Vec<(&u32, &u32)> = keys.iter().zip(values.iter()).collect();
+    let _: Vec<(&u32, &u32)> = iter::zip(&keys, &values).collect();
+    let _: Vec<(&u32, &u32)> = table.keys.iter().zip(&table.values).collect();
+
+    // Should trigger the lint: same source, but one input is shortened.
+    let _: Vec<(&u32, &[u32])> = keys.iter().zip(keys.chunks(2)).collect();
+    let _: Vec<(&u32, &u32)> = keys.iter().zip(keys.iter().skip(1)).collect();
+
+    // Should not trigger the lint: same source.
+    let _: Vec<(&u32, u32)> = keys.iter().zip(keys.iter().copied()).collect();
+
+    // Should not trigger the lint: derived from the same source.
+    let doubled: Vec<u32> = keys.iter().map(|key: &u32| key * 2).collect();
+    let _: Vec<(&u32, &u32)> = keys.iter().zip(&doubled).collect();
+
+    // Should not trigger the lint: arrays of the same length.
+    let left: [u8; 4] = [1, 2, 3, 4];
+    let right: [u8; 4] = [5, 6, 7, 8];
+    let _: Vec<(&u8, &u8)> = left.iter().zip(right.iter()).collect();
+
+    // Should not trigger the lint: infinite iterators.
+    let _: Vec<(usize, &u32)> = (0..).zip(keys.iter()).collect();
+    let _: Vec<(&u32, u32)> = keys.iter().zip(iter::repeat(0)).collect();
+
+    // Should not trigger the lint: lengths compared beforehand.
+    let _: u32 = checked(&keys, &values);
+    let _: u32 = early_return(&keys, &values);
+}
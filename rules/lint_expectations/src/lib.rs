@@ -0,0 +1,184 @@
+#![feature(rustc_private)]
+
+extern crate rustc_ast;
+extern crate rustc_errors;
+extern crate rustc_hir;
+extern crate rustc_lint;
+extern crate rustc_middle;
+extern crate rustc_session;
+extern crate rustc_span;
+
+use rustc_ast::{MetaItemInner, PathSegment};
+use rustc_errors::Diag;
+use rustc_hir::{Attribute, HirId};
+use rustc_lint::{LateContext, LateLintPass, LintStore, unerased_lint_store};
+use rustc_middle::{lint::LintExpectation, ty::TyCtxt};
+use rustc_session::{
+    Session,
+    declare_lint,
+    declare_lint_pass,
+    lint::LintExpectationId,
+};
+use rustc_span::Symbol;
+
+declare_lint! {
+    pub SECURITY_LINT_EXPECTATION,
+    Allow,
+    "Reports every `#[expect(...)]` attribute of the crate, to inventory waivers."
+}
+
+declare_lint! {
+    pub SECURITY_SUPPRESSED_FINDING,
+    Allow,
+    "Reports every finding of a security lint that was suppressed, and how."
+}
+
+declare_lint_pass!(SecurityLintExpectations => [SECURITY_LINT_EXPECTATION]);
+
+/// Returns the node carrying the expectation `id` and the name of the lint it
+/// expects, e.g. `security_panic_usage` for the first expectation of
+/// `#[expect(security_panic_usage, security_indexing_usage)]`.
+///
+/// # Arguments
+/// * `tcx` (`TyCtxt<'_>`) - The type context used to read the attributes.
+/// * `id` (`&LintExpectationId`) - The expectation.
+fn expected_lint(
+    tcx: TyCtxt<'_>,
+    id: &LintExpectationId,
+) -> Option<(HirId, Symbol)> {
+    let LintExpectationId::Stable {
+        hir_id,
+        attr_index,
+        lint_index: Some(lint_index),
+    }: LintExpectationId = *id
+    else {
+        return None;
+    };
+
+    let attribute: &Attribute =
+        tcx.hir_attrs(hir_id).get(usize::from(attr_index))?;
+    let path: String = attribute
+        .meta_item_list()?
+        .get(usize::from(lint_index))
+        .and_then(MetaItemInner::meta_item)?
+        .path
+        .segments
+        .iter()
+        .map(|segment: &PathSegment| segment.ident.as_str())
+        .collect::<Vec<&str>>()
+        .join("::");
+
+    Some((hir_id, Symbol::intern(&flat_name(tcx, path))))
+}
+
+/// Returns the flat name of the lint `path`, e.g. `security_panic_usage` for
+/// `security::panic_usage`, so that expectations are reported under the same
+/// name whichever form the attribute uses. Other paths are returned as is.
+///
+/// # Arguments
+/// * `tcx` (`TyCtxt<'_>`) - The type context used to look up the lint.
+/// * `path` (`String`) - The lint path written in the attribute.
+fn flat_name(tcx: TyCtxt<'_>, path: String) -> String {
+    let is_scoped: bool = path
+        .strip_prefix(security_utils::tool::TOOL)
+        .is_some_and(|rest: &str| rest.starts_with("::"));
+    if !is_scoped {
+        return path;
+    }
+
+    match unerased_lint_store(tcx.sess).find_lints(&path) {
+        Some([lint]) => lint.lint.name_lower(),
+        _ => path,
+    }
+}
+
+impl<'tcx> LateLintPass<'tcx> for SecurityLintExpectations {
+    /// Reports each expectation registered in the crate at its attribute,
+    /// with its reason. The expectations themselves are checked by rustc,
+    /// which reports the unfulfilled ones.
+    ///
+    /// # Arguments
+    /// * `context` (`&LateContext<'tcx>`) - The lint context providing access
+    ///   to the compiler's internal state.
+    fn check_crate(&mut self, context: &LateContext<'tcx>) {
+        for (id, expectation) in context.tcx.lint_expectations(()) {
+            let Some((node, lint)): Option<(HirId, Symbol)> =
+                expected_lint(context.tcx, id)
+            else {
+                continue;
+            };
+
+            report_expectation(context, node, lint, expectation);
+        }
+    }
+}
+
+/// Emits a `SECURITY_LINT_EXPECTATION` finding for `expectation`.
+///
+/// # Arguments
+/// * `context` (`&LateContext<'_>`) - The lint context used to emit the
+///   diagnostic.
+/// * `node` (`HirId`) - The node carrying the attribute.
+/// * `lint` (`Symbol`) - The name of the expected lint.
+/// * `expectation` (`&LintExpectation`) - The expectation.
+fn report_expectation(
+    context: &LateContext<'_>,
+    node: HirId,
+    lint: Symbol,
+    expectation: &LintExpectation,
+) {
+    security_utils::span_lint_hir(
+        context,
+        SECURITY_LINT_EXPECTATION,
+        node,
+        expectation.emission_span,
+        |diagnostic: &mut Diag<'_, ()>| {
+            diagnostic
+                .primary_message(format!("Expectation of `{lint}` detected."));
+            match expectation.reason {
+                Some(reason) => diagnostic.note(format!("reason: {reason}")),
+                None => diagnostic
+                    .help("document the waiver with `reason = \"...\"`"),
+            };
+        },
+    );
+}
+
+/// Registers the `SECURITY_LINT_EXPECTATION` lint and its corresponding lint
+/// pass, and the `SECURITY_SUPPRESSED_FINDING` lint, with the Rust compiler.
+///
+/// # Arguments
+/// * `session` (`&Session`) - The compiler session providing access to the
+///   compiler's internal state.
+/// * `lint_store` (`&mut LintStore`) - The lint store where the
+///   `SECURITY_LINT_EXPECTATION` lint and its corresponding lint pass will be
+///   registered.
+#[unsafe(no_mangle)]
+pub fn register_lints(session: &Session, lint_store: &mut LintStore) {
+    dylint_linting::init_config(session);
+
+    security_utils::tool::register_lints(
+        lint_store,
+        &[SECURITY_LINT_EXPECTATION, SECURITY_SUPPRESSED_FINDING],
+    );
+    lint_store.register_late_pass(|_: TyCtxt<'_>| {
+        Box::new(SecurityLintExpectations)
+    });
+}
+
+dylint_linting::dylint_library!();
+
+/// UI tests for the `SECURITY_LINT_EXPECTATION` lint. The tests check that
+/// every lint named by an `#[expect(...)]` attribute is reported once, with
+/// its reason, whether the expectation is fulfilled or not.
+#[cfg(test)]
+mod tests {
+    use dylint_testing::ui::Test;
+
+    #[test]
+    fn ui() {
+        Test::src_base(env!("CARGO_PKG_NAME"), "ui")
+            .rustc_flags(["--edition=2024", "-Z", "ui-testing"])
+            .run();
+    }
+}
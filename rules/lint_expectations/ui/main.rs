@@ -0,0 +1,17 @@
+#![warn(security_lint_expectation)]
+#![expect(dead_code, reason = "fixtures are never called")] // Should trigger.
+
+fn fixture() {}
+
+#[expect(unused_variables, unused_mut)] // Should trigger twice.
+fn main() {
+    let mut unused: i32 = 1;
+
+    #[expect(unused_assignments, reason = "overwritten below")] // Should trigger.
+    let mut value: i32 = 1;
+    value = 2;
+    let _: i32 = value;
+
+    #[allow(unused_variables)] // Should not trigger.
+    let ignored: i32 = 3;
+}
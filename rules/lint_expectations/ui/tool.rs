@@ -0,0 +1,6 @@
+#![feature(register_tool)]
+#![register_tool(security)]
+#![warn(security_lint_expectation)]
+
+#[expect(security::suppressed_finding, reason = "tool-scoped name")] // Should trigger.
+fn main() {}
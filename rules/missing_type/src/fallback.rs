@@ -0,0 +1,293 @@
+//! Detection of the let bindings typed by the integer and float fallback.
+//!
+//! An unsuffixed numeric literal whose type is not constrained by its uses
+//! is typed `i32` or `f64` by the compiler, without any diagnostic:
+//!
+//! ```rust
+//! let mut count = 0; // `i32`, which overflows past `i32::MAX` values.
+//! for _ in values {
+//!     count += 1;
+//! }
+//! println!("{count}");
+//! ```
+//!
+//! Which default was applied is not recorded in the inference results, so
+//! [`is_fallback`] reconstructs it: the binding has an `i32` or `f64` type,
+//! its initializer is built from unsuffixed literals only, and none of its
+//! uses constrains its type, e.g. as the argument of a function taking a
+//! concrete type or as an operand of an annotated value.
+
+use std::collections::HashSet;
+
+use rustc_ast::{LitFloatType, LitIntType, LitKind};
+use rustc_hir::{
+    BindingMode,
+    BodyId,
+    Expr,
+    ExprKind,
+    HirId,
+    LetStmt,
+    Node,
+    QPath,
+    def::Res,
+    def_id::DefId,
+    intravisit::{Visitor, walk_expr},
+};
+use rustc_lint::LateContext;
+use rustc_middle::ty::{self, FloatTy, IntTy, Ty, TypeVisitableExt};
+use rustc_span::{Span, symbol::Ident};
+
+/// Returns `true` if the type of the let binding `local` was determined by
+/// the `i32` or `f64` fallback rather than by its initializer or its uses.
+///
+/// # Arguments
+/// * `context` (`&LateContext<'_>`) - The lint context used to read the
+///   inferred types.
+/// * `body_id` (`BodyId`) - The body containing the binding.
+/// * `local` (`&LetStmt<'_>`) - The let binding missing a type annotation.
+pub fn is_fallback(
+    context: &LateContext<'_>,
+    body_id: BodyId,
+    local: &LetStmt<'_>,
+) -> bool {
+    let Some(init): Option<&Expr<'_>> = local.init else {
+        return false;
+    };
+    if !has_fallback_type(context.typeck_results().pat_ty(local.pat))
+        || !is_unconstrained(init)
+    {
+        return false;
+    }
+
+    let mut bindings: HashSet<HirId> = HashSet::new();
+    local.pat.each_binding(|_: BindingMode, id: HirId, _: Span, _: Ident| {
+        bindings.insert(id);
+    });
+    let mut finder: ConstraintFinder<'_, '_> =
+        ConstraintFinder { context, bindings, constrained: false };
+    finder.visit_body(context.tcx.hir_body(body_id));
+
+    !finder.constrained
+}
+
+/// Returns `true` if `ty` contains one of the types the fallback applies.
+///
+/// # Arguments
+/// * `ty` (`Ty<'_>`) - The type of the binding.
+fn has_fallback_type(ty: Ty<'_>) -> bool {
+    !ty.references_error()
+        && ty.walk().any(|argument: ty::GenericArg<'_>| {
+            matches!(
+                argument.as_type().map(|ty: Ty<'_>| ty.kind()),
+                Some(ty::Int(IntTy::I32) | ty::Float(FloatTy::F64))
+            )
+        })
+}
+
+/// Returns `true` if `expression` is built from literals and operators
+/// only, without a numeric literal with a suffix, so that nothing in it
+/// constrains the type of its numeric literals.
+///
+/// # Arguments
+/// * `expression` (`&Expr<'_>`) - The initializer of a let binding, or one of
+///   its operands.
+fn is_unconstrained(expression: &Expr<'_>) -> bool {
+    match &expression.kind {
+        ExprKind::Lit(literal) => !matches!(
+            literal.node,
+            LitKind::Int(_, LitIntType::Signed(_) | LitIntType::Unsigned(_))
+                | LitKind::Float(_, LitFloatType::Suffixed(_))
+                | LitKind::Err(_)
+        ),
+        ExprKind::Unary(_, operand)
+        | ExprKind::AddrOf(_, _, operand)
+        | ExprKind::Repeat(operand, _)
+        | ExprKind::DropTemps(operand) => is_unconstrained(operand),
+        ExprKind::Binary(_, left, right) => {
+            is_unconstrained(left) && is_unconstrained(right)
+        },
+        ExprKind::Tup(elements) | ExprKind::Array(elements) => {
+            elements.iter().all(is_unconstrained)
+        },
+        ExprKind::Block(block, None) => {
+            block.stmts.is_empty() && block.expr.is_some_and(is_unconstrained)
+        },
+        _ => false,
+    }
+}
+
+/// Visitor walking the body of a let binding and looking for a use of the
+/// binding that constrains its type.
+struct ConstraintFinder<'a, 'tcx> {
+    context: &'a LateContext<'tcx>,
+    bindings: HashSet<HirId>,
+    constrained: bool,
+}
+
+impl<'tcx> ConstraintFinder<'_, 'tcx> {
+    /// Returns `true` if `expression` is a path to one of the bindings.
+    ///
+    /// # Arguments
+    /// * `expression` (`&Expr<'_>`) - The expression to check.
+    fn is_binding(&self, expression: &Expr<'_>) -> bool {
+        matches!(
+            expression.kind,
+            ExprKind::Path(QPath::Resolved(None, path))
+                if matches!(path.res, Res::Local(id) if self.bindings.contains(&id))
+        )
+    }
+
+    /// Returns `true` if `operand`, an operand of the same type as a use of
+    /// the binding, constrains that type.
+    ///
+    /// # Arguments
+    /// * `operand` (`&Expr<'_>`) - The other operand.
+    fn is_constraining_operand(&self, operand: &Expr<'_>) -> bool {
+        !self.is_binding(operand) && !is_unconstrained(operand)
+    }
+
+    /// Returns `true` if the parameter `index` of the function called by
+    /// the call or method call `call` has a concrete type, which constrains
+    /// the argument. Parameters of a generic type do not, e.g. those of the
+    /// formatting macros.
+    ///
+    /// # Arguments
+    /// * `call` (`&'tcx Expr<'tcx>`) - The call.
+    /// * `index` (`usize`) - The index of the parameter, counting the receiver
+    ///   of a method call.
+    fn is_concrete_parameter(
+        &self,
+        call: &'tcx Expr<'tcx>,
+        index: usize,
+    ) -> bool {
+        let callee: Option<DefId> = match &call.kind {
+            ExprKind::Call(callee, _) => {
+                match self.context.typeck_results().expr_ty(callee).kind() {
+                    ty::FnDef(id, _) => Some(*id),
+                    _ => None,
+                }
+            },
+            _ => self
+                .context
+                .typeck_results()
+                .type_dependent_def_id(call.hir_id),
+        };
+        let Some(callee): Option<DefId> = callee else {
+            return true;
+        };
+
+        self.context
+            .tcx
+            .fn_sig(callee)
+            .instantiate_identity()
+            .skip_binder()
+            .inputs()
+            .get(index)
+            .is_none_or(|input: &Ty<'_>| !input.has_param())
+    }
+
+    /// Returns `true` if the use `expression` of a binding constrains its
+    /// type, according to the expression it is an operand of. The operands
+    /// of references, negations and blocks are followed to their parent.
+    ///
+    /// # Arguments
+    /// * `expression` (`&'tcx Expr<'tcx>`) - The use of the binding.
+    fn constrains(&self, expression: &'tcx Expr<'tcx>) -> bool {
+        let mut current: &'tcx Expr<'tcx> = expression;
+        loop {
+            let parent: Node<'tcx> =
+                self.context.tcx.parent_hir_node(current.hir_id);
+            let Node::Expr(parent_expression): Node<'tcx> = parent else {
+                return match parent {
+                    Node::LetStmt(local) => local.ty.is_some(),
+                    // A struct field, or the value of a function body with a
+                    // written return type.
+                    Node::ExprField(_)
+                    | Node::Item(_)
+                    | Node::ImplItem(_)
+                    | Node::TraitItem(_) => true,
+                    Node::Block(block) => {
+                        block.expr.is_some_and(|tail: &Expr<'_>| {
+                            tail.hir_id == current.hir_id
+                        }) && self.block_constrains(block.hir_id)
+                    },
+                    _ => false,
+                };
+            };
+
+            match &parent_expression.kind {
+                ExprKind::AddrOf(..)
+                | ExprKind::Unary(..)
+                | ExprKind::DropTemps(_) => current = parent_expression,
+                ExprKind::Call(_, arguments) => {
+                    return arguments
+                        .iter()
+                        .position(|argument: &Expr<'_>| {
+                            argument.hir_id == current.hir_id
+                        })
+                        .is_some_and(|index: usize| {
+                            self.is_concrete_parameter(
+                                parent_expression,
+                                index,
+                            )
+                        });
+                },
+                ExprKind::MethodCall(_, _, arguments, _) => {
+                    return arguments
+                        .iter()
+                        .position(|argument: &Expr<'_>| {
+                            argument.hir_id == current.hir_id
+                        })
+                        .is_some_and(|index: usize| {
+                            self.is_concrete_parameter(
+                                parent_expression,
+                                index + 1,
+                            )
+                        });
+                },
+                ExprKind::Binary(_, left, right)
+                | ExprKind::AssignOp(_, left, right)
+                | ExprKind::Assign(left, right, _) => {
+                    let other: &Expr<'_> = if left.hir_id == current.hir_id {
+                        right
+                    } else {
+                        left
+                    };
+                    return self.is_constraining_operand(other);
+                },
+                ExprKind::Ret(_)
+                | ExprKind::Break(..)
+                | ExprKind::Struct(..) => {
+                    return true;
+                },
+                _ => return false,
+            }
+        }
+    }
+
+    /// Returns `true` if the value of the block `block` is constrained:
+    /// the block is the body of a function item, or its value is itself a
+    /// constraining use.
+    ///
+    /// # Arguments
+    /// * `block` (`HirId`) - The block the use is the value of.
+    fn block_constrains(&self, block: HirId) -> bool {
+        match self.context.tcx.parent_hir_node(block) {
+            Node::Expr(expression) => self.constrains(expression),
+            _ => false,
+        }
+    }
+}
+
+impl<'tcx> Visitor<'tcx> for ConstraintFinder<'_, 'tcx> {
+    fn visit_expr(&mut self, expression: &'tcx Expr<'tcx>) {
+        if self.constrained {
+            return;
+        }
+        if self.is_binding(expression) && self.constrains(expression) {
+            self.constrained = true;
+            return;
+        }
+        walk_expr(self, expression);
+    }
+}
@@ -1,5 +1,6 @@
 #![feature(rustc_private)]
 
+extern crate rustc_ast;
 extern crate rustc_errors;
 extern crate rustc_hir;
 extern crate rustc_lint;
@@ -7,11 +8,53 @@ extern crate rustc_middle;
 extern crate rustc_session;
 extern crate rustc_span;
 
-use rustc_errors::Diag;
-use rustc_hir::{Body, BodyId, Expr, ExprKind, LetStmt, PatKind};
-use rustc_lint::{LateContext, LateLintPass, LintContext, LintStore};
-use rustc_middle::ty::TyCtxt;
-use rustc_session::{Session, declare_lint, declare_lint_pass};
+use rustc_ast::{LitFloatType, LitIntType, LitKind};
+use rustc_errors::{Applicability, Diag};
+use rustc_hir::{
+    BindingMode,
+    Body,
+    BodyId,
+    Expr,
+    ExprField,
+    ExprKind,
+    HirId,
+    LangItem,
+    LetExpr,
+    LetStmt,
+    LoopSource,
+    MatchSource,
+    Node,
+    Pat,
+    PatKind,
+    QPath,
+    Safety,
+    Stmt,
+    StmtKind,
+    UnOp,
+};
+use rustc_lint::{LateContext, LateLintPass, Lint, LintStore};
+use rustc_middle::ty::{
+    self,
+    ClosureArgs,
+    ClosureKind,
+    FnSig,
+    PolyFnSig,
+    Ty,
+    TyCtxt,
+    TypeVisitableExt,
+    print::{CratePrefixGuard, NoTrimmedGuard},
+};
+use rustc_session::{Session, declare_lint, impl_lint_pass};
+use rustc_span::{
+    Span,
+    def_id::DefId,
+    symbol::{Ident, sym},
+};
+use scope::Scope;
+use serde::Deserialize;
+
+mod fallback;
+mod scope;
 
 // This lint detects missing explicit type annotations on let bindings, except
 // when the pattern is `_`. It also detects missing explicit type annotations
@@ -33,14 +76,714 @@ declare_lint! {
     "Detects missing explicit type annotation on closure parameters."
 }
 
-declare_lint_pass!(MissingType => [
+declare_lint! {
+    pub MISSING_TARGET_TYPE,
+    Warn,
+    "Detects `collect`, `parse`, `into` and `try_into` results bound without \
+     a target type."
+}
+
+declare_lint! {
+    pub MISSING_LET_ELSE_TYPE,
+    Allow,
+    "Detects missing explicit type annotation on let-else bindings."
+}
+
+declare_lint! {
+    pub MISSING_IF_LET_TYPE,
+    Allow,
+    "Detects `if let` patterns binding values of an inferred type."
+}
+
+declare_lint! {
+    pub MISSING_WHILE_LET_TYPE,
+    Allow,
+    "Detects `while let` patterns binding values of an inferred type."
+}
+
+declare_lint! {
+    pub MISSING_FOR_LOOP_TYPE,
+    Allow,
+    "Detects `for` loop patterns binding values of an inferred type."
+}
+
+/// Configuration of the `missing_type` library, read from the
+/// `[missing_type]` table of the workspace's `dylint.toml`.
+#[derive(Default, Deserialize)]
+#[serde(default)]
+struct Config {
+    /// Whether the let bindings initialized by a literal whose type is
+    /// written in the literal itself, e.g. `let s = "abc";`, are skipped.
+    skip_literal_initializers: bool,
+    /// How the bindings whose inferred type cannot be written are handled.
+    unnameable_types: Unnameable,
+    /// Whether `missing_let_type` only reports the bindings typed by the
+    /// `i32` or `f64` fallback, e.g. `let total = 0;`.
+    fallback_only: bool,
+    /// Item paths (`crate::crypto`) and file globs (`src/crypto/**`) the
+    /// lints are restricted to. Everything is linted when empty.
+    include: Vec<String>,
+    /// Item paths and file globs the lints are not reported in.
+    exclude: Vec<String>,
+}
+
+/// How the bindings whose inferred type cannot be written in a type
+/// annotation, such as closures, are handled.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Deserialize)]
+#[serde(rename_all = "lowercase")]
+enum Unnameable {
+    /// Reported with a help note explaining why no type is suggested.
+    #[default]
+    Report,
+    /// Not reported.
+    Skip,
+    /// Reported with a nameable type the value coerces to, or with a boxed
+    /// trait object when it does not coerce.
+    Suggest,
+}
+
+/// Lint pass for the `missing_type` lints, holding the options loaded from
+/// the configuration.
+struct MissingType {
+    skip_literal_initializers: bool,
+    unnameable_types: Unnameable,
+    fallback_only: bool,
+    scope: Scope,
+}
+
+impl_lint_pass!(MissingType => [
     MISSING_LET_TYPE,
-    MISSING_CLOSURE_PARAM_TYPE
+    MISSING_CLOSURE_PARAM_TYPE,
+    MISSING_TARGET_TYPE,
+    MISSING_LET_ELSE_TYPE,
+    MISSING_IF_LET_TYPE,
+    MISSING_WHILE_LET_TYPE,
+    MISSING_FOR_LOOP_TYPE,
 ]);
 
+impl MissingType {
+    fn new(tcx: TyCtxt<'_>) -> Self {
+        let config: Config =
+            security_utils::config::load(tcx, env!("CARGO_PKG_NAME"));
+
+        Self {
+            skip_literal_initializers: config.skip_literal_initializers,
+            unnameable_types: config.unnameable_types,
+            fallback_only: config.fallback_only,
+            scope: Scope::new(&config.include, &config.exclude),
+        }
+    }
+
+    /// Returns `true` if the binding `pattern` is skipped because it is out
+    /// of the scope of the lints, or because its type cannot be written and
+    /// the configuration skips such bindings.
+    ///
+    /// # Arguments
+    /// * `context` (`&LateContext<'_>`) - The lint context used to read the
+    ///   inferred type.
+    /// * `pattern` (`&Pat<'_>`) - The binding missing a type annotation.
+    fn is_skipped(
+        &self,
+        context: &LateContext<'_>,
+        pattern: &Pat<'_>,
+    ) -> bool {
+        !self.scope.contains(context, pattern.hir_id, pattern.span)
+            || self.unnameable_types == Unnameable::Skip
+                && unnameable_part(context.typeck_results().pat_ty(pattern))
+                    .is_some()
+    }
+
+    /// Suggests annotating `pattern` with its inferred type, when the type
+    /// can be written. Types are printed with their full paths, e.g.
+    /// `std::vec::Vec<crate::Item>`, so that the annotation compiles without
+    /// new imports. Types that cannot be written are explained in a help
+    /// note instead, or replaced by a nameable form when configured, and
+    /// types left uninferred get neither.
+    ///
+    /// # Arguments
+    /// * `context` (`&LateContext<'_>`) - The lint context used to read the
+    ///   inferred type.
+    /// * `diagnostic` (`&mut Diag<'_, ()>`) - The diagnostic to extend.
+    /// * `pattern` (`&Pat<'_>`) - The binding missing a type annotation.
+    /// * `init` (`Option<&Expr<'_>>`) - The value bound, if any.
+    fn suggest_type(
+        &self,
+        context: &LateContext<'_>,
+        diagnostic: &mut Diag<'_, ()>,
+        pattern: &Pat<'_>,
+        init: Option<&Expr<'_>>,
+    ) {
+        let ty: Ty<'_> = context.typeck_results().pat_ty(pattern);
+        if ty.references_error() || ty.has_infer() {
+            return;
+        }
+        let Some(part): Option<&str> = unnameable_part(ty) else {
+            diagnostic.span_suggestion(
+                pattern.span.shrink_to_hi(),
+                "add the inferred type",
+                format!(": {}", written(ty)),
+                Applicability::MachineApplicable,
+            );
+            return;
+        };
+
+        match (self.unnameable_types, nameable_form(context.tcx, ty), init) {
+            (Unnameable::Suggest, Some(NameableForm::Coerced(form)), _) => {
+                diagnostic.span_suggestion(
+                    pattern.span.shrink_to_hi(),
+                    format!(
+                        "the inferred type contains {part}, add the function \
+                         pointer type it coerces to"
+                    ),
+                    format!(": {form}"),
+                    Applicability::MachineApplicable,
+                );
+            },
+            (
+                Unnameable::Suggest,
+                Some(NameableForm::Boxed(form)),
+                Some(init),
+            ) => {
+                diagnostic.multipart_suggestion(
+                    format!(
+                        "the inferred type contains {part}, box the value as \
+                         a trait object"
+                    ),
+                    vec![
+                        (pattern.span.shrink_to_hi(), format!(": {form}")),
+                        (init.span.shrink_to_lo(), String::from("Box::new(")),
+                        (init.span.shrink_to_hi(), String::from(")")),
+                    ],
+                    Applicability::MaybeIncorrect,
+                );
+            },
+            _ => {
+                diagnostic.help(format!(
+                    "the inferred type contains {part}, which cannot be \
+                     written in a type annotation"
+                ));
+            },
+        }
+    }
+
+    /// Reports the `if let` or `while let` pattern of `expression` if it
+    /// binds values. Such patterns cannot be annotated, so the diagnostic
+    /// shows the inferred types of the bindings and, for `if let`, the
+    /// annotated `let` the scrutinee can be bound with first.
+    ///
+    /// # Arguments
+    /// * `context` (`&LateContext<'_>`) - The lint context used to read the
+    ///   inferred types.
+    /// * `expression` (`&Expr<'_>`) - The `let` expression.
+    /// * `let_expr` (`&LetExpr<'_>`) - The pattern and the scrutinee.
+    fn check_let_expr(
+        &self,
+        context: &LateContext<'_>,
+        expression: &Expr<'_>,
+        let_expr: &LetExpr<'_>,
+    ) {
+        let bindings: Vec<String> = bindings(context, let_expr.pat);
+        if bindings.is_empty() || self.is_skipped(context, let_expr.pat) {
+            return;
+        }
+
+        let while_let: bool = is_while_let(context, expression);
+        let (lint, kind): (&'static Lint, &str) = if while_let {
+            (MISSING_WHILE_LET_TYPE, "while let")
+        } else {
+            (MISSING_IF_LET_TYPE, "if let")
+        };
+        let scrutinee: Ty<'_> =
+            context.typeck_results().expr_ty(let_expr.init);
+        security_utils::span_lint(
+            context,
+            lint,
+            let_expr.pat.span,
+            |diagnostic: &mut Diag<'_, ()>| {
+                diagnostic.primary_message(format!(
+                    "Missing explicit type annotation on `{kind}` binding."
+                ));
+                diagnostic.note(format!(
+                    "the pattern binds {}",
+                    bindings.join(", ")
+                ));
+                let nameable: bool = !scrutinee.references_error()
+                    && !scrutinee.has_infer()
+                    && unnameable_part(scrutinee).is_none();
+                if while_let {
+                    diagnostic.help(
+                        "`while let` patterns cannot be annotated, and the \
+                         scrutinee is evaluated again on each iteration: \
+                         annotate the type where it is produced instead",
+                    );
+                } else if nameable {
+                    diagnostic.help(format!(
+                        "`if let` patterns cannot be annotated, bind the \
+                         scrutinee with an annotated `let` first: `let value: \
+                         {} = ...;`",
+                        written(scrutinee)
+                    ));
+                } else {
+                    diagnostic.help(
+                        "`if let` patterns cannot be annotated, bind the \
+                         scrutinee with an annotated `let` first",
+                    );
+                }
+            },
+        );
+    }
+
+    /// Reports the pattern of the `for` loop iterating over `iterable` if
+    /// it binds values and the element type is not written in `iterable`.
+    /// `for` patterns cannot be annotated, so the diagnostic shows the
+    /// inferred types of the bindings and how the element can be rebound
+    /// with an annotated `let`.
+    ///
+    /// # Arguments
+    /// * `context` (`&LateContext<'_>`) - The lint context used to read the
+    ///   inferred types.
+    /// * `pattern` (`&Pat<'_>`) - The pattern of the loop.
+    /// * `iterable` (`&Expr<'_>`) - The expression iterated over.
+    fn check_for_loop(
+        &self,
+        context: &LateContext<'_>,
+        pattern: &Pat<'_>,
+        iterable: &Expr<'_>,
+    ) {
+        let bindings: Vec<String> = bindings(context, pattern);
+        if bindings.is_empty()
+            || has_written_element_type(iterable)
+            || self.is_skipped(context, pattern)
+        {
+            return;
+        }
+
+        let ty: Ty<'_> = context.typeck_results().pat_ty(pattern);
+        let nameable: bool = !ty.references_error()
+            && !ty.has_infer()
+            && unnameable_part(ty).is_none();
+        security_utils::span_lint(
+            context,
+            MISSING_FOR_LOOP_TYPE,
+            pattern.span,
+            |diagnostic: &mut Diag<'_, ()>| {
+                diagnostic
+                    .primary_message(
+                        "Missing explicit type annotation on `for` loop binding.",
+                    )
+                    .note(format!("the pattern binds {}", bindings.join(", ")));
+                match (pattern.kind, nameable) {
+                    (PatKind::Binding(_, _, ident, None), true) => {
+                        diagnostic.help(format!(
+                            "`for` patterns cannot be annotated, rebind the \
+                             element with an annotated `let` at the start of \
+                             the body: `let {ident}: {} = {ident};`",
+                            written(ty)
+                        ));
+                    },
+                    _ => {
+                        diagnostic.help(
+                            "`for` patterns cannot be annotated, rebind the \
+                             element with an annotated `let` at the start of \
+                             the body",
+                        );
+                    },
+                }
+            },
+        );
+    }
+
+    /// Reports the let binding `local` of the result of `conversion`, a
+    /// call whose target type is inferred, with the annotation and, for
+    /// `collect` and `parse`, the turbofish the target type can be written
+    /// with.
+    ///
+    /// # Arguments
+    /// * `context` (`&LateContext<'_>`) - The lint context used to read the
+    ///   inferred types.
+    /// * `local` (`&LetStmt<'_>`) - The let binding missing a type annotation.
+    /// * `conversion` (`&Expr<'_>`) - The call of the conversion method.
+    fn report_target_type(
+        &self,
+        context: &LateContext<'_>,
+        local: &LetStmt<'_>,
+        conversion: &Expr<'_>,
+    ) {
+        let ExprKind::MethodCall(segment, _, _, _): ExprKind<'_> =
+            conversion.kind
+        else {
+            return;
+        };
+        let method: Ident = segment.ident;
+        // The target type is the last generic argument of `collect` and
+        // `parse`, the trait parameter of `into` and `try_into`.
+        let target: Option<Ty<'_>> =
+            matches!(method.name.as_str(), "collect" | "parse")
+                .then(|| context.typeck_results().node_args(conversion.hir_id))
+                .and_then(|arguments: ty::GenericArgsRef<'_>| {
+                    arguments.types().last()
+                })
+                .filter(|target: &Ty<'_>| {
+                    !target.references_error()
+                        && !target.has_infer()
+                        && unnameable_part(*target).is_none()
+                });
+
+        security_utils::span_lint(
+            context,
+            MISSING_TARGET_TYPE,
+            local.pat.span,
+            |diagnostic: &mut Diag<'_, ()>| {
+                diagnostic
+                    .primary_message(format!(
+                        "Missing explicit target type on `{method}` result."
+                    ))
+                    .span_label(
+                        method.span,
+                        "the target type of this conversion is inferred from \
+                         the uses of the binding",
+                    );
+                self.suggest_type(context, diagnostic, local.pat, local.init);
+                if let Some(target) = target {
+                    diagnostic.span_suggestion(
+                        method.span.shrink_to_hi(),
+                        "or specify the target type with a turbofish",
+                        format!("::<{}>", written(target)),
+                        Applicability::MaybeIncorrect,
+                    );
+                }
+            },
+        );
+    }
+}
+
+/// Returns the bindings of `pattern` with their inferred types, e.g.
+/// ``["`count: &u32`"]``.
+///
+/// # Arguments
+/// * `context` (`&LateContext<'_>`) - The lint context used to read the
+///   inferred types.
+/// * `pattern` (`&Pat<'_>`) - The pattern to read the bindings of.
+fn bindings(context: &LateContext<'_>, pattern: &Pat<'_>) -> Vec<String> {
+    let mut bindings: Vec<String> = Vec::new();
+    pattern.each_binding(
+        |_: BindingMode, id: HirId, _: Span, ident: Ident| {
+            bindings.push(format!(
+                "`{ident}: {}`",
+                context.typeck_results().node_type(id)
+            ));
+        },
+    );
+    bindings
+}
+
+/// Returns the pattern and the iterated expression of the `for` loop that
+/// was desugared to `expression`, if any. `for pattern in iterable { .. }`
+/// is desugared to `match into_iter(iterable) { mut iter => loop {
+/// match next(&mut iter) { None => break, Some(pattern) => .. } } }`,
+/// where `Some(pattern)` is lowered to `Some { 0: pattern }`.
+///
+/// # Arguments
+/// * `expression` (`&Expr<'tcx>`) - The expression to unpack.
+fn for_loop<'tcx>(
+    expression: &Expr<'tcx>,
+) -> Option<(&'tcx Pat<'tcx>, &'tcx Expr<'tcx>)> {
+    let ExprKind::Match(into_iter, [arm], MatchSource::ForLoopDesugar): ExprKind<'tcx> =
+        expression.kind
+    else {
+        return None;
+    };
+    let ExprKind::Call(_, [iterable]): ExprKind<'tcx> = into_iter.kind else {
+        return None;
+    };
+    let ExprKind::Loop(block, _, LoopSource::ForLoop, _): ExprKind<'tcx> =
+        arm.body.kind
+    else {
+        return None;
+    };
+    let [Stmt { kind: StmtKind::Expr(next), .. }]: &[Stmt<'tcx>] = block.stmts
+    else {
+        return None;
+    };
+    let ExprKind::Match(_, [_, some], MatchSource::ForLoopDesugar): ExprKind<
+        'tcx,
+    > = next.kind
+    else {
+        return None;
+    };
+    let PatKind::Struct(_, [field], _): PatKind<'tcx> = some.pat.kind else {
+        return None;
+    };
+
+    Some((field.pat, iterable))
+}
+
+/// Returns `true` if the element type of `iterable` is written in it: a
+/// range or an array literal with a typed literal element, such as
+/// `0_u8..count` or `['a', 'b']`.
+///
+/// # Arguments
+/// * `iterable` (`&Expr<'_>`) - The expression iterated over by a `for` loop.
+fn has_written_element_type(iterable: &Expr<'_>) -> bool {
+    match iterable.kind {
+        ExprKind::Struct(QPath::LangItem(..), fields, _) => fields
+            .iter()
+            .any(|field: &ExprField<'_>| is_typed_literal(field.expr)),
+        ExprKind::Call(callee, bounds)
+            if matches!(
+                callee.kind,
+                ExprKind::Path(QPath::LangItem(
+                    LangItem::RangeInclusiveNew,
+                    _
+                ))
+            ) =>
+        {
+            bounds.iter().any(is_typed_literal)
+        },
+        ExprKind::Array(elements) => elements.iter().any(is_typed_literal),
+        _ => false,
+    }
+}
+
+/// Returns `true` if the `let` expression `expression` is the condition of
+/// a `while let` loop, which is lowered to a `loop` around an `if let`.
+///
+/// # Arguments
+/// * `context` (`&LateContext<'_>`) - The lint context used to walk the
+///   parents of the expression.
+/// * `expression` (`&Expr<'_>`) - The `let` expression.
+fn is_while_let(context: &LateContext<'_>, expression: &Expr<'_>) -> bool {
+    let parents: Vec<Node<'_>> = context
+        .tcx
+        .hir_parent_iter(expression.hir_id)
+        .map(|(_, node): (HirId, Node<'_>)| node)
+        // Conditions chaining several `let` expressions with `&&`.
+        .skip_while(|node: &Node<'_>| {
+            matches!(
+                node,
+                Node::Expr(Expr {
+                    kind: ExprKind::Binary(..) | ExprKind::DropTemps(_),
+                    ..
+                })
+            )
+        })
+        .take(3)
+        .collect();
+
+    matches!(
+        parents.as_slice(),
+        [
+            Node::Expr(Expr { kind: ExprKind::If(..), .. }),
+            Node::Block(_),
+            Node::Expr(Expr {
+                kind: ExprKind::Loop(_, _, LoopSource::While, _),
+                ..
+            }),
+        ]
+    )
+}
+
+/// A nameable form of a type that cannot be written.
+enum NameableForm {
+    /// A type the value coerces to, e.g. the function pointer of a function
+    /// item or of a closure capturing nothing.
+    Coerced(String),
+    /// A boxed trait object the value can be converted to with `Box::new`.
+    Boxed(String),
+}
+
+/// Returns `ty` as written in a type annotation, with full paths.
+///
+/// # Arguments
+/// * `ty` (`Ty<'_>`) - The type to print.
+fn written(ty: Ty<'_>) -> String {
+    let _crate_prefix: CratePrefixGuard = CratePrefixGuard::new();
+    let _no_trimmed: NoTrimmedGuard = NoTrimmedGuard::new();
+    ty.to_string()
+}
+
+/// Returns a nameable form of `ty`, if `ty` itself is a function item, a
+/// closure or an `impl Trait` type: the function pointer type of the
+/// function items and of the closures capturing nothing, or a boxed trait
+/// object for the other closures and the `impl Trait` types. Closures whose
+/// signature has late-bound lifetimes are not boxed, as their trait object
+/// type needs higher-ranked bounds.
+///
+/// # Arguments
+/// * `tcx` (`TyCtxt<'tcx>`) - The type context.
+/// * `ty` (`Ty<'tcx>`) - The inferred type.
+fn nameable_form<'tcx>(
+    tcx: TyCtxt<'tcx>,
+    ty: Ty<'tcx>,
+) -> Option<NameableForm> {
+    match ty.kind() {
+        ty::FnDef(..) => Some(NameableForm::Coerced(written(Ty::new_fn_ptr(
+            tcx,
+            ty.fn_sig(tcx),
+        )))),
+        ty::Closure(_, args) => {
+            let closure: ClosureArgs<TyCtxt<'tcx>> = args.as_closure();
+            let signature: PolyFnSig<'tcx> =
+                tcx.signature_unclosure(closure.sig(), Safety::Safe);
+            if closure.upvar_tys().is_empty() {
+                return Some(NameableForm::Coerced(written(Ty::new_fn_ptr(
+                    tcx, signature,
+                ))));
+            }
+            if !signature.bound_vars().is_empty() {
+                return None;
+            }
+
+            let signature: FnSig<'tcx> = signature.skip_binder();
+            let function: &str = match closure.kind() {
+                ClosureKind::Fn => "Fn",
+                ClosureKind::FnMut => "FnMut",
+                ClosureKind::FnOnce => "FnOnce",
+            };
+            let inputs: Vec<String> = signature
+                .inputs()
+                .iter()
+                .map(|input: &Ty<'tcx>| written(*input))
+                .collect();
+            let output: String = if signature.output().is_unit() {
+                String::new()
+            } else {
+                format!(" -> {}", written(signature.output()))
+            };
+            Some(NameableForm::Boxed(format!(
+                "std::boxed::Box<dyn {function}({}){output}>",
+                inputs.join(", ")
+            )))
+        },
+        ty::Alias(ty::Opaque, _) => {
+            let opaque: String = written(ty);
+            let bounds: &str = opaque.strip_prefix("impl ")?;
+            (!bounds.contains("use<")).then(|| {
+                NameableForm::Boxed(format!("std::boxed::Box<dyn {bounds}>"))
+            })
+        },
+        _ => None,
+    }
+}
+
+/// Methods ending an initializer without constraining the type of their
+/// receiver, e.g. `value.parse().unwrap()`.
+const UNWRAPPING_METHODS: [&str; 3] =
+    ["unwrap", "expect", "unwrap_or_default"];
+
+/// Returns the call of `collect`, `parse`, `into` or `try_into` producing
+/// the value of `expression` without a turbofish, through `?` and the
+/// unwrapping methods, if any. The target type of such a call is inferred
+/// from the uses of the value.
+///
+/// # Arguments
+/// * `context` (`&LateContext<'tcx>`) - The lint context used to resolve the
+///   methods.
+/// * `expression` (`&'tcx Expr<'tcx>`) - The initializer of a let binding.
+fn target_conversion<'tcx>(
+    context: &LateContext<'tcx>,
+    expression: &'tcx Expr<'tcx>,
+) -> Option<&'tcx Expr<'tcx>> {
+    let mut current: &'tcx Expr<'tcx> = expression;
+    loop {
+        match current.kind {
+            // `value?` is desugared to `match branch(value) { .. }`.
+            ExprKind::Match(scrutinee, _, MatchSource::TryDesugar(_)) => {
+                let ExprKind::Call(_, [inner]): ExprKind<'tcx> =
+                    scrutinee.kind
+                else {
+                    return None;
+                };
+                current = inner;
+            },
+            ExprKind::MethodCall(segment, receiver, _, _)
+                if UNWRAPPING_METHODS
+                    .contains(&segment.ident.name.as_str()) =>
+            {
+                current = receiver;
+            },
+            ExprKind::MethodCall(segment, _, _, _) => {
+                let method: DefId = context
+                    .typeck_results()
+                    .type_dependent_def_id(current.hir_id)?;
+                return (segment.args.is_none()
+                    && is_target_method(context.tcx, method))
+                .then_some(current);
+            },
+            _ => return None,
+        }
+    }
+}
+
+/// Returns `true` if `method` is `Iterator::collect`, `str::parse`,
+/// `Into::into` or `TryInto::try_into`.
+///
+/// # Arguments
+/// * `tcx` (`TyCtxt<'_>`) - The type context.
+/// * `method` (`DefId`) - The called method.
+fn is_target_method(tcx: TyCtxt<'_>, method: DefId) -> bool {
+    match tcx.item_name(method).as_str() {
+        "collect" => tcx.is_diagnostic_item(sym::iterator_collect_fn, method),
+        "into" | "try_into" => {
+            tcx.trait_of_assoc(method).is_some_and(|trait_id: DefId| {
+                tcx.is_diagnostic_item(sym::Into, trait_id)
+                    || tcx.is_diagnostic_item(sym::TryInto, trait_id)
+            })
+        },
+        "parse" => tcx.impl_of_assoc(method).is_some_and(|impl_id: DefId| {
+            tcx.type_of(impl_id).instantiate_identity().is_str()
+        }),
+        _ => false,
+    }
+}
+
+/// Returns `true` if `expression` is a literal whose type is written in the
+/// literal itself: a string, byte string, character or boolean literal, or
+/// a numeric literal with a suffix, such as `5u32` or `-1.5f64`. Numeric
+/// literals without a suffix are typed by inference and are not considered
+/// obvious.
+///
+/// # Arguments
+/// * `expression` (`&Expr<'_>`) - The initializer of a let binding.
+fn is_typed_literal(expression: &Expr<'_>) -> bool {
+    match &expression.kind {
+        ExprKind::Lit(literal) => !matches!(
+            literal.node,
+            LitKind::Int(_, LitIntType::Unsuffixed)
+                | LitKind::Float(_, LitFloatType::Unsuffixed)
+                | LitKind::Err(_)
+        ),
+        ExprKind::Unary(UnOp::Neg, operand) => {
+            matches!(operand.kind, ExprKind::Lit(_))
+                && is_typed_literal(operand)
+        },
+        _ => false,
+    }
+}
+
+/// Returns a description of the part of `ty` that cannot be written in the
+/// source code, if any: a closure, a coroutine, a function item or an
+/// `impl Trait` type.
+///
+/// # Arguments
+/// * `ty` (`Ty<'_>`) - The inferred type.
+fn unnameable_part(ty: Ty<'_>) -> Option<&'static str> {
+    ty.walk().find_map(|argument: ty::GenericArg<'_>| {
+        match argument.as_type().map(|ty: Ty<'_>| ty.kind()) {
+            Some(ty::Closure(..) | ty::CoroutineClosure(..)) => {
+                Some("a closure")
+            },
+            Some(ty::Coroutine(..)) => Some("a coroutine"),
+            Some(ty::FnDef(..)) => Some("a function item"),
+            Some(ty::Alias(ty::Opaque, _)) => Some("an `impl Trait` type"),
+            _ => None,
+        }
+    })
+}
+
 impl<'tcx> LateLintPass<'tcx> for MissingType {
-    /// Checks for missing explicit type annotations on let bindings, except
-    /// when the pattern is `_`.
+    /// Checks for missing explicit type annotations on let and let-else
+    /// bindings, except when the pattern is `_`.
     ///
     /// # Arguments
     /// * `context` (`&LateContext<'tcx>`) - The lint context providing access
@@ -80,15 +823,64 @@ impl<'tcx> LateLintPass<'tcx> for MissingType {
             return;
         }
 
+        // Skip if the type is written in the literal initializing the binding.
+        if self.skip_literal_initializers
+            && local.init.is_some_and(is_typed_literal)
+        {
+            return;
+        }
+
+        // Report the results of conversions to an inferred target type with
+        // the dedicated lint.
+        if local.ty.is_none()
+            && !self.is_skipped(context, local.pat)
+            && let Some(conversion) =
+                local.init.and_then(|init: &'tcx Expr<'tcx>| {
+                    target_conversion(context, init)
+                })
+        {
+            self.report_target_type(context, local, conversion);
+            return;
+        }
+
         // Check if the let statement has an explicit type annotation. If not,
-        // emit a warning.
-        if local.ty.is_none() {
-            context.span_lint(
-                MISSING_LET_TYPE,
+        // emit a warning, with the let-else sub-lint for let-else statements.
+        if local.ty.is_none() && !self.is_skipped(context, local.pat) {
+            let (lint, message): (&'static Lint, &str) = if local.els.is_some()
+            {
+                (
+                    MISSING_LET_ELSE_TYPE,
+                    "Missing explicit type annotation on let-else binding.",
+                )
+            } else {
+                (
+                    MISSING_LET_TYPE,
+                    "Missing explicit type annotation on let binding.",
+                )
+            };
+
+            // Only report the let bindings typed by the fallback, if
+            // configured.
+            let fallback: bool = self.fallback_only && local.els.is_none();
+            if fallback && !fallback::is_fallback(context, body_id, local) {
+                return;
+            }
+
+            security_utils::span_lint(
+                context,
+                lint,
                 local.pat.span,
                 |diagnostic: &mut Diag<'_, ()>| {
-                    diagnostic.primary_message(
-                        "Missing explicit type annotation on let binding.",
+                    diagnostic.primary_message(message);
+                    if fallback {
+                        diagnostic.note(
+                            "nothing constrains the type of the numeric \
+                             literals, so the compiler defaulted it to `i32` \
+                             or `f64`",
+                        );
+                    }
+                    self.suggest_type(
+                        context, diagnostic, local.pat, local.init,
                     );
                 },
             );
@@ -96,7 +888,8 @@ impl<'tcx> LateLintPass<'tcx> for MissingType {
     }
 
     /// Checks for missing explicit type annotations on closure parameters,
-    /// except when the parameter pattern is `_`.
+    /// except when the parameter pattern is `_`, and for `if let`,
+    /// `while let` and `for` patterns binding values.
     ///
     /// # Arguments
     /// * `context` (`&LateContext<'tcx>`) - The lint context providing access
@@ -108,10 +901,25 @@ impl<'tcx> LateLintPass<'tcx> for MissingType {
         context: &LateContext<'tcx>,
         expression: &'tcx Expr<'tcx>,
     ) {
+        // Check the patterns of `for` loops, whose desugaring is marked as an
+        // expansion.
+        if let Some((pattern, iterable)) = for_loop(expression) {
+            if !pattern.span.from_expansion() {
+                self.check_for_loop(context, pattern, iterable);
+            }
+            return;
+        }
+
         if expression.span.from_expansion() {
             return;
         }
 
+        // Check the patterns of `if let` and `while let` conditions.
+        if let ExprKind::Let(let_expr) = &expression.kind {
+            self.check_let_expr(context, expression, let_expr);
+            return;
+        }
+
         // Only check closure expressions.
         let ExprKind::Closure(closure): &ExprKind<'tcx> = &expression.kind
         else {
@@ -138,14 +946,20 @@ impl<'tcx> LateLintPass<'tcx> for MissingType {
 
             // Check if the parameter has an explicit type annotation. If not,
             // emit a warning.
-            if param.ty_span.is_empty() || param.ty_span == param.pat.span {
-                context.span_lint(
+            if (param.ty_span.is_empty() || param.ty_span == param.pat.span)
+                && !self.is_skipped(context, param.pat)
+            {
+                security_utils::span_lint(
+                    context,
                     MISSING_CLOSURE_PARAM_TYPE,
                     param.pat.span,
                     |diagnostic: &mut Diag<'_, ()>| {
                         diagnostic.primary_message(
                             "Closure parameter missing explicit type annotation.",
                         );
+                        self.suggest_type(
+                            context, diagnostic, param.pat, None,
+                        );
                     },
                 );
             }
@@ -167,8 +981,20 @@ impl<'tcx> LateLintPass<'tcx> for MissingType {
 pub fn register_lints(session: &Session, lint_store: &mut LintStore) {
     dylint_linting::init_config(session);
 
-    lint_store.register_lints(&[MISSING_LET_TYPE, MISSING_CLOSURE_PARAM_TYPE]);
-    lint_store.register_late_pass(|_: TyCtxt<'_>| Box::new(MissingType));
+    security_utils::tool::register_lints(
+        lint_store,
+        &[
+            MISSING_LET_TYPE,
+            MISSING_CLOSURE_PARAM_TYPE,
+            MISSING_TARGET_TYPE,
+            MISSING_LET_ELSE_TYPE,
+            MISSING_IF_LET_TYPE,
+            MISSING_WHILE_LET_TYPE,
+            MISSING_FOR_LOOP_TYPE,
+        ],
+    );
+    lint_store
+        .register_late_pass(|tcx: TyCtxt<'_>| Box::new(MissingType::new(tcx)));
 }
 
 dylint_linting::dylint_library!();
@@ -191,4 +1017,39 @@ mod tests {
             .rustc_flags(["--edition=2024", "-Z", "ui-testing"])
             .run();
     }
+
+    #[test]
+    fn ui_literals() {
+        Test::src_base(env!("CARGO_PKG_NAME"), "ui_literals")
+            .rustc_flags(["--edition=2024", "-Z", "ui-testing"])
+            .dylint_toml("missing_type.skip_literal_initializers = true")
+            .run();
+    }
+
+    #[test]
+    fn ui_unnameable() {
+        Test::src_base(env!("CARGO_PKG_NAME"), "ui_unnameable")
+            .rustc_flags(["--edition=2024", "-Z", "ui-testing"])
+            .dylint_toml(r#"missing_type.unnameable_types = "suggest""#)
+            .run();
+    }
+
+    #[test]
+    fn ui_fallback() {
+        Test::src_base(env!("CARGO_PKG_NAME"), "ui_fallback")
+            .rustc_flags(["--edition=2024", "-Z", "ui-testing"])
+            .dylint_toml("missing_type.fallback_only = true")
+            .run();
+    }
+
+    #[test]
+    fn ui_scope() {
+        Test::src_base(env!("CARGO_PKG_NAME"), "ui_scope")
+            .rustc_flags(["--edition=2024", "-Z", "ui-testing"])
+            .dylint_toml(
+                "missing_type.include = [\"crate::keys\", \"*_tables.rs\"]\n\
+                 missing_type.exclude = [\"crate::keys::legacy\"]",
+            )
+            .run();
+    }
 }
@@ -0,0 +1,210 @@
+//! Scoping of the lints to modules and source files.
+//!
+//! The `include` and `exclude` options of the `[missing_type]` table list
+//! item paths, such as `crate::crypto`, matched as in the `[allow]` table,
+//! and file globs, such as `src/crypto/**`. A binding is linted if it is in
+//! one of the included items or files, when any is listed, and in none of
+//! the excluded ones. The patterns are compiled once by [`Scope::new`], and
+//! matched for each binding by [`Scope::contains`].
+
+use std::path::{Component, Path};
+
+use rustc_hir::HirId;
+use rustc_lint::LateContext;
+use rustc_span::{FileName, Span};
+use security_utils::allow::{item_paths, matches_item};
+
+/// A component of a compiled file glob.
+enum Segment {
+    /// `**`, matching any number of directories.
+    Directories,
+    /// A file or directory name, where `*` matches any characters and `?`
+    /// a single one.
+    Name(Vec<char>),
+}
+
+/// A compiled pattern of the `include` or `exclude` options.
+enum Pattern {
+    /// An item path, matching the item and the items nested inside it.
+    Item(String),
+    /// A file glob, matching the end of the path of a source file.
+    File(Vec<Segment>),
+}
+
+impl Pattern {
+    /// Compiles `pattern`: a file glob if it contains a `/` or names a
+    /// `.rs` file, an item path otherwise.
+    ///
+    /// # Arguments
+    /// * `pattern` (`&str`) - The pattern as written in `dylint.toml`.
+    fn new(pattern: &str) -> Self {
+        if !pattern.contains('/') && !pattern.ends_with(".rs") {
+            return Self::Item(pattern.to_owned());
+        }
+
+        Self::File(
+            pattern
+                .split('/')
+                .filter(|segment: &&str| {
+                    !segment.is_empty() && *segment != "."
+                })
+                .map(|segment: &str| match segment {
+                    "**" => Segment::Directories,
+                    _ => Segment::Name(segment.chars().collect()),
+                })
+                .collect(),
+        )
+    }
+
+    /// Returns `true` if the pattern matches one of the paths `items` of
+    /// the item owning a binding, or the source file whose path has the
+    /// components `file`.
+    ///
+    /// # Arguments
+    /// * `items` (`&[String]`) - The paths of the item, e.g.
+    ///   `crate::crypto::derive`.
+    /// * `file` (`&[String]`) - The components of the path of the file.
+    fn matches(&self, items: &[String], file: &[String]) -> bool {
+        match self {
+            Self::Item(pattern) => {
+                items.iter().any(|path: &String| matches_item(pattern, path))
+            },
+            // Relative globs match at any directory, e.g. `src/crypto/**`
+            // matches `crates/keys/src/crypto/aes.rs`.
+            Self::File(segments) => (0..=file.len()).any(|start: usize| {
+                file.get(start..).is_some_and(|suffix: &[String]| {
+                    matches_segments(segments, suffix)
+                })
+            }),
+        }
+    }
+}
+
+/// Returns `true` if the glob `segments` matches exactly the path
+/// components `components`.
+///
+/// # Arguments
+/// * `segments` (`&[Segment]`) - The compiled glob.
+/// * `components` (`&[String]`) - The components of the path.
+fn matches_segments(segments: &[Segment], components: &[String]) -> bool {
+    match (segments.split_first(), components.split_first()) {
+        (None, _) => components.is_empty(),
+        (Some((Segment::Directories, rest)), _) => {
+            matches_segments(rest, components)
+                || components.split_first().is_some_and(
+                    |(_, remaining): (&String, &[String])| {
+                        matches_segments(segments, remaining)
+                    },
+                )
+        },
+        (Some((Segment::Name(name), rest)), Some((component, remaining))) => {
+            matches_name(name, &component.chars().collect::<Vec<char>>())
+                && matches_segments(rest, remaining)
+        },
+        (Some(_), None) => false,
+    }
+}
+
+/// Returns `true` if the name pattern `pattern` matches `name`, where `*`
+/// matches any characters and `?` a single one.
+///
+/// # Arguments
+/// * `pattern` (`&[char]`) - The name pattern.
+/// * `name` (`&[char]`) - The file or directory name.
+fn matches_name(pattern: &[char], name: &[char]) -> bool {
+    match (pattern.split_first(), name.split_first()) {
+        (None, _) => name.is_empty(),
+        (Some(('*', rest)), _) => {
+            matches_name(rest, name)
+                || name.split_first().is_some_and(
+                    |(_, remaining): (&char, &[char])| {
+                        matches_name(pattern, remaining)
+                    },
+                )
+        },
+        (Some((expected, rest)), Some((character, remaining))) => {
+            (*expected == '?' || expected == character)
+                && matches_name(rest, remaining)
+        },
+        (Some(_), None) => false,
+    }
+}
+
+/// The modules and files the lints are scoped to.
+pub struct Scope {
+    include: Vec<Pattern>,
+    exclude: Vec<Pattern>,
+}
+
+impl Scope {
+    /// Compiles the `include` and `exclude` options.
+    ///
+    /// # Arguments
+    /// * `include` (`&[String]`) - The modules and files linted.
+    /// * `exclude` (`&[String]`) - The modules and files not linted.
+    pub fn new(include: &[String], exclude: &[String]) -> Self {
+        Self {
+            include: include
+                .iter()
+                .map(|pattern: &String| Pattern::new(pattern))
+                .collect(),
+            exclude: exclude
+                .iter()
+                .map(|pattern: &String| Pattern::new(pattern))
+                .collect(),
+        }
+    }
+
+    /// Returns `true` if the binding `hir_id`, written at `span`, is
+    /// linted.
+    ///
+    /// # Arguments
+    /// * `context` (`&LateContext<'_>`) - The lint context used to resolve the
+    ///   item and the source file.
+    /// * `hir_id` (`HirId`) - The binding to lint.
+    /// * `span` (`Span`) - Its span.
+    pub fn contains(
+        &self,
+        context: &LateContext<'_>,
+        hir_id: HirId,
+        span: Span,
+    ) -> bool {
+        if self.include.is_empty() && self.exclude.is_empty() {
+            return true;
+        }
+
+        let items: [String; 2] = item_paths(context, hir_id);
+        let file: Vec<String> =
+            match context.tcx.sess.source_map().span_to_filename(span) {
+                FileName::Real(name) => {
+                    name.local_path().map(components).unwrap_or_default()
+                },
+                _ => Vec::new(),
+            };
+
+        (self.include.is_empty()
+            || self
+                .include
+                .iter()
+                .any(|pattern: &Pattern| pattern.matches(&items, &file)))
+            && !self
+                .exclude
+                .iter()
+                .any(|pattern: &Pattern| pattern.matches(&items, &file))
+    }
+}
+
+/// Returns the names of the components of `path`, without its root.
+///
+/// # Arguments
+/// * `path` (`&Path`) - The path of a source file.
+fn components(path: &Path) -> Vec<String> {
+    path.components()
+        .filter_map(|component: Component<'_>| match component {
+            Component::Normal(name) => {
+                Some(name.to_string_lossy().into_owned())
+            },
+            _ => None,
+        })
+        .collect()
+}
@@ -28,6 +28,18 @@ fn main() {
 
     // Closure with `_` pattern (should not trigger).
     let ignore: fn(i32) -> i32 = |_| 0;
+
+    // Let without type annotation (should trigger, with the full path of the
+    // type as a suggestion).
+    let values = vec![Point { x: 1 }];
+    let (first, count) = (values.first(), values.len());
+
+    // Let bound to a closure (should trigger, without a suggestion).
+    let closure = || 1;
+}
+
+struct Point {
+    x: i32,
 }
 
 /// Asynchronous function example to demonstrate that the `missing_type` lint
@@ -45,3 +57,21 @@ async fn async_with_let() -> i32 {
     let value: i32 = 10;
     value
 }
+
+fn numbers() -> impl Iterator<Item = u32> {
+    // Let bound to a function item (should trigger, without a suggestion).
+    let parse = u32::from_str_radix;
+    let _: Result<u32, std::num::ParseIntError> = parse("1", 10);
+
+    // Let bound to an adapter holding a closure (should trigger, without a
+    // suggestion).
+    let evens = (0..10).map(|value: u32| value * 2);
+    evens
+}
+
+fn sum() -> u32 {
+    // Let bound to an `impl Trait` value (should trigger, without a
+    // suggestion).
+    let values = numbers();
+    values.sum()
+}
@@ -0,0 +1,81 @@
+#![warn(
+    missing_let_else_type,
+    missing_if_let_type,
+    missing_while_let_type,
+    missing_for_loop_type
+)]
+
+fn first(values: &[u32]) -> u32 {
+    // Let-else without type annotation (should trigger the let-else lint
+    // only).
+    let Some(value) = values.first() else {
+        return 0;
+    };
+
+    // Let-else with type annotation (should not trigger).
+    let Some(last): Option<&u32> = values.last() else {
+        return 0;
+    };
+    *value + *last
+}
+
+fn drain(mut stack: Vec<(u32, String)>) -> u32 {
+    let mut total: u32 = 0;
+
+    // If-let binding values (should trigger).
+    if let Some((count, _)) = stack.last() {
+        total += count;
+    }
+
+    // If-let chain binding values (should trigger for each binding `let`).
+    if let Some((count, _)) = stack.first()
+        && let Some(name) = stack.get(1).map(|(_, name): &(u32, String)| name)
+    {
+        total += count + name.len() as u32;
+    }
+
+    // If-let without bindings (should not trigger).
+    if let None = stack.first() {
+        return total;
+    }
+
+    // While-let binding values (should trigger).
+    while let Some((count, _)) = stack.pop() {
+        total += count;
+    }
+    total
+}
+
+fn count(names: &[String], pairs: &[(u32, char)]) -> usize {
+    let mut total: usize = 0;
+
+    // For loop binding values (should trigger).
+    for name in names {
+        total += name.len();
+    }
+
+    // For loop destructuring the elements (should trigger once).
+    for (count, _) in pairs.iter().copied() {
+        total += count as usize;
+    }
+
+    // For loops over typed literals (should not trigger).
+    for index in 0_usize..3 {
+        total += index;
+    }
+    for letter in ['a', 'b'] {
+        total += letter.len_utf8();
+    }
+
+    // For loop without bindings (should not trigger).
+    for _ in names {
+        total += 1;
+    }
+    total
+}
+
+fn main() {
+    let _: u32 = first(&[1, 2]);
+    let _: u32 = drain(vec![(1, String::from("a"))]);
+    let _: usize = count(&[String::from("a")], &[(1, 'a')]);
+}
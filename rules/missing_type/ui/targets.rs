@@ -0,0 +1,42 @@
+use std::num::ParseIntError;
+
+fn read(input: &str) -> Result<u64, ParseIntError> {
+    // Parse result bound without a target type (should trigger).
+    let value = input.parse()?;
+    let total: u64 = value;
+
+    // Parse result with a turbofish (should not trigger).
+    let checked = input.parse::<u64>()?;
+
+    // Parse result with an annotation (should not trigger).
+    let annotated: u64 = input.parse()?;
+    Ok(total + checked + annotated)
+}
+
+fn main() {
+    let names: [&str; 2] = ["a", "b"];
+
+    // Collect result bound without a target type (should trigger).
+    let collected = names.iter().copied().collect();
+    let _: Vec<&str> = collected;
+
+    // Collect result with a turbofish (should not trigger).
+    let listed = names.iter().copied().collect::<Vec<&str>>();
+
+    // Unwrapped parse result (should trigger).
+    let port = "8080".parse().unwrap();
+    let _: u16 = port;
+
+    // Into and try_into results (should trigger).
+    let small: u8 = 7;
+    let widened = small.into();
+    let _: u64 = widened;
+    let narrowed = 300_u32.try_into();
+    let _: Result<u8, std::num::TryFromIntError> = narrowed;
+
+    // Other methods (should only trigger the let binding lint).
+    let count = names.iter().count();
+
+    let _: Result<u64, ParseIntError> = read("1");
+    let _: usize = listed.len() + count;
+}
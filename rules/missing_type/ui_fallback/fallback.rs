@@ -0,0 +1,58 @@
+fn scale(value: u64) -> u64 {
+    value * 2
+}
+
+fn offset() -> i64 {
+    // Typed by the return type (should not trigger).
+    let offset = 10;
+    offset
+}
+
+struct Limits {
+    maximum: u16,
+}
+
+fn main() {
+    // Typed by the integer fallback (should trigger).
+    let mut total = 0;
+    for _ in ["a", "b"] {
+        total += 1;
+    }
+
+    // Typed by a cast operand (should not trigger).
+    let mut width = 0;
+    for name in ["a", "b"] {
+        width += name.len() as i32;
+    }
+
+    // Typed by the float fallback (should trigger).
+    let ratio = 0.5;
+    println!("{total} {width} {ratio}");
+
+    // Typed by the fallback through a tuple (should trigger).
+    let pair = (1, "one");
+    println!("{pair:?}");
+
+    // Typed by a function parameter (should not trigger).
+    let size = 4;
+    let _: u64 = scale(size);
+
+    // Typed by an operand of a known type (should not trigger).
+    let step = 1;
+    let count: u8 = 3;
+    let _: u8 = count + step;
+
+    // Typed by a struct field (should not trigger).
+    let maximum = 512;
+    let _: Limits = Limits { maximum };
+
+    // Typed by a suffixed literal (should not trigger).
+    let mask = 0xff_u32;
+
+    // Not initialized by a literal (should not trigger).
+    let length = "abc".len();
+
+    let _: i64 = offset();
+    let _: u32 = mask;
+    let _: usize = length;
+}
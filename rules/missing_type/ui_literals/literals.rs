@@ -0,0 +1,17 @@
+fn main() {
+    // Let initialized by literals typed by their suffix or kind (should not
+    // trigger).
+    let count = 5u32;
+    let ratio = -1.5f64;
+    let name = "abc";
+    let bytes = b"abc";
+    let letter = 'a';
+    let enabled = true;
+
+    // Let initialized by literals typed by inference (should trigger).
+    let index = 5;
+    let scale = 2.0;
+
+    // Let initialized by an expression (should trigger).
+    let total = count + 1;
+}
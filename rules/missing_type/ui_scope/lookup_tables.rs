@@ -0,0 +1,5 @@
+fn main() {
+    // In a file included by a glob (should trigger).
+    let table = [0_u8; 16];
+    let _: usize = table.len();
+}
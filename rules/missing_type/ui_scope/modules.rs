@@ -0,0 +1,30 @@
+mod keys {
+    pub fn derive(seed: u64) -> u64 {
+        // In an included module (should trigger).
+        let mixed = seed ^ 0x5a5a;
+        mixed
+    }
+
+    pub mod legacy {
+        pub fn derive(seed: u64) -> u64 {
+            // In an excluded submodule (should not trigger).
+            let mixed = seed ^ 0xa5a5;
+            mixed
+        }
+    }
+}
+
+mod display {
+    pub fn render(value: u64) -> String {
+        // In a module out of the scope (should not trigger).
+        let rendered = format!("{value:x}");
+        rendered
+    }
+}
+
+fn main() {
+    // In the crate root, out of the scope (should not trigger).
+    let seed = 42_u64;
+    let _: u64 = keys::derive(seed) + keys::legacy::derive(seed);
+    let _: String = display::render(seed);
+}
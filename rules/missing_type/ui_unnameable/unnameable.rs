@@ -0,0 +1,31 @@
+fn evens() -> impl Iterator<Item = u32> {
+    (0..10).map(|value: u32| value * 2)
+}
+
+fn main() {
+    // Let bound to a function item (should trigger, with its function pointer
+    // type).
+    let parse = u32::from_str_radix;
+    let _: Result<u32, std::num::ParseIntError> = parse("1", 10);
+
+    // Let bound to a closure capturing nothing (should trigger, with its
+    // function pointer type).
+    let double = |value: u32| value * 2;
+    let _: u32 = double(1);
+
+    // Let bound to a capturing closure (should trigger, with a boxed trait
+    // object).
+    let offset: u32 = 3;
+    let shift = |value: u32| value + offset;
+    let _: u32 = shift(1);
+
+    // Let bound to an `impl Trait` value (should trigger, with a boxed trait
+    // object).
+    let values = evens();
+    let _: u32 = values.sum();
+
+    // Let bound to a type containing a closure (should trigger, without a
+    // suggestion).
+    let mapped = (0..3).map(|value: u32| value + offset);
+    let _: u32 = mapped.sum();
+}
@@ -0,0 +1,309 @@
+#![feature(rustc_private)]
+
+extern crate rustc_errors;
+extern crate rustc_hir;
+extern crate rustc_lint;
+extern crate rustc_middle;
+extern crate rustc_session;
+extern crate rustc_span;
+
+use rustc_errors::Diag;
+use rustc_hir::{
+    AssignOpKind,
+    BinOpKind,
+    Expr,
+    ExprKind,
+    FieldDef,
+    Node,
+    QPath,
+};
+use rustc_lint::{LateContext, LateLintPass, LintStore};
+use rustc_middle::ty::{Ty, TyCtxt};
+use rustc_session::{Session, declare_lint, impl_lint_pass};
+use rustc_span::Symbol;
+use serde::Deserialize;
+
+declare_lint! {
+    pub SECURITY_MONETARY_FLOAT,
+    Warn,
+    "Detects floating-point types used for monetary values."
+}
+
+/// Configuration of the `monetary_float` library, read from the
+/// `[monetary_float]` table of the workspace's `dylint.toml`.
+#[derive(Deserialize)]
+#[serde(default)]
+struct Config {
+    /// Identifier words that mark a value as monetary (e.g. `price` matches
+    /// `unit_price`, `priceCents` and `prices`).
+    patterns: Vec<String>,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Self {
+            patterns: ["amount", "price", "balance", "fee", "cost"]
+                .into_iter()
+                .map(String::from)
+                .collect(),
+        }
+    }
+}
+
+/// Lint pass holding the monetary identifier patterns loaded from the
+/// configuration.
+struct SecurityMonetaryFloat {
+    patterns: Vec<String>,
+}
+
+impl_lint_pass!(SecurityMonetaryFloat => [SECURITY_MONETARY_FLOAT]);
+
+impl SecurityMonetaryFloat {
+    fn new(tcx: TyCtxt<'_>) -> Self {
+        let config: Config =
+            security_utils::config::load(tcx, env!("CARGO_PKG_NAME"));
+
+        Self {
+            patterns: config
+                .patterns
+                .into_iter()
+                .map(|pattern: String| pattern.to_lowercase())
+                .collect(),
+        }
+    }
+
+    /// Returns `true` if one of the words of `name` is a configured pattern,
+    /// optionally followed by a plural `s`.
+    ///
+    /// # Arguments
+    /// * `name` (`&str`) - The identifier to check.
+    fn is_monetary(&self, name: &str) -> bool {
+        security_utils::words::matches(&self.patterns, name)
+    }
+
+    /// Returns the first monetary identifier mentioned by the arithmetic
+    /// expression tree rooted at `expression`, looking through nested
+    /// arithmetic, negations and casts.
+    ///
+    /// # Arguments
+    /// * `expression` (`&Expr<'_>`) - The operand to inspect.
+    fn monetary_operand(&self, expression: &Expr<'_>) -> Option<Symbol> {
+        let name: Symbol = match &expression.kind {
+            ExprKind::Binary(_, left, right) => {
+                return self
+                    .monetary_operand(left)
+                    .or_else(|| self.monetary_operand(right));
+            },
+            ExprKind::Unary(_, inner) | ExprKind::Cast(inner, _) => {
+                return self.monetary_operand(inner);
+            },
+            ExprKind::Path(QPath::Resolved(None, path)) => {
+                path.segments.last()?.ident.name
+            },
+            ExprKind::Field(_, ident) => ident.name,
+            ExprKind::MethodCall(segment, _, _, _) => segment.ident.name,
+            _ => return None,
+        };
+
+        self.is_monetary(name.as_str()).then_some(name)
+    }
+}
+
+/// Returns the operands of `expression` if it is a floating-point arithmetic
+/// operation (`+`, `-`, `*`, `/`, `%` or their compound assignments).
+///
+/// # Arguments
+/// * `context` (`&LateContext<'tcx>`) - The lint context, used to query the
+///   type of the expression.
+/// * `expression` (`&'tcx Expr<'tcx>`) - The expression to inspect.
+fn float_arithmetic<'tcx>(
+    context: &LateContext<'tcx>,
+    expression: &'tcx Expr<'tcx>,
+) -> Option<(&'tcx Expr<'tcx>, &'tcx Expr<'tcx>)> {
+    let (left, right): (&Expr<'_>, &Expr<'_>) = match &expression.kind {
+        ExprKind::Binary(operator, left, right)
+            if matches!(
+                operator.node,
+                BinOpKind::Add
+                    | BinOpKind::Sub
+                    | BinOpKind::Mul
+                    | BinOpKind::Div
+                    | BinOpKind::Rem
+            ) =>
+        {
+            (left, right)
+        },
+        ExprKind::AssignOp(operator, left, right)
+            if matches!(
+                operator.node,
+                AssignOpKind::AddAssign
+                    | AssignOpKind::SubAssign
+                    | AssignOpKind::MulAssign
+                    | AssignOpKind::DivAssign
+                    | AssignOpKind::RemAssign
+            ) =>
+        {
+            (left, right)
+        },
+        _ => return None,
+    };
+
+    let ty: Ty<'tcx> = context.typeck_results().expr_ty(left).peel_refs();
+    ty.is_floating_point().then_some((left, right))
+}
+
+impl<'tcx> LateLintPass<'tcx> for SecurityMonetaryFloat {
+    /// Detect `f32`/`f64` struct fields whose name matches a monetary pattern.
+    ///
+    /// # Arguments
+    /// * `context` (`&LateContext<'tcx>`) - The lint context providing access
+    ///   to the compiler's internal state.
+    /// * `field` (`&'tcx FieldDef<'tcx>`) - The field definition being
+    ///   checked.
+    fn check_field_def(
+        &mut self,
+        context: &LateContext<'tcx>,
+        field: &'tcx FieldDef<'tcx>,
+    ) {
+        if field.span.from_expansion()
+            || !self.is_monetary(field.ident.as_str())
+        {
+            return;
+        }
+
+        let ty: Ty<'tcx> =
+            context.tcx.type_of(field.def_id).instantiate_identity();
+        if !ty.is_floating_point() {
+            return;
+        }
+
+        security_utils::span_lint(
+            context,
+            SECURITY_MONETARY_FLOAT,
+            field.span,
+            |diagnostic: &mut Diag<'_, ()>| {
+                diagnostic.primary_message(format!(
+                    "Floating-point type `{ty}` used for monetary field `{}`.",
+                    field.ident
+                ));
+                diagnostic.help(
+                    "use an integer amount of the smallest currency unit or a \
+                     fixed-point decimal type such as `rust_decimal::Decimal`",
+                );
+            },
+        );
+    }
+
+    /// Detect floating-point arithmetic involving identifiers that match a
+    /// monetary pattern. Only the outermost arithmetic expression is reported
+    /// so that `price * quantity + fee` yields a single finding.
+    ///
+    /// # Arguments
+    /// * `context` (`&LateContext<'tcx>`) - The lint context providing access
+    ///   to the compiler's internal state.
+    /// * `expression` (`&'tcx Expr<'tcx>`) - The expression being checked for
+    ///   monetary floating-point arithmetic.
+    fn check_expr(
+        &mut self,
+        context: &LateContext<'tcx>,
+        expression: &'tcx Expr<'tcx>,
+    ) {
+        if expression.span.from_expansion() {
+            return;
+        }
+
+        let Some((left, right)): Option<(&Expr<'_>, &Expr<'_>)> =
+            float_arithmetic(context, expression)
+        else {
+            return;
+        };
+
+        // Skip if the parent expression is itself floating-point arithmetic,
+        // as it will be reported instead.
+        if let Node::Expr(parent) =
+            context.tcx.parent_hir_node(expression.hir_id)
+            && float_arithmetic(context, parent).is_some()
+        {
+            return;
+        }
+
+        let Some(name): Option<Symbol> = self
+            .monetary_operand(left)
+            .or_else(|| self.monetary_operand(right))
+        else {
+            return;
+        };
+
+        security_utils::span_lint(
+            context,
+            SECURITY_MONETARY_FLOAT,
+            expression.span,
+            |diagnostic: &mut Diag<'_, ()>| {
+                diagnostic.primary_message(format!(
+                    "Floating-point arithmetic on monetary value `{name}` \
+                     detected."
+                ));
+                diagnostic.help(
+                    "use an integer amount of the smallest currency unit or a \
+                     fixed-point decimal type such as `rust_decimal::Decimal`",
+                );
+            },
+        );
+    }
+}
+
+/// Registers the `SECURITY_MONETARY_FLOAT` lint and its corresponding lint
+/// pass with the Rust compiler. The monetary identifier patterns are read from
+/// the `[monetary_float]` table of `dylint.toml` when the pass is created.
+///
+/// # Arguments
+/// * `session` (`&Session`) - The compiler session providing access to the
+///   compiler's internal state.
+/// * `lint_store` (`&mut LintStore`) - The lint store where the
+///   `SECURITY_MONETARY_FLOAT` lint and its corresponding lint pass will be
+///   registered.
+#[unsafe(no_mangle)]
+pub fn register_lints(session: &Session, lint_store: &mut LintStore) {
+    dylint_linting::init_config(session);
+
+    security_utils::tool::register_lints(
+        lint_store,
+        &[SECURITY_MONETARY_FLOAT],
+    );
+    lint_store.register_late_pass(|tcx: TyCtxt<'_>| {
+        Box::new(SecurityMonetaryFloat::new(tcx))
+    });
+}
+
+dylint_linting::dylint_library!();
+
+/// UI tests for the `SECURITY_MONETARY_FLOAT` lint. The `ui` directory runs
+/// with the default patterns, while `ui_config` checks that patterns supplied
+/// through `dylint.toml` replace the defaults.
+#[cfg(test)]
+mod tests {
+    use dylint_testing::ui::Test;
+
+    #[test]
+    fn ui() {
+        Test::src_base(env!("CARGO_PKG_NAME"), "ui")
+            .rustc_flags(["--edition=2024", "-Z", "ui-testing"])
+            .run();
+    }
+
+    #[test]
+    fn ui_config() {
+        Test::src_base(env!("CARGO_PKG_NAME"), "ui_config")
+            .rustc_flags(["--edition=2024", "-Z", "ui-testing"])
+            .dylint_toml(r#"monetary_float.patterns = ["tip"]"#)
+            .run();
+    }
+
+    #[test]
+    fn ui_invalid_config() {
+        Test::src_base(env!("CARGO_PKG_NAME"), "ui_invalid_config")
+            .rustc_flags(["--edition=2024", "-Z", "ui-testing"])
+            .dylint_toml(r#"monetary_float.patern = ["tip"]"#)
+            .run();
+    }
+}
@@ -0,0 +1,36 @@
+#![warn(security_monetary_float)]
+
+#[allow(non_snake_case)]
+struct Invoice {
+    unit_price: f64, // Should trigger.
+    fees: f32,       // Should trigger.
+    quantity: f64,   // Should not trigger (not monetary).
+    balance: i64,    // Should not trigger (integer).
+    coffee: f64,     // Should not trigger (`fee` is not a whole word).
+    shippingCost: f64, // Should trigger.
+}
+
+/// The `main` function exercises floating-point arithmetic on monetary and
+/// non-monetary identifiers. Only the outermost arithmetic expression
+/// involving a monetary identifier should trigger the
+/// `SECURITY_MONETARY_FLOAT` lint.
+fn main() {
+    let invoice: Invoice = Invoice {
+        unit_price: 9.99,
+        fees: 0.5,
+        quantity: 3.0,
+        balance: 100,
+        coffee: 1.0,
+        shippingCost: 4.0,
+    };
+
+    let _: f64 = invoice.unit_price * invoice.quantity + 1.0; // Should trigger.
+
+    let mut total_amount: f64 = 0.0;
+    total_amount += 2.5; // Should trigger.
+
+    let _: f64 = invoice.quantity * 2.0; // Should not trigger.
+    let _: f64 = invoice.coffee + total_amount; // Should trigger.
+    let _: i64 = invoice.balance * 2; // Should not trigger (integer).
+    let _: f32 = -invoice.fees; // Should not trigger (not arithmetic).
+}
@@ -0,0 +1,15 @@
+#![warn(security_monetary_float)]
+
+struct Receipt {
+    price: f64, // Should not trigger (default pattern replaced).
+    tip: f32,   // Should trigger.
+}
+
+/// The `main` function checks that the configured `tip` pattern replaces the
+/// default monetary patterns.
+fn main() {
+    let receipt: Receipt = Receipt { price: 4.5, tip: 1.0 };
+
+    let _: f64 = receipt.price * 2.0; // Should not trigger.
+    let _: f32 = receipt.tip * 2.0; // Should trigger.
+}
@@ -0,0 +1,7 @@
+#![warn(security_monetary_float)]
+
+/// The `main` function checks that a misspelled key of the
+/// `[monetary_float]` table is reported instead of being ignored.
+fn main() {
+    let _: f64 = 4.5;
+}
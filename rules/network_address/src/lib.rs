@@ -0,0 +1,434 @@
+#![feature(rustc_private)]
+
+extern crate rustc_ast;
+extern crate rustc_errors;
+extern crate rustc_hir;
+extern crate rustc_lint;
+extern crate rustc_middle;
+extern crate rustc_session;
+extern crate rustc_span;
+
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr, SocketAddr};
+
+use rustc_ast::LitKind;
+use rustc_errors::Diag;
+use rustc_hir::{Expr, ExprKind, Node};
+use rustc_lint::{LateContext, LateLintPass, LintContext, LintStore};
+use rustc_middle::ty::{AdtDef, Ty, TyCtxt};
+use rustc_session::{Session, declare_lint, impl_lint_pass};
+use rustc_span::{Span, sym};
+use serde::Deserialize;
+
+declare_lint! {
+    pub SECURITY_HARDCODED_ADDRESS,
+    Warn,
+    "Detects hardcoded IP addresses and socket addresses outside of the allowed ranges, which bypass configuration review and disclose infrastructure details."
+}
+
+/// The address types of `std::net` whose construction from integer literals
+/// is a hardcoded address, e.g. `Ipv4Addr::new(10, 0, 0, 1)`.
+const ADDRESS_TYPES: [&str; 6] = [
+    "IpAddr",
+    "Ipv4Addr",
+    "Ipv6Addr",
+    "SocketAddr",
+    "SocketAddrV4",
+    "SocketAddrV6",
+];
+
+/// Configuration of the `network_address` library, read from the
+/// `[network_address]` table of the workspace's `dylint.toml`.
+#[derive(Deserialize)]
+#[serde(default)]
+struct Config {
+    /// Ranges in CIDR notation (e.g. `10.0.0.0/8`) whose addresses may be
+    /// hardcoded. A bare address stands for itself.
+    allowed_ranges: Vec<String>,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Self {
+            allowed_ranges: ["127.0.0.0/8", "::1/128", "0.0.0.0/32", "::/128"]
+                .into_iter()
+                .map(String::from)
+                .collect(),
+        }
+    }
+}
+
+/// A range of IP addresses: the addresses sharing the first `prefix` bits of
+/// `network`.
+struct Range {
+    network: IpAddr,
+    prefix: u32,
+}
+
+impl Range {
+    /// Parses a range in CIDR notation, or a single address.
+    ///
+    /// # Arguments
+    /// * `text` (`&str`) - The range, e.g. `10.0.0.0/8` or `::1`.
+    fn parse(text: &str) -> Option<Self> {
+        let (address, prefix): (&str, Option<&str>) =
+            match text.split_once('/') {
+                Some((address, prefix)) => (address, Some(prefix)),
+                None => (text, None),
+            };
+        let network: IpAddr = address.trim().parse().ok()?;
+        let bits: u32 = if network.is_ipv4() { 32 } else { 128 };
+        let prefix: u32 = match prefix {
+            Some(prefix) => prefix
+                .trim()
+                .parse()
+                .ok()
+                .filter(|prefix: &u32| *prefix <= bits)?,
+            None => bits,
+        };
+
+        Some(Self { network, prefix })
+    }
+
+    /// Returns `true` if `address` is in the range. IPv4 ranges do not
+    /// contain IPv6 addresses, including IPv4-mapped ones, and conversely.
+    ///
+    /// # Arguments
+    /// * `address` (`IpAddr`) - The address to check.
+    fn contains(&self, address: IpAddr) -> bool {
+        match (self.network, address) {
+            (IpAddr::V4(network), IpAddr::V4(address)) => {
+                let mask: u32 =
+                    u32::MAX.checked_shl(32 - self.prefix).unwrap_or(0);
+                u32::from(network) & mask == u32::from(address) & mask
+            },
+            (IpAddr::V6(network), IpAddr::V6(address)) => {
+                let mask: u128 =
+                    u128::MAX.checked_shl(128 - self.prefix).unwrap_or(0);
+                u128::from(network) & mask == u128::from(address) & mask
+            },
+            _ => false,
+        }
+    }
+}
+
+/// Returns the address written in `text`, with its port if any: an IP
+/// address, a socket address, or the host of a URL (e.g.
+/// `https://10.0.0.5:8443/api`).
+///
+/// # Arguments
+/// * `text` (`&str`) - The content of a string literal.
+fn parse_address(text: &str) -> Option<(IpAddr, Option<u16>)> {
+    if let Ok(address) = text.parse::<SocketAddr>() {
+        return Some((address.ip(), Some(address.port())));
+    }
+    if let Ok(address) = text.parse::<IpAddr>() {
+        return Some((address, None));
+    }
+
+    let (_, rest): (&str, &str) = text.split_once("://")?;
+    let authority: &str = rest.split(['/', '?', '#']).next()?;
+    let host: &str = authority
+        .rsplit_once('@')
+        .map_or(authority, |(_, host): (&str, &str)| host);
+    // An IPv6 host without a port keeps its brackets, e.g. `http://[::1]/`.
+    let host: &str = host
+        .strip_prefix('[')
+        .and_then(|host: &str| host.strip_suffix(']'))
+        .unwrap_or(host);
+
+    if let Ok(address) = host.parse::<SocketAddr>() {
+        return Some((address.ip(), Some(address.port())));
+    }
+    host.parse::<IpAddr>().ok().map(|address: IpAddr| (address, None))
+}
+
+/// Returns `true` if `ty` is one of the address types of `std::net`.
+///
+/// # Arguments
+/// * `tcx` (`TyCtxt<'_>`) - The type context used to name the type.
+/// * `ty` (`Ty<'_>`) - The type to check.
+fn is_address_type(tcx: TyCtxt<'_>, ty: Ty<'_>) -> bool {
+    ty.ty_adt_def().is_some_and(|adt: AdtDef<'_>| {
+        tcx.crate_name(adt.did().krate) == sym::core
+            && ADDRESS_TYPES.contains(&tcx.item_name(adt.did()).as_str())
+    })
+}
+
+/// Collects the integer literals `expression` is built from, through calls,
+/// arrays, tuples and borrows, or returns `None` if it is built from
+/// anything else, e.g. a variable or a constant.
+///
+/// # Arguments
+/// * `expression` (`&Expr<'_>`) - The expression to inspect.
+/// * `literals` (`&mut Vec<u128>`) - The literals found so far.
+fn integer_literals(
+    expression: &Expr<'_>,
+    literals: &mut Vec<u128>,
+) -> Option<()> {
+    match &expression.kind {
+        ExprKind::Lit(literal) => {
+            let LitKind::Int(value, _): LitKind = literal.node else {
+                return None;
+            };
+            literals.push(value.get());
+            Some(())
+        },
+        ExprKind::Call(_, arguments)
+        | ExprKind::Array(arguments)
+        | ExprKind::Tup(arguments) => {
+            arguments.iter().try_for_each(|argument: &Expr<'_>| {
+                integer_literals(argument, literals)
+            })
+        },
+        ExprKind::AddrOf(_, _, inner) => integer_literals(inner, literals),
+        _ => None,
+    }
+}
+
+/// Returns the address built from `literals`, with its port if any: the
+/// four octets of an IPv4 address or the eight segments of an IPv6 address,
+/// followed by the port, and by the flow information and scope of an IPv6
+/// socket address.
+///
+/// # Arguments
+/// * `literals` (`&[u128]`) - The integer literals of the construction.
+fn literal_address(literals: &[u128]) -> Option<(IpAddr, Option<u16>)> {
+    let (address, rest): (IpAddr, &[u128]) = match literals.len() {
+        4 | 5 => {
+            let (octets, rest): (&[u128], &[u128]) =
+                literals.split_at_checked(4)?;
+            let octets: Vec<u8> = octets
+                .iter()
+                .map(|octet: &u128| u8::try_from(*octet).ok())
+                .collect::<Option<Vec<u8>>>()?;
+            let octets: [u8; 4] = octets.try_into().ok()?;
+            (IpAddr::V4(Ipv4Addr::from(octets)), rest)
+        },
+        8 | 9 | 11 => {
+            let (segments, rest): (&[u128], &[u128]) =
+                literals.split_at_checked(8)?;
+            let segments: Vec<u16> = segments
+                .iter()
+                .map(|segment: &u128| u16::try_from(*segment).ok())
+                .collect::<Option<Vec<u16>>>()?;
+            let segments: [u16; 8] = segments.try_into().ok()?;
+            (IpAddr::V6(Ipv6Addr::from(segments)), rest)
+        },
+        _ => return None,
+    };
+
+    let port: Option<u16> = match rest.first() {
+        Some(port) => Some(u16::try_from(*port).ok()?),
+        None => None,
+    };
+    Some((address, port))
+}
+
+/// Returns the address built by `expression` if it is a call constructing
+/// one of the address types of `std::net` from integer literals only.
+///
+/// # Arguments
+/// * `context` (`&LateContext<'_>`) - The lint context, used to type the
+///   expression.
+/// * `expression` (`&Expr<'_>`) - The expression to inspect.
+fn constructed_address(
+    context: &LateContext<'_>,
+    expression: &Expr<'_>,
+) -> Option<(IpAddr, Option<u16>)> {
+    if !matches!(expression.kind, ExprKind::Call(..))
+        || !is_address_type(
+            context.tcx,
+            context.typeck_results().expr_ty(expression),
+        )
+    {
+        return None;
+    }
+
+    let mut literals: Vec<u128> = Vec::new();
+    integer_literals(expression, &mut literals)?;
+    literal_address(&literals)
+}
+
+/// Lint pass holding the allowed ranges loaded from the configuration.
+struct SecurityNetworkAddress {
+    allowed_ranges: Vec<Range>,
+}
+
+impl_lint_pass!(SecurityNetworkAddress => [SECURITY_HARDCODED_ADDRESS]);
+
+impl SecurityNetworkAddress {
+    fn new(tcx: TyCtxt<'_>) -> Self {
+        let config: Config =
+            security_utils::config::load(tcx, env!("CARGO_PKG_NAME"));
+
+        let mut allowed_ranges: Vec<Range> = Vec::new();
+        for text in &config.allowed_ranges {
+            match Range::parse(text) {
+                Some(range) => allowed_ranges.push(range),
+                None => {
+                    tcx.dcx().err(format!(
+                        "invalid address range `{text}` in the \
+                         `[network_address]` table of `dylint.toml`"
+                    ));
+                },
+            }
+        }
+
+        Self { allowed_ranges }
+    }
+
+    /// Reports `address` at `span` unless it is in one of the allowed
+    /// ranges.
+    ///
+    /// # Arguments
+    /// * `context` (`&LateContext<'_>`) - The lint context used to emit the
+    ///   diagnostic.
+    /// * `span` (`Span`) - The span of the literal or construction.
+    /// * `address` (`IpAddr`) - The hardcoded address.
+    /// * `port` (`Option<u16>`) - Its port, if any.
+    fn report(
+        &self,
+        context: &LateContext<'_>,
+        span: Span,
+        address: IpAddr,
+        port: Option<u16>,
+    ) {
+        if self
+            .allowed_ranges
+            .iter()
+            .any(|range: &Range| range.contains(address))
+        {
+            return;
+        }
+
+        let written: String = match port {
+            Some(port) => SocketAddr::new(address, port).to_string(),
+            None => address.to_string(),
+        };
+        security_utils::span_lint(
+            context,
+            SECURITY_HARDCODED_ADDRESS,
+            span,
+            |diagnostic: &mut Diag<'_, ()>| {
+                diagnostic.primary_message(format!(
+                    "Hardcoded address `{written}` detected."
+                ));
+                diagnostic.note(
+                    "a hardcoded endpoint bypasses the review of the \
+                     deployment configuration and discloses infrastructure \
+                     details to anyone reading the binary",
+                );
+                diagnostic.help(
+                    "read the address from the configuration or the \
+                     environment, or add its range to `allowed_ranges` in \
+                     `dylint.toml`",
+                );
+            },
+        );
+    }
+}
+
+impl<'tcx> LateLintPass<'tcx> for SecurityNetworkAddress {
+    /// Detect string literals holding an IP address, a socket address or a
+    /// URL with an IP host, and address types of `std::net` built from
+    /// integer literals. Test builds are not checked.
+    ///
+    /// # Arguments
+    /// * `context` (`&LateContext<'tcx>`) - The lint context providing access
+    ///   to the compiler's internal state.
+    /// * `expression` (`&'tcx Expr<'tcx>`) - The expression being checked.
+    fn check_expr(
+        &mut self,
+        context: &LateContext<'tcx>,
+        expression: &'tcx Expr<'tcx>,
+    ) {
+        if context.sess().opts.test || expression.span.from_expansion() {
+            return;
+        }
+
+        if let ExprKind::Lit(literal) = &expression.kind
+            && let LitKind::Str(text, _) = literal.node
+        {
+            if let Some((address, port)) = parse_address(text.as_str()) {
+                self.report(context, expression.span, address, port);
+            }
+            return;
+        }
+
+        let Some((address, port)): Option<(IpAddr, Option<u16>)> =
+            constructed_address(context, expression)
+        else {
+            return;
+        };
+
+        // Only the outermost construction is reported, e.g.
+        // `SocketAddrV4::new(Ipv4Addr::new(10, 0, 0, 1), 8080)`.
+        let mut parent: Node<'tcx> =
+            context.tcx.parent_hir_node(expression.hir_id);
+        while let Node::Expr(outer) = parent
+            && matches!(
+                outer.kind,
+                ExprKind::Array(..) | ExprKind::Tup(..) | ExprKind::AddrOf(..)
+            )
+        {
+            parent = context.tcx.parent_hir_node(outer.hir_id);
+        }
+        if let Node::Expr(outer) = parent
+            && constructed_address(context, outer).is_some()
+        {
+            return;
+        }
+
+        self.report(context, expression.span, address, port);
+    }
+}
+
+/// Registers the `SECURITY_HARDCODED_ADDRESS` lint and its corresponding lint
+/// pass with the Rust compiler. The allowed ranges are read from the
+/// `[network_address]` table of `dylint.toml` when the pass is created.
+///
+/// # Arguments
+/// * `session` (`&Session`) - The compiler session providing access to the
+///   compiler's internal state.
+/// * `lint_store` (`&mut LintStore`) - The lint store where the
+///   `SECURITY_HARDCODED_ADDRESS` lint and its corresponding lint pass will be
+///   registered.
+#[unsafe(no_mangle)]
+pub fn register_lints(session: &Session, lint_store: &mut LintStore) {
+    dylint_linting::init_config(session);
+
+    security_utils::tool::register_lints(
+        lint_store,
+        &[SECURITY_HARDCODED_ADDRESS],
+    );
+    lint_store.register_late_pass(|tcx: TyCtxt<'_>| {
+        Box::new(SecurityNetworkAddress::new(tcx))
+    });
+}
+
+dylint_linting::dylint_library!();
+
+/// UI tests for the `SECURITY_HARDCODED_ADDRESS` lint. The `ui` directory
+/// runs with the default ranges, while `ui_config` checks that ranges
+/// supplied through `dylint.toml` replace the defaults.
+#[cfg(test)]
+mod tests {
+    use dylint_testing::ui::Test;
+
+    #[test]
+    fn ui() {
+        Test::src_base(env!("CARGO_PKG_NAME"), "ui")
+            .rustc_flags(["--edition=2024", "-Z", "ui-testing"])
+            .run();
+    }
+
+    #[test]
+    fn ui_config() {
+        Test::src_base(env!("CARGO_PKG_NAME"), "ui_config")
+            .rustc_flags(["--edition=2024", "-Z", "ui-testing"])
+            .dylint_toml(
+                r#"network_address.allowed_ranges = ["10.0.0.0/8", "fd00::/8"]"#,
+            )
+            .run();
+    }
+}
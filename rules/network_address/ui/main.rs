@@ -0,0 +1,50 @@
+#![allow(dead_code)]
+
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr, SocketAddr, SocketAddrV4};
+
+const UPSTREAM: &str = "10.20.0.7:8443"; // Should trigger.
+
+fn endpoints(port: u16) {
+    // Should trigger.
+    let _: IpAddr = "192.168.1.10".parse().unwrap();
+
+    // Should trigger (the host of a URL).
+    let _ = "https://203.0.113.5/api/v1";
+
+    // Should trigger (an IPv6 socket address).
+    let _ = "[2001:db8::1]:53";
+
+    // Should trigger (built from literals).
+    let _ = Ipv4Addr::new(172, 16, 0, 1);
+
+    // Should trigger once (the outermost construction).
+    let _ = SocketAddrV4::new(Ipv4Addr::new(10, 0, 0, 1), 8080);
+
+    // Should trigger.
+    let _ = SocketAddr::from(([198, 51, 100, 2], 443));
+
+    // Should trigger.
+    let _ = Ipv6Addr::new(0x2001, 0xdb8, 0, 0, 0, 0, 0, 2);
+
+    // Should trigger (the port is not a literal, the address still is).
+    let _ = SocketAddrV4::new(Ipv4Addr::new(10, 0, 0, 2), port);
+
+    // Should not trigger (loopback and unspecified addresses).
+    let _ = "127.0.0.1:8080";
+    let _ = "http://[::1]:3000/";
+    let _ = "0.0.0.0:80";
+    let _ = Ipv4Addr::new(127, 0, 0, 1);
+    let _ = SocketAddr::from(([0, 0, 0, 0], port));
+
+    // Should not trigger (not addresses).
+    let _ = "https://example.com/api";
+    let _ = "1.2.3";
+    let _ = Ipv4Addr::from(0x0a00_0001_u32);
+}
+
+#[expect(security_hardcoded_address, reason = "the resolver is part of the protocol")]
+fn resolver() -> &'static str {
+    "9.9.9.9:53"
+}
+
+fn main() {}
@@ -0,0 +1,10 @@
+// compile-flags: --test
+
+// Should not trigger (test builds are not checked).
+const UPSTREAM: &str = "10.20.0.7:8443";
+
+#[test]
+fn connects() {
+    let _ = std::net::Ipv4Addr::new(172, 16, 0, 1);
+    let _ = UPSTREAM;
+}
@@ -0,0 +1,17 @@
+#![allow(dead_code)]
+
+use std::net::Ipv4Addr;
+
+fn endpoints() {
+    // Should not trigger (in an allowed range).
+    let _ = "10.20.0.7:8443";
+    let _ = "fd00::17";
+
+    // Should trigger (loopback is no longer allowed).
+    let _ = Ipv4Addr::new(127, 0, 0, 1);
+
+    // Should trigger.
+    let _ = "192.168.1.10";
+}
+
+fn main() {}
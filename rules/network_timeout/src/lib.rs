@@ -0,0 +1,795 @@
+#![feature(rustc_private)]
+
+extern crate rustc_ast;
+extern crate rustc_data_structures;
+extern crate rustc_errors;
+extern crate rustc_hir;
+extern crate rustc_lint;
+extern crate rustc_middle;
+extern crate rustc_session;
+extern crate rustc_span;
+
+use rustc_ast::LitKind;
+use rustc_data_structures::fx::FxHashSet;
+use rustc_errors::Diag;
+use rustc_hir::{
+    Expr,
+    ExprKind,
+    HirId,
+    MatchSource,
+    Node,
+    QPath,
+    def::{DefKind, Res},
+    def_id::DefId,
+    intravisit::{Visitor, walk_expr},
+};
+use rustc_lint::{LateContext, LateLintPass, LintStore};
+use rustc_middle::ty::{AdtDef, Ty, TyCtxt};
+use rustc_session::{Session, declare_lint, impl_lint_pass};
+use rustc_span::{Symbol, sym};
+use security_utils::calls::is_named;
+use serde::Deserialize;
+
+declare_lint! {
+    pub SECURITY_AWAIT_WITHOUT_TIMEOUT,
+    Warn,
+    "Detects network operations awaited without a timeout."
+}
+
+declare_lint! {
+    pub SECURITY_CLIENT_WITHOUT_TIMEOUT,
+    Warn,
+    "Detects HTTP clients created without a timeout."
+}
+
+declare_lint! {
+    pub SECURITY_SERVER_WITHOUT_TIMEOUT,
+    Warn,
+    "Detects HTTP servers reading requests without a timeout."
+}
+
+/// Configuration of the `network_timeout` library, read from the
+/// `[network_timeout]` table of the workspace's `dylint.toml`.
+#[derive(Deserialize)]
+#[serde(default)]
+struct Config {
+    /// Path prefixes of network clients (e.g. `reqwest` or `tokio::net`). A
+    /// future is a network operation when the called function, or the type
+    /// of its receiver, lives under one of these paths.
+    paths: Vec<String>,
+    /// Names of the functions and methods that bound a future with a timeout
+    /// (e.g. `tokio::time::timeout` or `RequestBuilder::timeout`).
+    timeout_functions: Vec<String>,
+    /// Paths of the functions creating an HTTP client, or sending a request
+    /// through a default one, whose timeouts are infinite (e.g.
+    /// `reqwest::Client::new`).
+    clients: Vec<String>,
+    /// Paths of the `build` methods of HTTP client builders (e.g.
+    /// `reqwest::ClientBuilder::build`), whose chain must configure a
+    /// timeout.
+    builders: Vec<String>,
+    /// Names of the builder methods setting a timeout on the client (e.g.
+    /// `ClientBuilder::timeout`).
+    builder_timeouts: Vec<String>,
+    /// Paths of the functions and methods serving HTTP connections (e.g.
+    /// `hyper::server::conn::http1::Builder::serve_connection` or
+    /// `axum::serve`), whose builder chain or application must configure one
+    /// of the `server_timeouts`.
+    servers: Vec<String>,
+    /// Names of the builder methods and types bounding the time a client
+    /// takes to send its request (e.g. `header_read_timeout` or
+    /// `TimeoutLayer`). A zero duration passed to one of these methods
+    /// disables the timeout.
+    server_timeouts: Vec<String>,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Self {
+            paths: [
+                "reqwest",
+                "hyper",
+                "hyper_util",
+                "surf",
+                "isahc",
+                "sqlx",
+                "tokio_postgres",
+                "redis",
+                "mongodb",
+                "tokio::net",
+                "async_std::net",
+            ]
+            .into_iter()
+            .map(String::from)
+            .collect(),
+            timeout_functions: ["timeout", "timeout_at", "connect_timeout"]
+                .into_iter()
+                .map(String::from)
+                .collect(),
+            clients: [
+                "reqwest::Client::new",
+                "ureq::agent",
+                "ureq::Agent::new",
+                "ureq::Agent::new_with_defaults",
+                "ureq::get",
+                "ureq::post",
+                "ureq::put",
+                "ureq::delete",
+                "ureq::head",
+                "ureq::patch",
+                "ureq::request",
+                "hyper::Client::new",
+            ]
+            .into_iter()
+            .map(String::from)
+            .collect(),
+            builders: [
+                "reqwest::ClientBuilder::build",
+                "ureq::AgentBuilder::build",
+                "ureq::config::ConfigBuilder::build",
+            ]
+            .into_iter()
+            .map(String::from)
+            .collect(),
+            builder_timeouts: [
+                "timeout",
+                "connect_timeout",
+                "read_timeout",
+                "timeout_connect",
+                "timeout_read",
+                "timeout_global",
+            ]
+            .into_iter()
+            .map(String::from)
+            .collect(),
+            servers: [
+                "hyper::server::Builder::serve",
+                "hyper::server::conn::http1::Builder::serve_connection",
+                "hyper::server::conn::http2::Builder::serve_connection",
+                "hyper_util::server::conn::auto::Builder::serve_connection",
+                "hyper_util::server::conn::auto::Builder::serve_connection_with_upgrades",
+                "hyper_util::server::conn::auto::Http1Builder::serve_connection",
+                "axum::serve",
+            ]
+            .into_iter()
+            .map(String::from)
+            .collect(),
+            server_timeouts: [
+                "http1_header_read_timeout",
+                "header_read_timeout",
+                "timer",
+                "keep_alive_timeout",
+                "client_request_timeout",
+                "client_disconnect_timeout",
+                "TimeoutLayer",
+            ]
+            .into_iter()
+            .map(String::from)
+            .collect(),
+        }
+    }
+}
+
+/// Lint pass holding the network paths, timeout functions, HTTP clients and
+/// servers loaded from the configuration.
+struct SecurityNetworkTimeout {
+    paths: Vec<String>,
+    timeout_functions: Vec<String>,
+    clients: Vec<String>,
+    builders: Vec<String>,
+    builder_timeouts: Vec<String>,
+    servers: Vec<String>,
+    server_timeouts: Vec<String>,
+}
+
+impl_lint_pass!(SecurityNetworkTimeout => [
+    SECURITY_AWAIT_WITHOUT_TIMEOUT,
+    SECURITY_CLIENT_WITHOUT_TIMEOUT,
+    SECURITY_SERVER_WITHOUT_TIMEOUT,
+]);
+
+/// Returns `true` if `expression` calls one of `timeout_functions`, whether
+/// as a function (`timeout(duration, future)`) or as a method
+/// (`.timeout(duration)` in a builder chain).
+///
+/// # Arguments
+/// * `expression` (`&Expr<'_>`) - The expression to inspect.
+/// * `timeout_functions` (`&[String]`) - The configured timeout functions.
+fn calls_timeout(expression: &Expr<'_>, timeout_functions: &[String]) -> bool {
+    let name: Symbol = match &expression.kind {
+        ExprKind::MethodCall(segment, _, _, _) => segment.ident.name,
+        ExprKind::Call(callee, _) => match &callee.kind {
+            ExprKind::Path(QPath::Resolved(_, path)) => {
+                match path.segments.last() {
+                    Some(segment) => segment.ident.name,
+                    None => return false,
+                }
+            },
+            ExprKind::Path(QPath::TypeRelative(_, segment)) => {
+                segment.ident.name
+            },
+            _ => return false,
+        },
+        _ => return false,
+    };
+
+    timeout_functions.iter().any(|function: &String| function == name.as_str())
+}
+
+/// Visitor searching an awaited expression for a call to a timeout function.
+struct TimeoutFinder<'a> {
+    timeout_functions: &'a [String],
+    found: bool,
+}
+
+impl<'tcx> Visitor<'tcx> for TimeoutFinder<'_> {
+    fn visit_expr(&mut self, expression: &'tcx Expr<'tcx>) {
+        if calls_timeout(expression, self.timeout_functions) {
+            self.found = true;
+            return;
+        }
+
+        walk_expr(self, expression);
+    }
+}
+
+/// Visitor searching the application passed to a server, and the
+/// initializers of the local variables it reads, for a method, function or
+/// type named after one of the server timeouts, e.g.
+/// `.layer(TimeoutLayer::new(duration))`. Each local is followed once, so
+/// that the search ends however the initializers chain.
+struct ServerTimeoutFinder<'a, 'tcx> {
+    context: &'a LateContext<'tcx>,
+    server_timeouts: &'a [String],
+    visited: FxHashSet<HirId>,
+    found: bool,
+}
+
+impl<'tcx> Visitor<'tcx> for ServerTimeoutFinder<'_, 'tcx> {
+    fn visit_expr(&mut self, expression: &'tcx Expr<'tcx>) {
+        if self.found {
+            return;
+        }
+
+        match &expression.kind {
+            ExprKind::MethodCall(segment, ..)
+                if is_named(self.server_timeouts, segment.ident.name) =>
+            {
+                self.found = true;
+                return;
+            },
+            ExprKind::Path(path) => {
+                match self.context.qpath_res(path, expression.hir_id) {
+                    Res::Local(id) => {
+                        if self.visited.insert(id)
+                            && let Node::LetStmt(local) =
+                                self.context.tcx.parent_hir_node(id)
+                            && let Some(init) = local.init
+                        {
+                            self.visit_expr(init);
+                        }
+                    },
+                    Res::Def(_, id) => {
+                        self.found =
+                            self.context.tcx.def_path_str(id).split("::").any(
+                                |segment: &str| {
+                                    self.server_timeouts
+                                        .iter()
+                                        .any(|name: &String| name == segment)
+                                },
+                            );
+                    },
+                    _ => {},
+                }
+                return;
+            },
+            _ => {},
+        }
+
+        walk_expr(self, expression);
+    }
+}
+
+/// Returns `true` if the builder chain ending with `builder` calls one of
+/// `names`, or if it does not start with a call, e.g. for a builder stored
+/// in a variable and configured elsewhere.
+///
+/// # Arguments
+/// * `builder` (`&Expr<'_>`) - The receiver of the `build` or `serve` call.
+/// * `names` (`&[String]`) - The names of the methods configuring a timeout.
+fn configures(builder: &Expr<'_>, names: &[String]) -> bool {
+    let mut current: &Expr<'_> = builder;
+    loop {
+        match &current.kind {
+            ExprKind::MethodCall(segment, receiver, _, _) => {
+                if is_named(names, segment.ident.name) {
+                    return true;
+                }
+                current = receiver;
+            },
+            ExprKind::Call(..) => return false,
+            _ => return true,
+        }
+    }
+}
+
+/// Returns `true` if `expression` is a zero `Duration`, i.e.
+/// `Duration::ZERO` or a `Duration::from_*` constructor called with `0`.
+///
+/// # Arguments
+/// * `context` (`&LateContext<'_>`) - The lint context, used to resolve the
+///   paths.
+/// * `expression` (`&Expr<'_>`) - The expression to inspect.
+fn is_zero_duration(context: &LateContext<'_>, expression: &Expr<'_>) -> bool {
+    let (path, hir_id, zero): (&QPath<'_>, HirId, bool) = match &expression
+        .kind
+    {
+        ExprKind::Path(path) => (path, expression.hir_id, true),
+        ExprKind::Call(callee, [argument]) => {
+            let ExprKind::Path(path): &ExprKind<'_> = &callee.kind else {
+                return false;
+            };
+            let zero: bool = matches!(
+                &argument.kind,
+                ExprKind::Lit(literal)
+                    if matches!(literal.node, LitKind::Int(value, _) if value.get() == 0)
+            );
+            (path, callee.hir_id, zero)
+        },
+        _ => return false,
+    };
+
+    let Res::Def(DefKind::AssocConst | DefKind::AssocFn, id): Res =
+        context.qpath_res(path, hir_id)
+    else {
+        return false;
+    };
+    let name: Symbol = context.tcx.item_name(id);
+    let parent: DefId = context.tcx.parent(id);
+    zero && (name.as_str() == "ZERO" || name.as_str().starts_with("from_"))
+        && matches!(context.tcx.def_kind(parent), DefKind::Impl { .. })
+        && context
+            .tcx
+            .type_of(parent)
+            .instantiate_identity()
+            .ty_adt_def()
+            .is_some_and(|adt: AdtDef<'_>| {
+                context.tcx.is_diagnostic_item(sym::Duration, adt.did())
+            })
+}
+
+impl SecurityNetworkTimeout {
+    fn new(tcx: TyCtxt<'_>) -> Self {
+        let config: Config =
+            security_utils::config::load(tcx, env!("CARGO_PKG_NAME"));
+
+        Self {
+            paths: config.paths,
+            timeout_functions: config.timeout_functions,
+            clients: config.clients,
+            builders: config.builders,
+            builder_timeouts: config.builder_timeouts,
+            servers: config.servers,
+            server_timeouts: config.server_timeouts,
+        }
+    }
+
+    /// Returns `true` if `path` is one of the configured network paths or an
+    /// item below one of them.
+    ///
+    /// # Arguments
+    /// * `path` (`&str`) - The full path of an item.
+    fn is_network_path(&self, path: &str) -> bool {
+        self.paths.iter().any(|prefix: &String| {
+            path.strip_prefix(prefix.as_str()).is_some_and(|rest: &str| {
+                rest.is_empty() || rest.starts_with("::")
+            })
+        })
+    }
+
+    /// Returns the path of the network operation producing the future
+    /// `awaited`, if the called function or the type of its receiver is part
+    /// of a network client.
+    ///
+    /// # Arguments
+    /// * `context` (`&LateContext<'tcx>`) - The lint context, used to resolve
+    ///   the called function.
+    /// * `awaited` (`&'tcx Expr<'tcx>`) - The expression being awaited.
+    fn network_operation<'tcx>(
+        &self,
+        context: &LateContext<'tcx>,
+        awaited: &'tcx Expr<'tcx>,
+    ) -> Option<String> {
+        let (callee, receiver): (DefId, Option<&Expr<'_>>) =
+            match &awaited.kind {
+                ExprKind::MethodCall(_, receiver, _, _) => (
+                    context
+                        .typeck_results()
+                        .type_dependent_def_id(awaited.hir_id)?,
+                    Some(receiver),
+                ),
+                ExprKind::Call(callee, _) => match &callee.kind {
+                    ExprKind::Path(path) => (
+                        context.qpath_res(path, callee.hir_id).opt_def_id()?,
+                        None,
+                    ),
+                    _ => return None,
+                },
+                _ => return None,
+            };
+
+        let path: String = context.tcx.def_path_str(callee);
+        if self.is_network_path(&path) {
+            return Some(path);
+        }
+
+        // Extension trait methods such as `AsyncReadExt::read` are defined
+        // outside the client, so the type of the receiver is checked instead.
+        let receiver_ty: Ty<'tcx> =
+            context.typeck_results().expr_ty(receiver?).peel_refs();
+        let receiver_path: String =
+            context.tcx.def_path_str(receiver_ty.ty_adt_def()?.did());
+        self.is_network_path(&receiver_path).then(|| {
+            format!("{receiver_path}::{}", context.tcx.item_name(callee))
+        })
+    }
+
+    /// Returns `true` if `awaited`, or an enclosing call such as
+    /// `timeout(duration, async { ... })`, applies a timeout.
+    ///
+    /// # Arguments
+    /// * `context` (`&LateContext<'tcx>`) - The lint context, used to walk the
+    ///   enclosing expressions.
+    /// * `expression` (`&'tcx Expr<'tcx>`) - The `.await` expression.
+    /// * `awaited` (`&'tcx Expr<'tcx>`) - The expression being awaited.
+    fn has_timeout<'tcx>(
+        &self,
+        context: &LateContext<'tcx>,
+        expression: &'tcx Expr<'tcx>,
+        awaited: &'tcx Expr<'tcx>,
+    ) -> bool {
+        let mut finder: TimeoutFinder<'_> = TimeoutFinder {
+            timeout_functions: &self.timeout_functions,
+            found: false,
+        };
+        finder.visit_expr(awaited);
+        if finder.found {
+            return true;
+        }
+
+        context.tcx.hir_parent_iter(expression.hir_id).any(
+            |(_, node): (HirId, Node<'tcx>)| {
+                matches!(node, Node::Expr(parent)
+                    if calls_timeout(parent, &self.timeout_functions))
+            },
+        )
+    }
+
+    /// Returns the path of the function or `build` method creating an HTTP
+    /// client without a timeout in `expression`, if any.
+    ///
+    /// # Arguments
+    /// * `context` (`&LateContext<'tcx>`) - The lint context, used to resolve
+    ///   the called function.
+    /// * `expression` (`&'tcx Expr<'tcx>`) - The expression to inspect.
+    fn unbounded_client<'tcx>(
+        &self,
+        context: &LateContext<'tcx>,
+        expression: &'tcx Expr<'tcx>,
+    ) -> Option<String> {
+        match &expression.kind {
+            ExprKind::Call(callee, _) => {
+                let ExprKind::Path(path): &ExprKind<'_> = &callee.kind else {
+                    return None;
+                };
+                let path: String = context.tcx.def_path_str(
+                    context.qpath_res(path, callee.hir_id).opt_def_id()?,
+                );
+                self.clients.contains(&path).then_some(path)
+            },
+            ExprKind::MethodCall(_, receiver, _, _) => {
+                let path: String = context.tcx.def_path_str(
+                    context
+                        .typeck_results()
+                        .type_dependent_def_id(expression.hir_id)?,
+                );
+                (self.builders.contains(&path)
+                    && !configures(receiver, &self.builder_timeouts))
+                .then_some(path)
+            },
+            _ => None,
+        }
+    }
+
+    /// Reports `expression` if it creates an HTTP client with infinite
+    /// timeouts.
+    ///
+    /// # Arguments
+    /// * `context` (`&LateContext<'tcx>`) - The lint context used to emit the
+    ///   diagnostic.
+    /// * `expression` (`&'tcx Expr<'tcx>`) - The expression to inspect.
+    fn check_client<'tcx>(
+        &self,
+        context: &LateContext<'tcx>,
+        expression: &'tcx Expr<'tcx>,
+    ) {
+        if expression.span.from_expansion() {
+            return;
+        }
+
+        let Some(client): Option<String> =
+            self.unbounded_client(context, expression)
+        else {
+            return;
+        };
+
+        security_utils::span_lint(
+            context,
+            SECURITY_CLIENT_WITHOUT_TIMEOUT,
+            expression.span,
+            |diagnostic: &mut Diag<'_, ()>| {
+                diagnostic.primary_message(format!(
+                    "HTTP client `{client}` without a timeout detected."
+                ));
+                diagnostic.note(
+                    "the default timeouts of the client are infinite, so a \
+                     stuck upstream pins the calling thread or task \
+                     indefinitely",
+                );
+                diagnostic.help(
+                    "configure `timeout` and `connect_timeout` (or their \
+                     equivalents) on the client builder",
+                );
+            },
+        );
+    }
+
+    /// Returns the path of the function or method serving HTTP connections
+    /// in `expression` without a timeout, if any: a method whose builder
+    /// chain never calls one of the `server_timeouts`, or a function whose
+    /// arguments (e.g. an axum `Router`) never reference one of them.
+    ///
+    /// # Arguments
+    /// * `context` (`&LateContext<'tcx>`) - The lint context, used to resolve
+    ///   the called function.
+    /// * `expression` (`&'tcx Expr<'tcx>`) - The expression to inspect.
+    fn unbounded_server<'tcx>(
+        &self,
+        context: &LateContext<'tcx>,
+        expression: &'tcx Expr<'tcx>,
+    ) -> Option<String> {
+        match &expression.kind {
+            ExprKind::Call(callee, arguments) => {
+                let ExprKind::Path(path): &ExprKind<'_> = &callee.kind else {
+                    return None;
+                };
+                let path: String = security_utils::paths::function_path(
+                    context.tcx,
+                    context.qpath_res(path, callee.hir_id).opt_def_id()?,
+                );
+                if !self.servers.contains(&path) {
+                    return None;
+                }
+                let mut finder: ServerTimeoutFinder<'_, 'tcx> =
+                    ServerTimeoutFinder {
+                        context,
+                        server_timeouts: &self.server_timeouts,
+                        visited: FxHashSet::default(),
+                        found: false,
+                    };
+                for argument in *arguments {
+                    finder.visit_expr(argument);
+                }
+                (!finder.found).then_some(path)
+            },
+            ExprKind::MethodCall(_, receiver, _, _) => {
+                let path: String = security_utils::paths::function_path(
+                    context.tcx,
+                    context
+                        .typeck_results()
+                        .type_dependent_def_id(expression.hir_id)?,
+                );
+                (self.servers.contains(&path)
+                    && !configures(receiver, &self.server_timeouts))
+                .then_some(path)
+            },
+            _ => None,
+        }
+    }
+
+    /// Reports `expression` if it serves HTTP connections without a timeout
+    /// on reading the requests, or if it disables such a timeout with a zero
+    /// duration.
+    ///
+    /// # Arguments
+    /// * `context` (`&LateContext<'tcx>`) - The lint context used to emit the
+    ///   diagnostic.
+    /// * `expression` (`&'tcx Expr<'tcx>`) - The expression to inspect.
+    fn check_server<'tcx>(
+        &self,
+        context: &LateContext<'tcx>,
+        expression: &'tcx Expr<'tcx>,
+    ) {
+        if expression.span.from_expansion() {
+            return;
+        }
+
+        if let ExprKind::MethodCall(segment, _, arguments, _) =
+            &expression.kind
+            && is_named(&self.server_timeouts, segment.ident.name)
+            && arguments
+                .iter()
+                .any(|argument: &Expr<'_>| is_zero_duration(context, argument))
+        {
+            let name: Symbol = segment.ident.name;
+            security_utils::span_lint(
+                context,
+                SECURITY_SERVER_WITHOUT_TIMEOUT,
+                segment.ident.span.with_hi(expression.span.hi()),
+                |diagnostic: &mut Diag<'_, ()>| {
+                    diagnostic.primary_message(format!(
+                        "Server timeout disabled by `{name}` detected."
+                    ));
+                    diagnostic.note(
+                        "a zero duration disables the timeout, so a client \
+                         sending its request slowly keeps its connection \
+                         open indefinitely",
+                    );
+                    diagnostic.help("set a finite, non-zero duration");
+                },
+            );
+            return;
+        }
+
+        let Some(server): Option<String> =
+            self.unbounded_server(context, expression)
+        else {
+            return;
+        };
+
+        security_utils::span_lint(
+            context,
+            SECURITY_SERVER_WITHOUT_TIMEOUT,
+            expression.span,
+            |diagnostic: &mut Diag<'_, ()>| {
+                diagnostic.primary_message(format!(
+                    "HTTP server `{server}` without a timeout detected."
+                ));
+                diagnostic.note(
+                    "clients sending their request headers slowly \
+                     (slowloris) keep their connections and tasks alive \
+                     indefinitely, so a few of them exhaust the server",
+                );
+                diagnostic.help(
+                    "set `header_read_timeout` (or a timer) on the server \
+                     builder, or add a `TimeoutLayer` to the application",
+                );
+            },
+        );
+    }
+}
+
+impl<'tcx> LateLintPass<'tcx> for SecurityNetworkTimeout {
+    /// Detect `.await` on futures returned by network clients that are not
+    /// bounded by a timeout, either in the awaited expression chain or by an
+    /// enclosing timeout call, and HTTP clients and servers created without
+    /// a timeout.
+    ///
+    /// # Arguments
+    /// * `context` (`&LateContext<'tcx>`) - The lint context providing access
+    ///   to the compiler's internal state.
+    /// * `expression` (`&'tcx Expr<'tcx>`) - The expression being checked for
+    ///   awaited network operations.
+    fn check_expr(
+        &mut self,
+        context: &LateContext<'tcx>,
+        expression: &'tcx Expr<'tcx>,
+    ) {
+        self.check_client(context, expression);
+        self.check_server(context, expression);
+
+        // `future.await` is lowered to
+        // `match IntoFuture::into_future(future) { ... }`.
+        let ExprKind::Match(scrutinee, _, MatchSource::AwaitDesugar): ExprKind<'_> =
+            expression.kind
+        else {
+            return;
+        };
+        let ExprKind::Call(_, [awaited]): ExprKind<'_> = scrutinee.kind else {
+            return;
+        };
+
+        if awaited.span.from_expansion() {
+            return;
+        }
+
+        let Some(operation): Option<String> =
+            self.network_operation(context, awaited)
+        else {
+            return;
+        };
+
+        if self.has_timeout(context, expression, awaited) {
+            return;
+        }
+
+        security_utils::span_lint(
+            context,
+            SECURITY_AWAIT_WITHOUT_TIMEOUT,
+            expression.span,
+            |diagnostic: &mut Diag<'_, ()>| {
+                diagnostic.primary_message(format!(
+                    "Network operation `{operation}` awaited without a \
+                     timeout detected."
+                ));
+                diagnostic.note(
+                    "a slow or unresponsive peer keeps this task waiting \
+                     indefinitely",
+                );
+                diagnostic.help(
+                    "wrap the future in `tokio::time::timeout` or set a \
+                     timeout on the request",
+                );
+            },
+        );
+    }
+}
+
+/// Registers the `SECURITY_AWAIT_WITHOUT_TIMEOUT`,
+/// `SECURITY_CLIENT_WITHOUT_TIMEOUT` and `SECURITY_SERVER_WITHOUT_TIMEOUT`
+/// lints and their lint pass with the Rust compiler. The network paths,
+/// timeout functions, HTTP clients and servers are read from the
+/// `[network_timeout]` table of `dylint.toml` when the pass is created.
+///
+/// # Arguments
+/// * `session` (`&Session`) - The compiler session providing access to the
+///   compiler's internal state.
+/// * `lint_store` (`&mut LintStore`) - The lint store where the lints and
+///   their lint pass will be registered.
+#[unsafe(no_mangle)]
+pub fn register_lints(session: &Session, lint_store: &mut LintStore) {
+    dylint_linting::init_config(session);
+
+    security_utils::tool::register_lints(
+        lint_store,
+        &[
+            SECURITY_AWAIT_WITHOUT_TIMEOUT,
+            SECURITY_CLIENT_WITHOUT_TIMEOUT,
+            SECURITY_SERVER_WITHOUT_TIMEOUT,
+        ],
+    );
+    lint_store.register_late_pass(|tcx: TyCtxt<'_>| {
+        Box::new(SecurityNetworkTimeout::new(tcx))
+    });
+}
+
+dylint_linting::dylint_library!();
+
+/// UI tests for the `SECURITY_AWAIT_WITHOUT_TIMEOUT`,
+/// `SECURITY_CLIENT_WITHOUT_TIMEOUT` and `SECURITY_SERVER_WITHOUT_TIMEOUT`
+/// lints. The `ui` directory uses stand-ins for `reqwest`, `ureq`, `tokio`,
+/// `hyper`, `axum`, `tower_http` and `actix_web`, while `ui_config` checks
+/// that network paths and timeout functions supplied through `dylint.toml`
+/// replace the defaults.
+#[cfg(test)]
+mod tests {
+    use dylint_testing::ui::Test;
+
+    #[test]
+    fn ui() {
+        Test::src_base(env!("CARGO_PKG_NAME"), "ui")
+            .rustc_flags(["--edition=2024", "-Z", "ui-testing"])
+            .run();
+    }
+
+    #[test]
+    fn ui_config() {
+        Test::src_base(env!("CARGO_PKG_NAME"), "ui_config")
+            .rustc_flags(["--edition=2024", "-Z", "ui-testing"])
+            .dylint_toml(
+                r#"network_timeout.paths = ["db"]
+network_timeout.timeout_functions = ["deadline"]"#,
+            )
+            .run();
+    }
+}
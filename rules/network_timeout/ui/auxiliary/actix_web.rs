@@ -0,0 +1,23 @@
+//! Minimal stand-in for the `actix-web` crate.
+
+use std::time::Duration;
+
+pub struct HttpServer;
+
+impl HttpServer {
+    pub fn new<F>(_factory: F) -> Self {
+        HttpServer
+    }
+
+    pub fn client_request_timeout(self, _timeout: Duration) -> Self {
+        self
+    }
+
+    pub fn bind(self, _address: &str) -> Result<Self, ()> {
+        Ok(self)
+    }
+
+    pub fn run(self) -> Result<(), ()> {
+        Ok(())
+    }
+}
@@ -0,0 +1,59 @@
+//! Minimal stand-in for the `hyper` crate.
+
+pub mod server {
+    use std::marker::PhantomData;
+    use std::time::Duration;
+
+    pub struct Server;
+
+    pub struct Builder<I> {
+        _incoming: PhantomData<I>,
+    }
+
+    impl Server {
+        pub fn bind(_address: &str) -> Builder<()> {
+            Builder { _incoming: PhantomData }
+        }
+    }
+
+    impl<I> Builder<I> {
+        pub fn http1_header_read_timeout(self, _timeout: Duration) -> Self {
+            self
+        }
+
+        pub fn http1_keepalive(self, _enabled: bool) -> Self {
+            self
+        }
+
+        pub fn serve<S>(self, _service: S) -> Result<(), ()> {
+            Ok(())
+        }
+    }
+
+    pub mod conn {
+        pub mod http1 {
+            use std::time::Duration;
+
+            pub struct Builder;
+
+            impl Builder {
+                pub fn new() -> Self {
+                    Builder
+                }
+
+                pub fn header_read_timeout(
+                    &mut self,
+                    _timeout: Duration,
+                ) -> &mut Self {
+                    self
+                }
+
+                pub fn keep_alive(&mut self, _enabled: bool) -> &mut Self {
+                    self
+                }
+
+                pub fn serve_connection<I, S>(&self, _io: I, _service: S) {}
+            }
+        }
+    }
+}
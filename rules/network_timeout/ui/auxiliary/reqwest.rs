@@ -0,0 +1,86 @@
+//! Minimal stand-in for the `reqwest` HTTP client.
+
+use std::time::Duration;
+
+pub struct Client;
+
+pub struct ClientBuilder;
+
+pub struct Request;
+
+pub struct Response;
+
+impl Client {
+    pub fn new() -> Self {
+        Self
+    }
+
+    pub fn builder() -> ClientBuilder {
+        ClientBuilder
+    }
+
+    pub fn get(&self, _url: &str) -> Request {
+        Request
+    }
+}
+
+impl ClientBuilder {
+    pub fn user_agent(self, _value: &str) -> Self {
+        self
+    }
+
+    pub fn timeout(self, _timeout: Duration) -> Self {
+        self
+    }
+
+    pub fn connect_timeout(self, _timeout: Duration) -> Self {
+        self
+    }
+
+    pub fn build(self) -> Result<Client, ()> {
+        Ok(Client)
+    }
+}
+
+impl Request {
+    pub fn timeout(self, _timeout: Duration) -> Self {
+        self
+    }
+
+    pub async fn send(self) -> Result<Response, ()> {
+        Ok(Response)
+    }
+}
+
+impl Response {
+    pub async fn text(self) -> Result<String, ()> {
+        Ok(String::new())
+    }
+}
+
+/// The blocking client, whose requests time out after 30 seconds by default.
+pub mod blocking {
+    pub struct Client;
+
+    pub struct ClientBuilder;
+
+    impl Client {
+        pub fn new() -> Self {
+            Self
+        }
+
+        pub fn builder() -> ClientBuilder {
+            ClientBuilder
+        }
+    }
+
+    impl ClientBuilder {
+        pub fn user_agent(self, _value: &str) -> Self {
+            self
+        }
+
+        pub fn build(self) -> Result<Client, ()> {
+            Ok(Client)
+        }
+    }
+}
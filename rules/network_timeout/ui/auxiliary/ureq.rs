@@ -0,0 +1,31 @@
+//! Minimal stand-in for the `ureq` HTTP client.
+
+use std::time::Duration;
+
+pub struct Agent;
+
+pub struct AgentBuilder;
+
+pub struct Request;
+
+pub fn agent() -> Agent {
+    Agent
+}
+
+pub fn get(_url: &str) -> Request {
+    Request
+}
+
+impl AgentBuilder {
+    pub fn new() -> Self {
+        Self
+    }
+
+    pub fn timeout_read(self, _timeout: Duration) -> Self {
+        self
+    }
+
+    pub fn build(self) -> Agent {
+        Agent
+    }
+}
@@ -0,0 +1,48 @@
+// aux-build:reqwest.rs
+// aux-build:ureq.rs
+#![warn(security_client_without_timeout)]
+
+extern crate reqwest;
+extern crate ureq;
+
+use std::time::Duration;
+
+fn unbounded() -> Result<(), ()> {
+    let _: reqwest::Client = reqwest::Client::new(); // Should trigger.
+    let _: reqwest::Client = reqwest::Client::builder()
+        .user_agent("demo")
+        .build()?; // Should trigger.
+    let _: ureq::Agent = ureq::agent(); // Should trigger.
+    let _: ureq::Request = ureq::get("https://example.com"); // Should trigger.
+    let _: ureq::Agent = ureq::AgentBuilder::new().build(); // Should trigger.
+    Ok(())
+}
+
+fn bounded(configured: reqwest::ClientBuilder) -> Result<(), ()> {
+    let duration: Duration = Duration::from_secs(5);
+
+    let _: reqwest::Client = reqwest::Client::builder()
+        .timeout(duration)
+        .user_agent("demo")
+        .build()?; // Should not trigger.
+    let _: reqwest::Client =
+        reqwest::Client::builder().connect_timeout(duration).build()?; // Should not trigger.
+    let _: ureq::Agent =
+        ureq::AgentBuilder::new().timeout_read(duration).build(); // Should not trigger.
+    let _: reqwest::Client = configured.build()?; // Should not trigger.
+    Ok(())
+}
+
+fn blocking() -> Result<(), ()> {
+    // Should not trigger: the blocking client times out after 30 seconds.
+    let _: reqwest::blocking::Client = reqwest::blocking::Client::new();
+    let _: reqwest::blocking::Client =
+        reqwest::blocking::Client::builder().user_agent("demo").build()?; // Should not trigger.
+    Ok(())
+}
+
+fn main() {
+    let _ = unbounded();
+    let _ = bounded(reqwest::Client::builder());
+    let _ = blocking();
+}
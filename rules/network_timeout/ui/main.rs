@@ -0,0 +1,51 @@
+// aux-build:reqwest.rs
+// aux-build:tokio.rs
+#![warn(security_await_without_timeout)]
+#![allow(security_client_without_timeout)]
+
+extern crate reqwest;
+extern crate tokio;
+
+use std::time::Duration;
+
+use tokio::{io::AsyncReadExt, net::TcpStream, time::timeout};
+
+async fn fetch(client: &reqwest::Client) -> Result<String, ()> {
+    let response: reqwest::Response =
+        client.get("https://example.com").send().await?; // Should trigger.
+    response.text().await // Should trigger.
+}
+
+async fn read_socket() -> Result<usize, ()> {
+    let mut stream: TcpStream = TcpStream::connect("127.0.0.1:80").await?; // Should trigger.
+    let mut buffer: [u8; 16] = [0; 16];
+    stream.read(&mut buffer).await // Should trigger.
+}
+
+async fn bounded(client: &reqwest::Client) -> Result<(), ()> {
+    let duration: Duration = Duration::from_secs(5);
+
+    let _: reqwest::Response = client
+        .get("https://example.com")
+        .timeout(duration)
+        .send()
+        .await?; // Should not trigger (timeout in the chain).
+
+    let _: Result<reqwest::Response, ()> =
+        timeout(duration, client.get("https://example.com").send()).await?; // Should not trigger.
+
+    let _: Result<TcpStream, ()> = timeout(duration, async {
+        TcpStream::connect("127.0.0.1:80").await // Should not trigger (enclosing timeout).
+    })
+    .await?;
+
+    tokio::time::sleep(duration).await; // Should not trigger (not a network operation).
+    Ok(())
+}
+
+fn main() {
+    let client: reqwest::Client = reqwest::Client::new();
+    let _ = fetch(&client);
+    let _ = read_socket();
+    let _ = bounded(&client);
+}
@@ -0,0 +1,62 @@
+// aux-build:actix_web.rs
+// aux-build:axum.rs
+// aux-build:hyper.rs
+// aux-build:tower_http.rs
+
+extern crate actix_web;
+extern crate axum;
+extern crate hyper;
+extern crate tower_http;
+
+use std::time::Duration;
+
+use actix_web::HttpServer;
+use axum::Router;
+use hyper::server::{Server, conn::http1};
+use tower_http::timeout::TimeoutLayer;
+
+fn unbounded() -> Result<(), ()> {
+    Server::bind("0.0.0.0:80").http1_keepalive(true).serve(())?; // Should trigger.
+    http1::Builder::new().serve_connection((), ()); // Should trigger.
+    axum::serve((), Router::new().route("/")) // Should trigger.
+}
+
+fn unbounded_router() -> Result<(), ()> {
+    let router: Router = Router::new().route("/");
+    axum::serve((), router) // Should trigger.
+}
+
+fn disabled() -> Result<(), ()> {
+    HttpServer::new(())
+        .client_request_timeout(Duration::ZERO) // Should trigger.
+        .bind("0.0.0.0:80")?
+        .run()?;
+    HttpServer::new(())
+        .client_request_timeout(Duration::from_secs(0)) // Should trigger.
+        .run()
+}
+
+fn bounded(configured: http1::Builder) -> Result<(), ()> {
+    let timeout: Duration = Duration::from_secs(5);
+
+    Server::bind("0.0.0.0:80")
+        .http1_header_read_timeout(timeout)
+        .serve(())?; // Should not trigger.
+    http1::Builder::new()
+        .header_read_timeout(timeout)
+        .serve_connection((), ()); // Should not trigger.
+    configured.serve_connection((), ()); // Should not trigger.
+    HttpServer::new(()).bind("0.0.0.0:80")?.run()?; // Should not trigger.
+    HttpServer::new(()).client_request_timeout(timeout).run()?; // Should not trigger.
+
+    let router: Router =
+        Router::new().route("/").layer(TimeoutLayer::new(timeout));
+    axum::serve((), router) // Should not trigger.
+}
+
+fn main() {
+    let _: Result<(), ()> = unbounded();
+    let _: Result<(), ()> = unbounded_router();
+    let _: Result<(), ()> = disabled();
+    let _: Result<(), ()> = bounded(http1::Builder::new());
+}
@@ -0,0 +1,30 @@
+#![warn(security_await_without_timeout)]
+
+use std::time::Duration;
+
+mod db {
+    pub struct Pool;
+
+    impl Pool {
+        pub async fn query(&self, _sql: &str) -> u64 {
+            0
+        }
+    }
+}
+
+async fn deadline<F: std::future::Future>(
+    _duration: Duration,
+    future: F,
+) -> F::Output {
+    future.await
+}
+
+async fn run(pool: &db::Pool) {
+    let _: u64 = pool.query("SELECT 1").await; // Should trigger.
+    let _: u64 = deadline(Duration::from_secs(1), pool.query("SELECT 1")).await; // Should not trigger.
+}
+
+fn main() {
+    let pool: db::Pool = db::Pool;
+    let _ = run(&pool);
+}
@@ -0,0 +1,461 @@
+#![feature(rustc_private)]
+
+extern crate rustc_ast;
+extern crate rustc_errors;
+extern crate rustc_hir;
+extern crate rustc_lint;
+extern crate rustc_middle;
+extern crate rustc_session;
+extern crate rustc_span;
+
+use rustc_ast::LitKind;
+use rustc_errors::Diag;
+use rustc_hir::{
+    Body,
+    Expr,
+    ExprKind,
+    FnDecl,
+    LoopSource,
+    QPath,
+    attrs::AttributeKind,
+    def::{DefKind, Res},
+    def_id::{DefId, LocalDefId},
+    find_attr,
+    intravisit::{FnKind, Visitor, walk_expr},
+};
+use rustc_lint::{LateContext, LateLintPass, LintStore};
+use rustc_middle::ty::{self, Ty, TyCtxt};
+use rustc_session::{Session, declare_lint, impl_lint_pass};
+use rustc_span::{Span, Symbol, sym};
+use security_utils::dedup::Reported;
+use serde::Deserialize;
+
+declare_lint! {
+    pub SECURITY_NO_STD_PANIC_FORMATTING,
+    Warn,
+    "Detects formatted panic messages and dynamic `expect` messages in `no_std` crates."
+}
+
+declare_lint! {
+    pub SECURITY_SILENT_PANIC_HANDLER,
+    Warn,
+    "Detects `#[panic_handler]` functions looping forever without disabling
+    interrupts, logging or resetting the device."
+}
+
+/// Configuration of the `no_std_usage` library, read from the
+/// `[no_std_usage]` table of the workspace's `dylint.toml`.
+#[derive(Deserialize)]
+#[serde(default)]
+struct Config {
+    /// Paths of the functions, or of the crates and modules whose functions,
+    /// disable interrupts, log or reset the device (e.g.
+    /// `cortex_m::peripheral::SCB::sys_reset` or `defmt`). A panic handler
+    /// calling one of them is not reported.
+    expected_functions: Vec<String>,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Self {
+            expected_functions: [
+                "cortex_m::interrupt::disable",
+                "cortex_m::peripheral::SCB::sys_reset",
+                "cortex_m::asm::bkpt",
+                "riscv::interrupt::disable",
+                "defmt",
+                "log",
+                "rtt_target",
+            ]
+            .into_iter()
+            .map(String::from)
+            .collect(),
+        }
+    }
+}
+
+/// Lint pass for `SECURITY_NO_STD_PANIC_FORMATTING`, which only reports in
+/// crates declaring `#![no_std]`, once per macro call site, and for
+/// `SECURITY_SILENT_PANIC_HANDLER`, holding the expected functions loaded
+/// from the configuration.
+struct SecurityNoStdUsage {
+    no_std: bool,
+    reported: Reported,
+    expected_functions: Vec<String>,
+}
+
+impl_lint_pass!(SecurityNoStdUsage => [
+    SECURITY_NO_STD_PANIC_FORMATTING,
+    SECURITY_SILENT_PANIC_HANDLER,
+]);
+
+impl SecurityNoStdUsage {
+    fn new(tcx: TyCtxt<'_>) -> Self {
+        let config: Config =
+            security_utils::config::load(tcx, env!("CARGO_PKG_NAME"));
+
+        Self {
+            no_std: false,
+            reported: Reported::default(),
+            expected_functions: config.expected_functions,
+        }
+    }
+
+    /// Returns `true` if `path` is one of the `expected_functions`, or a
+    /// function of one of the expected crates and modules.
+    ///
+    /// # Arguments
+    /// * `path` (`&str`) - The path of the called function.
+    fn is_expected(&self, path: &str) -> bool {
+        self.expected_functions.iter().any(|expected: &String| {
+            path.strip_prefix(expected.as_str()).is_some_and(|rest: &str| {
+                rest.is_empty() || rest.starts_with("::")
+            })
+        })
+    }
+}
+
+/// Returns `true` if `def_id` is the `new_v1` or `new_v1_formatted`
+/// constructor of `core::fmt::Arguments`, which `format_args!` uses when the
+/// format string has arguments.
+///
+/// # Arguments
+/// * `tcx` (`TyCtxt<'_>`) - The type context used to inspect the function.
+/// * `def_id` (`DefId`) - The called function.
+fn is_formatted_arguments(tcx: TyCtxt<'_>, def_id: DefId) -> bool {
+    let name: Symbol = tcx.item_name(def_id);
+
+    (name.as_str() == "new_v1" || name.as_str() == "new_v1_formatted")
+        && tcx.is_diagnostic_item(sym::FmtArgumentsNew, tcx.parent(def_id))
+}
+
+/// Visitor looking for a `core::fmt::Arguments` built with arguments.
+struct ArgumentsFinder<'a, 'tcx> {
+    context: &'a LateContext<'tcx>,
+    found: bool,
+}
+
+impl<'tcx> Visitor<'tcx> for ArgumentsFinder<'_, 'tcx> {
+    fn visit_expr(&mut self, expression: &'tcx Expr<'tcx>) {
+        if let ExprKind::Call(callee, _) = expression.kind
+            && let ExprKind::Path(qpath) = &callee.kind
+            && let Some(def_id) =
+                self.context.qpath_res(qpath, callee.hir_id).opt_def_id()
+            && is_formatted_arguments(self.context.tcx, def_id)
+        {
+            self.found = true;
+            return;
+        }
+
+        walk_expr(self, expression);
+    }
+}
+
+/// Returns the name of the `core::panicking` entry point called by
+/// `expression` if it formats its message at runtime: `panic_fmt` with
+/// formatted arguments, or `panic_display` and `unreachable_display`, used
+/// by `panic!("{}", value)` and `unreachable!("{}", value)`.
+///
+/// # Arguments
+/// * `context` (`&LateContext<'tcx>`) - The lint context.
+/// * `expression` (`&'tcx Expr<'tcx>`) - The expression to check.
+fn formatted_panic<'tcx>(
+    context: &LateContext<'tcx>,
+    expression: &'tcx Expr<'tcx>,
+) -> Option<Symbol> {
+    let ExprKind::Call(callee, arguments): ExprKind<'tcx> = expression.kind
+    else {
+        return None;
+    };
+    let ExprKind::Path(qpath): &ExprKind<'_> = &callee.kind else {
+        return None;
+    };
+    let def_id: DefId =
+        context.qpath_res(qpath, callee.hir_id).opt_def_id()?;
+    if context.tcx.crate_name(def_id.krate) != sym::core {
+        return None;
+    }
+
+    let name: Symbol = context.tcx.item_name(def_id);
+    match name.as_str() {
+        "panic_display" | "unreachable_display" => Some(name),
+        "panic_fmt" => {
+            let mut finder: ArgumentsFinder<'_, 'tcx> =
+                ArgumentsFinder { context, found: false };
+            arguments
+                .iter()
+                .for_each(|argument: &Expr<'_>| finder.visit_expr(argument));
+            finder.found.then_some(name)
+        },
+        _ => None,
+    }
+}
+
+/// Returns `true` if `message` is a string literal or a constant, which
+/// `expect` embeds without formatting.
+///
+/// # Arguments
+/// * `context` (`&LateContext<'_>`) - The lint context used to resolve paths.
+/// * `message` (`&Expr<'_>`) - The message passed to `expect`.
+fn is_static_message(context: &LateContext<'_>, message: &Expr<'_>) -> bool {
+    match &message.kind {
+        ExprKind::Lit(literal) => matches!(literal.node, LitKind::Str(..)),
+        ExprKind::Path(qpath @ QPath::Resolved(..)) => matches!(
+            context.qpath_res(qpath, message.hir_id),
+            Res::Def(DefKind::Const | DefKind::Static { .. }, _)
+        ),
+        _ => false,
+    }
+}
+
+/// Returns `true` if `expression` calls `Option::expect` or `Result::expect`
+/// with a message that is not a literal or a constant.
+///
+/// # Arguments
+/// * `context` (`&LateContext<'_>`) - The lint context.
+/// * `expression` (`&Expr<'_>`) - The expression to check.
+fn is_dynamic_expect(
+    context: &LateContext<'_>,
+    expression: &Expr<'_>,
+) -> bool {
+    let ExprKind::MethodCall(segment, receiver, [message], _): ExprKind<'_> =
+        expression.kind
+    else {
+        return false;
+    };
+    if segment.ident.name.as_str() != "expect" {
+        return false;
+    }
+
+    let receiver_ty: Ty<'_> =
+        context.typeck_results().expr_ty(receiver).peel_refs();
+    let is_option_or_result: bool =
+        receiver_ty.ty_adt_def().is_some_and(|adt: ty::AdtDef<'_>| {
+            context.tcx.is_diagnostic_item(sym::Option, adt.did())
+                || context.tcx.is_diagnostic_item(sym::Result, adt.did())
+        });
+
+    is_option_or_result && !is_static_message(context, message)
+}
+
+/// Visitor walking the body of a panic handler, recording its first
+/// `loop` and whether it calls one of the expected functions, including
+/// through the expansion of logging macros.
+struct HandlerVisitor<'a, 'tcx> {
+    context: &'a LateContext<'tcx>,
+    pass: &'a SecurityNoStdUsage,
+    hang: Option<Span>,
+    expected: bool,
+}
+
+impl<'tcx> Visitor<'tcx> for HandlerVisitor<'_, 'tcx> {
+    fn visit_expr(&mut self, expression: &'tcx Expr<'tcx>) {
+        let callee: Option<DefId> = match &expression.kind {
+            ExprKind::Call(callee, _) => match &callee.kind {
+                ExprKind::Path(qpath) => {
+                    self.context.qpath_res(qpath, callee.hir_id).opt_def_id()
+                },
+                _ => None,
+            },
+            ExprKind::MethodCall(..) => self
+                .context
+                .typeck_results()
+                .type_dependent_def_id(expression.hir_id),
+            ExprKind::Loop(_, _, LoopSource::Loop, _) => {
+                self.hang = self.hang.or(Some(expression.span));
+                None
+            },
+            _ => None,
+        };
+        if let Some(id) = callee
+            && self.pass.is_expected(&security_utils::paths::function_path(
+                self.context.tcx,
+                id,
+            ))
+        {
+            self.expected = true;
+        }
+
+        walk_expr(self, expression);
+    }
+}
+
+impl<'tcx> LateLintPass<'tcx> for SecurityNoStdUsage {
+    /// Records whether the crate declares `#![no_std]`.
+    ///
+    /// # Arguments
+    /// * `context` (`&LateContext<'tcx>`) - The lint context.
+    fn check_crate(&mut self, context: &LateContext<'tcx>) {
+        self.no_std = find_attr!(
+            context.tcx.hir_krate_attrs(),
+            AttributeKind::NoStd(..)
+        );
+    }
+
+    /// Reports the `#[panic_handler]` of the crate when it loops forever
+    /// without calling one of the `expected_functions`, which disable
+    /// interrupts, log the panic or reset the device. Only the calls made
+    /// directly by the handler, or by the macros it invokes, are considered.
+    ///
+    /// # Arguments
+    /// * `context` (`&LateContext<'tcx>`) - The lint context.
+    /// * `_kind` (`FnKind<'tcx>`) - The kind of function.
+    /// * `_declaration` (`&'tcx FnDecl<'tcx>`) - The function signature.
+    /// * `body` (`&'tcx Body<'tcx>`) - The body of the function.
+    /// * `_span` (`Span`) - The span of the function.
+    /// * `def_id` (`LocalDefId`) - The function.
+    fn check_fn(
+        &mut self,
+        context: &LateContext<'tcx>,
+        _kind: FnKind<'tcx>,
+        _declaration: &'tcx FnDecl<'tcx>,
+        body: &'tcx Body<'tcx>,
+        _span: Span,
+        def_id: LocalDefId,
+    ) {
+        if context.tcx.lang_items().panic_impl() != Some(def_id.to_def_id()) {
+            return;
+        }
+
+        let mut visitor: HandlerVisitor<'_, 'tcx> = HandlerVisitor {
+            context,
+            pass: self,
+            hang: None,
+            expected: false,
+        };
+        visitor.visit_expr(body.value);
+        let (Some(hang), false): (Option<Span>, bool) =
+            (visitor.hang, visitor.expected)
+        else {
+            return;
+        };
+
+        let name: Symbol = context.tcx.item_name(def_id.to_def_id());
+        security_utils::span_lint(
+            context,
+            SECURITY_SILENT_PANIC_HANDLER,
+            context.tcx.def_span(def_id),
+            |diagnostic: &mut Diag<'_, ()>| {
+                diagnostic.primary_message(format!(
+                    "Silent hang in panic handler `{name}` detected."
+                ));
+                diagnostic.span_note(hang, "the handler loops forever here");
+                diagnostic.note(
+                    "a device hanging on panic with interrupts still enabled \
+                     and nothing logged stops serving until it is power \
+                     cycled, which is a denial of service in the field",
+                );
+                diagnostic.help(
+                    "disable interrupts, log the panic or reset the device \
+                     (e.g. `SCB::sys_reset()`) before looping",
+                );
+            },
+        );
+    }
+
+    /// Reports panics with formatted messages and `expect` calls with
+    /// dynamic messages, which pull `core::fmt` into `no_std` binaries.
+    ///
+    /// # Arguments
+    /// * `context` (`&LateContext<'tcx>`) - The lint context.
+    /// * `expression` (`&'tcx Expr<'tcx>`) - The expression to check.
+    fn check_expr(
+        &mut self,
+        context: &LateContext<'tcx>,
+        expression: &'tcx Expr<'tcx>,
+    ) {
+        if !self.no_std {
+            return;
+        }
+
+        if let Some(function) = formatted_panic(context, expression) {
+            let span: Span = expression.span.source_callsite();
+            self.reported.span_lint(
+                context,
+                SECURITY_NO_STD_PANIC_FORMATTING,
+                span,
+                String::from(
+                    "Panic with a formatted message in a `no_std` crate \
+                     detected.",
+                ),
+                |diagnostic: &mut Diag<'_, ()>| {
+                    diagnostic.note(format!(
+                        "`core::panicking::{function}` pulls the `core::fmt` \
+                         machinery into the binary and embeds the message \
+                         in the firmware image"
+                    ));
+                    diagnostic.help(
+                        "use a string literal, or report the value through \
+                         a dedicated error channel",
+                    );
+                },
+            );
+        }
+
+        if !expression.span.from_expansion()
+            && is_dynamic_expect(context, expression)
+        {
+            security_utils::span_lint(
+                context,
+                SECURITY_NO_STD_PANIC_FORMATTING,
+                expression.span,
+                |diagnostic: &mut Diag<'_, ()>| {
+                    diagnostic.primary_message(
+                        "Dynamic `expect` message in a `no_std` crate \
+                         detected.",
+                    );
+                    diagnostic.note(
+                        "messages computed at runtime pull the `core::fmt` \
+                         machinery into the binary",
+                    );
+                    diagnostic.help(
+                        "use a string literal or a constant as the message",
+                    );
+                },
+            );
+        }
+    }
+}
+
+/// Registers the `SECURITY_NO_STD_PANIC_FORMATTING` and
+/// `SECURITY_SILENT_PANIC_HANDLER` lints and their shared lint pass with the
+/// Rust compiler. The expected functions are read from the `[no_std_usage]`
+/// table of `dylint.toml` when the pass is created.
+///
+/// # Arguments
+/// * `session` (`&Session`) - The compiler session providing access to the
+///   compiler's internal state.
+/// * `lint_store` (`&mut LintStore`) - The lint store where the lints and
+///   their lint pass will be registered.
+#[unsafe(no_mangle)]
+pub fn register_lints(session: &Session, lint_store: &mut LintStore) {
+    dylint_linting::init_config(session);
+
+    security_utils::tool::register_lints(
+        lint_store,
+        &[SECURITY_NO_STD_PANIC_FORMATTING, SECURITY_SILENT_PANIC_HANDLER],
+    );
+    lint_store.register_late_pass(|tcx: TyCtxt<'_>| {
+        Box::new(SecurityNoStdUsage::new(tcx))
+    });
+}
+
+dylint_linting::dylint_library!();
+
+/// UI tests for the `SECURITY_NO_STD_PANIC_FORMATTING` and
+/// `SECURITY_SILENT_PANIC_HANDLER` lints. The tests check that formatted
+/// panics and dynamic `expect` messages are reported in a `no_std` crate,
+/// while literal messages are not, that crates using `std` are not checked,
+/// and that panic handlers hanging silently are reported, while handlers
+/// resetting the device are not.
+#[cfg(test)]
+mod tests {
+    use dylint_testing::ui::Test;
+
+    #[test]
+    fn ui() {
+        Test::src_base(env!("CARGO_PKG_NAME"), "ui")
+            .rustc_flags(["--edition=2024", "-Z", "ui-testing"])
+            .run();
+    }
+}
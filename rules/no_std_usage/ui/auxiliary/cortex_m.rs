@@ -0,0 +1,21 @@
+// no-prefer-dynamic
+
+//! Minimal stand-in for the `cortex-m` crate, with the interrupt and reset
+//! functions a panic handler is expected to call.
+
+#![no_std]
+#![crate_type = "lib"]
+
+pub mod interrupt {
+    pub fn disable() {}
+}
+
+pub mod peripheral {
+    pub struct SCB;
+
+    impl SCB {
+        pub fn sys_reset() -> ! {
+            loop {}
+        }
+    }
+}
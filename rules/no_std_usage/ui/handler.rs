@@ -0,0 +1,10 @@
+#![no_std]
+#![crate_type = "lib"]
+
+use core::panic::PanicInfo;
+
+// Should trigger the lint: the handler hangs with interrupts enabled.
+#[panic_handler]
+fn panic(_info: &PanicInfo<'_>) -> ! {
+    loop {}
+}
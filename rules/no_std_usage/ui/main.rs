@@ -0,0 +1,27 @@
+#![no_std]
+#![crate_type = "lib"]
+
+const MESSAGE: &str = "sensor missing";
+
+pub fn read(sensor: Option<u32>, status: Result<u32, u8>, name: &str) -> u32 {
+    // Should trigger the lint: formatted panic messages.
+    if sensor == Some(0) {
+        panic!("sensor {} returned {:?}", name, sensor);
+    }
+    if sensor == Some(1) {
+        panic!("{}", name);
+    }
+    if sensor == Some(2) {
+        unreachable!("bad sensor {name}");
+    }
+
+    // Should trigger the lint: dynamic `expect` message.
+    let _: u32 = status.expect(name);
+
+    // Should not trigger the lint: literal messages.
+    if sensor == Some(3) {
+        panic!("sensor failure");
+    }
+    let _: u32 = status.expect("status");
+    sensor.expect(MESSAGE)
+}
@@ -0,0 +1,14 @@
+// aux-build:cortex_m.rs
+#![no_std]
+#![crate_type = "lib"]
+
+extern crate cortex_m;
+
+use core::panic::PanicInfo;
+
+// Should not trigger the lint: interrupts are disabled before looping.
+#[panic_handler]
+fn panic(_info: &PanicInfo<'_>) -> ! {
+    cortex_m::interrupt::disable();
+    loop {}
+}
@@ -0,0 +1,10 @@
+#![crate_type = "lib"]
+
+// Should not trigger the lint: the crate uses `std`.
+pub fn read(sensor: Option<u32>, name: &str) -> u32 {
+    if sensor == Some(0) {
+        panic!("sensor {} returned {:?}", name, sensor);
+    }
+
+    sensor.expect(name)
+}
@@ -0,0 +1,11 @@
+#![warn(security_panic_usage)]
+#![allow(security_missing_track_caller)]
+
+fn parse(text: &str) -> u16 {
+    text.parse().unwrap() // Should not trigger: bench target.
+}
+
+fn main() {
+    let port: u16 = parse("8080");
+    assert!(port > 0); // Should not trigger: bench target.
+}
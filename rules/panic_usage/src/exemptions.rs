@@ -0,0 +1,123 @@
+//! Code where panics may be accepted, as configured by the `allow_in_tests`,
+//! `allow_in_benches` and `allow_in_bins` options.
+//!
+//! A panic in a test or a benchmark fails the harness run, and a panic in
+//! `fn main` of a binary aborts the program before it serves anything: the
+//! panic is the intended report there. [`in_test`] recognizes the `#[test]`
+//! functions and `#[cfg(test)]` modules of a crate built with `--test`,
+//! [`is_bench_target`] the bench targets of a package, and [`in_main`] the
+//! entry point of a binary crate.
+
+use std::{env, ffi::OsString, path::Path};
+
+use rustc_ast::MetaItemInner;
+use rustc_hir::{
+    Attribute,
+    HirId,
+    Item,
+    ItemId,
+    ItemKind,
+    Node,
+    def::DefKind,
+};
+use rustc_middle::ty::TyCtxt;
+use rustc_session::config::CrateType;
+use rustc_span::{RealFileName, def_id::DefId, sym};
+
+/// Returns `true` if the item `id` carries `#[cfg(test)]`, kept by the
+/// compiler as a trace of the evaluated `cfg`.
+///
+/// # Arguments
+/// * `tcx` (`TyCtxt<'_>`) - The type context used to read the attributes.
+/// * `id` (`HirId`) - The item to check.
+fn is_cfg_test(tcx: TyCtxt<'_>, id: HirId) -> bool {
+    tcx.hir_attrs(id).iter().any(|attribute: &Attribute| {
+        attribute.has_name(sym::cfg_trace)
+            && attribute.meta_item_list().as_deref().is_some_and(
+                |items: &[MetaItemInner]| {
+                    matches!(items, [item] if item.has_name(sym::test))
+                },
+            )
+    })
+}
+
+/// Returns `true` if the item `id` is a `#[test]` or `#[bench]` function,
+/// i.e. a function of the module of the same name as one of its test
+/// descriptors, generated by the harness with `#[rustc_test_marker]`.
+///
+/// # Arguments
+/// * `tcx` (`TyCtxt<'_>`) - The type context used to look up the module.
+/// * `id` (`HirId`) - The item to check.
+fn is_test_fn(tcx: TyCtxt<'_>, id: HirId) -> bool {
+    let Node::Item(Item { kind: ItemKind::Fn { ident, .. }, .. }): Node<'_> =
+        tcx.hir_node(id)
+    else {
+        return false;
+    };
+
+    tcx.hir_module_free_items(tcx.parent_module(id)).any(|item: ItemId| {
+        tcx.def_kind(item.owner_id) == DefKind::Const
+            && tcx.item_name(item.owner_id.to_def_id()) == ident.name
+            && tcx.hir_attrs(item.hir_id()).iter().any(
+                |attribute: &Attribute| {
+                    attribute.has_name(sym::rustc_test_marker)
+                },
+            )
+    })
+}
+
+/// Returns `true` if `hir_id` is inside a `#[test]` function or a
+/// `#[cfg(test)]` item.
+///
+/// # Arguments
+/// * `tcx` (`TyCtxt<'_>`) - The type context used to walk the parents.
+/// * `hir_id` (`HirId`) - The node to check.
+pub fn in_test(tcx: TyCtxt<'_>, hir_id: HirId) -> bool {
+    tcx.hir_parent_id_iter(hir_id)
+        .any(|id: HirId| is_cfg_test(tcx, id) || is_test_fn(tcx, id))
+}
+
+/// Returns `true` if the crate is a Cargo bench target, whether it uses the
+/// built-in harness or not: its root is in the `benches` directory of the
+/// package being built, given by the `CARGO_MANIFEST_DIR` variable that Cargo
+/// sets for the compiler, and it is not a binary, for which Cargo also sets
+/// `CARGO_BIN_NAME`. Bench targets declared with a `path` outside of that
+/// directory are not recognized.
+///
+/// # Arguments
+/// * `tcx` (`TyCtxt<'_>`) - The type context used to read the crate root.
+pub fn is_bench_target(tcx: TyCtxt<'_>) -> bool {
+    let (Some(manifest_dir), None): (Option<OsString>, Option<OsString>) =
+        (env::var_os("CARGO_MANIFEST_DIR"), env::var_os("CARGO_BIN_NAME"))
+    else {
+        return false;
+    };
+    let (Some(root), Some(working_dir)): (
+        Option<RealFileName>,
+        Option<&Path>,
+    ) = (
+        tcx.sess.local_crate_source_file(),
+        tcx.sess.opts.working_dir.local_path(),
+    ) else {
+        return false;
+    };
+
+    root.local_path().is_some_and(|root: &Path| {
+        working_dir
+            .join(root)
+            .starts_with(Path::new(&manifest_dir).join("benches"))
+    })
+}
+
+/// Returns `true` if `hir_id` is inside the entry point of a binary crate,
+/// including its closures but not the items nested in it.
+///
+/// # Arguments
+/// * `tcx` (`TyCtxt<'_>`) - The type context used to look up the entry point.
+/// * `hir_id` (`HirId`) - The node to check.
+pub fn in_main(tcx: TyCtxt<'_>, hir_id: HirId) -> bool {
+    tcx.crate_types().contains(&CrateType::Executable)
+        && tcx.entry_fn(()).is_some_and(|(entry, _): (DefId, _)| {
+            entry == tcx.hir_get_parent_item(hir_id).to_def_id()
+        })
+}
@@ -1,5 +1,7 @@
 #![feature(rustc_private)]
 
+extern crate rustc_abi;
+extern crate rustc_ast;
 extern crate rustc_errors;
 extern crate rustc_hir;
 extern crate rustc_lint;
@@ -7,12 +9,47 @@ extern crate rustc_middle;
 extern crate rustc_session;
 extern crate rustc_span;
 
-use rustc_errors::Diag;
-use rustc_hir::{Expr, ExprKind};
-use rustc_lint::{LateContext, LateLintPass, LintContext, LintStore};
-use rustc_middle::ty::TyCtxt;
-use rustc_session::{Session, declare_lint, declare_lint_pass};
-use rustc_span::sym;
+mod exemptions;
+
+use rustc_abi::ExternAbi;
+use rustc_errors::{Applicability, Diag};
+use rustc_hir::{
+    BinOpKind,
+    BindingMode,
+    Body,
+    Expr,
+    ExprKind,
+    FnDecl,
+    FnHeader,
+    HirId,
+    LetStmt,
+    QPath,
+    def::{DefKind, Res},
+    def_id::{DefId, LocalDefId},
+    intravisit::{FnKind, Visitor, walk_expr},
+};
+use rustc_lint::{LateContext, LateLintPass, LintStore};
+use rustc_middle::{
+    middle::codegen_fn_attrs::CodegenFnAttrFlags,
+    ty::{Ty, TyCtxt, TyKind},
+};
+use rustc_session::{Session, declare_lint, impl_lint_pass};
+use rustc_span::{
+    ExpnData,
+    ExpnKind,
+    Ident,
+    MacroKind,
+    Span,
+    Symbol,
+    source_map::SourceMap,
+    sym,
+};
+use security_utils::{
+    allow::matches_pattern,
+    dedup::Reported,
+    known::{DefIdCache, KnownItems, is_panicking_module_fn},
+};
+use serde::Deserialize;
 
 declare_lint! {
     pub SECURITY_PANIC_USAGE,
@@ -20,7 +57,180 @@ declare_lint! {
     "Detects constructs that may panic at runtime."
 }
 
-declare_lint_pass!(SecurityPanicUsage => [SECURITY_PANIC_USAGE]);
+declare_lint! {
+    pub SECURITY_PARTIAL_CMP_UNWRAP,
+    Deny,
+    "Detects floating-point comparisons with `partial_cmp(..).unwrap()`, which panic on NaN."
+}
+
+declare_lint! {
+    pub SECURITY_MISSING_TRACK_CALLER,
+    Warn,
+    "Detects functions that unconditionally panic, or forward their arguments
+    to `unwrap`, without `#[track_caller]`."
+}
+
+declare_lint! {
+    pub SECURITY_SILENT_PANIC_HOOK,
+    Warn,
+    "Detects panic hooks that neither log nor forward the panic information."
+}
+
+/// Configuration of the `panic_usage` library, read from the `[panic_usage]`
+/// table of the workspace's `dylint.toml`.
+#[derive(Default, Deserialize)]
+#[serde(default)]
+struct Config {
+    /// Paths of the types, besides `Option` and `Result`, whose `unwrap`
+    /// and `expect` methods panic (e.g. `crate::checked::Checked`). A call
+    /// to one of them is reported.
+    panicking_types: Vec<String>,
+    /// Whether panics in `#[test]` functions and `#[cfg(test)]` modules are
+    /// accepted.
+    allow_in_tests: bool,
+    /// Whether panics in the bench targets, built from the `benches`
+    /// directory of the package, are accepted.
+    allow_in_benches: bool,
+    /// Whether panics in `fn main` of binary crates are accepted.
+    allow_in_bins: bool,
+}
+
+/// Lint pass for the lints of the `panic_usage` library, holding the
+/// panicking types and exemptions loaded from the configuration (a bench
+/// target being exempted as a whole) and reporting each panic
+/// once per macro call site. The panic entry points are resolved once per
+/// crate, and the backend of each called function is cached.
+struct SecurityPanicUsage {
+    panicking_types: Vec<String>,
+    allow_in_tests: bool,
+    allow_in_bench_target: bool,
+    allow_in_bins: bool,
+    reported: Reported,
+    known: KnownItems,
+    backends: DefIdCache<Option<PanicBackend>>,
+}
+
+impl_lint_pass!(SecurityPanicUsage => [
+    SECURITY_PANIC_USAGE,
+    SECURITY_PARTIAL_CMP_UNWRAP,
+    SECURITY_MISSING_TRACK_CALLER,
+    SECURITY_SILENT_PANIC_HOOK,
+]);
+
+impl SecurityPanicUsage {
+    fn new(tcx: TyCtxt<'_>) -> Self {
+        let config: Config =
+            security_utils::config::load(tcx, env!("CARGO_PKG_NAME"));
+
+        Self {
+            panicking_types: config.panicking_types,
+            allow_in_tests: config.allow_in_tests,
+            allow_in_bench_target: config.allow_in_benches
+                && exemptions::is_bench_target(tcx),
+            allow_in_bins: config.allow_in_bins,
+            reported: Reported::default(),
+            known: KnownItems::default(),
+            backends: DefIdCache::default(),
+        }
+    }
+
+    /// Returns `true` if panics at `hir_id` are accepted by the
+    /// configuration: in tests, bench targets or `fn main` of binaries.
+    ///
+    /// # Arguments
+    /// * `tcx` (`TyCtxt<'_>`) - The type context.
+    /// * `hir_id` (`HirId`) - The panicking expression.
+    fn is_exempt(&self, tcx: TyCtxt<'_>, hir_id: HirId) -> bool {
+        self.allow_in_bench_target
+            || (self.allow_in_tests && exemptions::in_test(tcx, hir_id))
+            || (self.allow_in_bins && exemptions::in_main(tcx, hir_id))
+    }
+
+    /// Returns `true` if the method call `expression` is an `unwrap` or
+    /// `expect` of `Option` or `Result`, resolved through the called method
+    /// rather than its name, or a method named so on one of the configured
+    /// panicking types.
+    ///
+    /// # Arguments
+    /// * `context` (`&LateContext<'_>`) - The lint context.
+    /// * `expression` (`&Expr<'_>`) - The method call.
+    fn is_panicking_unwrap(
+        &self,
+        context: &LateContext<'_>,
+        expression: &Expr<'_>,
+    ) -> bool {
+        let ExprKind::MethodCall(segment, receiver, _, _): ExprKind<'_> =
+            expression.kind
+        else {
+            return false;
+        };
+        if context
+            .typeck_results()
+            .type_dependent_def_id(expression.hir_id)
+            .is_some_and(|def_id: DefId| is_unwrap(context, def_id))
+        {
+            return true;
+        }
+        if self.panicking_types.is_empty()
+            || !matches!(segment.ident.name.as_str(), "unwrap" | "expect")
+        {
+            return false;
+        }
+
+        let TyKind::Adt(adt, _): &TyKind<'_> = context
+            .typeck_results()
+            .expr_ty_adjusted(receiver)
+            .peel_refs()
+            .kind()
+        else {
+            return false;
+        };
+        let path: String = context.tcx.def_path_str(adt.did());
+
+        self.panicking_types
+            .iter()
+            .any(|pattern: &String| matches_pattern(pattern, &path))
+    }
+}
+
+/// Returns the operands of `expression` if it is `a.partial_cmp(b).unwrap()`
+/// or `a.partial_cmp(b).expect(..)` on floating-point values, typically the
+/// body of a `sort_by` comparator or of a hand-written `Ord` implementation.
+/// The closures of `sort_by_key`, `min_by_key` and `max_by_key` cannot return
+/// floats, which are not `Ord`, so their float keys are reported at the `Ord`
+/// implementation of the wrapper type they go through. A comparison of values
+/// of the wrapper itself, such as `self.partial_cmp(other).unwrap()` over a
+/// derived `PartialOrd`, is not recognized.
+///
+/// # Arguments
+/// * `context` (`&LateContext<'tcx>`) - The lint context used to type the
+///   operands.
+/// * `expression` (`&'tcx Expr<'tcx>`) - The expression to check.
+fn float_partial_cmp_unwrap<'tcx>(
+    context: &LateContext<'tcx>,
+    expression: &'tcx Expr<'tcx>,
+) -> Option<(&'tcx Expr<'tcx>, &'tcx Expr<'tcx>)> {
+    let ExprKind::MethodCall(segment, receiver, _, _): ExprKind<'tcx> =
+        expression.kind
+    else {
+        return None;
+    };
+    if !matches!(segment.ident.name.as_str(), "unwrap" | "expect") {
+        return None;
+    }
+
+    let ExprKind::MethodCall(compare, left, [right], _): ExprKind<'tcx> =
+        receiver.kind
+    else {
+        return None;
+    };
+    let operand_ty: Ty<'tcx> =
+        context.typeck_results().expr_ty(left).peel_refs();
+
+    (compare.ident.name.as_str() == "partial_cmp"
+        && operand_ty.is_floating_point())
+    .then_some((left, right))
+}
 
 /// Enum representing the different panic backends that can be detected by the
 /// `SECURITY_PANIC_USAGE` lint, such as the `panicking` module, the
@@ -36,24 +246,573 @@ enum PanicBackend {
 }
 
 impl PanicBackend {
-    fn from_def_path(path: &str) -> Option<Self> {
-        if path.contains("panicking::") {
-            Some(Self::PanickingModule)
-        } else if path.contains("panic_fmt") {
-            Some(Self::PanicFmt)
-        } else if path.contains("panic_display") {
-            Some(Self::PanicDisplay)
-        } else if path.contains("assert_failed") {
-            Some(Self::AssertFailed)
-        } else if path.contains("begin_panic") {
-            Some(Self::BeginPanic)
+    /// Returns the panic backend `def_id` is, if any. The entry points with a
+    /// lang item are compared with the `known` items, and the other diverging
+    /// functions of the `core`/`std` `panicking` modules are recognized by
+    /// their parent modules (see [`is_panicking_module_fn`]), so that no path
+    /// is formatted for every called function and user items named after
+    /// these modules are not matched.
+    ///
+    /// # Arguments
+    /// * `tcx` (`TyCtxt<'_>`) - The type context used to look up the parent
+    ///   modules.
+    /// * `known` (`&KnownItems`) - The panic entry points of the crate.
+    /// * `def_id` (`DefId`) - The called function.
+    fn from_def_id(
+        tcx: TyCtxt<'_>,
+        known: &KnownItems,
+        def_id: DefId,
+    ) -> Option<Self> {
+        if known.panic_fmt == Some(def_id) {
+            return Some(Self::PanicFmt);
+        }
+        if known.panic_display == Some(def_id) {
+            return Some(Self::PanicDisplay);
+        }
+        if known.begin_panic == Some(def_id) {
+            return Some(Self::BeginPanic);
+        }
+
+        is_panicking_module_fn(tcx, def_id).then(|| {
+            if tcx.item_name(def_id).as_str() == "assert_failed" {
+                Self::AssertFailed
+            } else {
+                Self::PanickingModule
+            }
+        })
+    }
+}
+
+/// Returns the name of the outermost function-like macro of `core` or `std`
+/// that `span` was expanded from, if any (e.g. `assert_eq` for the
+/// `assert_failed` call generated by `assert_eq!`). User macros wrapping
+/// one of them are skipped, so that the reported macro is the one that
+/// panics rather than the wrapper.
+///
+/// # Arguments
+/// * `tcx` (`TyCtxt<'_>`) - The type context used to look up the crates of the
+///   macros.
+/// * `span` (`Span`) - The span of the call to the panic backend.
+fn originating_macro(tcx: TyCtxt<'_>, span: Span) -> Option<Symbol> {
+    let mut name: Option<Symbol> = None;
+    let mut current: Span = span;
+    while current.from_expansion() {
+        let data: ExpnData = current.ctxt().outer_expn_data();
+        if let ExpnKind::Macro(MacroKind::Bang, macro_name) = data.kind
+            && let Some(macro_id) = data.macro_def_id
+            && matches!(tcx.crate_name(macro_id.krate), sym::core | sym::std)
+        {
+            name = Some(macro_name);
+        }
+        current = data.call_site;
+    }
+
+    name
+}
+
+/// Returns `true` if `def_id` is the `unwrap` or `expect` method of the
+/// inherent impl of `Option` or `Result`. The method is resolved through
+/// its impl, as only some of them are diagnostic items.
+///
+/// # Arguments
+/// * `context` (`&LateContext<'_>`) - The lint context, used to look up the
+///   diagnostic items.
+/// * `def_id` (`DefId`) - The called method.
+fn is_unwrap(context: &LateContext<'_>, def_id: DefId) -> bool {
+    let Some(implementation): Option<DefId> =
+        context.tcx.impl_of_assoc(def_id)
+    else {
+        return false;
+    };
+    if context.tcx.trait_id_of_impl(implementation).is_some()
+        || !matches!(
+            context.tcx.item_name(def_id).as_str(),
+            "unwrap" | "expect"
+        )
+    {
+        return false;
+    }
+
+    match context.tcx.type_of(implementation).instantiate_identity().kind() {
+        TyKind::Adt(adt, _) => {
+            context.tcx.is_diagnostic_item(sym::Option, adt.did())
+                || context.tcx.is_diagnostic_item(sym::Result, adt.did())
+        },
+        _ => false,
+    }
+}
+
+/// Type `?` propagates, as unwrapped by `unwrap` or `expect` or returned by
+/// the enclosing function.
+#[derive(Clone, Copy)]
+enum Fallible<'tcx> {
+    Option,
+    /// A `Result`, with its error type.
+    Result(Ty<'tcx>),
+}
+
+impl<'tcx> Fallible<'tcx> {
+    /// Returns the `Option` or `Result` type `ty` is, if any.
+    ///
+    /// # Arguments
+    /// * `context` (`&LateContext<'tcx>`) - The lint context, used to look up
+    ///   the diagnostic items.
+    /// * `ty` (`Ty<'tcx>`) - The type to check.
+    fn of(context: &LateContext<'tcx>, ty: Ty<'tcx>) -> Option<Self> {
+        let TyKind::Adt(adt, arguments): &TyKind<'tcx> = ty.kind() else {
+            return None;
+        };
+        if context.tcx.is_diagnostic_item(sym::Option, adt.did()) {
+            Some(Self::Option)
+        } else if context.tcx.is_diagnostic_item(sym::Result, adt.did()) {
+            Some(Self::Result(
+                context.tcx.erase_and_anonymize_regions(arguments.type_at(1)),
+            ))
         } else {
             None
         }
     }
 }
 
+/// Returns the type returned by the function whose body holds `expression`,
+/// if it is a function or method that may use `?`, i.e. neither a closure,
+/// an `async` block or function, nor a constant context.
+///
+/// # Arguments
+/// * `context` (`&LateContext<'tcx>`) - The lint context.
+/// * `expression` (`&Expr<'_>`) - The expression inside the function.
+fn enclosing_return<'tcx>(
+    context: &LateContext<'tcx>,
+    expression: &Expr<'_>,
+) -> Option<Ty<'tcx>> {
+    let owner: LocalDefId =
+        context.tcx.hir_enclosing_body_owner(expression.hir_id);
+    if !matches!(context.tcx.def_kind(owner), DefKind::Fn | DefKind::AssocFn)
+        || context.tcx.hir_body_const_context(owner).is_some()
+    {
+        return None;
+    }
+
+    Some(
+        context
+            .tcx
+            .instantiate_bound_regions_with_erased(
+                context.tcx.fn_sig(owner).instantiate_identity(),
+            )
+            .output(),
+    )
+}
+
+/// Suggests replacing the `unwrap` or `expect` call `expression` on
+/// `receiver` with `?` when the enclosing function returns a compatible
+/// type. The fix is machine-applicable when `?` propagates the same type,
+/// and hints at `.ok_or(..)`, `.ok()` or `.map_err(..)` when a conversion
+/// is needed.
+///
+/// # Arguments
+/// * `context` (`&LateContext<'tcx>`) - The lint context.
+/// * `diagnostic` (`&mut Diag<'_, ()>`) - The diagnostic to extend.
+/// * `expression` (`&Expr<'_>`) - The `unwrap` or `expect` call.
+/// * `receiver` (`&Expr<'_>`) - The unwrapped value.
+fn suggest_propagation<'tcx>(
+    context: &LateContext<'tcx>,
+    diagnostic: &mut Diag<'_, ()>,
+    expression: &Expr<'_>,
+    receiver: &Expr<'_>,
+) {
+    if expression.span.from_expansion()
+        || !receiver.span.eq_ctxt(expression.span)
+    {
+        return;
+    }
+    let Some((unwrapped, returned)): Option<(Fallible<'tcx>, Fallible<'tcx>)> =
+        Fallible::of(
+            context,
+            context.typeck_results().expr_ty_adjusted(receiver),
+        )
+        .zip(
+            enclosing_return(context, expression)
+                .and_then(|ty: Ty<'tcx>| Fallible::of(context, ty)),
+        )
+    else {
+        return;
+    };
+    let span: Span = expression.span.with_lo(receiver.span.hi());
+
+    match (unwrapped, returned) {
+        (Fallible::Option, Fallible::Option) => {
+            diagnostic.span_suggestion(
+                span,
+                "propagate `None` to the caller with `?`",
+                "?",
+                Applicability::MachineApplicable,
+            );
+        },
+        (Fallible::Result(error), Fallible::Result(returned))
+            if error == returned =>
+        {
+            diagnostic.span_suggestion(
+                span,
+                "propagate the error to the caller with `?`",
+                "?",
+                Applicability::MachineApplicable,
+            );
+        },
+        (Fallible::Result(error), Fallible::Result(returned)) => {
+            diagnostic.span_suggestion(
+                span,
+                "propagate the error to the caller with `?`",
+                "?",
+                Applicability::MaybeIncorrect,
+            );
+            diagnostic.note(format!(
+                "`?` converts `{error}` into `{returned}` only if `{returned}` \
+                 implements `From<{error}>`, otherwise convert it with \
+                 `.map_err(..)?`"
+            ));
+        },
+        (Fallible::Option, Fallible::Result(_)) => {
+            diagnostic.help(
+                "convert `None` into an error with `.ok_or(..)?` to propagate \
+                 it to the caller",
+            );
+        },
+        (Fallible::Result(_), Fallible::Option) => {
+            diagnostic.help(
+                "discard the error with `.ok()?` to propagate `None` to the \
+                 caller",
+            );
+        },
+    }
+}
+
+/// Returns `true` if `span` comes from `todo!` or `unimplemented!`, whose
+/// functions are placeholders rather than panicking helpers.
+///
+/// # Arguments
+/// * `context` (`&LateContext<'_>`) - The lint context, used to look up the
+///   diagnostic items.
+/// * `span` (`Span`) - The span of the panic.
+fn is_placeholder(context: &LateContext<'_>, span: Span) -> bool {
+    span.ctxt()
+        .outer_expn_data()
+        .macro_def_id
+        .and_then(|macro_id: DefId| context.tcx.get_diagnostic_name(macro_id))
+        .is_some_and(|name: Symbol| {
+            matches!(name, sym::todo_macro | sym::unimplemented_macro)
+        })
+}
+
+/// Visitor finding the panic a function body reaches unconditionally: a call
+/// to a panic backend, or an `unwrap` or `expect` on a value derived from a
+/// parameter, the caller then being responsible for the panic. Branches
+/// (`if`, `match`, loops, the right operand of `&&` and `||` and the `else`
+/// of `let`-`else`), closures and nested items are not entered. The span of
+/// the panic is kept within its macro expansion, so that placeholders can be
+/// told apart.
+struct PanicFinder<'a, 'tcx> {
+    context: &'a LateContext<'tcx>,
+    known: &'a KnownItems,
+    backends: &'a mut DefIdCache<Option<PanicBackend>>,
+    parameters: Vec<HirId>,
+    panic: Option<Span>,
+}
+
+impl PanicFinder<'_, '_> {
+    /// Returns `true` if `expression` is a call to a panic backend, or an
+    /// `unwrap` or `expect` on a value derived from a parameter.
+    ///
+    /// # Arguments
+    /// * `expression` (`&Expr<'_>`) - The expression to check.
+    fn is_panic(&mut self, expression: &Expr<'_>) -> bool {
+        match &expression.kind {
+            ExprKind::Call(function, _) => {
+                let ExprKind::Path(path): &ExprKind<'_> = &function.kind
+                else {
+                    return false;
+                };
+                self.context
+                    .qpath_res(path, function.hir_id)
+                    .opt_def_id()
+                    .and_then(|def_id: DefId| {
+                        self.backends.get_or_insert_with(def_id, || {
+                            PanicBackend::from_def_id(
+                                self.context.tcx,
+                                self.known,
+                                def_id,
+                            )
+                        })
+                    })
+                    .is_some()
+            },
+            ExprKind::MethodCall(_, receiver, _, _) => {
+                self.context
+                    .typeck_results()
+                    .type_dependent_def_id(expression.hir_id)
+                    .is_some_and(|def_id: DefId| {
+                        is_unwrap(self.context, def_id)
+                    })
+                    && self.is_parameter(receiver)
+            },
+            _ => false,
+        }
+    }
+
+    /// Returns `true` if `expression` is a parameter, or a field, reference,
+    /// dereference or method call (e.g. `as_ref()`) on one.
+    ///
+    /// # Arguments
+    /// * `expression` (`&Expr<'_>`) - The receiver of the `unwrap` call.
+    fn is_parameter(&self, expression: &Expr<'_>) -> bool {
+        match &expression.kind {
+            ExprKind::Path(QPath::Resolved(None, path)) => {
+                matches!(path.res, Res::Local(id) if self.parameters.contains(&id))
+            },
+            ExprKind::Field(base, _)
+            | ExprKind::AddrOf(_, _, base)
+            | ExprKind::Unary(_, base)
+            | ExprKind::MethodCall(_, base, _, _) => self.is_parameter(base),
+            _ => false,
+        }
+    }
+}
+
+impl<'tcx> Visitor<'tcx> for PanicFinder<'_, 'tcx> {
+    fn visit_expr(&mut self, expression: &'tcx Expr<'tcx>) {
+        if self.panic.is_some() {
+            return;
+        }
+
+        match &expression.kind {
+            ExprKind::If(..)
+            | ExprKind::Match(..)
+            | ExprKind::Loop(..)
+            | ExprKind::Closure(..)
+            | ExprKind::ConstBlock(..) => {},
+            ExprKind::Binary(operator, left, _)
+                if matches!(operator.node, BinOpKind::And | BinOpKind::Or) =>
+            {
+                self.visit_expr(left);
+            },
+            _ if self.is_panic(expression) => {
+                self.panic = Some(expression.span);
+            },
+            _ => walk_expr(self, expression),
+        }
+    }
+
+    fn visit_local(&mut self, local: &'tcx LetStmt<'tcx>) {
+        if let Some(init) = local.init {
+            self.visit_expr(init);
+        }
+    }
+}
+
+/// Returns the bindings of the parameters of `body`.
+///
+/// # Arguments
+/// * `body` (`&Body<'_>`) - The body of the function or closure.
+fn parameters(body: &Body<'_>) -> Vec<HirId> {
+    let mut parameters: Vec<HirId> = Vec::new();
+    for param in body.params {
+        param.pat.each_binding(
+            |_: BindingMode, id: HirId, _: Span, _: Ident| {
+                parameters.push(id);
+            },
+        );
+    }
+
+    parameters
+}
+
+/// Visitor checking whether an expression reads one of `parameters`.
+struct ParameterUse {
+    parameters: Vec<HirId>,
+    used: bool,
+}
+
+impl<'tcx> Visitor<'tcx> for ParameterUse {
+    fn visit_expr(&mut self, expression: &'tcx Expr<'tcx>) {
+        if let ExprKind::Path(QPath::Resolved(None, path)) = &expression.kind
+            && let Res::Local(id) = path.res
+            && self.parameters.contains(&id)
+        {
+            self.used = true;
+        }
+        walk_expr(self, expression);
+    }
+}
+
+/// Returns the body of the hook passed to `std::panic::set_hook`, i.e. of
+/// the closure or local function boxed by `argument`.
+///
+/// # Arguments
+/// * `context` (`&LateContext<'tcx>`) - The lint context, used to resolve the
+///   hook.
+/// * `argument` (`&'tcx Expr<'tcx>`) - The argument of `set_hook`.
+fn hook_body<'tcx>(
+    context: &LateContext<'tcx>,
+    argument: &'tcx Expr<'tcx>,
+) -> Option<&'tcx Body<'tcx>> {
+    let hook: &'tcx Expr<'tcx> = match &argument.kind {
+        ExprKind::Call(_, [hook])
+            if context.typeck_results().expr_ty(argument).is_box() =>
+        {
+            hook
+        },
+        _ => return None,
+    };
+
+    match &hook.kind {
+        ExprKind::Closure(closure) => Some(context.tcx.hir_body(closure.body)),
+        ExprKind::Path(path) => {
+            let Res::Def(DefKind::Fn, def_id): Res =
+                context.qpath_res(path, hook.hir_id)
+            else {
+                return None;
+            };
+            context.tcx.hir_maybe_body_owned_by(def_id.as_local()?)
+        },
+        _ => None,
+    }
+}
+
+/// Returns the name of the function `kind` describes, if it is a function or
+/// method that may carry `#[track_caller]`, i.e. not a closure, an `async`
+/// function or a function with a non-Rust ABI.
+///
+/// # Arguments
+/// * `kind` (`&FnKind<'_>`) - The kind of the function.
+fn trackable(kind: &FnKind<'_>) -> Option<Ident> {
+    let (ident, header): (Ident, FnHeader) = match kind {
+        FnKind::ItemFn(ident, _, header) => (*ident, *header),
+        FnKind::Method(ident, sig) => (*ident, sig.header),
+        FnKind::Closure => return None,
+    };
+
+    (!header.is_async() && header.abi == ExternAbi::Rust).then_some(ident)
+}
+
+/// Returns the leading whitespace of the line of `span`, if nothing else
+/// precedes `span` on it.
+///
+/// # Arguments
+/// * `source_map` (`&SourceMap`) - The source map, used to read the line.
+/// * `span` (`Span`) - The span to indent like.
+fn indentation(source_map: &SourceMap, span: Span) -> Option<String> {
+    let before: String = source_map.span_to_prev_source(span).ok()?;
+    let line: &str = before.rsplit('\n').next().unwrap_or_default();
+
+    line.chars().all(char::is_whitespace).then(|| line.to_owned())
+}
+
 impl<'tcx> LateLintPass<'tcx> for SecurityPanicUsage {
+    /// Resolves the panic entry points of the crate.
+    ///
+    /// # Arguments
+    /// * `context` (`&LateContext<'tcx>`) - The lint context.
+    fn check_crate(&mut self, context: &LateContext<'tcx>) {
+        self.known = KnownItems::new(context.tcx);
+    }
+
+    /// Detect functions that unconditionally panic, or forward a parameter
+    /// to `unwrap` or `expect`, without `#[track_caller]`, whose panic
+    /// messages then point at the function instead of its caller. The entry
+    /// point, placeholders built on `todo!` or `unimplemented!` and functions
+    /// expanded from macros are skipped.
+    ///
+    /// # Arguments
+    /// * `context` (`&LateContext<'tcx>`) - The lint context providing access
+    ///   to the compiler's internal state.
+    /// * `kind` (`FnKind<'tcx>`) - The kind of the function.
+    /// * `_` (`&'tcx FnDecl<'tcx>`) - The declaration of the function.
+    /// * `body` (`&'tcx Body<'tcx>`) - The body of the function.
+    /// * `span` (`Span`) - The span of the function.
+    /// * `def_id` (`LocalDefId`) - The function being checked.
+    fn check_fn(
+        &mut self,
+        context: &LateContext<'tcx>,
+        kind: FnKind<'tcx>,
+        _: &'tcx FnDecl<'tcx>,
+        body: &'tcx Body<'tcx>,
+        span: Span,
+        def_id: LocalDefId,
+    ) {
+        let Some(ident): Option<Ident> = trackable(&kind) else {
+            return;
+        };
+        if span.from_expansion()
+            || context.tcx.entry_fn(()).is_some_and(
+                |(entry, _): (DefId, _)| entry == def_id.to_def_id(),
+            )
+            || context
+                .tcx
+                .codegen_fn_attrs(def_id)
+                .flags
+                .contains(CodegenFnAttrFlags::TRACK_CALLER)
+        {
+            return;
+        }
+
+        let mut finder: PanicFinder<'_, 'tcx> = PanicFinder {
+            context,
+            known: &self.known,
+            backends: &mut self.backends,
+            parameters: Vec::new(),
+            panic: None,
+        };
+        for param in body.params {
+            param.pat.each_binding(
+                |_: BindingMode, id: HirId, _: Span, _: Ident| {
+                    finder.parameters.push(id);
+                },
+            );
+        }
+        finder.visit_expr(body.value);
+        let Some(panic): Option<Span> = finder.panic else {
+            return;
+        };
+        if is_placeholder(context, panic) {
+            return;
+        }
+        let panic: Span = panic.source_callsite();
+
+        let indentation: Option<String> =
+            indentation(context.tcx.sess.source_map(), span);
+        security_utils::span_lint(
+            context,
+            SECURITY_MISSING_TRACK_CALLER,
+            ident.span,
+            |diagnostic: &mut Diag<'_, ()>| {
+                diagnostic.primary_message(format!(
+                    "Panicking function `{ident}` without `#[track_caller]` \
+                     detected."
+                ));
+                diagnostic.span_note(panic, "the function panics here");
+                diagnostic.note(
+                    "panic messages point at this function instead of the \
+                     call site responsible for the panic",
+                );
+                match indentation {
+                    Some(indentation) => {
+                        diagnostic.span_suggestion_verbose(
+                            span.shrink_to_lo(),
+                            "report the location of the caller",
+                            format!("#[track_caller]\n{indentation}"),
+                            Applicability::MachineApplicable,
+                        );
+                    },
+                    None => {
+                        diagnostic.help(
+                            "add `#[track_caller]` to report the location of \
+                             the caller",
+                        );
+                    },
+                }
+            },
+        );
+    }
+
     /// Detect calls to panic-related functions and methods, such as `unwrap`,
     /// `expect`, and functions in the standard library's panic module.
     ///
@@ -67,59 +826,154 @@ impl<'tcx> LateLintPass<'tcx> for SecurityPanicUsage {
         context: &LateContext<'tcx>,
         expression: &'tcx Expr<'tcx>,
     ) {
-        // Detect direct calls to `unwrap` and `expect` methods.
-        // This checks for method calls where the method name is `unwrap` or
-        // `expect`, and the method is defined in the local crate (to avoid
-        // false positives from external crates).
-        if let ExprKind::MethodCall(_, _, _, _) = &expression.kind
-            && let Some(def_id) = context
-                .typeck_results()
-                .type_dependent_def_id(expression.hir_id)
-            && (context.tcx.is_diagnostic_item(sym::unwrap, def_id)
-                || context.tcx.is_diagnostic_item(sym::option_unwrap, def_id)
-                || context.tcx.is_diagnostic_item(sym::except, def_id)
-                || context.tcx.is_diagnostic_item(sym::option_expect, def_id))
+        // Detect panic hooks ignoring the `PanicHookInfo` they receive.
+        if let ExprKind::Call(function, [argument]) = &expression.kind
+            && let ExprKind::Path(path) = &function.kind
+            && let Some(def_id) =
+                context.qpath_res(path, function.hir_id).opt_def_id()
+            && context.tcx.def_path_str(def_id) == "std::panic::set_hook"
+            && let Some(body) = hook_body(context, argument)
         {
-            context.span_lint(
-                SECURITY_PANIC_USAGE,
+            let mut usage: ParameterUse =
+                ParameterUse { parameters: parameters(body), used: false };
+            usage.visit_expr(body.value);
+
+            if !usage.used {
+                security_utils::span_lint(
+                    context,
+                    SECURITY_SILENT_PANIC_HOOK,
+                    argument.span,
+                    |diagnostic: &mut Diag<'_, ()>| {
+                        diagnostic.primary_message(
+                            "Panic hook discarding the panic information \
+                             detected.",
+                        );
+                        diagnostic.note(
+                            "the message and location of every panic are \
+                             lost, hiding crashes and exploitation attempts \
+                             from operators",
+                        );
+                        diagnostic.help(
+                            "log the `PanicHookInfo`, or forward it to the \
+                             previous hook returned by \
+                             `std::panic::take_hook`",
+                        );
+                    },
+                );
+            }
+        }
+
+        // Detect NaN-sensitive float comparators, with a fix on top of the
+        // generic `unwrap` finding below.
+        if !expression.span.from_expansion()
+            && let Some((left, right)) =
+                float_partial_cmp_unwrap(context, expression)
+        {
+            let source_map: &SourceMap = context.tcx.sess.source_map();
+            let replacement: Option<String> = source_map
+                .span_to_snippet(left.span)
+                .ok()
+                .zip(source_map.span_to_snippet(right.span).ok())
+                .map(|(left, right): (String, String)| {
+                    format!("{left}.total_cmp({right})")
+                });
+
+            security_utils::span_lint(
+                context,
+                SECURITY_PARTIAL_CMP_UNWRAP,
                 expression.span,
                 |diagnostic: &mut Diag<'_, ()>| {
                     diagnostic.primary_message(
-                        "Call to panic backend `unwrap/expect` detected.",
+                        "Float comparison with `partial_cmp(..).unwrap()` \
+                         detected.",
+                    );
+                    diagnostic.note(
+                        "`partial_cmp` returns `None` when either value is \
+                         NaN, so the comparison panics",
+                    );
+                    match replacement {
+                        Some(replacement) => {
+                            diagnostic.span_suggestion(
+                                expression.span,
+                                "use `total_cmp`, which orders NaN after \
+                                 every other value",
+                                replacement,
+                                Applicability::MaybeIncorrect,
+                            );
+                        },
+                        None => {
+                            diagnostic.help(
+                                "use `total_cmp`, or handle NaN explicitly",
+                            );
+                        },
+                    }
+                },
+            );
+        }
+
+        // Detect calls to the `unwrap` and `expect` methods of `Option`,
+        // `Result` and the configured panicking types. User methods merely
+        // named `unwrap` or `expect` are not reported. The call is replaced
+        // with `?` where the enclosing function can propagate the failure.
+        if let ExprKind::MethodCall(_, receiver, _, _) = &expression.kind
+            && self.is_panicking_unwrap(context, expression)
+            && !self.is_exempt(context.tcx, expression.hir_id)
+        {
+            self.reported.span_lint(
+                context,
+                SECURITY_PANIC_USAGE,
+                expression.span,
+                String::from(
+                    "Call to panic backend `unwrap/expect` detected.",
+                ),
+                |diagnostic: &mut Diag<'_, ()>| {
+                    suggest_propagation(
+                        context, diagnostic, expression, receiver,
                     );
                 },
             );
         }
 
-        // Detect calls to panic-related functions in the standard library.
+        // Detect calls to panic-related functions in the standard library,
+        // named after the macro they were expanded from when there is one.
         if let ExprKind::Call(func, _) = &expression.kind
             && let ExprKind::Path(path) = &func.kind
             && let Some(def_id) =
                 context.qpath_res(path, func.hir_id).opt_def_id()
             && let Some(kind) =
-                PanicBackend::from_def_path(&context.tcx.def_path_str(def_id))
+                self.backends.get_or_insert_with(def_id, || {
+                    PanicBackend::from_def_id(context.tcx, &self.known, def_id)
+                })
+            && !self.is_exempt(context.tcx, expression.hir_id)
         {
-            context.span_lint(
+            self.reported.span_lint(
+                context,
                 SECURITY_PANIC_USAGE,
                 expression.span.source_callsite(),
-                |diag: &mut Diag<'_, ()>| {
-                    diag.primary_message(format!(
-                        "Call to panic backend `{kind:?}` detected."
-                    ));
+                match originating_macro(context.tcx, expression.span) {
+                    Some(name) => {
+                        format!("Usage of `{name}!` macro detected.")
+                    },
+                    None => {
+                        format!("Call to panic backend `{kind:?}` detected.")
+                    },
                 },
+                |_: &mut Diag<'_, ()>| {},
             );
         }
     }
 }
 
-/// Registers the `SECURITY_PANIC_USAGE` lint and its corresponding lint pass
-/// with the Rust compiler. This function is called by the compiler when the
-/// library is loaded as a plugin. It initializes the lint configuration and
-/// registers the lint and its corresponding lint pass with the compiler's lint
-/// store. This allows the `SECURITY_PANIC_USAGE` lint to be used when
-/// compiling code that depends on this library, and ensures that the lint will
-/// be applied to the code being compiled, allowing it to detect and warn about
-/// constructs that may panic at runtime.
+/// Registers the `SECURITY_PANIC_USAGE`, `SECURITY_PARTIAL_CMP_UNWRAP`,
+/// `SECURITY_MISSING_TRACK_CALLER` and `SECURITY_SILENT_PANIC_HOOK` lints and
+/// their corresponding lint pass with the Rust compiler. This function is
+/// called by the compiler when the library is loaded as a plugin. It
+/// initializes the lint configuration and registers the lints and their
+/// corresponding lint pass with the compiler's lint store. This allows the
+/// `SECURITY_PANIC_USAGE` lint to be used when compiling code that depends on
+/// this library, and ensures that the lint will be applied to the code being
+/// compiled, allowing it to detect and warn about constructs that may panic at
+/// runtime.
 ///
 /// # Arguments
 /// * `session` (`&Session`) - The compiler session providing access to the
@@ -131,18 +985,36 @@ impl<'tcx> LateLintPass<'tcx> for SecurityPanicUsage {
 pub fn register_lints(session: &Session, lint_store: &mut LintStore) {
     dylint_linting::init_config(session);
 
-    lint_store.register_lints(&[SECURITY_PANIC_USAGE]);
-    lint_store
-        .register_late_pass(|_: TyCtxt<'_>| Box::new(SecurityPanicUsage));
+    security_utils::tool::register_lints(
+        lint_store,
+        &[
+            SECURITY_PANIC_USAGE,
+            SECURITY_PARTIAL_CMP_UNWRAP,
+            SECURITY_MISSING_TRACK_CALLER,
+            SECURITY_SILENT_PANIC_HOOK,
+        ],
+    );
+    lint_store.register_late_pass(|tcx: TyCtxt<'_>| {
+        Box::new(SecurityPanicUsage::new(tcx))
+    });
 }
 
 dylint_linting::dylint_library!();
 
-/// UI test for the `SECURITY_PANIC_USAGE` lint. This test compiles the code in
-/// the `ui` directory with the appropriate compiler flags for UI testing. The
-/// test will pass if the expected warnings are emitted for calls to
-/// panic-related functions and methods, and fail if any unexpected warnings
-/// are emitted or if the expected warnings are not emitted.
+/// UI test for the lints of the `panic_usage` library. This test compiles the
+/// code in the `ui` directory with the appropriate compiler flags for UI
+/// testing. The test will pass if the expected warnings are emitted for calls
+/// to panic-related functions and methods, for float comparators built on
+/// `partial_cmp`, for panicking helpers without `#[track_caller]` and for
+/// panic hooks discarding the panic information, and fail if any unexpected
+/// warnings are emitted or if the expected warnings are not emitted. The
+/// `ui_allow` test checks that items listed in the `[allow]` table of
+/// `dylint.toml` are skipped, and the `ui_types` test that the `unwrap` and
+/// `expect` methods of the configured panicking types are reported. The
+/// `ui_tests`, `ui_benches` and `ui_bins` tests check that panics in tests,
+/// compiled with `--test`, in bench targets and in `fn main` are accepted
+/// when configured so, and that a `benches` directory nested in the package
+/// does not make a bench target.
 #[cfg(test)]
 mod tests {
     use dylint_testing::ui::Test;
@@ -153,4 +1025,52 @@ mod tests {
             .rustc_flags(["--edition=2024", "-Z", "ui-testing"])
             .run();
     }
+
+    #[test]
+    fn ui_allow() {
+        Test::src_base(env!("CARGO_PKG_NAME"), "ui_allow")
+            .rustc_flags(["--edition=2024", "-Z", "ui-testing"])
+            .dylint_toml(
+                r#"allow.security_panic_usage = ["crate::fixtures", "allow::legacy_*"]"#,
+            )
+            .run();
+    }
+
+    #[test]
+    fn ui_types() {
+        Test::src_base(env!("CARGO_PKG_NAME"), "ui_types")
+            .rustc_flags(["--edition=2024", "-Z", "ui-testing"])
+            .dylint_toml(
+                r#"panic_usage.panicking_types = ["checked::Checked"]"#,
+            )
+            .run();
+    }
+
+    #[test]
+    fn ui_tests() {
+        Test::src_base(env!("CARGO_PKG_NAME"), "ui_tests")
+            .rustc_flags(["--edition=2024", "-Z", "ui-testing", "--test"])
+            .dylint_toml("panic_usage.allow_in_tests = true")
+            .run();
+    }
+
+    #[test]
+    fn ui_benches() {
+        Test::src_base(env!("CARGO_PKG_NAME"), "benches")
+            .rustc_flags(["--edition=2024", "-Z", "ui-testing"])
+            .dylint_toml("panic_usage.allow_in_benches = true")
+            .run();
+        Test::src_base(env!("CARGO_PKG_NAME"), "ui_benches")
+            .rustc_flags(["--edition=2024", "-Z", "ui-testing"])
+            .dylint_toml("panic_usage.allow_in_benches = true")
+            .run();
+    }
+
+    #[test]
+    fn ui_bins() {
+        Test::src_base(env!("CARGO_PKG_NAME"), "ui_bins")
+            .rustc_flags(["--edition=2024", "-Z", "ui-testing"])
+            .dylint_toml("panic_usage.allow_in_bins = true")
+            .run();
+    }
 }
@@ -0,0 +1,13 @@
+#![warn(security_panic_usage)]
+
+/// A user module named after the `panicking` modules of `core` and `std`.
+mod panicking {
+    pub fn recover(value: Option<u8>) -> u8 {
+        value.unwrap_or_default()
+    }
+}
+
+fn main() {
+    let _: u8 = panicking::recover(None); // Should not trigger.
+    let _ = std::panic::take_hook(); // Should not trigger.
+}
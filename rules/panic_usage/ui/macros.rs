@@ -0,0 +1,30 @@
+#![feature(panic_internals)]
+#![allow(internal_features)]
+#![warn(security_panic_usage)]
+
+/// A user macro wrapping `panic!`.
+macro_rules! fail {
+    ($message:literal) => {
+        panic!($message)
+    };
+}
+
+pub fn check(value: u8) {
+    debug_assert!(value > 0); // Should name `debug_assert!`.
+    assert!(value < 8, "value {value} out of range"); // Should name `assert!`.
+    debug_assert_eq!(value, 1); // Should name `debug_assert_eq!`.
+
+    if value == 2 {
+        unreachable!("value {}", value); // Should name `unreachable!`.
+    }
+    if value == 3 {
+        fail!("three"); // Should name `panic!`, not the wrapper.
+    }
+    if value == 4 {
+        core::panicking::panic("four"); // Should name the backend.
+    }
+}
+
+fn main() {
+    check(1);
+}
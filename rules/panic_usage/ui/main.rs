@@ -19,4 +19,15 @@ fn main() {
     todo!(); // Should trigger.
     unimplemented!(); // Should trigger.
     unreachable!(); // Should trigger.
+
+    let _: i32 = unwrap_twice!(x); // Should trigger once.
+}
+
+/// Each use of the macro unwraps twice, at two spans of the macro definition
+/// sharing the call site.
+#[macro_export]
+macro_rules! unwrap_twice {
+    ($value:expr) => {
+        $value.unwrap() + $value.unwrap()
+    };
 }
@@ -0,0 +1,25 @@
+#![warn(security_silent_panic_hook)]
+
+use std::panic::{self, PanicHookInfo};
+
+fn silent(_: &PanicHookInfo<'_>) {}
+
+fn report(info: &PanicHookInfo<'_>) {
+    eprintln!("panic: {info}");
+}
+
+fn main() {
+    panic::set_hook(Box::new(|_| {})); // Should trigger.
+    panic::set_hook(Box::new(|_info| std::process::abort())); // Should trigger.
+    std::panic::set_hook(Box::new(silent)); // Should trigger.
+
+    panic::set_hook(Box::new(|info: &PanicHookInfo<'_>| {
+        eprintln!("panic at {:?}", info.location()); // Should not trigger.
+    }));
+    panic::set_hook(Box::new(report)); // Should not trigger.
+
+    let previous = panic::take_hook();
+    panic::set_hook(Box::new(move |info: &PanicHookInfo<'_>| {
+        previous(info); // Should not trigger (forwarded).
+    }));
+}
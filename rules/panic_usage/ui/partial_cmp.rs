@@ -0,0 +1,41 @@
+#![allow(security_panic_usage, security_missing_track_caller)]
+
+use std::cmp::Ordering;
+
+#[derive(PartialEq, PartialOrd)]
+struct Score(f64);
+
+impl Eq for Score {}
+
+impl Ord for Score {
+    fn cmp(&self, other: &Self) -> Ordering {
+        // Should trigger the lint: hand-written `Ord` for a float wrapper.
+        self.0.partial_cmp(&other.0).unwrap()
+    }
+}
+
+fn main() {
+    let mut values: Vec<f64> = vec![2.0, 1.0, f64::NAN];
+    let mut counts: Vec<u32> = vec![2, 1];
+
+    // Should trigger the lint: NaN-sensitive comparators.
+    values.sort_by(|a: &f64, b: &f64| a.partial_cmp(b).unwrap());
+    let _: Option<&f64> =
+        values.iter().max_by(|a: &&f64, b: &&f64| a.partial_cmp(b).expect("NaN"));
+
+    // Should not trigger the lint: total order, or non-float operands.
+    values.sort_by(|a: &f64, b: &f64| a.total_cmp(b));
+    counts.sort_by(|a: &u32, b: &u32| a.partial_cmp(b).unwrap());
+
+    let mut scores: Vec<Score> = vec![Score(1.0), Score(0.5)];
+    scores.sort();
+
+    // Should not trigger the lint: the float key goes through `Score`, whose
+    // `Ord` implementation is reported.
+    values.sort_by_key(|value: &f64| Score(*value));
+    let _: Option<&f64> = values.iter().min_by_key(|value: &&f64| Score(**value));
+
+    // Should not trigger the lint: the operands are not floats, the `unwrap`
+    // is left to `security_panic_usage`.
+    let _: Ordering = scores[0].partial_cmp(&scores[1]).unwrap();
+}
@@ -0,0 +1,46 @@
+#![warn(security_panic_usage)]
+#![allow(security_missing_track_caller)]
+
+use std::num::ParseIntError;
+
+pub fn first(values: &[u8]) -> Option<u8> {
+    let value: u8 = values.first().copied().unwrap(); // Should suggest `?`.
+    Some(value)
+}
+
+pub fn parse(text: &str) -> Result<u8, ParseIntError> {
+    let value: u8 = text.parse::<u8>().expect("invalid"); // Should suggest `?`.
+    Ok(value)
+}
+
+pub fn boxed(text: &str) -> Result<u8, Box<dyn std::error::Error>> {
+    // Should suggest `?`, noting the conversion of the error.
+    Ok(text.parse::<u8>().unwrap())
+}
+
+pub fn missing(values: &[u8]) -> Result<u8, String> {
+    Ok(values.first().copied().unwrap()) // Should hint at `.ok_or(..)?`.
+}
+
+pub fn discarded(text: &str) -> Option<u8> {
+    Some(text.parse::<u8>().unwrap()) // Should hint at `.ok()?`.
+}
+
+pub fn closure(values: &[u8]) -> Option<u8> {
+    // Should not suggest `?`: it would return from the closure.
+    values.iter().map(|value: &u8| Some(*value).unwrap()).max()
+}
+
+pub const fn constant(value: Option<u8>) -> Option<u8> {
+    Some(value.unwrap()) // Should not suggest `?`: constant function.
+}
+
+fn main() {
+    let _: Option<u8> = first(&[1]);
+    let _: Result<u8, ParseIntError> = parse("1");
+    let _: Result<u8, Box<dyn std::error::Error>> = boxed("1");
+    let _: Result<u8, String> = missing(&[1]);
+    let _: Option<u8> = discarded("1");
+    let _: Option<u8> = closure(&[1]);
+    let _: Option<u8> = constant(Some(1));
+}
@@ -0,0 +1,28 @@
+#![warn(security_panic_usage)]
+
+/// A wrapper whose `unwrap` falls back to a default instead of panicking.
+pub struct Lenient(Option<u8>);
+
+impl Lenient {
+    pub fn unwrap(self) -> u8 {
+        self.0.unwrap_or_default()
+    }
+}
+
+pub trait Expect {
+    fn expect(&self, message: &str) -> usize;
+}
+
+impl Expect for Vec<u8> {
+    fn expect(&self, message: &str) -> usize {
+        self.len() + message.len()
+    }
+}
+
+fn main() {
+    let _: u8 = Lenient(None).unwrap(); // Should not trigger: user method.
+    let _: usize = vec![1].expect("none"); // Should not trigger: user method.
+
+    let parsed: Result<u8, std::num::ParseIntError> = "1".parse();
+    let _: u8 = parsed.expect("invalid"); // Should trigger.
+}
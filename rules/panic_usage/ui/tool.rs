@@ -0,0 +1,19 @@
+#![feature(register_tool)]
+#![register_tool(security)]
+#![deny(security::panic_usage)]
+#![allow(security::missing_track_caller)]
+
+#[allow(security::panic_usage)]
+fn allowed(value: Option<u8>) -> u8 {
+    value.unwrap() // Should not trigger.
+}
+
+#[expect(security::panic_usage)]
+fn expected(value: Option<u8>) -> u8 {
+    value.unwrap() // Should not trigger.
+}
+
+fn main() {
+    let value: Option<u8> = Some(allowed(Some(1)) + expected(Some(2)));
+    let _: u8 = value.unwrap(); // Should trigger.
+}
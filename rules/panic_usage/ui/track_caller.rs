@@ -0,0 +1,71 @@
+#![warn(security_missing_track_caller)]
+#![allow(security_panic_usage)]
+
+struct Config {
+    path: Option<String>,
+}
+
+impl Config {
+    fn path(&self) -> &str {
+        self.path.as_deref().expect("configuration not loaded") // Should trigger.
+    }
+
+    #[track_caller]
+    fn path_or_panic(&self) -> &str {
+        self.path.as_deref().unwrap() // Should not trigger (tracked).
+    }
+}
+
+fn fail(message: &str) -> ! {
+    panic!("{message}") // Should trigger.
+}
+
+fn unwrap_id(id: Option<u32>) -> u32 {
+    let id: u32 = id.unwrap(); // Should trigger.
+    id + 1
+}
+
+fn invariant_broken() {
+    unreachable!("the cache is always warm"); // Should trigger.
+}
+
+#[track_caller]
+fn tracked(id: Option<u32>) -> u32 {
+    id.unwrap() // Should not trigger (tracked).
+}
+
+fn checked(id: u32) -> u32 {
+    if id == 0 {
+        panic!("zero id"); // Should not trigger (conditional).
+    }
+    assert!(id < 10); // Should not trigger (conditional).
+    id
+}
+
+fn default_id() -> u32 {
+    std::env::var("ID").unwrap().parse().unwrap_or(0) // Should not trigger (not an argument).
+}
+
+fn later(_: u32) -> u32 {
+    todo!() // Should not trigger (placeholder).
+}
+
+fn with_closure(id: Option<u32>) -> impl Fn() -> u32 {
+    move || id.unwrap() // Should not trigger (closure).
+}
+
+async fn fetch(id: Option<u32>) -> u32 {
+    id.unwrap() // Should not trigger (async).
+}
+
+fn main() {
+    let config: Config = Config { path: Some(String::from("a")) };
+    let _: &str = config.path();
+    let _: &str = config.path_or_panic();
+    let _: u32 = unwrap_id(Some(1)) + tracked(Some(1)) + checked(1) + default_id();
+    let _: fn(u32) -> u32 = later;
+    let _: u32 = with_closure(Some(1))();
+    let _ = fetch(Some(1));
+    invariant_broken();
+    fail("unreachable");
+}
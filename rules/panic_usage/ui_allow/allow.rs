@@ -0,0 +1,35 @@
+#![warn(security_panic_usage)]
+
+/// Items under `crate::fixtures` are allowed through the `[allow]` table of
+/// `dylint.toml`, as well as the `legacy_parser` function.
+mod fixtures {
+    pub fn sample() -> i32 {
+        Some(1).unwrap() // Should not trigger.
+    }
+
+    // Allowed findings still fulfill expectations.
+    #[expect(security_panic_usage, reason = "fixtures may panic")]
+    pub fn expected() -> i32 {
+        Some(3).unwrap() // Should not trigger.
+    }
+
+    pub mod nested {
+        pub fn sample() -> i32 {
+            Some(2).expect("") // Should not trigger.
+        }
+    }
+}
+
+fn legacy_parser() -> i32 {
+    "1".parse::<i32>().unwrap() // Should not trigger.
+}
+
+fn main() {
+    let value: i32 = fixtures::sample()
+        + fixtures::nested::sample()
+        + fixtures::expected();
+    let _: i32 = value + legacy_parser();
+
+    let x: Option<i32> = None;
+    x.unwrap(); // Should trigger.
+}
@@ -0,0 +1,11 @@
+#![warn(security_panic_usage)]
+#![allow(security_missing_track_caller)]
+
+// A `benches` directory nested in the package does not hold bench targets.
+fn parse(text: &str) -> u16 {
+    text.parse().unwrap() // Should trigger: not a bench target.
+}
+
+fn main() {
+    let _: u16 = parse("8080");
+}
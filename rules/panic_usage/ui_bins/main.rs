@@ -0,0 +1,17 @@
+#![warn(security_panic_usage)]
+#![allow(security_missing_track_caller)]
+
+fn port(text: &str) -> u16 {
+    text.parse().unwrap() // Should trigger: not the entry point.
+}
+
+fn main() {
+    let port: u16 = std::env::args().nth(1).map(|text: String| port(&text)).unwrap(); // Should not trigger.
+    let _: u16 = Some(port).map(|port: u16| port.checked_add(1).unwrap()).unwrap_or_default(); // Should not trigger: closure of `main`.
+    assert!(port > 0); // Should not trigger.
+
+    fn nested() {
+        panic!("nested"); // Should trigger: nested item.
+    }
+    nested();
+}
@@ -0,0 +1,24 @@
+#![warn(security_panic_usage)]
+#![allow(security_missing_track_caller)]
+
+pub fn parse(text: &str) -> u8 {
+    text.parse().unwrap() // Should trigger: not a test.
+}
+
+#[test]
+fn parses() {
+    assert_eq!(parse("1"), 1); // Should not trigger.
+    let _: u8 = "2".parse().unwrap(); // Should not trigger.
+}
+
+#[cfg(test)]
+mod tests {
+    fn helper(text: &str) -> u8 {
+        text.parse().expect("invalid") // Should not trigger: test module.
+    }
+
+    #[test]
+    fn helps() {
+        assert!(helper("3") == 3); // Should not trigger.
+    }
+}
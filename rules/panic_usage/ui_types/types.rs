@@ -0,0 +1,33 @@
+#![warn(security_panic_usage)]
+
+pub mod checked {
+    /// A value validated on access, panicking when invalid.
+    pub struct Checked(pub Option<u32>);
+
+    impl Checked {
+        pub fn unwrap(self) -> u32 {
+            self.0.unwrap_or_else(|| panic!("invalid value"))
+        }
+
+        pub fn expect(&self, message: &str) -> u32 {
+            self.0.unwrap_or_else(|| panic!("{message}"))
+        }
+    }
+
+    /// A value with a default, not listed in `panicking_types`.
+    pub struct Defaulted(pub Option<u32>);
+
+    impl Defaulted {
+        pub fn unwrap(self) -> u32 {
+            self.0.unwrap_or_default()
+        }
+    }
+}
+
+use checked::{Checked, Defaulted};
+
+fn main() {
+    let _: u32 = Checked(Some(1)).unwrap(); // Should trigger: configured.
+    let _: u32 = (&Checked(None)).expect("invalid"); // Should trigger.
+    let _: u32 = Defaulted(None).unwrap(); // Should not trigger.
+}
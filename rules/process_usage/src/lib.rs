@@ -0,0 +1,693 @@
+#![feature(rustc_private)]
+
+extern crate rustc_ast;
+extern crate rustc_errors;
+extern crate rustc_hir;
+extern crate rustc_lint;
+extern crate rustc_middle;
+extern crate rustc_session;
+extern crate rustc_span;
+
+use std::path::Path;
+
+use rustc_ast::LitKind;
+use rustc_errors::Diag;
+use rustc_hir::{
+    Body,
+    BodyId,
+    Expr,
+    ExprKind,
+    HirId,
+    Node,
+    Param,
+    PatKind,
+    QPath,
+    StmtKind,
+    def::Res,
+    def_id::DefId,
+    intravisit::{Visitor, walk_expr},
+};
+use rustc_lint::{LateContext, LateLintPass, LintStore};
+use rustc_middle::ty::{AdtDef, Ty, TyCtxt};
+use rustc_session::{Session, declare_lint, impl_lint_pass};
+use rustc_span::{RealFileName, Symbol};
+use security_utils::{allow::matches_pattern, calls::is_named};
+use serde::Deserialize;
+
+declare_lint! {
+    pub SECURITY_COMMAND_INHERITED_ENV,
+    Warn,
+    "Detects processes spawned without clearing the inherited environment."
+}
+
+declare_lint! {
+    pub SECURITY_COMMAND_RELATIVE_PROGRAM,
+    Warn,
+    "Detects processes whose program is looked up in `PATH`."
+}
+
+declare_lint! {
+    pub SECURITY_PRIVILEGE_DROP,
+    Warn,
+    "Detects user privileges dropped before group privileges, and unchecked
+    results of privilege-dropping calls."
+}
+
+/// Configuration of the `process_usage` library, read from the
+/// `[process_usage]` table of the workspace's `dylint.toml`.
+#[derive(Deserialize)]
+#[serde(default)]
+struct Config {
+    /// Paths of the process builder types (e.g. `std::process::Command`),
+    /// whose `new` function takes the program to run.
+    commands: Vec<String>,
+    /// Names of the methods spawning the process of a builder (e.g.
+    /// `Command::spawn`).
+    spawn_methods: Vec<String>,
+    /// Names of the builder methods discarding the inherited environment
+    /// (e.g. `Command::env_clear`).
+    env_clear_methods: Vec<String>,
+    /// Patterns of the paths of the crate roots (e.g. `src/bin/*.rs`) of the
+    /// privileged binaries, such as setuid helpers, whose spawned processes
+    /// must not inherit the environment. `*` matches any sequence of
+    /// characters, so the default `["*"]` checks every crate.
+    privileged_binaries: Vec<String>,
+    /// Paths of the functions changing the user of the process (e.g.
+    /// `libc::setuid`).
+    uid_functions: Vec<String>,
+    /// Paths of the functions changing the groups of the process (e.g.
+    /// `libc::setgid` or `libc::setgroups`), which must be called before
+    /// the user is changed.
+    gid_functions: Vec<String>,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Self {
+            commands: [
+                "std::process::Command",
+                "tokio::process::Command",
+                "async_process::Command",
+            ]
+            .into_iter()
+            .map(String::from)
+            .collect(),
+            spawn_methods: ["spawn", "output", "status", "exec"]
+                .into_iter()
+                .map(String::from)
+                .collect(),
+            env_clear_methods: vec![String::from("env_clear")],
+            privileged_binaries: vec![String::from("*")],
+            uid_functions: [
+                "libc::setuid",
+                "libc::seteuid",
+                "libc::setreuid",
+                "libc::setresuid",
+                "nix::unistd::setuid",
+                "nix::unistd::seteuid",
+                "nix::unistd::setresuid",
+            ]
+            .into_iter()
+            .map(String::from)
+            .collect(),
+            gid_functions: [
+                "libc::setgid",
+                "libc::setegid",
+                "libc::setregid",
+                "libc::setresgid",
+                "libc::setgroups",
+                "libc::initgroups",
+                "nix::unistd::setgid",
+                "nix::unistd::setegid",
+                "nix::unistd::setresgid",
+                "nix::unistd::setgroups",
+                "nix::unistd::initgroups",
+            ]
+            .into_iter()
+            .map(String::from)
+            .collect(),
+        }
+    }
+}
+
+/// Lint pass holding the process builders, privileged binaries and
+/// privilege-dropping functions loaded from the configuration.
+struct SecurityProcessUsage {
+    commands: Vec<String>,
+    spawn_methods: Vec<String>,
+    env_clear_methods: Vec<String>,
+    privileged_binaries: Vec<String>,
+    uid_functions: Vec<String>,
+    gid_functions: Vec<String>,
+}
+
+impl_lint_pass!(SecurityProcessUsage => [
+    SECURITY_COMMAND_INHERITED_ENV,
+    SECURITY_COMMAND_RELATIVE_PROGRAM,
+    SECURITY_PRIVILEGE_DROP,
+]);
+
+/// Privileges changed by a privilege-dropping call.
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum Privilege {
+    /// The user, e.g. `setuid`.
+    User,
+    /// The groups, e.g. `setgid` or `setgroups`.
+    Group,
+}
+
+/// A call to a privilege-dropping function.
+struct PrivilegeCall<'tcx> {
+    expression: &'tcx Expr<'tcx>,
+    function: Symbol,
+    privilege: Privilege,
+}
+
+/// Visitor collecting the privilege-dropping calls of a body, in evaluation
+/// order. Closures and nested items are not entered, as they are checked as
+/// bodies of their own.
+struct PrivilegeCalls<'a, 'tcx> {
+    context: &'a LateContext<'tcx>,
+    pass: &'a SecurityProcessUsage,
+    calls: Vec<PrivilegeCall<'tcx>>,
+}
+
+impl<'tcx> Visitor<'tcx> for PrivilegeCalls<'_, 'tcx> {
+    fn visit_expr(&mut self, expression: &'tcx Expr<'tcx>) {
+        walk_expr(self, expression);
+
+        if let ExprKind::Call(callee, _) = &expression.kind
+            && let ExprKind::Path(path) = &callee.kind
+            && let Some(def_id) =
+                self.context.qpath_res(path, callee.hir_id).opt_def_id()
+            && let Some(privilege) =
+                self.pass.privilege(&self.context.tcx.def_path_str(def_id))
+        {
+            self.calls.push(PrivilegeCall {
+                expression,
+                function: self.context.tcx.item_name(def_id),
+                privilege,
+            });
+        }
+    }
+}
+
+/// Returns `true` if the value of `expression` is discarded: the whole of an
+/// expression statement (`setuid(uid);`), possibly as the tail of a block
+/// (`unsafe { setuid(uid) };`), or bound to `_`.
+///
+/// # Arguments
+/// * `context` (`&LateContext<'_>`) - The lint context, used to walk up the
+///   parents of `expression`.
+/// * `expression` (`&Expr<'_>`) - The call to check.
+fn is_discarded(context: &LateContext<'_>, expression: &Expr<'_>) -> bool {
+    let mut current: HirId = expression.hir_id;
+    loop {
+        match context.tcx.parent_hir_node(current) {
+            Node::Stmt(statement) => {
+                return matches!(statement.kind, StmtKind::Semi(_));
+            },
+            Node::LetStmt(local) => {
+                return matches!(local.pat.kind, PatKind::Wild);
+            },
+            Node::Block(block)
+                if block
+                    .expr
+                    .is_some_and(|tail: &Expr<'_>| tail.hir_id == current) =>
+            {
+                current = block.hir_id;
+            },
+            Node::Expr(parent)
+                if matches!(parent.kind, ExprKind::Block(..)) =>
+            {
+                current = parent.hir_id;
+            },
+            _ => return false,
+        }
+    }
+}
+
+/// Visitor looking for a call to one of `methods` on a builder stored in the
+/// local `binding`, e.g. `command.env_clear()` after
+/// `let mut command = Command::new(..)`.
+struct BindingCall<'a> {
+    binding: HirId,
+    methods: &'a [String],
+    found: bool,
+}
+
+impl<'tcx> Visitor<'tcx> for BindingCall<'_> {
+    fn visit_expr(&mut self, expression: &'tcx Expr<'tcx>) {
+        if let ExprKind::MethodCall(segment, receiver, _, _) = &expression.kind
+            && is_named(self.methods, segment.ident.name)
+            && local(root(receiver)) == Some(self.binding)
+        {
+            self.found = true;
+        }
+        walk_expr(self, expression);
+    }
+}
+
+/// Returns the expression a chain of method calls starts from, e.g.
+/// `Command::new("sh")` in `Command::new("sh").arg("-c").spawn()`.
+///
+/// # Arguments
+/// * `expression` (`&'a Expr<'tcx>`) - The last call of the chain.
+fn root<'a, 'tcx>(expression: &'a Expr<'tcx>) -> &'a Expr<'tcx> {
+    let mut current: &Expr<'_> = expression;
+    while let ExprKind::MethodCall(_, receiver, _, _) = &current.kind {
+        current = receiver;
+    }
+
+    current
+}
+
+/// Returns the local binding `expression` reads, if any.
+///
+/// # Arguments
+/// * `expression` (`&Expr<'_>`) - The expression to resolve.
+fn local(expression: &Expr<'_>) -> Option<HirId> {
+    match &expression.kind {
+        ExprKind::Path(QPath::Resolved(None, path)) => match path.res {
+            Res::Local(id) => Some(id),
+            _ => None,
+        },
+        ExprKind::AddrOf(_, _, inner) => local(inner),
+        _ => None,
+    }
+}
+
+impl SecurityProcessUsage {
+    fn new(tcx: TyCtxt<'_>) -> Self {
+        let config: Config =
+            security_utils::config::load(tcx, env!("CARGO_PKG_NAME"));
+
+        Self {
+            commands: config.commands,
+            spawn_methods: config.spawn_methods,
+            env_clear_methods: config.env_clear_methods,
+            privileged_binaries: config.privileged_binaries,
+            uid_functions: config.uid_functions,
+            gid_functions: config.gid_functions,
+        }
+    }
+
+    /// Returns the privileges changed by the function at `path`, if it is
+    /// one of the configured privilege-dropping functions.
+    ///
+    /// # Arguments
+    /// * `path` (`&str`) - The path of the called function.
+    fn privilege(&self, path: &str) -> Option<Privilege> {
+        if self.uid_functions.iter().any(|function: &String| function == path)
+        {
+            Some(Privilege::User)
+        } else if self
+            .gid_functions
+            .iter()
+            .any(|function: &String| function == path)
+        {
+            Some(Privilege::Group)
+        } else {
+            None
+        }
+    }
+
+    /// Returns `true` if the crate being compiled is one of the configured
+    /// privileged binaries, matched on the path of its root source file.
+    ///
+    /// # Arguments
+    /// * `context` (`&LateContext<'_>`) - The lint context, used to read the
+    ///   path of the crate root.
+    fn is_privileged(&self, context: &LateContext<'_>) -> bool {
+        let Some(source): Option<RealFileName> =
+            context.tcx.sess.local_crate_source_file()
+        else {
+            return false;
+        };
+        let path: String = source
+            .local_path()
+            .map(|path: &Path| path.to_string_lossy().into_owned())
+            .unwrap_or_default();
+
+        self.privileged_binaries
+            .iter()
+            .any(|pattern: &String| matches_pattern(pattern, &path))
+    }
+
+    /// Returns `true` if `ty` is one of the configured process builders, or a
+    /// reference to one.
+    ///
+    /// # Arguments
+    /// * `context` (`&LateContext<'_>`) - The lint context, used to print the
+    ///   path of the type.
+    /// * `ty` (`Ty<'_>`) - The type to check.
+    fn is_command(&self, context: &LateContext<'_>, ty: Ty<'_>) -> bool {
+        ty.peel_refs().ty_adt_def().is_some_and(|adt: AdtDef<'_>| {
+            self.commands.contains(&context.tcx.def_path_str(adt.did()))
+        })
+    }
+
+    /// Returns `true` if the builder spawned by a call on `receiver` clears
+    /// its environment, either in the chain of calls leading to `receiver`
+    /// or, when the chain starts from a local binding, through that binding
+    /// anywhere in the enclosing body. Builders of unknown origin (fields,
+    /// parameters) are assumed to be cleared by their owner.
+    ///
+    /// # Arguments
+    /// * `context` (`&LateContext<'_>`) - The lint context, used to read the
+    ///   enclosing body.
+    /// * `receiver` (`&Expr<'_>`) - The receiver of the spawning call.
+    fn clears_env(
+        &self,
+        context: &LateContext<'_>,
+        receiver: &Expr<'_>,
+    ) -> bool {
+        let mut current: &Expr<'_> = receiver;
+        while let ExprKind::MethodCall(segment, inner, _, _) = &current.kind {
+            if is_named(&self.env_clear_methods, segment.ident.name) {
+                return true;
+            }
+            current = inner;
+        }
+
+        match (&current.kind, local(current)) {
+            (ExprKind::Call(..), _) => false,
+            (_, Some(binding)) => {
+                let Some(body): Option<&Body<'_>> = context
+                    .enclosing_body
+                    .map(|body: BodyId| context.tcx.hir_body(body))
+                else {
+                    return true;
+                };
+                if body
+                    .params
+                    .iter()
+                    .any(|param: &Param<'_>| param.pat.hir_id == binding)
+                {
+                    return true;
+                }
+                let mut finder: BindingCall<'_> = BindingCall {
+                    binding,
+                    methods: &self.env_clear_methods,
+                    found: false,
+                };
+                finder.visit_expr(body.value);
+                finder.found
+            },
+            _ => true,
+        }
+    }
+
+    /// Reports `expression` if it spawns a process from a builder that never
+    /// clears the environment inherited from the current process.
+    ///
+    /// # Arguments
+    /// * `context` (`&LateContext<'tcx>`) - The lint context used to emit the
+    ///   diagnostic.
+    /// * `expression` (`&'tcx Expr<'tcx>`) - The expression to inspect.
+    fn check_spawn<'tcx>(
+        &self,
+        context: &LateContext<'tcx>,
+        expression: &'tcx Expr<'tcx>,
+    ) {
+        let ExprKind::MethodCall(segment, receiver, _, _): &ExprKind<'_> =
+            &expression.kind
+        else {
+            return;
+        };
+        if !is_named(&self.spawn_methods, segment.ident.name)
+            || !self.is_command(
+                context,
+                context.typeck_results().expr_ty(receiver),
+            )
+            || self.clears_env(context, receiver)
+            || !self.is_privileged(context)
+        {
+            return;
+        }
+
+        security_utils::span_lint(
+            context,
+            SECURITY_COMMAND_INHERITED_ENV,
+            expression.span,
+            |diagnostic: &mut Diag<'_, ()>| {
+                diagnostic.primary_message(format!(
+                    "Process spawned by `{}` with an inherited environment \
+                     detected.",
+                    segment.ident
+                ));
+                diagnostic.note(
+                    "the child inherits variables such as `LD_PRELOAD` and \
+                     `PATH`, which let whoever controls the environment of \
+                     this process run code in the child",
+                );
+                diagnostic.help(
+                    "call `env_clear()` on the command, then set the \
+                     variables the child needs with `env()`",
+                );
+            },
+        );
+    }
+
+    /// Reports `expression` if it creates a process builder for a program
+    /// name without a path separator, which is looked up in `PATH`.
+    ///
+    /// # Arguments
+    /// * `context` (`&LateContext<'tcx>`) - The lint context used to emit the
+    ///   diagnostic.
+    /// * `expression` (`&'tcx Expr<'tcx>`) - The expression to inspect.
+    fn check_program<'tcx>(
+        &self,
+        context: &LateContext<'tcx>,
+        expression: &'tcx Expr<'tcx>,
+    ) {
+        let ExprKind::Call(callee, [program]): &ExprKind<'_> =
+            &expression.kind
+        else {
+            return;
+        };
+        let ExprKind::Lit(literal): &ExprKind<'_> = &program.kind else {
+            return;
+        };
+        let LitKind::Str(name, _): LitKind = literal.node else {
+            return;
+        };
+        let ExprKind::Path(path): &ExprKind<'_> = &callee.kind else {
+            return;
+        };
+        let Some(def_id): Option<DefId> =
+            context.qpath_res(path, callee.hir_id).opt_def_id()
+        else {
+            return;
+        };
+        let constructor: String = context.tcx.def_path_str(def_id);
+        if name.as_str().contains(['/', '\\'])
+            || !self.commands.iter().any(|command: &String| {
+                constructor.strip_prefix(command.as_str()) == Some("::new")
+            })
+        {
+            return;
+        }
+
+        security_utils::span_lint(
+            context,
+            SECURITY_COMMAND_RELATIVE_PROGRAM,
+            program.span,
+            |diagnostic: &mut Diag<'_, ()>| {
+                diagnostic.primary_message(format!(
+                    "Program `{name}` resolved through `PATH` detected."
+                ));
+                diagnostic.note(
+                    "the program is looked up in the directories of `PATH`, \
+                     so whoever controls `PATH`, or can write to one of its \
+                     directories, chooses the program that runs",
+                );
+                diagnostic.help(
+                    "use the absolute path of the program, or resolve it \
+                     from a fixed list of trusted directories and validate \
+                     it before spawning",
+                );
+            },
+        );
+    }
+}
+
+impl<'tcx> LateLintPass<'tcx> for SecurityProcessUsage {
+    /// Detect bodies dropping the user privileges before the group
+    /// privileges, and privilege-dropping calls whose result is discarded.
+    ///
+    /// # Arguments
+    /// * `context` (`&LateContext<'tcx>`) - The lint context providing access
+    ///   to the compiler's internal state.
+    /// * `body` (`&Body<'tcx>`) - The body being checked for
+    ///   privilege-dropping calls.
+    fn check_body(&mut self, context: &LateContext<'tcx>, body: &Body<'tcx>) {
+        let mut collector: PrivilegeCalls<'_, 'tcx> =
+            PrivilegeCalls { context, pass: self, calls: Vec::new() };
+        collector.visit_expr(body.value);
+        let calls: Vec<PrivilegeCall<'tcx>> = collector.calls;
+
+        for (index, call) in calls.iter().enumerate() {
+            if call.expression.span.from_expansion() {
+                continue;
+            }
+
+            if is_discarded(context, call.expression) {
+                report_unchecked(context, call);
+            }
+
+            if call.privilege == Privilege::User
+                && let Some(group) = calls
+                    .iter()
+                    .skip(index.saturating_add(1))
+                    .find(|later: &&PrivilegeCall<'tcx>| {
+                        later.privilege == Privilege::Group
+                    })
+            {
+                report_order(context, call, group);
+            }
+        }
+    }
+
+    /// Detect processes spawned from a builder that never clears the
+    /// environment inherited from the current process, and programs looked
+    /// up in `PATH`.
+    ///
+    /// # Arguments
+    /// * `context` (`&LateContext<'tcx>`) - The lint context providing access
+    ///   to the compiler's internal state.
+    /// * `expression` (`&'tcx Expr<'tcx>`) - The expression being checked for
+    ///   spawned processes.
+    fn check_expr(
+        &mut self,
+        context: &LateContext<'tcx>,
+        expression: &'tcx Expr<'tcx>,
+    ) {
+        if expression.span.from_expansion() {
+            return;
+        }
+
+        self.check_spawn(context, expression);
+        self.check_program(context, expression);
+    }
+}
+
+/// Reports a privilege-dropping call whose result is discarded.
+///
+/// # Arguments
+/// * `context` (`&LateContext<'_>`) - The lint context used to emit the
+///   diagnostic.
+/// * `call` (`&PrivilegeCall<'_>`) - The unchecked call.
+fn report_unchecked(context: &LateContext<'_>, call: &PrivilegeCall<'_>) {
+    security_utils::span_lint(
+        context,
+        SECURITY_PRIVILEGE_DROP,
+        call.expression.span,
+        |diagnostic: &mut Diag<'_, ()>| {
+            diagnostic.primary_message(format!(
+                "Unchecked result of `{}` detected.",
+                call.function
+            ));
+            diagnostic.note(
+                "if the call fails, the process keeps running with its \
+                 privileges",
+            );
+            diagnostic.help(
+                "check the result and abort when the privileges could not \
+                 be dropped",
+            );
+        },
+    );
+}
+
+/// Reports a call dropping the user privileges before `group`, a later call
+/// dropping the group privileges.
+///
+/// # Arguments
+/// * `context` (`&LateContext<'_>`) - The lint context used to emit the
+///   diagnostic.
+/// * `user` (`&PrivilegeCall<'_>`) - The call changing the user.
+/// * `group` (`&PrivilegeCall<'_>`) - The later call changing the groups.
+fn report_order(
+    context: &LateContext<'_>,
+    user: &PrivilegeCall<'_>,
+    group: &PrivilegeCall<'_>,
+) {
+    security_utils::span_lint(
+        context,
+        SECURITY_PRIVILEGE_DROP,
+        user.expression.span,
+        |diagnostic: &mut Diag<'_, ()>| {
+            diagnostic.primary_message(format!(
+                "Privileges dropped by `{}` before `{}` detected.",
+                user.function, group.function
+            ));
+            diagnostic.span_note(
+                group.expression.span,
+                "the groups are changed here, after the process lost the \
+                 privilege to change them",
+            );
+            diagnostic.help(
+                "drop the supplementary groups and the group (`setgroups`, \
+                 `setgid`) before the user (`setuid`)",
+            );
+        },
+    );
+}
+
+/// Registers the `SECURITY_COMMAND_INHERITED_ENV`,
+/// `SECURITY_COMMAND_RELATIVE_PROGRAM` and `SECURITY_PRIVILEGE_DROP` lints and
+/// their lint pass with the Rust compiler. The process builders, privileged
+/// binaries and privilege-dropping functions are read from the
+/// `[process_usage]` table of `dylint.toml` when the pass is created.
+///
+/// # Arguments
+/// * `session` (`&Session`) - The compiler session providing access to the
+///   compiler's internal state.
+/// * `lint_store` (`&mut LintStore`) - The lint store where the lints and
+///   their lint pass will be registered.
+#[unsafe(no_mangle)]
+pub fn register_lints(session: &Session, lint_store: &mut LintStore) {
+    dylint_linting::init_config(session);
+
+    security_utils::tool::register_lints(
+        lint_store,
+        &[
+            SECURITY_COMMAND_INHERITED_ENV,
+            SECURITY_COMMAND_RELATIVE_PROGRAM,
+            SECURITY_PRIVILEGE_DROP,
+        ],
+    );
+    lint_store.register_late_pass(|tcx: TyCtxt<'_>| {
+        Box::new(SecurityProcessUsage::new(tcx))
+    });
+}
+
+dylint_linting::dylint_library!();
+
+/// UI tests for the `SECURITY_COMMAND_INHERITED_ENV`,
+/// `SECURITY_COMMAND_RELATIVE_PROGRAM` and `SECURITY_PRIVILEGE_DROP` lints,
+/// the latter using the `libc` crate of the sysroot. The `ui_privileged` test
+/// restricts `SECURITY_COMMAND_INHERITED_ENV` to the crate roots matching
+/// `privileged_binaries`.
+#[cfg(test)]
+mod tests {
+    use dylint_testing::ui::Test;
+
+    #[test]
+    fn ui() {
+        Test::src_base(env!("CARGO_PKG_NAME"), "ui")
+            .rustc_flags(["--edition=2024", "-Z", "ui-testing"])
+            .run();
+    }
+
+    #[test]
+    fn ui_privileged() {
+        Test::src_base(env!("CARGO_PKG_NAME"), "ui_privileged")
+            .rustc_flags(["--edition=2024", "-Z", "ui-testing"])
+            .dylint_toml(
+                r#"process_usage.privileged_binaries = ["*/setuid_*.rs"]"#,
+            )
+            .run();
+    }
+}
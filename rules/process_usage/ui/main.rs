@@ -0,0 +1,32 @@
+#![warn(security_command_inherited_env)]
+
+use std::process::{Command, Output};
+
+fn sanitized() -> std::io::Result<Output> {
+    Command::new("/usr/bin/id")
+        .env_clear()
+        .env("PATH", "/usr/bin")
+        .output() // Should not trigger.
+}
+
+fn configured(command: &mut Command) -> std::io::Result<Output> {
+    command.output() // Should not trigger (configured by the caller).
+}
+
+fn main() -> std::io::Result<()> {
+    Command::new("/usr/bin/id").arg("-u").spawn()?; // Should trigger.
+    let _: std::process::ExitStatus =
+        Command::new("/bin/true").env("LANG", "C").status()?; // Should trigger.
+
+    let mut inherited: Command = Command::new("/usr/bin/whoami");
+    inherited.arg("--help");
+    inherited.output()?; // Should trigger.
+
+    let mut cleared: Command = Command::new("/usr/bin/whoami");
+    cleared.env_clear();
+    cleared.output()?; // Should not trigger.
+
+    sanitized()?;
+    configured(&mut Command::new("/bin/true"))?;
+    Ok(())
+}
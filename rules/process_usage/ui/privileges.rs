@@ -0,0 +1,42 @@
+// `libc` is loaded from the sysroot of the toolchain.
+#![feature(rustc_private)]
+#![warn(security_privilege_drop)]
+
+extern crate libc;
+
+use libc::{gid_t, uid_t};
+
+fn reversed(user: uid_t, group: gid_t) -> Result<(), i32> {
+    if unsafe { libc::setuid(user) } != 0 {
+        // Should trigger (before `setgid`).
+        return Err(1);
+    }
+    if unsafe { libc::setgid(group) } != 0 {
+        return Err(2);
+    }
+    Ok(())
+}
+
+fn unchecked(user: uid_t, group: gid_t) {
+    unsafe {
+        libc::setgroups(0, std::ptr::null()); // Should trigger (unchecked).
+        let _ = libc::setgid(group); // Should trigger (unchecked).
+    }
+    unsafe { libc::setuid(user) }; // Should trigger (unchecked).
+}
+
+fn ordered(user: uid_t, group: gid_t) -> Result<(), i32> {
+    let groups: i32 = unsafe { libc::setgroups(0, std::ptr::null()) }; // Should not trigger.
+    let status: i32 = unsafe { libc::setgid(group) }; // Should not trigger.
+    if groups != 0 || status != 0 || unsafe { libc::setuid(user) } != 0 {
+        // Should not trigger.
+        return Err(3);
+    }
+    Ok(())
+}
+
+fn main() {
+    let _: Result<(), i32> = reversed(1000, 1000);
+    unchecked(1000, 1000);
+    let _: Result<(), i32> = ordered(1000, 1000);
+}
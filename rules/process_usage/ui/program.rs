@@ -0,0 +1,18 @@
+#![warn(security_command_relative_program)]
+#![allow(security_command_inherited_env)]
+
+use std::process::Command;
+
+fn main() -> std::io::Result<()> {
+    Command::new("git").arg("status").output()?; // Should trigger.
+    let mut shell: Command = Command::new("sh"); // Should trigger.
+    shell.arg("-c").arg("true").status()?;
+
+    Command::new("/usr/bin/git").arg("status").output()?; // Should not trigger.
+    Command::new("./scripts/build.sh").status()?; // Should not trigger.
+    Command::new("tools/fmt").status()?; // Should not trigger (not looked up in `PATH`).
+
+    let program: String = String::from("git");
+    Command::new(program).status()?; // Should not trigger (not a literal).
+    Ok(())
+}
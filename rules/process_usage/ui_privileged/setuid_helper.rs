@@ -0,0 +1,8 @@
+#![warn(security_command_inherited_env)]
+
+use std::process::Command;
+
+fn main() -> std::io::Result<()> {
+    Command::new("/usr/bin/id").spawn()?; // Should trigger (privileged).
+    Ok(())
+}
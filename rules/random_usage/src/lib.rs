@@ -0,0 +1,257 @@
+#![feature(rustc_private)]
+
+extern crate rustc_ast;
+extern crate rustc_errors;
+extern crate rustc_hir;
+extern crate rustc_lint;
+extern crate rustc_middle;
+extern crate rustc_session;
+extern crate rustc_span;
+
+use rustc_ast::LitKind;
+use rustc_errors::{Applicability, Diag};
+use rustc_hir::{BinOpKind, Expr, ExprKind, def_id::DefId};
+use rustc_lint::{LateContext, LateLintPass, LintStore};
+use rustc_middle::ty::TyCtxt;
+use rustc_session::{Session, declare_lint, impl_lint_pass};
+use rustc_span::{Symbol, source_map::SourceMap};
+use serde::Deserialize;
+
+declare_lint! {
+    pub SECURITY_MODULO_BIAS,
+    Warn,
+    "Detects random integers reduced to a range with `%`, which makes the smaller values more likely."
+}
+
+/// Configuration of the `random_usage` library, read from the
+/// `[random_usage]` table of the workspace's `dylint.toml`.
+#[derive(Deserialize)]
+#[serde(default)]
+struct Config {
+    /// Names of the functions and methods returning a uniformly random
+    /// integer (e.g. `Rng::gen` or `rand::random`).
+    random_functions: Vec<String>,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Self {
+            random_functions: [
+                "gen", "random", "next_u32", "next_u64", "rand",
+            ]
+            .into_iter()
+            .map(String::from)
+            .collect(),
+        }
+    }
+}
+
+/// Lint pass holding the random functions loaded from the configuration.
+struct SecurityRandomUsage {
+    random_functions: Vec<String>,
+}
+
+impl_lint_pass!(SecurityRandomUsage => [SECURITY_MODULO_BIAS]);
+
+/// Returns the name of the function or method called by `expression`, if it
+/// is a call.
+///
+/// # Arguments
+/// * `context` (`&LateContext<'_>`) - The lint context, used to resolve the
+///   callee.
+/// * `expression` (`&Expr<'_>`) - The expression to inspect.
+fn called_name(
+    context: &LateContext<'_>,
+    expression: &Expr<'_>,
+) -> Option<Symbol> {
+    match &expression.kind {
+        ExprKind::MethodCall(segment, _, _, _) => Some(segment.ident.name),
+        ExprKind::Call(callee, _) => {
+            let ExprKind::Path(path): &ExprKind<'_> = &callee.kind else {
+                return None;
+            };
+            let def_id: DefId =
+                context.qpath_res(path, callee.hir_id).opt_def_id()?;
+            Some(context.tcx.item_name(def_id))
+        },
+        _ => None,
+    }
+}
+
+/// Returns `true` if `expression` is an integer literal whose value is a
+/// power of two, a divisor that a uniformly random integer reduces without
+/// bias.
+///
+/// # Arguments
+/// * `expression` (`&Expr<'_>`) - The divisor.
+fn is_power_of_two(expression: &Expr<'_>) -> bool {
+    let ExprKind::Lit(literal): &ExprKind<'_> = &expression.kind else {
+        return false;
+    };
+    matches!(literal.node, LitKind::Int(value, _) if value.get().is_power_of_two())
+}
+
+/// Returns the call `expression` reads a random value from, looking through
+/// casts (e.g. `rng.next_u32() as usize`).
+///
+/// # Arguments
+/// * `expression` (`&'tcx Expr<'tcx>`) - The left operand of `%`.
+fn peel_casts<'tcx>(expression: &'tcx Expr<'tcx>) -> &'tcx Expr<'tcx> {
+    match &expression.kind {
+        ExprKind::Cast(inner, _) => peel_casts(inner),
+        _ => expression,
+    }
+}
+
+impl SecurityRandomUsage {
+    fn new(tcx: TyCtxt<'_>) -> Self {
+        let config: Config =
+            security_utils::config::load(tcx, env!("CARGO_PKG_NAME"));
+
+        Self { random_functions: config.random_functions }
+    }
+
+    /// Returns `true` if `function` is one of the configured random
+    /// functions.
+    ///
+    /// # Arguments
+    /// * `function` (`Symbol`) - The name of the called function.
+    fn is_random_function(&self, function: Symbol) -> bool {
+        self.random_functions
+            .iter()
+            .any(|name: &String| name == function.as_str())
+    }
+}
+
+impl<'tcx> LateLintPass<'tcx> for SecurityRandomUsage {
+    /// Detect `%` applied to the integer returned by a random function,
+    /// unless the divisor is a power of two literal.
+    ///
+    /// # Arguments
+    /// * `context` (`&LateContext<'tcx>`) - The lint context providing access
+    ///   to the compiler's internal state.
+    /// * `expression` (`&'tcx Expr<'tcx>`) - The expression being checked.
+    fn check_expr(
+        &mut self,
+        context: &LateContext<'tcx>,
+        expression: &'tcx Expr<'tcx>,
+    ) {
+        if expression.span.from_expansion() {
+            return;
+        }
+
+        let ExprKind::Binary(operator, left, right): &ExprKind<'_> =
+            &expression.kind
+        else {
+            return;
+        };
+        if operator.node != BinOpKind::Rem
+            || is_power_of_two(right)
+            || !context.typeck_results().expr_ty(left).is_integral()
+        {
+            return;
+        }
+
+        let random: &Expr<'_> = peel_casts(left);
+        let Some(function): Option<Symbol> = called_name(context, random)
+        else {
+            return;
+        };
+        if !self.is_random_function(function) {
+            return;
+        }
+
+        // `rng.gen() % n` becomes `rng.gen_range(0..n)`, of the type of `n`
+        // like the remainder, and `rng.random()` becomes
+        // `rng.random_range(0..n)` with rand 0.9.
+        let source_map: &SourceMap = context.tcx.sess.source_map();
+        let replacement: Option<String> = match &random.kind {
+            ExprKind::MethodCall(segment, receiver, [], _)
+                if matches!(segment.ident.name.as_str(), "gen" | "random") =>
+            {
+                let method: &str = if segment.ident.name.as_str() == "gen" {
+                    "gen_range"
+                } else {
+                    "random_range"
+                };
+                source_map
+                    .span_to_snippet(receiver.span)
+                    .ok()
+                    .zip(source_map.span_to_snippet(right.span).ok())
+                    .map(|(receiver, divisor): (String, String)| {
+                        format!("{receiver}.{method}(0..{divisor})")
+                    })
+            },
+            _ => None,
+        };
+
+        security_utils::span_lint(
+            context,
+            SECURITY_MODULO_BIAS,
+            expression.span,
+            |diagnostic: &mut Diag<'_, ()>| {
+                diagnostic.primary_message(format!(
+                    "Random value from `{function}` reduced with `%` detected."
+                ));
+                diagnostic.note(
+                    "unless the divisor is a power of two, the smaller \
+                     results are more likely than the larger ones, which \
+                     makes generated tokens, passwords or indices easier to \
+                     guess",
+                );
+                match replacement {
+                    Some(replacement) => {
+                        diagnostic.span_suggestion(
+                            expression.span,
+                            "sample the range uniformly",
+                            replacement,
+                            Applicability::MaybeIncorrect,
+                        );
+                    },
+                    None => {
+                        diagnostic.help(
+                            "sample the range uniformly with `gen_range` (or \
+                             `random_range`), or reject the values above the \
+                             largest multiple of the divisor",
+                        );
+                    },
+                }
+            },
+        );
+    }
+}
+
+/// Registers the `SECURITY_MODULO_BIAS` lint and its corresponding lint pass
+/// with the Rust compiler. The random functions are read from the
+/// `[random_usage]` table of `dylint.toml` when the pass is created.
+///
+/// # Arguments
+/// * `session` (`&Session`) - The compiler session providing access to the
+///   compiler's internal state.
+/// * `lint_store` (`&mut LintStore`) - The lint store where the
+///   `SECURITY_MODULO_BIAS` lint and its corresponding lint pass will be
+///   registered.
+#[unsafe(no_mangle)]
+pub fn register_lints(session: &Session, lint_store: &mut LintStore) {
+    dylint_linting::init_config(session);
+
+    security_utils::tool::register_lints(lint_store, &[SECURITY_MODULO_BIAS]);
+    lint_store.register_late_pass(|tcx: TyCtxt<'_>| {
+        Box::new(SecurityRandomUsage::new(tcx))
+    });
+}
+
+dylint_linting::dylint_library!();
+
+/// UI tests for the `SECURITY_MODULO_BIAS` lint.
+#[cfg(test)]
+mod tests {
+    use dylint_testing::ui::Test;
+
+    #[test]
+    fn ui() {
+        Test::src_base(env!("CARGO_PKG_NAME"), "ui")
+            .rustc_flags(["--edition=2024", "-Z", "ui-testing"])
+            .run();
+    }
+}
@@ -0,0 +1,52 @@
+#![allow(dead_code)]
+
+/// Stand-in for `rand::Rng`.
+trait Rng {
+    fn r#gen<T: Default>(&mut self) -> T {
+        T::default()
+    }
+
+    fn next_u32(&mut self) -> u32 {
+        4
+    }
+
+    fn gen_range(&mut self, range: std::ops::Range<usize>) -> usize {
+        range.start
+    }
+}
+
+struct ThreadRng;
+
+impl Rng for ThreadRng {}
+
+/// Stand-in for `rand::random`.
+fn random<T: Default>() -> T {
+    T::default()
+}
+
+const CHARSET: &[u8] = b"abcdefghijklmnopqrstuvwxyz0123456789";
+
+fn token(rng: &mut ThreadRng) {
+    // Should trigger.
+    let _ = rng.r#gen::<u32>() % 10;
+
+    // Should trigger (through a cast).
+    let _ = rng.next_u32() as usize % CHARSET.len();
+
+    // Should trigger.
+    let _ = random::<u32>() % 62;
+
+    // Should trigger, with a suggestion.
+    let _ = rng.r#gen::<usize>() % CHARSET.len();
+
+    // Should not trigger (a power of two divides the range evenly).
+    let _ = rng.next_u32() % 16;
+
+    // Should not trigger (uniform sampling).
+    let _ = rng.gen_range(0..CHARSET.len());
+
+    // Should not trigger (not a random value).
+    let _ = CHARSET.len() % 7;
+}
+
+fn main() {}
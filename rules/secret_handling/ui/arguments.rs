@@ -0,0 +1,52 @@
+// aux-build:clap.rs
+// aux-build:clap_derive.rs
+
+extern crate clap;
+extern crate clap_derive;
+
+use clap::{Arg, Command};
+use clap_derive::Parser;
+use std::env;
+
+#[derive(Parser)]
+struct Options {
+    #[arg(long)]
+    host: String,
+    #[arg(long)]
+    password: String, // Should trigger.
+    #[arg(long)]
+    api_token: Option<String>, // Should trigger.
+    #[arg(long)]
+    password_file: String, // Should not trigger.
+}
+
+struct Config {
+    user: String,
+    secret: String,
+}
+
+fn builder() -> Command {
+    Command::new("app")
+        .arg(Arg::new("host").long("host")) // Should not trigger.
+        .arg(Arg::new("db-password").long("db-password")) // Should trigger.
+        .arg(Arg::new("apiKey")) // Should trigger.
+}
+
+fn arguments(config: &mut Config) {
+    let password: Option<String> = env::args().nth(1); // Should trigger.
+    let (user, token): (Option<String>, Option<String>) = (env::args().nth(2), env::args().nth(3)); // Should trigger.
+    let key_path: Option<String> = env::args().nth(4); // Should not trigger.
+    let host: Option<String> = env::args_os().nth(5).map(|host| host.to_string_lossy().into_owned()); // Should not trigger.
+    let secret: String = String::from("from a file"); // Should not trigger.
+
+    config.secret = env::args().last().unwrap_or_default(); // Should trigger.
+    config.user = env::args().next().unwrap_or_default(); // Should not trigger.
+
+    let _ = (password, user, token, key_path, host, secret);
+}
+
+fn main() {
+    let mut config: Config = Config { user: String::new(), secret: String::new() };
+    let _ = builder();
+    arguments(&mut config);
+}
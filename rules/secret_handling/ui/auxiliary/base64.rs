@@ -0,0 +1,19 @@
+//! Minimal stand-in for the `base64` crate.
+
+pub mod engine {
+    pub trait Engine {
+        fn encode<T: AsRef<[u8]>>(&self, _input: T) -> String {
+            String::new()
+        }
+    }
+
+    pub mod general_purpose {
+        pub struct GeneralPurpose;
+
+        impl super::Engine for GeneralPurpose {}
+
+        pub const STANDARD: GeneralPurpose = GeneralPurpose;
+    }
+}
+
+pub use engine::Engine;
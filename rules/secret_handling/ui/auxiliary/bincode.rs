@@ -0,0 +1,5 @@
+//! Minimal stand-in for the `bincode` crate.
+
+pub fn serialize_into<W: std::io::Write, T: ?Sized>(_writer: W, _value: &T) -> Result<(), ()> {
+    Ok(())
+}
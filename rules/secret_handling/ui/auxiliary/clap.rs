@@ -0,0 +1,27 @@
+//! Minimal stand-in for the `clap` crate.
+
+pub trait FromArgMatches {}
+
+pub struct Arg;
+
+impl Arg {
+    pub fn new(_id: &'static str) -> Arg {
+        Arg
+    }
+
+    pub fn long(self, _long: &'static str) -> Arg {
+        self
+    }
+}
+
+pub struct Command;
+
+impl Command {
+    pub fn new(_name: &'static str) -> Command {
+        Command
+    }
+
+    pub fn arg(self, _arg: Arg) -> Command {
+        self
+    }
+}
@@ -0,0 +1,31 @@
+// force-host
+// no-prefer-dynamic
+
+//! Minimal stand-in for the `clap` derives. Like the real `Parser` derive,
+//! `#[derive(Parser)]` implements `clap::FromArgMatches` for the type, which
+//! is what the lint uses to recognize argument types after expansion. The
+//! `arg` helper attribute is accepted and ignored.
+
+#![crate_type = "proc-macro"]
+
+extern crate proc_macro;
+
+use proc_macro::{TokenStream, TokenTree};
+
+#[proc_macro_derive(Parser, attributes(arg))]
+pub fn parser(item: TokenStream) -> TokenStream {
+    let mut after_struct: bool = false;
+
+    for token in item {
+        if let TokenTree::Ident(ident) = token {
+            if after_struct {
+                return format!("impl clap::FromArgMatches for {ident} {{}}")
+                    .parse()
+                    .unwrap();
+            }
+            after_struct = ident.to_string() == "struct";
+        }
+    }
+
+    TokenStream::new()
+}
@@ -0,0 +1,5 @@
+//! Minimal stand-in for the `hex` crate.
+
+pub fn encode<T: AsRef<[u8]>>(_data: T) -> String {
+    String::new()
+}
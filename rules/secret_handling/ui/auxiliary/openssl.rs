@@ -0,0 +1,16 @@
+//! Minimal stand-in for the `openssl` crate.
+
+pub mod symm {
+    #[derive(Clone, Copy)]
+    pub struct Cipher;
+
+    impl Cipher {
+        pub fn aes_256_cbc() -> Cipher {
+            Cipher
+        }
+    }
+
+    pub fn encrypt(_cipher: Cipher, _key: &[u8], _iv: Option<&[u8]>, _data: &[u8]) -> Result<Vec<u8>, ()> {
+        Ok(Vec::new())
+    }
+}
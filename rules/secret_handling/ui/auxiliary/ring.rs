@@ -0,0 +1,30 @@
+//! Minimal stand-in for the `ring` crate.
+
+pub mod aead {
+    pub struct Algorithm;
+
+    pub static AES_256_GCM: Algorithm = Algorithm;
+
+    pub struct UnboundKey;
+
+    impl UnboundKey {
+        pub fn new(_algorithm: &'static Algorithm, _key_bytes: &[u8]) -> Result<UnboundKey, ()> {
+            Ok(UnboundKey)
+        }
+    }
+}
+
+pub mod hmac {
+    #[derive(Clone, Copy)]
+    pub struct Algorithm;
+
+    pub static HMAC_SHA256: Algorithm = Algorithm;
+
+    pub struct Key;
+
+    impl Key {
+        pub fn new(_algorithm: Algorithm, _key_value: &[u8]) -> Key {
+            Key
+        }
+    }
+}
@@ -0,0 +1,9 @@
+//! Minimal stand-in for the `zeroize` crate.
+
+pub struct Zeroizing<T>(pub T);
+
+impl<T: Clone> Clone for Zeroizing<T> {
+    fn clone(&self) -> Self {
+        Zeroizing(self.0.clone())
+    }
+}
@@ -0,0 +1,45 @@
+// aux-build:zeroize.rs
+
+extern crate zeroize;
+
+use zeroize::Zeroizing;
+
+// Should trigger the lint: secret-named field.
+#[derive(Clone)]
+struct Credentials {
+    user: String,
+    password: String,
+}
+
+// Should trigger the lint: copyable key material.
+#[derive(Clone, Copy)]
+struct SigningKey {
+    key_bytes: [u8; 32],
+}
+
+// Should trigger the lint: known secret wrapper type.
+#[derive(Clone)]
+enum Session {
+    Anonymous,
+    Authenticated(Zeroizing<Vec<u8>>),
+}
+
+// Should not trigger the lint: no secret.
+#[derive(Clone, Copy)]
+struct Point {
+    x: i32,
+    y: i32,
+}
+
+fn main() {
+    let credentials: Credentials = Credentials {
+        user: String::new(),
+        password: String::new(),
+    };
+    let _ = (credentials.user, credentials.password);
+    let key: SigningKey = SigningKey { key_bytes: [0; 32] };
+    let _ = key.key_bytes;
+    let _ = [Session::Anonymous, Session::Authenticated(Zeroizing(Vec::new()))];
+    let point: Point = Point { x: 0, y: 0 };
+    let _ = (point.x, point.y);
+}
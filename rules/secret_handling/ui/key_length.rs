@@ -0,0 +1,27 @@
+// aux-build:openssl.rs
+// aux-build:ring.rs
+
+extern crate openssl;
+extern crate ring;
+
+use openssl::symm::{self, Cipher};
+use ring::{aead, hmac};
+
+const SHORT_KEY: [u8; 8] = *b"01234567";
+const KEY: [u8; 32] = [7; 32];
+
+fn keys(derived: &[u8]) {
+    let _ = symm::encrypt(Cipher::aes_256_cbc(), &SHORT_KEY, None, b""); // Should trigger.
+    let _ = symm::encrypt(Cipher::aes_256_cbc(), b"secret", None, b""); // Should trigger.
+    let _ = symm::encrypt(Cipher::aes_256_cbc(), &KEY, None, b""); // Should not trigger.
+    let _ = symm::encrypt(Cipher::aes_256_cbc(), derived, None, b""); // Should not trigger.
+
+    let buffer: Vec<u8> = Vec::with_capacity(8);
+    let _ = aead::UnboundKey::new(&aead::AES_256_GCM, &buffer); // Should trigger.
+    let _ = hmac::Key::new(hmac::HMAC_SHA256, &vec![0; 10]); // Should trigger.
+    let _ = hmac::Key::new(hmac::HMAC_SHA256, &vec![0; 32]); // Should not trigger.
+}
+
+fn main() {
+    keys(&[]);
+}
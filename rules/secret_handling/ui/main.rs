@@ -0,0 +1,42 @@
+// aux-build:base64.rs
+// aux-build:hex.rs
+
+extern crate base64;
+extern crate hex;
+
+use base64::Engine;
+use base64::engine::general_purpose::STANDARD;
+
+const KEY: [u8; 4] = [0x13, 0x37, 0xbe, 0xef];
+
+struct Credentials {
+    user: String,
+    password: String,
+}
+
+fn encoded(credentials: &Credentials, api_token: &[u8], payload: &[u8]) {
+    let _: String = STANDARD.encode(&credentials.password); // Should trigger.
+    let _: String = hex::encode(api_token); // Should trigger.
+    let _: String = STANDARD.encode(credentials.user.as_bytes()); // Should not trigger.
+    let _: String = hex::encode(payload); // Should not trigger.
+}
+
+fn xored(secret: &[u8], payload: &[u8], session_key: u8) -> Vec<u8> {
+    let mut obfuscated: Vec<u8> = Vec::new();
+    for index in 0..secret.len() {
+        obfuscated.push(secret[index] ^ KEY[index % KEY.len()]); // Should trigger.
+        obfuscated.push(payload[index] ^ KEY[index % KEY.len()]); // Should not trigger.
+        obfuscated.push(secret[index] ^ session_key); // Should not trigger.
+    }
+    let mut password_byte: u8 = secret[0];
+    password_byte ^= 0x5a; // Should trigger.
+    obfuscated.push(password_byte);
+    obfuscated
+}
+
+fn main() {
+    let credentials: Credentials =
+        Credentials { user: String::new(), password: String::new() };
+    encoded(&credentials, b"token", b"payload");
+    let _: Vec<u8> = xored(b"secret", b"payload", 0x42);
+}
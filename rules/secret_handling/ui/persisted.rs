@@ -0,0 +1,42 @@
+// aux-build:bincode.rs
+
+extern crate bincode;
+
+use std::env;
+use std::fs::{self, File, OpenOptions};
+use std::io::{self, Write};
+use std::os::unix::fs::OpenOptionsExt;
+
+struct Credentials {
+    user: String,
+    password: String,
+}
+
+fn persisted(credentials: &Credentials, api_token: &str) -> io::Result<()> {
+    fs::write("/dev/shm/session", &credentials.password)?; // Should trigger.
+    fs::write(env::temp_dir().join("token"), api_token)?; // Should trigger.
+    fs::write("/dev/shm/user", &credentials.user)?; // Should not trigger.
+    fs::write("/etc/app/token", api_token)?; // Should not trigger.
+
+    let mut log: File = File::create("debug.log")?;
+    writeln!(log, "authenticating with {}", api_token)?; // Should trigger.
+    writeln!(log, "authenticating {}", credentials.user)?; // Should not trigger.
+
+    let mut shared: File = OpenOptions::new().write(true).create(true).mode(0o644).open("keys")?;
+    shared.write_all(api_token.as_bytes())?; // Should trigger.
+
+    let mut private: File = OpenOptions::new().write(true).create(true).mode(0o600).open("keys")?;
+    private.write_all(api_token.as_bytes())?; // Should not trigger.
+    write!(private, "{api_token}")?; // Should not trigger.
+
+    let path: String = String::from("/dev/shm/credentials.json");
+    let file: File = File::create(&path)?;
+    let _ = bincode::serialize_into(file, &credentials.password); // Should trigger.
+    let _ = bincode::serialize_into(io::stdout(), &credentials.password); // Should not trigger.
+    Ok(())
+}
+
+fn main() {
+    let credentials: Credentials = Credentials { user: String::new(), password: String::new() };
+    let _ = persisted(&credentials, "");
+}
@@ -0,0 +1,167 @@
+#![feature(rustc_private)]
+
+extern crate rustc_ast;
+extern crate rustc_errors;
+extern crate rustc_hir;
+extern crate rustc_lint;
+extern crate rustc_middle;
+extern crate rustc_session;
+extern crate rustc_span;
+
+use rustc_ast::LitKind;
+use rustc_errors::Diag;
+use rustc_hir::{AssignOpKind, BinOpKind, Expr, ExprKind};
+use rustc_lint::{LateContext, LateLintPass, LintStore};
+use rustc_middle::ty::{self, Ty, TyCtxt};
+use rustc_session::{Session, declare_lint, declare_lint_pass};
+
+declare_lint! {
+    pub SECURITY_SHIFT_USAGE,
+    Deny,
+    "Detects shifts by amounts that may exceed the bit width of the operand."
+}
+
+declare_lint_pass!(SecurityShiftUsage => [SECURITY_SHIFT_USAGE]);
+
+/// Returns the bit width of the primitive integer type `ty`, or `None` if
+/// `ty` is not a primitive integer type (e.g. a type with an overloaded `Shl`
+/// or `Shr` implementation).
+///
+/// # Arguments
+/// * `context` (`&LateContext<'_>`) - The lint context, used to resolve the
+///   pointer width of `isize` and `usize`.
+/// * `ty` (`Ty<'_>`) - The type of the shifted operand.
+fn bit_width(context: &LateContext<'_>, ty: Ty<'_>) -> Option<u64> {
+    let pointer_width: u64 = context.tcx.data_layout.pointer_size().bits();
+
+    match ty.kind() {
+        ty::Int(int_ty) => Some(int_ty.bit_width().unwrap_or(pointer_width)),
+        ty::Uint(uint_ty) => {
+            Some(uint_ty.bit_width().unwrap_or(pointer_width))
+        },
+        _ => None,
+    }
+}
+
+/// Returns `true` if `amount` is an integer literal strictly below `width`,
+/// in which case the shift can never overflow.
+///
+/// # Arguments
+/// * `amount` (`&Expr<'_>`) - The shift amount expression.
+/// * `width` (`u64`) - The bit width of the shifted operand.
+fn is_safe_literal(amount: &Expr<'_>, width: u64) -> bool {
+    if let ExprKind::Lit(literal) = &amount.kind
+        && let LitKind::Int(value, _) = literal.node
+    {
+        return value.get() < u128::from(width);
+    }
+
+    false
+}
+
+impl<'tcx> LateLintPass<'tcx> for SecurityShiftUsage {
+    /// Detect `<<`, `>>`, `<<=` and `>>=` on primitive integers whose shift
+    /// amount is not a literal below the operand's bit width.
+    ///
+    /// # Arguments
+    /// * `context` (`&LateContext<'tcx>`) - The lint context providing access
+    ///   to the compiler's internal state.
+    /// * `expression` (`&'tcx Expr<'tcx>`) - The expression being checked for
+    ///   shift operations.
+    fn check_expr(
+        &mut self,
+        context: &LateContext<'tcx>,
+        expression: &'tcx Expr<'tcx>,
+    ) {
+        if expression.span.from_expansion() {
+            return;
+        }
+
+        let (operand, amount, method): (&Expr<'_>, &Expr<'_>, &str) =
+            match &expression.kind {
+                ExprKind::Binary(operator, operand, amount) => {
+                    match operator.node {
+                        BinOpKind::Shl => (operand, amount, "checked_shl"),
+                        BinOpKind::Shr => (operand, amount, "checked_shr"),
+                        _ => return,
+                    }
+                },
+                ExprKind::AssignOp(operator, operand, amount) => {
+                    match operator.node {
+                        AssignOpKind::ShlAssign => {
+                            (operand, amount, "checked_shl")
+                        },
+                        AssignOpKind::ShrAssign => {
+                            (operand, amount, "checked_shr")
+                        },
+                        _ => return,
+                    }
+                },
+                _ => return,
+            };
+
+        let operand_ty: Ty<'tcx> =
+            context.typeck_results().expr_ty(operand).peel_refs();
+        let Some(width): Option<u64> = bit_width(context, operand_ty) else {
+            return;
+        };
+
+        if is_safe_literal(amount, width) {
+            return;
+        }
+
+        security_utils::span_lint(
+            context,
+            SECURITY_SHIFT_USAGE,
+            expression.span,
+            |diagnostic: &mut Diag<'_, ()>| {
+                diagnostic.primary_message(format!(
+                    "Shift of `{operand_ty}` by an amount that may reach its \
+                     bit width ({width}) detected."
+                ));
+                diagnostic.note(
+                    "this panics in debug mode and silently masks the shift \
+                     amount in release mode",
+                );
+                diagnostic.help(format!(
+                    "use `{method}` and handle the `None` case explicitly"
+                ));
+            },
+        );
+    }
+}
+
+/// Registers the `SECURITY_SHIFT_USAGE` lint and its corresponding lint pass
+/// with the Rust compiler.
+///
+/// # Arguments
+/// * `session` (`&Session`) - The compiler session providing access to the
+///   compiler's internal state.
+/// * `lint_store` (`&mut LintStore`) - The lint store where the
+///   `SECURITY_SHIFT_USAGE` lint and its corresponding lint pass will be
+///   registered.
+#[unsafe(no_mangle)]
+pub fn register_lints(session: &Session, lint_store: &mut LintStore) {
+    dylint_linting::init_config(session);
+
+    security_utils::tool::register_lints(lint_store, &[SECURITY_SHIFT_USAGE]);
+    lint_store
+        .register_late_pass(|_: TyCtxt<'_>| Box::new(SecurityShiftUsage));
+}
+
+dylint_linting::dylint_library!();
+
+/// UI tests for the `SECURITY_SHIFT_USAGE` lint. The tests check that shifts
+/// by variable or out-of-range amounts are reported, while shifts by literals
+/// below the operand's bit width and overloaded shift operators are not.
+#[cfg(test)]
+mod tests {
+    use dylint_testing::ui::Test;
+
+    #[test]
+    fn ui() {
+        Test::src_base(env!("CARGO_PKG_NAME"), "ui")
+            .rustc_flags(["--edition=2024", "-Z", "ui-testing"])
+            .run();
+    }
+}
@@ -0,0 +1,36 @@
+#![warn(security_shift_usage)]
+
+use std::ops::Shl;
+
+struct Bits(u8);
+
+impl Shl<u32> for Bits {
+    type Output = Self;
+
+    fn shl(self, amount: u32) -> Self {
+        Self(self.0.wrapping_shl(amount))
+    }
+}
+
+/// The `main` function exercises shifts on primitive integers by literal and
+/// variable amounts, as well as an overloaded shift operator. Only shifts by
+/// amounts that are not literals below the operand's bit width should trigger
+/// the `SECURITY_SHIFT_USAGE` lint.
+fn main() {
+    let value: u32 = 1;
+    let amount: u32 = 4;
+
+    let _: u32 = value << 3; // Should not trigger (literal below 32).
+    let _: u32 = value >> 31; // Should not trigger (literal below 32).
+
+    let _: u32 = value << amount; // Should trigger.
+    let _: u32 = value >> amount; // Should trigger.
+    let _: u64 = 1u64 << (amount + 1); // Should trigger.
+
+    let mut shifted: usize = 1;
+    shifted <<= amount; // Should trigger.
+    shifted >>= 2; // Should not trigger (literal below pointer width).
+    let _: usize = shifted;
+
+    let _: Bits = Bits(1) << amount; // Should not trigger (overloaded).
+}
@@ -0,0 +1,461 @@
+#![feature(rustc_private)]
+
+extern crate rustc_errors;
+extern crate rustc_hir;
+extern crate rustc_lint;
+extern crate rustc_middle;
+extern crate rustc_session;
+extern crate rustc_span;
+
+use rustc_errors::Diag;
+use rustc_hir::{
+    Expr,
+    ExprKind,
+    LetStmt,
+    PatKind,
+    QPath,
+    def_id::DefId,
+    intravisit::{Visitor, walk_expr},
+};
+use rustc_lint::{LateContext, LateLintPass, LintStore};
+use rustc_middle::ty::{AdtDef, Ty, TyCtxt};
+use rustc_session::{Session, declare_lint, impl_lint_pass};
+use rustc_span::{Span, Symbol, sym};
+use security_utils::calls::{Call, call, is_named};
+use serde::Deserialize;
+
+declare_lint! {
+    pub SECURITY_TIMESTAMP_IDENTIFIER,
+    Warn,
+    "Detects timestamps used to build tokens, nonces or identifiers."
+}
+
+declare_lint! {
+    pub SECURITY_TIMESTAMP_RANDOMNESS,
+    Warn,
+    "Detects timestamps used as a source of randomness."
+}
+
+/// Configuration of the `timestamp_identifier` library, read from the
+/// `[timestamp_identifier]` table of the workspace's `dylint.toml`.
+#[derive(Deserialize)]
+#[serde(default)]
+struct Config {
+    /// Identifier words that mark a value as a token or identifier (e.g.
+    /// `nonce` matches `request_nonce` and `nonceValue`).
+    patterns: Vec<String>,
+    /// Names of the functions and methods seeding a random number generator
+    /// (e.g. `SeedableRng::seed_from_u64` or `fastrand::seed`).
+    seed_functions: Vec<String>,
+    /// Names of the functions and methods creating or opening a file from the
+    /// path passed as first argument (e.g. `File::create`).
+    path_functions: Vec<String>,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Self {
+            patterns: ["token", "id", "nonce", "session", "uuid"]
+                .into_iter()
+                .map(String::from)
+                .collect(),
+            seed_functions: [
+                "seed_from_u64",
+                "from_seed",
+                "with_seed",
+                "seed",
+                "srand",
+            ]
+            .into_iter()
+            .map(String::from)
+            .collect(),
+            path_functions: [
+                "create",
+                "create_new",
+                "open",
+                "create_dir",
+                "create_dir_all",
+                "write",
+            ]
+            .into_iter()
+            .map(String::from)
+            .collect(),
+        }
+    }
+}
+
+/// Methods extracting the sub-second part, or the full precision, of a clock
+/// reading, whose result looks random but is predictable.
+const SUBSECOND_METHODS: [&str; 8] = [
+    "subsec_nanos",
+    "subsec_micros",
+    "subsec_millis",
+    "as_nanos",
+    "as_micros",
+    "timestamp_nanos",
+    "timestamp_nanos_opt",
+    "timestamp_subsec_nanos",
+];
+
+/// Lint pass holding the identifier patterns, seed functions and path
+/// functions loaded from the configuration.
+struct SecurityTimestampIdentifier {
+    patterns: Vec<String>,
+    seed_functions: Vec<String>,
+    path_functions: Vec<String>,
+}
+
+impl_lint_pass!(SecurityTimestampIdentifier => [
+    SECURITY_TIMESTAMP_IDENTIFIER,
+    SECURITY_TIMESTAMP_RANDOMNESS,
+]);
+
+/// Returns `true` if `def_id` is one of the clock readings this lint tracks:
+/// `SystemTime::now`, `Instant::now`, or any `now` function of the `chrono`
+/// crate (`Utc::now`, `Local::now`).
+///
+/// # Arguments
+/// * `tcx` (`TyCtxt<'_>`) - The type context used to resolve the function.
+/// * `def_id` (`DefId`) - The called function.
+fn is_clock_reading(tcx: TyCtxt<'_>, def_id: DefId) -> bool {
+    if tcx.item_name(def_id).as_str() != "now" {
+        return false;
+    }
+
+    if tcx.crate_name(def_id.krate).as_str() == "chrono" {
+        return true;
+    }
+
+    let parent: DefId = tcx.parent(def_id);
+    let self_ty: Ty<'_> = tcx.type_of(parent).instantiate_identity();
+    self_ty.ty_adt_def().is_some_and(|adt: AdtDef<'_>| {
+        tcx.crate_name(adt.did().krate) == sym::std
+            && matches!(
+                tcx.item_name(adt.did()).as_str(),
+                "SystemTime" | "Instant"
+            )
+    })
+}
+
+/// Visitor searching an expression tree for a clock reading, including inside
+/// `format!` expansions and method chains. Struct literals are not entered, as
+/// their fields are checked individually.
+struct ClockFinder<'a, 'tcx> {
+    context: &'a LateContext<'tcx>,
+    found: Option<Span>,
+}
+
+impl<'tcx> Visitor<'tcx> for ClockFinder<'_, 'tcx> {
+    fn visit_expr(&mut self, expression: &'tcx Expr<'tcx>) {
+        if self.found.is_some() {
+            return;
+        }
+
+        if let ExprKind::Call(callee, _) = &expression.kind
+            && let ExprKind::Path(path) = &callee.kind
+            && let Some(def_id) =
+                self.context.qpath_res(path, callee.hir_id).opt_def_id()
+            && is_clock_reading(self.context.tcx, def_id)
+        {
+            self.found = Some(expression.span);
+            return;
+        }
+
+        if matches!(expression.kind, ExprKind::Struct(..)) {
+            return;
+        }
+
+        walk_expr(self, expression);
+    }
+}
+
+/// Returns the span of the clock reading found in `expression`, if any.
+///
+/// # Arguments
+/// * `context` (`&LateContext<'tcx>`) - The lint context, used to resolve the
+///   called functions.
+/// * `expression` (`&'tcx Expr<'tcx>`) - The expression to search.
+fn find_clock<'tcx>(
+    context: &LateContext<'tcx>,
+    expression: &'tcx Expr<'tcx>,
+) -> Option<Span> {
+    let mut finder: ClockFinder<'_, 'tcx> =
+        ClockFinder { context, found: None };
+    finder.visit_expr(expression);
+    finder.found
+}
+
+/// Visitor searching an expression tree for the sub-second part of a clock
+/// reading, e.g.
+/// `SystemTime::now().duration_since(UNIX_EPOCH)?.subsec_nanos()`.
+struct SubsecondFinder<'a, 'tcx> {
+    context: &'a LateContext<'tcx>,
+    found: Option<Span>,
+}
+
+impl<'tcx> Visitor<'tcx> for SubsecondFinder<'_, 'tcx> {
+    fn visit_expr(&mut self, expression: &'tcx Expr<'tcx>) {
+        if self.found.is_some() {
+            return;
+        }
+
+        if let ExprKind::MethodCall(segment, receiver, _, _) = &expression.kind
+            && SUBSECOND_METHODS.contains(&segment.ident.name.as_str())
+            && find_clock(self.context, receiver).is_some()
+        {
+            self.found = Some(expression.span);
+            return;
+        }
+
+        walk_expr(self, expression);
+    }
+}
+
+impl SecurityTimestampIdentifier {
+    fn new(tcx: TyCtxt<'_>) -> Self {
+        let config: Config =
+            security_utils::config::load(tcx, env!("CARGO_PKG_NAME"));
+
+        Self {
+            patterns: config
+                .patterns
+                .into_iter()
+                .map(|pattern: String| pattern.to_lowercase())
+                .collect(),
+            seed_functions: config.seed_functions,
+            path_functions: config.path_functions,
+        }
+    }
+
+    /// Returns `true` if one of the words of `name` is a configured pattern,
+    /// optionally followed by a plural `s`.
+    ///
+    /// # Arguments
+    /// * `name` (`&str`) - The identifier to check.
+    fn is_identifier_name(&self, name: &str) -> bool {
+        security_utils::words::matches(&self.patterns, name)
+    }
+
+    /// Reports `value` if `name` matches an identifier pattern and `value` is
+    /// derived from a clock reading.
+    ///
+    /// # Arguments
+    /// * `context` (`&LateContext<'tcx>`) - The lint context used to emit the
+    ///   diagnostic.
+    /// * `name` (`Symbol`) - The name of the binding, field or place being
+    ///   assigned.
+    /// * `span` (`Span`) - The span to report.
+    /// * `value` (`&'tcx Expr<'tcx>`) - The assigned expression.
+    fn check_assignment<'tcx>(
+        &self,
+        context: &LateContext<'tcx>,
+        name: Symbol,
+        span: Span,
+        value: &'tcx Expr<'tcx>,
+    ) {
+        if span.from_expansion() || !self.is_identifier_name(name.as_str()) {
+            return;
+        }
+
+        let Some(clock_span): Option<Span> = find_clock(context, value) else {
+            return;
+        };
+
+        security_utils::span_lint(
+            context,
+            SECURITY_TIMESTAMP_IDENTIFIER,
+            span,
+            |diagnostic: &mut Diag<'_, ()>| {
+                diagnostic.primary_message(format!(
+                    "Timestamp used to derive identifier `{name}`."
+                ));
+                diagnostic.span_note(clock_span, "the timestamp is read here");
+                diagnostic.help(
+                    "timestamps are predictable; use a cryptographically \
+                     secure random generator or a random UUID instead",
+                );
+            },
+        );
+    }
+
+    /// Reports the call `expression` if it seeds a random number generator
+    /// from a clock reading, or creates a file whose path is derived from
+    /// the sub-second part of a clock reading.
+    ///
+    /// # Arguments
+    /// * `context` (`&LateContext<'tcx>`) - The lint context used to emit the
+    ///   diagnostic.
+    /// * `expression` (`&'tcx Expr<'tcx>`) - The expression to inspect.
+    fn check_randomness<'tcx>(
+        &self,
+        context: &LateContext<'tcx>,
+        expression: &'tcx Expr<'tcx>,
+    ) {
+        if expression.span.from_expansion() {
+            return;
+        }
+        let Some(call): Option<Call<'_>> = call(context, expression) else {
+            return;
+        };
+        let function: Symbol = context.tcx.item_name(call.callee);
+        let arguments: &[Expr<'_>] = call.arguments;
+
+        let (clock_span, usage): (Span, &str) =
+            if is_named(&self.seed_functions, function) {
+                let Some(clock_span): Option<Span> =
+                    arguments.iter().find_map(|argument: &'tcx Expr<'tcx>| {
+                        find_clock(context, argument)
+                    })
+                else {
+                    return;
+                };
+                (clock_span, "seed of")
+            } else if is_named(&self.path_functions, function) {
+                let Some(path): Option<&Expr<'_>> = arguments.first() else {
+                    return;
+                };
+                let mut finder: SubsecondFinder<'_, 'tcx> =
+                    SubsecondFinder { context, found: None };
+                finder.visit_expr(path);
+                let Some(clock_span): Option<Span> = finder.found else {
+                    return;
+                };
+                (clock_span, "file name passed to")
+            } else {
+                return;
+            };
+
+        security_utils::span_lint(
+            context,
+            SECURITY_TIMESTAMP_RANDOMNESS,
+            expression.span,
+            |diagnostic: &mut Diag<'_, ()>| {
+                diagnostic.primary_message(format!(
+                    "Timestamp used as the {usage} `{function}`."
+                ));
+                diagnostic.span_note(clock_span, "the timestamp is read here");
+                diagnostic.help(
+                    "timestamps are predictable; seed from the operating \
+                     system (e.g. `rand::rngs::OsRng`) or create temporary \
+                     files with the `tempfile` crate",
+                );
+            },
+        );
+    }
+}
+
+/// Returns the name of the place written by an assignment, if it is a local
+/// variable, a path or a field.
+///
+/// # Arguments
+/// * `place` (`&Expr<'_>`) - The left-hand side of the assignment.
+fn place_name(place: &Expr<'_>) -> Option<Symbol> {
+    match &place.kind {
+        ExprKind::Path(QPath::Resolved(None, path)) => {
+            Some(path.segments.last()?.ident.name)
+        },
+        ExprKind::Field(_, ident) => Some(ident.name),
+        _ => None,
+    }
+}
+
+impl<'tcx> LateLintPass<'tcx> for SecurityTimestampIdentifier {
+    /// Detect `let` bindings with an identifier-like name initialized from a
+    /// timestamp.
+    ///
+    /// # Arguments
+    /// * `context` (`&LateContext<'tcx>`) - The lint context providing access
+    ///   to the compiler's internal state.
+    /// * `local` (`&'tcx LetStmt<'tcx>`) - The let statement being checked.
+    fn check_local(
+        &mut self,
+        context: &LateContext<'tcx>,
+        local: &'tcx LetStmt<'tcx>,
+    ) {
+        if let PatKind::Binding(_, _, ident, _) = local.pat.kind
+            && let Some(init) = local.init
+        {
+            self.check_assignment(context, ident.name, local.pat.span, init);
+        }
+    }
+
+    /// Detect assignments and struct fields with an identifier-like name whose
+    /// value is derived from a timestamp, and timestamps seeding random number
+    /// generators or naming files.
+    ///
+    /// # Arguments
+    /// * `context` (`&LateContext<'tcx>`) - The lint context providing access
+    ///   to the compiler's internal state.
+    /// * `expression` (`&'tcx Expr<'tcx>`) - The expression being checked for
+    ///   assignments and struct literals.
+    fn check_expr(
+        &mut self,
+        context: &LateContext<'tcx>,
+        expression: &'tcx Expr<'tcx>,
+    ) {
+        self.check_randomness(context, expression);
+
+        match &expression.kind {
+            ExprKind::Assign(place, value, _) => {
+                if let Some(name) = place_name(place) {
+                    self.check_assignment(context, name, place.span, value);
+                }
+            },
+            ExprKind::Struct(_, fields, _) => {
+                for field in *fields {
+                    self.check_assignment(
+                        context,
+                        field.ident.name,
+                        field.span,
+                        field.expr,
+                    );
+                }
+            },
+            _ => {},
+        }
+    }
+}
+
+/// Registers the `SECURITY_TIMESTAMP_IDENTIFIER` and
+/// `SECURITY_TIMESTAMP_RANDOMNESS` lints and their lint pass with the Rust
+/// compiler. The identifier patterns, seed functions and path functions are
+/// read from the `[timestamp_identifier]` table of `dylint.toml` when the pass
+/// is created.
+///
+/// # Arguments
+/// * `session` (`&Session`) - The compiler session providing access to the
+///   compiler's internal state.
+/// * `lint_store` (`&mut LintStore`) - The lint store where the lints and
+///   their lint pass will be registered.
+#[unsafe(no_mangle)]
+pub fn register_lints(session: &Session, lint_store: &mut LintStore) {
+    dylint_linting::init_config(session);
+
+    security_utils::tool::register_lints(
+        lint_store,
+        &[SECURITY_TIMESTAMP_IDENTIFIER, SECURITY_TIMESTAMP_RANDOMNESS],
+    );
+    lint_store.register_late_pass(|tcx: TyCtxt<'_>| {
+        Box::new(SecurityTimestampIdentifier::new(tcx))
+    });
+}
+
+dylint_linting::dylint_library!();
+
+/// UI tests for the `SECURITY_TIMESTAMP_IDENTIFIER` and
+/// `SECURITY_TIMESTAMP_RANDOMNESS` lints. The tests check that tokens, nonces
+/// and identifiers derived from `SystemTime`, `Instant` and `chrono` clock
+/// readings are reported, while unrelated bindings and timestamps stored under
+/// non-identifier names are not, and that clock readings seeding generators or
+/// naming files are reported.
+#[cfg(test)]
+mod tests {
+    use dylint_testing::ui::Test;
+
+    #[test]
+    fn ui() {
+        Test::src_base(env!("CARGO_PKG_NAME"), "ui")
+            .rustc_flags(["--edition=2024", "-Z", "ui-testing"])
+            .run();
+    }
+}
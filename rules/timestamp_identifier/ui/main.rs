@@ -0,0 +1,42 @@
+// aux-build:chrono.rs
+#![warn(security_timestamp_identifier)]
+
+extern crate chrono;
+
+use std::time::{Instant, SystemTime, UNIX_EPOCH};
+
+struct Session {
+    session_id: String,
+    created_at: u64,
+}
+
+/// The `main` function derives several values from clock readings. Only the
+/// values stored under token, nonce or identifier names should trigger the
+/// `SECURITY_TIMESTAMP_IDENTIFIER` lint.
+fn main() {
+    let nanos: u128 = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|elapsed| elapsed.as_nanos())
+        .unwrap_or_default();
+
+    // Should trigger.
+    let reset_token: String = format!(
+        "{}",
+        SystemTime::now().duration_since(UNIX_EPOCH).unwrap_or_default().as_nanos()
+    );
+
+    // Should trigger.
+    let nonce: u64 = chrono::Utc::now().timestamp_millis() as u64;
+
+    let started: Instant = Instant::now(); // Should not trigger (not an identifier).
+
+    let mut request_id: u128 = 0;
+    request_id = Instant::now().elapsed().as_nanos(); // Should trigger.
+
+    let session: Session = Session {
+        session_id: format!("{:?}", Instant::now()), // Should trigger.
+        created_at: nanos as u64, // Should not trigger (not an identifier).
+    };
+
+    let order_id: u64 = 42; // Should not trigger (not a timestamp).
+}
@@ -0,0 +1,51 @@
+#![warn(security_timestamp_randomness)]
+
+use std::{
+    fs::File,
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+mod rand {
+    pub struct StdRng(pub u64);
+
+    impl StdRng {
+        pub fn seed_from_u64(state: u64) -> Self {
+            Self(state)
+        }
+    }
+}
+
+fn nanos() -> u32 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .subsec_nanos()
+}
+
+fn main() {
+    let _: rand::StdRng = rand::StdRng::seed_from_u64(
+        SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_secs(),
+    ); // Should trigger.
+
+    let _ = File::create(format!(
+        "/tmp/upload-{}",
+        SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap_or_default()
+            .subsec_nanos()
+    )); // Should trigger.
+
+    let _ = File::create(format!(
+        "/var/log/app-{}.log",
+        SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_secs()
+    )); // Should not trigger (not sub-second).
+
+    let _: rand::StdRng = rand::StdRng::seed_from_u64(42); // Should not trigger.
+    let _ = File::create(format!("/tmp/upload-{}", nanos())); // Should not trigger (derived in another function).
+}
@@ -0,0 +1,527 @@
+#![feature(rustc_private)]
+
+extern crate rustc_ast;
+extern crate rustc_errors;
+extern crate rustc_hir;
+extern crate rustc_lint;
+extern crate rustc_middle;
+extern crate rustc_session;
+extern crate rustc_span;
+
+use rustc_ast::LitKind;
+use rustc_errors::Diag;
+use rustc_hir::{
+    Expr,
+    ExprKind,
+    QPath,
+    def::{CtorOf, DefKind, Res},
+    def_id::DefId,
+    intravisit::{Visitor, walk_expr},
+};
+use rustc_lint::{LateContext, LateLintPass, LintStore};
+use rustc_middle::ty::TyCtxt;
+use rustc_session::{Session, declare_lint, impl_lint_pass};
+use rustc_span::Symbol;
+use security_utils::calls::{Call, call, is_constructor};
+use serde::Deserialize;
+
+declare_lint! {
+    pub SECURITY_WEAK_TLS_VERSION,
+    Warn,
+    "Detects TLS configurations permitting SSL, TLS 1.0 or TLS 1.1."
+}
+
+declare_lint! {
+    pub SECURITY_WEAK_TLS_CIPHER,
+    Warn,
+    "Detects TLS configurations enabling weak cipher suites or legacy renegotiation."
+}
+
+/// Configuration of the `tls_config` library, read from the `[tls_config]`
+/// table of the workspace's `dylint.toml`.
+#[derive(Deserialize)]
+#[serde(default)]
+struct Config {
+    /// Paths of the constants and variants naming a weak protocol version
+    /// (e.g. `native_tls::Protocol::Tlsv10`). A call passing one of them is
+    /// reported.
+    weak_versions: Vec<String>,
+    /// Paths of the options disabling a weak protocol version (e.g.
+    /// `openssl::ssl::SslOptions::NO_TLSV1`). A call to `clear_options`
+    /// passing one of them is reported.
+    version_options: Vec<String>,
+    /// Names of the methods taking an OpenSSL cipher string (e.g.
+    /// `SslContextBuilder::set_cipher_list`).
+    cipher_list_methods: Vec<String>,
+    /// Components of cipher strings and cipher suite names enabling weak
+    /// ciphers (e.g. `NULL`, `EXPORT` or `RC4`).
+    weak_ciphers: Vec<String>,
+    /// Paths of the enums naming cipher suites (e.g. `rustls::CipherSuite`),
+    /// whose variants are weak when their name contains one of
+    /// `weak_ciphers`.
+    cipher_suite_types: Vec<String>,
+    /// Paths of the options enabling legacy renegotiation (e.g.
+    /// `openssl::ssl::SslOptions::ALLOW_UNSAFE_LEGACY_RENEGOTIATION`). A call
+    /// to `set_options` passing one of them is reported.
+    renegotiation_options: Vec<String>,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Self {
+            weak_versions: [
+                "native_tls::Protocol::Sslv3",
+                "native_tls::Protocol::Tlsv10",
+                "native_tls::Protocol::Tlsv11",
+                "openssl::ssl::SslVersion::SSL3",
+                "openssl::ssl::SslVersion::TLS1",
+                "openssl::ssl::SslVersion::TLS1_1",
+                "reqwest::tls::Version::TLS_1_0",
+                "reqwest::tls::Version::TLS_1_1",
+                "rustls::ProtocolVersion::SSLv2",
+                "rustls::ProtocolVersion::SSLv3",
+                "rustls::ProtocolVersion::TLSv1_0",
+                "rustls::ProtocolVersion::TLSv1_1",
+            ]
+            .into_iter()
+            .map(String::from)
+            .collect(),
+            version_options: [
+                "openssl::ssl::SslOptions::NO_SSLV2",
+                "openssl::ssl::SslOptions::NO_SSLV3",
+                "openssl::ssl::SslOptions::NO_TLSV1",
+                "openssl::ssl::SslOptions::NO_TLSV1_1",
+            ]
+            .into_iter()
+            .map(String::from)
+            .collect(),
+            cipher_list_methods: ["set_cipher_list", "set_ciphersuites"]
+                .into_iter()
+                .map(String::from)
+                .collect(),
+            weak_ciphers: [
+                "NULL",
+                "eNULL",
+                "aNULL",
+                "anon",
+                "ADH",
+                "AECDH",
+                "EXPORT",
+                "EXP",
+                "EXPORT40",
+                "EXPORT56",
+                "LOW",
+                "RC2",
+                "RC4",
+                "DES",
+                "3DES",
+                "MD5",
+                "ALL",
+                "COMPLEMENTOFALL",
+            ]
+            .into_iter()
+            .map(String::from)
+            .collect(),
+            cipher_suite_types: ["rustls::CipherSuite"]
+                .into_iter()
+                .map(String::from)
+                .collect(),
+            renegotiation_options: [
+                "openssl::ssl::SslOptions::ALLOW_UNSAFE_LEGACY_RENEGOTIATION",
+                "openssl::ssl::SslOptions::LEGACY_SERVER_CONNECT",
+            ]
+            .into_iter()
+            .map(String::from)
+            .collect(),
+        }
+    }
+}
+
+/// Lint pass holding the weak protocol versions, ciphers and options loaded
+/// from the configuration.
+struct SecurityTlsConfig {
+    weak_versions: Vec<String>,
+    version_options: Vec<String>,
+    cipher_list_methods: Vec<String>,
+    weak_ciphers: Vec<String>,
+    cipher_suite_types: Vec<String>,
+    renegotiation_options: Vec<String>,
+}
+
+impl_lint_pass!(SecurityTlsConfig => [
+    SECURITY_WEAK_TLS_VERSION,
+    SECURITY_WEAK_TLS_CIPHER,
+]);
+
+/// Weak TLS configuration set by a call.
+enum WeakConfig {
+    /// A cipher enabled by a cipher string, e.g. `RC4` in `"HIGH:RC4"`.
+    CipherString(String),
+    /// A cipher suite constant, e.g.
+    /// `rustls::CipherSuite::TLS_RSA_WITH_NULL_SHA`.
+    CipherSuite(String),
+    /// An option enabling legacy renegotiation.
+    Renegotiation(String),
+}
+
+/// Visitor collecting the paths of the constants and variants referenced by
+/// the arguments of a call. Nested calls (other than enum variant and struct
+/// constructors such as `Some(...)`) and closures are not entered, as they
+/// are checked on their own.
+struct PathCollector<'a, 'tcx> {
+    context: &'a LateContext<'tcx>,
+    paths: Vec<String>,
+}
+
+impl<'tcx> Visitor<'tcx> for PathCollector<'_, 'tcx> {
+    fn visit_expr(&mut self, expression: &'tcx Expr<'tcx>) {
+        match &expression.kind {
+            ExprKind::Path(path) => {
+                if let Some(id) = constant(self.context, path, expression) {
+                    self.paths.push(self.context.tcx.def_path_str(id));
+                }
+            },
+            ExprKind::Call(callee, _)
+                if is_constructor(self.context, callee) =>
+            {
+                walk_expr(self, expression);
+            },
+            ExprKind::Call(..)
+            | ExprKind::MethodCall(..)
+            | ExprKind::Closure(..) => {},
+            _ => walk_expr(self, expression),
+        }
+    }
+}
+
+/// Returns the constant or enum variant `path` resolves to, if any. Unit
+/// variants resolve to their constructor, whose parent is the variant.
+///
+/// # Arguments
+/// * `context` (`&LateContext<'_>`) - The lint context, used to resolve the
+///   path.
+/// * `path` (`&QPath<'_>`) - The path to resolve.
+/// * `expression` (`&Expr<'_>`) - The path expression.
+fn constant(
+    context: &LateContext<'_>,
+    path: &QPath<'_>,
+    expression: &Expr<'_>,
+) -> Option<DefId> {
+    match context.qpath_res(path, expression.hir_id) {
+        Res::Def(DefKind::Const | DefKind::AssocConst, id) => Some(id),
+        Res::Def(DefKind::Ctor(CtorOf::Variant, _), id) => {
+            Some(context.tcx.parent(id))
+        },
+        _ => None,
+    }
+}
+
+impl SecurityTlsConfig {
+    fn new(tcx: TyCtxt<'_>) -> Self {
+        let config: Config =
+            security_utils::config::load(tcx, env!("CARGO_PKG_NAME"));
+
+        Self {
+            weak_versions: config.weak_versions,
+            version_options: config.version_options,
+            cipher_list_methods: config.cipher_list_methods,
+            weak_ciphers: config.weak_ciphers,
+            cipher_suite_types: config.cipher_suite_types,
+            renegotiation_options: config.renegotiation_options,
+        }
+    }
+
+    /// Returns the weak protocol version permitted by a call to `method`
+    /// referencing `paths` in its arguments, i.e. a weak version passed to
+    /// it, or an option disabling a weak version passed to `clear_options`.
+    ///
+    /// # Arguments
+    /// * `method` (`Symbol`) - The name of the called method or function.
+    /// * `paths` (`&[String]`) - The constants referenced by the arguments.
+    fn weak_version(
+        &self,
+        method: Symbol,
+        paths: &[String],
+    ) -> Option<String> {
+        let weak: &[String] = if method.as_str() == "clear_options" {
+            &self.version_options
+        } else {
+            &self.weak_versions
+        };
+        paths.iter().find(|path: &&String| weak.contains(path)).cloned()
+    }
+
+    /// Returns `true` if `name`, split on `separator`, contains one of the
+    /// configured weak ciphers, e.g. `EXP-RC4-MD5` split on `-`.
+    ///
+    /// # Arguments
+    /// * `name` (`&str`) - The cipher string entry or cipher suite name.
+    /// * `separator` (`char`) - The separator of the components of `name`.
+    fn is_weak_cipher(&self, name: &str, separator: char) -> bool {
+        name.split(separator).any(|component: &str| {
+            self.weak_ciphers.iter().any(|weak: &String| weak == component)
+        })
+    }
+
+    /// Returns the first entry of the OpenSSL cipher string `ciphers`
+    /// enabling a weak cipher. Entries excluding (`!RC4`, `-RC4`) or
+    /// reordering (`+RC4`) ciphers, and selectors such as `@STRENGTH`, do
+    /// not enable anything.
+    ///
+    /// # Arguments
+    /// * `ciphers` (`&str`) - The cipher string, e.g. `"HIGH:!aNULL:!MD5"`.
+    fn weak_cipher_entry(&self, ciphers: &str) -> Option<String> {
+        ciphers
+            .split([':', ',', ' '])
+            .filter(|entry: &&str| {
+                !entry.is_empty() && !entry.starts_with(['!', '-', '+', '@'])
+            })
+            .find(|entry: &&str| self.is_weak_cipher(entry, '-'))
+            .map(str::to_owned)
+    }
+
+    /// Returns the weak cipher or renegotiation setting of a call to `method`
+    /// with `arguments` referencing `paths`: a weak entry of the cipher
+    /// string passed to one of the cipher list methods, a weak cipher suite
+    /// constant, or a legacy renegotiation option passed to `set_options`.
+    ///
+    /// # Arguments
+    /// * `method` (`Symbol`) - The name of the called method or function.
+    /// * `arguments` (`&[Expr<'_>]`) - The arguments of the call.
+    /// * `paths` (`&[String]`) - The constants referenced by the arguments.
+    fn weak_config(
+        &self,
+        method: Symbol,
+        arguments: &[Expr<'_>],
+        paths: &[String],
+    ) -> Option<WeakConfig> {
+        if self
+            .cipher_list_methods
+            .iter()
+            .any(|name: &String| name == method.as_str())
+            && let Some(entry) = arguments.iter().find_map(
+                |argument: &Expr<'_>| match &argument.kind {
+                    ExprKind::Lit(literal) => match literal.node {
+                        LitKind::Str(ciphers, _) => {
+                            self.weak_cipher_entry(ciphers.as_str())
+                        },
+                        _ => None,
+                    },
+                    _ => None,
+                },
+            )
+        {
+            return Some(WeakConfig::CipherString(entry));
+        }
+
+        if method.as_str() == "set_options"
+            && let Some(option) = paths.iter().find(|path: &&String| {
+                self.renegotiation_options.contains(path)
+            })
+        {
+            return Some(WeakConfig::Renegotiation(option.clone()));
+        }
+
+        paths
+            .iter()
+            .find(|path: &&String| {
+                path.rsplit_once("::").is_some_and(
+                    |(parent, name): (&str, &str)| {
+                        self.cipher_suite_types
+                            .iter()
+                            .any(|ty: &String| ty == parent)
+                            && self.is_weak_cipher(name, '_')
+                    },
+                )
+            })
+            .map(|path: &String| WeakConfig::CipherSuite(path.clone()))
+    }
+}
+
+/// Emits a `SECURITY_WEAK_TLS_VERSION` finding for the call `expression` to
+/// `method`, permitting `version`.
+///
+/// # Arguments
+/// * `context` (`&LateContext<'_>`) - The lint context used to emit the
+///   diagnostic.
+/// * `expression` (`&Expr<'_>`) - The call.
+/// * `method` (`Symbol`) - The name of the called method or function.
+/// * `version` (`&str`) - The weak version, or the option disabling it.
+fn report_version(
+    context: &LateContext<'_>,
+    expression: &Expr<'_>,
+    method: Symbol,
+    version: &str,
+) {
+    security_utils::span_lint(
+        context,
+        SECURITY_WEAK_TLS_VERSION,
+        expression.span,
+        |diagnostic: &mut Diag<'_, ()>| {
+            diagnostic.primary_message(format!(
+                "Weak TLS protocol version permitted by `{method}` detected."
+            ));
+            diagnostic.note(if method.as_str() == "clear_options" {
+                format!(
+                    "clearing `{version}` re-enables SSL, TLS 1.0 or TLS 1.1, \
+                     which are deprecated (RFC 8996) and open to downgrade \
+                     attacks"
+                )
+            } else {
+                format!(
+                    "`{version}` is deprecated (RFC 8996) and open to \
+                     downgrade attacks"
+                )
+            });
+            diagnostic.help("require TLS 1.2 or later");
+        },
+    );
+}
+
+/// Emits a `SECURITY_WEAK_TLS_CIPHER` finding for the call `expression` to
+/// `method`, setting `config`.
+///
+/// # Arguments
+/// * `context` (`&LateContext<'_>`) - The lint context used to emit the
+///   diagnostic.
+/// * `expression` (`&Expr<'_>`) - The call.
+/// * `method` (`Symbol`) - The name of the called method or function.
+/// * `config` (`WeakConfig`) - The weak setting.
+fn report_cipher(
+    context: &LateContext<'_>,
+    expression: &Expr<'_>,
+    method: Symbol,
+    config: WeakConfig,
+) {
+    security_utils::span_lint(
+        context,
+        SECURITY_WEAK_TLS_CIPHER,
+        expression.span,
+        |diagnostic: &mut Diag<'_, ()>| {
+            match config {
+                WeakConfig::CipherString(entry) => {
+                    diagnostic.primary_message(format!(
+                        "Weak TLS cipher suite enabled by `{method}` detected."
+                    ));
+                    diagnostic.note(format!(
+                        "`{entry}` enables NULL, anonymous, export-grade or \
+                         broken ciphers, which do not protect the connection"
+                    ));
+                    diagnostic.help(
+                        "keep the default cipher list of the library, or \
+                         exclude weak ciphers (e.g. `HIGH:!aNULL:!eNULL:!MD5`)",
+                    );
+                },
+                WeakConfig::CipherSuite(suite) => {
+                    diagnostic.primary_message(format!(
+                        "Weak TLS cipher suite enabled by `{method}` detected."
+                    ));
+                    diagnostic.note(format!(
+                        "`{suite}` is a NULL, anonymous, export-grade or \
+                         broken cipher suite, which does not protect the \
+                         connection"
+                    ));
+                    diagnostic
+                        .help("keep the default cipher suites of the library");
+                },
+                WeakConfig::Renegotiation(option) => {
+                    diagnostic.primary_message(format!(
+                        "Legacy TLS renegotiation enabled by `{method}` \
+                         detected."
+                    ));
+                    diagnostic.note(format!(
+                        "`{option}` allows renegotiation with peers lacking \
+                         RFC 5746 support, which is open to prefix injection \
+                         attacks (CVE-2009-3555)"
+                    ));
+                    diagnostic.help("do not set this option");
+                },
+            };
+        },
+    );
+}
+
+impl<'tcx> LateLintPass<'tcx> for SecurityTlsConfig {
+    /// Detect calls configuring a TLS connector, acceptor or client with a
+    /// weak protocol version, e.g. `min_protocol_version(Some(Tlsv10))`,
+    /// `with_protocol_versions(&[TLSv1_1, ...])` or
+    /// `clear_options(SslOptions::NO_TLSV1)`, and with weak ciphers or legacy
+    /// renegotiation, e.g. `set_cipher_list("ALL")` or
+    /// `set_options(SslOptions::ALLOW_UNSAFE_LEGACY_RENEGOTIATION)`.
+    ///
+    /// # Arguments
+    /// * `context` (`&LateContext<'tcx>`) - The lint context providing access
+    ///   to the compiler's internal state.
+    /// * `expression` (`&'tcx Expr<'tcx>`) - The expression being checked for
+    ///   weak TLS configurations.
+    fn check_expr(
+        &mut self,
+        context: &LateContext<'tcx>,
+        expression: &'tcx Expr<'tcx>,
+    ) {
+        if expression.span.from_expansion() {
+            return;
+        }
+
+        let Some(call): Option<Call<'_>> = call(context, expression) else {
+            return;
+        };
+        let method: Symbol = context.tcx.item_name(call.callee);
+        let arguments: &[Expr<'_>] = call.arguments;
+
+        let mut collector: PathCollector<'_, 'tcx> =
+            PathCollector { context, paths: Vec::new() };
+        for argument in arguments {
+            collector.visit_expr(argument);
+        }
+
+        if let Some(version) = self.weak_version(method, &collector.paths) {
+            report_version(context, expression, method, &version);
+        }
+        if let Some(config) =
+            self.weak_config(method, arguments, &collector.paths)
+        {
+            report_cipher(context, expression, method, config);
+        }
+    }
+}
+
+/// Registers the `SECURITY_WEAK_TLS_VERSION` and `SECURITY_WEAK_TLS_CIPHER`
+/// lints and their lint pass with the Rust compiler. The weak protocol
+/// versions, ciphers and options are read from the `[tls_config]` table of
+/// `dylint.toml` when the pass is created.
+///
+/// # Arguments
+/// * `session` (`&Session`) - The compiler session providing access to the
+///   compiler's internal state.
+/// * `lint_store` (`&mut LintStore`) - The lint store where the lints and
+///   their lint pass will be registered.
+#[unsafe(no_mangle)]
+pub fn register_lints(session: &Session, lint_store: &mut LintStore) {
+    dylint_linting::init_config(session);
+
+    security_utils::tool::register_lints(
+        lint_store,
+        &[SECURITY_WEAK_TLS_VERSION, SECURITY_WEAK_TLS_CIPHER],
+    );
+    lint_store.register_late_pass(|tcx: TyCtxt<'_>| {
+        Box::new(SecurityTlsConfig::new(tcx))
+    });
+}
+
+dylint_linting::dylint_library!();
+
+/// UI tests for the `SECURITY_WEAK_TLS_VERSION` and `SECURITY_WEAK_TLS_CIPHER`
+/// lints. The `ui` directory uses stand-ins for `native_tls`, `openssl` and
+/// `rustls`.
+#[cfg(test)]
+mod tests {
+    use dylint_testing::ui::Test;
+
+    #[test]
+    fn ui() {
+        Test::src_base(env!("CARGO_PKG_NAME"), "ui")
+            .rustc_flags(["--edition=2024", "-Z", "ui-testing"])
+            .run();
+    }
+}
@@ -0,0 +1,31 @@
+//! Minimal stand-in for the `native-tls` crate.
+
+pub enum Protocol {
+    Sslv3,
+    Tlsv10,
+    Tlsv11,
+    Tlsv12,
+}
+
+pub struct TlsConnector;
+
+pub struct TlsConnectorBuilder;
+
+impl TlsConnector {
+    pub fn builder() -> TlsConnectorBuilder {
+        TlsConnectorBuilder
+    }
+}
+
+impl TlsConnectorBuilder {
+    pub fn min_protocol_version(
+        &mut self,
+        _protocol: Option<Protocol>,
+    ) -> &mut Self {
+        self
+    }
+
+    pub fn build(&self) -> Result<TlsConnector, ()> {
+        Ok(TlsConnector)
+    }
+}
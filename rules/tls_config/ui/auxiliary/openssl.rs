@@ -0,0 +1,43 @@
+//! Minimal stand-in for the `openssl` crate.
+
+pub mod ssl {
+    pub struct SslVersion(i32);
+
+    impl SslVersion {
+        pub const SSL3: SslVersion = SslVersion(0x300);
+        pub const TLS1: SslVersion = SslVersion(0x301);
+        pub const TLS1_1: SslVersion = SslVersion(0x302);
+        pub const TLS1_2: SslVersion = SslVersion(0x303);
+    }
+
+    pub struct SslOptions(u64);
+
+    impl SslOptions {
+        pub const NO_TLSV1: SslOptions = SslOptions(1);
+        pub const NO_COMPRESSION: SslOptions = SslOptions(2);
+        pub const ALLOW_UNSAFE_LEGACY_RENEGOTIATION: SslOptions = SslOptions(4);
+    }
+
+    pub struct SslContextBuilder;
+
+    impl SslContextBuilder {
+        pub fn set_min_proto_version(
+            &mut self,
+            _version: Option<SslVersion>,
+        ) -> Result<(), ()> {
+            Ok(())
+        }
+
+        pub fn set_options(&mut self, _options: SslOptions) -> SslOptions {
+            SslOptions(0)
+        }
+
+        pub fn set_cipher_list(&mut self, _ciphers: &str) -> Result<(), ()> {
+            Ok(())
+        }
+
+        pub fn clear_options(&mut self, _options: SslOptions) -> SslOptions {
+            SslOptions(0)
+        }
+    }
+}
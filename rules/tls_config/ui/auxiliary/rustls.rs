@@ -0,0 +1,25 @@
+//! Minimal stand-in for the `rustls` crate.
+
+#![allow(non_camel_case_types)]
+
+#[derive(PartialEq)]
+pub enum ProtocolVersion {
+    SSLv3,
+    TLSv1_0,
+    TLSv1_1,
+    TLSv1_2,
+    TLSv1_3,
+}
+
+pub fn accepts(_versions: &[ProtocolVersion]) -> bool {
+    true
+}
+
+pub enum CipherSuite {
+    TLS_RSA_WITH_NULL_SHA,
+    TLS13_AES_128_GCM_SHA256,
+}
+
+pub fn with_cipher_suites(_suites: &[CipherSuite]) -> bool {
+    true
+}
@@ -0,0 +1,31 @@
+// aux-build:openssl.rs
+// aux-build:rustls.rs
+#![warn(security_weak_tls_cipher)]
+
+extern crate openssl;
+extern crate rustls;
+
+use openssl::ssl::{SslContextBuilder, SslOptions};
+use rustls::CipherSuite;
+
+fn weak(builder: &mut SslContextBuilder) -> Result<(), ()> {
+    builder.set_cipher_list("ALL")?; // Should trigger.
+    builder.set_cipher_list("HIGH:!aNULL:EXP-RC4-MD5")?; // Should trigger.
+    builder.set_options(SslOptions::ALLOW_UNSAFE_LEGACY_RENEGOTIATION); // Should trigger.
+    let _: bool = rustls::with_cipher_suites(&[CipherSuite::TLS_RSA_WITH_NULL_SHA]); // Should trigger.
+    Ok(())
+}
+
+fn strong(builder: &mut SslContextBuilder) -> Result<(), ()> {
+    builder.set_cipher_list("HIGH:!aNULL:!eNULL:!MD5:-RC4:@STRENGTH")?; // Should not trigger.
+    builder.set_cipher_list("ECDHE-RSA-AES128-GCM-SHA256")?; // Should not trigger.
+    builder.set_options(SslOptions::NO_COMPRESSION); // Should not trigger.
+    let _: bool = rustls::with_cipher_suites(&[CipherSuite::TLS13_AES_128_GCM_SHA256]); // Should not trigger.
+    Ok(())
+}
+
+fn main() {
+    let mut builder: SslContextBuilder = SslContextBuilder;
+    let _ = weak(&mut builder);
+    let _ = strong(&mut builder);
+}
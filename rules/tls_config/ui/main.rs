@@ -0,0 +1,48 @@
+// aux-build:native_tls.rs
+// aux-build:openssl.rs
+// aux-build:rustls.rs
+#![warn(security_weak_tls_version)]
+
+extern crate native_tls;
+extern crate openssl;
+extern crate rustls;
+
+use native_tls::{Protocol, TlsConnector, TlsConnectorBuilder};
+use openssl::ssl::{SslContextBuilder, SslOptions, SslVersion};
+use rustls::ProtocolVersion;
+
+fn weak(builder: &mut SslContextBuilder) -> Result<TlsConnector, ()> {
+    builder.set_min_proto_version(Some(SslVersion::TLS1))?; // Should trigger.
+    builder.clear_options(SslOptions::NO_TLSV1); // Should trigger.
+    let _: bool = rustls::accepts(&[
+        ProtocolVersion::TLSv1_1,
+        ProtocolVersion::TLSv1_2,
+    ]); // Should trigger.
+
+    let mut connector: TlsConnectorBuilder = TlsConnector::builder();
+    connector
+        .min_protocol_version(Some(Protocol::Tlsv10)) // Should trigger.
+        .build()
+}
+
+fn strong(builder: &mut SslContextBuilder) -> Result<TlsConnector, ()> {
+    builder.set_min_proto_version(Some(SslVersion::TLS1_2))?; // Should not trigger.
+    builder.set_options(SslOptions::NO_TLSV1); // Should not trigger.
+    builder.clear_options(SslOptions::NO_COMPRESSION); // Should not trigger.
+    let _: bool = rustls::accepts(&[ProtocolVersion::TLSv1_3]); // Should not trigger.
+
+    let version: ProtocolVersion = ProtocolVersion::TLSv1_0;
+    if version == ProtocolVersion::TLSv1_0 {
+        // Should not trigger.
+        return Err(());
+    }
+
+    let mut connector: TlsConnectorBuilder = TlsConnector::builder();
+    connector.min_protocol_version(Some(Protocol::Tlsv12)).build() // Should not trigger.
+}
+
+fn main() {
+    let mut builder: SslContextBuilder = SslContextBuilder;
+    let _ = weak(&mut builder);
+    let _ = strong(&mut builder);
+}
@@ -0,0 +1,426 @@
+//! Alias tracking of the raw pointers and references derived from the locals
+//! of a function.
+//!
+//! The compiler checks the references of a function against each other, but
+//! not against the raw pointers to the same place: a reference may be
+//! created while a raw pointer to its place exists, and both may then be
+//! used. Accessing the place through the pointer while a `&mut T` to it is
+//! live, or writing to it while a `&T` to it is live, is undefined behavior,
+//! whether or not the accesses are ever observed to conflict. [`aliases`]
+//! records, in evaluation order, the raw pointers derived from a local, the
+//! references bound to a place of the same local, and their uses:
+//!
+//! ```rust
+//! let mut count: u32 = 0;
+//! let pointer: *mut u32 = &raw mut count;
+//! let shared: &u32 = &count;
+//! unsafe { *pointer = 1 }; // `shared` is live: it is used below.
+//! println!("{shared}");
+//! ```
+
+use std::collections::{HashMap, HashSet};
+
+use rustc_hir::{
+    Body,
+    BorrowKind,
+    Expr,
+    ExprKind,
+    HirId,
+    LetStmt,
+    Mutability,
+    PatKind,
+    QPath,
+    UnOp,
+    def::Res,
+    def_id::LocalDefId,
+    intravisit::{Visitor, walk_expr, walk_local},
+};
+use rustc_middle::ty::{Ty, TyCtxt, TypeckResults};
+use rustc_span::{Span, Symbol};
+
+use crate::escape::place_local;
+
+/// Methods of raw pointers reading their pointee.
+const READING_METHODS: [&str; 6] = [
+    "read",
+    "read_unaligned",
+    "read_volatile",
+    "as_ref",
+    "copy_to",
+    "copy_to_nonoverlapping",
+];
+
+/// Methods of raw pointers writing to their pointee.
+const WRITING_METHODS: [&str; 10] = [
+    "write",
+    "write_unaligned",
+    "write_volatile",
+    "write_bytes",
+    "as_mut",
+    "copy_from",
+    "copy_from_nonoverlapping",
+    "replace",
+    "swap",
+    "drop_in_place",
+];
+
+/// Methods of owned values returning a raw pointer to their storage or
+/// buffer.
+const POINTER_METHODS: [&str; 2] = ["as_ptr", "as_mut_ptr"];
+
+/// A reference bound to a place of a local.
+#[derive(Debug, Clone, Copy)]
+struct Reference {
+    /// The local the reference borrows.
+    root: HirId,
+    /// Whether the reference is a `&mut T`.
+    mutable: bool,
+    /// The position of the borrow in evaluation order.
+    step: usize,
+    /// The borrow expression.
+    span: Span,
+}
+
+/// An access to a local through a raw pointer.
+#[derive(Debug, Clone)]
+struct Access {
+    /// The local the pointer points to.
+    root: HirId,
+    /// Whether the access writes to the pointee.
+    write: bool,
+    /// The position of the access in evaluation order.
+    step: usize,
+    /// The accessing expression.
+    span: Span,
+    /// The source of the pointer, e.g. `pointer` or `pointer.add(1)`.
+    pointer: String,
+}
+
+/// A raw pointer accessing a local while a conflicting reference to that
+/// local is live.
+#[derive(Debug, Clone)]
+pub struct Alias {
+    /// The source of the pointer.
+    pub pointer: String,
+    /// The local both point to.
+    pub root: Symbol,
+    /// The binding of the reference.
+    pub reference: Symbol,
+    /// Whether the reference is a `&mut T`.
+    pub mutable: bool,
+    /// The borrow creating the reference.
+    pub borrow: Span,
+    /// The access through the pointer.
+    pub access: Span,
+    /// The use of the reference after the access, proving it live.
+    pub span: Span,
+}
+
+/// Visitor recording, in evaluation order, the raw pointers and references
+/// to the locals of a body and their uses.
+struct AliasVisitor<'tcx> {
+    tcx: TyCtxt<'tcx>,
+    typeck: &'tcx TypeckResults<'tcx>,
+    /// The position of the next event in evaluation order.
+    step: usize,
+    /// The locals holding a raw pointer to a local, with that local.
+    pointers: HashMap<HirId, HirId>,
+    /// The locals holding a reference to a place of a local.
+    references: HashMap<HirId, Reference>,
+    accesses: Vec<Access>,
+    /// The references and accesses already reported together.
+    reported: HashSet<(HirId, Span)>,
+    aliases: Vec<Alias>,
+}
+
+impl<'tcx> AliasVisitor<'tcx> {
+    /// Returns the position of a new event in evaluation order.
+    fn next_step(&mut self) -> usize {
+        self.step += 1;
+        self.step
+    }
+
+    /// Returns the local the raw pointer `expression` points to, if it is
+    /// derived from a local of the function.
+    ///
+    /// # Arguments
+    /// * `expression` (`&Expr<'_>`) - The expression to inspect.
+    fn pointer_root(&self, expression: &Expr<'_>) -> Option<HirId> {
+        match &expression.kind {
+            ExprKind::AddrOf(BorrowKind::Raw, _, place) => {
+                place_local(self.tcx, self.typeck, place)
+                    .map(|(id, _): (HirId, Symbol)| id)
+            },
+            ExprKind::AddrOf(BorrowKind::Ref, _, place)
+                if self.typeck.expr_ty_adjusted(expression).is_raw_ptr() =>
+            {
+                place_local(self.tcx, self.typeck, place)
+                    .map(|(id, _): (HirId, Symbol)| id)
+            },
+            ExprKind::Cast(inner, _) | ExprKind::DropTemps(inner) => {
+                match &inner.kind {
+                    ExprKind::AddrOf(BorrowKind::Ref, _, place) => {
+                        place_local(self.tcx, self.typeck, place)
+                            .map(|(id, _): (HirId, Symbol)| id)
+                    },
+                    _ => self.pointer_root(inner),
+                }
+            },
+            ExprKind::MethodCall(segment, receiver, _, _) => {
+                if self.typeck.expr_ty(receiver).is_raw_ptr() {
+                    return self
+                        .typeck
+                        .expr_ty(expression)
+                        .is_raw_ptr()
+                        .then(|| self.pointer_root(receiver))
+                        .flatten();
+                }
+                POINTER_METHODS
+                    .contains(&segment.ident.as_str())
+                    .then(|| place_local(self.tcx, self.typeck, receiver))
+                    .flatten()
+                    .map(|(id, _): (HirId, Symbol)| id)
+            },
+            ExprKind::Path(QPath::Resolved(None, path)) => match path.res {
+                Res::Local(id) => self.pointers.get(&id).copied(),
+                _ => None,
+            },
+            _ => None,
+        }
+    }
+
+    /// Returns the reference `expression` creates to a place of a local, if
+    /// any.
+    ///
+    /// # Arguments
+    /// * `expression` (`&Expr<'_>`) - The bound value.
+    fn reference(&self, expression: &Expr<'_>) -> Option<(HirId, bool)> {
+        match &expression.kind {
+            ExprKind::AddrOf(BorrowKind::Ref, mutability, place)
+                if self.typeck.expr_ty_adjusted(expression).is_ref() =>
+            {
+                place_local(self.tcx, self.typeck, place).map(
+                    |(id, _): (HirId, Symbol)| {
+                        (id, *mutability == Mutability::Mut)
+                    },
+                )
+            },
+            _ => None,
+        }
+    }
+
+    /// Records the access through the raw pointer `pointer` at `span`, if
+    /// it is derived from a local.
+    ///
+    /// # Arguments
+    /// * `pointer` (`&Expr<'_>`) - The accessed pointer.
+    /// * `write` (`bool`) - Whether the access writes to the pointee.
+    /// * `span` (`Span`) - The accessing expression.
+    fn record_access(&mut self, pointer: &Expr<'_>, write: bool, span: Span) {
+        let Some(root): Option<HirId> = self.pointer_root(pointer) else {
+            return;
+        };
+        let step: usize = self.next_step();
+        let source: String = self
+            .tcx
+            .sess
+            .source_map()
+            .span_to_snippet(pointer.span)
+            .unwrap_or_default();
+        self.accesses.push(Access {
+            root,
+            write,
+            step,
+            span,
+            pointer: source,
+        });
+    }
+
+    /// Binds the local `id` to `value`, following the raw pointers and the
+    /// references to the locals of the function.
+    ///
+    /// # Arguments
+    /// * `id` (`HirId`) - The bound local.
+    /// * `value` (`&Expr<'_>`) - The bound value.
+    fn bind(&mut self, id: HirId, value: &Expr<'_>) {
+        self.references.remove(&id);
+        self.pointers.remove(&id);
+        if let Some(root) = self.pointer_root(value) {
+            self.pointers.insert(id, root);
+        } else if let Some((root, mutable)) = self.reference(value) {
+            let step: usize = self.next_step();
+            self.references.insert(
+                id,
+                Reference { root, mutable, step, span: value.span },
+            );
+        }
+    }
+
+    /// Visits the place `place` written to, recording the writes through
+    /// raw pointers.
+    ///
+    /// # Arguments
+    /// * `place` (`&'tcx Expr<'tcx>`) - The written place.
+    fn visit_place(&mut self, place: &'tcx Expr<'tcx>) {
+        match &place.kind {
+            ExprKind::Field(base, _) => self.visit_place(base),
+            ExprKind::Index(base, index, _) => {
+                self.visit_expr(index);
+                self.visit_place(base);
+            },
+            ExprKind::Unary(UnOp::Deref, pointer)
+                if self.typeck.expr_ty(pointer).is_raw_ptr() =>
+            {
+                self.visit_expr(pointer);
+                self.record_access(pointer, true, place.span);
+            },
+            _ => self.visit_expr(place),
+        }
+    }
+
+    /// Records the aliases of the reference `id`, used at `span`, with the
+    /// accesses through raw pointers since its borrow.
+    ///
+    /// # Arguments
+    /// * `id` (`HirId`) - The used reference.
+    /// * `span` (`Span`) - The use of the reference.
+    fn use_reference(&mut self, id: HirId, span: Span) {
+        let Some(reference): Option<Reference> =
+            self.references.get(&id).copied()
+        else {
+            return;
+        };
+        for access in &self.accesses {
+            if access.root != reference.root
+                || access.step < reference.step
+                || !(reference.mutable || access.write)
+                || !self.reported.insert((id, access.span))
+            {
+                continue;
+            }
+            self.aliases.push(Alias {
+                pointer: access.pointer.clone(),
+                root: self.tcx.hir_name(reference.root),
+                reference: self.tcx.hir_name(id),
+                mutable: reference.mutable,
+                borrow: reference.span,
+                access: access.span,
+                span,
+            });
+        }
+    }
+}
+
+impl<'tcx> Visitor<'tcx> for AliasVisitor<'tcx> {
+    fn visit_local(&mut self, local: &'tcx LetStmt<'tcx>) {
+        walk_local(self, local);
+
+        if let Some(init) = local.init
+            && let PatKind::Binding(_, id, _, _) = local.pat.kind
+        {
+            self.bind(id, init);
+        }
+    }
+
+    fn visit_expr(&mut self, expression: &'tcx Expr<'tcx>) {
+        match &expression.kind {
+            ExprKind::Closure(_) => {},
+            ExprKind::Assign(place, value, _) => {
+                self.visit_expr(value);
+                if let ExprKind::Path(QPath::Resolved(None, path)) =
+                    &place.kind
+                    && let Res::Local(id) = path.res
+                {
+                    self.bind(id, value);
+                }
+                self.visit_place(place);
+            },
+            ExprKind::AssignOp(_, place, value) => {
+                self.visit_expr(value);
+                self.visit_place(place);
+            },
+            ExprKind::AddrOf(BorrowKind::Ref, Mutability::Mut, place) => {
+                self.visit_place(place);
+            },
+            ExprKind::Unary(UnOp::Deref, pointer)
+                if self.typeck.expr_ty(pointer).is_raw_ptr() =>
+            {
+                self.visit_expr(pointer);
+                self.record_access(pointer, false, expression.span);
+            },
+            ExprKind::MethodCall(segment, receiver, arguments, _)
+                if self.typeck.expr_ty(receiver).is_raw_ptr() =>
+            {
+                walk_expr(self, expression);
+                let name: &str = segment.ident.as_str();
+                if READING_METHODS.contains(&name)
+                    || WRITING_METHODS.contains(&name)
+                {
+                    self.record_access(
+                        receiver,
+                        WRITING_METHODS.contains(&name),
+                        expression.span,
+                    );
+                }
+                for argument in *arguments {
+                    if self.typeck.expr_ty(argument).is_raw_ptr() {
+                        let ty: Ty<'_> = self.typeck.expr_ty(argument);
+                        self.record_access(
+                            argument,
+                            ty.is_mutable_ptr(),
+                            argument.span,
+                        );
+                    }
+                }
+            },
+            ExprKind::Call(_, arguments) => {
+                walk_expr(self, expression);
+                for argument in *arguments {
+                    let ty: Ty<'_> = self.typeck.expr_ty(argument);
+                    if ty.is_raw_ptr() {
+                        self.record_access(
+                            argument,
+                            ty.is_mutable_ptr(),
+                            argument.span,
+                        );
+                    }
+                }
+            },
+            ExprKind::Path(QPath::Resolved(None, path)) => {
+                if let Res::Local(id) = path.res {
+                    self.use_reference(id, expression.span);
+                }
+            },
+            _ => walk_expr(self, expression),
+        }
+    }
+}
+
+/// Returns the raw pointers of `body`, the body of the function `def_id`,
+/// accessing a local while a reference to that local is live: a `&mut T`
+/// used after any access through the pointer, or a `&T` used after a write
+/// through it. Loops and branches are followed in source order, and closures
+/// are not entered.
+///
+/// # Arguments
+/// * `tcx` (`TyCtxt<'tcx>`) - The type context.
+/// * `def_id` (`LocalDefId`) - The function owning `body`.
+/// * `body` (`&'tcx Body<'tcx>`) - The body to analyze.
+pub fn aliases<'tcx>(
+    tcx: TyCtxt<'tcx>,
+    def_id: LocalDefId,
+    body: &'tcx Body<'tcx>,
+) -> Vec<Alias> {
+    let mut visitor: AliasVisitor<'tcx> = AliasVisitor {
+        tcx,
+        typeck: tcx.typeck(def_id),
+        step: 0,
+        pointers: HashMap::new(),
+        references: HashMap::new(),
+        accesses: Vec::new(),
+        reported: HashSet::new(),
+        aliases: Vec::new(),
+    };
+    visitor.visit_expr(body.value);
+    visitor.aliases
+}
@@ -0,0 +1,508 @@
+//! Escape analysis of the raw pointers derived from the locals and
+//! temporaries of a function.
+//!
+//! A raw pointer carries no lifetime: the compiler lets it outlive the value
+//! it was derived from, and the dangling pointer is only detected, if ever,
+//! when it is dereferenced. [`escapes`] follows, through the locals of a
+//! body, the raw pointers derived from a local of the function (`&raw const
+//! value`, `&mut value as *mut T`, `buffer.as_ptr()`) or from a temporary
+//! (`CString::new(name)?.as_ptr()`), and reports where they leave the
+//! lifetime of their pointee:
+//!
+//! ```rust
+//! fn header() -> *const u8 {
+//!     let bytes: [u8; 4] = *b"RIFF";
+//!     bytes.as_ptr() // `bytes` is dropped when `header` returns.
+//! }
+//!
+//! fn greet() {
+//!     let name: *const c_char = CString::new("world").unwrap().as_ptr();
+//!     unsafe { puts(name) }; // The `CString` was dropped with its statement.
+//! }
+//! ```
+
+use std::collections::{HashMap, HashSet};
+
+use rustc_hir::{
+    Body,
+    BorrowKind,
+    Expr,
+    ExprKind,
+    HirId,
+    LetStmt,
+    Param,
+    PatKind,
+    QPath,
+    UnOp,
+    def::{DefKind, Res},
+    def_id::{DefId, LocalDefId},
+    intravisit::{Visitor, walk_expr, walk_local},
+};
+use rustc_middle::{
+    middle::region::{Scope, ScopeTree},
+    ty::{Ty, TyCtxt, TypeckResults},
+};
+use rustc_span::{Span, Symbol};
+
+/// Methods returning a raw pointer to the storage or the buffer of their
+/// receiver.
+const POINTER_METHODS: [&str; 2] = ["as_ptr", "as_mut_ptr"];
+
+/// Methods storing their argument in the collection they are called on.
+const STORING_METHODS: [&str; 4] =
+    ["push", "push_back", "push_front", "insert"];
+
+/// What a raw pointer points to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Pointee {
+    /// A local of the function, or a buffer it owns, named by its binding.
+    Local(HirId, Symbol),
+    /// A temporary dropped at the end of its statement, by its span.
+    Temporary(Span),
+}
+
+/// How a raw pointer leaves the lifetime of its pointee.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Kind {
+    /// The pointer is returned from the function.
+    Returned,
+    /// The pointer is stored in a place outliving the pointee, described by
+    /// its source, e.g. `self.buffer`.
+    Stored(String),
+    /// The pointer is passed to a foreign function that may keep it, by its
+    /// name.
+    Retained(Symbol),
+    /// The pointer to a temporary is used after the end of the statement
+    /// creating it.
+    UsedAfterDrop,
+}
+
+/// A raw pointer leaving the lifetime of its pointee.
+#[derive(Debug, Clone)]
+pub struct Escape {
+    /// What the pointer points to.
+    pub pointee: Pointee,
+    /// How the pointer escapes.
+    pub kind: Kind,
+    /// The expression deriving the pointer.
+    pub origin: Span,
+    /// The expression where the pointer escapes.
+    pub span: Span,
+}
+
+/// Visitor following the raw pointers derived from the locals and
+/// temporaries of a body, and recording their escapes.
+struct EscapeVisitor<'a, 'tcx> {
+    tcx: TyCtxt<'tcx>,
+    typeck: &'tcx TypeckResults<'tcx>,
+    scopes: &'tcx ScopeTree,
+    retaining_functions: &'a [String],
+    /// The bindings of the parameters of the body.
+    parameters: HashSet<HirId>,
+    /// The locals holding a raw pointer to a local or a temporary, with the
+    /// expression deriving it.
+    pointers: HashMap<HirId, (Pointee, Span)>,
+    /// The locals holding a pointer to a temporary already reported as used
+    /// after the drop of the temporary.
+    reported: HashSet<HirId>,
+    escapes: Vec<Escape>,
+}
+
+impl<'tcx> EscapeVisitor<'_, 'tcx> {
+    /// Returns the local `place` is a place of as the pointee of a pointer
+    /// derived by `origin`.
+    ///
+    /// # Arguments
+    /// * `place` (`&Expr<'_>`) - The place the pointer is derived from.
+    /// * `origin` (`Span`) - The expression deriving the pointer.
+    fn local_pointee(
+        &self,
+        place: &Expr<'_>,
+        origin: Span,
+    ) -> Option<(Pointee, Span)> {
+        place_local(self.tcx, self.typeck, place).map(
+            |(id, name): (HirId, Symbol)| (Pointee::Local(id, name), origin),
+        )
+    }
+
+    /// Returns what the raw pointer `expression` points to, with the
+    /// expression deriving it, if it is a pointer to a local or a temporary.
+    ///
+    /// # Arguments
+    /// * `expression` (`&'tcx Expr<'tcx>`) - The expression to inspect.
+    fn pointee(
+        &self,
+        expression: &'tcx Expr<'tcx>,
+    ) -> Option<(Pointee, Span)> {
+        match &expression.kind {
+            ExprKind::AddrOf(BorrowKind::Raw, _, place) => {
+                self.local_pointee(place, expression.span)
+            },
+            ExprKind::AddrOf(BorrowKind::Ref, _, place)
+                if self.typeck.expr_ty_adjusted(expression).is_raw_ptr() =>
+            {
+                self.local_pointee(place, expression.span)
+            },
+            ExprKind::Cast(inner, _) | ExprKind::DropTemps(inner) => {
+                match &inner.kind {
+                    ExprKind::AddrOf(BorrowKind::Ref, _, place) => {
+                        self.local_pointee(place, expression.span)
+                    },
+                    _ => self.pointee(inner),
+                }
+            },
+            ExprKind::MethodCall(segment, receiver, _, _) => {
+                if self.typeck.expr_ty(receiver).is_raw_ptr() {
+                    return self.pointee(receiver);
+                }
+                if !POINTER_METHODS.contains(&segment.ident.as_str()) {
+                    return None;
+                }
+                if let Some(pointer) =
+                    self.local_pointee(receiver, expression.span)
+                {
+                    return Some(pointer);
+                }
+                let ty: Ty<'_> = self.typeck.expr_ty(receiver);
+                (matches!(
+                    receiver.kind,
+                    ExprKind::Call(..) | ExprKind::MethodCall(..)
+                ) && !ty.is_ref()
+                    && !ty.is_raw_ptr())
+                .then_some((
+                    Pointee::Temporary(receiver.span),
+                    expression.span,
+                ))
+            },
+            ExprKind::Path(QPath::Resolved(None, path)) => match path.res {
+                Res::Local(id) => self.pointers.get(&id).copied(),
+                _ => None,
+            },
+            _ => None,
+        }
+    }
+
+    /// Returns the scope of the local `id`.
+    ///
+    /// # Arguments
+    /// * `id` (`HirId`) - The binding of the local.
+    fn scope(&self, id: HirId) -> Option<Scope> {
+        self.scopes.var_scope(id.local_id)
+    }
+
+    /// Returns `true` if `place` outlives the body: a static, or a field,
+    /// element or dereference of a parameter holding a reference, e.g.
+    /// `self.buffer` or `*out`.
+    ///
+    /// # Arguments
+    /// * `place` (`&Expr<'_>`) - The assigned place or the receiver.
+    fn outlives_body(&self, place: &Expr<'_>) -> bool {
+        let mut projected: bool = false;
+        let mut current: &Expr<'_> = place;
+        loop {
+            match &current.kind {
+                ExprKind::Field(base, _)
+                | ExprKind::Index(base, _, _)
+                | ExprKind::Unary(UnOp::Deref, base) => {
+                    projected = true;
+                    current = base;
+                },
+                ExprKind::Path(QPath::Resolved(None, path)) => {
+                    return match path.res {
+                        Res::Def(DefKind::Static { .. }, _) => true,
+                        Res::Local(id) => {
+                            let ty: Ty<'_> = self.typeck.node_type(id);
+                            projected
+                                && self.parameters.contains(&id)
+                                && (ty.is_ref() || ty.is_raw_ptr())
+                        },
+                        _ => false,
+                    };
+                },
+                _ => return false,
+            }
+        }
+    }
+
+    /// Records the pointers to locals or temporaries returned by the value
+    /// `expression` of the body, directly or in an aggregate.
+    ///
+    /// # Arguments
+    /// * `expression` (`&'tcx Expr<'tcx>`) - The returned value.
+    fn check_returned(&mut self, expression: &'tcx Expr<'tcx>) {
+        match &expression.kind {
+            ExprKind::Block(block, _) => {
+                if let Some(tail) = block.expr {
+                    self.check_returned(tail);
+                }
+            },
+            ExprKind::If(_, then, otherwise) => {
+                self.check_returned(then);
+                if let Some(otherwise) = otherwise {
+                    self.check_returned(otherwise);
+                }
+            },
+            ExprKind::Match(_, arms, _) => {
+                for arm in *arms {
+                    self.check_returned(arm.body);
+                }
+            },
+            ExprKind::Tup(elements) | ExprKind::Array(elements) => {
+                for element in *elements {
+                    self.check_returned(element);
+                }
+            },
+            ExprKind::Struct(_, fields, _) => {
+                for field in *fields {
+                    self.check_returned(field.expr);
+                }
+            },
+            ExprKind::Call(callee, arguments)
+                if matches!(
+                    callee.kind,
+                    ExprKind::Path(QPath::Resolved(None, _))
+                ) && self.typeck.expr_ty(expression).is_adt() =>
+            {
+                for argument in *arguments {
+                    self.check_returned(argument);
+                }
+            },
+            _ => {
+                if let Some((pointee, origin)) = self.pointee(expression) {
+                    self.escapes.push(Escape {
+                        pointee,
+                        kind: Kind::Returned,
+                        origin,
+                        span: expression.span,
+                    });
+                }
+            },
+        }
+    }
+
+    /// Records the assignment of `value` to `place` if it stores a pointer to
+    /// a local or a temporary in a place outliving it, and follows the
+    /// pointer if `place` is a local.
+    ///
+    /// # Arguments
+    /// * `place` (`&'tcx Expr<'tcx>`) - The assigned place.
+    /// * `value` (`&'tcx Expr<'tcx>`) - The assigned value.
+    /// * `span` (`Span`) - The span of the assignment.
+    fn check_assignment(
+        &mut self,
+        place: &'tcx Expr<'tcx>,
+        value: &'tcx Expr<'tcx>,
+        span: Span,
+    ) {
+        let Some((pointee, origin)): Option<(Pointee, Span)> =
+            self.pointee(value)
+        else {
+            return;
+        };
+
+        if let ExprKind::Path(QPath::Resolved(None, path)) = &place.kind
+            && let Res::Local(destination) = path.res
+        {
+            self.pointers.insert(destination, (pointee, origin));
+            if let Pointee::Local(id, _) = pointee
+                && let (Some(inner), Some(outer)) =
+                    (self.scope(id), self.scope(destination))
+                && inner != outer
+                && self.scopes.is_subscope_of(inner, outer)
+            {
+                self.escapes.push(Escape {
+                    pointee,
+                    kind: Kind::Stored(
+                        self.tcx.hir_name(destination).to_string(),
+                    ),
+                    origin,
+                    span,
+                });
+            }
+            return;
+        }
+
+        if self.outlives_body(place) {
+            self.escapes.push(Escape {
+                pointee,
+                kind: Kind::Stored(self.source(place)),
+                origin,
+                span,
+            });
+        }
+    }
+
+    /// Returns the source of `expression`, or an empty string if it is not
+    /// available.
+    ///
+    /// # Arguments
+    /// * `expression` (`&Expr<'_>`) - The expression to print.
+    fn source(&self, expression: &Expr<'_>) -> String {
+        self.tcx
+            .sess
+            .source_map()
+            .span_to_snippet(expression.span)
+            .unwrap_or_default()
+    }
+
+    /// Returns `true` if `id` is a foreign function whose name contains one
+    /// of the `retaining_functions`, e.g. `register_callback`.
+    ///
+    /// # Arguments
+    /// * `id` (`DefId`) - The called function.
+    fn is_retaining(&self, id: DefId) -> bool {
+        let name: Symbol = self.tcx.item_name(id);
+        self.tcx.is_foreign_item(id)
+            && self.retaining_functions.iter().any(|pattern: &String| {
+                name.as_str().to_lowercase().contains(pattern.as_str())
+            })
+    }
+}
+
+impl<'tcx> Visitor<'tcx> for EscapeVisitor<'_, 'tcx> {
+    fn visit_local(&mut self, local: &'tcx LetStmt<'tcx>) {
+        walk_local(self, local);
+
+        if let Some(init) = local.init
+            && let PatKind::Binding(_, id, _, _) = local.pat.kind
+            && let Some(pointer) = self.pointee(init)
+        {
+            self.pointers.insert(id, pointer);
+        }
+    }
+
+    fn visit_expr(&mut self, expression: &'tcx Expr<'tcx>) {
+        match &expression.kind {
+            ExprKind::Closure(_) => return,
+            ExprKind::Ret(Some(value)) => self.check_returned(value),
+            ExprKind::Assign(place, value, _) => {
+                self.visit_expr(value);
+                self.check_assignment(place, value, expression.span);
+                if !matches!(place.kind, ExprKind::Path(_)) {
+                    self.visit_expr(place);
+                }
+                return;
+            },
+            ExprKind::MethodCall(segment, receiver, arguments, _)
+                if STORING_METHODS.contains(&segment.ident.as_str())
+                    && self.outlives_body(receiver) =>
+            {
+                for argument in *arguments {
+                    if let Some((pointee, origin)) = self.pointee(argument) {
+                        self.escapes.push(Escape {
+                            pointee,
+                            kind: Kind::Stored(self.source(receiver)),
+                            origin,
+                            span: expression.span,
+                        });
+                    }
+                }
+            },
+            ExprKind::Call(callee, arguments) => {
+                if let ExprKind::Path(path) = &callee.kind
+                    && let Some(id) =
+                        self.typeck.qpath_res(path, callee.hir_id).opt_def_id()
+                    && self.is_retaining(id)
+                {
+                    for argument in *arguments {
+                        if let Some((pointee @ Pointee::Local(..), origin)) =
+                            self.pointee(argument)
+                        {
+                            self.escapes.push(Escape {
+                                pointee,
+                                kind: Kind::Retained(self.tcx.item_name(id)),
+                                origin,
+                                span: argument.span,
+                            });
+                        }
+                    }
+                }
+            },
+            ExprKind::Path(QPath::Resolved(None, path)) => {
+                if let Res::Local(id) = path.res
+                    && let Some((pointee @ Pointee::Temporary(_), origin)) =
+                        self.pointers.get(&id).copied()
+                    && self.reported.insert(id)
+                {
+                    self.escapes.push(Escape {
+                        pointee,
+                        kind: Kind::UsedAfterDrop,
+                        origin,
+                        span: expression.span,
+                    });
+                }
+            },
+            _ => {},
+        }
+
+        walk_expr(self, expression);
+    }
+}
+
+/// Returns the local `expression` is a place of, through fields and indices
+/// but not dereferences, if that local is not a reference: the storage of the
+/// place is the storage of the local.
+///
+/// # Arguments
+/// * `tcx` (`TyCtxt<'_>`) - The type context.
+/// * `typeck` (`&TypeckResults<'_>`) - The typeck results of the body.
+/// * `expression` (`&Expr<'_>`) - The place.
+pub fn place_local(
+    tcx: TyCtxt<'_>,
+    typeck: &TypeckResults<'_>,
+    expression: &Expr<'_>,
+) -> Option<(HirId, Symbol)> {
+    match &expression.kind {
+        ExprKind::Field(base, _) | ExprKind::Index(base, _, _) => {
+            place_local(tcx, typeck, base)
+        },
+        ExprKind::Path(QPath::Resolved(None, path)) => {
+            let Res::Local(id): Res = path.res else {
+                return None;
+            };
+            let ty: Ty<'_> = typeck.node_type(id);
+            (!ty.is_ref() && !ty.is_raw_ptr() && !ty.is_box())
+                .then(|| (id, tcx.hir_name(id)))
+        },
+        _ => None,
+    }
+}
+
+/// Returns the escapes of the raw pointers derived from the locals and
+/// temporaries of `body`, the body of the function `def_id`. Closures are
+/// not entered, as their pointers may point to the captured locals of the
+/// enclosing function.
+///
+/// # Arguments
+/// * `tcx` (`TyCtxt<'tcx>`) - The type context.
+/// * `def_id` (`LocalDefId`) - The function owning `body`.
+/// * `body` (`&'tcx Body<'tcx>`) - The body to analyze.
+/// * `retaining_functions` (`&[String]`) - Name fragments of the foreign
+///   functions keeping the pointers passed to them.
+pub fn escapes<'tcx>(
+    tcx: TyCtxt<'tcx>,
+    def_id: LocalDefId,
+    body: &'tcx Body<'tcx>,
+    retaining_functions: &[String],
+) -> Vec<Escape> {
+    let mut visitor: EscapeVisitor<'_, 'tcx> = EscapeVisitor {
+        tcx,
+        typeck: tcx.typeck(def_id),
+        scopes: tcx.region_scope_tree(def_id),
+        retaining_functions,
+        parameters: body
+            .params
+            .iter()
+            .filter_map(|param: &Param<'_>| match param.pat.kind {
+                PatKind::Binding(_, id, _, _) => Some(id),
+                _ => None,
+            })
+            .collect(),
+        pointers: HashMap::new(),
+        reported: HashSet::new(),
+        escapes: Vec::new(),
+    };
+    visitor.visit_expr(body.value);
+    visitor.check_returned(body.value);
+    visitor.escapes
+}
@@ -5,21 +5,51 @@ extern crate rustc_hir;
 extern crate rustc_lint;
 extern crate rustc_middle;
 extern crate rustc_session;
+extern crate rustc_span;
+
+mod alias;
+mod escape;
 
 use rustc_errors::Diag;
 use rustc_hir::{
     BlockCheckMode,
+    Body,
+    CRATE_HIR_ID,
+    Constness,
     Expr,
     ExprKind,
+    FnDecl,
+    FnHeader,
     HeaderSafety,
     Item,
     ItemKind,
     Safety,
+    UnOp,
     UnsafeSource,
+    def_id::{CRATE_DEF_ID, DefId, LocalDefId},
+    intravisit::{FnKind, Visitor, walk_expr},
+};
+use rustc_lint::{
+    LateContext,
+    LateLintPass,
+    Level,
+    LintId,
+    LintStore,
+    unerased_lint_store,
+};
+use rustc_middle::{
+    lint::{LevelAndSource, LintLevelSource},
+    ty::{self, Ty, TyCtxt, TypeckResults},
+};
+use rustc_session::{Session, declare_lint, impl_lint_pass};
+use rustc_span::{Span, sym};
+use security_utils::dedup::Reported;
+use serde::Deserialize;
+
+use crate::{
+    alias::Alias,
+    escape::{Escape, Kind, Pointee},
 };
-use rustc_lint::{LateContext, LateLintPass, LintContext, LintStore};
-use rustc_middle::ty::TyCtxt;
-use rustc_session::{Session, declare_lint, declare_lint_pass};
 
 declare_lint! {
     pub SECURITY_UNSAFE_USAGE,
@@ -28,9 +58,528 @@ declare_lint! {
     traits and unsafe implementations."
 }
 
-declare_lint_pass!(SecurityUnsafeUsage => [SECURITY_UNSAFE_USAGE]);
+declare_lint! {
+    pub SECURITY_STATIC_UNSAFE_CELL,
+    Warn,
+    "Detects static items containing an `UnsafeCell` or a `SyncUnsafeCell`
+    outside of well-known synchronization primitives."
+}
+
+declare_lint! {
+    pub SECURITY_CONST_FN_UNSAFE,
+    Warn,
+    "Detects unsafe blocks, raw pointer operations and `transmute` inside
+    `const fn` bodies."
+}
+
+declare_lint! {
+    pub SECURITY_DANGLING_POINTER,
+    Warn,
+    "Detects raw pointers to locals or temporaries returned, stored in
+    longer-lived places or passed to foreign functions keeping them."
+}
+
+declare_lint! {
+    pub SECURITY_POINTER_ALIASING,
+    Warn,
+    "Detects raw pointers accessing a local while a conflicting reference to
+    that local is live."
+}
+
+declare_lint! {
+    pub SECURITY_UNSAFE_FORBIDDEN,
+    Warn,
+    "Reports crates forbidding `unsafe_code`, whose unsafe block checks are
+    skipped."
+}
+
+/// Configuration of the `unsafe_usage` library, read from the
+/// `[unsafe_usage]` table of the workspace's `dylint.toml`.
+#[derive(Deserialize)]
+#[serde(default)]
+struct Config {
+    /// Paths of the synchronization primitives, or of the modules defining
+    /// them (e.g. `std::sync`), whose `UnsafeCell` is not reported when
+    /// stored in a static.
+    sync_primitives: Vec<String>,
+    /// Name fragments of the foreign functions keeping the pointers passed
+    /// to them, e.g. `register` for `register_callback`, matched case
+    /// insensitively.
+    retaining_functions: Vec<String>,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Self {
+            sync_primitives: [
+                "core::sync",
+                "std::sync",
+                "std::thread::LocalKey",
+                "once_cell::sync",
+                "parking_lot",
+                "spin",
+                "crossbeam",
+                "tokio::sync",
+            ]
+            .into_iter()
+            .map(String::from)
+            .collect(),
+            retaining_functions: [
+                "register",
+                "callback",
+                "userdata",
+                "install",
+                "attach",
+                "set_context",
+            ]
+            .into_iter()
+            .map(String::from)
+            .collect(),
+        }
+    }
+}
+
+/// Lint pass for `SECURITY_UNSAFE_USAGE` and `SECURITY_CONST_FN_UNSAFE`,
+/// reporting the unsafe blocks of each macro call site once, and for
+/// `SECURITY_STATIC_UNSAFE_CELL`, `SECURITY_DANGLING_POINTER` and
+/// `SECURITY_POINTER_ALIASING`. The unsafe and raw pointer lints are skipped
+/// when the crate forbids `unsafe_code`.
+struct SecurityUnsafeUsage {
+    reported: Reported,
+    sync_primitives: Vec<String>,
+    retaining_functions: Vec<String>,
+    unsafe_forbidden: bool,
+}
+
+impl_lint_pass!(SecurityUnsafeUsage => [
+    SECURITY_UNSAFE_USAGE,
+    SECURITY_STATIC_UNSAFE_CELL,
+    SECURITY_CONST_FN_UNSAFE,
+    SECURITY_DANGLING_POINTER,
+    SECURITY_POINTER_ALIASING,
+    SECURITY_UNSAFE_FORBIDDEN,
+]);
+
+/// Visitor walking the body of a `const fn` and reporting its unsafe
+/// blocks, raw pointer operations and calls to `transmute`.
+struct ConstFnVisitor<'a, 'tcx> {
+    context: &'a LateContext<'tcx>,
+    typeck_results: &'tcx TypeckResults<'tcx>,
+    reported: &'a mut Reported,
+    fn_span: Span,
+}
+
+impl ConstFnVisitor<'_, '_> {
+    /// Emits a `SECURITY_CONST_FN_UNSAFE` finding at `span`, unless it was
+    /// already emitted for its macro call site.
+    ///
+    /// # Arguments
+    /// * `span` (`Span`) - The span of the unsafe operation.
+    /// * `what` (`&str`) - A description of the operation.
+    fn report(&mut self, span: Span, what: &str) {
+        let fn_span: Span = self.fn_span;
+        self.reported.span_lint(
+            self.context,
+            SECURITY_CONST_FN_UNSAFE,
+            span,
+            format!("{what} inside a `const fn` detected."),
+            |diagnostic: &mut Diag<'_, ()>| {
+                diagnostic.span_note(
+                    fn_span,
+                    "undefined behavior during const evaluation is baked \
+                     into compile-time results, and is much harder to \
+                     observe in testing than at runtime",
+                );
+                diagnostic.help(
+                    "review this operation with heightened scrutiny, or \
+                     compute the value at runtime",
+                );
+            },
+        );
+    }
+}
+
+impl<'tcx> ConstFnVisitor<'_, 'tcx> {
+    /// Returns `true` if `callee` is `core::mem::transmute`.
+    ///
+    /// # Arguments
+    /// * `callee` (`&Expr<'tcx>`) - The called expression.
+    fn is_transmute(&self, callee: &Expr<'tcx>) -> bool {
+        let ExprKind::Path(path): &ExprKind<'_> = &callee.kind else {
+            return false;
+        };
+
+        self.typeck_results
+            .qpath_res(path, callee.hir_id)
+            .opt_def_id()
+            .is_some_and(|id: DefId| {
+                self.context.tcx.is_diagnostic_item(sym::transmute, id)
+            })
+    }
+}
+
+impl<'tcx> Visitor<'tcx> for ConstFnVisitor<'_, 'tcx> {
+    fn visit_expr(&mut self, expression: &'tcx Expr<'tcx>) {
+        match &expression.kind {
+            ExprKind::Block(block, _)
+                if block.rules
+                    == BlockCheckMode::UnsafeBlock(
+                        UnsafeSource::UserProvided,
+                    ) =>
+            {
+                self.report(expression.span, "Unsafe block");
+            },
+            ExprKind::Unary(UnOp::Deref, pointer)
+                if self.typeck_results.expr_ty(pointer).is_raw_ptr() =>
+            {
+                self.report(expression.span, "Raw pointer dereference");
+            },
+            ExprKind::MethodCall(segment, receiver, _, _)
+                if self.typeck_results.expr_ty(receiver).is_raw_ptr() =>
+            {
+                self.report(
+                    expression.span,
+                    &format!("Raw pointer operation `{}`", segment.ident),
+                );
+            },
+            ExprKind::Call(callee, _) if self.is_transmute(callee) => {
+                self.report(expression.span, "Call to `transmute`");
+            },
+            _ => {},
+        }
+
+        walk_expr(self, expression);
+    }
+}
+
+impl SecurityUnsafeUsage {
+    fn new(tcx: TyCtxt<'_>) -> Self {
+        let config: Config =
+            security_utils::config::load(tcx, env!("CARGO_PKG_NAME"));
+
+        Self {
+            reported: Reported::default(),
+            sync_primitives: config.sync_primitives,
+            retaining_functions: config
+                .retaining_functions
+                .iter()
+                .map(|pattern: &String| pattern.to_lowercase())
+                .collect(),
+            unsafe_forbidden: false,
+        }
+    }
+
+    /// Returns `true` if `path` is one of the configured synchronization
+    /// primitives or is defined in one of the configured modules.
+    ///
+    /// # Arguments
+    /// * `path` (`&str`) - The path of the type, e.g. `std::sync::Mutex`.
+    fn is_sync_primitive(&self, path: &str) -> bool {
+        self.sync_primitives.iter().any(|primitive: &String| {
+            path.strip_prefix(primitive.as_str()).is_some_and(|rest: &str| {
+                rest.is_empty() || rest.starts_with("::")
+            })
+        })
+    }
+
+    /// Returns the `UnsafeCell` or `SyncUnsafeCell` type `ty` contains
+    /// through its fields, elements or references, if any. Synchronization
+    /// primitives are not entered, nor are raw pointers, function pointers
+    /// and types already in `visited`, which guards against recursive types.
+    ///
+    /// # Arguments
+    /// * `context` (`&LateContext<'tcx>`) - The lint context, used to resolve
+    ///   the types of the fields.
+    /// * `ty` (`Ty<'tcx>`) - The type to search.
+    /// * `visited` (`&mut Vec<Ty<'tcx>>`) - The types already searched.
+    fn find_cell<'tcx>(
+        &self,
+        context: &LateContext<'tcx>,
+        ty: Ty<'tcx>,
+        visited: &mut Vec<Ty<'tcx>>,
+    ) -> Option<Ty<'tcx>> {
+        if visited.contains(&ty) {
+            return None;
+        }
+        visited.push(ty);
+
+        match ty.kind() {
+            ty::Adt(adt, args) => {
+                if adt.is_unsafe_cell()
+                    || context
+                        .tcx
+                        .is_diagnostic_item(sym::SyncUnsafeCell, adt.did())
+                {
+                    return Some(ty);
+                }
+                if self.is_sync_primitive(&context.tcx.def_path_str(adt.did()))
+                {
+                    return None;
+                }
+                adt.all_fields().find_map(|field: &ty::FieldDef| {
+                    self.find_cell(
+                        context,
+                        field.ty(context.tcx, args),
+                        visited,
+                    )
+                })
+            },
+            ty::Array(element, _)
+            | ty::Slice(element)
+            | ty::Ref(_, element, _) => {
+                self.find_cell(context, *element, visited)
+            },
+            ty::Tuple(elements) => {
+                elements.iter().find_map(|element: Ty<'tcx>| {
+                    self.find_cell(context, element, visited)
+                })
+            },
+            _ => None,
+        }
+    }
+
+    /// Reports `item` if it is a static whose type contains an `UnsafeCell`
+    /// or a `SyncUnsafeCell` outside of the configured synchronization
+    /// primitives.
+    ///
+    /// # Arguments
+    /// * `context` (`&LateContext<'_>`) - The lint context, used to resolve
+    ///   the type of the static.
+    /// * `item` (`&Item<'_>`) - The item to check.
+    fn check_static(&self, context: &LateContext<'_>, item: &Item<'_>) {
+        let ItemKind::Static(_, ident, hir_ty, _): &ItemKind<'_> = &item.kind
+        else {
+            return;
+        };
+        let static_ty: Ty<'_> =
+            context.tcx.type_of(item.owner_id).instantiate_identity();
+        let Some(cell): Option<Ty<'_>> =
+            self.find_cell(context, static_ty, &mut Vec::new())
+        else {
+            return;
+        };
+
+        security_utils::span_lint(
+            context,
+            SECURITY_STATIC_UNSAFE_CELL,
+            item.span,
+            |diagnostic: &mut Diag<'_, ()>| {
+                diagnostic.primary_message(format!(
+                    "Static `{ident}` containing `{cell}` detected."
+                ));
+                diagnostic.span_label(
+                    hir_ty.span,
+                    format!("this type contains a `{cell}`"),
+                );
+                diagnostic.help(
+                    "hand-rolled synchronization through a shared cell is \
+                    prone to data races, use a `Mutex`, an atomic or a \
+                    `OnceLock` instead",
+                );
+            },
+        );
+    }
+
+    /// Emits a `SECURITY_DANGLING_POINTER` finding for `escape`.
+    ///
+    /// # Arguments
+    /// * `context` (`&LateContext<'_>`) - The lint context.
+    /// * `escape` (`&Escape`) - The escaping pointer.
+    fn report_escape(context: &LateContext<'_>, escape: &Escape) {
+        let pointee: String = match escape.pointee {
+            Pointee::Local(_, name) => format!("the local `{name}`"),
+            Pointee::Temporary(_) => String::from("a temporary"),
+        };
+        let message: String = match &escape.kind {
+            Kind::Returned => {
+                format!("Raw pointer to {pointee} returned from its function")
+            },
+            Kind::Stored(place) => format!(
+                "Raw pointer to {pointee} stored in `{place}`, which outlives \
+                 it,"
+            ),
+            Kind::Retained(function) => format!(
+                "Raw pointer to {pointee} passed to `{function}`, which may \
+                 keep it,"
+            ),
+            Kind::UsedAfterDrop => format!(
+                "Raw pointer to {pointee} used after the temporary is dropped"
+            ),
+        };
+
+        security_utils::span_lint(
+            context,
+            SECURITY_DANGLING_POINTER,
+            escape.span,
+            |diagnostic: &mut Diag<'_, ()>| {
+                diagnostic.primary_message(format!("{message} detected."));
+                match escape.pointee {
+                    Pointee::Local(..) => diagnostic.span_label(
+                        escape.origin,
+                        "the pointer is derived here, and dangles once the \
+                         local goes out of scope",
+                    ),
+                    Pointee::Temporary(temporary) => diagnostic.span_label(
+                        temporary,
+                        "this temporary is dropped at the end of the \
+                         statement",
+                    ),
+                };
+                diagnostic.help(
+                    "keep the pointee alive for as long as the pointer is \
+                     used, by binding it to a local, moving it to the heap \
+                     with `Box::into_raw` or storing it alongside the pointer",
+                );
+            },
+        );
+    }
+
+    /// Emits a `SECURITY_POINTER_ALIASING` finding for `alias`.
+    ///
+    /// # Arguments
+    /// * `context` (`&LateContext<'_>`) - The lint context.
+    /// * `alias` (`&Alias`) - The conflicting pointer and reference.
+    fn report_alias(context: &LateContext<'_>, alias: &Alias) {
+        security_utils::span_lint(
+            context,
+            SECURITY_POINTER_ALIASING,
+            alias.access,
+            |diagnostic: &mut Diag<'_, ()>| {
+                diagnostic.primary_message(format!(
+                    "Raw pointer `{}` accessing `{}` while the reference `{}` \
+                     is live detected.",
+                    alias.pointer, alias.root, alias.reference
+                ));
+                diagnostic.span_label(
+                    alias.borrow,
+                    format!(
+                        "`{}` borrows `{}` here",
+                        alias.reference, alias.root
+                    ),
+                );
+                diagnostic.span_label(
+                    alias.span,
+                    format!("`{}` is used again here", alias.reference),
+                );
+                diagnostic.note(if alias.mutable {
+                    "accessing a place through a raw pointer while a mutable \
+                     reference to it is live is undefined behavior"
+                } else {
+                    "writing to a place through a raw pointer while a shared \
+                     reference to it is live is undefined behavior"
+                });
+                diagnostic.help(
+                    "derive the pointer from the reference, or end the uses \
+                     of the reference before accessing the pointer",
+                );
+            },
+        );
+    }
+}
 
 impl<'tcx> LateLintPass<'tcx> for SecurityUnsafeUsage {
+    /// Records whether the crate forbids `unsafe_code`, with
+    /// `#![forbid(unsafe_code)]` or `-F unsafe_code`. The compiler then
+    /// rejects every construct `SECURITY_UNSAFE_USAGE` and
+    /// `SECURITY_CONST_FN_UNSAFE` report, so their checks are skipped and a
+    /// single `SECURITY_UNSAFE_FORBIDDEN` confirmation is emitted instead.
+    /// Safe code can still create raw pointers and hand them to downstream
+    /// crates, so the dangling pointer and aliasing checks keep running.
+    ///
+    /// # Arguments
+    /// * `context` (`&LateContext<'tcx>`) - The lint context.
+    fn check_crate(&mut self, context: &LateContext<'tcx>) {
+        let Some([unsafe_code]): Option<&[LintId]> =
+            unerased_lint_store(context.tcx.sess).find_lints("unsafe_code")
+        else {
+            return;
+        };
+        let level: LevelAndSource =
+            context.tcx.lint_level_at_node(unsafe_code.lint, CRATE_HIR_ID);
+        self.unsafe_forbidden = level.level == Level::Forbid;
+        if !self.unsafe_forbidden {
+            return;
+        }
+
+        let span: Span = match level.src {
+            LintLevelSource::Node { span, .. } => span,
+            _ => context.tcx.def_span(CRATE_DEF_ID),
+        };
+        security_utils::span_lint(
+            context,
+            SECURITY_UNSAFE_FORBIDDEN,
+            span,
+            |diagnostic: &mut Diag<'_, ()>| {
+                diagnostic.primary_message(
+                    "Crate forbidding `unsafe_code` detected.",
+                );
+                diagnostic.note(
+                    "the compiler rejects unsafe blocks, functions, traits \
+                     and impls in this crate, so the unsafe block checks are \
+                     skipped; raw pointers are still checked",
+                );
+            },
+        );
+    }
+
+    /// Detect raw pointers to locals or temporaries escaping their
+    /// lifetime or aliasing a live reference, and unsafe blocks, raw pointer
+    /// dereferences and method calls, and calls to `transmute` inside
+    /// `const fn` bodies. Undefined behavior during const evaluation
+    /// silently poisons the values computed at compile time, so these
+    /// operations deserve a closer review than their runtime counterparts.
+    /// The `const fn` checks are skipped in crates forbidding `unsafe_code`.
+    ///
+    /// # Arguments
+    /// * `context` (`&LateContext<'tcx>`) - The lint context providing access
+    ///   to the compiler's internal state.
+    /// * `kind` (`FnKind<'tcx>`) - The kind of the function.
+    /// * `_` (`&'tcx FnDecl<'tcx>`) - The declaration of the function.
+    /// * `body` (`&'tcx Body<'tcx>`) - The body of the function.
+    /// * `span` (`Span`) - The span of the function.
+    /// * `def_id` (`LocalDefId`) - The function being checked.
+    fn check_fn(
+        &mut self,
+        context: &LateContext<'tcx>,
+        kind: FnKind<'tcx>,
+        _: &'tcx FnDecl<'tcx>,
+        body: &'tcx Body<'tcx>,
+        span: Span,
+        def_id: LocalDefId,
+    ) {
+        let header: FnHeader = match kind {
+            FnKind::ItemFn(_, _, header) => header,
+            FnKind::Method(_, sig) => sig.header,
+            FnKind::Closure => return,
+        };
+        if span.from_expansion() {
+            return;
+        }
+
+        for escape in escape::escapes(
+            context.tcx,
+            def_id,
+            body,
+            &self.retaining_functions,
+        ) {
+            Self::report_escape(context, &escape);
+        }
+        for alias in alias::aliases(context.tcx, def_id, body) {
+            Self::report_alias(context, &alias);
+        }
+        if self.unsafe_forbidden || header.constness != Constness::Const {
+            return;
+        }
+
+        let mut visitor: ConstFnVisitor<'_, 'tcx> = ConstFnVisitor {
+            context,
+            typeck_results: context.tcx.typeck(def_id),
+            reported: &mut self.reported,
+            fn_span: context.tcx.def_span(def_id),
+        };
+        visitor.visit_expr(body.value);
+    }
+
     /// Detect unsafe blocks with user-provided unsafe source.
     ///
     /// # Arguments
@@ -43,23 +592,27 @@ impl<'tcx> LateLintPass<'tcx> for SecurityUnsafeUsage {
         context: &LateContext<'tcx>,
         expression: &'tcx Expr<'tcx>,
     ) {
+        if self.unsafe_forbidden {
+            return;
+        }
+
         // Only check block expressions.
         if let ExprKind::Block(block, _) = &expression.kind
             && let BlockCheckMode::UnsafeBlock(UnsafeSource::UserProvided) =
                 block.rules
         {
-            context.span_lint(
+            self.reported.span_lint(
+                context,
                 SECURITY_UNSAFE_USAGE,
                 expression.span,
-                |diagnostic: &mut Diag<'_, ()>| {
-                    diagnostic
-                        .primary_message("Usage of unsafe block detected.");
-                },
+                String::from("Usage of unsafe block detected."),
+                |_: &mut Diag<'_, ()>| {},
             );
         }
     }
 
-    /// Detect unsafe function, trait and implementation definitions.
+    /// Detect unsafe function, trait and implementation definitions, and
+    /// statics containing an `UnsafeCell`.
     ///
     /// # Arguments
     /// * `context` (`&LateContext<'tcx>`) - The lint context providing access
@@ -71,6 +624,11 @@ impl<'tcx> LateLintPass<'tcx> for SecurityUnsafeUsage {
         context: &LateContext<'tcx>,
         item: &'tcx Item<'tcx>,
     ) {
+        self.check_static(context, item);
+        if self.unsafe_forbidden {
+            return;
+        }
+
         match &item.kind {
             // Unsafe function.
             ItemKind::Fn { sig, .. } => {
@@ -78,7 +636,8 @@ impl<'tcx> LateLintPass<'tcx> for SecurityUnsafeUsage {
                     sig.header.safety,
                     HeaderSafety::Normal(Safety::Unsafe)
                 ) {
-                    context.span_lint(
+                    security_utils::span_lint(
+                        context,
                         SECURITY_UNSAFE_USAGE,
                         item.span,
                         |diagnostic: &mut Diag<'_, ()>| {
@@ -92,7 +651,8 @@ impl<'tcx> LateLintPass<'tcx> for SecurityUnsafeUsage {
             // Unsafe trait.
             ItemKind::Trait(_, _, safety, _, _, _, _) => {
                 if *safety == Safety::Unsafe {
-                    context.span_lint(
+                    security_utils::span_lint(
+                        context,
                         SECURITY_UNSAFE_USAGE,
                         item.span,
                         |diagnostic: &mut Diag<'_, ()>| {
@@ -108,7 +668,8 @@ impl<'tcx> LateLintPass<'tcx> for SecurityUnsafeUsage {
                 if let Some(trait_impl) = impl_.of_trait
                     && trait_impl.safety == Safety::Unsafe
                 {
-                    context.span_lint(
+                    security_utils::span_lint(
+                        context,
                         SECURITY_UNSAFE_USAGE,
                         item.span,
                         |diagnostic: &mut Diag<'_, ()>| {
@@ -124,34 +685,52 @@ impl<'tcx> LateLintPass<'tcx> for SecurityUnsafeUsage {
     }
 }
 
-/// Registers the `SECURITY_UNSAFE_USAGE` lint and its corresponding lint pass
-/// with the Rust compiler.
+/// Registers the `SECURITY_UNSAFE_USAGE`, `SECURITY_STATIC_UNSAFE_CELL`,
+/// `SECURITY_CONST_FN_UNSAFE`, `SECURITY_DANGLING_POINTER`,
+/// `SECURITY_POINTER_ALIASING` and `SECURITY_UNSAFE_FORBIDDEN` lints and
+/// their corresponding lint pass with the Rust compiler.
 ///
 /// # Arguments
 /// * `session` (`&Session`) - The compiler session providing access to the
 ///   compiler's internal state.
-/// * `lint_store` (`&mut LintStore`) - The lint store where the
-///   `SECURITY_UNSAFE_USAGE` lint and its corresponding lint pass will be
-///   registered.
+/// * `lint_store` (`&mut LintStore`) - The lint store where the lints and
+///   their corresponding lint pass will be registered.
 #[unsafe(no_mangle)]
 pub fn register_lints(session: &Session, lint_store: &mut LintStore) {
     dylint_linting::init_config(session);
 
-    lint_store.register_lints(&[SECURITY_UNSAFE_USAGE]);
-    lint_store
-        .register_late_pass(|_: TyCtxt<'_>| Box::new(SecurityUnsafeUsage));
+    security_utils::tool::register_lints(
+        lint_store,
+        &[
+            SECURITY_UNSAFE_USAGE,
+            SECURITY_STATIC_UNSAFE_CELL,
+            SECURITY_CONST_FN_UNSAFE,
+            SECURITY_DANGLING_POINTER,
+            SECURITY_POINTER_ALIASING,
+            SECURITY_UNSAFE_FORBIDDEN,
+        ],
+    );
+    lint_store.register_late_pass(|tcx: TyCtxt<'_>| {
+        Box::new(SecurityUnsafeUsage::new(tcx))
+    });
 }
 
 dylint_linting::dylint_library!();
 
-/// UI tests for the `SECURITY_UNSAFE_USAGE` lint. These tests are located in
-/// the `ui` directory and are compiled with the appropriate compiler flags for
-/// UI testing. The tests check that the expected warnings are emitted for
-/// unsafe block usage, unsafe function definitions, unsafe trait definitions
-/// and unsafe implementation definitions, while ensuring that no warnings are
-/// emitted for safe code. The tests will pass if the expected warnings are
-/// emitted and fail if any unexpected warnings are emitted or if the expected
-/// warnings are not emitted.
+/// UI tests for the `SECURITY_UNSAFE_USAGE`, `SECURITY_STATIC_UNSAFE_CELL`,
+/// `SECURITY_CONST_FN_UNSAFE`, `SECURITY_DANGLING_POINTER`,
+/// `SECURITY_POINTER_ALIASING` and `SECURITY_UNSAFE_FORBIDDEN` lints. These
+/// tests are located in the `ui` directory and are compiled with the
+/// appropriate compiler flags for UI testing. The tests check that the
+/// expected warnings are emitted for unsafe block usage, unsafe function
+/// definitions, unsafe trait definitions, unsafe implementation definitions,
+/// statics containing an `UnsafeCell` outside of synchronization primitives,
+/// unsafe operations inside `const fn` bodies, raw pointers outliving their
+/// pointee and raw pointers aliasing live references, and for crates
+/// forbidding `unsafe_code`, while ensuring that no warnings are emitted for
+/// safe code. The tests will pass if the expected warnings are emitted and
+/// fail if any unexpected warnings are emitted or if the expected warnings
+/// are not emitted.
 #[cfg(test)]
 mod tests {
     use dylint_testing::ui::Test;
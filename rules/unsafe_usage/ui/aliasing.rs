@@ -0,0 +1,64 @@
+#![allow(security_unsafe_usage)]
+
+// Should trigger (write through the pointer while a shared reference is
+// live).
+fn shared() -> u32 {
+    let mut count: u32 = 0;
+    let pointer: *mut u32 = &raw mut count;
+    let current: &u32 = &count;
+    unsafe { *pointer = 1 };
+    *current
+}
+
+// Should trigger (read through the pointer while a mutable reference is
+// live).
+fn exclusive() -> u32 {
+    let mut values: [u32; 2] = [1, 2];
+    let pointer: *const u32 = values.as_ptr();
+    let first: &mut u32 = &mut values[0];
+    let second: u32 = unsafe { pointer.add(1).read() };
+    *first += second;
+    *first
+}
+
+// Should trigger (write through a pointer passed to a function while a
+// shared reference is live).
+fn written() -> u64 {
+    let mut total: u64 = 0;
+    let pointer: *mut u64 = &mut total as *mut u64;
+    let view: &u64 = &total;
+    unsafe { std::ptr::write(pointer, 3) };
+    *view
+}
+
+// Should not trigger (shared reference and read through the pointer).
+fn read() -> u32 {
+    let count: u32 = 5;
+    let pointer: *const u32 = &raw const count;
+    let current: &u32 = &count;
+    unsafe { *pointer + *current }
+}
+
+// Should not trigger (reference no longer used after the access).
+fn ended() -> u32 {
+    let mut count: u32 = 0;
+    let pointer: *mut u32 = &raw mut count;
+    let current: &mut u32 = &mut count;
+    *current += 1;
+    unsafe { *pointer += 1 };
+    count
+}
+
+// Should not trigger (pointer derived from the reference).
+fn derived() -> u32 {
+    let mut count: u32 = 0;
+    let current: &mut u32 = &mut count;
+    let pointer: *mut u32 = current;
+    unsafe { *pointer = 1 };
+    *current += 1;
+    count
+}
+
+fn main() {
+    let _ = (shared(), exclusive(), written(), read(), ended(), derived());
+}
@@ -0,0 +1,50 @@
+#![allow(security_unsafe_usage)]
+
+use std::mem::transmute;
+
+// Should trigger (unsafe block, `add` and dereference).
+const fn second(values: *const u32) -> u32 {
+    unsafe {
+        let next: *const u32 = values.add(1);
+        *next
+    }
+}
+
+#[repr(transparent)]
+struct Meters(u32);
+
+// Should trigger (unsafe block and `transmute`).
+const fn to_meters(value: u32) -> Meters {
+    unsafe { transmute::<u32, Meters>(value) }
+}
+
+// Should not trigger (safe const code).
+const fn checked_double(value: u32) -> Option<u32> {
+    value.checked_mul(2)
+}
+
+struct Buffer {
+    data: [u8; 4],
+}
+
+impl Buffer {
+    // Should trigger (method body).
+    const fn first(&self) -> u8 {
+        let pointer: *const u8 = self.data.as_ptr();
+        unsafe { *pointer }
+    }
+}
+
+// Should not trigger (not a `const fn`).
+fn runtime_second(values: *const u32) -> u32 {
+    unsafe { *values.add(1) }
+}
+
+const VALUES: [u32; 2] = [1, 2];
+const SECOND: u32 = second(VALUES.as_ptr());
+
+fn main() {
+    let buffer: Buffer = Buffer { data: [1, 2, 3, 4] };
+    let _ = (SECOND, to_meters(1).0, checked_double(2), buffer.first());
+    let _ = runtime_second(VALUES.as_ptr());
+}
@@ -0,0 +1,102 @@
+#![allow(security_unsafe_usage)]
+#![allow(dangling_pointers_from_locals, dangling_pointers_from_temporaries)]
+
+use std::ffi::{CString, c_char, c_void};
+
+unsafe extern "C" {
+    fn register_callback(data: *mut c_void);
+    fn puts(text: *const c_char) -> i32;
+}
+
+static HEADER: [u8; 4] = *b"RIFF";
+
+struct Parser {
+    cursor: *const u8,
+    pending: Vec<*const u8>,
+}
+
+// Should trigger (local buffer returned).
+fn header() -> *const u8 {
+    let bytes: [u8; 4] = *b"RIFF";
+    bytes.as_ptr()
+}
+
+// Should trigger (raw borrow returned through a local).
+fn counter() -> *mut u32 {
+    let mut count: u32 = 0;
+    let pointer: *mut u32 = &raw mut count;
+    return pointer;
+}
+
+// Should trigger (pointer returned in a tuple).
+fn parameter(value: u64) -> (*const u64, usize) {
+    (&value as *const u64, 8)
+}
+
+impl Parser {
+    // Should trigger (pointers stored in the receiver).
+    fn reset(&mut self) {
+        let buffer: Vec<u8> = vec![0; 16];
+        self.cursor = buffer.as_ptr();
+        self.pending.push(buffer.as_ptr());
+    }
+}
+
+// Should trigger (pointer stored through an output parameter).
+fn fill(out: &mut *const u8) {
+    let byte: u8 = 1;
+    *out = &byte;
+}
+
+// Should trigger (pointer to an inner local stored in an outer one).
+fn nested() -> u8 {
+    let mut pointer: *const u8 = std::ptr::null();
+    {
+        let byte: u8 = 2;
+        pointer = &raw const byte;
+    }
+    unsafe { *pointer }
+}
+
+// Should trigger (pointer to a local passed to a foreign registration).
+fn install() {
+    let mut state: u32 = 0;
+    unsafe { register_callback(&raw mut state as *mut c_void) };
+}
+
+// Should trigger (pointer to a dropped temporary).
+fn greet() {
+    let name: *const c_char = CString::new("world").unwrap().as_ptr();
+    unsafe { puts(name) };
+}
+
+// Should not trigger (pointer to a static, to the heap and to a borrowed
+// slice).
+fn escaped(values: &[u8]) -> (*const u8, *mut u32, *const u8) {
+    let boxed: Box<u32> = Box::new(0);
+    (HEADER.as_ptr(), Box::into_raw(boxed), values.as_ptr())
+}
+
+// Should not trigger (pointers used within the lifetime of their pointee).
+fn local() -> u8 {
+    let bytes: [u8; 2] = [1, 2];
+    let pointer: *const u8 = bytes.as_ptr();
+    let name: CString = CString::new("world").unwrap();
+    unsafe { puts(name.as_ptr()) };
+    unsafe { puts(CString::new("world").unwrap().as_ptr()) };
+    unsafe { *pointer }
+}
+
+fn main() {
+    let mut parser: Parser = Parser {
+        cursor: std::ptr::null(),
+        pending: Vec::new(),
+    };
+    let mut out: *const u8 = std::ptr::null();
+    let _ = (header(), counter(), parameter(1), nested(), local());
+    let _ = escaped(&[1]);
+    parser.reset();
+    fill(&mut out);
+    install();
+    greet();
+}
@@ -0,0 +1,20 @@
+#![forbid(unsafe_code)]
+#![warn(security_unsafe_forbidden, security_const_fn_unsafe)]
+
+// Should not trigger `security_const_fn_unsafe`: the checks are skipped, as
+// dereferencing the pointer would be rejected by the compiler.
+const fn signed(value: &u8) -> *const i8 {
+    (value as *const u8).cast::<i8>()
+}
+
+// Should trigger `security_dangling_pointer`: safe code cannot dereference
+// the pointer, but the downstream crates calling it can.
+pub fn dangling() -> *const u8 {
+    let value: u8 = 1;
+    &raw const value
+}
+
+fn main() {
+    let _ = signed(&1);
+    let _ = dangling();
+}
@@ -26,4 +26,16 @@ fn main() {
     {
         safe_function(); // Safe block: should not trigger.
     }
+
+    call_twice!(unsafe_function); // Should trigger once.
+}
+
+/// Each use of the macro contains two unsafe blocks, at two spans of the
+/// macro definition sharing the call site.
+#[macro_export]
+macro_rules! call_twice {
+    ($function:ident) => {
+        unsafe { $function() };
+        unsafe { $function() };
+    };
 }
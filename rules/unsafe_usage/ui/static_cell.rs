@@ -0,0 +1,51 @@
+#![feature(sync_unsafe_cell)]
+#![warn(security_static_unsafe_cell)]
+#![allow(security_unsafe_usage)]
+
+use std::cell::{Cell, SyncUnsafeCell, UnsafeCell};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Mutex, OnceLock, RwLock};
+
+struct Racy {
+    value: UnsafeCell<u32>,
+}
+
+unsafe impl Sync for Racy {}
+
+struct Wrapper(Racy);
+
+struct Counter {
+    hits: AtomicUsize,
+    names: Mutex<Vec<String>>,
+}
+
+static RACY: Racy = Racy { value: UnsafeCell::new(0) }; // Should trigger.
+static WRAPPED: [Wrapper; 2] = [
+    Wrapper(Racy { value: UnsafeCell::new(0) }),
+    Wrapper(Racy { value: UnsafeCell::new(1) }),
+]; // Should trigger.
+static SHARED: SyncUnsafeCell<u32> = SyncUnsafeCell::new(0); // Should trigger.
+static REFERENCED: &Racy = &RACY; // Should trigger.
+static mut CELL: Cell<u32> = Cell::new(0); // Should trigger.
+
+static HITS: AtomicUsize = AtomicUsize::new(0); // Should not trigger.
+static COUNTER: Counter = Counter {
+    hits: AtomicUsize::new(0),
+    names: Mutex::new(Vec::new()),
+}; // Should not trigger.
+static CONFIG: OnceLock<String> = OnceLock::new(); // Should not trigger.
+static TABLE: RwLock<Vec<u8>> = RwLock::new(Vec::new()); // Should not trigger.
+static LIMIT: u32 = 10; // Should not trigger.
+
+thread_local! {
+    static LOCAL: Cell<u32> = const { Cell::new(0) }; // Should not trigger.
+}
+
+fn main() {
+    HITS.fetch_add(1, Ordering::Relaxed);
+    COUNTER.hits.fetch_add(1, Ordering::Relaxed);
+    let _ = CONFIG.get();
+    drop(TABLE.read());
+    let _ = (&RACY, &WRAPPED, &SHARED, REFERENCED, &raw const CELL, LIMIT);
+    LOCAL.set(1);
+}
@@ -0,0 +1,354 @@
+#![feature(rustc_private)]
+
+extern crate rustc_data_structures;
+extern crate rustc_errors;
+extern crate rustc_hir;
+extern crate rustc_lint;
+extern crate rustc_middle;
+extern crate rustc_session;
+extern crate rustc_span;
+
+use rustc_data_structures::fx::FxHashSet;
+use rustc_errors::Diag;
+use rustc_hir::{
+    Body,
+    Expr,
+    ExprKind,
+    HirId,
+    Item,
+    ItemKind,
+    def::{DefKind, Res},
+    def_id::{DefId, LocalDefId},
+    intravisit::{Visitor, walk_expr},
+};
+use rustc_lint::{LateContext, LateLintPass, LintStore};
+use rustc_middle::{
+    hir::nested_filter,
+    ty::{AdtDef, TyCtxt, TypeckResults},
+};
+use rustc_session::{Session, declare_lint, impl_lint_pass};
+use rustc_span::{ExpnData, ExpnKind, MacroKind, Span, Symbol, sym};
+use security_utils::{dedup::Reported, known::is_panic_function};
+use serde::Deserialize;
+
+declare_lint! {
+    pub SECURITY_WASM_EXPORT_PANIC,
+    Warn,
+    "Detects panic-capable code inside functions exported to JavaScript with `#[wasm_bindgen]`."
+}
+
+/// Configuration of the `wasm_usage` library, read from the `[wasm_usage]`
+/// table of the workspace's `dylint.toml`.
+#[derive(Deserialize)]
+#[serde(default)]
+struct Config {
+    /// Attribute macros exporting a function to JavaScript by generating an
+    /// exported shim calling it.
+    attributes: Vec<String>,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Self { attributes: vec!["wasm_bindgen".to_string()] }
+    }
+}
+
+/// Lint pass holding the export attributes loaded from the configuration,
+/// and the exported functions already inspected.
+struct SecurityWasmUsage {
+    attributes: Vec<Symbol>,
+    exports: FxHashSet<LocalDefId>,
+}
+
+impl_lint_pass!(SecurityWasmUsage => [SECURITY_WASM_EXPORT_PANIC]);
+
+impl SecurityWasmUsage {
+    fn new(tcx: TyCtxt<'_>) -> Self {
+        let config: Config =
+            security_utils::config::load(tcx, env!("CARGO_PKG_NAME"));
+
+        Self {
+            attributes: config
+                .attributes
+                .iter()
+                .map(|attribute: &String| Symbol::intern(attribute))
+                .collect(),
+            exports: FxHashSet::default(),
+        }
+    }
+
+    /// Returns `true` if `span` was produced by one of the configured
+    /// attribute macros.
+    ///
+    /// # Arguments
+    /// * `span` (`Span`) - The span whose expansion chain is inspected.
+    fn expanded_from_export_attribute(&self, mut span: Span) -> bool {
+        while span.from_expansion() {
+            let data: ExpnData = span.ctxt().outer_expn_data();
+            if let ExpnKind::Macro(MacroKind::Attr, name) = data.kind
+                && self.attributes.iter().any(|attribute: &Symbol| {
+                    name.as_str().rsplit("::").next()
+                        == Some(attribute.as_str())
+                })
+            {
+                return true;
+            }
+            span = data.call_site;
+        }
+
+        false
+    }
+}
+
+/// Visitor collecting the local functions and methods called or referenced
+/// by the body of an exported shim, i.e. the functions it exports.
+struct ExportCollector<'a, 'tcx> {
+    typeck_results: &'tcx TypeckResults<'tcx>,
+    exports: &'a mut Vec<LocalDefId>,
+}
+
+impl<'tcx> Visitor<'tcx> for ExportCollector<'_, 'tcx> {
+    fn visit_expr(&mut self, expression: &'tcx Expr<'tcx>) {
+        let res: Option<Res> = match &expression.kind {
+            ExprKind::MethodCall(..) => self
+                .typeck_results
+                .type_dependent_def(expression.hir_id)
+                .map(|(kind, id): (DefKind, DefId)| Res::Def(kind, id)),
+            ExprKind::Path(path) => {
+                Some(self.typeck_results.qpath_res(path, expression.hir_id))
+            },
+            _ => None,
+        };
+        if let Some(Res::Def(DefKind::Fn | DefKind::AssocFn, id)) = res
+            && let Some(local) = id.as_local()
+        {
+            self.exports.push(local);
+        }
+
+        walk_expr(self, expression);
+    }
+}
+
+/// Returns `true` if `def_id` is an `unwrap` or `expect` method of `Option`
+/// or `Result`. The methods of `Result` are not diagnostic items, so they are
+/// recognized by name on the inherent impls of `Result`.
+///
+/// # Arguments
+/// * `tcx` (`TyCtxt<'_>`) - The type context used to inspect the method.
+/// * `def_id` (`DefId`) - The called method.
+fn is_unwrap(tcx: TyCtxt<'_>, def_id: DefId) -> bool {
+    if tcx.is_diagnostic_item(sym::option_unwrap, def_id)
+        || tcx.is_diagnostic_item(sym::option_expect, def_id)
+    {
+        return true;
+    }
+
+    let parent: DefId = tcx.parent(def_id);
+    matches!(tcx.def_kind(parent), DefKind::Impl { of_trait: false })
+        && tcx.type_of(parent).instantiate_identity().ty_adt_def().is_some_and(
+            |adt: AdtDef<'_>| tcx.is_diagnostic_item(sym::Result, adt.did()),
+        )
+        && matches!(tcx.item_name(def_id).as_str(), "unwrap" | "expect")
+}
+
+/// Visitor walking the body of an exported function, including nested
+/// closures, and reporting every panic-capable construct. Findings are
+/// reported once per macro call site.
+struct ExportVisitor<'a, 'tcx> {
+    context: &'a LateContext<'tcx>,
+    typeck_results: &'tcx TypeckResults<'tcx>,
+    export_span: Span,
+    reported: Reported,
+}
+
+impl ExportVisitor<'_, '_> {
+    /// Emits a `SECURITY_WASM_EXPORT_PANIC` finding at `span`, with the lint
+    /// level of `node`.
+    ///
+    /// # Arguments
+    /// * `node` (`HirId`) - The expression of the construct.
+    /// * `span` (`Span`) - The span of the panic-capable construct.
+    /// * `what` (`&str`) - A description of the construct.
+    fn report(&mut self, node: HirId, span: Span, what: &str) {
+        let message: String =
+            format!("{what} inside a wasm-bindgen export detected.");
+        if !self.reported.insert(SECURITY_WASM_EXPORT_PANIC, span, &message) {
+            return;
+        }
+
+        security_utils::span_lint_hir(
+            self.context,
+            SECURITY_WASM_EXPORT_PANIC,
+            node,
+            span,
+            |diagnostic: &mut Diag<'_, ()>| {
+                diagnostic.primary_message(message);
+                diagnostic.span_note(
+                    self.export_span,
+                    "a panic in this export aborts the wasm instance, which \
+                     stays unusable for the embedding page or host",
+                );
+                diagnostic.help(
+                    "return `Result<JsValue, JsError>` and propagate the \
+                     error to JavaScript with `?`",
+                );
+            },
+        );
+    }
+}
+
+impl<'tcx> Visitor<'tcx> for ExportVisitor<'_, 'tcx> {
+    type MaybeTyCtxt = TyCtxt<'tcx>;
+    type NestedFilter = nested_filter::OnlyBodies;
+
+    fn maybe_tcx(&mut self) -> Self::MaybeTyCtxt {
+        self.context.tcx
+    }
+
+    fn visit_expr(&mut self, expression: &'tcx Expr<'tcx>) {
+        let tcx: TyCtxt<'tcx> = self.context.tcx;
+
+        let callee: Option<DefId> = match &expression.kind {
+            ExprKind::MethodCall(..) => {
+                self.typeck_results.type_dependent_def_id(expression.hir_id)
+            },
+            ExprKind::Call(callee, _) => match &callee.kind {
+                ExprKind::Path(path) => self
+                    .typeck_results
+                    .qpath_res(path, callee.hir_id)
+                    .opt_def_id(),
+                _ => None,
+            },
+            _ => None,
+        };
+
+        // Indexing and slicing: `array[i]`, `array[a..b]`.
+        if let ExprKind::Index(..) = expression.kind
+            && !expression.span.from_expansion()
+        {
+            self.report(
+                expression.hir_id,
+                expression.span,
+                "Indexing operation",
+            );
+        }
+
+        if let Some(def_id) = callee {
+            // `unwrap` and `expect` on `Option` and `Result`.
+            if !expression.span.from_expansion() && is_unwrap(tcx, def_id) {
+                self.report(
+                    expression.hir_id,
+                    expression.span,
+                    "Call to `unwrap/expect`",
+                );
+            }
+
+            // Explicit panics: `panic!`, `assert!`, `todo!`, ...
+            if is_panic_function(tcx, def_id) {
+                self.report(
+                    expression.hir_id,
+                    expression.span.source_callsite(),
+                    "Explicit panic",
+                );
+            }
+        }
+
+        walk_expr(self, expression);
+    }
+}
+
+impl<'tcx> LateLintPass<'tcx> for SecurityWasmUsage {
+    /// Detect the shims exported by the configured attribute macros and walk
+    /// the bodies of the functions they call for panic-capable code. The
+    /// attribute is consumed by the macro, which emits the function
+    /// unchanged, so exported functions are recognized through the
+    /// `#[export_name]` shim generated next to them.
+    ///
+    /// # Arguments
+    /// * `context` (`&LateContext<'tcx>`) - The lint context providing access
+    ///   to the compiler's internal state.
+    /// * `item` (`&'tcx Item<'tcx>`) - The item being checked for exported
+    ///   shims.
+    fn check_item(
+        &mut self,
+        context: &LateContext<'tcx>,
+        item: &'tcx Item<'tcx>,
+    ) {
+        let ItemKind::Fn { body, .. }: ItemKind<'_> = item.kind else {
+            return;
+        };
+
+        if !self.expanded_from_export_attribute(item.span)
+            || !context
+                .tcx
+                .codegen_fn_attrs(item.owner_id)
+                .contains_extern_indicator()
+        {
+            return;
+        }
+
+        let mut exports: Vec<LocalDefId> = Vec::new();
+        ExportCollector {
+            typeck_results: context.tcx.typeck_body(body),
+            exports: &mut exports,
+        }
+        .visit_expr(context.tcx.hir_body(body).value);
+
+        for export in exports {
+            if !self.exports.insert(export) {
+                continue;
+            }
+            let Some(body): Option<&Body<'_>> =
+                context.tcx.hir_maybe_body_owned_by(export)
+            else {
+                continue;
+            };
+
+            let mut visitor: ExportVisitor<'_, 'tcx> = ExportVisitor {
+                context,
+                typeck_results: context.tcx.typeck(export),
+                export_span: context.tcx.def_span(export),
+                reported: Reported::default(),
+            };
+            visitor.visit_expr(body.value);
+        }
+    }
+}
+
+/// Registers the `SECURITY_WASM_EXPORT_PANIC` lint and its lint pass with
+/// the Rust compiler. The export attributes are read from the `[wasm_usage]`
+/// table of `dylint.toml` when the pass is created.
+///
+/// # Arguments
+/// * `session` (`&Session`) - The compiler session providing access to the
+///   compiler's internal state.
+/// * `lint_store` (`&mut LintStore`) - The lint store where the lint and its
+///   lint pass will be registered.
+#[unsafe(no_mangle)]
+pub fn register_lints(session: &Session, lint_store: &mut LintStore) {
+    dylint_linting::init_config(session);
+
+    security_utils::tool::register_lints(
+        lint_store,
+        &[SECURITY_WASM_EXPORT_PANIC],
+    );
+    lint_store.register_late_pass(|tcx: TyCtxt<'_>| {
+        Box::new(SecurityWasmUsage::new(tcx))
+    });
+}
+
+dylint_linting::dylint_library!();
+
+/// UI tests for the `SECURITY_WASM_EXPORT_PANIC` lint. The `ui` directory
+/// uses a stand-in for the `wasm-bindgen` attribute macro.
+#[cfg(test)]
+mod tests {
+    use dylint_testing::ui::Test;
+
+    #[test]
+    fn ui() {
+        Test::src_base(env!("CARGO_PKG_NAME"), "ui")
+            .rustc_flags(["--edition=2024", "-Z", "ui-testing"])
+            .run();
+    }
+}
@@ -0,0 +1,59 @@
+// force-host
+// no-prefer-dynamic
+
+//! Minimal stand-in for the `wasm-bindgen` attribute macro. Like the real
+//! macro, `#[wasm_bindgen]` emits the function unchanged, followed by an
+//! exported shim calling it, which is what the lint uses to recognize
+//! exported functions after expansion. On an `impl` block, a shim is
+//! generated for each method.
+
+#![crate_type = "proc-macro"]
+
+extern crate proc_macro;
+
+use proc_macro::{TokenStream, TokenTree};
+
+fn functions(item: TokenStream, owner: Option<&str>, shims: &mut String) {
+    let mut after_fn: bool = false;
+    let mut after_impl: bool = false;
+    let mut owner: Option<String> = owner.map(String::from);
+
+    for token in item {
+        match token {
+            TokenTree::Ident(ident) if after_fn => {
+                let path: String = match &owner {
+                    Some(owner) => format!("{owner}::{ident}"),
+                    None => ident.to_string(),
+                };
+                shims.push_str(&format!(
+                    "const _: () = {{ #[unsafe(export_name = \"{ident}\")] \
+                     pub extern \"C\" fn __wasm_bindgen_generated_{ident}() \
+                     {{ let _ = {path}; }} }};"
+                ));
+                after_fn = false;
+            },
+            TokenTree::Ident(ident) if after_impl => {
+                owner = Some(ident.to_string());
+                after_impl = false;
+            },
+            TokenTree::Ident(ident) => {
+                after_fn = ident.to_string() == "fn";
+                after_impl = ident.to_string() == "impl";
+            },
+            TokenTree::Group(group) if owner.is_some() => {
+                functions(group.stream(), owner.as_deref(), shims);
+            },
+            _ => {},
+        }
+    }
+}
+
+#[proc_macro_attribute]
+pub fn wasm_bindgen(_: TokenStream, item: TokenStream) -> TokenStream {
+    let mut shims: String = String::new();
+    functions(item.clone(), None, &mut shims);
+
+    let mut output: TokenStream = item;
+    output.extend(shims.parse::<TokenStream>().unwrap());
+    output
+}
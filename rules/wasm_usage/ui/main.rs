@@ -0,0 +1,58 @@
+// aux-build:wasm_bindgen.rs
+
+extern crate wasm_bindgen;
+
+use wasm_bindgen::wasm_bindgen;
+
+#[wasm_bindgen]
+pub fn parse(input: &str) -> u32 {
+    let value: u32 = input.parse().unwrap(); // Should trigger.
+    let digits: Vec<u32> = vec![value];
+    let first: u32 = digits[0]; // Should trigger.
+
+    if first > 100 {
+        panic!("value out of range"); // Should trigger.
+    }
+
+    let check = || assert!(first < 100); // Should trigger (closure in export).
+    check();
+    first
+}
+
+#[wasm_bindgen]
+pub fn lookup(values: &[u32], index: usize) -> Option<u32> {
+    // Should not trigger: `std::thread::panicking` is a query.
+    if std::thread::panicking() {
+        return None;
+    }
+    values.get(index).copied() // Should not trigger.
+}
+
+#[wasm_bindgen]
+pub fn checked(input: &str) -> Result<u32, String> {
+    let value: u32 = input.parse().map_err(|_| String::from("invalid"))?; // Should not trigger.
+    Ok(value)
+}
+
+pub struct Counter {
+    count: u32,
+}
+
+#[wasm_bindgen]
+impl Counter {
+    pub fn increment(&mut self) {
+        self.count = self.count.checked_add(1).expect("overflow"); // Should trigger.
+    }
+}
+
+/// Functions that are not exported should not trigger the
+/// `SECURITY_WASM_EXPORT_PANIC` lint.
+pub fn helper(input: &str) -> u32 {
+    input.parse().unwrap() // Should not trigger.
+}
+
+fn main() {
+    let mut counter: Counter = Counter { count: 0 };
+    counter.increment();
+    let _ = (parse("1"), lookup(&[1], 0), checked("1"), helper("1"));
+}
@@ -0,0 +1,426 @@
+#![feature(rustc_private)]
+
+extern crate rustc_ast;
+extern crate rustc_errors;
+extern crate rustc_hir;
+extern crate rustc_lint;
+extern crate rustc_middle;
+extern crate rustc_session;
+extern crate rustc_span;
+
+use rustc_ast::LitKind;
+use rustc_errors::Diag;
+use rustc_hir::{
+    Expr,
+    ExprField,
+    ExprKind,
+    QPath,
+    def::{CtorOf, DefKind, Res},
+    def_id::DefId,
+    intravisit::{Visitor, walk_expr},
+};
+use rustc_lint::{LateContext, LateLintPass, LintStore};
+use rustc_middle::ty::{Ty, TyCtxt};
+use rustc_session::{Session, declare_lint, impl_lint_pass};
+use rustc_span::{Span, Symbol};
+use security_utils::calls::{Call, call, is_constructor};
+use serde::Deserialize;
+
+declare_lint! {
+    pub SECURITY_XML_EXTERNAL_ENTITY,
+    Warn,
+    "Detects XML parser configurations enabling DTD processing or external entity resolution."
+}
+
+/// Configuration of the `xml_config` library, read from the `[xml_config]`
+/// table of the workspace's `dylint.toml`.
+#[derive(Deserialize)]
+#[serde(default)]
+struct Config {
+    /// Paths of the parser options enabling DTD loading or entity
+    /// substitution (e.g. the `XML_PARSE_NOENT` option of libxml2). A call
+    /// passing one of them is reported.
+    entity_options: Vec<String>,
+    /// Paths of the boolean fields of parser options enabling DTD processing
+    /// (e.g. `roxmltree::ParsingOptions::allow_dtd`). Setting one of them to
+    /// `true` is reported.
+    entity_fields: Vec<String>,
+    /// Paths of the functions creating a parser that resolves entities with
+    /// a custom resolver (e.g.
+    /// `quick_xml::de::Deserializer::from_str_with_resolver`).
+    resolver_functions: Vec<String>,
+    /// Paths of the resolvers only expanding the predefined entities (e.g.
+    /// `quick_xml::de::PredefinedEntityResolver`). A call to one of the
+    /// `resolver_functions` passing one of them is not reported.
+    safe_resolvers: Vec<String>,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Self {
+            entity_options: [
+                "libxml::bindings::xmlParserOption_XML_PARSE_NOENT",
+                "libxml::bindings::xmlParserOption_XML_PARSE_DTDLOAD",
+                "libxml::bindings::xmlParserOption_XML_PARSE_DTDATTR",
+                "libxml::bindings::xmlParserOption_XML_PARSE_DTDVALID",
+                "libxml::bindings::xmlParserOption_XML_PARSE_HUGE",
+            ]
+            .into_iter()
+            .map(String::from)
+            .collect(),
+            entity_fields: ["roxmltree::ParsingOptions::allow_dtd"]
+                .into_iter()
+                .map(String::from)
+                .collect(),
+            resolver_functions: [
+                "quick_xml::de::Deserializer::with_resolver",
+                "quick_xml::de::Deserializer::from_str_with_resolver",
+            ]
+            .into_iter()
+            .map(String::from)
+            .collect(),
+            safe_resolvers: ["quick_xml::de::PredefinedEntityResolver"]
+                .into_iter()
+                .map(String::from)
+                .collect(),
+        }
+    }
+}
+
+/// Lint pass holding the options, fields and resolvers loaded from the
+/// configuration.
+struct SecurityXmlConfig {
+    entity_options: Vec<String>,
+    entity_fields: Vec<String>,
+    resolver_functions: Vec<String>,
+    safe_resolvers: Vec<String>,
+}
+
+impl_lint_pass!(SecurityXmlConfig => [SECURITY_XML_EXTERNAL_ENTITY]);
+
+/// Parser configuration enabling entity expansion.
+enum EntityConfig {
+    /// A parser option passed to a call, e.g. `XML_PARSE_NOENT`.
+    Option(String),
+    /// A boolean field set to `true`, e.g. `ParsingOptions::allow_dtd`.
+    Field(String),
+    /// A function creating a parser with a custom entity resolver.
+    Resolver(String),
+}
+
+/// Visitor collecting the paths of the constants referenced by the arguments
+/// of a call, e.g. the options combined with `|`. Nested calls (other than
+/// constructors such as `Some(...)`) and closures are not entered, as they
+/// are checked on their own.
+struct PathCollector<'a, 'tcx> {
+    context: &'a LateContext<'tcx>,
+    paths: Vec<String>,
+}
+
+impl<'tcx> Visitor<'tcx> for PathCollector<'_, 'tcx> {
+    fn visit_expr(&mut self, expression: &'tcx Expr<'tcx>) {
+        match &expression.kind {
+            ExprKind::Path(path) => {
+                if let Some(id) = constant(self.context, path, expression) {
+                    self.paths.push(self.context.tcx.def_path_str(id));
+                }
+            },
+            ExprKind::Call(callee, _)
+                if is_constructor(self.context, callee) =>
+            {
+                walk_expr(self, expression);
+            },
+            ExprKind::Call(..)
+            | ExprKind::MethodCall(..)
+            | ExprKind::Closure(..) => {},
+            _ => walk_expr(self, expression),
+        }
+    }
+}
+
+/// Returns the constant `path` resolves to, if any.
+///
+/// # Arguments
+/// * `context` (`&LateContext<'_>`) - The lint context, used to resolve the
+///   path.
+/// * `path` (`&QPath<'_>`) - The path to resolve.
+/// * `expression` (`&Expr<'_>`) - The path expression.
+fn constant(
+    context: &LateContext<'_>,
+    path: &QPath<'_>,
+    expression: &Expr<'_>,
+) -> Option<DefId> {
+    match context.qpath_res(path, expression.hir_id) {
+        Res::Def(DefKind::Const | DefKind::AssocConst, id) => Some(id),
+        Res::Def(DefKind::Ctor(CtorOf::Variant, _), id) => {
+            Some(context.tcx.parent(id))
+        },
+        _ => None,
+    }
+}
+
+/// Returns `true` if `expression` is the literal `true`.
+///
+/// # Arguments
+/// * `expression` (`&Expr<'_>`) - The expression to inspect.
+fn is_true(expression: &Expr<'_>) -> bool {
+    matches!(
+        &expression.kind,
+        ExprKind::Lit(literal) if matches!(literal.node, LitKind::Bool(true))
+    )
+}
+
+impl SecurityXmlConfig {
+    fn new(tcx: TyCtxt<'_>) -> Self {
+        let config: Config =
+            security_utils::config::load(tcx, env!("CARGO_PKG_NAME"));
+
+        Self {
+            entity_options: config.entity_options,
+            entity_fields: config.entity_fields,
+            resolver_functions: config.resolver_functions,
+            safe_resolvers: config.safe_resolvers,
+        }
+    }
+
+    /// Returns the configured field named `field` of the struct `ty`, if it
+    /// is one of the `entity_fields`.
+    ///
+    /// # Arguments
+    /// * `context` (`&LateContext<'_>`) - The lint context, used to name the
+    ///   struct.
+    /// * `ty` (`Ty<'_>`) - The type of the struct.
+    /// * `field` (`Symbol`) - The name of the field.
+    fn entity_field(
+        &self,
+        context: &LateContext<'_>,
+        ty: Ty<'_>,
+        field: Symbol,
+    ) -> Option<String> {
+        let path: String = format!(
+            "{}::{field}",
+            security_utils::paths::struct_path(context.tcx, ty)?
+        );
+        self.entity_fields.contains(&path).then_some(path)
+    }
+
+    /// Returns the entity expansion enabled by the call `expression`: one of
+    /// the `entity_options` passed to it, or a call to one of the
+    /// `resolver_functions` not passing one of the `safe_resolvers`.
+    ///
+    /// # Arguments
+    /// * `context` (`&LateContext<'tcx>`) - The lint context, used to resolve
+    ///   the call.
+    /// * `expression` (`&'tcx Expr<'tcx>`) - The expression to inspect.
+    fn call_config<'tcx>(
+        &self,
+        context: &LateContext<'tcx>,
+        expression: &'tcx Expr<'tcx>,
+    ) -> Option<(Symbol, EntityConfig)> {
+        let Call { callee: id, arguments, .. }: Call<'_> =
+            call(context, expression)?;
+        let name: Symbol = context.tcx.item_name(id);
+
+        let path: String =
+            security_utils::paths::function_path(context.tcx, id);
+        if self.resolver_functions.contains(&path)
+            && !arguments.iter().any(|argument: &Expr<'_>| {
+                security_utils::paths::struct_path(
+                    context.tcx,
+                    context.typeck_results().expr_ty(argument),
+                )
+                .is_some_and(|ty: String| self.safe_resolvers.contains(&ty))
+            })
+        {
+            return Some((name, EntityConfig::Resolver(path)));
+        }
+
+        let mut collector: PathCollector<'_, 'tcx> =
+            PathCollector { context, paths: Vec::new() };
+        for argument in arguments {
+            collector.visit_expr(argument);
+        }
+        collector
+            .paths
+            .into_iter()
+            .find(|path: &String| self.entity_options.contains(path))
+            .map(|option: String| (name, EntityConfig::Option(option)))
+    }
+
+    /// Returns the entity expansion enabled by `expression` setting one of
+    /// the `entity_fields` to `true`, in a struct expression or an
+    /// assignment, and the span of the field.
+    ///
+    /// # Arguments
+    /// * `context` (`&LateContext<'_>`) - The lint context, used to type the
+    ///   struct.
+    /// * `expression` (`&Expr<'_>`) - The expression to inspect.
+    fn field_config(
+        &self,
+        context: &LateContext<'_>,
+        expression: &Expr<'_>,
+    ) -> Option<(Span, Symbol, EntityConfig)> {
+        match &expression.kind {
+            ExprKind::Struct(_, fields, _) => {
+                let ty: Ty<'_> = context.typeck_results().expr_ty(expression);
+                fields.iter().find_map(|field: &ExprField<'_>| {
+                    if !is_true(field.expr) {
+                        return None;
+                    }
+                    let path: String =
+                        self.entity_field(context, ty, field.ident.name)?;
+                    Some((
+                        field.span,
+                        field.ident.name,
+                        EntityConfig::Field(path),
+                    ))
+                })
+            },
+            ExprKind::Assign(target, value, _) if is_true(value) => {
+                let ExprKind::Field(base, field): &ExprKind<'_> = &target.kind
+                else {
+                    return None;
+                };
+                let ty: Ty<'_> = context.typeck_results().expr_ty(base);
+                let path: String =
+                    self.entity_field(context, ty, field.name)?;
+                Some((expression.span, field.name, EntityConfig::Field(path)))
+            },
+            _ => None,
+        }
+    }
+}
+
+/// Emits a `SECURITY_XML_EXTERNAL_ENTITY` finding at `span`, where `name`
+/// sets `config`.
+///
+/// # Arguments
+/// * `context` (`&LateContext<'_>`) - The lint context used to emit the
+///   diagnostic.
+/// * `span` (`Span`) - The span of the call, field or assignment.
+/// * `name` (`Symbol`) - The name of the called function or of the field.
+/// * `config` (`EntityConfig`) - The setting enabling entity expansion.
+fn report(
+    context: &LateContext<'_>,
+    span: Span,
+    name: Symbol,
+    config: EntityConfig,
+) {
+    security_utils::span_lint(
+        context,
+        SECURITY_XML_EXTERNAL_ENTITY,
+        span,
+        |diagnostic: &mut Diag<'_, ()>| {
+            diagnostic.primary_message(format!(
+                "XML entity expansion enabled by `{name}` detected."
+            ));
+            match config {
+                EntityConfig::Option(option) => {
+                    diagnostic.note(format!(
+                        "`{option}` makes the parser load external DTDs or \
+                         substitute entities, so an untrusted document can \
+                         read local files, reach internal hosts (XXE) or \
+                         exhaust memory"
+                    ));
+                    diagnostic.help(
+                        "do not pass this option when parsing untrusted \
+                         documents",
+                    );
+                },
+                EntityConfig::Field(field) => {
+                    diagnostic.note(format!(
+                        "`{field}` enables DTD processing, so an untrusted \
+                         document can declare entities expanding to large \
+                         amounts of data (billion laughs)"
+                    ));
+                    diagnostic.help(
+                        "keep DTD processing disabled when parsing untrusted \
+                         documents",
+                    );
+                },
+                EntityConfig::Resolver(function) => {
+                    diagnostic.note(format!(
+                        "`{function}` resolves the entities declared by the \
+                         document with a custom resolver, which exposes \
+                         whatever the resolver can reach (XXE)"
+                    ));
+                    diagnostic.help(
+                        "use the predefined entities only, or make sure the \
+                         resolver never reads files or fetches URLs",
+                    );
+                },
+            };
+        },
+    );
+}
+
+impl<'tcx> LateLintPass<'tcx> for SecurityXmlConfig {
+    /// Detect XML parser configurations enabling DTD processing or external
+    /// entity resolution, e.g. `XML_PARSE_NOENT | XML_PARSE_DTDLOAD` passed
+    /// to libxml2, `ParsingOptions { allow_dtd: true, .. }` (roxmltree) or
+    /// `Deserializer::from_str_with_resolver` (quick-xml). The parsers
+    /// created by the default constructors do not expand external entities
+    /// and are not reported.
+    ///
+    /// # Arguments
+    /// * `context` (`&LateContext<'tcx>`) - The lint context providing access
+    ///   to the compiler's internal state.
+    /// * `expression` (`&'tcx Expr<'tcx>`) - The expression being checked for
+    ///   XML parser configurations.
+    fn check_expr(
+        &mut self,
+        context: &LateContext<'tcx>,
+        expression: &'tcx Expr<'tcx>,
+    ) {
+        if expression.span.from_expansion() {
+            return;
+        }
+
+        if let Some((name, config)) = self.call_config(context, expression) {
+            report(context, expression.span, name, config);
+        }
+        if let Some((span, name, config)) =
+            self.field_config(context, expression)
+        {
+            report(context, span, name, config);
+        }
+    }
+}
+
+/// Registers the `SECURITY_XML_EXTERNAL_ENTITY` lint and its lint pass with
+/// the Rust compiler. The options, fields and resolvers are read from the
+/// `[xml_config]` table of `dylint.toml` when the pass is created.
+///
+/// # Arguments
+/// * `session` (`&Session`) - The compiler session providing access to the
+///   compiler's internal state.
+/// * `lint_store` (`&mut LintStore`) - The lint store where the lint and its
+///   lint pass will be registered.
+#[unsafe(no_mangle)]
+pub fn register_lints(session: &Session, lint_store: &mut LintStore) {
+    dylint_linting::init_config(session);
+
+    security_utils::tool::register_lints(
+        lint_store,
+        &[SECURITY_XML_EXTERNAL_ENTITY],
+    );
+    lint_store.register_late_pass(|tcx: TyCtxt<'_>| {
+        Box::new(SecurityXmlConfig::new(tcx))
+    });
+}
+
+dylint_linting::dylint_library!();
+
+/// UI tests for the `SECURITY_XML_EXTERNAL_ENTITY` lint. The `ui` directory
+/// uses stand-ins for `libxml`, `quick_xml` and `roxmltree`.
+#[cfg(test)]
+mod tests {
+    use dylint_testing::ui::Test;
+
+    #[test]
+    fn ui() {
+        Test::src_base(env!("CARGO_PKG_NAME"), "ui")
+            .rustc_flags(["--edition=2024", "-Z", "ui-testing"])
+            .run();
+    }
+}
@@ -0,0 +1,24 @@
+//! Minimal stand-in for the `libxml` crate.
+
+#![allow(non_camel_case_types, non_upper_case_globals)]
+
+pub mod bindings {
+    pub type xmlParserOption = u32;
+
+    pub const xmlParserOption_XML_PARSE_RECOVER: xmlParserOption = 1;
+    pub const xmlParserOption_XML_PARSE_NOENT: xmlParserOption = 2;
+    pub const xmlParserOption_XML_PARSE_DTDLOAD: xmlParserOption = 4;
+    pub const xmlParserOption_XML_PARSE_NONET: xmlParserOption = 2048;
+
+    pub struct xmlDoc;
+
+    pub unsafe fn xmlReadMemory(
+        _buffer: *const u8,
+        _size: i32,
+        _url: *const u8,
+        _encoding: *const u8,
+        _options: i32,
+    ) -> *mut xmlDoc {
+        std::ptr::null_mut()
+    }
+}
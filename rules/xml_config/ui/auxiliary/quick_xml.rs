@@ -0,0 +1,28 @@
+//! Minimal stand-in for the `quick-xml` crate.
+
+pub mod de {
+    use std::marker::PhantomData;
+
+    pub trait EntityResolver {}
+
+    pub struct PredefinedEntityResolver;
+
+    impl EntityResolver for PredefinedEntityResolver {}
+
+    pub struct Deserializer<'de, R, E: EntityResolver = PredefinedEntityResolver> {
+        _source: PhantomData<&'de R>,
+        _resolver: E,
+    }
+
+    impl<'de> Deserializer<'de, &'de str> {
+        pub fn from_str(_source: &'de str) -> Self {
+            Self { _source: PhantomData, _resolver: PredefinedEntityResolver }
+        }
+    }
+
+    impl<'de, E: EntityResolver> Deserializer<'de, &'de str, E> {
+        pub fn from_str_with_resolver(_source: &'de str, resolver: E) -> Self {
+            Self { _source: PhantomData, _resolver: resolver }
+        }
+    }
+}
@@ -0,0 +1,22 @@
+//! Minimal stand-in for the `roxmltree` crate.
+
+#[derive(Default)]
+pub struct ParsingOptions {
+    pub allow_dtd: bool,
+    pub nodes_limit: u32,
+}
+
+pub struct Document;
+
+impl Document {
+    pub fn parse(_text: &str) -> Result<Document, ()> {
+        Ok(Document)
+    }
+
+    pub fn parse_with_options(
+        _text: &str,
+        _options: ParsingOptions,
+    ) -> Result<Document, ()> {
+        Ok(Document)
+    }
+}
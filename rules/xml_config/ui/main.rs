@@ -0,0 +1,75 @@
+// aux-build:libxml.rs
+// aux-build:quick_xml.rs
+// aux-build:roxmltree.rs
+
+extern crate libxml;
+extern crate quick_xml;
+extern crate roxmltree;
+
+use libxml::bindings::{
+    xmlDoc, xmlParserOption_XML_PARSE_DTDLOAD, xmlParserOption_XML_PARSE_NOENT,
+    xmlParserOption_XML_PARSE_NONET, xmlParserOption_XML_PARSE_RECOVER,
+    xmlReadMemory,
+};
+use quick_xml::de::{Deserializer, EntityResolver, PredefinedEntityResolver};
+use roxmltree::{Document, ParsingOptions};
+
+struct FileResolver;
+
+impl EntityResolver for FileResolver {}
+
+fn libxml2(text: &str) -> *mut xmlDoc {
+    let options: u32 =
+        xmlParserOption_XML_PARSE_NOENT | xmlParserOption_XML_PARSE_DTDLOAD;
+    let _: *mut xmlDoc = unsafe {
+        xmlReadMemory(
+            text.as_ptr(),
+            text.len() as i32,
+            std::ptr::null(),
+            std::ptr::null(),
+            (xmlParserOption_XML_PARSE_NOENT | xmlParserOption_XML_PARSE_NONET)
+                as i32,
+        )
+    }; // Should trigger.
+    let _: *mut xmlDoc = unsafe {
+        xmlReadMemory(
+            text.as_ptr(),
+            text.len() as i32,
+            std::ptr::null(),
+            std::ptr::null(),
+            (xmlParserOption_XML_PARSE_RECOVER | xmlParserOption_XML_PARSE_NONET)
+                as i32,
+        )
+    }; // Should not trigger.
+    let _: u32 = options;
+    std::ptr::null_mut()
+}
+
+fn quick(text: &str) {
+    let _: Deserializer<'_, &str, FileResolver> =
+        Deserializer::from_str_with_resolver(text, FileResolver); // Should trigger.
+    let _: Deserializer<'_, &str> =
+        Deserializer::from_str_with_resolver(text, PredefinedEntityResolver); // Should not trigger.
+    let _: Deserializer<'_, &str> = Deserializer::from_str(text); // Should not trigger.
+}
+
+fn roxml(text: &str) {
+    let _: Result<Document, ()> = Document::parse_with_options(
+        text,
+        ParsingOptions { allow_dtd: true, ..ParsingOptions::default() }, // Should trigger.
+    );
+    let mut options: ParsingOptions = ParsingOptions::default();
+    options.allow_dtd = true; // Should trigger.
+    let _: Result<Document, ()> = Document::parse_with_options(text, options);
+    let _: Result<Document, ()> = Document::parse_with_options(
+        text,
+        ParsingOptions { allow_dtd: false, nodes_limit: 1024 }, // Should not trigger.
+    );
+    let _: Result<Document, ()> = Document::parse(text); // Should not trigger.
+}
+
+fn main() {
+    let _: *mut xmlDoc = libxml2("<a/>");
+    quick("<a/>");
+    roxml("<a/>");
+}
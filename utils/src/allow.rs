@@ -0,0 +1,142 @@
+//! Per-module-path allow lists, read from the `[allow]` table of the
+//! workspace's `dylint.toml`:
+//!
+//! ```toml
+//! [allow]
+//! security_panic_usage = ["crate::tests::*", "crate::bin::*"]
+//! ```
+//!
+//! Each key is the name of a lint, flat or tool-scoped (e.g.
+//! `"security::panic_usage"`), and each value a list of item paths. A
+//! path matches the item being checked, and every item nested inside it, and
+//! may contain `*` wildcards.
+
+use std::{collections::HashMap, sync::OnceLock};
+
+use rustc_hir::{HirId, def_id::DefId};
+use rustc_lint::{LateContext, Lint};
+use rustc_middle::ty::print::CratePrefixGuard;
+
+use crate::tool;
+
+/// The `[allow]` table, loaded on first use.
+static ALLOW: OnceLock<HashMap<String, Vec<String>>> = OnceLock::new();
+
+/// Returns the `[allow]` table of `dylint.toml`, or an empty table if it is
+/// missing.
+fn allow_table() -> &'static HashMap<String, Vec<String>> {
+    ALLOW.get_or_init(|| dylint_linting::config_or_default("allow"))
+}
+
+/// Returns `true` if `pattern` matches `path`, where `*` in `pattern` matches
+/// any sequence of characters, including `::`.
+///
+/// # Arguments
+/// * `pattern` (`&str`) - The pattern to match.
+/// * `path` (`&str`) - The item path to match against.
+pub fn matches_pattern(pattern: &str, path: &str) -> bool {
+    let Some((prefix, rest)): Option<(&str, &str)> = pattern.split_once('*')
+    else {
+        return pattern == path;
+    };
+
+    let Some(remaining): Option<&str> = path.strip_prefix(prefix) else {
+        return false;
+    };
+
+    remaining
+        .char_indices()
+        .map(|(index, _): (usize, char)| index)
+        .chain(std::iter::once(remaining.len()))
+        .filter_map(|index: usize| remaining.get(index..))
+        .any(|suffix: &str| matches_pattern(rest, suffix))
+}
+
+/// Returns `true` if `pattern` matches `path` or one of its parent items,
+/// e.g. `crate::tests` matches `crate::tests::helper`.
+///
+/// # Arguments
+/// * `pattern` (`&str`) - The pattern to match.
+/// * `path` (`&str`) - The item path to match against.
+pub fn matches_item(pattern: &str, path: &str) -> bool {
+    path.match_indices("::")
+        .filter_map(|(index, _): (usize, &str)| path.get(..index))
+        .chain(std::iter::once(path))
+        .any(|parent: &str| matches_pattern(pattern, parent))
+}
+
+/// Returns the paths of the item owning `node`, once with the `crate::`
+/// prefix and once with the name of the crate (e.g. `crate::tests::helper`
+/// and `my_crate::tests::helper`).
+///
+/// # Arguments
+/// * `context` (`&LateContext<'_>`) - The lint context of the node.
+/// * `node` (`HirId`) - The node being checked.
+pub fn item_paths(context: &LateContext<'_>, node: HirId) -> [String; 2] {
+    let owner: DefId = node.owner.to_def_id();
+
+    let path: String = {
+        let _guard: CratePrefixGuard = CratePrefixGuard::new();
+        context.tcx.def_path_str(owner)
+    };
+    let path: String = match path.as_str() {
+        "" => String::from("crate"),
+        _ if path == "crate" || path.starts_with("crate::") => path,
+        _ => format!("crate::{path}"),
+    };
+
+    let crate_name: String = context.tcx.crate_name(owner.krate).to_string();
+    let named: String = match path.strip_prefix("crate") {
+        Some(rest) => format!("{crate_name}{rest}"),
+        None => path.clone(),
+    };
+
+    [path, named]
+}
+
+/// Returns `true` if `lint` is allowed for the item owning `node`.
+///
+/// # Arguments
+/// * `context` (`&LateContext<'_>`) - The lint context of the node.
+/// * `lint` (`&'static Lint`) - The lint about to be emitted.
+/// * `node` (`HirId`) - The node the lint is emitted at.
+pub fn is_allowed(
+    context: &LateContext<'_>,
+    lint: &'static Lint,
+    node: HirId,
+) -> bool {
+    let name: String = lint.name_lower();
+    let Some(patterns): Option<&Vec<String>> = allow_table()
+        .get(&name)
+        .or_else(|| allow_table().get(&tool::scoped_name(&name)))
+    else {
+        return false;
+    };
+
+    let paths: [String; 2] = item_paths(context, node);
+    patterns.iter().any(|pattern: &String| {
+        paths.iter().any(|path: &String| matches_item(pattern, path))
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{matches_item, matches_pattern};
+
+    #[test]
+    fn pattern() {
+        assert!(matches_pattern("crate::tests", "crate::tests"));
+        assert!(matches_pattern("crate::tests::*", "crate::tests::a::b"));
+        assert!(matches_pattern("crate::*::helper", "crate::a::b::helper"));
+        assert!(!matches_pattern("crate::tests::*", "crate::tests"));
+        assert!(!matches_pattern("crate::tests", "crate::tests_extra"));
+    }
+
+    #[test]
+    fn item() {
+        assert!(matches_item("crate::tests", "crate::tests::helper"));
+        assert!(matches_item("crate::tests::*", "crate::tests::a::helper"));
+        assert!(!matches_item("crate::tests", "crate::tests_extra::helper"));
+        assert!(!matches_item("crate::generated", "crate::api"));
+    }
+}
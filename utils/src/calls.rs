@@ -0,0 +1,98 @@
+//! Calls made by the checked code.
+//!
+//! Lints matching the callees of the checked code against their
+//! configuration resolve function calls, method calls and their inputs the
+//! same way: [`call`] returns the called function of either form, leaving out
+//! the constructors of tuple structs and variants such as `Some(..)`, which
+//! are written as calls but call no function.
+
+use rustc_hir::{
+    Expr,
+    ExprKind,
+    def::{DefKind, Res},
+    def_id::DefId,
+};
+use rustc_lint::LateContext;
+use rustc_span::Symbol;
+
+/// A call to a function or a method.
+#[derive(Debug, Clone, Copy)]
+pub struct Call<'tcx> {
+    /// The called function or method.
+    pub callee: DefId,
+    /// The receiver of a method call, `None` for a function call.
+    pub receiver: Option<&'tcx Expr<'tcx>>,
+    /// The arguments of the call, without the receiver.
+    pub arguments: &'tcx [Expr<'tcx>],
+}
+
+impl<'tcx> Call<'tcx> {
+    /// Returns the inputs of the call: the receiver of a method call, if
+    /// any, followed by the arguments.
+    pub fn inputs(&self) -> Vec<&'tcx Expr<'tcx>> {
+        self.receiver.into_iter().chain(self.arguments).collect()
+    }
+}
+
+/// Returns `true` if `callee` is the constructor of a tuple struct or
+/// variant, e.g. `Some`.
+///
+/// # Arguments
+/// * `context` (`&LateContext<'_>`) - The lint context, used to resolve the
+///   callee.
+/// * `callee` (`&Expr<'_>`) - The called expression.
+pub fn is_constructor(context: &LateContext<'_>, callee: &Expr<'_>) -> bool {
+    let ExprKind::Path(path): &ExprKind<'_> = &callee.kind else {
+        return false;
+    };
+
+    matches!(
+        context.qpath_res(path, callee.hir_id),
+        Res::Def(DefKind::Ctor(..), _)
+    )
+}
+
+/// Returns the call made by `expression`, if it is a call to a function or a
+/// method. Constructors such as `Some(..)` are not calls.
+///
+/// # Arguments
+/// * `context` (`&LateContext<'tcx>`) - The lint context, used to resolve the
+///   callee.
+/// * `expression` (`&'tcx Expr<'tcx>`) - The expression to inspect.
+pub fn call<'tcx>(
+    context: &LateContext<'tcx>,
+    expression: &'tcx Expr<'tcx>,
+) -> Option<Call<'tcx>> {
+    match &expression.kind {
+        ExprKind::MethodCall(_, receiver, arguments, _) => Some(Call {
+            callee: context
+                .typeck_results()
+                .type_dependent_def_id(expression.hir_id)?,
+            receiver: Some(receiver),
+            arguments,
+        }),
+        ExprKind::Call(callee, arguments) => {
+            if is_constructor(context, callee) {
+                return None;
+            }
+            let ExprKind::Path(path): &ExprKind<'_> = &callee.kind else {
+                return None;
+            };
+            Some(Call {
+                callee: context.qpath_res(path, callee.hir_id).opt_def_id()?,
+                receiver: None,
+                arguments,
+            })
+        },
+        _ => None,
+    }
+}
+
+/// Returns `true` if `function` is one of `names`.
+///
+/// # Arguments
+/// * `names` (`&[String]`) - The configured function or method names.
+/// * `function` (`Symbol`) - The name of the called function or method.
+pub fn is_named(names: &[String], function: Symbol) -> bool {
+    names.iter().any(|name: &String| name == function.as_str())
+}
@@ -0,0 +1,179 @@
+//! Validated configuration tables, read from the workspace's `dylint.toml`.
+//!
+//! `dylint_linting::config_or_default` ignores the keys a library does not
+//! know, so a misspelled key silently leaves the library with its default
+//! configuration, and it panics on a value of the wrong type. [`load`]
+//! reports both as compiler errors naming the table and the key instead,
+//! with the closest known key as a suggestion:
+//!
+//! ```text
+//! error: unknown key `patern` in the `[monetary_float]` table of `dylint.toml`, did you mean `patterns`?
+//! ```
+
+use std::collections::BTreeMap;
+
+use dylint_linting::ConfigError;
+use rustc_middle::ty::TyCtxt;
+use rustc_span::{Symbol, edit_distance::find_best_match_for_name};
+use serde::{
+    Deserialize,
+    Deserializer,
+    de::{DeserializeOwned, IgnoredAny, Visitor, value::Error},
+    forward_to_deserialize_any,
+};
+
+/// Reads the `name` table of `dylint.toml` as `T`. An unknown key or an
+/// invalid value is reported as a compiler error, and the default
+/// configuration is used for the rest of the run; a missing table yields
+/// the default configuration.
+///
+/// # Arguments
+/// * `tcx` (`TyCtxt<'_>`) - The type context used to report errors.
+/// * `name` (`&str`) - The name of the table, i.e. of the library.
+pub fn load<T: Default + DeserializeOwned>(tcx: TyCtxt<'_>, name: &str) -> T {
+    match validate::<T>(name) {
+        Ok(config) => config.unwrap_or_default(),
+        Err(message) => {
+            tcx.dcx().err(message);
+            T::default()
+        },
+    }
+}
+
+/// Reads the `name` table of `dylint.toml` as `T`, checking its keys
+/// against the fields of `T`, or returns `None` if the table is missing.
+///
+/// # Arguments
+/// * `name` (`&str`) - The name of the table.
+fn validate<T: DeserializeOwned>(name: &str) -> Result<Option<T>, String> {
+    let keys: Option<BTreeMap<String, IgnoredAny>> =
+        dylint_linting::config(name)
+            .map_err(|error: ConfigError| invalid(name, &error))?;
+
+    let fields: &'static [&'static str] = fields::<T>();
+    if let Some(key) = keys
+        .iter()
+        .flat_map(BTreeMap::keys)
+        .find(|key: &&String| !fields.contains(&key.as_str()))
+    {
+        return Err(unknown_key(name, key, fields));
+    }
+
+    dylint_linting::config::<T>(name)
+        .map_err(|error: ConfigError| invalid(name, &error))
+}
+
+/// Returns the error reporting the `name` table as invalid.
+///
+/// # Arguments
+/// * `name` (`&str`) - The name of the table.
+/// * `error` (`&ConfigError`) - The error reading or deserializing it.
+fn invalid(name: &str, error: &ConfigError) -> String {
+    format!("invalid `[{name}]` table of `dylint.toml`: {error}")
+}
+
+/// Returns the error reporting `key` as unknown in the `name` table, with
+/// the closest of `fields` as a suggestion, or the list of `fields` if none
+/// is close.
+///
+/// # Arguments
+/// * `name` (`&str`) - The name of the table.
+/// * `key` (`&str`) - The unknown key.
+/// * `fields` (`&[&str]`) - The keys of the table.
+fn unknown_key(name: &str, key: &str, fields: &[&str]) -> String {
+    let candidates: Vec<Symbol> =
+        fields.iter().map(|field: &&str| Symbol::intern(field)).collect();
+    let message: String = format!(
+        "unknown key `{key}` in the `[{name}]` table of `dylint.toml`"
+    );
+
+    match find_best_match_for_name(&candidates, Symbol::intern(key), None) {
+        Some(suggestion) => format!("{message}, did you mean `{suggestion}`?"),
+        None => format!(
+            "{message}, expected one of {}",
+            fields
+                .iter()
+                .map(|field: &&str| format!("`{field}`"))
+                .collect::<Vec<String>>()
+                .join(", ")
+        ),
+    }
+}
+
+/// Returns the fields of the struct `T`, as declared to serde by its
+/// `Deserialize` implementation.
+fn fields<'de, T: Deserialize<'de>>() -> &'static [&'static str] {
+    let mut fields: &'static [&'static str] = &[];
+    // The deserializer fails right after recording the fields.
+    let _: Result<T, Error> =
+        T::deserialize(FieldsDeserializer { fields: &mut fields });
+
+    fields
+}
+
+/// A deserializer recording the fields of the struct deserialized from it,
+/// and failing on anything else.
+struct FieldsDeserializer<'a> {
+    fields: &'a mut &'static [&'static str],
+}
+
+impl<'de> Deserializer<'de> for FieldsDeserializer<'_> {
+    type Error = Error;
+
+    fn deserialize_any<V: Visitor<'de>>(
+        self,
+        _: V,
+    ) -> Result<V::Value, Error> {
+        Err(serde::de::Error::custom("not a struct"))
+    }
+
+    fn deserialize_struct<V: Visitor<'de>>(
+        self,
+        _: &'static str,
+        fields: &'static [&'static str],
+        _: V,
+    ) -> Result<V::Value, Error> {
+        *self.fields = fields;
+        Err(serde::de::Error::custom("fields recorded"))
+    }
+
+    forward_to_deserialize_any! {
+        bool i8 i16 i32 i64 i128 u8 u16 u32 u64 u128 f32 f64 char str string
+        bytes byte_buf option unit unit_struct newtype_struct seq tuple
+        tuple_struct map enum identifier ignored_any
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rustc_span::create_default_session_globals_then;
+    use serde::Deserialize;
+
+    use super::{fields, unknown_key};
+
+    #[derive(Deserialize)]
+    #[allow(dead_code)]
+    struct Config {
+        patterns: Vec<String>,
+        sync_primitives: Vec<String>,
+    }
+
+    #[test]
+    fn keys() {
+        assert_eq!(fields::<Config>(), ["patterns", "sync_primitives"]);
+
+        create_default_session_globals_then(|| {
+            assert_eq!(
+                unknown_key("demo", "patern", fields::<Config>()),
+                "unknown key `patern` in the `[demo]` table of \
+                 `dylint.toml`, did you mean `patterns`?"
+            );
+            assert_eq!(
+                unknown_key("demo", "timeout", fields::<Config>()),
+                "unknown key `timeout` in the `[demo]` table of \
+                 `dylint.toml`, expected one of `patterns`, \
+                 `sync_primitives`"
+            );
+        });
+    }
+}
@@ -0,0 +1,76 @@
+//! Deduplication of the findings of a lint pass.
+//!
+//! Code generated by macros (derives, `#[tokio::main]`, the test harness,
+//! `macro_rules!` helpers, ...) often contains the same construct several
+//! times, each with a span pointing into the macro definition but sharing the
+//! call site of the macro. [`Reported`] keeps one finding per lint, call site
+//! and message, so that each use of a macro is reported once.
+
+use rustc_data_structures::fx::FxHashSet;
+use rustc_errors::Diag;
+use rustc_lint::{LateContext, Lint};
+use rustc_span::Span;
+
+/// The findings already emitted by a lint pass, keyed on the lint, the call
+/// site of the primary span and the primary message.
+#[derive(Debug, Default)]
+pub struct Reported {
+    seen: FxHashSet<(&'static str, Span, String)>,
+}
+
+impl Reported {
+    /// Returns `true`, and records the finding, if no finding of `lint` with
+    /// `message` was recorded for the call site of `span` yet.
+    ///
+    /// # Arguments
+    /// * `lint` (`&'static Lint`) - The lint being emitted.
+    /// * `span` (`Span`) - The primary span of the finding.
+    /// * `message` (`&str`) - The primary message of the finding.
+    pub fn insert(
+        &mut self,
+        lint: &'static Lint,
+        span: Span,
+        message: &str,
+    ) -> bool {
+        self.seen.insert((
+            lint.name,
+            span.source_callsite(),
+            message.to_owned(),
+        ))
+    }
+
+    /// Emits `lint` at `span` with `message` as primary message through
+    /// [`span_lint`](crate::span_lint), unless the same finding was already
+    /// emitted for the call site of `span`.
+    ///
+    /// # Arguments
+    /// * `context` (`&LateContext<'_>`) - The lint context used to emit the
+    ///   diagnostic.
+    /// * `lint` (`&'static Lint`) - The lint being emitted.
+    /// * `span` (`Span`) - The primary span of the diagnostic.
+    /// * `message` (`String`) - The primary message of the diagnostic.
+    /// * `decorate` (`impl FnOnce(&mut Diag<'_, ()>)`) - The closure adding
+    ///   notes and suggestions to the diagnostic.
+    pub fn span_lint(
+        &mut self,
+        context: &LateContext<'_>,
+        lint: &'static Lint,
+        span: Span,
+        message: String,
+        decorate: impl for<'a, 'b> FnOnce(&'b mut Diag<'a, ()>),
+    ) {
+        if !self.insert(lint, span, &message) {
+            return;
+        }
+
+        crate::span_lint(
+            context,
+            lint,
+            span,
+            |diagnostic: &mut Diag<'_, ()>| {
+                diagnostic.primary_message(message);
+                decorate(diagnostic);
+            },
+        );
+    }
+}
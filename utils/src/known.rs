@@ -0,0 +1,166 @@
+//! Items resolved once per crate.
+//!
+//! Lint passes compare the callees of many expressions against the same
+//! standard library items, or against the paths of their configuration.
+//! [`KnownItems`] resolves the lang items the lints need in `check_crate`, so
+//! that each expression is checked with `DefId` comparisons, and
+//! [`DefIdCache`] memoizes per-`DefId` results such as whether the path of a
+//! function is configured, so that each path is formatted once per crate
+//! rather than once per call.
+
+use rustc_data_structures::fx::FxHashMap;
+use rustc_hir::{LanguageItems, def::DefKind, def_id::DefId};
+use rustc_middle::ty::TyCtxt;
+use rustc_span::{Symbol, sym};
+
+/// The DefIds of the standard library items shared by the lints, resolved
+/// from the lang items of the crate. An item missing from the crate, e.g.
+/// `begin_panic` in a `no_std` crate, is `None`.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct KnownItems {
+    /// `core::panicking::panic_fmt`, called by `panic!` and the assertions.
+    pub panic_fmt: Option<DefId>,
+    /// `core::panicking::panic_display`, called by `panic!("{}", value)`.
+    pub panic_display: Option<DefId>,
+    /// `std::panicking::begin_panic`, called by `panic!` in edition 2015.
+    pub begin_panic: Option<DefId>,
+    /// The `Index` trait.
+    pub index_trait: Option<DefId>,
+    /// The `IndexMut` trait.
+    pub index_mut_trait: Option<DefId>,
+}
+
+impl KnownItems {
+    /// Resolves the items of the crate being checked.
+    ///
+    /// # Arguments
+    /// * `tcx` (`TyCtxt<'_>`) - The type context used to look up the lang
+    ///   items.
+    pub fn new(tcx: TyCtxt<'_>) -> Self {
+        let lang_items: &LanguageItems = tcx.lang_items();
+
+        Self {
+            panic_fmt: lang_items.panic_fmt(),
+            panic_display: lang_items.panic_display(),
+            begin_panic: lang_items.begin_panic_fn(),
+            index_trait: lang_items.index_trait(),
+            index_mut_trait: lang_items.index_mut_trait(),
+        }
+    }
+
+    /// Returns `true` if `def_id` is the `Index` or `IndexMut` trait.
+    ///
+    /// # Arguments
+    /// * `def_id` (`DefId`) - The trait to check.
+    pub fn is_index_trait(&self, def_id: DefId) -> bool {
+        self.index_trait == Some(def_id)
+            || self.index_mut_trait == Some(def_id)
+    }
+}
+
+/// Returns `true` if `def_id` is a diverging function of the `core` or `std`
+/// `panicking` modules, such as `core::panicking::panic` or
+/// `assert_failed`. Functions of these modules that return, such as
+/// `std::panicking::set_hook`, and items merely named after them, such as
+/// `std::thread::panicking`, do not match.
+///
+/// # Arguments
+/// * `tcx` (`TyCtxt<'_>`) - The type context used to look up the parent
+///   modules.
+/// * `def_id` (`DefId`) - The called function.
+pub fn is_panicking_module_fn(tcx: TyCtxt<'_>, def_id: DefId) -> bool {
+    let krate: Symbol = tcx.crate_name(def_id.krate);
+    if krate != sym::core && krate != sym::std {
+        return false;
+    }
+
+    if !matches!(tcx.def_kind(def_id), DefKind::Fn)
+        || !tcx.fn_sig(def_id).skip_binder().output().skip_binder().is_never()
+    {
+        return false;
+    }
+
+    let mut parent: Option<DefId> = tcx.opt_parent(def_id);
+    while let Some(module) = parent {
+        if tcx.def_kind(module) == DefKind::Mod
+            && tcx.opt_item_name(module) == Some(sym::panicking)
+        {
+            return true;
+        }
+        parent = tcx.opt_parent(module);
+    }
+
+    false
+}
+
+/// Returns `true` if `def_id` is a panic entry point of the standard
+/// library: the `panic_fmt`, `panic_display` or `begin_panic` lang items,
+/// or another diverging function of the `panicking` modules (see
+/// [`is_panicking_module_fn`]).
+///
+/// # Arguments
+/// * `tcx` (`TyCtxt<'_>`) - The type context used to look up the lang items.
+/// * `def_id` (`DefId`) - The called function.
+pub fn is_panic_function(tcx: TyCtxt<'_>, def_id: DefId) -> bool {
+    let lang_items: &LanguageItems = tcx.lang_items();
+
+    [
+        lang_items.panic_fmt(),
+        lang_items.panic_display(),
+        lang_items.begin_panic_fn(),
+    ]
+    .contains(&Some(def_id))
+        || is_panicking_module_fn(tcx, def_id)
+}
+
+/// Results computed once per `DefId`, e.g. whether the path of a called
+/// function is one of the configured paths.
+#[derive(Debug)]
+pub struct DefIdCache<T> {
+    entries: FxHashMap<DefId, T>,
+}
+
+impl<T> Default for DefIdCache<T> {
+    fn default() -> Self {
+        Self { entries: FxHashMap::default() }
+    }
+}
+
+impl<T: Copy> DefIdCache<T> {
+    /// Returns the result cached for `def_id`, computing it with `compute`
+    /// on first use.
+    ///
+    /// # Arguments
+    /// * `def_id` (`DefId`) - The item the result is about.
+    /// * `compute` (`impl FnOnce() -> T`) - The closure computing the result.
+    pub fn get_or_insert_with(
+        &mut self,
+        def_id: DefId,
+        compute: impl FnOnce() -> T,
+    ) -> T {
+        *self.entries.entry(def_id).or_insert_with(compute)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rustc_hir::def_id::{CRATE_DEF_ID, DefId};
+
+    use super::DefIdCache;
+
+    #[test]
+    fn cache() {
+        let mut cache: DefIdCache<usize> = DefIdCache::default();
+        let id: DefId = CRATE_DEF_ID.to_def_id();
+        let mut computed: usize = 0;
+
+        for _ in 0..3 {
+            let value: usize = cache.get_or_insert_with(id, || {
+                computed += 1;
+                42
+            });
+            assert_eq!(value, 42);
+        }
+        assert_eq!(computed, 1);
+    }
+}
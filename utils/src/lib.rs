@@ -0,0 +1,94 @@
+#![feature(rustc_private)]
+
+extern crate rustc_data_structures;
+extern crate rustc_errors;
+extern crate rustc_hir;
+extern crate rustc_lint;
+extern crate rustc_middle;
+extern crate rustc_session;
+extern crate rustc_span;
+
+pub mod allow;
+pub mod calls;
+pub mod config;
+pub mod dedup;
+pub mod known;
+pub mod paths;
+pub mod registry;
+pub mod suppression;
+pub mod tool;
+pub mod words;
+
+use rustc_errors::{Diag, MultiSpan};
+use rustc_hir::HirId;
+use rustc_lint::{LateContext, Level, Lint};
+use rustc_middle::lint::LevelAndSource;
+
+use crate::suppression::Mechanism;
+
+/// Emits `lint` at `span` unless it is allowed for the item being checked by
+/// the `[allow]` table of `dylint.toml`. Every lint of the workspace reports
+/// through this function (or [`span_lint_hir`]) instead of calling
+/// `LintContext::span_lint` directly.
+///
+/// # Arguments
+/// * `context` (`&LateContext<'_>`) - The lint context used to emit the
+///   diagnostic.
+/// * `lint` (`&'static Lint`) - The lint being emitted.
+/// * `span` (`S`) - The primary span of the diagnostic.
+/// * `decorate` (`impl FnOnce(&mut Diag<'_, ()>)`) - The closure building the
+///   diagnostic.
+pub fn span_lint<S: Into<MultiSpan>>(
+    context: &LateContext<'_>,
+    lint: &'static Lint,
+    span: S,
+    decorate: impl for<'a, 'b> FnOnce(&'b mut Diag<'a, ()>),
+) {
+    span_lint_hir(
+        context,
+        lint,
+        context.last_node_with_lint_attrs,
+        span,
+        decorate,
+    );
+}
+
+/// Emits `lint` at `span` with the lint level of `node`, unless it is allowed
+/// for the item owning `node` by the `[allow]` table of `dylint.toml`. Lints
+/// reporting nodes nested in the one being checked (e.g. the expressions of a
+/// body walked from `check_item`) use this function so that `#[allow]` and
+/// `#[expect]` attributes on the reported node apply.
+///
+/// A finding covered by an `#[expect(...)]` attribute is emitted even when
+/// the `[allow]` table allows it, which fulfills the expectation instead of
+/// reporting it as unfulfilled. Findings silenced by the `[allow]` table or
+/// by a lint level are reported through the `security_suppressed_finding`
+/// lint (see [`suppression`]).
+///
+/// # Arguments
+/// * `context` (`&LateContext<'_>`) - The lint context used to emit the
+///   diagnostic.
+/// * `lint` (`&'static Lint`) - The lint being emitted.
+/// * `node` (`HirId`) - The node whose lint level applies.
+/// * `span` (`S`) - The primary span of the diagnostic.
+/// * `decorate` (`impl FnOnce(&mut Diag<'_, ()>)`) - The closure building the
+///   diagnostic.
+pub fn span_lint_hir<S: Into<MultiSpan>>(
+    context: &LateContext<'_>,
+    lint: &'static Lint,
+    node: HirId,
+    span: S,
+    decorate: impl for<'a, 'b> FnOnce(&'b mut Diag<'a, ()>),
+) {
+    let span: MultiSpan = span.into();
+    let level: LevelAndSource = context.tcx.lint_level_at_node(lint, node);
+    if allow::is_allowed(context, lint, node) && level.level != Level::Expect {
+        suppression::report(context, lint, node, span, Mechanism::AllowTable);
+        return;
+    }
+    if let Some(mechanism) = Mechanism::of(&level) {
+        suppression::report(context, lint, node, span.clone(), mechanism);
+    }
+
+    context.tcx.node_span_lint(lint, node, span, decorate);
+}
@@ -0,0 +1,48 @@
+//! Paths of the items used by the checked code, as matched against the
+//! function and type paths of the configurations.
+//!
+//! `TyCtxt::def_path_str` names the methods of inherent impls after the impl,
+//! generic parameters included (e.g. `std::vec::Vec::<T>::new`), which no
+//! configured path matches. [`function_path`] names them after their type
+//! instead, e.g. `std::vec::Vec::new`, and [`struct_path`] names a struct
+//! type without its generic arguments.
+
+use rustc_hir::{def::DefKind, def_id::DefId};
+use rustc_middle::ty::{Ty, TyCtxt, TyKind};
+
+/// Returns the path of the function `id`, naming the methods of inherent
+/// impls after their type without its generic parameters, e.g.
+/// `std::fs::File::create`.
+///
+/// # Arguments
+/// * `tcx` (`TyCtxt<'_>`) - The type context used to name the function.
+/// * `id` (`DefId`) - The function.
+pub fn function_path(tcx: TyCtxt<'_>, id: DefId) -> String {
+    let parent: DefId = tcx.parent(id);
+    if matches!(tcx.def_kind(parent), DefKind::Impl { of_trait: false })
+        && let Some(adt) =
+            tcx.type_of(parent).instantiate_identity().ty_adt_def()
+    {
+        return format!(
+            "{}::{}",
+            tcx.def_path_str(adt.did()),
+            tcx.item_name(id)
+        );
+    }
+    tcx.def_path_str(id)
+}
+
+/// Returns the path of the struct `ty`, behind any references, if it is a
+/// struct.
+///
+/// # Arguments
+/// * `tcx` (`TyCtxt<'_>`) - The type context used to name the struct.
+/// * `ty` (`Ty<'_>`) - The type to inspect.
+pub fn struct_path(tcx: TyCtxt<'_>, ty: Ty<'_>) -> Option<String> {
+    match ty.peel_refs().kind() {
+        TyKind::Adt(adt, _) if adt.is_struct() => {
+            Some(tcx.def_path_str(adt.did()))
+        },
+        _ => None,
+    }
+}
@@ -0,0 +1,123 @@
+//! Registration of lint libraries with `cargo security-lints`.
+//!
+//! The subcommand knows the lints of this repository, but not those of the
+//! other libraries listed in `[workspace.metadata.dylint]`. A library
+//! registering its lints with [`register`] instead of
+//! [`tool::register_lints`] also describes them to the subcommand: when the
+//! [`REGISTRY_VARIABLE`] environment variable names a directory, as it does
+//! in the runs of the subcommand, the lints of the library and the levels it
+//! sets in each profile are written to a `<library>.toml` file of that
+//! directory:
+//!
+//! ```toml
+//! lints = ["security_acme_unaudited_ffi"]
+//!
+//! [profiles.crypto]
+//! security_acme_unaudited_ffi = "deny"
+//! ```
+//!
+//! The subcommand then adds these levels to the profiles it applies.
+
+use std::{
+    collections::BTreeMap,
+    env,
+    error::Error,
+    fs,
+    path::{Path, PathBuf},
+    process,
+};
+
+use rustc_lint::{Level, Lint, LintStore};
+use rustc_session::Session;
+use serde::Serialize;
+
+use crate::tool;
+
+/// The environment variable naming the directory the libraries are
+/// registered in.
+pub const REGISTRY_VARIABLE: &str = "SECURITY_LINTS_REGISTRY";
+
+/// A lint library, as described to `cargo security-lints`.
+#[derive(Debug, Clone, Copy)]
+pub struct Library<'a> {
+    /// The name of the library, e.g. `acme_lints`.
+    pub name: &'a str,
+    /// The lints of the library.
+    pub lints: &'a [&'static Lint],
+    /// The levels the profiles set, as the name of a profile (e.g.
+    /// `crypto`), a lint of the library and its level: `Allow`, `Warn` or
+    /// `Deny`.
+    pub profiles: &'a [(&'a str, &'static Lint, Level)],
+}
+
+/// The content of the file registering a library.
+#[derive(Serialize)]
+struct Registration<'a> {
+    lints: Vec<String>,
+    profiles: BTreeMap<&'a str, BTreeMap<String, &'static str>>,
+}
+
+/// Registers the lints of `library` with the Rust compiler (see
+/// [`tool::register_lints`]) and, in the runs of `cargo security-lints`,
+/// with the subcommand. Libraries call this function from `register_lints`.
+///
+/// # Arguments
+/// * `session` (`&Session`) - The compiler session, used to warn when the
+///   library cannot be registered with the subcommand.
+/// * `lint_store` (`&mut LintStore`) - The lint store where the lints are
+///   registered.
+/// * `library` (`&Library<'_>`) - The library to register.
+pub fn register(
+    session: &Session,
+    lint_store: &mut LintStore,
+    library: &Library<'_>,
+) {
+    tool::register_lints(lint_store, library.lints);
+
+    let Some(directory) = env::var_os(REGISTRY_VARIABLE) else {
+        return;
+    };
+    if let Err(error) = write(Path::new(&directory), library) {
+        session.dcx().warn(format!(
+            "could not register the `{}` library with `cargo \
+             security-lints`: {error}",
+            library.name
+        ));
+    }
+}
+
+/// Writes the file registering `library` to `directory`. The file is
+/// written under a temporary name first, so that the compilations running
+/// in parallel never leave a partial file behind.
+///
+/// # Arguments
+/// * `directory` (`&Path`) - The directory the libraries are registered in.
+/// * `library` (`&Library<'_>`) - The library to register.
+fn write(
+    directory: &Path,
+    library: &Library<'_>,
+) -> Result<(), Box<dyn Error>> {
+    let mut registration: Registration<'_> = Registration {
+        lints: library
+            .lints
+            .iter()
+            .map(|lint: &&'static Lint| lint.name_lower())
+            .collect(),
+        profiles: BTreeMap::new(),
+    };
+    for (profile, lint, level) in library.profiles {
+        registration
+            .profiles
+            .entry(profile)
+            .or_default()
+            .insert(lint.name_lower(), level.as_str());
+    }
+
+    fs::create_dir_all(directory)?;
+    let temporary: PathBuf =
+        directory.join(format!("{}.{}.tmp", library.name, process::id()));
+    fs::write(&temporary, toml::to_string(&registration)?)?;
+    fs::rename(&temporary, directory.join(format!("{}.toml", library.name)))?;
+
+    Ok(())
+}
@@ -0,0 +1,99 @@
+//! Reporting of suppressed findings.
+//!
+//! A finding silenced by the `[allow]` table of `dylint.toml`, an
+//! `#[allow(...)]` or `#[expect(...)]` attribute or a command-line lint level
+//! (e.g. a profile) is reported through the `security_suppressed_finding`
+//! lint, with the mechanism that silenced it. The lint is declared and
+//! registered by the `lint_expectations` library and allowed by default;
+//! every library finds it in the lint store by name, since each one links
+//! its own copy of this crate.
+
+use rustc_errors::{Diag, MultiSpan};
+use rustc_hir::HirId;
+use rustc_lint::{LateContext, Level, Lint, LintId, unerased_lint_store};
+use rustc_middle::lint::{LevelAndSource, LintLevelSource};
+
+/// Name of the lint reporting suppressed findings.
+pub const SUPPRESSION_LINT: &str = "security_suppressed_finding";
+
+/// Mechanism silencing a finding.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Mechanism {
+    /// The `[allow]` table of `dylint.toml`.
+    AllowTable,
+    /// An `#[allow(...)]` attribute.
+    AllowAttribute,
+    /// An `#[expect(...)]` attribute.
+    ExpectAttribute,
+    /// A `-A` flag passed to rustc, e.g. by a profile.
+    CommandLine,
+}
+
+impl Mechanism {
+    /// Returns the mechanism silencing a finding emitted with `level`, if
+    /// any. Lints allowed by default are not suppressed, only disabled.
+    ///
+    /// # Arguments
+    /// * `level` (`&LevelAndSource`) - The lint level at the reported node.
+    pub fn of(level: &LevelAndSource) -> Option<Self> {
+        match (level.level, &level.src) {
+            (Level::Expect, _) => Some(Self::ExpectAttribute),
+            (Level::Allow, LintLevelSource::Node { .. }) => {
+                Some(Self::AllowAttribute)
+            },
+            (Level::Allow, LintLevelSource::CommandLine(..)) => {
+                Some(Self::CommandLine)
+            },
+            _ => None,
+        }
+    }
+
+    /// Returns the name of the mechanism, as written in the `suppressed by:`
+    /// note of the diagnostic.
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::AllowTable => "allow-table",
+            Self::AllowAttribute => "allow-attribute",
+            Self::ExpectAttribute => "expect-attribute",
+            Self::CommandLine => "command-line",
+        }
+    }
+}
+
+/// Reports a finding of `lint` silenced by `mechanism` through the
+/// `security_suppressed_finding` lint, with the lint level of `node`. Nothing
+/// is reported when the `lint_expectations` library is not loaded.
+///
+/// # Arguments
+/// * `context` (`&LateContext<'_>`) - The lint context used to emit the
+///   diagnostic.
+/// * `lint` (`&'static Lint`) - The suppressed lint.
+/// * `node` (`HirId`) - The node of the suppressed finding.
+/// * `span` (`MultiSpan`) - The primary span of the suppressed finding.
+/// * `mechanism` (`Mechanism`) - The mechanism silencing the finding.
+pub fn report(
+    context: &LateContext<'_>,
+    lint: &'static Lint,
+    node: HirId,
+    span: MultiSpan,
+    mechanism: Mechanism,
+) {
+    let Some(&[suppression]): Option<&[LintId]> =
+        unerased_lint_store(context.tcx.sess).find_lints(SUPPRESSION_LINT)
+    else {
+        return;
+    };
+
+    context.tcx.node_span_lint(
+        suppression.lint,
+        node,
+        span,
+        |diagnostic: &mut Diag<'_, ()>| {
+            diagnostic.primary_message(format!(
+                "Suppressed finding of `{}` detected.",
+                lint.name_lower()
+            ));
+            diagnostic.note(format!("suppressed by: {}", mechanism.name()));
+        },
+    );
+}
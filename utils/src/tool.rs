@@ -0,0 +1,116 @@
+//! Tool-scoped names of the lints, e.g. `security::panic_usage` for
+//! `security_panic_usage`.
+//!
+//! Every lint keeps its flat name, and is also registered as the only member
+//! of a lint group named after the `security` tool, so that attributes can
+//! use either form:
+//!
+//! ```rust,ignore
+//! #![feature(register_tool)]
+//! #![register_tool(security)]
+//!
+//! #[allow(security::panic_usage)]
+//! fn first(values: &[u8]) -> u8 {
+//!     *values.first().unwrap()
+//! }
+//! ```
+//!
+//! rustc only accepts tool-scoped lint names for the tools registered by the
+//! crate, hence the `register_tool` attribute. As the lints of each library
+//! are registered separately and a lint group cannot be extended once
+//! registered, there is no `security::all` group covering the whole suite.
+//!
+//! A lint split out of another keeps the findings it took over reachable
+//! through the former name: [`split_lint`] reports them under the former lint
+//! while only that lint has a level set, so that existing attributes and
+//! flags keep applying until the new lint is configured.
+
+use rustc_hir::HirId;
+use rustc_lint::{LateContext, Lint, LintId, LintStore};
+use rustc_middle::lint::{LevelAndSource, LintLevelSource};
+
+/// The tool the lints are scoped under.
+pub const TOOL: &str = "security";
+
+/// Returns the tool-scoped name of the lint `name`, e.g.
+/// `security::panic_usage` for `security_panic_usage` and
+/// `security::missing_let_type` for `missing_let_type`.
+///
+/// # Arguments
+/// * `name` (`&str`) - The flat name of the lint, in lowercase.
+pub fn scoped_name(name: &str) -> String {
+    let name: &str = name
+        .strip_prefix(TOOL)
+        .and_then(|rest: &str| rest.strip_prefix('_'))
+        .unwrap_or(name);
+
+    format!("{TOOL}::{name}")
+}
+
+/// Registers `lints` with the Rust compiler, each under its flat name and
+/// under its tool-scoped name (see [`scoped_name`]). Libraries call this
+/// function from `register_lints` instead of `LintStore::register_lints`.
+///
+/// # Arguments
+/// * `lint_store` (`&mut LintStore`) - The lint store where the lints are
+///   registered.
+/// * `lints` (`&[&'static Lint]`) - The lints of the library.
+pub fn register_lints(lint_store: &mut LintStore, lints: &[&'static Lint]) {
+    lint_store.register_lints(lints);
+
+    for &lint in lints {
+        // Group names are static; each is leaked once per compilation.
+        let name: &'static str = scoped_name(&lint.name_lower()).leak();
+        lint_store.register_group(true, name, None, vec![LintId::of(lint)]);
+    }
+}
+
+/// Returns the lint reporting a finding at `node` that moved from `former` to
+/// `lint` when `lint` was split out of it: `former` while its level is set,
+/// by an attribute or a command-line flag, and the level of `lint` is not,
+/// so that an existing `allow`, `expect` or `deny` of the former lint keeps
+/// applying, and `lint` otherwise.
+///
+/// # Arguments
+/// * `context` (`&LateContext<'_>`) - The lint context, providing the lint
+///   levels.
+/// * `node` (`HirId`) - The node whose lint levels apply.
+/// * `lint` (`&'static Lint`) - The lint the finding moved to.
+/// * `former` (`&'static Lint`) - The lint that reported the finding before
+///   the split.
+pub fn split_lint(
+    context: &LateContext<'_>,
+    node: HirId,
+    lint: &'static Lint,
+    former: &'static Lint,
+) -> &'static Lint {
+    let level: LevelAndSource = context.tcx.lint_level_at_node(lint, node);
+    let former_level: LevelAndSource =
+        context.tcx.lint_level_at_node(former, node);
+
+    if matches!(level.src, LintLevelSource::Default)
+        && !matches!(former_level.src, LintLevelSource::Default)
+    {
+        former
+    } else {
+        lint
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::scoped_name;
+
+    #[test]
+    fn scoped() {
+        assert_eq!(
+            scoped_name("security_panic_usage"),
+            "security::panic_usage"
+        );
+        assert_eq!(
+            scoped_name("missing_let_type"),
+            "security::missing_let_type"
+        );
+        assert_eq!(scoped_name("securityless"), "security::securityless");
+    }
+}
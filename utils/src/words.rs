@@ -0,0 +1,69 @@
+//! Splitting of identifiers into words.
+//!
+//! Several lints recognize an identifier from the words it is made of, e.g.
+//! `price` in `unit_price` or `totalPrices` but not in `priceless`. [`split`]
+//! cuts `snake_case`, `kebab-case` and `camelCase` names into lowercase words,
+//! and [`matches`] compares them with the configured patterns.
+
+/// Returns the lowercase words of `name`, split on `_`, `-` and lower-to-upper
+/// case boundaries. Empty words, e.g. before a leading `_`, are left out.
+///
+/// # Arguments
+/// * `name` (`&str`) - The identifier to split.
+pub fn split(name: &str) -> Vec<String> {
+    let mut words: Vec<String> = Vec::new();
+    let mut current: String = String::new();
+    let mut previous_lowercase: bool = false;
+
+    for character in name.chars() {
+        let separator: bool = matches!(character, '_' | '-');
+        if separator || (character.is_uppercase() && previous_lowercase) {
+            words.push(std::mem::take(&mut current));
+        }
+        if !separator {
+            current.extend(character.to_lowercase());
+        }
+        previous_lowercase = character.is_lowercase();
+    }
+    words.push(current);
+
+    words.retain(|word: &String| !word.is_empty());
+    words
+}
+
+/// Returns `true` if one of the words of `name` (see [`split`]) is one of
+/// `patterns`, optionally followed by a plural `s`.
+///
+/// # Arguments
+/// * `patterns` (`&[String]`) - The lowercase words to look for.
+/// * `name` (`&str`) - The identifier to check.
+pub fn matches(patterns: &[String], name: &str) -> bool {
+    split(name).iter().any(|word: &String| {
+        patterns.iter().any(|pattern: &String| {
+            word == pattern || word.strip_suffix('s') == Some(pattern)
+        })
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{matches, split};
+
+    #[test]
+    fn words() {
+        assert_eq!(split("unit_price"), ["unit", "price"]);
+        assert_eq!(split("totalPrices"), ["total", "prices"]);
+        assert_eq!(split("x-api-key"), ["x", "api", "key"]);
+        assert_eq!(split("_HTTPRequest"), ["httprequest"]);
+        assert!(split("").is_empty());
+    }
+
+    #[test]
+    fn patterns() {
+        let patterns: [String; 1] = [String::from("price")];
+        assert!(matches(&patterns, "unitPrice"));
+        assert!(matches(&patterns, "prices"));
+        assert!(!matches(&patterns, "priceless"));
+        assert!(!matches(&patterns, "__"));
+    }
+}